@@ -0,0 +1,87 @@
+// Contention benchmark: one RwLock around the whole MempoolManager versus SharedMempool's
+// per-pool locks, with concurrent submitters, task updates and processing entries
+
+use std::sync::Arc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pcl_backend::*;
+use tokio::sync::RwLock;
+
+const WORKERS: usize = 8;
+const OPS_PER_WORKER: usize = 200;
+
+fn tx_data() -> TransactionData {
+    TransactionData::new(
+        vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+        vec![("utxo_bench".to_string(), 2.0)],
+        NodeKeypair::new().address().parse().unwrap(),
+        0.2,
+        0.1,
+    )
+}
+
+// Each worker hits a different pool, the way submissions, validators and leaders do
+async fn run_single_lock(data: TransactionData) {
+    let mempool = Arc::new(RwLock::new(MempoolManager::new()));
+    let mut handles = Vec::new();
+    for worker in 0..WORKERS {
+        let mempool = mempool.clone();
+        let data = data.clone();
+        handles.push(tokio::spawn(async move {
+            for i in 0..OPS_PER_WORKER {
+                let id = format!("tx_{}_{}", worker, i);
+                let mut guard = mempool.write().await;
+                match worker % 3 {
+                    0 => guard.add_raw_transaction(RawTransaction::new(id, data.clone())).unwrap(),
+                    1 => guard.add_validation_task(ValidationTask::new(id, "leader_1".to_string(), ValidationTaskType::SignatureValidation)).unwrap(),
+                    _ => guard.add_processing_transaction(ProcessingTransaction::new(id, data.clone(), "sig".to_string(), "leader_1".to_string())).unwrap(),
+                }
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+async fn run_sharded(data: TransactionData) {
+    let mempool = Arc::new(SharedMempool::new());
+    let mut handles = Vec::new();
+    for worker in 0..WORKERS {
+        let mempool = mempool.clone();
+        let data = data.clone();
+        handles.push(tokio::spawn(async move {
+            for i in 0..OPS_PER_WORKER {
+                let id = format!("tx_{}_{}", worker, i);
+                match worker % 3 {
+                    0 => mempool.add_raw_transaction(RawTransaction::new(id, data.clone())).await.unwrap(),
+                    1 => mempool.add_validation_task(ValidationTask::new(id, "leader_1".to_string(), ValidationTaskType::SignatureValidation)).await.unwrap(),
+                    _ => mempool.add_processing_transaction(ProcessingTransaction::new(id, data.clone(), "sig".to_string(), "leader_1".to_string())).await.unwrap(),
+                }
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+fn mempool_contention(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(WORKERS)
+        .build()
+        .unwrap();
+    let data = tx_data();
+
+    let mut group = c.benchmark_group("mempool_contention");
+    group.throughput(Throughput::Elements((WORKERS * OPS_PER_WORKER) as u64));
+    group.bench_with_input(BenchmarkId::new("single_lock", WORKERS), &data, |b, data| {
+        b.iter(|| runtime.block_on(run_single_lock(data.clone())))
+    });
+    group.bench_with_input(BenchmarkId::new("sharded", WORKERS), &data, |b, data| {
+        b.iter(|| runtime.block_on(run_sharded(data.clone())))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mempool_contention);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+// Dispatch tick benchmark: finding the due webhook deliveries among 50k stored ones, a handful still
+// pending, by decoding every delivery record versus reading the due index from a snapshot
+
+use std::sync::Arc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pcl_backend::*;
+
+const DELIVERIES: u32 = 50_000;
+const PENDING: u32 = 20;
+const NOW: u64 = 1_000;
+
+fn delivery(n: u32) -> WebhookDelivery {
+    WebhookDelivery {
+        id: format!("sub_{}:tx_{}", n % 50, n),
+        subscription_id: format!("sub_{}", n % 50),
+        tx_id: format!("tx_{}", n),
+        payload: format!(r#"{{"event":"address.received","tx_id":"tx_{}","amount":5.0}}"#, n),
+        status: if n.is_multiple_of(DELIVERIES / PENDING) { DeliveryStatus::Pending } else { DeliveryStatus::Delivered },
+        attempts: 1,
+        next_attempt_at: NOW,
+        last_attempt_at: Some(NOW),
+        last_error: None,
+        response_status: Some(200),
+    }
+}
+
+fn dispatch_tick(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+    for n in 0..DELIVERIES {
+        storage.store_webhook_delivery(&delivery(n)).unwrap();
+    }
+    let webhooks = WebhookDispatcher::new(storage.clone());
+
+    let mut group = c.benchmark_group("webhook_dispatch_tick");
+    group.bench_with_input(BenchmarkId::new("full_scan", DELIVERIES), &NOW, |b, now| {
+        b.iter(|| {
+            let due: Vec<WebhookDelivery> = storage.load_webhook_deliveries().unwrap()
+                .into_iter()
+                .filter(|delivery| delivery.is_due(*now))
+                .collect();
+            assert_eq!(due.len(), PENDING as usize);
+        })
+    });
+    group.bench_with_input(BenchmarkId::new("due_index", DELIVERIES), &NOW, |b, now| {
+        b.iter(|| assert_eq!(webhooks.due_deliveries(*now).unwrap().len(), PENDING as usize))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, dispatch_tick);
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+// Embeds the git commit and build time as VERGEN_* variables for src/version.rs. Outside a git checkout
+// (e.g. a source tarball) vergen warns and fills in placeholders instead of failing the build.
+fn main() {
+    if let Err(e) = vergen::EmitBuilder::builder().git_sha(true).build_timestamp().emit() {
+        println!("cargo:warning=Could not embed build info: {}", e);
+    }
+}
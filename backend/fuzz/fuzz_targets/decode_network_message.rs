@@ -0,0 +1,17 @@
+#![no_main]
+
+// Arbitrary bytes into the gossip decoders: they must return an error, never panic, and
+// anything they accept must survive a re-encode
+use libfuzzer_sys::fuzz_target;
+use pcl_backend::{decode_bincode, NetworkMessage, MAX_MESSAGE_SIZE};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = NetworkMessage::decode(data) {
+        // Re-encoding may grow past the size limit (e.g. 1e2 becomes 100.0), which is a clean error
+        if let Ok(bytes) = message.encode() {
+            NetworkMessage::decode(&bytes).expect("re-encoded message must decode");
+        }
+    }
+
+    let _ = decode_bincode::<NetworkMessage>(data, MAX_MESSAGE_SIZE);
+});
@@ -0,0 +1,50 @@
+#![no_main]
+
+// Decoded gossip fed through ConsensusManager::handle_network_message against a node with a
+// throwaway RocksDB directory and one registered leader and validator. Their ids are fixed
+// (...0001 and ...0002) so mutated messages can reach past the registry lookups.
+use std::sync::OnceLock;
+use libfuzzer_sys::fuzz_target;
+use pcl_backend::{ConsensusManager, NetworkManager, NetworkMessage, Node, NodeKeypair, NodeRole, StorageManager};
+
+struct Target {
+    runtime: tokio::runtime::Runtime,
+    consensus: ConsensusManager,
+    _dir: tempfile::TempDir,
+}
+
+fn target() -> &'static Target {
+    static TARGET: OnceLock<Target> = OnceLock::new();
+    TARGET.get_or_init(|| {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let consensus = runtime.block_on(async {
+            let local_node = Node::new("10.0.0.1".parse().unwrap(), &NodeKeypair::new()).unwrap();
+            let network = NetworkManager::new(local_node.clone()).await.unwrap();
+            let storage = StorageManager::new(dir.path()).unwrap();
+            let consensus = ConsensusManager::new(local_node, network, storage).unwrap();
+
+            let mut registry = consensus.node_registry.write().await;
+            let mut leader = Node::new("10.0.0.2".parse().unwrap(), &NodeKeypair::new()).unwrap();
+            leader.id = uuid::Uuid::from_u128(1);
+            leader.role = NodeRole::Leader;
+            registry.register_node(leader).unwrap();
+            let mut validator = Node::new("10.0.0.3".parse().unwrap(), &NodeKeypair::new()).unwrap();
+            validator.id = uuid::Uuid::from_u128(2);
+            validator.role = NodeRole::Validator;
+            registry.register_node(validator).unwrap();
+            drop(registry);
+            consensus
+        });
+        Target { runtime, consensus, _dir: dir }
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(message) = NetworkMessage::decode(data) else {
+        return;
+    };
+    let target = target();
+    // Rejections are expected; only panics count as findings
+    let _ = target.runtime.block_on(target.consensus.handle_network_message(&message));
+});
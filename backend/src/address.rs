@@ -0,0 +1,99 @@
+// Address module - validated account addresses
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::crypto::{address_from_public_key, decode_address, MULTISIG_ADDRESS_VERSION, ADDRESS_VERSION};
+use crate::error::{PclError, Result};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// 25 decoded bytes encode to 33-35 base58 characters depending on leading zeros
+const MIN_ADDRESS_LEN: usize = 25;
+const MAX_ADDRESS_LEN: usize = 35;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Address(String);
+
+impl Address {
+    // Checks length, base58 charset, version byte and checksum, in that order, so the error says
+    // what is actually wrong with the input
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+
+        if input.len() < MIN_ADDRESS_LEN || input.len() > MAX_ADDRESS_LEN {
+            return Err(PclError::InvalidAddress(format!(
+                "'{}' has length {}, expected {}-{} characters", input, input.len(), MIN_ADDRESS_LEN, MAX_ADDRESS_LEN
+            )));
+        }
+        if let Some(bad) = input.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+            return Err(PclError::InvalidAddress(format!("'{}' contains invalid character '{}'", input, bad)));
+        }
+
+        let (version, _) = decode_address(input)
+            .map_err(|e| PclError::InvalidAddress(format!("'{}': {}", input, e)))?;
+        if version != ADDRESS_VERSION && version != MULTISIG_ADDRESS_VERSION {
+            return Err(PclError::InvalidAddress(format!("'{}' has unknown version {}", input, version)));
+        }
+
+        Ok(Self(input.to_string()))
+    }
+
+    pub fn from_public_key(public_key: &VerifyingKey) -> Self {
+        Self(address_from_public_key(public_key))
+    }
+
+    pub fn is_multisig(&self) -> bool {
+        matches!(decode_address(&self.0), Ok((MULTISIG_ADDRESS_VERSION, _)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // Only for strings produced by the address encoders themselves
+    pub(crate) fn from_encoded(encoded: String) -> Self {
+        Self(encoded)
+    }
+}
+
+impl FromStr for Address {
+    type Err = PclError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Address {
+    type Error = PclError;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
@@ -0,0 +1,116 @@
+// Admission module - leader-side policy over which raw transactions a leader takes on
+//
+// Every valid, signed transaction used to be accepted by whichever leader received it. Operators of
+// leader nodes may have their own rules: addresses they will not serve, a cap on amounts, hours they
+// are staffed. A leader asks its AdmissionPolicy about each raw transaction it receives, submitted
+// directly or gossiped, and refuses the ones it rejects with the policy's reason, which is returned to
+// the submitter. The default admits everything; RuleBasedPolicy applies the admission section of the
+// node config. Validity is still checked by the workflow; a policy only narrows what a leader accepts.
+
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use crate::config::AdmissionConfig;
+use crate::error::{PclError, Result};
+use crate::transaction::TransactionData;
+
+// What a policy sees of a transaction
+#[derive(Debug, Clone)]
+pub struct AdmissionRequest {
+    pub user: String,
+    pub recipients: Vec<String>,
+    pub amount: f64, // total sent to the recipients
+    pub fee: f64,
+    pub received_at: DateTime<Utc>,
+}
+
+impl AdmissionRequest {
+    pub fn from_transaction(tx_data: &TransactionData, received_at: DateTime<Utc>) -> Self {
+        Self {
+            user: tx_data.user.to_string(),
+            recipients: tx_data.to.iter().map(|(address, _)| address.to_string()).collect(),
+            amount: tx_data.to.iter().map(|(_, amount)| amount).sum(),
+            fee: tx_data.fee,
+            received_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdmissionDecision {
+    Admit,
+    Reject(String), // reason, shown to the submitter
+}
+
+pub trait AdmissionPolicy: Send + Sync {
+    fn name(&self) -> &str;
+    fn evaluate(&self, request: &AdmissionRequest) -> AdmissionDecision;
+}
+
+// Turns a rejection into the error returned to the submitter
+pub fn check_admission(policy: &dyn AdmissionPolicy, request: &AdmissionRequest) -> Result<()> {
+    match policy.evaluate(request) {
+        AdmissionDecision::Admit => Ok(()),
+        AdmissionDecision::Reject(reason) => Err(PclError::AdmissionRejected(format!("{} ({} policy)", reason, policy.name()))),
+    }
+}
+
+pub struct AllowAll;
+
+impl AdmissionPolicy for AllowAll {
+    fn name(&self) -> &str {
+        "allow-all"
+    }
+
+    fn evaluate(&self, _request: &AdmissionRequest) -> AdmissionDecision {
+        AdmissionDecision::Admit
+    }
+}
+
+// The rules of an AdmissionConfig, checked in order: blocklist, amount cap, business hours
+pub struct RuleBasedPolicy {
+    config: AdmissionConfig,
+}
+
+impl RuleBasedPolicy {
+    pub fn new(config: AdmissionConfig) -> Self {
+        Self { config }
+    }
+
+    fn within_business_hours(&self, at: DateTime<Utc>) -> bool {
+        let Some(hours) = &self.config.business_hours else {
+            return true;
+        };
+        if hours.weekdays_only && matches!(at.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        let hour = at.hour();
+        if hours.start_hour < hours.end_hour {
+            hour >= hours.start_hour && hour < hours.end_hour
+        } else {
+            hour >= hours.start_hour || hour < hours.end_hour
+        }
+    }
+}
+
+impl AdmissionPolicy for RuleBasedPolicy {
+    fn name(&self) -> &str {
+        "rules"
+    }
+
+    fn evaluate(&self, request: &AdmissionRequest) -> AdmissionDecision {
+        let blocked = std::iter::once(&request.user).chain(&request.recipients)
+            .find(|address| self.config.blocked_addresses.contains(address));
+        if let Some(address) = blocked {
+            return AdmissionDecision::Reject(format!("address {} is blocked by this leader", address));
+        }
+        if let Some(max) = self.config.max_amount.filter(|max| request.amount > *max) {
+            return AdmissionDecision::Reject(format!("amount {} is over this leader's limit of {} XMBL", request.amount, max));
+        }
+        if let Some(hours) = self.config.business_hours.as_ref().filter(|_| !self.within_business_hours(request.received_at)) {
+            return AdmissionDecision::Reject(format!(
+                "this leader only accepts transactions from {:02}:00 to {:02}:00 UTC{}",
+                hours.start_hour, hours.end_hour, if hours.weekdays_only { " on weekdays" } else { "" }
+            ));
+        }
+        AdmissionDecision::Admit
+    }
+}
@@ -0,0 +1,190 @@
+// Auth module - API keys with role scopes and per-key rate limits for the node HTTP API
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::crypto::hash_data;
+use crate::error::{PclError, Result};
+use crate::storage::StorageManager;
+
+pub const API_KEY_PREFIX: &str = "pcl_";
+pub const API_KEY_HEADER: &str = "X-API-Key";
+const RATE_WINDOW_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    Read,
+    Submit,
+    Faucet,
+    Admin, // implies every other scope
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Submit => "submit",
+            Scope::Faucet => "faucet",
+            Scope::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = PclError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "read" => Ok(Scope::Read),
+            "submit" => Ok(Scope::Submit),
+            "faucet" => Ok(Scope::Faucet),
+            "admin" => Ok(Scope::Admin),
+            other => Err(PclError::Validation(format!(
+                "Unknown scope '{}', expected read, submit, faucet or admin", other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Stored form of a key: only the SHA-256 of the secret is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub key_hash: String,
+    pub scopes: Vec<Scope>,
+    pub rate_limit_per_minute: u32,
+    pub created_at: u64,
+    pub revoked: bool,
+}
+
+impl ApiKey {
+    pub fn allows(&self, scope: Scope) -> bool {
+        self.scopes.contains(&Scope::Admin) || self.scopes.contains(&scope)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyUsage {
+    pub requests: u64,
+    pub rejected: u64, // scope or rate limit refusals
+    pub last_used_at: Option<u64>,
+}
+
+pub fn hash_api_key(secret: &str) -> String {
+    hex::encode(hash_data(secret.as_bytes()))
+}
+
+pub struct ApiKeyManager {
+    storage: Arc<StorageManager>,
+    windows: Mutex<HashMap<String, (u64, u32)>>, // key id -> (window start, requests in window)
+    usage: Mutex<HashMap<String, ApiKeyUsage>>,
+}
+
+impl ApiKeyManager {
+    pub fn new(storage: Arc<StorageManager>) -> Self {
+        Self {
+            storage,
+            windows: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Returns the stored key and the secret, which is not recoverable afterwards
+    pub fn create_key(&self, name: &str, scopes: Vec<Scope>, rate_limit_per_minute: u32, now: u64) -> Result<(ApiKey, String)> {
+        if scopes.is_empty() {
+            return Err(PclError::Validation("An API key needs at least one scope".to_string()));
+        }
+        if rate_limit_per_minute == 0 {
+            return Err(PclError::Validation("rate_limit_per_minute must be positive".to_string()));
+        }
+
+        let mut secret_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret_bytes);
+        let secret = format!("{}{}", API_KEY_PREFIX, hex::encode(secret_bytes));
+
+        let key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            key_hash: hash_api_key(&secret),
+            scopes,
+            rate_limit_per_minute,
+            created_at: now,
+            revoked: false,
+        };
+        self.storage.store_api_key(&key)?;
+        Ok((key, secret))
+    }
+
+    pub fn list_keys(&self) -> Result<Vec<ApiKey>> {
+        self.storage.load_api_keys()
+    }
+
+    pub fn revoke(&self, key_id: &str) -> Result<bool> {
+        match self.list_keys()?.into_iter().find(|key| key.id == key_id && !key.revoked) {
+            Some(mut key) => {
+                key.revoked = true;
+                self.storage.store_api_key(&key)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub fn usage(&self, key_id: &str) -> ApiKeyUsage {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+            .get(key_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Resolves the presented secret and checks scope and rate limit, counting the request either way
+    pub fn authorize(&self, secret: Option<&str>, scope: Scope, now: u64) -> Result<ApiKey> {
+        let secret = secret.ok_or_else(|| PclError::Unauthorized("API key required".to_string()))?;
+        let key = self.storage.load_api_key(&hash_api_key(secret))?
+            .filter(|key| !key.revoked)
+            .ok_or_else(|| PclError::Unauthorized("Unknown or revoked API key".to_string()))?;
+
+        let result = if !key.allows(scope) {
+            Err(PclError::Forbidden(format!("API key '{}' lacks the {} scope", key.name, scope)))
+        } else {
+            self.check_rate_limit(&key, now)
+        };
+
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = usage.entry(key.id.clone()).or_default();
+        entry.requests += 1;
+        entry.last_used_at = Some(now);
+        if result.is_err() {
+            entry.rejected += 1;
+        }
+        result.map(|_| key)
+    }
+
+    // Fixed one-minute windows per key
+    fn check_rate_limit(&self, key: &ApiKey, now: u64) -> Result<()> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let window = windows.entry(key.id.clone()).or_insert((now, 0));
+        if now.saturating_sub(window.0) >= RATE_WINDOW_MS {
+            *window = (now, 0);
+        }
+        if window.1 >= key.rate_limit_per_minute {
+            return Err(PclError::RateLimited(format!(
+                "API key '{}' is limited to {} requests per minute", key.name, key.rate_limit_per_minute
+            )));
+        }
+        window.1 += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+// Bandwidth module - bytes sent and received per peer and per gossip topic, with per-peer caps
+//
+// Network stats only counted messages, so a few peers using most of a node's bandwidth went unseen.
+// The network manager now meters the encoded size of every message it sends to or receives from a
+// peer, split by gossip topic, and GET /peers and /metrics report the totals. A cap in bytes per
+// second, node-wide or set for one peer, bounds each direction: messages past it within the current
+// second are not sent to that peer, or are dropped on receipt, and counted as throttled.
+
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::config::NetworkConfig;
+use crate::mesh::MESH_TOPICS;
+use crate::network::{NetworkMessage, PeerId};
+
+// Caps are counted over windows of this length
+const RATE_WINDOW_MS: u64 = 1000;
+
+// The gossip topic a message travels on
+pub fn topic_of(message: &NetworkMessage) -> &'static str {
+    match message {
+        NetworkMessage::TransactionGossip(_)
+        | NetworkMessage::TransactionAnnouncement(_)
+        | NetworkMessage::TransactionRequest(_) => "transactions",
+        NetworkMessage::ValidationTask(_)
+        | NetworkMessage::ProcessingTransactionGossip(_)
+        | NetworkMessage::VerifiedProcessingTxBroadcast(_)
+        | NetworkMessage::AmountReveal(_)
+        | NetworkMessage::TaskOffer(_)
+        | NetworkMessage::TaskOfferAck(_) => "processing",
+        NetworkMessage::Pulse(_)
+        | NetworkMessage::PulseResponse(_)
+        | NetworkMessage::PulseReceipt(_)
+        | NetworkMessage::UptimeData(_) => "pulses",
+        NetworkMessage::MeshHeartbeat(heartbeat) => MESH_TOPICS.iter()
+            .find(|topic| **topic == heartbeat.topic)
+            .copied()
+            .unwrap_or("consensus"),
+        NetworkMessage::LeaderElection(_)
+        | NetworkMessage::TransactionInvalidation(_)
+        | NetworkMessage::EquivocationEvidence(_)
+        | NetworkMessage::GovernanceProposal(_)
+        | NetworkMessage::ScreeningList(_)
+        | NetworkMessage::PeerBinding(_) => "consensus",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficDirection {
+    Sent,
+    Received,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TopicBytes {
+    pub sent: u64,
+    pub received: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerBandwidth {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub throttled_sent: u64,     // messages not sent because the peer was over its cap
+    pub throttled_received: u64, // messages from the peer dropped for the same reason
+    pub topics: BTreeMap<String, TopicBytes>,
+    pub cap_bytes_per_sec: Option<u64>, // set only when the peer has its own cap
+    #[serde(skip)]
+    window: [(u64, u64); 2], // (window start, bytes in it) for sent and received
+}
+
+impl PeerBandwidth {
+    fn window_mut(&mut self, direction: TrafficDirection) -> &mut (u64, u64) {
+        match direction {
+            TrafficDirection::Sent => &mut self.window[0],
+            TrafficDirection::Received => &mut self.window[1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthMeter {
+    peers: HashMap<PeerId, PeerBandwidth>,
+    default_cap: u64, // bytes per second per direction, 0 for none
+}
+
+impl BandwidthMeter {
+    pub fn new(config: &NetworkConfig) -> Self {
+        let mut meter = Self::default();
+        meter.set_limits(config);
+        meter
+    }
+
+    pub fn set_limits(&mut self, config: &NetworkConfig) {
+        self.default_cap = config.peer_bandwidth_cap_bytes_per_sec;
+        for (peer_id, cap) in &config.peer_bandwidth_caps {
+            self.set_peer_cap(peer_id, Some(*cap));
+        }
+    }
+
+    // None goes back to the node-wide cap
+    pub fn set_peer_cap(&mut self, peer_id: &str, cap_bytes_per_sec: Option<u64>) {
+        self.peers.entry(peer_id.to_string()).or_default().cap_bytes_per_sec = cap_bytes_per_sec;
+    }
+
+    // Counts a message to or from a peer; false when it would take the peer past its cap this second
+    pub fn record(&mut self, peer_id: &str, topic: &str, bytes: u64, direction: TrafficDirection, now: u64) -> bool {
+        let default_cap = self.default_cap;
+        let peer = self.peers.entry(peer_id.to_string()).or_default();
+        let cap = peer.cap_bytes_per_sec.unwrap_or(default_cap);
+        let window = peer.window_mut(direction);
+        if now.saturating_sub(window.0) >= RATE_WINDOW_MS {
+            *window = (now, 0);
+        }
+        // A message on its own larger than the cap still goes through, once per window
+        if cap > 0 && window.1 > 0 && window.1 + bytes > cap {
+            match direction {
+                TrafficDirection::Sent => peer.throttled_sent += 1,
+                TrafficDirection::Received => peer.throttled_received += 1,
+            }
+            return false;
+        }
+        window.1 += bytes;
+        let topic_bytes = peer.topics.entry(topic.to_string()).or_default();
+        match direction {
+            TrafficDirection::Sent => {
+                peer.bytes_sent += bytes;
+                topic_bytes.sent += bytes;
+            }
+            TrafficDirection::Received => {
+                peer.bytes_received += bytes;
+                topic_bytes.received += bytes;
+            }
+        }
+        true
+    }
+
+    pub fn peer(&self, peer_id: &str) -> Option<&PeerBandwidth> {
+        self.peers.get(peer_id)
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = (&PeerId, &PeerBandwidth)> {
+        self.peers.iter()
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let mut peers: Vec<_> = self.peers.iter().collect();
+        peers.sort_by(|a, b| a.0.cmp(b.0));
+        let mut out = String::new();
+        out.push_str("# HELP pcl_peer_bytes_total Encoded message bytes exchanged with each peer, by gossip topic\n");
+        out.push_str("# TYPE pcl_peer_bytes_total counter\n");
+        for (peer_id, peer) in &peers {
+            for (topic, bytes) in &peer.topics {
+                out.push_str(&format!("pcl_peer_bytes_total{{peer=\"{}\",topic=\"{}\",direction=\"sent\"}} {}\n", peer_id, topic, bytes.sent));
+                out.push_str(&format!("pcl_peer_bytes_total{{peer=\"{}\",topic=\"{}\",direction=\"received\"}} {}\n", peer_id, topic, bytes.received));
+            }
+        }
+        out.push_str("# HELP pcl_peer_throttled_messages_total Messages held back by a peer's bandwidth cap\n");
+        out.push_str("# TYPE pcl_peer_throttled_messages_total counter\n");
+        for (peer_id, peer) in &peers {
+            out.push_str(&format!("pcl_peer_throttled_messages_total{{peer=\"{}\",direction=\"sent\"}} {}\n", peer_id, peer.throttled_sent));
+            out.push_str(&format!("pcl_peer_throttled_messages_total{{peer=\"{}\",direction=\"received\"}} {}\n", peer_id, peer.throttled_received));
+        }
+        out
+    }
+}
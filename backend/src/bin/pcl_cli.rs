@@ -0,0 +1,215 @@
+// PCL CLI - operator tooling for inspecting a running node
+use clap::{Args, Parser, Subcommand};
+use pcl_backend::*;
+use std::collections::HashMap;
+
+#[derive(Parser)]
+#[command(name = "pcl-cli")]
+#[command(about = "Peer Consensus Layer operator CLI")]
+struct Cli {
+    /// Node HTTP API to talk to
+    #[arg(long, global = true, default_value = client::DEFAULT_NODE_URL)]
+    node: String,
+
+    /// API key for nodes with API key auth enabled (needs the read scope)
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Inspect the node's mempools
+    #[command(subcommand)]
+    Mempool(MempoolCommands),
+}
+
+#[derive(Subcommand)]
+enum MempoolCommands {
+    /// List entries in one or all mempool stages
+    List(MempoolFilter),
+    /// Show every mempool entry that belongs to a transaction
+    Show {
+        /// Transaction id (raw_tx_id)
+        tx_id: String,
+    },
+    /// Poll the mempools and print entries as they appear, change status or leave
+    Watch {
+        #[command(flatten)]
+        filter: MempoolFilter,
+
+        /// Poll interval in seconds
+        #[arg(short, long, default_value_t = 2)]
+        interval: u64,
+    },
+}
+
+#[derive(Args)]
+struct MempoolFilter {
+    /// Stage to inspect: raw, tasks, processing or final (default: all)
+    #[arg(short, long)]
+    stage: Option<MempoolStage>,
+
+    /// Only entries held by this leader
+    #[arg(short, long)]
+    leader: Option<String>,
+
+    /// Only entries for this user (sender, or assigned validator for tasks)
+    #[arg(short, long)]
+    user: Option<String>,
+}
+
+impl MempoolFilter {
+    fn stages(&self) -> Vec<MempoolStage> {
+        self.stage.map(|stage| vec![stage]).unwrap_or_else(|| MempoolStage::ALL.to_vec())
+    }
+
+    fn matches(&self, entry: &MempoolEntry) -> bool {
+        let leader_ok = self.leader.as_ref().map_or(true, |leader| entry.leader.as_ref() == Some(leader));
+        let user_ok = self.user.as_ref().map_or(true, |user| entry.user.as_ref() == Some(user));
+        leader_ok && user_ok
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut client = PclClient::new(&cli.node)?;
+    if let Some(api_key) = &cli.api_key {
+        client = client.with_api_key(api_key);
+    }
+
+    match cli.command {
+        Commands::Mempool(MempoolCommands::List(filter)) => {
+            let entries = fetch_entries(&client, &filter.stages()).await?;
+            let entries: Vec<_> = entries.into_iter().filter(|e| filter.matches(e)).collect();
+            print_entries(&entries);
+            println!("{} entries", entries.len());
+        }
+        Commands::Mempool(MempoolCommands::Show { tx_id }) => {
+            let entries: Vec<_> = fetch_entries(&client, &MempoolStage::ALL).await?
+                .into_iter()
+                .filter(|e| e.tx_id == tx_id)
+                .collect();
+            if entries.is_empty() {
+                return Err(PclError::Mempool(format!("Transaction {} is not in any mempool", tx_id)));
+            }
+            for entry in entries {
+                println!("[{}] {} ({})", entry.stage, entry.id, entry.status);
+                println!("{}\n", serde_json::to_string_pretty(&entry.details)?);
+            }
+        }
+        Commands::Mempool(MempoolCommands::Watch { filter, interval }) => {
+            watch(&client, &filter, interval).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_entries(client: &PclClient, stages: &[MempoolStage]) -> Result<Vec<MempoolEntry>> {
+    let mut entries = Vec::new();
+    for stage in stages {
+        entries.extend(client.mempool_stage_entries(*stage).await?);
+    }
+    Ok(entries)
+}
+
+async fn watch(client: &PclClient, filter: &MempoolFilter, interval: u64) -> Result<()> {
+    // Raw entries are gossiped, so the same tx id can sit in several leaders' pools
+    let mut known: HashMap<(MempoolStage, String, Option<String>), String> = HashMap::new();
+    let mut ticker = tokio::time::interval(tokio::time::Duration::from_secs(interval.max(1)));
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+
+        let entries = match fetch_entries(client, &filter.stages()).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("⚠️  {}", e);
+                continue;
+            }
+        };
+
+        let mut current = HashMap::new();
+        for entry in entries.iter().filter(|e| filter.matches(e)) {
+            let key = (entry.stage, entry.id.clone(), entry.leader.clone());
+            match known.get(&key) {
+                None => println!("+ {}", entry_line(entry)),
+                Some(status) if *status != entry.status => println!("~ {}", entry_line(entry)),
+                Some(_) => {}
+            }
+            current.insert(key, entry.status.clone());
+        }
+        for (stage, id, leader) in known.keys().filter(|key| !current.contains_key(*key)) {
+            println!("- [{}] {} leader={}", stage, id, leader.as_deref().unwrap_or("-"));
+        }
+        known = current;
+    }
+}
+
+fn entry_line(entry: &MempoolEntry) -> String {
+    format!(
+        "[{}] {} leader={} user={} status={}",
+        entry.stage,
+        entry.id,
+        entry.leader.as_deref().unwrap_or("-"),
+        entry.user.as_deref().unwrap_or("-"),
+        entry.status
+    )
+}
+
+fn print_entries(entries: &[MempoolEntry]) {
+    let rows: Vec<Vec<String>> = entries.iter()
+        .map(|e| vec![
+            e.stage.to_string(),
+            shorten(&e.id),
+            shorten(&e.tx_id),
+            e.leader.clone().unwrap_or_else(|| "-".to_string()),
+            shorten(e.user.as_deref().unwrap_or("-")),
+            e.status.clone(),
+            e.timestamp.to_string(),
+        ])
+        .collect();
+    print_table(&["STAGE", "ID", "TX", "LEADER", "USER", "STATUS", "TIMESTAMP"], &rows);
+}
+
+// Long hashes and addresses are cut down so the table fits a terminal; `show` prints them in full
+fn shorten(value: &str) -> String {
+    const MAX: usize = 18;
+    if value.chars().count() <= MAX {
+        value.to_string()
+    } else {
+        format!("{}…", value.chars().take(MAX - 1).collect::<String>())
+    }
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(headers.to_vec()));
+    println!("{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  "));
+    for row in rows {
+        println!("{}", format_row(row.iter().map(|c| c.as_str()).collect()));
+    }
+}
@@ -0,0 +1,377 @@
+// PCL Wallet CLI - key management, offline and Ledger signing, multisig and fee sponsorship helpers
+use clap::{Args, Parser, Subcommand};
+use pcl_backend::*;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "pcl-wallet")]
+#[command(about = "Peer Consensus Layer wallet utilities")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate a new ed25519 keypair
+    Keygen,
+    /// Generate a random HD wallet seed
+    Seed,
+    /// Generate a 24-word mnemonic backup phrase
+    Mnemonic,
+    /// Recover the node identity and wallet addresses from a mnemonic phrase
+    Restore {
+        /// The 24-word phrase, quoted
+        #[arg(short, long)]
+        mnemonic: String,
+
+        /// Optional BIP39 passphrase the phrase was created with
+        #[arg(long, default_value = "")]
+        passphrase: String,
+
+        /// Account index
+        #[arg(short, long, default_value_t = 0)]
+        account: u32,
+
+        /// Number of addresses to recover
+        #[arg(short, long, default_value_t = 5)]
+        count: u32,
+    },
+    /// Derive addresses from an HD wallet seed
+    Derive {
+        /// Hex encoded seed (16-64 bytes)
+        #[arg(short, long)]
+        seed: String,
+
+        /// Account index
+        #[arg(short, long, default_value_t = 0)]
+        account: u32,
+
+        /// Number of addresses to derive
+        #[arg(short, long, default_value_t = 5)]
+        count: u32,
+    },
+    /// Derive a multisig address from a threshold and a set of public keys
+    MultisigAddress {
+        /// Number of signatures required to spend
+        #[arg(short, long)]
+        threshold: u8,
+
+        /// Hex encoded cosigner public key (repeat for each cosigner)
+        #[arg(short, long = "pubkey", required = true)]
+        pubkeys: Vec<String>,
+    },
+    /// Sign a prepared transaction offline; the output is ready for POST /transaction/broadcast
+    Sign {
+        #[command(flatten)]
+        key: KeySource,
+
+        /// Path to the POST /transaction/prepare response, or just its unsigned_tx
+        #[arg(short, long)]
+        file: PathBuf,
+    },
+    /// Prepare a transfer on a node, sign it here and broadcast it
+    Send {
+        #[command(flatten)]
+        key: KeySource,
+
+        /// Node HTTP API to talk to
+        #[arg(long, default_value = client::DEFAULT_NODE_URL)]
+        node: String,
+
+        /// Recipient address
+        #[arg(long)]
+        to: String,
+
+        /// UTXO to spend
+        #[arg(long)]
+        from: String,
+
+        #[arg(long)]
+        amount: f64,
+
+        /// Defaults to the node's medium fee estimate
+        #[arg(long)]
+        fee: Option<f64>,
+    },
+    /// Produce a partial signature over an unsigned multisig transaction
+    SignPartial {
+        /// Hex encoded secret key of the cosigner
+        #[arg(short, long)]
+        secret: String,
+
+        /// Path to the transaction JSON (the body that will be POSTed to /transaction)
+        #[arg(short, long)]
+        tx: PathBuf,
+    },
+    /// Combine partial signatures into a submittable multisig transaction
+    Combine {
+        /// Path to the transaction JSON
+        #[arg(short, long)]
+        tx: PathBuf,
+
+        /// Path to a partial signature JSON file (repeat for each cosigner)
+        #[arg(short, long = "partial", required = true)]
+        partials: Vec<PathBuf>,
+    },
+    /// Sign a transaction as its fee payer so the sponsor covers the fee
+    Sponsor {
+        /// Hex encoded secret key of the sponsor
+        #[arg(short, long)]
+        secret: String,
+
+        /// Path to the transaction JSON with a `fee_payer` entry
+        #[arg(short, long)]
+        tx: PathBuf,
+    },
+}
+
+// Where the sender's key lives: a hex secret, or the device for --ledger
+#[derive(Args)]
+struct KeySource {
+    /// Hex encoded secret key of the sender
+    #[arg(short, long, required_unless_present = "ledger")]
+    secret: Option<String>,
+
+    /// Sign on a Ledger device; the key is derived on-device and never leaves it
+    #[arg(long, conflicts_with = "secret")]
+    ledger: bool,
+
+    /// Ledger account index
+    #[arg(long, default_value_t = 0, requires = "ledger")]
+    account: u32,
+
+    /// Ledger address index within the account
+    #[arg(long, default_value_t = 0, requires = "ledger")]
+    index: u32,
+}
+
+enum SigningKey {
+    Secret(NodeKeypair),
+    #[cfg(feature = "ledger")]
+    Ledger { signer: LedgerSigner, account: u32, index: u32, public_key: ed25519_dalek::VerifyingKey },
+}
+
+impl SigningKey {
+    fn open(source: &KeySource) -> Result<Self> {
+        if !source.ledger {
+            return Ok(SigningKey::Secret(keypair_from_hex(source.secret.as_deref().unwrap_or_default())?));
+        }
+        open_ledger(source)
+    }
+
+    fn address(&self) -> String {
+        match self {
+            SigningKey::Secret(keypair) => keypair.address(),
+            #[cfg(feature = "ledger")]
+            SigningKey::Ledger { public_key, .. } => address_from_public_key(public_key),
+        }
+    }
+
+    fn sign(&self, unsigned: &serde_json::Value) -> Result<serde_json::Value> {
+        match self {
+            SigningKey::Secret(keypair) => sign_submission(unsigned, keypair),
+            #[cfg(feature = "ledger")]
+            SigningKey::Ledger { signer, account, index, public_key } => {
+                if !address_matches_public_key(unsigned["user"].as_str().unwrap_or(""), public_key) {
+                    return Err(PclError::SignatureVerification(
+                        "Ledger address does not belong to the transaction's user".to_string()
+                    ));
+                }
+                eprintln!("Review and approve the transaction on the device");
+                let signature = signer.sign(*account, *index, &submission_hash(unsigned)?)?;
+                let signed = attach_submission_signature(unsigned, public_key, &signature);
+                verify_submission_signature(&signed)?;
+                Ok(signed)
+            }
+        }
+    }
+}
+
+// Shows the address on the device screen so the user can check it against what the wallet prints
+#[cfg(feature = "ledger")]
+fn open_ledger(source: &KeySource) -> Result<SigningKey> {
+    let signer = LedgerSigner::connect()?;
+    eprintln!("Confirm the address for {} on the device", address_derivation_path(source.account, source.index));
+    let public_key = signer.public_key(source.account, source.index, true)?;
+    eprintln!("Using Ledger address {}", address_from_public_key(&public_key));
+    Ok(SigningKey::Ledger { signer, account: source.account, index: source.index, public_key })
+}
+
+#[cfg(not(feature = "ledger"))]
+fn open_ledger(_source: &KeySource) -> Result<SigningKey> {
+    Err(PclError::HardwareWallet("pcl-wallet was built without Ledger support; rebuild with --features ledger".to_string()))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Keygen => {
+            let keypair = NodeKeypair::new();
+            let output = serde_json::json!({
+                "secret_key": hex::encode(keypair.signing_key.to_bytes()),
+                "public_key": hex::encode(keypair.public_key().to_bytes()),
+                "address": keypair.address(),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Commands::Seed => {
+            let mut seed = [0u8; 32];
+            rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut seed);
+            println!("{}", hex::encode(seed));
+        }
+        Commands::Mnemonic => {
+            let mnemonic = generate_mnemonic();
+            let output = serde_json::json!({
+                "mnemonic": mnemonic,
+                "address": derive_address(&mnemonic_to_seed(&mnemonic, "")?, 0, 0)?.0,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Commands::Restore { mnemonic, passphrase, account, count } => {
+            let seed = mnemonic_to_seed(&mnemonic, &passphrase)?;
+            let identity = identity_from_mnemonic(&mnemonic, &passphrase)?;
+
+            let mut addresses = Vec::new();
+            for index in 0..count {
+                let (address, keypair) = derive_address(&seed, account, index)?;
+                addresses.push(serde_json::json!({
+                    "path": address_derivation_path(account, index),
+                    "address": address,
+                    "secret_key": hex::encode(keypair.signing_key.to_bytes()),
+                }));
+            }
+            let output = serde_json::json!({
+                "node_identity": {
+                    "path": node_identity_derivation_path(),
+                    "secret_key": hex::encode(identity.signing_key.to_bytes()),
+                    "public_key": hex::encode(identity.public_key().to_bytes()),
+                    "address": identity.address(),
+                },
+                "seed": hex::encode(seed),
+                "addresses": addresses,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Commands::Derive { seed, account, count } => {
+            let seed = hex::decode(&seed)
+                .map_err(|e| PclError::NodeIdentity(format!("Invalid seed hex: {}", e)))?;
+            let master = ExtendedKeypair::from_seed(&seed)?;
+
+            let mut addresses = Vec::new();
+            for index in 0..count {
+                let path = address_derivation_path(account, index);
+                let child = master.derive_path(&path)?;
+                addresses.push(serde_json::json!({
+                    "path": path,
+                    "address": child.address(),
+                    "public_key": hex::encode(child.keypair.public_key().to_bytes()),
+                }));
+            }
+            println!("{}", serde_json::to_string_pretty(&addresses)?);
+        }
+        Commands::MultisigAddress { threshold, pubkeys } => {
+            let keys = pubkeys
+                .iter()
+                .map(|k| multisig::decode_public_key(k))
+                .collect::<Result<Vec<_>>>()?;
+            let policy = MultisigPolicy::new(threshold, &keys)?;
+            let output = serde_json::json!({
+                "address": policy.address(),
+                "multisig": policy,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Commands::Sign { key, file } => {
+            let signing_key = SigningKey::open(&key)?;
+            let prepared = read_json(&file)?;
+            let unsigned = prepared.get("unsigned_tx").cloned().unwrap_or(prepared);
+
+            let signed = signing_key.sign(&unsigned)?;
+            println!("{}", serde_json::to_string_pretty(&signed)?);
+        }
+        Commands::Send { key, node, to, from, amount, fee } => {
+            let signing_key = SigningKey::open(&key)?;
+            let mut body = serde_json::json!({
+                "to": to,
+                "from": from,
+                "amount": amount,
+                "user": signing_key.address(),
+            });
+            if let Some(fee) = fee {
+                body["fee"] = serde_json::json!(fee);
+            }
+
+            let client = PclClient::new(&node)?;
+            let runtime = tokio::runtime::Runtime::new()?;
+            let prepared = runtime.block_on(client.prepare_transaction(&body))?;
+            let signed = signing_key.sign(&prepared["unsigned_tx"])?;
+            let response = runtime.block_on(client.broadcast_transaction(&signed))?;
+            println!("{}", serde_json::to_string_pretty(&response)?);
+        }
+        Commands::SignPartial { secret, tx } => {
+            let keypair = keypair_from_hex(&secret)?;
+            let body = read_json(&tx)?;
+            let policy = policy_from_body(&body)?;
+
+            let partial = PartialSignature::sign(&keypair, &submission_signing_bytes(&body)?);
+            if !policy.contains_key(&partial.public_key) {
+                return Err(PclError::SignatureVerification("Secret key is not a cosigner of this multisig".to_string()));
+            }
+            println!("{}", serde_json::to_string_pretty(&partial)?);
+        }
+        Commands::Combine { tx, partials } => {
+            let mut body = read_json(&tx)?;
+            let policy = policy_from_body(&body)?;
+
+            let partials = partials
+                .iter()
+                .map(|path| Ok(serde_json::from_value::<PartialSignature>(read_json(path)?)?))
+                .collect::<Result<Vec<_>>>()?;
+            let combined = combine_partial_signatures(&policy, partials)?;
+
+            if !policy.verify(&submission_signing_bytes(&body)?, &combined)? {
+                return Err(PclError::SignatureVerification("Partial signatures do not meet the threshold".to_string()));
+            }
+
+            body["signatures"] = serde_json::to_value(combined)?;
+            println!("{}", serde_json::to_string_pretty(&body)?);
+        }
+        Commands::Sponsor { secret, tx } => {
+            let keypair = keypair_from_hex(&secret)?;
+            let mut body = read_json(&tx)?;
+
+            let fee_payer: FeePayer = serde_json::from_value(body["fee_payer"].clone())
+                .map_err(|e| PclError::Validation(format!("Transaction has no valid fee_payer: {}", e)))?;
+            if fee_payer.public_key != hex::encode(keypair.public_key().to_bytes()) {
+                return Err(PclError::SignatureVerification("Secret key does not match the fee payer".to_string()));
+            }
+
+            let signature = keypair.sign_data(&submission_hash(&body)?);
+            body["fee_payer"]["signature"] = serde_json::Value::String(hex::encode(signature.to_bytes()));
+            println!("{}", serde_json::to_string_pretty(&body)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn keypair_from_hex(secret: &str) -> Result<NodeKeypair> {
+    let secret_bytes = hex::decode(secret)
+        .map_err(|e| PclError::NodeIdentity(format!("Invalid secret key hex: {}", e)))?;
+    NodeKeypair::from_bytes(&secret_bytes)
+}
+
+fn read_json(path: &PathBuf) -> Result<serde_json::Value> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn policy_from_body(body: &serde_json::Value) -> Result<MultisigPolicy> {
+    let policy: MultisigPolicy = serde_json::from_value(body["multisig"].clone())
+        .map_err(|e| PclError::Validation(format!("Transaction has no valid multisig policy: {}", e)))?;
+    policy.validate()?;
+    Ok(policy)
+}
@@ -0,0 +1,138 @@
+// Binding module - links a transport PeerId to the application key a node signs with
+//
+// The transport identity that messages arrive from and the node keypair used for votes, pulses and
+// signatures are independent keys, so nothing stopped one peer from speaking for another node id.
+// Each node signs a binding statement with both keys and sends it at handshake. Receivers keep the
+// verified bindings and only accept identity-bearing messages whose claimed node id is bound to the
+// peer they arrived from. The binding also carries the sender's software build, which is informational
+// and left out of the signed statement so bindings verify across versions.
+
+use std::collections::HashMap;
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::{decode_public_key, decode_signature};
+use crate::version::BuildInfo;
+
+// Transport peer ids are derived from the transport public key, so a binding cannot name a peer id
+// its transport key does not own
+pub fn peer_id_for(transport_key: &VerifyingKey) -> String {
+    format!("peer_{}", hex::encode(transport_key.to_bytes()))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerBinding {
+    pub peer_id: String,
+    pub node_id: String,
+    pub app_public_key: String,       // hex encoded node key
+    pub transport_public_key: String, // hex encoded
+    pub app_signature: String,        // node key over the statement
+    pub transport_signature: String,  // transport key over the statement
+    #[serde(default)]
+    pub build: Option<BuildInfo>, // None from nodes that predate build info
+}
+
+impl PeerBinding {
+    pub fn sign(node_id: &str, app_keypair: &NodeKeypair, transport_keypair: &NodeKeypair) -> Self {
+        let mut binding = Self {
+            peer_id: peer_id_for(&transport_keypair.public_key()),
+            node_id: node_id.to_string(),
+            app_public_key: hex::encode(app_keypair.public_key().to_bytes()),
+            transport_public_key: hex::encode(transport_keypair.public_key().to_bytes()),
+            app_signature: String::new(),
+            transport_signature: String::new(),
+            build: Some(BuildInfo::current()),
+        };
+        let statement = binding.statement();
+        binding.app_signature = hex::encode(app_keypair.sign_data(&statement).to_bytes());
+        binding.transport_signature = hex::encode(transport_keypair.sign_data(&statement).to_bytes());
+        binding
+    }
+
+    pub fn statement(&self) -> Vec<u8> {
+        format!(
+            "pcl-peer-binding:{}:{}:{}:{}", self.peer_id, self.node_id, self.app_public_key, self.transport_public_key
+        ).into_bytes()
+    }
+
+    // Both keys signed the statement and the peer id belongs to the transport key. Callers check the
+    // app key against the node's registered key.
+    pub fn verify(&self) -> Result<()> {
+        let transport_key = decode_public_key(&self.transport_public_key)?;
+        if peer_id_for(&transport_key) != self.peer_id {
+            return Err(PclError::SignatureVerification(format!(
+                "Peer id {} is not derived from the binding's transport key", self.peer_id
+            )));
+        }
+        let statement = self.statement();
+        let app_key = decode_public_key(&self.app_public_key)?;
+        for (signature, key, signer) in [(&self.app_signature, &app_key, "node"), (&self.transport_signature, &transport_key, "transport")] {
+            if !verify_data_signature(&statement, &decode_signature(signature)?, key)? {
+                return Err(PclError::SignatureVerification(format!(
+                    "Invalid {} signature on the binding of {} to {}", signer, self.node_id, self.peer_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Verified bindings, by node id and by peer id
+#[derive(Debug, Clone, Default)]
+pub struct BindingRegistry {
+    by_node: HashMap<String, PeerBinding>,
+    node_by_peer: HashMap<String, String>,
+}
+
+impl BindingRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A newer binding for the node replaces the old one, e.g. after its transport key changed
+    pub fn insert(&mut self, binding: PeerBinding) -> Result<()> {
+        binding.verify()?;
+        if let Some(previous) = self.by_node.remove(&binding.node_id) {
+            self.node_by_peer.remove(&previous.peer_id);
+        }
+        if let Some(other_node) = self.node_by_peer.insert(binding.peer_id.clone(), binding.node_id.clone()) {
+            self.by_node.remove(&other_node);
+        }
+        self.by_node.insert(binding.node_id.clone(), binding);
+        Ok(())
+    }
+
+    pub fn for_node(&self, node_id: &str) -> Option<&PeerBinding> {
+        self.by_node.get(node_id)
+    }
+
+    pub fn node_for_peer(&self, peer_id: &str) -> Option<&str> {
+        self.node_by_peer.get(peer_id).map(String::as_str)
+    }
+
+    // The message from `peer_id` may speak for `node_id` only if that binding was verified
+    pub fn check(&self, peer_id: &str, node_id: &str) -> Result<()> {
+        match self.node_for_peer(peer_id) {
+            Some(bound) if bound == node_id => Ok(()),
+            Some(bound) => Err(PclError::SignatureVerification(format!(
+                "Peer {} is bound to node {}, not {}", peer_id, bound, node_id
+            ))),
+            None => Err(PclError::SignatureVerification(format!(
+                "Peer {} has no verified binding to node {}", peer_id, node_id
+            ))),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PeerBinding> {
+        self.by_node.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_node.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_node.is_empty()
+    }
+}
@@ -0,0 +1,73 @@
+// Blinding module - salted commitments that keep transaction amounts out of gossip
+//
+// With blinded_amounts on, the raw transaction is gossiped with every amount zeroed and a commitment
+// to the real ones. The opening (amounts plus salt) goes only to the validating leaders as encrypted
+// direct messages, and finalization checks it against the commitment before anything is written.
+
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use crate::address::Address;
+use crate::crypto::hash_data;
+use crate::error::{PclError, Result};
+use crate::transaction::{RawTransaction, TransactionData};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmountOpening {
+    pub raw_tx_id: String,
+    pub salt: String, // hex encoded, 32 random bytes
+    pub to: Vec<(Address, f64)>,
+    pub from: Vec<(String, f64)>,
+    pub change: Option<f64>,
+}
+
+impl AmountOpening {
+    pub fn new(raw_tx_id: &str, tx_data: &TransactionData) -> Self {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            raw_tx_id: raw_tx_id.to_string(),
+            salt: hex::encode(salt),
+            to: tx_data.to.clone(),
+            from: tx_data.from.clone(),
+            change: tx_data.change,
+        }
+    }
+
+    // Hex hash over the tx id, salt and amounts; the salt stops anyone guessing round amounts
+    pub fn commitment(&self) -> Result<String> {
+        let amounts = serde_json::to_string(&(&self.to, &self.from, &self.change))?;
+        let preimage = format!("pcl-amount-commitment:{}:{}:{}", self.raw_tx_id, self.salt, amounts);
+        Ok(hex::encode(hash_data(preimage.as_bytes())))
+    }
+
+    // The opening must match the gossiped commitment and the transaction being finalized
+    pub fn verify(&self, commitment: &str, tx_data: &TransactionData) -> Result<()> {
+        if self.commitment()? != commitment {
+            return Err(PclError::Validation(format!("Revealed amounts for {} do not match the commitment", self.raw_tx_id)));
+        }
+        if self.to != tx_data.to || self.from != tx_data.from || self.change != tx_data.change {
+            return Err(PclError::Validation(format!("Revealed amounts for {} do not match the transaction", self.raw_tx_id)));
+        }
+        Ok(())
+    }
+
+    // Puts the amounts back into a blinded transaction
+    pub fn reveal(&self, tx_data: &mut TransactionData) {
+        tx_data.to = self.to.clone();
+        tx_data.from = self.from.clone();
+        tx_data.change = self.change;
+    }
+}
+
+// The copy that is gossiped: addresses and utxo ids stay, amounts are zeroed
+pub fn blind_transaction(tx: &RawTransaction) -> RawTransaction {
+    let mut blinded = tx.clone();
+    for (_, amount) in blinded.tx_data.to.iter_mut() {
+        *amount = 0.0;
+    }
+    for (_, amount) in blinded.tx_data.from.iter_mut() {
+        *amount = 0.0;
+    }
+    blinded.tx_data.change = None;
+    blinded
+}
@@ -0,0 +1,423 @@
+// Client module - minimal async client for a node's HTTP JSON API
+
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use crate::error::{PclError, Result};
+use crate::fees::FeeEstimate;
+use crate::limits::MAX_RESPONSE_SIZE;
+use crate::receipt::TransactionReceipt;
+use crate::reconcile::{SyncLeaf, SyncNode, SyncedTransaction};
+use crate::search::TransactionQuery;
+use crate::transaction::{TransactionCancellation, UserValidationTaskCompletion};
+
+pub const DEFAULT_NODE_URL: &str = "http://127.0.0.1:8080";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MempoolStage {
+    Raw,
+    Tasks,
+    Processing,
+    Final,
+}
+
+impl MempoolStage {
+    pub const ALL: [MempoolStage; 4] = [MempoolStage::Raw, MempoolStage::Tasks, MempoolStage::Processing, MempoolStage::Final];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MempoolStage::Raw => "raw",
+            MempoolStage::Tasks => "tasks",
+            MempoolStage::Processing => "processing",
+            MempoolStage::Final => "final",
+        }
+    }
+}
+
+impl FromStr for MempoolStage {
+    type Err = PclError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "raw" => Ok(MempoolStage::Raw),
+            "tasks" => Ok(MempoolStage::Tasks),
+            "processing" => Ok(MempoolStage::Processing),
+            "final" => Ok(MempoolStage::Final),
+            other => Err(PclError::Validation(format!(
+                "Unknown mempool stage '{}', expected raw, tasks, processing or final", other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for MempoolStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// One row of a mempool listing, the same shape for every stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MempoolEntry {
+    pub stage: MempoolStage,
+    pub id: String,    // tx id, or task id for the tasks stage
+    pub tx_id: String,
+    pub leader: Option<String>,
+    pub user: Option<String>,
+    pub status: String,
+    pub timestamp: u64,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolListing {
+    pub stage: MempoolStage,
+    pub count: usize,
+    pub entries: Vec<MempoolEntry>,
+}
+
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+impl MempoolEntry {
+    // Listing order: newest first, then id, then leader (raw entries repeat per leader)
+    pub fn sort_key(&self) -> (std::cmp::Reverse<u64>, &str, Option<&str>) {
+        (std::cmp::Reverse(self.timestamp), self.id.as_str(), self.leader.as_deref())
+    }
+
+    // Opaque position of this entry in the listing order
+    pub fn cursor(&self) -> String {
+        hex::encode(format!("{}\n{}\n{}", self.timestamp, self.id, self.leader.as_deref().unwrap_or("")))
+    }
+}
+
+// One page of a stage listing; pass next_cursor back to continue where this page ended
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolPage {
+    pub stage: MempoolStage,
+    pub count: usize, // entries in the whole stage
+    pub offset: usize,
+    pub limit: usize,
+    pub entries: Vec<MempoolEntry>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequest {
+    pub offset: usize,
+    pub limit: usize,
+    pub cursor: Option<String>, // takes precedence over offset, stays stable while entries arrive
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self { offset: 0, limit: DEFAULT_PAGE_LIMIT, cursor: None }
+    }
+}
+
+impl PageRequest {
+    // Reads offset, limit and cursor from a query string, ignoring other parameters
+    pub fn from_query(query: &str) -> Result<Self> {
+        let mut page = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "offset" => page.offset = value.parse()
+                    .map_err(|_| PclError::Validation(format!("offset must be a non-negative integer, got '{}'", value)))?,
+                "limit" => page.limit = value.parse()
+                    .map_err(|_| PclError::Validation(format!("limit must be a positive integer, got '{}'", value)))?,
+                "cursor" if !value.is_empty() => page.cursor = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if page.limit == 0 || page.limit > MAX_PAGE_LIMIT {
+            return Err(PclError::Validation(format!("limit must be between 1 and {}", MAX_PAGE_LIMIT)));
+        }
+        Ok(page)
+    }
+
+    pub fn to_query(&self) -> String {
+        match &self.cursor {
+            Some(cursor) => format!("limit={}&cursor={}", self.limit, cursor),
+            None => format!("offset={}&limit={}", self.offset, self.limit),
+        }
+    }
+}
+
+// Cuts one page out of a full stage listing. Entries are sorted here, so callers can pass them in any order.
+pub fn paginate(stage: MempoolStage, mut entries: Vec<MempoolEntry>, request: &PageRequest) -> Result<MempoolPage> {
+    entries.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    let count = entries.len();
+
+    let start = match &request.cursor {
+        Some(cursor) => {
+            let (timestamp, id, leader) = decode_cursor(cursor)?;
+            let position = (std::cmp::Reverse(timestamp), id.as_str(), Some(leader.as_str()).filter(|l| !l.is_empty()));
+            entries.partition_point(|entry| entry.sort_key() <= position)
+        }
+        None => request.offset.min(count),
+    };
+    let end = (start + request.limit).min(count);
+    let next_cursor = if end < count { entries.get(end - 1).map(|entry| entry.cursor()) } else { None };
+
+    Ok(MempoolPage {
+        stage,
+        count,
+        offset: start,
+        limit: request.limit,
+        entries: entries.drain(start..end).collect(),
+        next_cursor,
+    })
+}
+
+fn decode_cursor(cursor: &str) -> Result<(u64, String, String)> {
+    let invalid = || PclError::Validation(format!("Invalid cursor '{}'", cursor));
+    let bytes = hex::decode(cursor).map_err(|_| invalid())?;
+    let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+    let mut parts = text.splitn(3, '\n');
+    let timestamp = parts.next().and_then(|t| t.parse().ok()).ok_or_else(invalid)?;
+    let id = parts.next().ok_or_else(invalid)?.to_string();
+    let leader = parts.next().ok_or_else(invalid)?.to_string();
+    Ok((timestamp, id, leader))
+}
+
+#[derive(Debug, Clone)]
+pub struct PclClient {
+    host: String, // host:port
+    api_key: Option<String>,
+}
+
+impl PclClient {
+    // Accepts "http://host:port" or plain "host:port"
+    pub fn new(node_url: &str) -> Result<Self> {
+        let host = node_url.trim()
+            .trim_start_matches("http://")
+            .trim_end_matches('/');
+        if host.is_empty() || host.contains('/') || node_url.starts_with("https://") {
+            return Err(PclError::Network(format!("Unsupported node URL '{}'", node_url)));
+        }
+        Ok(Self { host: host.to_string(), api_key: None })
+    }
+
+    // Sent as X-API-Key on every request, for nodes with API key auth enabled
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    // Splits a full "http://host:port/path" URL into a client for the host and the request path
+    pub fn from_url(url: &str) -> Result<(Self, String)> {
+        let rest = url.trim().strip_prefix("http://")
+            .ok_or_else(|| PclError::Network(format!("Unsupported URL '{}', expected http://", url)))?;
+        let (host, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        Ok((Self::new(host)?, path.to_string()))
+    }
+
+    pub async fn health(&self) -> Result<serde_json::Value> {
+        self.get("/health").await
+    }
+
+    pub async fn network(&self) -> Result<serde_json::Value> {
+        self.get("/network").await
+    }
+
+    // Identity and leader state, plus whether they were restored from disk at startup
+    pub async fn node_info(&self) -> Result<serde_json::Value> {
+        self.get("/node").await
+    }
+
+    pub async fn leaders(&self) -> Result<serde_json::Value> {
+        self.get("/leaders").await
+    }
+
+    pub async fn mempools(&self) -> Result<serde_json::Value> {
+        self.get("/mempools").await
+    }
+
+    pub async fn mempool_stage(&self, stage: MempoolStage) -> Result<MempoolListing> {
+        let value = self.get(&format!("/mempool/{}", stage)).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub async fn mempool_page(&self, stage: MempoolStage, request: &PageRequest) -> Result<MempoolPage> {
+        let value = self.get(&format!("/mempools?stage={}&{}", stage, request.to_query())).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    // Walks a stage page by page with cursors, so large mempools never come back in one response
+    pub async fn mempool_stage_entries(&self, stage: MempoolStage) -> Result<Vec<MempoolEntry>> {
+        let mut request = PageRequest { limit: MAX_PAGE_LIMIT, ..PageRequest::default() };
+        let mut entries = Vec::new();
+        loop {
+            let page = self.mempool_page(stage, &request).await?;
+            entries.extend(page.entries);
+            match page.next_cursor {
+                Some(cursor) => request.cursor = Some(cursor),
+                None => return Ok(entries),
+            }
+        }
+    }
+
+    pub async fn transaction(&self, tx_id: &str) -> Result<serde_json::Value> {
+        self.get(&format!("/transaction/{}", tx_id)).await
+    }
+
+    // Signed proof of finalization; check it with TransactionReceipt::verify
+    pub async fn receipt(&self, tx_id: &str) -> Result<TransactionReceipt> {
+        let value = self.get(&format!("/transaction/{}/receipt", tx_id)).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub async fn search_transactions(&self, query: &TransactionQuery) -> Result<serde_json::Value> {
+        self.get(&format!("/search/transactions?{}", query.to_query())).await
+    }
+
+    pub async fn balance(&self, address: &str) -> Result<serde_json::Value> {
+        self.get(&format!("/balance/{}", address)).await
+    }
+
+    // Current minimum fee and suggestions for a transaction of the given weight (the node's reference
+    // weight if None); submissions below minimum_fee are refused with FEE_TOO_LOW
+    pub async fn fee_estimate(&self, weight: Option<u64>) -> Result<FeeEstimate> {
+        let path = match weight {
+            Some(weight) => format!("/fee-estimate?weight={}", weight),
+            None => "/fee-estimate".to_string(),
+        };
+        let value = self.get(&path).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub async fn submit_transaction(&self, body: &serde_json::Value, idempotency_key: Option<&str>) -> Result<serde_json::Value> {
+        let headers: Vec<(&str, &str)> = idempotency_key.map(|key| ("Idempotency-Key", key)).into_iter().collect();
+        self.request("POST", "/transaction", Some(body), &headers).await
+    }
+
+    // Canonical unsigned form of a transaction, for signing offline with `pcl-wallet sign`
+    pub async fn prepare_transaction(&self, body: &serde_json::Value) -> Result<serde_json::Value> {
+        self.post("/transaction/prepare", body).await
+    }
+
+    pub async fn broadcast_transaction(&self, signed: &serde_json::Value) -> Result<serde_json::Value> {
+        self.post("/transaction/broadcast", signed).await
+    }
+
+    // Withdraws a raw transaction before processing; sign the cancellation with TransactionCancellation::sign
+    pub async fn cancel_transaction(&self, cancellation: &TransactionCancellation) -> Result<serde_json::Value> {
+        self.post(&format!("/transaction/{}/cancel", cancellation.raw_tx_id), &serde_json::to_value(cancellation)?).await
+    }
+
+    pub async fn validation_tasks(&self, address: &str) -> Result<serde_json::Value> {
+        self.get(&format!("/tasks/{}", address)).await
+    }
+
+    pub async fn request_validation_tasks(&self, user: &str) -> Result<serde_json::Value> {
+        self.post("/tasks/request", &serde_json::json!({ "user": user })).await
+    }
+
+    pub async fn complete_validation_task(&self, completion: &UserValidationTaskCompletion) -> Result<serde_json::Value> {
+        self.post("/tasks/complete", &serde_json::to_value(completion)?).await
+    }
+
+    // Finalized-set tree of a peer, for reconciling after a partition
+    pub async fn sync_node(&self, prefix: &str) -> Result<SyncNode> {
+        Ok(serde_json::from_value(self.get(&format!("/sync/tree?prefix={}", prefix)).await?)?)
+    }
+
+    pub async fn sync_leaves(&self, prefix: &str) -> Result<Vec<SyncLeaf>> {
+        Ok(serde_json::from_value(self.get(&format!("/sync/leaves?prefix={}", prefix)).await?)?)
+    }
+
+    pub async fn sync_transaction(&self, tx_id: &str) -> Result<SyncedTransaction> {
+        Ok(serde_json::from_value(self.get(&format!("/sync/transaction/{}", tx_id)).await?)?)
+    }
+
+    pub async fn get(&self, path: &str) -> Result<serde_json::Value> {
+        self.request("GET", path, None, &[]).await
+    }
+
+    pub async fn post(&self, path: &str, body: &serde_json::Value) -> Result<serde_json::Value> {
+        self.request("POST", path, Some(body), &[]).await
+    }
+
+    // Posts an already serialized body as-is and returns the status code; used where the exact bytes
+    // matter (signed webhook payloads) and the response need not be JSON
+    pub async fn post_raw(&self, path: &str, body: &str, headers: &[(&str, &str)]) -> Result<u16> {
+        let response = self.send("POST", path, body, headers).await?;
+        let (head, _body) = split_response(&response)?;
+        status_code(head)
+    }
+
+    async fn request(&self, method: &str, path: &str, body: Option<&serde_json::Value>, headers: &[(&str, &str)]) -> Result<serde_json::Value> {
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let response = self.send(method, path, &body, headers).await?;
+        parse_response(&response)
+    }
+
+    async fn send(&self, method: &str, path: &str, body: &str, headers: &[(&str, &str)]) -> Result<String> {
+        let mut stream = TcpStream::connect(&self.host).await
+            .map_err(|e| PclError::Network(format!("Cannot reach node at {}: {}", self.host, e)))?;
+
+        let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", method, path, self.host);
+        if let Some(api_key) = &self.api_key {
+            request.push_str(&format!("{}: {}\r\n", crate::auth::API_KEY_HEADER, api_key));
+        }
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if !body.is_empty() {
+            request.push_str(&format!("Content-Type: application/json\r\nContent-Length: {}\r\n", body.len()));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+
+        stream.write_all(request.as_bytes()).await?;
+        // Read one byte past the cap so an oversized response is detected rather than truncated
+        let mut response = Vec::new();
+        (&mut stream).take(MAX_RESPONSE_SIZE as u64 + 1).read_to_end(&mut response).await?;
+        if response.len() > MAX_RESPONSE_SIZE {
+            return Err(PclError::Network(format!(
+                "Response from {} exceeds the {} byte limit", self.host, MAX_RESPONSE_SIZE
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&response).into_owned())
+    }
+}
+
+// Splits a raw HTTP response into status and JSON body, turning error responses into PclError
+pub fn parse_response(response: &str) -> Result<serde_json::Value> {
+    let (head, body) = split_response(response)?;
+    let status = status_code(head)?;
+
+    let body = body.trim();
+    let value: serde_json::Value = if body.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_str(body)?
+    };
+
+    if !(200..300).contains(&status) {
+        let code = value["error"]["code"].as_str().unwrap_or("HTTP_ERROR");
+        let message = value["error"]["message"].as_str()
+            .or_else(|| value["error"].as_str())
+            .unwrap_or("request failed");
+        return Err(PclError::Network(format!("{} {}: {}", status, code, message)));
+    }
+    Ok(value)
+}
+
+fn split_response(response: &str) -> Result<(&str, &str)> {
+    response.split_once("\r\n\r\n")
+        .ok_or_else(|| PclError::Network("Malformed HTTP response".to_string()))
+}
+
+fn status_code(head: &str) -> Result<u16> {
+    head.split_whitespace().nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| PclError::Network("Malformed HTTP status line".to_string()))
+}
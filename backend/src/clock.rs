@@ -0,0 +1,129 @@
+// Clock module - measured offset of the local clock, applied to the timestamps the node produces
+//
+// Averaged validation timestamps only mean something if nodes agree on the time. ClockSync keeps the
+// latest NTP measurement and the offsets implied by peers' pulse timestamps, and uses NTP while it is
+// fresh and the peer median otherwise. The chosen offset is applied process-wide through now_ms, and a
+// node whose own clock is further off than max_drift_ms reports itself unhealthy until it is fixed.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use crate::config::ClockConfig;
+
+// Fewer peers than this can't outvote one badly set clock
+pub const MIN_PEER_SAMPLES: usize = 3;
+
+static CLOCK_OFFSET_MS: AtomicI64 = AtomicI64::new(0);
+static CLOCK_HEALTHY: AtomicBool = AtomicBool::new(true);
+
+// The local clock as is, in ms since epoch
+pub fn system_now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+// The local clock corrected by the last measured offset
+pub fn now_ms() -> u64 {
+    (system_now_ms() as i64 + clock_offset_ms()).max(0) as u64
+}
+
+pub fn now_utc() -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(now_ms() as i64).single().unwrap_or_else(Utc::now)
+}
+
+pub fn clock_offset_ms() -> i64 {
+    CLOCK_OFFSET_MS.load(Ordering::Relaxed)
+}
+
+pub fn clock_healthy() -> bool {
+    CLOCK_HEALTHY.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OffsetSource {
+    Ntp,
+    PeerMedian,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockStatus {
+    pub offset_ms: i64, // how far the local clock is behind (+) or ahead (-)
+    pub source: OffsetSource,
+    pub measured_at: u64,
+    pub healthy: bool,
+    pub max_drift_ms: i64,
+}
+
+pub struct ClockSync {
+    config: ClockConfig,
+    ntp: Option<(i64, u64)>,              // (offset ms, measured at)
+    peers: HashMap<String, (i64, u64)>,   // peer id -> (offset ms, measured at)
+    status: Option<ClockStatus>,
+}
+
+impl ClockSync {
+    pub fn new(config: ClockConfig) -> Self {
+        Self { config, ntp: None, peers: HashMap::new(), status: None }
+    }
+
+    // Applies the offset persisted by a previous run until a fresh measurement replaces it
+    pub fn restore(&mut self, status: ClockStatus) {
+        apply(&status);
+        self.status = Some(status);
+    }
+
+    pub fn record_ntp(&mut self, offset_ms: i64, now: u64) {
+        self.ntp = Some((offset_ms, now));
+    }
+
+    // A peer's timestamp against our raw clock when it arrived; network delay makes peers look slightly behind
+    pub fn record_peer(&mut self, peer_id: &str, peer_timestamp_ms: u64, local_ms: u64) {
+        let offset = peer_timestamp_ms as i64 - local_ms as i64;
+        self.peers.insert(peer_id.to_string(), (offset, local_ms));
+    }
+
+    // NTP while it is fresh, otherwise the median of recent peer offsets
+    pub fn estimate(&self, now: u64) -> Option<(i64, OffsetSource)> {
+        let cutoff = now.saturating_sub(self.config.sync_interval_secs * 2 * 1000);
+        if let Some((offset, _)) = self.ntp.filter(|(_, at)| *at >= cutoff) {
+            return Some((offset, OffsetSource::Ntp));
+        }
+
+        let mut offsets: Vec<i64> = self.peers.values()
+            .filter(|(_, at)| *at >= cutoff)
+            .map(|(offset, _)| *offset)
+            .collect();
+        if offsets.len() < MIN_PEER_SAMPLES {
+            return None;
+        }
+        offsets.sort_unstable();
+        let mid = offsets.len() / 2;
+        let median = if offsets.len().is_multiple_of(2) { (offsets[mid - 1] + offsets[mid]) / 2 } else { offsets[mid] };
+        Some((median, OffsetSource::PeerMedian))
+    }
+
+    // Re-estimates and applies the offset; None keeps whatever was applied before
+    pub fn update(&mut self, now: u64) -> Option<ClockStatus> {
+        let (offset_ms, source) = self.estimate(now)?;
+        let status = ClockStatus {
+            offset_ms,
+            source,
+            measured_at: now,
+            healthy: offset_ms.abs() <= self.config.max_drift_ms,
+            max_drift_ms: self.config.max_drift_ms,
+        };
+        apply(&status);
+        self.status = Some(status.clone());
+        Some(status)
+    }
+
+    pub fn status(&self) -> Option<&ClockStatus> {
+        self.status.as_ref()
+    }
+}
+
+fn apply(status: &ClockStatus) {
+    CLOCK_OFFSET_MS.store(status.offset_ms, Ordering::Relaxed);
+    CLOCK_HEALTHY.store(status.healthy, Ordering::Relaxed);
+}
@@ -0,0 +1,800 @@
+// Config module - runtime node and consensus parameters
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use crate::discovery::parse_multiaddr;
+use crate::error::{PclError, Result};
+
+// Upper bounds keep a typo in a testnet config from stalling or flooding the network
+pub const MAX_VALIDATION_COMPLETIONS: usize = 64;
+pub const MAX_BROADCAST_FANOUT: usize = 32;
+pub const MAX_CROSS_VALIDATORS: usize = 32;
+pub const MAX_EXPORT_BATCH_SIZE: usize = 10_000;
+pub const DEFAULT_TLS_CERT_PATH: &str = "./pcl_tls/cert.pem";
+pub const DEFAULT_TLS_KEY_PATH: &str = "./pcl_tls/key.pem";
+pub const MAX_LOG_FILES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsensusConfig {
+    // Validator completions (validation timestamps) a leader needs before it processes a raw tx
+    pub min_validation_completions: usize,
+    // Cross-validator completions (k) a raw tx needs before its leader processes it; 0 waits for none
+    pub cross_validation_quorum: usize,
+    // Cross-validators (n) assigned to a raw tx at once while the quorum is on
+    pub cross_validators_per_tx: usize,
+    // Leader signatures, including the processing leader's own, needed to finalize a tx
+    pub required_leader_signatures: usize,
+    // Number of other leaders a raw tx is gossiped to
+    pub broadcast_fanout: usize,
+    // Locks whose transaction is gone are released by GC once they are this old
+    pub locked_utxo_ttl_secs: u64,
+    // Newly bonded stake only counts for elections and validation after this long
+    pub stake_bonding_period_secs: u64,
+    // Unbonded stake is returned to its owner this long after the request
+    pub stake_unbonding_delay_secs: u64,
+    // Bonded stake an address needs before it is given validation tasks; 0 lets anyone validate
+    pub min_validator_stake: f64,
+    // Bonded stake a node needs to be nominated for leadership; 0 nominates every eligible node
+    pub min_leader_stake: f64,
+    // Gossip a salted commitment instead of amounts; only the validating leaders are sent the amounts
+    pub blinded_amounts: bool,
+    // Receipted pulses a newly seen node needs before its votes and task completions count; 0 disables probation
+    pub probation_pulse_receipts: usize,
+    // Minimum time a newly seen node stays on probation
+    pub probation_period_secs: u64,
+    // A validation task not completed this long after assignment counts as missed against its validator
+    pub task_deadline_secs: u64,
+    // How long the origin leader collects validation task offers for a transaction
+    pub task_offer_window_ms: u64,
+    // Offers accepted per transaction, each from a different leader
+    pub task_offers_selected: usize,
+    // Charged to a user cancelling their raw transaction, out of its stake
+    pub cancellation_fee: f64,
+    // Submissions one user may make at once, then the sustained rate per second; a rate of 0 disables the limit
+    pub user_submission_burst: u32,
+    pub user_submissions_per_sec: f64,
+    // How long a transaction may sit in each of the six workflow steps, in step order, before it counts as stalled
+    pub workflow_step_timeouts_secs: [u64; 6],
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            min_validation_completions: 1,
+            cross_validation_quorum: 0,
+            cross_validators_per_tx: 3,
+            required_leader_signatures: 1,
+            broadcast_fanout: 3,
+            locked_utxo_ttl_secs: 600,
+            stake_bonding_period_secs: 3600,
+            stake_unbonding_delay_secs: 24 * 3600,
+            min_validator_stake: 0.0,
+            min_leader_stake: 0.0,
+            blinded_amounts: false,
+            probation_pulse_receipts: 0,
+            probation_period_secs: 3600,
+            task_deadline_secs: 300,
+            task_offer_window_ms: 2000,
+            task_offers_selected: 2,
+            cancellation_fee: 0.01,
+            user_submission_burst: 20,
+            user_submissions_per_sec: 5.0,
+            workflow_step_timeouts_secs: [30, 60, 120, 600, 120, 300],
+        }
+    }
+}
+
+impl ConsensusConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.min_validation_completions == 0 || self.min_validation_completions > MAX_VALIDATION_COMPLETIONS {
+            return Err(PclError::Config(format!(
+                "min_validation_completions must be between 1 and {}, got {}",
+                MAX_VALIDATION_COMPLETIONS, self.min_validation_completions
+            )));
+        }
+        if self.broadcast_fanout == 0 || self.broadcast_fanout > MAX_BROADCAST_FANOUT {
+            return Err(PclError::Config(format!(
+                "broadcast_fanout must be between 1 and {}, got {}",
+                MAX_BROADCAST_FANOUT, self.broadcast_fanout
+            )));
+        }
+        if self.cross_validators_per_tx > MAX_CROSS_VALIDATORS || self.cross_validation_quorum > self.cross_validators_per_tx {
+            return Err(PclError::Config(format!(
+                "cross_validation_quorum ({}) must not exceed cross_validators_per_tx ({}), which is at most {}",
+                self.cross_validation_quorum, self.cross_validators_per_tx, MAX_CROSS_VALIDATORS
+            )));
+        }
+        // Leaders do not gather co-signatures from each other yet, so a finalization only ever carries the
+        // processing leader's own; requiring more would stop every finalization
+        if self.required_leader_signatures != 1 {
+            return Err(PclError::Config(format!(
+                "required_leader_signatures must be 1 until leader co-signing exists, got {}",
+                self.required_leader_signatures
+            )));
+        }
+        if self.locked_utxo_ttl_secs == 0 {
+            return Err(PclError::Config("locked_utxo_ttl_secs must be positive".to_string()));
+        }
+        if self.task_deadline_secs == 0 {
+            return Err(PclError::Config("task_deadline_secs must be positive".to_string()));
+        }
+        if self.task_offer_window_ms == 0 || self.task_offers_selected == 0 {
+            return Err(PclError::Config("task_offer_window_ms and task_offers_selected must be positive".to_string()));
+        }
+        if self.workflow_step_timeouts_secs.contains(&0) {
+            return Err(PclError::Config("workflow_step_timeouts_secs must all be positive".to_string()));
+        }
+        if self.probation_pulse_receipts > 0 && self.probation_period_secs == 0 {
+            return Err(PclError::Config("probation_period_secs must be positive when probation is enabled".to_string()));
+        }
+        if self.stake_bonding_period_secs == 0 || self.stake_unbonding_delay_secs == 0 {
+            return Err(PclError::Config("stake_bonding_period_secs and stake_unbonding_delay_secs must be positive".to_string()));
+        }
+        if !self.user_submissions_per_sec.is_finite() || self.user_submissions_per_sec < 0.0 {
+            return Err(PclError::Config(format!(
+                "user_submissions_per_sec must be a non-negative rate, got {}", self.user_submissions_per_sec
+            )));
+        }
+        if self.user_submissions_per_sec > 0.0 && self.user_submission_burst == 0 {
+            return Err(PclError::Config("user_submission_burst must be positive when submissions are rate limited".to_string()));
+        }
+        for (name, value) in [
+            ("min_validator_stake", self.min_validator_stake),
+            ("min_leader_stake", self.min_leader_stake),
+            ("cancellation_fee", self.cancellation_fee),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(PclError::Config(format!("{} must be a non-negative amount, got {}", name, value)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeeConfig {
+    // Minimum fee while mempool depth and finalization latency are at or below their targets
+    pub base_fee: f64,
+    // Charged per unit of transaction weight on top of base_fee, scaled by the same pressure
+    pub fee_per_weight_unit: f64,
+    pub target_mempool_depth: usize,
+    pub target_finalization_latency_ms: u64,
+    // Depth and latency are averaged over this many seconds
+    pub window_secs: u64,
+    // Pressure never multiplies the minimum fee by more than this
+    pub max_multiplier: f64,
+    // Largest fraction of the minimum fee waived for senders with a top reputation score
+    pub max_reputation_discount: f64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            base_fee: 0.01,
+            fee_per_weight_unit: 0.00001,
+            target_mempool_depth: 100,
+            target_finalization_latency_ms: 2000,
+            window_secs: 300,
+            max_multiplier: 100.0,
+            max_reputation_discount: 0.2,
+        }
+    }
+}
+
+impl FeeConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !self.base_fee.is_finite() || self.base_fee < 0.0 {
+            return Err(PclError::Config(format!("fees base_fee must be a non-negative amount, got {}", self.base_fee)));
+        }
+        if !self.fee_per_weight_unit.is_finite() || self.fee_per_weight_unit < 0.0 {
+            return Err(PclError::Config(format!(
+                "fees fee_per_weight_unit must be a non-negative amount, got {}", self.fee_per_weight_unit
+            )));
+        }
+        if self.target_mempool_depth == 0 || self.target_finalization_latency_ms == 0 || self.window_secs == 0 {
+            return Err(PclError::Config(
+                "fees target_mempool_depth, target_finalization_latency_ms and window_secs must be positive".to_string()
+            ));
+        }
+        if !(self.max_multiplier >= 1.0 && self.max_multiplier.is_finite()) {
+            return Err(PclError::Config(format!("fees max_multiplier must be at least 1, got {}", self.max_multiplier)));
+        }
+        if !(0.0..1.0).contains(&self.max_reputation_discount) {
+            return Err(PclError::Config(format!(
+                "fees max_reputation_discount must be at least 0 and below 1, got {}", self.max_reputation_discount
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    // sqlite:// or postgres:// DSN; export is off when unset
+    pub dsn: Option<String>,
+    pub batch_size: usize,
+    pub flush_interval_ms: u64,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            dsn: None,
+            batch_size: 100,
+            flush_interval_ms: 1000,
+        }
+    }
+}
+
+impl ExportConfig {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(dsn) = &self.dsn {
+            if !["sqlite:", "postgres://", "postgresql://"].iter().any(|scheme| dsn.starts_with(scheme)) {
+                return Err(PclError::Config(format!("export dsn must be a sqlite: or postgres:// URL, got '{}'", dsn)));
+            }
+        }
+        if self.batch_size == 0 || self.batch_size > MAX_EXPORT_BATCH_SIZE {
+            return Err(PclError::Config(format!(
+                "export batch_size must be between 1 and {}, got {}",
+                MAX_EXPORT_BATCH_SIZE, self.batch_size
+            )));
+        }
+        if self.flush_interval_ms == 0 {
+            return Err(PclError::Config("export flush_interval_ms must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    pub enabled: bool,
+    pub cert_path: Option<String>, // PEM certificate chain
+    pub key_path: Option<String>,  // PEM private key (PKCS#8, RSA or EC)
+    // Devnets: generate a localhost certificate at the cert/key paths if none exists yet
+    pub self_signed: bool,
+}
+
+impl TlsConfig {
+    pub fn cert_path(&self) -> PathBuf {
+        PathBuf::from(self.cert_path.as_deref().unwrap_or(DEFAULT_TLS_CERT_PATH))
+    }
+
+    pub fn key_path(&self) -> PathBuf {
+        PathBuf::from(self.key_path.as_deref().unwrap_or(DEFAULT_TLS_KEY_PATH))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && !self.self_signed && (self.cert_path.is_none() || self.key_path.is_none()) {
+            return Err(PclError::Config(
+                "tls needs cert_path and key_path, or self_signed for a devnet".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    // Off by default so devnets stay open; private deployments should turn it on
+    pub enabled: bool,
+    // Applied to keys created without an explicit limit
+    pub default_rate_limit_per_minute: u32,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_rate_limit_per_minute: 600,
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.default_rate_limit_per_minute == 0 {
+            return Err(PclError::Config("auth default_rate_limit_per_minute must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    // host:port of peers to contact first when joining the network
+    pub bootnodes: Vec<String>,
+    // Leaders publish a signed heartbeat per gossip topic this often
+    pub mesh_heartbeat_interval_secs: u64,
+    // Below this share of other leaders heard on a topic the node counts as isolated and dials more peers
+    pub mesh_min_delivery_ratio: f64,
+    // Messages sent per second for each outbound class, 0 for no cap; control goes out first
+    pub outbound_control_per_sec: u32,
+    pub outbound_validation_per_sec: u32,
+    pub outbound_gossip_per_sec: u32,
+    // Messages held per class while over its cap; the oldest is dropped beyond this
+    pub outbound_queue_limit: usize,
+    // Use the node identity key as the transport identity too, so one key controls both layers and
+    // the PeerId needs no separate binding statement
+    pub transport_key_from_identity: bool,
+    // Local peer discovery over mDNS; turn off where multicast does not reach peers, e.g. containers
+    pub enable_mdns: bool,
+    // Multiaddrs dialed at startup and whenever they drop out, e.g. /ip4/10.0.0.5/tcp/9000
+    pub static_peers: Vec<String>,
+    // host:port names resolved at startup and every dns_seed_refresh_secs, each record dialed
+    pub dns_seeds: Vec<String>,
+    pub dns_seed_refresh_secs: u64,
+    // Gossip mesh maintenance runs this often; lower it for high TPS so a thinned mesh is repaired sooner
+    pub gossip_heartbeat_interval_ms: u64,
+    // Target mesh size per node, grafted up to when below mesh_n_low and pruned back to when above mesh_n_high
+    pub gossip_mesh_n: usize,
+    pub gossip_mesh_n_low: usize,
+    pub gossip_mesh_n_high: usize,
+    // Heartbeats of message ids kept to drop repeats, and how many of them are advertised to non-mesh peers
+    pub gossip_history_length: usize,
+    pub gossip_history_gossip: usize,
+    // Send own messages to every connected peer rather than only the mesh
+    pub gossip_flood_publish: bool,
+    // Times a relayed notice may be forwarded before nodes stop passing it on
+    pub gossip_max_hops: u32,
+    // Bytes per second exchanged with any one peer in each direction, 0 for no cap; peers listed in
+    // peer_bandwidth_caps (peer id -> bytes per second) get their own
+    pub peer_bandwidth_cap_bytes_per_sec: u64,
+    pub peer_bandwidth_caps: BTreeMap<String, u64>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            bootnodes: Vec::new(),
+            mesh_heartbeat_interval_secs: 10,
+            mesh_min_delivery_ratio: 0.5,
+            outbound_control_per_sec: 0,
+            outbound_validation_per_sec: 500,
+            outbound_gossip_per_sec: 200,
+            outbound_queue_limit: 10_000,
+            transport_key_from_identity: false,
+            enable_mdns: true,
+            static_peers: Vec::new(),
+            dns_seeds: Vec::new(),
+            dns_seed_refresh_secs: 300,
+            gossip_heartbeat_interval_ms: 10_000,
+            gossip_mesh_n: 6,
+            gossip_mesh_n_low: 5,
+            gossip_mesh_n_high: 12,
+            gossip_history_length: 5,
+            gossip_history_gossip: 3,
+            gossip_flood_publish: true,
+            gossip_max_hops: 8,
+            peer_bandwidth_cap_bytes_per_sec: 0,
+            peer_bandwidth_caps: BTreeMap::new(),
+        }
+    }
+}
+
+impl NetworkConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.mesh_heartbeat_interval_secs == 0 {
+            return Err(PclError::Config("network mesh_heartbeat_interval_secs must be positive".to_string()));
+        }
+        if !(0.0..=1.0).contains(&self.mesh_min_delivery_ratio) {
+            return Err(PclError::Config("network mesh_min_delivery_ratio must be between 0 and 1".to_string()));
+        }
+        if self.outbound_queue_limit == 0 {
+            return Err(PclError::Config("network outbound_queue_limit must be positive".to_string()));
+        }
+        if self.gossip_heartbeat_interval_ms == 0 {
+            return Err(PclError::Config("network gossip_heartbeat_interval_ms must be positive".to_string()));
+        }
+        if !(self.gossip_mesh_n_low <= self.gossip_mesh_n && self.gossip_mesh_n <= self.gossip_mesh_n_high) || self.gossip_mesh_n_low == 0 {
+            return Err(PclError::Config(format!(
+                "network gossip mesh sizes must satisfy 0 < mesh_n_low <= mesh_n <= mesh_n_high, got {} <= {} <= {}",
+                self.gossip_mesh_n_low, self.gossip_mesh_n, self.gossip_mesh_n_high
+            )));
+        }
+        if self.gossip_history_length == 0 || self.gossip_history_gossip > self.gossip_history_length {
+            return Err(PclError::Config("network gossip_history_gossip must not exceed a positive gossip_history_length".to_string()));
+        }
+        if self.gossip_max_hops == 0 {
+            return Err(PclError::Config("network gossip_max_hops must be positive".to_string()));
+        }
+        if let Some((peer_id, _)) = self.peer_bandwidth_caps.iter().find(|(_, cap)| **cap == 0) {
+            return Err(PclError::Config(format!("network peer_bandwidth_caps for {} must be positive", peer_id)));
+        }
+        if self.dns_seed_refresh_secs == 0 {
+            return Err(PclError::Config("network dns_seed_refresh_secs must be positive".to_string()));
+        }
+        let is_host_port = |address: &str| address.rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok_and(|port| port > 0));
+        for bootnode in &self.bootnodes {
+            if !is_host_port(bootnode) {
+                return Err(PclError::Config(format!("bootnode must be host:port, got '{}'", bootnode)));
+            }
+        }
+        for seed in &self.dns_seeds {
+            if !is_host_port(seed) {
+                return Err(PclError::Config(format!("DNS seed must be host:port, got '{}'", seed)));
+            }
+        }
+        for peer in &self.static_peers {
+            parse_multiaddr(peer)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json, // one JSON object per line for log shippers
+}
+
+impl FromStr for LogFormat {
+    type Err = PclError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(PclError::Config(format!("log format must be text or json, got '{}'", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    // Filter directives such as "info" or "info,pcl_backend::network=debug"; RUST_LOG wins when set
+    pub level: String,
+    // Logs go to stderr when unset
+    pub file: Option<String>,
+    // The file is rotated to <file>.1 once it would grow past this size
+    pub max_file_size_mb: u64,
+    // Rotated files kept besides the active one; older ones are deleted
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            level: "error".to_string(), // the console already narrates progress with println
+            file: None,
+            max_file_size_mb: 100,
+            max_files: 5,
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn max_file_bytes(&self) -> u64 {
+        self.max_file_size_mb * 1024 * 1024
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.level.trim().is_empty() {
+            return Err(PclError::Config("logging level must not be empty".to_string()));
+        }
+        if self.max_file_size_mb == 0 {
+            return Err(PclError::Config("logging max_file_size_mb must be positive".to_string()));
+        }
+        if self.max_files > MAX_LOG_FILES {
+            return Err(PclError::Config(format!(
+                "logging max_files must be at most {}, got {}", MAX_LOG_FILES, self.max_files
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    // Off leaves the local clock uncorrected; peer pulses still feed the estimate
+    pub enabled: bool,
+    pub ntp_server: String,
+    pub sync_interval_secs: u64,
+    // Offsets beyond this mark the node unhealthy in /health and its pulses
+    pub max_drift_ms: i64,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ntp_server: crate::doctor::DEFAULT_NTP_SERVER.to_string(),
+            sync_interval_secs: 300,
+            max_drift_ms: 1000,
+        }
+    }
+}
+
+impl ClockConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && self.ntp_server.trim().is_empty() {
+            return Err(PclError::Config("clock ntp_server must not be empty".to_string()));
+        }
+        if self.sync_interval_secs == 0 || self.max_drift_ms <= 0 {
+            return Err(PclError::Config("clock sync_interval_secs and max_drift_ms must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Leader-side admission rules for the transactions this node accepts as leader; empty admits everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    // Senders and recipients refused outright
+    pub blocked_addresses: Vec<String>,
+    // Largest amount one transaction may send, in XMBL
+    pub max_amount: Option<f64>,
+    pub business_hours: Option<BusinessHours>,
+}
+
+// UTC hours transactions are accepted in; start 22 and end 6 spans midnight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BusinessHours {
+    pub start_hour: u32,
+    pub end_hour: u32, // exclusive
+    #[serde(default)]
+    pub weekdays_only: bool,
+}
+
+impl AdmissionConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.max_amount.is_some_and(|max| !max.is_finite() || max <= 0.0) {
+            return Err(PclError::Config("admission max_amount must be positive".to_string()));
+        }
+        if let Some(hours) = &self.business_hours {
+            if hours.start_hour > 23 || hours.end_hour > 24 || hours.start_hour == hours.end_hour {
+                return Err(PclError::Config(
+                    "admission business_hours need start_hour 0-23 and a different end_hour 0-24".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Maintainer-signed address screening; off unless a maintainer key is set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningConfig {
+    // Hex encoded public key of the list maintainer; must be the same on every node of a network
+    pub maintainer_key: Option<String>,
+    // http:// URL serving the latest signed list as JSON, polled in addition to gossip
+    pub list_url: Option<String>,
+    pub refresh_secs: u64,
+}
+
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            maintainer_key: None,
+            list_url: None,
+            refresh_secs: 3600,
+        }
+    }
+}
+
+impl ScreeningConfig {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(key) = &self.maintainer_key {
+            crate::multisig::decode_public_key(key)
+                .map_err(|e| PclError::Config(format!("screening maintainer_key: {}", e)))?;
+        } else if self.list_url.is_some() {
+            return Err(PclError::Config("screening list_url needs a maintainer_key to check lists against".to_string()));
+        }
+        if self.refresh_secs == 0 {
+            return Err(PclError::Config("screening refresh_secs must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Ledgers hosted next to the default one, each reached under /tenants/{id}
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenancyConfig {
+    pub tenants: Vec<String>,
+}
+
+impl TenancyConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (i, tenant) in self.tenants.iter().enumerate() {
+            crate::tenancy::validate_tenant_id(tenant)?;
+            if self.tenants[..i].contains(tenant) {
+                return Err(PclError::Config(format!("tenant '{}' is listed twice", tenant)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Other leaders' APIs (host:port) whose finalized sets are compared with this node's, so a node
+// catches up on what was finalized on the other side of a partition once it heals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconcileConfig {
+    pub peers: Vec<String>,
+    pub interval_secs: u64,
+}
+
+impl Default for ReconcileConfig {
+    fn default() -> Self {
+        Self {
+            peers: Vec::new(),
+            interval_secs: 60,
+        }
+    }
+}
+
+impl ReconcileConfig {
+    pub fn validate(&self) -> Result<()> {
+        for peer in &self.peers {
+            if !peer.rsplit_once(':').is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok_and(|port| port > 0)) {
+                return Err(PclError::Config(format!("reconcile peer must be host:port, got '{}'", peer)));
+            }
+        }
+        if self.interval_secs == 0 {
+            return Err(PclError::Config("reconcile interval_secs must be positive".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeConfig {
+    pub consensus: ConsensusConfig,
+    pub fees: FeeConfig,
+    pub export: ExportConfig,
+    pub tls: TlsConfig,
+    pub auth: AuthConfig,
+    pub network: NetworkConfig,
+    pub logging: LoggingConfig,
+    pub clock: ClockConfig,
+    pub admission: AdmissionConfig,
+    pub screening: ScreeningConfig,
+    pub tenancy: TenancyConfig,
+    pub reconcile: ReconcileConfig,
+}
+
+impl NodeConfig {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let config: NodeConfig = serde_json::from_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Reads the JSON file named by PCL_CONFIG (if any), then applies PCL_* environment overrides
+    pub fn load() -> Result<Self> {
+        let mut config = match std::env::var("PCL_CONFIG") {
+            Ok(path) => Self::from_file(path)?,
+            Err(_) => Self::default(),
+        };
+        config.apply_env_overrides(|key| std::env::var(key).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn apply_env_overrides<F: Fn(&str) -> Option<String>>(&mut self, lookup: F) -> Result<()> {
+        let consensus = &mut self.consensus;
+        for (key, field) in [
+            ("PCL_MIN_VALIDATION_COMPLETIONS", &mut consensus.min_validation_completions),
+            ("PCL_REQUIRED_LEADER_SIGNATURES", &mut consensus.required_leader_signatures),
+            ("PCL_BROADCAST_FANOUT", &mut consensus.broadcast_fanout),
+            ("PCL_CROSS_VALIDATION_QUORUM", &mut consensus.cross_validation_quorum),
+            ("PCL_CROSS_VALIDATORS_PER_TX", &mut consensus.cross_validators_per_tx),
+        ] {
+            if let Some(value) = lookup(key) {
+                *field = value.trim().parse()
+                    .map_err(|_| PclError::Config(format!("{} must be a positive integer, got '{}'", key, value)))?;
+            }
+        }
+        if let Some(dsn) = lookup("PCL_EXPORT_DSN") {
+            self.export.dsn = Some(dsn.trim().to_string()).filter(|dsn| !dsn.is_empty());
+        }
+        // Giving a certificate or asking for a self-signed one turns TLS on
+        if let Some(cert_path) = lookup("PCL_TLS_CERT") {
+            self.tls.cert_path = Some(cert_path);
+            self.tls.enabled = true;
+        }
+        if let Some(key_path) = lookup("PCL_TLS_KEY") {
+            self.tls.key_path = Some(key_path);
+            self.tls.enabled = true;
+        }
+        if let Some(value) = lookup("PCL_TLS_SELF_SIGNED") {
+            self.tls.self_signed = matches!(value.trim(), "1" | "true" | "yes");
+            self.tls.enabled |= self.tls.self_signed;
+        }
+        if let Some(value) = lookup("PCL_AUTH_ENABLED") {
+            self.auth.enabled = matches!(value.trim(), "1" | "true" | "yes");
+        }
+        if let Some(value) = lookup("PCL_BOOTNODES") {
+            self.network.bootnodes = split_list(&value);
+        }
+        if let Some(value) = lookup("PCL_ENABLE_MDNS") {
+            self.network.enable_mdns = matches!(value.trim(), "1" | "true" | "yes");
+        }
+        if let Some(value) = lookup("PCL_STATIC_PEERS") {
+            self.network.static_peers = split_list(&value);
+        }
+        if let Some(value) = lookup("PCL_PEER_BANDWIDTH_CAP") {
+            self.network.peer_bandwidth_cap_bytes_per_sec = value.trim().parse()
+                .map_err(|_| PclError::Config(format!("PCL_PEER_BANDWIDTH_CAP must be bytes per second, got '{}'", value)))?;
+        }
+        if let Some(value) = lookup("PCL_GOSSIP_MAX_HOPS") {
+            self.network.gossip_max_hops = value.trim().parse()
+                .map_err(|_| PclError::Config(format!("PCL_GOSSIP_MAX_HOPS must be a number of hops, got '{}'", value)))?;
+        }
+        if let Some(value) = lookup("PCL_DNS_SEEDS") {
+            self.network.dns_seeds = split_list(&value);
+        }
+        if let Some(value) = lookup("PCL_LOG_FORMAT") {
+            self.logging.format = value.trim().parse()?;
+        }
+        if let Some(path) = lookup("PCL_LOG_FILE") {
+            self.logging.file = Some(path.trim().to_string()).filter(|path| !path.is_empty());
+        }
+        if let Some(server) = lookup("PCL_NTP_SERVER") {
+            self.clock.ntp_server = server.trim().to_string();
+        }
+        if let Some(value) = lookup("PCL_ADMISSION_BLOCKLIST") {
+            self.admission.blocked_addresses = split_list(&value);
+        }
+        if let Some(key) = lookup("PCL_SCREENING_MAINTAINER_KEY") {
+            self.screening.maintainer_key = Some(key.trim().to_string()).filter(|key| !key.is_empty());
+        }
+        if let Some(url) = lookup("PCL_SCREENING_LIST_URL") {
+            self.screening.list_url = Some(url.trim().to_string()).filter(|url| !url.is_empty());
+        }
+        if let Some(value) = lookup("PCL_TENANTS") {
+            self.tenancy.tenants = split_list(&value);
+        }
+        if let Some(value) = lookup("PCL_RECONCILE_PEERS") {
+            self.reconcile.peers = split_list(&value);
+        }
+        if let Some(value) = lookup("PCL_ADMISSION_MAX_AMOUNT") {
+            self.admission.max_amount = Some(value.trim().parse()
+                .map_err(|_| PclError::Config(format!("PCL_ADMISSION_MAX_AMOUNT must be a number, got '{}'", value)))?);
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.consensus.validate()?;
+        self.fees.validate()?;
+        self.export.validate()?;
+        self.tls.validate()?;
+        self.auth.validate()?;
+        self.network.validate()?;
+        self.logging.validate()?;
+        self.clock.validate()?;
+        self.admission.validate()?;
+        self.screening.validate()?;
+        self.tenancy.validate()?;
+        self.reconcile.validate()
+    }
+}
+
+// Comma separated env values, e.g. PCL_BOOTNODES=a:9000,b:9000
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
@@ -1,6 +1,7 @@
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
+use hmac::{Hmac, Mac};
 use rand::{rngs::OsRng, RngCore};
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use crate::error::{PclError, Result};
@@ -52,6 +53,10 @@ impl NodeKeypair {
         Ok(signature)
     }
 
+    pub fn address(&self) -> String {
+        address_from_public_key(&self.public_key())
+    }
+
     pub fn sign_data(&self, data: &[u8]) -> Signature {
         let mut hasher = Sha256::new();
         hasher.update(data);
@@ -134,4 +139,161 @@ pub fn sign_data(keypair: &NodeKeypair, data: &[u8]) -> Signature {
 
 pub fn hash_data(data: &[u8]) -> Vec<u8> {
     hash_transaction_data(data)
-} 
\ No newline at end of file
+}
+
+// Addresses: base58check(version || first 20 bytes of sha256(payload))
+pub const ADDRESS_VERSION: u8 = 0x00;
+pub const MULTISIG_ADDRESS_VERSION: u8 = 0x05;
+const ADDRESS_PAYLOAD_LEN: usize = 20;
+const ADDRESS_CHECKSUM_LEN: usize = 4;
+
+pub fn address_from_public_key(public_key: &VerifyingKey) -> String {
+    encode_address(ADDRESS_VERSION, &public_key.to_bytes())
+}
+
+pub fn encode_address(version: u8, preimage: &[u8]) -> String {
+    let mut bytes = vec![version];
+    bytes.extend_from_slice(&hash_data(preimage)[..ADDRESS_PAYLOAD_LEN]);
+    let checksum = address_checksum(&bytes);
+    bytes.extend_from_slice(&checksum);
+    bs58::encode(bytes).into_string()
+}
+
+// Returns the version byte and 20 byte payload once the checksum has been verified
+pub fn decode_address(address: &str) -> Result<(u8, Vec<u8>)> {
+    let bytes = bs58::decode(address).into_vec()
+        .map_err(|e| PclError::InvalidAddress(format!("invalid base58 encoding: {}", e)))?;
+    if bytes.len() != 1 + ADDRESS_PAYLOAD_LEN + ADDRESS_CHECKSUM_LEN {
+        return Err(PclError::InvalidAddress(format!("decoded length {} is not {}", bytes.len(), 1 + ADDRESS_PAYLOAD_LEN + ADDRESS_CHECKSUM_LEN)));
+    }
+
+    let (body, checksum) = bytes.split_at(1 + ADDRESS_PAYLOAD_LEN);
+    if address_checksum(body) != checksum {
+        return Err(PclError::InvalidAddress("checksum mismatch".to_string()));
+    }
+
+    Ok((body[0], body[1..].to_vec()))
+}
+
+pub fn address_matches_public_key(address: &str, public_key: &VerifyingKey) -> bool {
+    address == address_from_public_key(public_key)
+}
+
+fn address_checksum(bytes: &[u8]) -> [u8; ADDRESS_CHECKSUM_LEN] {
+    let first = Sha256::digest(bytes);
+    let second = Sha256::digest(first);
+    let mut checksum = [0u8; ADDRESS_CHECKSUM_LEN];
+    checksum.copy_from_slice(&second[..ADDRESS_CHECKSUM_LEN]);
+    checksum
+}
+
+// Hierarchical deterministic keys (SLIP-0010 for ed25519: hardened derivation only)
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+pub const PCL_COIN_TYPE: u32 = 7337;
+
+#[derive(Debug, Clone)]
+pub struct ExtendedKeypair {
+    pub keypair: NodeKeypair,
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+}
+
+impl ExtendedKeypair {
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(PclError::NodeIdentity(format!("HD seed must be 16-64 bytes, got {}", seed.len())));
+        }
+        let (key, chain_code) = hmac_sha512(b"ed25519 seed", &[seed])?;
+        Ok(Self { keypair: NodeKeypair::from_bytes(&key)?, chain_code, depth: 0 })
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        let hardened = index | HARDENED_OFFSET;
+        let (key, chain_code) = hmac_sha512(
+            &self.chain_code,
+            &[&[0u8], &self.keypair.signing_key.to_bytes(), &hardened.to_be_bytes()],
+        )?;
+        Ok(Self { keypair: NodeKeypair::from_bytes(&key)?, chain_code, depth: self.depth.saturating_add(1) })
+    }
+
+    // Paths look like m/44'/7337'/0'/0'/5'; every level is hardened, so the ' is optional
+    pub fn derive_path(&self, path: &str) -> Result<Self> {
+        let mut segments = path.split('/');
+        if segments.next() != Some("m") {
+            return Err(PclError::NodeIdentity(format!("Derivation path must start with m/: {}", path)));
+        }
+
+        let mut node = self.clone();
+        for segment in segments {
+            let index: u32 = segment.trim_end_matches('\'').parse()
+                .map_err(|_| PclError::NodeIdentity(format!("Invalid path segment '{}' in {}", segment, path)))?;
+            if index >= HARDENED_OFFSET {
+                return Err(PclError::NodeIdentity(format!("Path index out of range: {}", index)));
+            }
+            node = node.derive_child(index)?;
+        }
+        Ok(node)
+    }
+
+    pub fn address(&self) -> String {
+        self.keypair.address()
+    }
+}
+
+pub fn address_derivation_path(account: u32, index: u32) -> String {
+    format!("m/44'/{}'/{}'/0'/{}'", PCL_COIN_TYPE, account, index)
+}
+
+// Derives the address at `index` of `account` for a seed: seed -> many addresses
+pub fn derive_address(seed: &[u8], account: u32, index: u32) -> Result<(String, NodeKeypair)> {
+    let node = ExtendedKeypair::from_seed(seed)?.derive_path(&address_derivation_path(account, index))?;
+    Ok((node.address(), node.keypair))
+}
+
+// The node identity lives on its own branch so it never collides with a wallet address
+pub fn node_identity_derivation_path() -> String {
+    format!("m/44'/{}'/0'/1'/0'", PCL_COIN_TYPE)
+}
+
+// BIP39 backup phrases: 24 words encode 32 bytes of entropy plus a checksum
+pub const MNEMONIC_WORDS: usize = 24;
+
+pub fn generate_mnemonic() -> String {
+    let mut entropy = [0u8; 32];
+    OsRng.fill_bytes(&mut entropy);
+    bip39::Mnemonic::from_entropy(&entropy)
+        .expect("32 bytes is a valid BIP39 entropy length")
+        .to_string()
+}
+
+// The 64 byte BIP39 seed; the passphrase is the optional "25th word", empty for none
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64]> {
+    let mnemonic = bip39::Mnemonic::parse(phrase)
+        .map_err(|e| PclError::NodeIdentity(format!("Invalid mnemonic: {}", e)))?;
+    if mnemonic.word_count() != MNEMONIC_WORDS {
+        return Err(PclError::NodeIdentity(format!(
+            "Mnemonic has {} words, expected {}", mnemonic.word_count(), MNEMONIC_WORDS
+        )));
+    }
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+pub fn identity_from_mnemonic(phrase: &str, passphrase: &str) -> Result<NodeKeypair> {
+    let seed = mnemonic_to_seed(phrase, passphrase)?;
+    Ok(ExtendedKeypair::from_seed(&seed)?.derive_path(&node_identity_derivation_path())?.keypair)
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> Result<([u8; 32], [u8; 32])> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key)
+        .map_err(|e| PclError::NodeIdentity(format!("HMAC key error: {}", e)))?;
+    for part in parts {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    Ok((left, right))
+}
@@ -0,0 +1,140 @@
+// Cubic DLT module - placing finalized transactions in the XMBL cubic geometry
+//
+// The cube fills one layer at a time, nine facets to a layer, and a transaction's digital root picks
+// its home facet (roots are taken mod 9, so 9 and 0 share one). Two transactions with the same root
+// compete for the same slot: the one ordered first by averaged timestamp, then tx id, keeps it and
+// the other chains into the next free facet of the layer. Placement depends only on the set of
+// finalized transactions, not the order a node heard about them, so every node agrees on it.
+
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+
+pub const FACETS_PER_LAYER: u64 = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CubicPlacement {
+    pub tx_id: String,
+    pub digital_root: u32,
+    pub timestamp: u64, // averaged timestamp, which orders competing transactions
+    pub layer: u64,
+    pub facet: u64,
+    pub home_facet: u64,
+    pub position: u64, // layer * FACETS_PER_LAYER + facet
+    pub chained: u64,  // occupied facets passed over before finding a free one
+}
+
+impl CubicPlacement {
+    pub fn collided(&self) -> bool {
+        self.chained > 0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CubicGeometry {
+    order: BTreeMap<(u64, String), u32>, // (timestamp, tx_id) -> digital root
+    placements: HashMap<String, CubicPlacement>,
+    slots: HashMap<u64, String>, // position -> tx_id
+}
+
+pub fn home_facet(digital_root: u32) -> u64 {
+    digital_root as u64 % FACETS_PER_LAYER
+}
+
+impl CubicGeometry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Rebuilds the geometry from (tx_id, digital_root, timestamp) of every finalized transaction
+    pub fn restore<'a>(&mut self, transactions: impl IntoIterator<Item = (&'a str, u32, u64)>) {
+        self.order = transactions.into_iter()
+            .map(|(tx_id, digital_root, timestamp)| ((timestamp, tx_id.to_string()), digital_root))
+            .collect();
+        self.replace_all();
+    }
+
+    // Places a finalized transaction, or moves it if its timestamp or root changed. Transactions
+    // ordered after it may move too when it lands before them.
+    pub fn place(&mut self, tx_id: &str, digital_root: u32, timestamp: u64) -> CubicPlacement {
+        if let Some(existing) = self.placements.get(tx_id) {
+            if existing.digital_root == digital_root && existing.timestamp == timestamp {
+                return existing.clone();
+            }
+            let key = (existing.timestamp, tx_id.to_string());
+            self.order.remove(&key);
+            self.order.insert((timestamp, tx_id.to_string()), digital_root);
+            self.replace_all();
+            return self.placements[tx_id].clone();
+        }
+
+        let key = (timestamp, tx_id.to_string());
+        let in_order = self.order.last_key_value().is_none_or(|(last, _)| *last < key);
+        self.order.insert(key, digital_root);
+        if in_order {
+            self.append(tx_id, digital_root, timestamp)
+        } else {
+            self.replace_all();
+            self.placements[tx_id].clone()
+        }
+    }
+
+    pub fn placement(&self, tx_id: &str) -> Option<&CubicPlacement> {
+        self.placements.get(tx_id)
+    }
+
+    pub fn occupant(&self, layer: u64, facet: u64) -> Option<&str> {
+        self.slots.get(&(layer * FACETS_PER_LAYER + facet)).map(String::as_str)
+    }
+
+    // Every placement, by position
+    pub fn placements(&self) -> Vec<&CubicPlacement> {
+        let mut placements: Vec<&CubicPlacement> = self.placements.values().collect();
+        placements.sort_by_key(|placement| placement.position);
+        placements
+    }
+
+    pub fn collisions(&self) -> usize {
+        self.placements.values().filter(|placement| placement.collided()).count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.placements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+
+    fn replace_all(&mut self) {
+        self.placements.clear();
+        self.slots.clear();
+        let order = std::mem::take(&mut self.order);
+        for ((timestamp, tx_id), digital_root) in &order {
+            self.append(tx_id, *digital_root, *timestamp);
+        }
+        self.order = order;
+    }
+
+    // Lower layers are always full, so the open layer is the one the next slot falls in
+    fn append(&mut self, tx_id: &str, digital_root: u32, timestamp: u64) -> CubicPlacement {
+        let layer = self.slots.len() as u64 / FACETS_PER_LAYER;
+        let home = home_facet(digital_root);
+        let chained = (0..FACETS_PER_LAYER)
+            .find(|hop| !self.slots.contains_key(&(layer * FACETS_PER_LAYER + (home + hop) % FACETS_PER_LAYER)))
+            .expect("the open layer has a free facet");
+        let facet = (home + chained) % FACETS_PER_LAYER;
+        let placement = CubicPlacement {
+            tx_id: tx_id.to_string(),
+            digital_root,
+            timestamp,
+            layer,
+            facet,
+            home_facet: home,
+            position: layer * FACETS_PER_LAYER + facet,
+            chained,
+        };
+        self.slots.insert(placement.position, tx_id.to_string());
+        self.placements.insert(tx_id.to_string(), placement.clone());
+        placement
+    }
+}
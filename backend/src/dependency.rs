@@ -0,0 +1,111 @@
+// Dependency module - which unconfirmed transactions spend the outputs of which others
+//
+// An output is named "{tx_id}:{suffix}", so an input whose prefix is a transaction still in the raw
+// or processing mempool spends a pending output, and the spender depends on that transaction
+// (child-pays-for-parent). The graph is rebuilt from the mempools whenever it is needed rather than
+// kept in step with them. Besides answering which parents a transaction waits on and which children
+// wait on it, it gives the full set of descendants, so invalidating a parent takes its children with it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use serde::{Deserialize, Serialize};
+use crate::client::MempoolStage;
+
+// The transaction whose output a UTXO id names
+pub fn output_parent(utxo_id: &str) -> Option<&str> {
+    utxo_id.rsplit_once(':').map(|(tx_id, _)| tx_id).filter(|tx_id| !tx_id.is_empty())
+}
+
+// A raw or processing transaction and the UTXOs it spends
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTransaction {
+    pub tx_id: String,
+    pub stage: MempoolStage,
+    pub inputs: Vec<String>,
+}
+
+// One edge as the API returns it: the transaction at the other end and the UTXOs that link them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionDependency {
+    pub tx_id: String,
+    pub stage: MempoolStage,
+    pub utxo_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    stages: HashMap<String, MempoolStage>,
+    parents: BTreeMap<String, BTreeMap<String, Vec<String>>>,  // child -> parent -> utxo ids
+    children: BTreeMap<String, BTreeMap<String, Vec<String>>>, // parent -> child -> utxo ids
+}
+
+impl DependencyGraph {
+    // A transaction listed more than once (held by several leaders, or in two stages) keeps its
+    // first entry
+    pub fn build(pending: impl IntoIterator<Item = PendingTransaction>) -> Self {
+        let mut graph = Self::default();
+        let mut unique = Vec::new();
+        for tx in pending {
+            if !graph.stages.contains_key(&tx.tx_id) {
+                graph.stages.insert(tx.tx_id.clone(), tx.stage);
+                unique.push(tx);
+            }
+        }
+        for tx in &unique {
+            for utxo_id in &tx.inputs {
+                let Some(parent) = output_parent(utxo_id).filter(|parent| *parent != tx.tx_id && graph.stages.contains_key(*parent)) else {
+                    continue;
+                };
+                graph.parents.entry(tx.tx_id.clone()).or_default()
+                    .entry(parent.to_string()).or_default().push(utxo_id.clone());
+                graph.children.entry(parent.to_string()).or_default()
+                    .entry(tx.tx_id.clone()).or_default().push(utxo_id.clone());
+            }
+        }
+        graph
+    }
+
+    pub fn contains(&self, tx_id: &str) -> bool {
+        self.stages.contains_key(tx_id)
+    }
+
+    pub fn stage(&self, tx_id: &str) -> Option<MempoolStage> {
+        self.stages.get(tx_id).copied()
+    }
+
+    // Pending transactions whose outputs tx_id spends
+    pub fn dependencies(&self, tx_id: &str) -> Vec<TransactionDependency> {
+        self.edges(self.parents.get(tx_id))
+    }
+
+    // Pending transactions spending outputs of tx_id
+    pub fn dependents(&self, tx_id: &str) -> Vec<TransactionDependency> {
+        self.edges(self.children.get(tx_id))
+    }
+
+    // Every transaction that depends on tx_id directly or through others, parents before their children
+    pub fn descendants(&self, tx_id: &str) -> Vec<String> {
+        let mut seen = BTreeSet::new();
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([tx_id.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.children.get(&current).into_iter().flat_map(|children| children.keys()) {
+                if child != tx_id && seen.insert(child.clone()) {
+                    order.push(child.clone());
+                    queue.push_back(child.clone());
+                }
+            }
+        }
+        order
+    }
+
+    fn edges(&self, edges: Option<&BTreeMap<String, Vec<String>>>) -> Vec<TransactionDependency> {
+        edges.into_iter()
+            .flatten()
+            .filter_map(|(tx_id, utxo_ids)| Some(TransactionDependency {
+                tx_id: tx_id.clone(),
+                stage: self.stage(tx_id)?,
+                utxo_ids: utxo_ids.clone(),
+            }))
+            .collect()
+    }
+}
@@ -0,0 +1,99 @@
+// Discovery module - static peers and DNS seeds for networks where mDNS finds nobody
+//
+// Local discovery relied on mDNS, which most container networks do not carry, so a node in a pod
+// or compose network never found its neighbours. mDNS can now be turned off and peers named up
+// front instead: static peers as multiaddrs, and DNS seeds whose records are resolved at startup
+// and again periodically, so a headless service or round-robin name can follow peers as they move.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use crate::config::NetworkConfig;
+use crate::error::{PclError, Result};
+use crate::network::NetworkManager;
+
+// The host:port a multiaddr dials: /ip4, /ip6 or /dns{,4,6} followed by /tcp/<port>, with an
+// optional trailing /p2p/<peer id>
+pub fn parse_multiaddr(multiaddr: &str) -> Result<String> {
+    let invalid = || PclError::Config(format!("static peer must be a multiaddr like /ip4/10.0.0.1/tcp/9000, got '{}'", multiaddr));
+    let parts: Vec<&str> = multiaddr.strip_prefix('/').ok_or_else(invalid)?.split('/').collect();
+    let (protocol, host, transport, port) = match parts.as_slice() {
+        [protocol, host, transport, port] | [protocol, host, transport, port, "p2p", _] => (*protocol, *host, *transport, *port),
+        _ => return Err(invalid()),
+    };
+    let port = port.parse::<u16>().ok().filter(|port| *port > 0).ok_or_else(invalid)?;
+    if transport != "tcp" || host.is_empty() {
+        return Err(invalid());
+    }
+    match protocol {
+        "ip4" if host.parse::<std::net::Ipv4Addr>().is_ok() => Ok(format!("{}:{}", host, port)),
+        "ip6" if host.parse::<std::net::Ipv6Addr>().is_ok() => Ok(format!("[{}]:{}", host, port)),
+        "dns" | "dns4" | "dns6" => Ok(format!("{}:{}", host, port)),
+        _ => Err(invalid()),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PeerDiscovery {
+    enable_mdns: bool,
+    static_peers: Vec<String>, // host:port of each configured multiaddr
+    dns_seeds: Vec<String>,    // host:port
+    resolved: BTreeMap<String, BTreeSet<String>>, // seed -> addresses from its last successful lookup
+}
+
+impl PeerDiscovery {
+    pub fn new(config: &NetworkConfig) -> Result<Self> {
+        Ok(Self {
+            enable_mdns: config.enable_mdns,
+            static_peers: config.static_peers.iter().map(|peer| parse_multiaddr(peer)).collect::<Result<_>>()?,
+            dns_seeds: config.dns_seeds.clone(),
+            resolved: BTreeMap::new(),
+        })
+    }
+
+    pub fn mdns_enabled(&self) -> bool {
+        self.enable_mdns
+    }
+
+    pub fn static_peers(&self) -> &[String] {
+        &self.static_peers
+    }
+
+    // Addresses from the last successful lookup of every seed
+    pub fn seed_addresses(&self) -> Vec<String> {
+        self.resolved.values().flatten().cloned().collect()
+    }
+
+    // Looks every seed up again. A seed that fails to resolve keeps its previous addresses, so a
+    // DNS outage does not forget peers. Returns how many addresses changed.
+    pub async fn resolve_seeds(&mut self) -> usize {
+        let mut changed = 0;
+        for seed in &self.dns_seeds {
+            match tokio::net::lookup_host(seed.as_str()).await {
+                Ok(addresses) => {
+                    let addresses: BTreeSet<String> = addresses.map(|address| address.to_string()).collect();
+                    let previous = self.resolved.insert(seed.clone(), addresses.clone()).unwrap_or_default();
+                    changed += addresses.symmetric_difference(&previous).count();
+                }
+                Err(e) => log::warn!("Could not resolve DNS seed {}: {}", seed, e),
+            }
+        }
+        changed
+    }
+
+    // Re-resolves the seeds and dials every static peer and seed address not already connected.
+    // Returns the addresses that connected.
+    pub async fn dial(&mut self, network: &mut NetworkManager) -> Result<Vec<String>> {
+        self.resolve_seeds().await;
+        let connected: HashSet<String> = network.peers.read().await.values().map(|peer| peer.multiaddr.clone()).collect();
+        let candidates: BTreeSet<String> = self.static_peers.iter().cloned().chain(self.seed_addresses())
+            .filter(|address| !connected.contains(address))
+            .collect();
+        let mut dialed = Vec::new();
+        for address in candidates {
+            match network.connect_to_peer(&address).await {
+                Ok(()) => dialed.push(address),
+                Err(e) => log::warn!("Could not dial {}: {}", address, e),
+            }
+        }
+        Ok(dialed)
+    }
+}
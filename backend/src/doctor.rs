@@ -0,0 +1,250 @@
+// Doctor module - preflight checks an operator can run before starting a node
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use crate::config::NodeConfig;
+use crate::error::{PclError, Result};
+use crate::storage::StorageManager;
+
+pub const DEFAULT_NTP_SERVER: &str = "pool.ntp.org:123";
+// Validation timestamps are averaged across nodes, so skew shows up directly in finalized times
+pub const MAX_CLOCK_SKEW_WARN_MS: i64 = 500;
+pub const MAX_CLOCK_SKEW_FAIL_MS: i64 = 5_000;
+pub const MIN_OPEN_FILES: u64 = 4_096; // RocksDB keeps many SST files open
+pub const MIN_FREE_DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+pub const MIN_FREE_DISK_FAIL_BYTES: u64 = 100 * 1024 * 1024;
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(3);
+// Seconds between the NTP epoch (1900) and the Unix epoch (1970)
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip, // not applicable on this node or platform
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus, detail: String) -> Self {
+        Self { name: name.to_string(), status, detail }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    // Warnings do not block startup, failures do
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.status != CheckStatus::Fail)
+    }
+
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+}
+
+pub struct DoctorOptions<'a> {
+    pub data_dir: &'a Path,
+    pub api_port: u16,
+    pub ntp_server: &'a str,
+    pub config: &'a NodeConfig,
+}
+
+pub async fn run_checks(options: &DoctorOptions<'_>) -> DoctorReport {
+    let mut checks = vec![
+        check_storage(options.data_dir),
+        check_keystore(),
+        check_port(options.api_port).await,
+    ];
+    if options.config.tls.enabled {
+        checks.push(check_tls_files(options.config));
+    }
+    checks.push(check_clock_skew(options.ntp_server).await);
+    checks.push(check_open_files_limit());
+    checks.push(check_disk_space(options.data_dir));
+    checks.extend(check_bootnodes(&options.config.network.bootnodes).await);
+    DoctorReport { checks }
+}
+
+pub fn check_storage(data_dir: &Path) -> CheckResult {
+    let result = StorageManager::new(data_dir).and_then(|storage| storage.write_probe());
+    match result {
+        Ok(()) => CheckResult::new("storage", CheckStatus::Pass, format!("RocksDB at {:?} opened and accepted a write", data_dir)),
+        Err(e) => CheckResult::new("storage", CheckStatus::Fail, format!("RocksDB at {:?}: {} (is another node running on it?)", data_dir, e)),
+    }
+}
+
+// Node identity keys are generated in memory at startup, so there is nothing on disk to decrypt yet
+pub fn check_keystore() -> CheckResult {
+    CheckResult::new("keystore", CheckStatus::Skip, "No persistent keystore; identity keys are generated at startup".to_string())
+}
+
+fn check_tls_files(config: &NodeConfig) -> CheckResult {
+    let missing: Vec<String> = [config.tls.cert_path(), config.tls.key_path()].into_iter()
+        .filter(|path| !path.exists())
+        .map(|path| format!("{:?}", path))
+        .collect();
+    if missing.is_empty() {
+        CheckResult::new("tls", CheckStatus::Pass, "Certificate and key present".to_string())
+    } else if config.tls.self_signed {
+        CheckResult::new("tls", CheckStatus::Warn, format!("{} missing, a self-signed pair will be generated", missing.join(", ")))
+    } else {
+        CheckResult::new("tls", CheckStatus::Fail, format!("{} missing", missing.join(", ")))
+    }
+}
+
+pub async fn check_port(port: u16) -> CheckResult {
+    let name = format!("port {}", port);
+    match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(_) => CheckResult::new(&name, CheckStatus::Pass, "Bindable".to_string()),
+        Err(e) => CheckResult::new(&name, CheckStatus::Fail, format!("Cannot bind: {}", e)),
+    }
+}
+
+pub async fn check_clock_skew(ntp_server: &str) -> CheckResult {
+    match query_clock_offset_ms(ntp_server).await {
+        Ok(offset) if offset.abs() > MAX_CLOCK_SKEW_FAIL_MS => {
+            CheckResult::new("clock", CheckStatus::Fail, format!("Clock is {}ms off {}", offset, ntp_server))
+        }
+        Ok(offset) if offset.abs() > MAX_CLOCK_SKEW_WARN_MS => {
+            CheckResult::new("clock", CheckStatus::Warn, format!("Clock is {}ms off {}", offset, ntp_server))
+        }
+        Ok(offset) => CheckResult::new("clock", CheckStatus::Pass, format!("Within {}ms of {}", offset.abs(), ntp_server)),
+        // No NTP reachable is not proof the clock is wrong
+        Err(e) => CheckResult::new("clock", CheckStatus::Warn, format!("Could not measure skew against {}: {}", ntp_server, e)),
+    }
+}
+
+// One SNTP (RFC 4330) exchange. Returns how far the local clock is behind (+) or ahead (-) in ms.
+pub async fn query_clock_offset_ms(ntp_server: &str) -> Result<i64> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    let mut request = [0u8; 48];
+    request[0] = 0x23; // LI 0, version 4, mode 3 (client)
+
+    let exchange = async {
+        socket.connect(ntp_server).await?;
+        let sent_at = unix_millis();
+        socket.send(&request).await?;
+        let mut response = [0u8; 48];
+        let len = socket.recv(&mut response).await?;
+        Ok::<_, std::io::Error>((sent_at, response, len, unix_millis()))
+    };
+    let (sent_at, response, len, received_at) = tokio::time::timeout(NETWORK_TIMEOUT, exchange).await
+        .map_err(|_| PclError::Network("NTP request timed out".to_string()))??;
+    if len < 48 {
+        return Err(PclError::Network(format!("Short NTP response of {} bytes", len)));
+    }
+    sntp_offset_ms(&response, sent_at, received_at)
+}
+
+// offset = ((server receive - client send) + (server transmit - client receive)) / 2
+pub fn sntp_offset_ms(response: &[u8; 48], sent_at_ms: i64, received_at_ms: i64) -> Result<i64> {
+    let server_received = ntp_timestamp_ms(&response[32..40])?;
+    let server_sent = ntp_timestamp_ms(&response[40..48])?;
+    Ok(((server_received - sent_at_ms) + (server_sent - received_at_ms)) / 2)
+}
+
+fn ntp_timestamp_ms(bytes: &[u8]) -> Result<i64> {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    if seconds < NTP_UNIX_OFFSET_SECS {
+        return Err(PclError::Network("NTP server sent an unset timestamp".to_string()));
+    }
+    Ok(((seconds - NTP_UNIX_OFFSET_SECS) * 1000 + ((fraction * 1000) >> 32)) as i64)
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as i64
+}
+
+#[cfg(unix)]
+pub fn check_open_files_limit() -> CheckResult {
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // getrlimit only writes into the struct we pass
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+        return CheckResult::new("open files", CheckStatus::Warn, format!("getrlimit failed: {}", std::io::Error::last_os_error()));
+    }
+    let current = limit.rlim_cur;
+    if current < MIN_OPEN_FILES {
+        CheckResult::new("open files", CheckStatus::Warn, format!(
+            "Soft limit {} is below {}; raise it with ulimit -n (hard limit {})", current, MIN_OPEN_FILES, limit.rlim_max
+        ))
+    } else {
+        CheckResult::new("open files", CheckStatus::Pass, format!("Soft limit {}", current))
+    }
+}
+
+#[cfg(not(unix))]
+pub fn check_open_files_limit() -> CheckResult {
+    CheckResult::new("open files", CheckStatus::Skip, "Not checked on this platform".to_string())
+}
+
+#[cfg(unix)]
+pub fn check_disk_space(data_dir: &Path) -> CheckResult {
+    use std::os::unix::ffi::OsStrExt;
+
+    // The data dir may not exist yet on a first run; measure the nearest existing ancestor
+    let Some(existing) = data_dir.ancestors().find(|path| path.exists()) else {
+        return CheckResult::new("disk", CheckStatus::Warn, format!("No existing parent directory for {:?}", data_dir));
+    };
+    let Ok(path) = std::ffi::CString::new(existing.as_os_str().as_bytes()) else {
+        return CheckResult::new("disk", CheckStatus::Warn, format!("Path {:?} contains a NUL byte", existing));
+    };
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // statvfs reads the NUL-terminated path and only writes into the struct we pass
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return CheckResult::new("disk", CheckStatus::Warn, format!("statvfs failed: {}", std::io::Error::last_os_error()));
+    }
+    let free = stats.f_bavail as u64 * stats.f_frsize as u64;
+    let detail = format!("{} MiB free at {:?}", free / (1024 * 1024), existing);
+    if free < MIN_FREE_DISK_FAIL_BYTES {
+        CheckResult::new("disk", CheckStatus::Fail, detail)
+    } else if free < MIN_FREE_DISK_WARN_BYTES {
+        CheckResult::new("disk", CheckStatus::Warn, detail)
+    } else {
+        CheckResult::new("disk", CheckStatus::Pass, detail)
+    }
+}
+
+#[cfg(not(unix))]
+pub fn check_disk_space(_data_dir: &Path) -> CheckResult {
+    CheckResult::new("disk", CheckStatus::Skip, "Not checked on this platform".to_string())
+}
+
+// One result per bootnode; a node with none configured only gets a skip
+pub async fn check_bootnodes(bootnodes: &[String]) -> Vec<CheckResult> {
+    if bootnodes.is_empty() {
+        return vec![CheckResult::new("bootnodes", CheckStatus::Skip, "None configured".to_string())];
+    }
+
+    let mut results = Vec::new();
+    for bootnode in bootnodes {
+        let name = format!("bootnode {}", bootnode);
+        let started = std::time::Instant::now();
+        let result = match tokio::time::timeout(NETWORK_TIMEOUT, TcpStream::connect(bootnode.as_str())).await {
+            Ok(Ok(stream)) => {
+                let peer = stream.peer_addr().map(|addr: SocketAddr| addr.to_string()).unwrap_or_default();
+                CheckResult::new(&name, CheckStatus::Pass, format!("Connected to {} in {}ms", peer, started.elapsed().as_millis()))
+            }
+            Ok(Err(e)) => CheckResult::new(&name, CheckStatus::Fail, format!("Connection failed: {}", e)),
+            Err(_) => CheckResult::new(&name, CheckStatus::Fail, format!("No answer within {}s", NETWORK_TIMEOUT.as_secs())),
+        };
+        results.push(result);
+    }
+    results
+}
@@ -0,0 +1,115 @@
+// Embed module - running a consensus node inside another application
+//
+// ConsensusManager is driven by the network loop: peer messages arrive as NetworkMessages and are
+// dispatched to its handlers. An application hosting a node in-process, such as a game server
+// settling payments or an exchange crediting deposits, only wants to submit its own transactions
+// and follow them. EmbeddedNode wraps a manager with that surface: submit a transaction, query its
+// status, subscribe to its events with the same filters as /events, and read the current leaders
+// and the node's metrics. The manager stays reachable for anything the wrapper does not cover.
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+use crate::client::MempoolStage;
+use crate::config::ConsensusConfig;
+use crate::consensus::{ConsensusManager, SystemStatus};
+use crate::crypto::NodeKeypair;
+use crate::error::{PclError, Result};
+use crate::events::{EventFilter, StreamEvent};
+use crate::network::NetworkManager;
+use crate::node::Node;
+use crate::storage::StorageManager;
+use crate::transaction::{RawTransaction, TransactionData};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionStatus {
+    pub tx_id: String,
+    pub stages: Vec<MempoolStage>, // mempools holding the transaction right now
+    pub workflow_step: Option<u8>, // 1 to 6 while its workflow is running
+    pub finalized: bool,
+}
+
+// Events of one subscriber, already filtered
+pub struct EventSubscription {
+    receiver: broadcast::Receiver<StreamEvent>,
+    filter: EventFilter,
+}
+
+impl EventSubscription {
+    // The next matching event, or None once the node is gone. A subscriber that falls more than
+    // EVENT_CHANNEL_CAPACITY events behind loses the oldest ones.
+    pub async fn next(&mut self) -> Option<StreamEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => log::warn!("Embedded event subscriber lagged, {} events dropped", skipped),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmbeddedNode {
+    consensus: ConsensusManager,
+}
+
+impl EmbeddedNode {
+    pub fn new(consensus: ConsensusManager) -> Self {
+        Self { consensus }
+    }
+
+    // Opens the node's database in data_dir and builds its network manager; keypair is the node's identity key
+    pub async fn open(local_node: Node, keypair: &NodeKeypair, data_dir: &Path, config: ConsensusConfig) -> Result<Self> {
+        let network = NetworkManager::new(local_node.clone()).await?;
+        let storage = StorageManager::new(data_dir)?;
+        Ok(Self::new(ConsensusManager::with_config(local_node, keypair, network, storage, config)?))
+    }
+
+    // Starts the background loops (pulses, elections, gossip); submissions work without them
+    pub async fn start(&self) -> Result<()> {
+        self.consensus.start().await
+    }
+
+    pub async fn shutdown(&self) -> Result<()> {
+        self.consensus.shutdown().await
+    }
+
+    pub fn consensus(&self) -> &ConsensusManager {
+        &self.consensus
+    }
+
+    // Runs the transaction through the workflow and returns its raw_tx_id once it is final
+    pub async fn submit_transaction(&self, tx_data: TransactionData) -> Result<String> {
+        let raw_tx_id = tx_data.raw_tx_id().map_err(PclError::Transaction)?;
+        self.consensus.process_transaction_workflow(RawTransaction::new(raw_tx_id.clone(), tx_data)).await?;
+        Ok(raw_tx_id)
+    }
+
+    pub async fn query_status(&self, tx_id: &str) -> Result<TransactionStatus> {
+        let stages = self.consensus.mempool.stages_of(tx_id).await;
+        let workflow_step = self.consensus.consensus_state.read().await
+            .active_transactions.get(tx_id)
+            .map(|workflow| workflow.current_step);
+        let finalized = stages.contains(&MempoolStage::Final)
+            || self.consensus.storage_manager.load_finalized_transaction(tx_id)?.is_some();
+        if stages.is_empty() && workflow_step.is_none() && !finalized {
+            return Err(PclError::Transaction(format!("Unknown transaction {}", tx_id)));
+        }
+        Ok(TransactionStatus { tx_id: tx_id.to_string(), stages, workflow_step, finalized })
+    }
+
+    // Events from submission on; nothing earlier is replayed
+    pub fn subscribe_events(&self, filter: EventFilter) -> EventSubscription {
+        EventSubscription { receiver: self.consensus.events.subscribe(), filter }
+    }
+
+    pub async fn current_leaders(&self) -> Vec<String> {
+        self.consensus.leader_election.read().await.current_leaders.clone()
+    }
+
+    pub async fn metrics(&self) -> Result<SystemStatus> {
+        self.consensus.get_system_status().await
+    }
+}
@@ -0,0 +1,92 @@
+// Envelope module - encrypted direct messages between two ed25519 identities
+//
+// Both sides convert their ed25519 identity keys to X25519 and run ECDH, so no extra key exchange is
+// needed: the sender's key is fixed, which also authenticates it. The shared secret is hashed with both
+// public keys into a ChaCha20-Poly1305 key, and the keys are bound as associated data so an envelope
+// cannot be replayed as if it came from, or was meant for, someone else.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::VerifyingKey;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::crypto::NodeKeypair;
+use crate::error::{PclError, Result};
+use crate::multisig::decode_public_key;
+
+const KEY_DOMAIN: &[u8] = b"pcl-direct-message-v1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedEnvelope {
+    pub sender_public_key: String,    // hex encoded ed25519 key
+    pub recipient_public_key: String, // hex encoded ed25519 key
+    pub nonce: String,                // hex encoded, random per envelope
+    pub ciphertext: String,           // hex encoded, includes the Poly1305 tag
+}
+
+impl EncryptedEnvelope {
+    pub fn seal(sender: &NodeKeypair, recipient: &VerifyingKey, plaintext: &[u8]) -> Result<Self> {
+        let sender_public = sender.public_key();
+        let cipher = cipher(sender, recipient, &sender_public, recipient)?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &associated_data(&sender_public, recipient) })
+            .map_err(|_| PclError::Encryption("Failed to encrypt direct message".to_string()))?;
+
+        Ok(Self {
+            sender_public_key: hex::encode(sender_public.to_bytes()),
+            recipient_public_key: hex::encode(recipient.to_bytes()),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    // Only the recipient's key opens the envelope; any change to it fails authentication
+    pub fn open(&self, recipient: &NodeKeypair) -> Result<Vec<u8>> {
+        let recipient_public = recipient.public_key();
+        if hex::encode(recipient_public.to_bytes()) != self.recipient_public_key {
+            return Err(PclError::Encryption("Direct message is addressed to another key".to_string()));
+        }
+        let sender_public = self.sender()?;
+        let cipher = cipher(recipient, &sender_public, &sender_public, &recipient_public)?;
+
+        let nonce = hex::decode(&self.nonce)
+            .ok()
+            .filter(|nonce| nonce.len() == NONCE_LEN)
+            .ok_or_else(|| PclError::Encryption("Invalid direct message nonce".to_string()))?;
+        let ciphertext = hex::decode(&self.ciphertext)
+            .map_err(|e| PclError::Encryption(format!("Invalid direct message ciphertext: {}", e)))?;
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &associated_data(&sender_public, &recipient_public) })
+            .map_err(|_| PclError::Encryption("Direct message failed authentication".to_string()))
+    }
+
+    pub fn sender(&self) -> Result<VerifyingKey> {
+        decode_public_key(&self.sender_public_key)
+    }
+}
+
+// ECDH between our key and the peer's, hashed with both public keys in sender, recipient order
+fn cipher(own: &NodeKeypair, peer: &VerifyingKey, sender: &VerifyingKey, recipient: &VerifyingKey) -> Result<ChaCha20Poly1305> {
+    let secret = x25519_dalek::StaticSecret::from(own.signing_key.to_scalar_bytes());
+    let peer_public = x25519_dalek::PublicKey::from(peer.to_montgomery().to_bytes());
+    let shared = secret.diffie_hellman(&peer_public);
+    if !shared.was_contributory() {
+        return Err(PclError::Encryption("Peer public key is a low-order point".to_string()));
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DOMAIN);
+    hasher.update(shared.as_bytes());
+    hasher.update(sender.to_bytes());
+    hasher.update(recipient.to_bytes());
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&hasher.finalize())))
+}
+
+fn associated_data(sender: &VerifyingKey, recipient: &VerifyingKey) -> Vec<u8> {
+    [sender.to_bytes(), recipient.to_bytes()].concat()
+}
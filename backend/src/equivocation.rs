@@ -0,0 +1,174 @@
+// Equivocation module - evidence that a leader signed conflicting processing entries
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use crate::error::{PclError, Result};
+use crate::mempool::FinalizationClaim;
+use crate::multisig::decode_public_key;
+use crate::transaction::ProcessingTransaction;
+
+// Two entries for the same raw_tx_id, both validly signed by the same leader, with different content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationEvidence {
+    pub raw_tx_id: String,
+    pub leader_id: String,
+    pub leader_public_key: String, // hex encoded
+    pub first: ProcessingTransaction,
+    pub second: ProcessingTransaction,
+    pub reporter_id: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl EquivocationEvidence {
+    pub fn new(first: ProcessingTransaction, second: ProcessingTransaction, leader_public_key: &VerifyingKey, reporter_id: &str) -> Result<Self> {
+        let evidence = Self {
+            raw_tx_id: first.tx_id.clone(),
+            leader_id: first.leader.clone(),
+            leader_public_key: hex::encode(leader_public_key.to_bytes()),
+            first,
+            second,
+            reporter_id: reporter_id.to_string(),
+            detected_at: Utc::now(),
+        };
+        evidence.verify()?;
+        Ok(evidence)
+    }
+
+    // Anyone can check the evidence without trusting the reporter
+    pub fn verify(&self) -> Result<()> {
+        if self.first.tx_id != self.raw_tx_id || self.second.tx_id != self.raw_tx_id {
+            return Err(PclError::Consensus("Evidence entries are for different transactions".to_string()));
+        }
+        if self.first.leader != self.leader_id || self.second.leader != self.leader_id {
+            return Err(PclError::Consensus("Evidence entries are from different leaders".to_string()));
+        }
+
+        let first_bytes = self.first.signing_bytes().map_err(PclError::Serialization)?;
+        let second_bytes = self.second.signing_bytes().map_err(PclError::Serialization)?;
+        if first_bytes == second_bytes {
+            return Err(PclError::Consensus("Evidence entries are identical".to_string()));
+        }
+
+        let public_key = decode_public_key(&self.leader_public_key)?;
+        if !self.first.verify_leader_signature(&public_key) || !self.second.verify_leader_signature(&public_key) {
+            return Err(PclError::SignatureVerification("Evidence entry is not signed by the accused leader".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Two leaders' signed entries for the same raw_tx_id, where the fork tie-break favours the winner.
+// Backs an invalidation notice from a node that is not a current leader.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkEvidence {
+    pub winning_entry: ProcessingTransaction,
+    pub winning_public_key: String, // hex encoded
+    pub losing_entry: ProcessingTransaction,
+    pub losing_public_key: String,  // hex encoded
+}
+
+impl ForkEvidence {
+    pub fn new(winning_entry: ProcessingTransaction, winning_key: &VerifyingKey, losing_entry: ProcessingTransaction, losing_key: &VerifyingKey) -> Result<Self> {
+        let evidence = Self {
+            winning_entry,
+            winning_public_key: hex::encode(winning_key.to_bytes()),
+            losing_entry,
+            losing_public_key: hex::encode(losing_key.to_bytes()),
+        };
+        evidence.verify()?;
+        Ok(evidence)
+    }
+
+    fn claim(entry: &ProcessingTransaction, public_key: &str) -> FinalizationClaim {
+        FinalizationClaim {
+            raw_tx_id: entry.tx_id.clone(),
+            leader_id: entry.leader.clone(),
+            leader_public_key: public_key.to_string(),
+            averaged_timestamp: entry.timestamp,
+        }
+    }
+
+    // Checks the entries against the keys they carry; callers still match those keys to the leaders
+    pub fn verify(&self) -> Result<()> {
+        if self.winning_entry.tx_id != self.losing_entry.tx_id {
+            return Err(PclError::Consensus("Fork evidence entries are for different transactions".to_string()));
+        }
+        if self.winning_entry.leader == self.losing_entry.leader {
+            return Err(PclError::Consensus("Fork evidence entries are from the same leader".to_string()));
+        }
+
+        let winning_key = decode_public_key(&self.winning_public_key)?;
+        let losing_key = decode_public_key(&self.losing_public_key)?;
+        if !self.winning_entry.verify_leader_signature(&winning_key) || !self.losing_entry.verify_leader_signature(&losing_key) {
+            return Err(PclError::SignatureVerification("Fork evidence entry is not signed by its leader".to_string()));
+        }
+
+        let winner = Self::claim(&self.winning_entry, &self.winning_public_key);
+        if !winner.beats(&Self::claim(&self.losing_entry, &self.losing_public_key)) {
+            return Err(PclError::Consensus(format!(
+                "Entry from {} does not win the tie-break for {}", self.winning_entry.leader, self.winning_entry.tx_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Remembers the first signed entry seen from each leader per raw transaction
+#[derive(Debug, Clone, Default)]
+pub struct EquivocationDetector {
+    pub seen_entries: HashMap<(String, String), ProcessingTransaction>, // (raw_tx_id, leader_id) -> entry
+    pub evidence: HashMap<String, Vec<EquivocationEvidence>>, // leader_id -> evidence against them
+}
+
+impl EquivocationDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns evidence if the entry conflicts with one the same leader signed earlier. Entries with
+    // a bad signature are rejected rather than stored, so a forged entry cannot frame a leader.
+    pub fn observe(&mut self, entry: &ProcessingTransaction, leader_public_key: &VerifyingKey, reporter_id: &str) -> Result<Option<EquivocationEvidence>> {
+        if !entry.verify_leader_signature(leader_public_key) {
+            return Err(PclError::SignatureVerification(format!(
+                "Processing entry for {} is not signed by leader {}", entry.tx_id, entry.leader
+            )));
+        }
+
+        let key = (entry.tx_id.clone(), entry.leader.clone());
+        let stored = match self.seen_entries.get(&key) {
+            None => {
+                self.seen_entries.insert(key, entry.clone());
+                return Ok(None);
+            }
+            Some(stored) => stored.clone(),
+        };
+
+        if stored.signing_bytes() == entry.signing_bytes() {
+            return Ok(None);
+        }
+
+        let evidence = EquivocationEvidence::new(stored, entry.clone(), leader_public_key, reporter_id)?;
+        self.record(evidence.clone());
+        Ok(Some(evidence))
+    }
+
+    // Stores verified evidence received from a peer. Returns false if it was already known.
+    pub fn record(&mut self, evidence: EquivocationEvidence) -> bool {
+        let known = self.evidence.entry(evidence.leader_id.clone()).or_default();
+        if known.iter().any(|e| e.raw_tx_id == evidence.raw_tx_id) {
+            return false;
+        }
+        known.push(evidence);
+        true
+    }
+
+    pub fn has_equivocated(&self, leader_id: &str) -> bool {
+        self.evidence.get(leader_id).map_or(false, |e| !e.is_empty())
+    }
+
+    pub fn forget_transaction(&mut self, raw_tx_id: &str) {
+        self.seen_entries.retain(|(tx_id, _), _| tx_id != raw_tx_id);
+    }
+}
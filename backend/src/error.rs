@@ -29,12 +29,58 @@ pub enum PclError {
     #[error("Validation error: {0}")]
     Validation(String),
     
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    
+    #[error("Configuration error: {0}")]
+    Config(String),
+    
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    
+    #[error("Rate limit exceeded: {0}")]
+    RateLimited(String),
+    
+    #[error("Submission rate exceeded: {0}")]
+    SubmissionRateLimited(String),
+    
+    #[error("Not admitted: {0}")]
+    AdmissionRejected(String),
+    
+    #[error("Screened: {0}")]
+    Screened(String),
+    
+    #[error("Unknown tenant: {0}")]
+    UnknownTenant(String),
+    
+    #[error("Insufficient stake: {0}")]
+    InsufficientStake(String),
+    
+    #[error("Fee too low: {0}")]
+    FeeTooLow(String),
+    
+    #[error("On probation: {0}")]
+    Probation(String),
+    
+    #[error("Expired: {0}")]
+    Expired(String),
+    
+    #[error("Hardware wallet error: {0}")]
+    HardwareWallet(String),
+    
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+    
     #[error("Serialization error: {0}")]
     Serialization(String),
     
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
+    #[cfg(feature = "native")]
     #[error("RocksDB error: {0}")]
     RocksDb(#[from] rocksdb::Error),
     
@@ -48,10 +94,47 @@ pub enum PclError {
     Libp2p(String),
 }
 
+#[cfg(feature = "native")]
 impl From<libp2p::swarm::ConnectionDenied> for PclError {
     fn from(error: libp2p::swarm::ConnectionDenied) -> Self {
         PclError::Network(error.to_string())
     }
 }
 
+impl PclError {
+    // Stable machine-readable code for API error responses
+    pub fn code(&self) -> &'static str {
+        match self {
+            PclError::NodeIdentity(_) => "NODE_IDENTITY",
+            PclError::SignatureVerification(_) => "INVALID_SIGNATURE",
+            PclError::IpValidation(_) => "INVALID_IP",
+            PclError::Mempool(_) => "MEMPOOL_ERROR",
+            PclError::Transaction(_) => "INVALID_TRANSACTION",
+            PclError::Network(_) | PclError::Libp2p(_) => "NETWORK_ERROR",
+            PclError::Storage(_) => "STORAGE_ERROR",
+            #[cfg(feature = "native")]
+            PclError::RocksDb(_) => "STORAGE_ERROR",
+            PclError::Consensus(_) => "CONSENSUS_ERROR",
+            PclError::Validation(_) => "VALIDATION_ERROR",
+            PclError::InvalidAddress(_) => "INVALID_ADDRESS",
+            PclError::Config(_) => "CONFIG_ERROR",
+            PclError::Unauthorized(_) => "UNAUTHORIZED",
+            PclError::Forbidden(_) => "FORBIDDEN",
+            PclError::RateLimited(_) => "RATE_LIMITED",
+            PclError::SubmissionRateLimited(_) => "SUBMISSION_RATE_LIMITED",
+            PclError::AdmissionRejected(_) => "ADMISSION_REJECTED",
+            PclError::Screened(_) => "ADDRESS_SCREENED",
+            PclError::UnknownTenant(_) => "UNKNOWN_TENANT",
+            PclError::InsufficientStake(_) => "INSUFFICIENT_STAKE",
+            PclError::FeeTooLow(_) => "FEE_TOO_LOW",
+            PclError::Probation(_) => "ON_PROBATION",
+            PclError::Expired(_) => "EXPIRED",
+            PclError::HardwareWallet(_) => "HARDWARE_WALLET",
+            PclError::Encryption(_) => "ENCRYPTION_ERROR",
+            PclError::Serialization(_) | PclError::SerdeJson(_) | PclError::Bincode(_) => "SERIALIZATION_ERROR",
+            PclError::Io(_) => "IO_ERROR",
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, PclError>; 
\ No newline at end of file
@@ -0,0 +1,100 @@
+// Events module - the transaction event stream and per-subscriber server-side filters
+//
+// Every workflow step a transaction enters, and its finalization, is published as an event.
+// Subscribers pass a filter when they connect so only matching events are sent to them: a wallet
+// watching one address gets its own transactions instead of the whole firehose.
+
+use serde::{Deserialize, Serialize};
+use crate::error::{PclError, Result};
+use crate::metrics::WorkflowStep;
+
+// Stage of the event sent once a transaction is final, after the six workflow steps
+pub const FINALIZED_STAGE: &str = "finalized";
+
+// Stage of the event sent when a transaction is refused because of the screening list
+pub const SCREENED_STAGE: &str = "screened";
+
+// Stage of the event sent when reconciling with another leader finds the same transaction finalized
+// under a different leader, whichever side the fork tie-break keeps
+pub const SYNC_CONFLICT_STAGE: &str = "sync_conflict";
+
+// Stage of the event sent when a transaction has been in one workflow step past that step's timeout
+pub const STALLED_STAGE: &str = "stalled";
+
+// Stage of the event sent when a transaction passes its expires_at before validation starts and is dropped
+pub const EXPIRED_STAGE: &str = "expired";
+
+// Events a subscriber can ask for before the oldest unread ones are dropped
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamEvent {
+    pub stage: String, // a workflow step name, "finalized", "screened", "sync_conflict", "stalled" or "expired"
+    pub tx_id: String,
+    pub sender: String,
+    pub recipient: String,
+    pub amount: f64,
+    pub timestamp: u64, // ms since epoch
+}
+
+impl StreamEvent {
+    pub fn new(stage: &str, tx_id: &str, sender: &str, recipient: &str, amount: f64, timestamp: u64) -> Self {
+        Self {
+            stage: stage.to_string(),
+            tx_id: tx_id.to_string(),
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+            timestamp,
+        }
+    }
+
+    // One server-sent event frame, named after the stage so clients can listen per stage
+    pub fn to_sse(&self) -> String {
+        format!("event: {}\ndata: {}\n\n", self.stage, serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+pub fn is_known_stage(stage: &str) -> bool {
+    stage == FINALIZED_STAGE || stage == SCREENED_STAGE || stage == SYNC_CONFLICT_STAGE || stage == STALLED_STAGE || stage == EXPIRED_STAGE
+        || WorkflowStep::ALL.iter().any(|step| step.as_str() == stage)
+}
+
+// Empty lists match everything; every given condition must hold
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventFilter {
+    pub addresses: Vec<String>, // matches the sender or the recipient
+    pub min_amount: Option<f64>,
+    pub stages: Vec<String>,
+}
+
+impl EventFilter {
+    // Parses address=, min_amount= and stage= parameters; address and stage take comma separated
+    // lists and may repeat
+    pub fn from_query(query: &str) -> Result<Self> {
+        let mut filter = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            let values = value.split(',').filter(|value| !value.is_empty()).map(str::to_string);
+            match key {
+                "address" => filter.addresses.extend(values),
+                "stage" => filter.stages.extend(values),
+                "min_amount" if !value.is_empty() => {
+                    filter.min_amount = Some(value.parse().map_err(|_| {
+                        PclError::Validation(format!("Invalid value '{}' for min_amount", value))
+                    })?);
+                }
+                _ => {}
+            }
+        }
+        if let Some(stage) = filter.stages.iter().find(|stage| !is_known_stage(stage)) {
+            return Err(PclError::Validation(format!("Unknown event stage {}", stage)));
+        }
+        Ok(filter)
+    }
+
+    pub fn matches(&self, event: &StreamEvent) -> bool {
+        (self.addresses.is_empty() || self.addresses.iter().any(|address| *address == event.sender || *address == event.recipient))
+            && self.min_amount.is_none_or(|min_amount| event.amount >= min_amount)
+            && (self.stages.is_empty() || self.stages.contains(&event.stage))
+    }
+}
@@ -0,0 +1,128 @@
+// Fees module - rolling minimum fee and fee suggestions from mempool pressure
+//
+// The floor is base_fee plus fee_per_weight_unit for every unit of transaction weight, scaled by how far recent mempool depth and finalization latency are above
+// their targets. Both are averaged over the last window_secs, so a burst raises the floor for a while
+// and it falls back to base_fee once the backlog clears.
+
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use crate::config::FeeConfig;
+use crate::error::{PclError, Result};
+
+// Weight quoted when a caller does not name one: about a signed one-input, one-output transfer
+pub const REFERENCE_TX_WEIGHT: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    pub weight: u64,           // the suggestions below are for a transaction of this weight
+    pub fee_per_weight: f64,   // current charge per weight unit
+    pub minimum_fee: f64,
+    pub low: f64,    // accepted now, may be refused if pressure rises before it is submitted
+    pub medium: f64,
+    pub high: f64,   // stays above the floor through a sharp rise in pressure
+    pub pressure: f64,
+    pub mempool_depth: f64,          // average over the window
+    pub finalization_latency_ms: f64, // average over the window, 0 with no finalizations
+}
+
+pub struct FeeMarket {
+    config: FeeConfig,
+    depth_samples: VecDeque<(u64, usize)>,   // (ms since epoch, pending transactions)
+    latency_samples: VecDeque<(u64, u64)>,   // (ms since epoch, submission to finalization ms)
+}
+
+impl FeeMarket {
+    pub fn new(config: FeeConfig) -> Self {
+        Self {
+            config,
+            depth_samples: VecDeque::new(),
+            latency_samples: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &FeeConfig {
+        &self.config
+    }
+
+    // Swaps in new fee parameters, e.g. from governance; recorded samples are kept
+    pub fn set_config(&mut self, config: FeeConfig) {
+        self.config = config;
+    }
+
+    pub fn record_depth(&mut self, depth: usize, now: u64) {
+        self.depth_samples.push_back((now, depth));
+        let cutoff = self.cutoff(now);
+        while self.depth_samples.front().is_some_and(|(at, _)| *at < cutoff) {
+            self.depth_samples.pop_front();
+        }
+    }
+
+    pub fn record_finalization(&mut self, latency_ms: u64, now: u64) {
+        self.latency_samples.push_back((now, latency_ms));
+        let cutoff = self.cutoff(now);
+        while self.latency_samples.front().is_some_and(|(at, _)| *at < cutoff) {
+            self.latency_samples.pop_front();
+        }
+    }
+
+    // 1 when the network is at or below its targets, capped at max_multiplier
+    pub fn pressure(&self, now: u64) -> f64 {
+        let depth = self.average_depth(now) / self.config.target_mempool_depth as f64;
+        let latency = self.average_latency_ms(now) / self.config.target_finalization_latency_ms as f64;
+        (depth.max(1.0) * latency.max(1.0)).min(self.config.max_multiplier)
+    }
+
+    // Floor for a transaction of the given weight
+    pub fn minimum_fee(&self, weight: u64, now: u64) -> f64 {
+        (self.config.base_fee + self.config.fee_per_weight_unit * weight as f64) * self.pressure(now)
+    }
+
+    pub fn estimate(&self, weight: u64, now: u64) -> FeeEstimate {
+        let minimum_fee = self.minimum_fee(weight, now);
+        FeeEstimate {
+            weight,
+            fee_per_weight: self.config.fee_per_weight_unit * self.pressure(now),
+            minimum_fee,
+            low: minimum_fee,
+            medium: minimum_fee * 1.5,
+            high: minimum_fee * 3.0,
+            pressure: self.pressure(now),
+            mempool_depth: self.average_depth(now),
+            finalization_latency_ms: self.average_latency_ms(now),
+        }
+    }
+
+    pub fn check_fee(&self, fee: f64, weight: u64, now: u64) -> Result<()> {
+        self.check_discounted_fee(fee, weight, now, 0.0)
+    }
+
+    // As check_fee, with `discount` (0-1) of the minimum waived, e.g. for the sender's reputation
+    pub fn check_discounted_fee(&self, fee: f64, weight: u64, now: u64, discount: f64) -> Result<()> {
+        let minimum_fee = self.minimum_fee(weight, now) * (1.0 - discount.clamp(0.0, 1.0));
+        if !fee.is_finite() || fee < minimum_fee {
+            return Err(PclError::FeeTooLow(format!(
+                "Fee {} is below the current minimum fee of {:.6} XMBL for weight {}", fee, minimum_fee, weight
+            )));
+        }
+        Ok(())
+    }
+
+    fn cutoff(&self, now: u64) -> u64 {
+        now.saturating_sub(self.config.window_secs * 1000)
+    }
+
+    fn average_depth(&self, now: u64) -> f64 {
+        let cutoff = self.cutoff(now);
+        average(self.depth_samples.iter().filter(|(at, _)| *at >= cutoff).map(|(_, depth)| *depth as f64))
+    }
+
+    fn average_latency_ms(&self, now: u64) -> f64 {
+        let cutoff = self.cutoff(now);
+        average(self.latency_samples.iter().filter(|(at, _)| *at >= cutoff).map(|(_, latency)| *latency as f64))
+    }
+}
+
+fn average(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
@@ -0,0 +1,114 @@
+// Gateway module - serving the HTTP API from a library consensus node
+//
+// pcl-node's HTTP handlers drive ConsensusProtocol, a single-process model of the workflow, while the
+// peer-to-peer consensus lives in ConsensusManager. With --gateway the node runs a ConsensusManager
+// in-process through EmbeddedNode and its API becomes a thin layer over it: submissions are checked
+// and forwarded into the manager's workflow, and transaction reads, receipts, balances and mempool
+// counts come from the manager and its database. Routes the manager has no equivalent for are refused
+// rather than answered from the simulation.
+
+use serde::Serialize;
+use crate::address::Address;
+use crate::embed::{EmbeddedNode, TransactionStatus};
+use crate::error::{PclError, Result};
+use crate::mempool::FinalizedTransaction;
+use crate::multisig::{MultisigPolicy, PartialSignature};
+use crate::receipt::TransactionReceipt;
+use crate::transaction::{prepare_submission, verify_submission_signature, FeePayer, TransactionData};
+
+// Everything a node knows about one transaction, as GET /transaction/{id} returns it in gateway mode
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionView {
+    #[serde(flatten)]
+    pub status: TransactionStatus,
+    pub finalized_transaction: Option<FinalizedTransaction>,
+    pub receipt: Option<TransactionReceipt>,
+}
+
+#[derive(Clone)]
+pub struct Gateway {
+    node: EmbeddedNode,
+}
+
+impl Gateway {
+    pub fn new(node: EmbeddedNode) -> Self {
+        Self { node }
+    }
+
+    pub fn node(&self) -> &EmbeddedNode {
+        &self.node
+    }
+
+    // Checks and forwards an HTTP submission; returns its raw_tx_id once the workflow has finalized it
+    pub async fn submit(&self, body: &serde_json::Value) -> Result<String> {
+        let tx_data = transaction_from_submission(body)?;
+        log::info!("Gateway forwarding submission from {} to the consensus node", tx_data.user);
+        self.node.submit_transaction(tx_data).await
+    }
+
+    pub async fn transaction(&self, tx_id: &str) -> Result<TransactionView> {
+        let status = self.node.query_status(tx_id).await?;
+        let storage = &self.node.consensus().storage_manager;
+        Ok(TransactionView {
+            finalized_transaction: storage.load_finalized_transaction(tx_id)?,
+            receipt: storage.load_receipt(tx_id)?,
+            status,
+        })
+    }
+
+    // Receipts are issued by the manager's workflow when it finalizes
+    pub fn receipt(&self, tx_id: &str) -> Result<Option<TransactionReceipt>> {
+        self.node.consensus().storage_manager.load_receipt(tx_id)
+    }
+
+    // Unspent value the consensus node holds for the address
+    pub async fn balance(&self, address: &str) -> Result<f64> {
+        let address = Address::parse(address)?;
+        let tx_pool = self.node.consensus().mempool.tx.read().await;
+        Ok(tx_pool.balances().get(address.as_str()).copied().unwrap_or(0.0))
+    }
+
+    // Entries per stage of the manager's mempools, under the names GET /mempools uses
+    pub async fn mempool_counts(&self) -> serde_json::Value {
+        let mempool = &self.node.consensus().mempool;
+        serde_json::json!({
+            "raw_tx_mempool": {"count": mempool.raw_tx.read().await.transactions.len()},
+            "validation_tasks_mempool": {"count": mempool.validation_tasks.read().await.tasks.len()},
+            "locked_utxo_mempool": {"count": mempool.locked_utxo.read().await.locked_utxos.len()},
+            "processing_tx_mempool": {"count": mempool.processing_tx.read().await.transactions.len()},
+            "tx_mempool": {"count": mempool.tx.read().await.finalized_transactions.len()},
+        })
+    }
+}
+
+// The HTTP body (to, from, amount, user, stake, fee and signatures, as POST /transaction takes it)
+// in the manager's form. A single-signer body must carry a valid submission signature; multisig
+// cosignatures are passed on for the workflow to check. The one input is taken as spending exactly
+// amount plus stake and fee, so the transaction has no change output.
+pub fn transaction_from_submission(body: &serde_json::Value) -> Result<TransactionData> {
+    let prepared = prepare_submission(body)?;
+    let multisig = serde_json::from_value::<MultisigPolicy>(body["multisig"].clone()).ok();
+    if multisig.is_none() {
+        verify_submission_signature(body)?;
+    }
+
+    let to = Address::parse(prepared["to"].as_str().unwrap_or(""))?;
+    let user = Address::parse(prepared["user"].as_str().unwrap_or(""))?;
+    let from = prepared["from"].as_str()
+        .filter(|utxo_id| !utxo_id.is_empty())
+        .ok_or_else(|| PclError::Validation("from: missing UTXO id".to_string()))?;
+    let amount = prepared["amount"].as_f64().unwrap_or(1.0);
+    let stake = prepared["stake"].as_f64().unwrap_or(0.2);
+    let fee = prepared["fee"].as_f64().unwrap_or(0.1);
+    if amount <= 0.0 || stake < 0.0 || fee < 0.0 {
+        return Err(PclError::Validation("amount must be positive and stake and fee not negative".to_string()));
+    }
+
+    let mut tx_data = TransactionData::new(vec![(to, amount)], vec![(from.to_string(), amount + stake + fee)], user, stake, fee);
+    tx_data.change = None; // rounding can leave a dust remainder of the exact sum
+    tx_data.sig = body["sig"].as_str().map(str::to_string);
+    tx_data.multisig = multisig;
+    tx_data.signatures = serde_json::from_value::<Vec<PartialSignature>>(body["signatures"].clone()).unwrap_or_default();
+    tx_data.fee_payer = serde_json::from_value::<FeePayer>(body["fee_payer"].clone()).ok();
+    Ok(tx_data)
+}
@@ -0,0 +1,219 @@
+// Gossip module - mesh membership and message history, maintained on a configurable heartbeat
+//
+// The gossip parameters were fixed: a 10 second heartbeat and default mesh sizes, which repair a
+// thinned mesh too slowly at high TPS. They now come from the network config. Each heartbeat prunes
+// peers that went away, grafts connected peers while the mesh is below mesh_n_low and prunes back
+// to mesh_n above mesh_n_high, then starts a new history window. Ids of messages seen in the last
+// history_length windows are kept to drop repeats, and the last history_gossip windows are what a
+// node advertises to peers outside its mesh. With flood_publish on, own messages go to every peer.
+//
+// Invalidation notices and equivocation evidence are relayed on by every node that receives them, and
+// were re-gossiped unconditionally. They now carry a hop count that each relay bumps and that stops at
+// max_hops, and a node remembers what it has relayed or published for far longer than the history
+// windows, so each notice goes out from each node once.
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use serde::{Deserialize, Serialize};
+use crate::config::NetworkConfig;
+use crate::crypto::hash_data;
+use crate::network::{NetworkMessage, PeerId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipParams {
+    pub heartbeat_interval_ms: u64,
+    pub mesh_n: usize,
+    pub mesh_n_low: usize,
+    pub mesh_n_high: usize,
+    pub history_length: usize,
+    pub history_gossip: usize,
+    pub flood_publish: bool,
+    pub max_hops: u32,
+}
+
+impl GossipParams {
+    pub fn new(config: &NetworkConfig) -> Self {
+        Self {
+            heartbeat_interval_ms: config.gossip_heartbeat_interval_ms,
+            mesh_n: config.gossip_mesh_n,
+            mesh_n_low: config.gossip_mesh_n_low,
+            mesh_n_high: config.gossip_mesh_n_high,
+            history_length: config.gossip_history_length,
+            history_gossip: config.gossip_history_gossip,
+            flood_publish: config.gossip_flood_publish,
+            max_hops: config.gossip_max_hops,
+        }
+    }
+}
+
+// Peers added to and removed from the mesh by one heartbeat
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshChange {
+    pub grafted: Vec<PeerId>,
+    pub pruned: Vec<PeerId>,
+}
+
+// Relayed notices remembered per node, the oldest forgotten first
+const RELAYED_CAPACITY: usize = 10_000;
+
+// Hop count of a message type that nodes relay on, None for everything else
+pub fn relay_hops(message: &NetworkMessage) -> Option<u32> {
+    match message {
+        NetworkMessage::TransactionInvalidation(notice) => Some(notice.hops),
+        NetworkMessage::EquivocationEvidence(evidence) => Some(evidence.hops),
+        _ => None,
+    }
+}
+
+fn relay_hops_mut(message: &mut NetworkMessage) -> Option<&mut u32> {
+    match message {
+        NetworkMessage::TransactionInvalidation(notice) => Some(&mut notice.hops),
+        NetworkMessage::EquivocationEvidence(evidence) => Some(&mut evidence.hops),
+        _ => None,
+    }
+}
+
+// Content id of a message, the same on every node that sees it; a relayed copy keeps the original's id
+pub fn gossip_message_id(message: &NetworkMessage) -> String {
+    let bytes = match relay_hops(message) {
+        Some(hops) if hops > 0 => {
+            let mut original = message.clone();
+            if let Some(hops) = relay_hops_mut(&mut original) {
+                *hops = 0;
+            }
+            serde_json::to_vec(&original)
+        }
+        _ => serde_json::to_vec(message),
+    }.unwrap_or_default();
+    hex::encode(&hash_data(&bytes)[..16])
+}
+
+#[derive(Debug, Clone)]
+pub struct GossipMesh {
+    params: GossipParams,
+    mesh: BTreeSet<PeerId>,
+    history: VecDeque<Vec<String>>, // message ids per heartbeat window, newest first
+    seen: HashSet<String>,          // every id in history
+    relayed: HashSet<String>,       // relayed or published notices, kept past the history
+    relayed_order: VecDeque<String>,
+    pub hop_limited: u64,           // notices not relayed because they reached max_hops
+}
+
+impl GossipMesh {
+    pub fn new(config: &NetworkConfig) -> Self {
+        let mut history = VecDeque::new();
+        history.push_front(Vec::new());
+        Self {
+            params: GossipParams::new(config),
+            mesh: BTreeSet::new(),
+            history,
+            seen: HashSet::new(),
+            relayed: HashSet::new(),
+            relayed_order: VecDeque::new(),
+            hop_limited: 0,
+        }
+    }
+
+    // Takes new parameters without dropping the mesh or history; sizes apply from the next heartbeat
+    pub fn set_params(&mut self, config: &NetworkConfig) {
+        self.params = GossipParams::new(config);
+    }
+
+    pub fn params(&self) -> &GossipParams {
+        &self.params
+    }
+
+    pub fn mesh_peers(&self) -> Vec<PeerId> {
+        self.mesh.iter().cloned().collect()
+    }
+
+    pub fn heartbeat(&mut self, connected: &[PeerId]) -> MeshChange {
+        let mut change = MeshChange::default();
+        let connected_set: HashSet<&PeerId> = connected.iter().collect();
+        let gone: Vec<PeerId> = self.mesh.iter().filter(|peer| !connected_set.contains(peer)).cloned().collect();
+        for peer in gone {
+            self.mesh.remove(&peer);
+            change.pruned.push(peer);
+        }
+        if self.mesh.len() < self.params.mesh_n_low {
+            for peer in connected {
+                if self.mesh.len() >= self.params.mesh_n {
+                    break;
+                }
+                if self.mesh.insert(peer.clone()) {
+                    change.grafted.push(peer.clone());
+                }
+            }
+        } else if self.mesh.len() > self.params.mesh_n_high {
+            let excess: Vec<PeerId> = self.mesh.iter().skip(self.params.mesh_n).cloned().collect();
+            for peer in excess {
+                self.mesh.remove(&peer);
+                change.pruned.push(peer);
+            }
+        }
+
+        self.history.push_front(Vec::new());
+        while self.history.len() > self.params.history_length.max(1) {
+            for id in self.history.pop_back().unwrap_or_default() {
+                self.seen.remove(&id);
+            }
+        }
+        change
+    }
+
+    // Records a message id; false if it was already seen within the history
+    pub fn remember(&mut self, message_id: &str) -> bool {
+        if !self.seen.insert(message_id.to_string()) {
+            return false;
+        }
+        if let Some(window) = self.history.front_mut() {
+            window.push(message_id.to_string());
+        }
+        true
+    }
+
+    // Records a notice this node sends; false if it already sent it
+    pub fn mark_relayed(&mut self, message_id: &str) -> bool {
+        if !self.relayed.insert(message_id.to_string()) {
+            return false;
+        }
+        self.relayed_order.push_back(message_id.to_string());
+        while self.relayed_order.len() > RELAYED_CAPACITY {
+            if let Some(oldest) = self.relayed_order.pop_front() {
+                self.relayed.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    // The copy of a received notice to pass on, one hop further; None if the message is not relayed,
+    // has used up its hops or was sent by this node before
+    pub fn relay(&mut self, message: &NetworkMessage) -> Option<NetworkMessage> {
+        let hops = relay_hops(message)?;
+        if hops >= self.params.max_hops {
+            self.hop_limited += 1;
+            return None;
+        }
+        if !self.mark_relayed(&gossip_message_id(message)) {
+            return None;
+        }
+        let mut relayed = message.clone();
+        if let Some(relayed_hops) = relay_hops_mut(&mut relayed) {
+            *relayed_hops = hops + 1;
+        }
+        Some(relayed)
+    }
+
+    // Ids advertised to peers outside the mesh: the last history_gossip windows
+    pub fn gossip_ids(&self) -> Vec<String> {
+        self.history.iter().take(self.params.history_gossip).flatten().cloned().collect()
+    }
+
+    // Peers an own message goes to: everyone with flood publishing, the mesh otherwise
+    pub fn publish_targets(&self, connected: &[PeerId]) -> Vec<PeerId> {
+        if self.params.flood_publish {
+            connected.to_vec()
+        } else {
+            self.mesh_peers()
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Governance module - network parameter changes agreed by leader signatures
+//
+// A leader proposes one parameter change with the time it takes effect and signs it; other leaders
+// co-sign the same proposal as it is gossiped. Once a majority of the current leaders has signed, the
+// proposal is scheduled and every node applies it at effective_at, so quorum sizes and fees change
+// network-wide without operators editing configs in lockstep.
+
+use std::collections::{BTreeMap, HashMap};
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use crate::config::{ConsensusConfig, FeeConfig};
+use crate::crypto::{hash_data, verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::decode_signature;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "parameter", content = "value", rename_all = "snake_case")]
+pub enum ParameterChange {
+    MinValidationCompletions(usize),
+    RequiredLeaderSignatures(usize),
+    BroadcastFanout(usize),
+    BaseFee(f64),
+    FeePerWeightUnit(f64),
+}
+
+impl ParameterChange {
+    pub fn apply(&self, parameters: &mut ParameterSet) {
+        match *self {
+            ParameterChange::MinValidationCompletions(value) => parameters.consensus.min_validation_completions = value,
+            ParameterChange::RequiredLeaderSignatures(value) => parameters.consensus.required_leader_signatures = value,
+            ParameterChange::BroadcastFanout(value) => parameters.consensus.broadcast_fanout = value,
+            ParameterChange::BaseFee(value) => parameters.fees.base_fee = value,
+            ParameterChange::FeePerWeightUnit(value) => parameters.fees.fee_per_weight_unit = value,
+        }
+    }
+}
+
+// The parameters governance can change, as currently in force
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSet {
+    pub consensus: ConsensusConfig,
+    pub fees: FeeConfig,
+}
+
+impl ParameterSet {
+    pub fn validate(&self) -> Result<()> {
+        self.consensus.validate()?;
+        self.fees.validate()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalSignature {
+    pub leader_id: String,
+    pub public_key: String, // hex encoded
+    pub signature: String,  // hex encoded
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovernanceProposal {
+    pub proposal_id: String, // hex hash of proposer, change and times
+    pub proposer_id: String,
+    pub change: ParameterChange,
+    pub effective_at: u64, // ms since epoch
+    pub proposed_at: u64,
+    pub signatures: Vec<ProposalSignature>, // the proposer's first
+}
+
+impl GovernanceProposal {
+    pub fn new(proposer_id: &str, proposer: &NodeKeypair, change: ParameterChange, effective_at: u64, proposed_at: u64) -> Result<Self> {
+        let preimage = format!(
+            "{}:{}:{}:{}", proposer_id, serde_json::to_string(&change)?, effective_at, proposed_at
+        );
+        let mut proposal = Self {
+            proposal_id: hex::encode(hash_data(preimage.as_bytes())),
+            proposer_id: proposer_id.to_string(),
+            change,
+            effective_at,
+            proposed_at,
+            signatures: Vec::new(),
+        };
+        proposal.cosign(proposer_id, proposer)?;
+        Ok(proposal)
+    }
+
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(format!(
+            "pcl-governance:{}:{}:{}", self.proposal_id, serde_json::to_string(&self.change)?, self.effective_at
+        ).into_bytes())
+    }
+
+    // Adds a leader's signature; signing twice replaces the earlier one
+    pub fn cosign(&mut self, leader_id: &str, keypair: &NodeKeypair) -> Result<()> {
+        let signature = keypair.sign_data(&self.signing_bytes()?);
+        self.signatures.retain(|existing| existing.leader_id != leader_id);
+        self.signatures.push(ProposalSignature {
+            leader_id: leader_id.to_string(),
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+        Ok(())
+    }
+
+    // Leaders whose signatures verify against their registered keys; anyone else's are ignored
+    pub fn valid_signers(&self, leader_keys: &HashMap<String, VerifyingKey>) -> Result<Vec<String>> {
+        let message = self.signing_bytes()?;
+        let mut signers = Vec::new();
+        for signature in &self.signatures {
+            let Some(key) = leader_keys.get(&signature.leader_id) else { continue };
+            let Ok(decoded) = decode_signature(&signature.signature) else { continue };
+            if verify_data_signature(&message, &decoded, key)? && !signers.contains(&signature.leader_id) {
+                signers.push(signature.leader_id.clone());
+            }
+        }
+        Ok(signers)
+    }
+}
+
+// A majority of the current leaders
+pub fn governance_quorum(leader_count: usize) -> usize {
+    leader_count / 2 + 1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Pending,   // waiting for co-signatures
+    Scheduled, // quorum reached, applies at effective_at
+    Applied,
+    Rejected,  // the change no longer validates against the parameters in force when it came due
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedProposal {
+    pub proposal: GovernanceProposal,
+    pub status: ProposalStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Governance {
+    active: ParameterSet,
+    proposals: BTreeMap<String, TrackedProposal>,
+}
+
+impl Governance {
+    pub fn new(active: ParameterSet) -> Self {
+        Self { active, proposals: BTreeMap::new() }
+    }
+
+    pub fn active(&self) -> &ParameterSet {
+        &self.active
+    }
+
+    pub fn proposals(&self) -> impl Iterator<Item = &TrackedProposal> {
+        self.proposals.values()
+    }
+
+    pub fn proposal(&self, proposal_id: &str) -> Option<&TrackedProposal> {
+        self.proposals.get(proposal_id)
+    }
+
+    // Records a proposal or merges new signatures into the known copy. leader_keys are the current
+    // leaders' registered keys; the quorum is a majority of them.
+    pub fn submit(&mut self, proposal: &GovernanceProposal, leader_keys: &HashMap<String, VerifyingKey>, now: u64) -> Result<ProposalStatus> {
+        let signers = proposal.valid_signers(leader_keys)?;
+        if !signers.contains(&proposal.proposer_id) {
+            return Err(PclError::SignatureVerification(format!(
+                "Proposal {} is not signed by its proposer {} as a current leader", proposal.proposal_id, proposal.proposer_id
+            )));
+        }
+
+        let tracked = match self.proposals.get_mut(&proposal.proposal_id) {
+            Some(tracked) => {
+                if tracked.proposal.change != proposal.change || tracked.proposal.effective_at != proposal.effective_at {
+                    return Err(PclError::Validation(format!("Proposal {} does not match the known copy", proposal.proposal_id)));
+                }
+                for signature in &proposal.signatures {
+                    let known = tracked.proposal.signatures.iter().any(|existing| existing.leader_id == signature.leader_id);
+                    if signers.contains(&signature.leader_id) && !known {
+                        tracked.proposal.signatures.push(signature.clone());
+                    }
+                }
+                tracked
+            }
+            None => {
+                if proposal.effective_at <= now {
+                    return Err(PclError::Validation(format!("Proposal {} takes effect in the past", proposal.proposal_id)));
+                }
+                let mut candidate = self.active.clone();
+                proposal.change.apply(&mut candidate);
+                candidate.validate()?;
+
+                let mut proposal = proposal.clone();
+                proposal.signatures.retain(|signature| signers.contains(&signature.leader_id));
+                self.proposals.entry(proposal.proposal_id.clone())
+                    .or_insert(TrackedProposal { proposal, status: ProposalStatus::Pending })
+            }
+        };
+
+        if tracked.status == ProposalStatus::Pending
+            && tracked.proposal.valid_signers(leader_keys)?.len() >= governance_quorum(leader_keys.len())
+        {
+            tracked.status = ProposalStatus::Scheduled;
+            log::info!("Governance proposal {} scheduled for {}", tracked.proposal.proposal_id, tracked.proposal.effective_at);
+        }
+        Ok(tracked.status)
+    }
+
+    // Applies scheduled proposals that came due, oldest effective_at first; returns those applied
+    pub fn apply_due(&mut self, now: u64) -> Vec<GovernanceProposal> {
+        let mut due: Vec<&mut TrackedProposal> = self.proposals.values_mut()
+            .filter(|tracked| tracked.status == ProposalStatus::Scheduled && tracked.proposal.effective_at <= now)
+            .collect();
+        due.sort_by_key(|tracked| tracked.proposal.effective_at);
+
+        let mut applied = Vec::new();
+        for tracked in due {
+            let mut candidate = self.active.clone();
+            tracked.proposal.change.apply(&mut candidate);
+            match candidate.validate() {
+                Ok(()) => {
+                    self.active = candidate;
+                    tracked.status = ProposalStatus::Applied;
+                    applied.push(tracked.proposal.clone());
+                }
+                Err(e) => {
+                    log::warn!("Governance proposal {} rejected at its effective time: {}", tracked.proposal.proposal_id, e);
+                    tracked.status = ProposalStatus::Rejected;
+                }
+            }
+        }
+        applied
+    }
+}
@@ -0,0 +1,42 @@
+// Health module - liveness and readiness as orchestrators expect them
+//
+// /health reported healthy whenever the clock was in bounds, so a node with a corrupt database or
+// no peers still took traffic. Liveness now only says the process answers; readiness says the node
+// can do useful work: storage takes writes, at least one peer is connected, consensus is in normal
+// operation and the clock is within the allowed drift. Each failed check adds a reason.
+
+use serde::{Deserialize, Serialize};
+use crate::consensus::ConsensusPhase;
+use crate::error::Result;
+
+// Peers a node needs before it reports ready
+pub const MIN_READY_PEERS: usize = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub reasons: Vec<String>, // one per failed check, empty when ready
+    pub db_writable: bool,
+    pub peers: usize,
+    pub consensus_phase: ConsensusPhase,
+    pub clock_healthy: bool,
+}
+
+impl Readiness {
+    pub fn evaluate(db_check: Result<()>, peers: usize, consensus_phase: ConsensusPhase, clock_healthy: bool) -> Self {
+        let mut reasons = Vec::new();
+        if let Err(e) = &db_check {
+            reasons.push(format!("Storage is not writable: {}", e));
+        }
+        if peers < MIN_READY_PEERS {
+            reasons.push(format!("{} peers connected, need at least {}", peers, MIN_READY_PEERS));
+        }
+        if consensus_phase != ConsensusPhase::NormalOperation {
+            reasons.push(format!("Consensus is in {:?}, not normal operation", consensus_phase));
+        }
+        if !clock_healthy {
+            reasons.push(format!("Clock is {} ms off", crate::clock::clock_offset_ms()));
+        }
+        Self { ready: reasons.is_empty(), reasons, db_writable: db_check.is_ok(), peers, consensus_phase, clock_healthy }
+    }
+}
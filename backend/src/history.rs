@@ -0,0 +1,108 @@
+// History module - balances and UTXOs as they stood at a past finalization
+//
+// Auditors ask what an address held at some earlier point. Each entry of the finalization sequence
+// records how the UTXO set changed since the entry before it, and every CHECKPOINT_INTERVAL entries
+// (and the first one a node records after starting) the whole unspent set is saved as a checkpoint.
+// The state at sequence N is the nearest checkpoint at or below N with the changes up to N replayed
+// on top. Sequences from before a node kept history cannot be reconstructed.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::error::{PclError, Result};
+use crate::storage::StorageManager;
+
+pub const CHECKPOINT_INTERVAL: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalUtxo {
+    pub owner: String,
+    pub amount: f64,
+}
+
+// Unspent outputs by utxo id
+pub type UtxoSnapshot = BTreeMap<String, HistoricalUtxo>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtxoCheckpoint {
+    pub sequence: u64,
+    pub utxos: UtxoSnapshot,
+}
+
+// What finalization `sequence` (and anything else since the previous entry) did to the unspent set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtxoDelta {
+    pub sequence: u64,
+    pub spent: Vec<String>,
+    pub created: Vec<(String, HistoricalUtxo)>,
+}
+
+impl UtxoDelta {
+    pub fn between(sequence: u64, before: &UtxoSnapshot, after: &UtxoSnapshot) -> Self {
+        Self {
+            sequence,
+            spent: before.keys().filter(|utxo_id| !after.contains_key(*utxo_id)).cloned().collect(),
+            created: after.iter()
+                .filter(|(utxo_id, utxo)| before.get(*utxo_id) != Some(utxo))
+                .map(|(utxo_id, utxo)| (utxo_id.clone(), utxo.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn apply(&self, utxos: &mut UtxoSnapshot) {
+        for utxo_id in &self.spent {
+            utxos.remove(utxo_id);
+        }
+        for (utxo_id, utxo) in &self.created {
+            utxos.insert(utxo_id.clone(), utxo.clone());
+        }
+    }
+}
+
+// The unspent set after one finalization, as GET /balance and /utxos with ?at_seq= report it
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalState {
+    pub sequence: u64,
+    pub checkpoint: u64, // the checkpoint it was replayed from
+    pub utxos: UtxoSnapshot,
+}
+
+impl HistoricalState {
+    pub fn utxos_of<'a>(&'a self, address: &'a str) -> impl Iterator<Item = (&'a String, &'a HistoricalUtxo)> + 'a {
+        self.utxos.iter().filter(move |(_, utxo)| utxo.owner == address)
+    }
+
+    pub fn balance_of(&self, address: &str) -> f64 {
+        self.utxos_of(address).map(|(_, utxo)| utxo.amount).sum()
+    }
+}
+
+// Writes one entry per finalization: a delta against the last entry it wrote, or a checkpoint
+#[derive(Debug, Default)]
+pub struct HistoryRecorder {
+    last: Option<UtxoSnapshot>,
+}
+
+impl HistoryRecorder {
+    pub fn record(&mut self, storage: &StorageManager, sequence: u64, utxos: UtxoSnapshot) -> Result<()> {
+        match &self.last {
+            Some(last) if !sequence.is_multiple_of(CHECKPOINT_INTERVAL) => storage.store_utxo_delta(&UtxoDelta::between(sequence, last, &utxos))?,
+            _ => storage.store_utxo_checkpoint(&UtxoCheckpoint { sequence, utxos: utxos.clone() })?,
+        }
+        self.last = Some(utxos);
+        Ok(())
+    }
+}
+
+// Replays the recorded changes from the nearest checkpoint at or below `sequence`
+pub fn state_at(storage: &StorageManager, sequence: u64) -> Result<HistoricalState> {
+    if sequence == 0 || storage.load_finalizations(sequence - 1, 1)?.first().map(|(found, _)| *found) != Some(sequence) {
+        return Err(PclError::Validation(format!("No finalization #{} on this node", sequence)));
+    }
+    let checkpoint = storage.load_utxo_checkpoint_at_or_before(sequence)?
+        .ok_or_else(|| PclError::Validation(format!("No history recorded at or before #{}", sequence)))?;
+    let mut utxos = checkpoint.utxos;
+    for delta in storage.load_utxo_deltas(checkpoint.sequence, sequence)? {
+        delta.apply(&mut utxos);
+    }
+    Ok(HistoricalState { sequence, checkpoint: checkpoint.sequence, utxos })
+}
@@ -0,0 +1,79 @@
+// Idempotency module - answering client retries with the original result
+//
+// A client that times out on POST /transaction and retries with the same Idempotency-Key (or
+// client_tx_id) must not submit twice. The first response is remembered under the key for 24h: the
+// same key with the same body replays it, the same key with a different body is a conflict. Keys are
+// scoped to whoever submitted them, the API key when one was presented and otherwise the sender, so
+// two clients picking the same key never see each other's transactions. The store is saved with the
+// consensus state, so a retry after a restart still finds the original.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+// Records are kept for 24h so client retries return the original result
+pub const IDEMPOTENCY_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub raw_tx_id: String,
+    pub request_hash: String, // detects the same key being reused for a different body
+    pub response: String,
+    pub created_at: u64, // ms since epoch
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyCheck {
+    New,
+    Replay(IdempotencyRecord),   // same key and body: answer with the original response
+    Conflict(IdempotencyRecord), // same key, different body
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdempotencyStore {
+    records: HashMap<String, IdempotencyRecord>, // "{scope}\n{key}" -> record
+}
+
+fn scoped(scope: &str, key: &str) -> String {
+    format!("{}\n{}", scope, key)
+}
+
+impl IdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // Drops records older than the TTL; returns how many
+    pub fn prune(&mut self, now: u64) -> usize {
+        let cutoff = now.saturating_sub(IDEMPOTENCY_TTL_MS);
+        let before = self.records.len();
+        self.records.retain(|_, record| record.created_at >= cutoff);
+        before - self.records.len()
+    }
+
+    pub fn check(&mut self, scope: &str, key: &str, request_hash: &str, now: u64) -> IdempotencyCheck {
+        self.prune(now);
+        match self.records.get(&scoped(scope, key)) {
+            None => IdempotencyCheck::New,
+            Some(record) if record.request_hash == request_hash => IdempotencyCheck::Replay(record.clone()),
+            Some(record) => IdempotencyCheck::Conflict(record.clone()),
+        }
+    }
+
+    pub fn remember(&mut self, scope: &str, key: &str, raw_tx_id: &str, request_hash: &str, response: &str, now: u64) {
+        self.records.insert(scoped(scope, key), IdempotencyRecord {
+            raw_tx_id: raw_tx_id.to_string(),
+            request_hash: request_hash.to_string(),
+            response: response.to_string(),
+            created_at: now,
+        });
+    }
+}
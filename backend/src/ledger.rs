@@ -0,0 +1,154 @@
+// Ledger module - signing with the PCL app on a Ledger device over USB HID
+//
+// The key never leaves the device: it is derived on-device at the wallet's BIP32 path and the device
+// signs SHA-256 of what it is sent, exactly like NodeKeypair::sign_data. Building the APDUs needs no
+// hardware; talking to a device needs the `ledger` feature.
+
+use crate::crypto::HARDENED_OFFSET;
+use crate::error::{PclError, Result};
+#[cfg(feature = "ledger")]
+use crate::crypto::{address_derivation_path, address_from_public_key};
+#[cfg(feature = "ledger")]
+use ed25519_dalek::{Signature, VerifyingKey};
+
+pub const CLA: u8 = 0xe0;
+pub const INS_GET_PUBLIC_KEY: u8 = 0x02;
+pub const INS_SIGN: u8 = 0x04;
+
+// GET_PUBLIC_KEY: P1 asks the device to show the address and wait for the user to approve it
+pub const P1_SILENT: u8 = 0x00;
+pub const P1_CONFIRM: u8 = 0x01;
+
+// SIGN: the path goes first, then the message in chunks; the device signs after the last one
+pub const P1_SIGN_INIT: u8 = 0x00;
+pub const P1_SIGN_ADD: u8 = 0x01;
+pub const P1_SIGN_LAST: u8 = 0x02;
+
+// APDU payloads carry a one-byte length
+pub const MAX_APDU_DATA: usize = 255;
+pub const SW_OK: u16 = 0x9000;
+pub const SW_DENIED: u16 = 0x6985;
+
+const MAX_PATH_DEPTH: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApduRequest {
+    pub ins: u8,
+    pub p1: u8,
+    pub p2: u8,
+    pub data: Vec<u8>,
+}
+
+// A path like m/44'/7337'/0'/0'/5' as a depth byte followed by big-endian hardened indexes
+pub fn encode_derivation_path(path: &str) -> Result<Vec<u8>> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(PclError::HardwareWallet(format!("Derivation path must start with m/: {}", path)));
+    }
+
+    let indexes = segments
+        .map(|segment| {
+            let index: u32 = segment.trim_end_matches('\'').parse()
+                .map_err(|_| PclError::HardwareWallet(format!("Invalid path segment: {}", segment)))?;
+            if index >= HARDENED_OFFSET {
+                return Err(PclError::HardwareWallet(format!("Path index out of range: {}", index)));
+            }
+            Ok(index | HARDENED_OFFSET)
+        })
+        .collect::<Result<Vec<u32>>>()?;
+    if indexes.is_empty() || indexes.len() > MAX_PATH_DEPTH {
+        return Err(PclError::HardwareWallet(format!("Derivation path must have 1 to {} levels: {}", MAX_PATH_DEPTH, path)));
+    }
+
+    let mut encoded = vec![indexes.len() as u8];
+    for index in indexes {
+        encoded.extend_from_slice(&index.to_be_bytes());
+    }
+    Ok(encoded)
+}
+
+pub fn public_key_request(path: &str, confirm: bool) -> Result<ApduRequest> {
+    Ok(ApduRequest {
+        ins: INS_GET_PUBLIC_KEY,
+        p1: if confirm { P1_CONFIRM } else { P1_SILENT },
+        p2: 0,
+        data: encode_derivation_path(path)?,
+    })
+}
+
+// The exchanges for one signature, in order; only the answer to the last one carries the signature
+pub fn sign_requests(path: &str, message: &[u8]) -> Result<Vec<ApduRequest>> {
+    if message.is_empty() {
+        return Err(PclError::HardwareWallet("Nothing to sign".to_string()));
+    }
+
+    let mut requests = vec![ApduRequest { ins: INS_SIGN, p1: P1_SIGN_INIT, p2: 0, data: encode_derivation_path(path)? }];
+    let chunks: Vec<&[u8]> = message.chunks(MAX_APDU_DATA).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let p1 = if i + 1 == chunks.len() { P1_SIGN_LAST } else { P1_SIGN_ADD };
+        requests.push(ApduRequest { ins: INS_SIGN, p1, p2: 0, data: chunk.to_vec() });
+    }
+    Ok(requests)
+}
+
+pub fn check_status(status: u16) -> Result<()> {
+    match status {
+        SW_OK => Ok(()),
+        SW_DENIED => Err(PclError::HardwareWallet("Rejected on the device".to_string())),
+        other => Err(PclError::HardwareWallet(format!("Device returned status {:#06x}; is the PCL app open?", other))),
+    }
+}
+
+#[cfg(feature = "ledger")]
+pub struct LedgerSigner {
+    transport: ledger_transport_hid::TransportNativeHID,
+}
+
+#[cfg(feature = "ledger")]
+impl LedgerSigner {
+    // Opens the first Ledger found on USB
+    pub fn connect() -> Result<Self> {
+        let api = ledger_transport_hid::hidapi::HidApi::new()
+            .map_err(|e| PclError::HardwareWallet(format!("Could not open USB HID: {}", e)))?;
+        let transport = ledger_transport_hid::TransportNativeHID::new(&api)
+            .map_err(|e| PclError::HardwareWallet(format!("No Ledger device found: {}", e)))?;
+        Ok(Self { transport })
+    }
+
+    // With `confirm` the device shows the address and the call returns once the user approves it
+    pub fn public_key(&self, account: u32, index: u32, confirm: bool) -> Result<VerifyingKey> {
+        let answer = self.exchange(&public_key_request(&address_derivation_path(account, index), confirm)?)?;
+        let bytes: [u8; 32] = answer.try_into()
+            .map_err(|_| PclError::HardwareWallet("Device returned a malformed public key".to_string()))?;
+        VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| PclError::HardwareWallet(format!("Device returned an invalid public key: {}", e)))
+    }
+
+    pub fn address(&self, account: u32, index: u32, confirm: bool) -> Result<String> {
+        Ok(address_from_public_key(&self.public_key(account, index, confirm)?))
+    }
+
+    pub fn sign(&self, account: u32, index: u32, message: &[u8]) -> Result<Signature> {
+        let mut answer = Vec::new();
+        for request in sign_requests(&address_derivation_path(account, index), message)? {
+            answer = self.exchange(&request)?;
+        }
+        let bytes: [u8; 64] = answer.try_into()
+            .map_err(|_| PclError::HardwareWallet("Device returned a malformed signature".to_string()))?;
+        Ok(Signature::from_bytes(&bytes))
+    }
+
+    fn exchange(&self, request: &ApduRequest) -> Result<Vec<u8>> {
+        let command = ledger_transport::APDUCommand {
+            cla: CLA,
+            ins: request.ins,
+            p1: request.p1,
+            p2: request.p2,
+            data: request.data.as_slice(),
+        };
+        let answer = self.transport.exchange(&command)
+            .map_err(|e| PclError::HardwareWallet(format!("Device exchange failed: {}", e)))?;
+        check_status(answer.retcode())?;
+        Ok(answer.data().to_vec())
+    }
+}
@@ -1,20 +1,249 @@
+// Core types (addresses, keys, transactions, receipts and their signing) build on their own,
+// including for wasm32-unknown-unknown with --no-default-features --features wasm; everything that
+// needs storage, networking or the async runtime is behind the native feature, which is on by default.
+#[cfg(feature = "native")]
 pub mod node;
+#[cfg(feature = "native")]
 pub mod mempool;
 pub mod transaction;
+#[cfg(feature = "native")]
 pub mod consensus;
+#[cfg(feature = "native")]
 pub mod network;
 pub mod crypto;
+#[cfg(feature = "native")]
 pub mod storage;
 pub mod error;
+pub mod multisig;
+pub mod address;
+pub mod version;
+#[cfg(feature = "native")]
+pub mod config;
+#[cfg(feature = "native")]
+pub mod equivocation;
+#[cfg(feature = "native")]
+pub mod client;
+#[cfg(feature = "native")]
+pub mod export;
+#[cfg(feature = "native")]
+pub mod webhook;
+#[cfg(feature = "native")]
+pub mod tls;
+#[cfg(feature = "native")]
+pub mod auth;
+pub mod limits;
+#[cfg(feature = "native")]
+pub mod search;
+#[cfg(feature = "native")]
+pub mod doctor;
+#[cfg(feature = "native")]
+pub mod logging;
+#[cfg(feature = "native")]
+pub mod metrics;
+#[cfg(feature = "native")]
+pub mod staking;
+#[cfg(feature = "native")]
+pub mod fees;
+pub mod receipt;
+#[cfg(feature = "native")]
+pub mod ledger;
+#[cfg(feature = "native")]
+pub mod envelope;
+#[cfg(feature = "native")]
+pub mod blinding;
+#[cfg(feature = "native")]
+pub mod clock;
+#[cfg(feature = "native")]
+pub mod probation;
+#[cfg(feature = "native")]
+pub mod governance;
+#[cfg(feature = "native")]
+pub mod reputation;
+#[cfg(feature = "native")]
+pub mod mesh;
+#[cfg(feature = "native")]
+pub mod sequence;
+#[cfg(feature = "native")]
+pub mod events;
+#[cfg(feature = "native")]
+pub mod relay;
+#[cfg(feature = "native")]
+pub mod outbound;
+#[cfg(feature = "native")]
+pub mod peers;
+#[cfg(feature = "native")]
+pub mod binding;
+#[cfg(feature = "native")]
+pub mod transport;
+#[cfg(feature = "native")]
+pub mod negotiation;
+#[cfg(feature = "native")]
+pub mod verifiers;
+#[cfg(feature = "native")]
+pub mod uptime_writer;
+#[cfg(feature = "native")]
+pub mod discovery;
+#[cfg(feature = "native")]
+pub mod health;
+#[cfg(feature = "native")]
+pub mod replay;
+#[cfg(feature = "native")]
+pub mod gossip;
+#[cfg(feature = "native")]
+pub mod dependency;
+#[cfg(feature = "native")]
+pub mod submission_rate;
+#[cfg(feature = "native")]
+pub mod embed;
+#[cfg(feature = "native")]
+pub mod gateway;
+#[cfg(feature = "native")]
+pub mod admission;
+#[cfg(feature = "native")]
+pub mod screening;
+#[cfg(feature = "native")]
+pub mod tenancy;
+#[cfg(feature = "native")]
+pub mod history;
+#[cfg(feature = "native")]
+pub mod bandwidth;
+#[cfg(feature = "native")]
+pub mod reconcile;
+#[cfg(feature = "native")]
+pub mod stall;
+#[cfg(feature = "native")]
+pub mod quorum;
+#[cfg(feature = "native")]
+pub mod cubic_dlt;
+#[cfg(feature = "native")]
+pub mod utxo;
+#[cfg(feature = "native")]
+pub mod idempotency;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+#[cfg(feature = "native")]
 pub use node::*;
 pub use crypto::*;
 pub use crypto::{generate_keypair, sign_data, hash_data};
 pub use error::*;
+pub use address::Address;
+pub use version::{BuildInfo, parse_version, versions_too_far_apart, CRATE_VERSION, GIT_SHA, BUILD_TIMESTAMP, MAX_MINOR_VERSION_SKEW};
+#[cfg(feature = "native")]
+pub use config::{ConsensusConfig, FeeConfig, ExportConfig, TlsConfig, AuthConfig, NetworkConfig, LoggingConfig, LogFormat, ClockConfig, NodeConfig, AdmissionConfig, BusinessHours, ScreeningConfig, TenancyConfig, ReconcileConfig};
+#[cfg(feature = "native")]
+pub use equivocation::{EquivocationEvidence, EquivocationDetector, ForkEvidence};
+#[cfg(feature = "native")]
+pub use client::{PclClient, MempoolStage, MempoolEntry, MempoolListing, MempoolPage, PageRequest};
+#[cfg(feature = "native")]
+pub use export::{ExportRecord, ExportPipeline};
+#[cfg(feature = "sql-export")]
+pub use export::SqlExporter;
+#[cfg(feature = "native")]
+pub use webhook::{Subscription, WebhookDelivery, DeliveryStatus, WebhookDispatcher};
+#[cfg(feature = "native")]
+pub use auth::{Scope, ApiKey, ApiKeyUsage, ApiKeyManager};
+pub use limits::{MAX_MESSAGE_SIZE, MAX_TX_WEIGHT, check_tx_weight, decode_json, decode_bincode};
+#[cfg(feature = "native")]
+pub use doctor::{CheckStatus, CheckResult, DoctorReport, DoctorOptions};
+#[cfg(feature = "native")]
+pub use logging::{init_logging, log_level_control, set_log_node_id, LogLevelControl, RotatingFileWriter};
+#[cfg(feature = "native")]
+pub use metrics::{WorkflowStep, StepTiming, WorkflowTimings, Histogram, WorkflowMetrics};
+#[cfg(feature = "native")]
+pub use fees::{FeeMarket, FeeEstimate, REFERENCE_TX_WEIGHT};
+pub use receipt::{ReceiptSignature, TransactionReceipt};
+#[cfg(feature = "native")]
+pub use envelope::EncryptedEnvelope;
+#[cfg(feature = "native")]
+pub use blinding::{AmountOpening, blind_transaction};
+#[cfg(feature = "native")]
+pub use clock::{OffsetSource, ClockStatus, ClockSync};
+#[cfg(feature = "native")]
+pub use probation::{PulseReceipt, ProbationRecord, ProbationStatus, ProbationTracker};
+#[cfg(feature = "native")]
+pub use governance::{ParameterChange, ParameterSet, ProposalSignature, GovernanceProposal, ProposalStatus, TrackedProposal, Governance, governance_quorum};
+#[cfg(feature = "native")]
+pub use ledger::{ApduRequest, encode_derivation_path};
+#[cfg(feature = "ledger")]
+pub use ledger::LedgerSigner;
+#[cfg(feature = "native")]
+pub use reputation::{ReputationEvent, ReputationRecord, ReputationLedger};
+#[cfg(feature = "native")]
+pub use mesh::{MeshHeartbeat, TopicHealth, MeshHealth, MeshMonitor, MESH_TOPICS};
+#[cfg(feature = "native")]
+pub use sequence::{SequenceCertificate, GlobalSequencer};
+#[cfg(feature = "native")]
+pub use relay::{TxAnnouncement, RelayStats, CompactRelay, short_tx_hash, SHORT_HASH_LEN};
+#[cfg(feature = "native")]
+pub use outbound::{MessageClass, ClassStats, OutboundQueues};
+#[cfg(feature = "native")]
+pub use peers::{PeerRecord, AddressBook, MAX_CONSECUTIVE_DIAL_FAILURES};
+#[cfg(feature = "native")]
+pub use binding::{PeerBinding, BindingRegistry, peer_id_for};
+#[cfg(feature = "native")]
+pub use transport::{Transport, Libp2pStub, MemoryNetwork, MemoryTransport, InFlight};
+#[cfg(feature = "native")]
+pub use negotiation::{TaskOffer, OfferSelection, TaskNegotiation};
+#[cfg(feature = "native")]
+pub use uptime_writer::{UptimeWriteBuffer, UptimeWriteStats};
+#[cfg(feature = "native")]
+pub use discovery::{PeerDiscovery, parse_multiaddr};
+#[cfg(feature = "native")]
+pub use health::{Readiness, MIN_READY_PEERS};
+#[cfg(feature = "native")]
+pub use replay::JournalReplay;
+#[cfg(feature = "native")]
+pub use gossip::{GossipParams, GossipMesh, MeshChange, gossip_message_id, relay_hops};
+#[cfg(feature = "native")]
+pub use dependency::{PendingTransaction, TransactionDependency, DependencyGraph, output_parent};
+#[cfg(feature = "native")]
+pub use submission_rate::SubmissionRateLimiter;
+#[cfg(feature = "native")]
+pub use embed::{EmbeddedNode, EventSubscription, TransactionStatus};
+#[cfg(feature = "native")]
+pub use gateway::{transaction_from_submission, Gateway, TransactionView};
+#[cfg(feature = "native")]
+pub use admission::{check_admission, AdmissionDecision, AdmissionPolicy, AdmissionRequest, AllowAll, RuleBasedPolicy};
+#[cfg(feature = "native")]
+pub use screening::{fetch_screening_list, Screening, ScreeningList};
+#[cfg(feature = "native")]
+pub use tenancy::{split_tenant_path, tenant_key, validate_tenant_id, TENANT_PATH_PREFIX};
+#[cfg(feature = "native")]
+pub use bandwidth::{topic_of, BandwidthMeter, TrafficDirection, PeerBandwidth, TopicBytes};
+#[cfg(feature = "native")]
+pub use stall::{StalledTransaction, StallMonitor};
+#[cfg(feature = "native")]
+pub use quorum::{QuorumTracker, ValidationQuorum, CROSS_VALIDATION_TASK};
+#[cfg(feature = "native")]
+pub use cubic_dlt::{home_facet, CubicGeometry, CubicPlacement, FACETS_PER_LAYER};
+#[cfg(feature = "native")]
+pub use utxo::{Transfer, UTXO_EPSILON};
+#[cfg(feature = "native")]
+pub use idempotency::{IdempotencyCheck, IdempotencyRecord, IdempotencyStore, IDEMPOTENCY_TTL_MS, MAX_IDEMPOTENCY_KEY_LEN};
+#[cfg(feature = "native")]
+pub use reconcile::{ReconcileReport, SyncChild, SyncConflict, SyncDescent, SyncLeaf, SyncNode, SyncTree, SyncedTransaction, SYNC_LEAF_LIMIT};
+#[cfg(feature = "native")]
+pub use history::{state_at, HistoricalState, HistoricalUtxo, HistoryRecorder, UtxoCheckpoint, UtxoDelta, UtxoSnapshot, CHECKPOINT_INTERVAL};
+#[cfg(feature = "native")]
+pub use verifiers::{TaskSubject, TaskVerifier, TaskVerifierRegistry, SignatureVerifier, SpendingPowerVerifier, TimestampVerifier, TimestampMathVerifier, DigitalRootVerifier};
+#[cfg(feature = "native")]
+pub use events::{StreamEvent, EventFilter, is_known_stage, FINALIZED_STAGE, SCREENED_STAGE, SYNC_CONFLICT_STAGE, STALLED_STAGE, EXPIRED_STAGE, EVENT_CHANNEL_CAPACITY};
+#[cfg(feature = "native")]
+pub use staking::{StakeStatus, StakeEventKind, StakeEvent, StakePosition, StakeLedger};
+#[cfg(feature = "native")]
+pub use search::{IndexedTransaction, TransactionIndex, TransactionQuery, SearchSort, SearchResults};
 pub use transaction::{
-    TransactionData, RawTransaction, ValidationTask, ValidationTaskType, ProcessingTransaction
+    TransactionData, RawTransaction, ValidationTask, ValidationTaskType, ProcessingTransaction,
+    FeePayer, UserValidationTaskCompletion, TransactionCancellation, submission_signing_bytes, submission_hash, submission_weight,
+    prepare_submission, sign_submission, attach_submission_signature, verify_submission_signature, derive_task_id, WEIGHT_PER_IO, WEIGHT_PER_SIGNATURE
 };
+#[cfg(feature = "native")]
 pub use mempool::*;
+pub use multisig::{MultisigPolicy, PartialSignature, combine_partial_signatures};
+#[cfg(feature = "native")]
 pub use storage::*;
+#[cfg(feature = "native")]
 pub use network::*;
+#[cfg(feature = "native")]
 pub use consensus::*; 
\ No newline at end of file
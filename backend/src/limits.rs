@@ -0,0 +1,86 @@
+// Limits module - size caps applied before and during decoding of untrusted peer and client input
+
+use std::fmt;
+use std::marker::PhantomData;
+use bincode::Options;
+use serde::de::{DeserializeOwned, Deserializer, SeqAccess, Visitor};
+use serde::Deserialize;
+use crate::error::{PclError, Result};
+
+// Largest encoded gossip message a node will accept or send
+pub const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+// Largest HTTP response the client will buffer
+pub const MAX_RESPONSE_SIZE: usize = 16 * 1024 * 1024;
+
+// Element caps for collections inside deserialized types
+pub const MAX_TX_IO: usize = 256; // inputs or outputs per transaction
+pub const MAX_SIGNATURES: usize = 64;
+pub const MAX_VALIDATION_ENTRIES: usize = 1024; // validation tasks or timestamps per raw transaction
+
+// Largest transaction weight (see TransactionData::weight) accepted for validation
+pub const MAX_TX_WEIGHT: u64 = 16 * 1024;
+
+fn check_size(len: usize, max: usize) -> Result<()> {
+    if len > max {
+        return Err(PclError::Validation(format!(
+            "Message of {} bytes exceeds the {} byte limit", len, max
+        )));
+    }
+    Ok(())
+}
+
+pub fn check_tx_weight(weight: u64) -> Result<()> {
+    if weight > MAX_TX_WEIGHT {
+        return Err(PclError::Validation(format!(
+            "Transaction weight {} exceeds the {} limit", weight, MAX_TX_WEIGHT
+        )));
+    }
+    Ok(())
+}
+
+// Rejects oversized input before serde_json gets to allocate anything for it
+pub fn decode_json<T: DeserializeOwned>(bytes: &[u8], max: usize) -> Result<T> {
+    check_size(bytes.len(), max)?;
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+// Same wire format as bincode::serialize, but a length prefix claiming more than `max` bytes
+// fails instead of preallocating
+pub fn decode_bincode<T: DeserializeOwned>(bytes: &[u8], max: usize) -> Result<T> {
+    check_size(bytes.len(), max)?;
+    Ok(bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(max as u64)
+        .deserialize(bytes)?)
+}
+
+// Vec deserializer that stops at MAX elements instead of trusting the declared length
+struct BoundedVecVisitor<T, const MAX: usize>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>, const MAX: usize> Visitor<'de> for BoundedVecVisitor<T, MAX> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", MAX)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Vec<T>, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(MAX));
+        while let Some(item) = seq.next_element()? {
+            if items.len() == MAX {
+                return Err(serde::de::Error::custom(format!("sequence exceeds {} elements", MAX)));
+            }
+            items.push(item);
+        }
+        Ok(items)
+    }
+}
+
+pub fn bounded_vec<'de, D, T, const MAX: usize>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_seq(BoundedVecVisitor::<T, MAX>(PhantomData))
+}
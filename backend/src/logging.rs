@@ -0,0 +1,288 @@
+// Logging module - text or JSON log lines, optionally written to a size-rotated file
+//
+// The filter sits behind a reload handle, so operators can raise or lower the level of one module,
+// e.g. pcl_backend::network=trace, through PUT /admin/log-level without restarting the node.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields, MakeWriter};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+use crate::config::{LogFormat, LoggingConfig};
+use crate::error::{PclError, Result};
+
+// Known once the node identity exists, which is after logging has started
+static NODE_ID: RwLock<Option<String>> = RwLock::new(None);
+
+// Adds node_id to every JSON line from here on
+pub fn set_log_node_id(node_id: &str) {
+    if let Ok(mut current) = NODE_ID.write() {
+        *current = Some(node_id.to_string());
+    }
+}
+
+// Filter control of the installed subscriber, once init_logging has run
+static LOG_LEVELS: OnceLock<LogLevelControl> = OnceLock::new();
+
+pub fn log_level_control() -> Option<&'static LogLevelControl> {
+    LOG_LEVELS.get()
+}
+
+// Installs the global subscriber. log::* calls in the rest of the crate are bridged into it.
+pub fn init_logging(config: &LoggingConfig) -> Result<()> {
+    let directives = std::env::var("RUST_LOG").unwrap_or_else(|_| config.level.clone());
+    let (filter, control) = LogLevelControl::new(&directives)?;
+
+    let (writer, ansi) = match &config.file {
+        Some(path) => (BoxMakeWriter::new(RotatingFileWriter::new(path, config.max_file_bytes(), config.max_files)?), false),
+        None => (BoxMakeWriter::new(io::stderr), true),
+    };
+    let registry = tracing_subscriber::registry().with(filter);
+    let result = match config.format {
+        LogFormat::Text => registry.with(tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi)).try_init(),
+        LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().fmt_fields(JsonFields::new()).event_format(JsonEventFormat).with_writer(writer))
+            .try_init(),
+    };
+    result.map_err(|e| PclError::Config(format!("could not install logger: {}", e)))?;
+    let _ = LOG_LEVELS.set(control);
+    Ok(())
+}
+
+// The startup directives plus per-target levels set at runtime, which are appended so they win over
+// anything the startup directives said about the same target
+pub struct LogLevelControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    base: String,
+    overrides: Mutex<BTreeMap<String, LevelFilter>>, // target -> level
+}
+
+impl LogLevelControl {
+    // The returned layer goes first on the registry; the control reloads it
+    pub fn new(directives: &str) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let (layer, handle) = reload::Layer::new(parse_filter(directives)?);
+        Ok((layer, Self { handle, base: directives.to_string(), overrides: Mutex::new(BTreeMap::new()) }))
+    }
+
+    // Sets the level of a module path such as pcl_backend::network; returns the directives now in effect
+    pub fn set_level(&self, target: &str, level: &str) -> Result<String> {
+        if target.is_empty() || !target.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':') {
+            return Err(PclError::Validation(format!("Invalid log target '{}': expected a module path", target)));
+        }
+        let level: LevelFilter = level.parse()
+            .map_err(|_| PclError::Validation(format!("Invalid log level '{}': expected trace, debug, info, warn, error or off", level)))?;
+
+        let mut overrides = self.overrides.lock().map_err(|_| PclError::Config("log level lock poisoned".to_string()))?;
+        let mut updated = overrides.clone();
+        updated.insert(target.to_string(), level);
+        let directives = self.render(&updated);
+        self.handle.reload(parse_filter(&directives)?)
+            .map_err(|e| PclError::Config(format!("could not reload log filter: {}", e)))?;
+        *overrides = updated;
+
+        // Records from the log crate are dropped before reaching the filter unless log's own maximum allows them
+        log::set_max_level(as_log_level(LevelFilter::current()));
+        Ok(directives)
+    }
+
+    pub fn directives(&self) -> String {
+        self.overrides.lock().map(|overrides| self.render(&overrides)).unwrap_or_else(|_| self.base.clone())
+    }
+
+    fn render(&self, overrides: &BTreeMap<String, LevelFilter>) -> String {
+        let mut directives: Vec<String> = vec![self.base.clone()];
+        directives.extend(overrides.iter().map(|(target, level)| format!("{}={}", target, level)));
+        directives.retain(|directive| !directive.is_empty());
+        directives.join(",")
+    }
+}
+
+fn parse_filter(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::try_new(directives)
+        .map_err(|e| PclError::Config(format!("invalid log filter '{}': {}", directives, e)))
+}
+
+fn as_log_level(filter: LevelFilter) -> log::LevelFilter {
+    match filter.into_level() {
+        None => log::LevelFilter::Off,
+        Some(Level::ERROR) => log::LevelFilter::Error,
+        Some(Level::WARN) => log::LevelFilter::Warn,
+        Some(Level::INFO) => log::LevelFilter::Info,
+        Some(Level::DEBUG) => log::LevelFilter::Debug,
+        Some(Level::TRACE) => log::LevelFilter::Trace,
+    }
+}
+
+// One JSON object per line: timestamp, level, target, node_id, the fields of any enclosing span
+// (the workflow span carries raw_tx_id), then the event's own fields such as message and peer_id
+pub struct JsonEventFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonEventFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut line = Map::new();
+        line.insert("timestamp".to_string(), chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true).into());
+        line.insert("level".to_string(), metadata.level().as_str().into());
+        line.insert("target".to_string(), metadata.target().into());
+        if let Some(node_id) = NODE_ID.read().ok().and_then(|node_id| node_id.clone()) {
+            line.insert("node_id".to_string(), node_id.into());
+        }
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                // Span fields were recorded by JsonFields, so they are already a JSON object
+                if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                    if let Ok(Value::Object(span_fields)) = serde_json::from_str(&fields.fields) {
+                        line.extend(span_fields);
+                    }
+                }
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut line));
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl JsonVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        match field.name() {
+            // Records bridged from the log crate carry their real target as a field
+            "log.target" => {
+                self.0.insert("target".to_string(), value);
+            }
+            name if name.starts_with("log.") => {}
+            name => {
+                self.0.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for JsonVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::String(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+}
+
+// Appends to a log file and rotates it once the next line would push it past max_bytes
+#[derive(Clone)]
+pub struct RotatingFileWriter {
+    inner: Arc<Mutex<RotatingFile>>,
+}
+
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    pub fn new<P: AsRef<Path>>(path: P, max_bytes: u64, max_files: usize) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RotatingFile { path, max_bytes, max_files, file, size })),
+        })
+    }
+}
+
+// <file>.1 is the most recent rotated file, <file>.<max_files> the oldest kept
+pub fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+impl RotatingFile {
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files > 0 {
+            let oldest = rotated_log_path(&self.path, self.max_files);
+            if oldest.exists() {
+                fs::remove_file(oldest)?;
+            }
+            for index in (1..self.max_files).rev() {
+                let from = rotated_log_path(&self.path, index);
+                if from.exists() {
+                    fs::rename(from, rotated_log_path(&self.path, index + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated_log_path(&self.path, 1))?;
+        }
+        // With max_files = 0 this just truncates the active file
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut file = self.inner.lock().map_err(|_| io::Error::other("log file lock poisoned"))?;
+        // A line bigger than max_bytes still gets written, alone in a fresh file
+        if file.size > 0 && file.size + buf.len() as u64 > file.max_bytes {
+            file.rotate()?;
+        }
+        file.file.write_all(buf)?;
+        file.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut file = self.inner.lock().map_err(|_| io::Error::other("log file lock poisoned"))?;
+        file.file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileWriter {
+    type Writer = RotatingFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
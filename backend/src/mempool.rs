@@ -1,12 +1,18 @@
 // Mempool module - TODO: Implement mempool functionality 
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 use crate::transaction::{RawTransaction, ValidationTask, ProcessingTransaction, TransactionData};
+use crate::client::MempoolStage;
+use crate::dependency::{DependencyGraph, PendingTransaction};
 use crate::error::{PclError, Result};
+use crate::limits::check_tx_weight;
+use crate::crypto::address_matches_public_key;
+use ed25519_dalek::VerifyingKey;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawTxMempool {
@@ -40,6 +46,38 @@ pub struct TxMempool {
     pub finalized_transactions: HashMap<String, FinalizedTransaction>,
     pub xmbl_integrated: HashMap<String, XmblIntegration>, // tx_id -> xmbl_data
     pub utxo_pool: HashMap<String, UtxoEntry>, // utxo_id -> utxo
+    #[serde(default)]
+    pub finalization_claims: HashMap<String, FinalizationClaim>, // raw_tx_id -> winning claim
+}
+
+// A leader's claim to have processed a raw transaction, used to resolve forks when two
+// leaders both produce a processing entry for the same raw_tx_id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinalizationClaim {
+    pub raw_tx_id: String,
+    pub leader_id: String,
+    pub leader_public_key: String, // hex encoded
+    pub averaged_timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictResolution {
+    // No competing claim existed
+    Accepted,
+    // The new claim won, the returned previous claim must be invalidated
+    Replaced(FinalizationClaim),
+    // The existing claim won, the new claim must be invalidated
+    Rejected(FinalizationClaim),
+    // Same leader re-submitting the same claim
+    Duplicate,
+}
+
+impl FinalizationClaim {
+    // Deterministic tie-break: earliest averaged timestamp wins, equal timestamps go to the
+    // lowest leader public key, so every node picks the same entry regardless of arrival order
+    pub fn beats(&self, other: &FinalizationClaim) -> bool {
+        (self.averaged_timestamp, &self.leader_public_key) < (other.averaged_timestamp, &other.leader_public_key)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,21 +191,76 @@ impl MempoolManager {
         self.processing_tx.add_transaction(tx)
     }
 
-    pub fn finalize_transaction(&mut self, tx_id: String, validator_sig: String) -> Result<()> {
-        self.tx.finalize_transaction(tx_id, validator_sig)
+    pub fn record_finalization_claim(&mut self, claim: FinalizationClaim) -> ConflictResolution {
+        self.tx.record_finalization_claim(claim)
     }
 
     pub fn record_pulse(&mut self, node_id: String, family_id: Uuid, response_time_ms: u64) -> Result<()> {
         self.uptime.record_pulse(node_id, family_id, response_time_ms)
     }
 
-    pub fn invalidate_transaction(&mut self, tx_id: &str) -> Result<()> {
-        // Remove from all mempools
-        let _ = self.raw_tx.remove_transaction(tx_id);
-        let _ = self.processing_tx.remove_transaction(tx_id);
-        let _ = self.validation_tasks.remove_tasks_for_tx(tx_id);
-        let _ = self.locked_utxo.unlock_utxos_for_tx(tx_id);
-        Ok(())
+    // Removes the transaction and every pending transaction spending its outputs from all mempools.
+    // Returns what was removed, the transaction itself first.
+    pub fn invalidate_transaction(&mut self, tx_id: &str) -> Result<Vec<String>> {
+        let mut invalidated = vec![tx_id.to_string()];
+        invalidated.extend(self.dependency_graph().descendants(tx_id));
+        for tx_id in &invalidated {
+            let _ = self.raw_tx.remove_transaction(tx_id);
+            let _ = self.processing_tx.remove_transaction(tx_id);
+            let _ = self.validation_tasks.remove_tasks_for_tx(tx_id);
+            let _ = self.locked_utxo.unlock_utxos_for_tx(tx_id);
+        }
+        Ok(invalidated)
+    }
+
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        dependency_graph_of(&self.raw_tx, &self.processing_tx)
+    }
+
+    // Submission or gossip: the transaction enters the raw stage with its inputs locked
+    pub fn submit_transaction(&mut self, tx: RawTransaction) -> Result<()> {
+        submit_into(&mut self.raw_tx, &mut self.locked_utxo, &self.processing_tx, &self.tx, tx)
+    }
+
+    // Replace-by-fee: see replace_in. Returns the invalidated transactions, the original first.
+    pub fn replace_transaction(&mut self, original_id: &str, replacement: RawTransaction, sender_key: &VerifyingKey) -> Result<Vec<String>> {
+        replace_in(
+            &mut self.raw_tx, &mut self.validation_tasks, &mut self.locked_utxo, &mut self.processing_tx, &self.tx,
+            original_id, replacement, sender_key,
+        )
+    }
+
+    // Validation complete: the leader's processing entry replaces the raw one
+    pub fn start_processing(&mut self, tx_id: &str, leader_id: &str, leader_sig: &str) -> Result<()> {
+        start_processing_in(&mut self.raw_tx, &mut self.processing_tx, tx_id, leader_id, leader_sig)
+    }
+
+    // Spends the inputs, creates the outputs and moves the processing entry to finalized
+    pub fn finalize_processing(&mut self, tx_id: &str, validator_sig: &str) -> Result<()> {
+        finalize_in(&mut self.locked_utxo, &mut self.processing_tx, &mut self.tx, tx_id, validator_sig)
+    }
+
+    // Every stage currently holding the transaction; more than one means a transition went wrong
+    pub fn stages_of(&self, tx_id: &str) -> Vec<MempoolStage> {
+        let mut stages = Vec::new();
+        if self.raw_tx.transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Raw);
+        }
+        if self.processing_tx.transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Processing);
+        }
+        if self.tx.finalized_transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Final);
+        }
+        stages
+    }
+
+    pub fn tracked_transactions(&self) -> HashSet<String> {
+        self.raw_tx.transactions.keys()
+            .chain(self.processing_tx.transactions.keys())
+            .chain(self.tx.finalized_transactions.keys())
+            .cloned()
+            .collect()
     }
 
     pub fn get_mempool_stats(&self) -> MempoolStats {
@@ -180,6 +273,489 @@ impl MempoolManager {
             active_nodes: self.uptime.pulse_data.len(),
         }
     }
+
+    // Periodic GC: a transaction invalidated on another node never unlocks its inputs here
+    pub fn collect_stale_locks(&mut self, ttl: chrono::Duration, now: DateTime<Utc>) -> Vec<String> {
+        let live: HashSet<String> = self.raw_tx.transactions.keys()
+            .chain(self.processing_tx.transactions.keys())
+            .cloned()
+            .collect();
+        self.locked_utxo.collect_garbage(&live, ttl, now)
+    }
+
+    // Consistency pass for a mempool snapshot loaded after a crash: the individual pools are written
+    // independently, so a restart can leave references between them dangling
+    pub fn recover(&mut self) -> RecoveryReport {
+        let mut report = RecoveryReport::default();
+
+        // Processing entries whose transaction already finalized were never cleaned up
+        let mut finalized: Vec<String> = self.processing_tx.transactions.keys()
+            .filter(|tx_id| self.tx.finalized_transactions.contains_key(*tx_id))
+            .cloned()
+            .collect();
+        finalized.sort();
+        for tx_id in &finalized {
+            let _ = self.processing_tx.remove_transaction(tx_id);
+        }
+        report.finalized_processing_removed = finalized;
+
+        // Rebuild missing timestamp/signature indexes from the entry itself
+        let mut repaired = Vec::new();
+        for (tx_id, tx) in &self.processing_tx.transactions {
+            let missing_timestamp = !self.processing_tx.timestamp_averages.contains_key(tx_id);
+            let missing_signature = !self.processing_tx.signatures.contains_key(tx_id);
+            if missing_timestamp || missing_signature {
+                self.processing_tx.timestamp_averages.insert(tx_id.clone(), tx.timestamp);
+                self.processing_tx.signatures.insert(tx_id.clone(), tx.sig.clone());
+                repaired.push(tx_id.clone());
+            }
+        }
+        repaired.sort();
+        report.processing_indexes_repaired = repaired;
+
+        let processing = &self.processing_tx.transactions;
+        let before = self.processing_tx.timestamp_averages.len() + self.processing_tx.signatures.len();
+        self.processing_tx.timestamp_averages.retain(|tx_id, _| processing.contains_key(tx_id));
+        self.processing_tx.signatures.retain(|tx_id, _| processing.contains_key(tx_id));
+        report.dangling_index_entries += before - self.processing_tx.timestamp_averages.len() - self.processing_tx.signatures.len();
+
+        let live: Vec<&String> = self.raw_tx.transactions.keys()
+            .chain(self.processing_tx.transactions.keys())
+            .collect();
+
+        // Locks only make sense while the locking transaction is still in flight
+        let mut orphaned_locks: Vec<String> = self.locked_utxo.locked_utxos.values()
+            .filter(|lock| !live.contains(&&lock.locked_by_tx))
+            .map(|lock| lock.utxo_id.clone())
+            .collect();
+        orphaned_locks.sort();
+        for utxo_id in &orphaned_locks {
+            let _ = self.locked_utxo.unlock_utxo(utxo_id);
+        }
+        report.orphaned_locks_released = orphaned_locks;
+
+        // Task ids are prefixed with their transaction id
+        let mut orphaned_tasks: Vec<String> = self.validation_tasks.tasks.keys()
+            .filter(|task_id| !live.iter().any(|tx_id| task_id.starts_with(tx_id.as_str())))
+            .cloned()
+            .collect();
+        orphaned_tasks.sort();
+        for task_id in &orphaned_tasks {
+            self.validation_tasks.tasks.remove(task_id);
+        }
+        report.orphaned_tasks_removed = orphaned_tasks;
+
+        // Secondary indexes
+        let tasks = &self.validation_tasks.tasks;
+        for task_ids in self.validation_tasks.assigned_tasks.values_mut().chain(self.validation_tasks.user_tasks.values_mut()) {
+            let before = task_ids.len();
+            task_ids.retain(|task_id| tasks.contains_key(task_id));
+            report.dangling_index_entries += before - task_ids.len();
+        }
+        self.validation_tasks.assigned_tasks.retain(|_, task_ids| !task_ids.is_empty());
+        self.validation_tasks.user_tasks.retain(|_, task_ids| !task_ids.is_empty());
+
+        let locked = &self.locked_utxo.locked_utxos;
+        for utxo_ids in self.locked_utxo.tx_locks.values_mut() {
+            let before = utxo_ids.len();
+            utxo_ids.retain(|utxo_id| locked.contains_key(utxo_id));
+            report.dangling_index_entries += before - utxo_ids.len();
+        }
+        self.locked_utxo.tx_locks.retain(|_, utxo_ids| !utxo_ids.is_empty());
+
+        let raw = &self.raw_tx.transactions;
+        let before = self.raw_tx.hash_to_tx.len();
+        self.raw_tx.hash_to_tx.retain(|_, tx_id| raw.contains_key(tx_id));
+        report.dangling_index_entries += before - self.raw_tx.hash_to_tx.len();
+        for tx_ids in self.raw_tx.tx_by_user.values_mut() {
+            let before = tx_ids.len();
+            tx_ids.retain(|tx_id| raw.contains_key(tx_id));
+            report.dangling_index_entries += before - tx_ids.len();
+        }
+        self.raw_tx.tx_by_user.retain(|_, tx_ids| !tx_ids.is_empty());
+
+        report
+    }
+}
+
+// Concurrent mempool: every pool has its own lock so raw submissions, task updates and
+// finalization don't serialize behind each other.
+//
+// Lock ordering: when more than one pool is held at once, acquire them in field order
+// (raw_tx, validation_tasks, locked_utxo, processing_tx, tx, uptime) and never take an
+// earlier pool while holding a later one.
+pub struct SharedMempool {
+    pub raw_tx: RwLock<RawTxMempool>,
+    pub validation_tasks: RwLock<ValidationTasksMempool>,
+    pub locked_utxo: RwLock<LockedUtxoMempool>,
+    pub processing_tx: RwLock<ProcessingTxMempool>,
+    pub tx: RwLock<TxMempool>,
+    pub uptime: RwLock<UptimeMempool>,
+}
+
+impl Default for SharedMempool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<MempoolManager> for SharedMempool {
+    fn from(mempool: MempoolManager) -> Self {
+        Self {
+            raw_tx: RwLock::new(mempool.raw_tx),
+            validation_tasks: RwLock::new(mempool.validation_tasks),
+            locked_utxo: RwLock::new(mempool.locked_utxo),
+            processing_tx: RwLock::new(mempool.processing_tx),
+            tx: RwLock::new(mempool.tx),
+            uptime: RwLock::new(mempool.uptime),
+        }
+    }
+}
+
+impl SharedMempool {
+    pub fn new() -> Self {
+        MempoolManager::new().into()
+    }
+
+    pub async fn add_raw_transaction(&self, tx: RawTransaction) -> Result<()> {
+        let hash = RawTxMempool::transaction_hash(&tx)?;
+        self.raw_tx.write().await.insert_hashed(tx, hash);
+        Ok(())
+    }
+
+    pub async fn add_validation_task(&self, task: ValidationTask) -> Result<()> {
+        self.validation_tasks.write().await.add_task(task)
+    }
+
+    pub async fn stages_of(&self, tx_id: &str) -> Vec<MempoolStage> {
+        let mut stages = Vec::new();
+        if self.raw_tx.read().await.transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Raw);
+        }
+        if self.processing_tx.read().await.transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Processing);
+        }
+        if self.tx.read().await.finalized_transactions.contains_key(tx_id) {
+            stages.push(MempoolStage::Final);
+        }
+        stages
+    }
+
+    pub async fn lock_utxo(&self, utxo_id: String, amount: f64, tx_id: String) -> Result<()> {
+        self.locked_utxo.write().await.lock_utxo(utxo_id, amount, tx_id)
+    }
+
+    pub async fn add_processing_transaction(&self, tx: ProcessingTransaction) -> Result<()> {
+        self.processing_tx.write().await.add_transaction(tx)
+    }
+
+    pub async fn record_finalization_claim(&self, claim: FinalizationClaim) -> ConflictResolution {
+        self.tx.write().await.record_finalization_claim(claim)
+    }
+
+    pub async fn invalidate_transaction(&self, tx_id: &str) -> Result<Vec<String>> {
+        let mut raw_tx = self.raw_tx.write().await;
+        let mut validation_tasks = self.validation_tasks.write().await;
+        let mut locked_utxo = self.locked_utxo.write().await;
+        let mut processing_tx = self.processing_tx.write().await;
+        let mut invalidated = vec![tx_id.to_string()];
+        invalidated.extend(dependency_graph_of(&raw_tx, &processing_tx).descendants(tx_id));
+        for tx_id in &invalidated {
+            let _ = raw_tx.remove_transaction(tx_id);
+            let _ = validation_tasks.remove_tasks_for_tx(tx_id);
+            let _ = locked_utxo.unlock_utxos_for_tx(tx_id);
+            let _ = processing_tx.remove_transaction(tx_id);
+        }
+        Ok(invalidated)
+    }
+
+    pub async fn dependency_graph(&self) -> DependencyGraph {
+        let raw_tx = self.raw_tx.read().await;
+        let processing_tx = self.processing_tx.read().await;
+        dependency_graph_of(&raw_tx, &processing_tx)
+    }
+
+    pub async fn submit_transaction(&self, tx: RawTransaction) -> Result<()> {
+        let mut raw_tx = self.raw_tx.write().await;
+        let mut locked_utxo = self.locked_utxo.write().await;
+        let processing_tx = self.processing_tx.read().await;
+        let tx_pool = self.tx.read().await;
+        submit_into(&mut raw_tx, &mut locked_utxo, &processing_tx, &tx_pool, tx)
+    }
+
+    pub async fn replace_transaction(&self, original_id: &str, replacement: RawTransaction, sender_key: &VerifyingKey) -> Result<Vec<String>> {
+        let mut raw_tx = self.raw_tx.write().await;
+        let mut validation_tasks = self.validation_tasks.write().await;
+        let mut locked_utxo = self.locked_utxo.write().await;
+        let mut processing_tx = self.processing_tx.write().await;
+        let tx_pool = self.tx.read().await;
+        replace_in(&mut raw_tx, &mut validation_tasks, &mut locked_utxo, &mut processing_tx, &tx_pool, original_id, replacement, sender_key)
+    }
+
+    pub async fn start_processing(&self, tx_id: &str, leader_id: &str, leader_sig: &str) -> Result<()> {
+        let mut raw_tx = self.raw_tx.write().await;
+        let mut processing_tx = self.processing_tx.write().await;
+        start_processing_in(&mut raw_tx, &mut processing_tx, tx_id, leader_id, leader_sig)
+    }
+
+    pub async fn finalize_processing(&self, tx_id: &str, validator_sig: &str) -> Result<()> {
+        let mut locked_utxo = self.locked_utxo.write().await;
+        let mut processing_tx = self.processing_tx.write().await;
+        let mut tx_pool = self.tx.write().await;
+        finalize_in(&mut locked_utxo, &mut processing_tx, &mut tx_pool, tx_id, validator_sig)
+    }
+
+    pub async fn get_mempool_stats(&self) -> MempoolStats {
+        MempoolStats {
+            raw_tx_count: self.raw_tx.read().await.transactions.len(),
+            validation_tasks_count: self.validation_tasks.read().await.tasks.len(),
+            locked_utxo_count: self.locked_utxo.read().await.locked_utxos.len(),
+            processing_tx_count: self.processing_tx.read().await.transactions.len(),
+            finalized_tx_count: self.tx.read().await.finalized_transactions.len(),
+            active_nodes: self.uptime.read().await.pulse_data.len(),
+        }
+    }
+
+    // Consistent copy for persistence; holds every pool (in lock order) while cloning
+    pub async fn snapshot(&self) -> MempoolManager {
+        let raw_tx = self.raw_tx.read().await;
+        let validation_tasks = self.validation_tasks.read().await;
+        let locked_utxo = self.locked_utxo.read().await;
+        let processing_tx = self.processing_tx.read().await;
+        let tx = self.tx.read().await;
+        let uptime = self.uptime.read().await;
+        MempoolManager {
+            raw_tx: raw_tx.clone(),
+            validation_tasks: validation_tasks.clone(),
+            locked_utxo: locked_utxo.clone(),
+            processing_tx: processing_tx.clone(),
+            tx: tx.clone(),
+            uptime: uptime.clone(),
+        }
+    }
+}
+
+// Stage transitions shared by MempoolManager and SharedMempool, which hand in the pools they hold.
+// A transaction sits in at most one of raw, processing and finalized, and its inputs stay locked
+// from submission until it is finalized or invalidated.
+
+fn dependency_graph_of(raw_tx: &RawTxMempool, processing_tx: &ProcessingTxMempool) -> DependencyGraph {
+    let raw = raw_tx.transactions.values()
+        .map(|tx| (tx.raw_tx_id.clone(), MempoolStage::Raw, &tx.tx_data));
+    let processing = processing_tx.transactions.values()
+        .map(|tx| (tx.tx_id.clone(), MempoolStage::Processing, &tx.tx_data));
+    DependencyGraph::build(raw.chain(processing).map(|(tx_id, stage, data)| PendingTransaction {
+        tx_id,
+        stage,
+        inputs: data.from.iter().map(|(utxo_id, _)| utxo_id.clone()).collect(),
+    }))
+}
+
+// The outputs finalizing a transaction creates: one per recipient, then the change
+pub fn output_utxos(tx_id: &str, tx_data: &TransactionData) -> Vec<(String, f64, String)> {
+    let mut outputs: Vec<(String, f64, String)> = tx_data.to.iter().enumerate()
+        .map(|(i, (address, amount))| (format!("{}:{}", tx_id, i), *amount, address.to_string()))
+        .collect();
+    if let Some(change) = tx_data.change.filter(|change| *change > 0.0) {
+        outputs.push((format!("{}:change", tx_id), change, tx_data.user.to_string()));
+    }
+    outputs
+}
+
+// An output of a transaction still in the raw or processing mempool, as (amount, owner)
+fn pending_output(raw_tx: &RawTxMempool, processing_tx: &ProcessingTxMempool, utxo_id: &str) -> Option<(f64, String)> {
+    let parent = crate::dependency::output_parent(utxo_id)?;
+    let data = raw_tx.transactions.get(parent).map(|tx| &tx.tx_data)
+        .or_else(|| processing_tx.transactions.get(parent).map(|tx| &tx.tx_data))?;
+    output_utxos(parent, data).into_iter()
+        .find(|(output_id, _, _)| output_id == utxo_id)
+        .map(|(_, amount, owner)| (amount, owner))
+}
+
+// A transaction already known at any stage is a duplicate gossip and accepted as a no-op. Otherwise
+// every input must be an unspent UTXO of the sender, or an output of a pending transaction paying the
+// sender, that no other transaction has locked. Spending a pending output makes the transaction a
+// child of that one: it cannot finalize first and is invalidated with it.
+fn submit_into(
+    raw_tx: &mut RawTxMempool,
+    locked_utxo: &mut LockedUtxoMempool,
+    processing_tx: &ProcessingTxMempool,
+    tx_pool: &TxMempool,
+    tx: RawTransaction,
+) -> Result<()> {
+    let tx_id = tx.raw_tx_id.clone();
+    if raw_tx.transactions.contains_key(&tx_id)
+        || processing_tx.transactions.contains_key(&tx_id)
+        || tx_pool.finalized_transactions.contains_key(&tx_id)
+    {
+        return Ok(());
+    }
+
+    let data = &tx.tx_data;
+    if data.from.is_empty() || data.to.iter().any(|(_, amount)| !amount.is_finite() || *amount <= 0.0) || data.stake < 0.0 || data.fee < 0.0 {
+        return Err(PclError::Transaction(format!(
+            "Transaction {} needs inputs, positive outputs and a non-negative stake and fee", tx_id
+        )));
+    }
+    if !data.validate_amounts() {
+        return Err(PclError::Transaction(format!("Transaction {} spends more than its inputs", tx_id)));
+    }
+    check_tx_weight(data.weight())?;
+    let owner = data.user.to_string();
+    let mut seen = HashSet::new();
+    for (utxo_id, amount) in &data.from {
+        if !seen.insert(utxo_id) {
+            return Err(PclError::Transaction(format!("Transaction {} spends {} twice", tx_id, utxo_id)));
+        }
+        let spendable = match tx_pool.utxo_pool.get(utxo_id) {
+            Some(utxo) => !utxo.spent && utxo.owner == owner && utxo.amount == *amount,
+            None => pending_output(raw_tx, processing_tx, utxo_id).is_some_and(|(value, to)| to == owner && value == *amount),
+        };
+        if !spendable {
+            return Err(PclError::Transaction(format!(
+                "Transaction {} spends {}, which is not an unspent {} XMBL UTXO of {}", tx_id, utxo_id, amount, owner
+            )));
+        }
+        if let Some(lock) = locked_utxo.locked_utxos.get(utxo_id).filter(|lock| lock.locked_by_tx != tx_id) {
+            return Err(PclError::Mempool(format!("UTXO {} is locked by transaction {}", utxo_id, lock.locked_by_tx)));
+        }
+    }
+
+    for (utxo_id, amount) in &data.from {
+        locked_utxo.lock_utxo(utxo_id.clone(), *amount, tx_id.clone())?;
+    }
+    raw_tx.add_transaction(tx)
+}
+
+// A stuck raw transaction may be replaced by one from the same sender spending exactly the same
+// inputs with a strictly higher fee, signed with sender_key, which must be the sender's. The replacement must pass every submission check as if the
+// original's locks were not there; if it does, the original and everything spending its outputs
+// leave the mempools. Once processing has started the original can no longer be replaced.
+fn replace_in(
+    raw_tx: &mut RawTxMempool,
+    validation_tasks: &mut ValidationTasksMempool,
+    locked_utxo: &mut LockedUtxoMempool,
+    processing_tx: &mut ProcessingTxMempool,
+    tx_pool: &TxMempool,
+    original_id: &str,
+    replacement: RawTransaction,
+    sender_key: &VerifyingKey,
+) -> Result<Vec<String>> {
+    let Some(original) = raw_tx.get_transaction(original_id).map(|tx| tx.tx_data.clone()) else {
+        return Err(PclError::Mempool(if processing_tx.transactions.contains_key(original_id) {
+            format!("Transaction {} is already processing and can no longer be replaced", original_id)
+        } else {
+            format!("Transaction {} is not in the raw mempool", original_id)
+        }));
+    };
+    let replacement_id = replacement.raw_tx_id.clone();
+    let data = &replacement.tx_data;
+    let inputs = |data: &TransactionData| data.from.iter().map(|(utxo_id, _)| utxo_id.clone()).collect::<BTreeSet<_>>();
+    if replacement_id == original_id || data.user != original.user || inputs(data) != inputs(&original) {
+        return Err(PclError::Transaction(format!(
+            "Transaction {} does not replace {}: it must come from the same sender and spend the same inputs", replacement_id, original_id
+        )));
+    }
+    if !address_matches_public_key(data.user.as_str(), sender_key) || !data.verify_signature_with_public_key(sender_key) {
+        return Err(PclError::SignatureVerification(format!(
+            "Replacement {} for {} is not signed by its sender {}", replacement_id, original_id, data.user
+        )));
+    }
+    if data.fee <= original.fee {
+        return Err(PclError::Transaction(format!(
+            "Replacement {} must pay a higher fee than {} ({} <= {})", replacement_id, original_id, data.fee, original.fee
+        )));
+    }
+
+    let mut invalidated = vec![original_id.to_string()];
+    invalidated.extend(dependency_graph_of(raw_tx, processing_tx).descendants(original_id));
+    locked_utxo.unlock_utxos_for_tx(original_id)?;
+    if let Err(e) = submit_into(raw_tx, locked_utxo, processing_tx, tx_pool, replacement) {
+        for (utxo_id, amount) in &original.from {
+            locked_utxo.lock_utxo(utxo_id.clone(), *amount, original_id.to_string())?;
+        }
+        return Err(e);
+    }
+    for tx_id in &invalidated {
+        let _ = raw_tx.remove_transaction(tx_id);
+        let _ = validation_tasks.remove_tasks_for_tx(tx_id);
+        let _ = locked_utxo.unlock_utxos_for_tx(tx_id);
+        let _ = processing_tx.remove_transaction(tx_id);
+    }
+    Ok(invalidated)
+}
+
+fn start_processing_in(
+    raw_tx: &mut RawTxMempool,
+    processing_tx: &mut ProcessingTxMempool,
+    tx_id: &str,
+    leader_id: &str,
+    leader_sig: &str,
+) -> Result<()> {
+    let raw = raw_tx.get_transaction(tx_id)
+        .ok_or_else(|| PclError::Mempool(format!("Transaction {} is not in the raw mempool", tx_id)))?;
+    let entry = ProcessingTransaction::new(tx_id.to_string(), raw.tx_data.clone(), leader_sig.to_string(), leader_id.to_string());
+    raw_tx.remove_transaction(tx_id)?;
+    processing_tx.add_transaction(entry)
+}
+
+fn finalize_in(
+    locked_utxo: &mut LockedUtxoMempool,
+    processing_tx: &mut ProcessingTxMempool,
+    tx_pool: &mut TxMempool,
+    tx_id: &str,
+    validator_sig: &str,
+) -> Result<()> {
+    let entry = processing_tx.transactions.get(tx_id).cloned()
+        .ok_or_else(|| PclError::Mempool(format!("Transaction {} is not in the processing mempool", tx_id)))?;
+    tx_pool.apply_spend(tx_id, &entry.tx_data)?;
+    locked_utxo.unlock_utxos_for_tx(tx_id)?;
+    processing_tx.remove_transaction(tx_id)?;
+
+    let finalized_tx = FinalizedTransaction {
+        tx_id: tx_id.to_string(),
+        xmbl_cubic_root: entry.tx_data.calculate_digital_root() as u8,
+        tx_data: entry.tx_data,
+        validator_signature: validator_sig.to_string(),
+        finalized_at: Utc::now(),
+    };
+    tx_pool.finalized_transactions.insert(tx_id.to_string(), finalized_tx);
+    Ok(())
+}
+
+// What the startup recovery scan found and fixed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    pub orphaned_locks_released: Vec<String>, // utxo ids locked by a tx that no longer exists
+    pub orphaned_tasks_removed: Vec<String>, // task ids whose raw transaction vanished
+    pub finalized_processing_removed: Vec<String>, // processing entries left behind after finalization
+    pub processing_indexes_repaired: Vec<String>, // processing entries missing their timestamp/signature index
+    pub dangling_index_entries: usize, // hash, user and leader index entries pointing at nothing
+}
+
+impl RecoveryReport {
+    pub fn is_clean(&self) -> bool {
+        self.total_repairs() == 0
+    }
+
+    pub fn total_repairs(&self) -> usize {
+        self.orphaned_locks_released.len()
+            + self.orphaned_tasks_removed.len()
+            + self.finalized_processing_removed.len()
+            + self.processing_indexes_repaired.len()
+            + self.dangling_index_entries
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} orphaned locks released, {} orphaned tasks removed, {} finalized processing entries removed, {} processing indexes repaired, {} dangling index entries dropped",
+            self.orphaned_locks_released.len(),
+            self.orphaned_tasks_removed.len(),
+            self.finalized_processing_removed.len(),
+            self.processing_indexes_repaired.len(),
+            self.dangling_index_entries,
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -202,18 +778,26 @@ impl RawTxMempool {
     }
 
     pub fn add_transaction(&mut self, tx: RawTransaction) -> Result<()> {
+        let hash = Self::transaction_hash(&tx)?;
+        self.insert_hashed(tx, hash);
+        Ok(())
+    }
+
+    pub fn transaction_hash(tx: &RawTransaction) -> Result<String> {
+        Ok(hex::encode(crate::crypto::hash_transaction_data(&serde_json::to_vec(&tx.tx_data)?)))
+    }
+
+    // Insert with a precomputed hash, so callers can hash before taking the pool lock
+    pub fn insert_hashed(&mut self, tx: RawTransaction, hash_str: String) {
         let tx_id = tx.raw_tx_id.clone();
-        let user = tx.tx_data.user.clone();
-        
-        // Calculate transaction hash
-        let hash = crate::crypto::hash_transaction_data(&serde_json::to_vec(&tx.tx_data)?);
-        let hash_str = hex::encode(hash);
+        let user = tx.tx_data.user.to_string();
         
         self.hash_to_tx.insert(hash_str, tx_id.clone());
-        self.tx_by_user.entry(user).or_insert_with(Vec::new).push(tx_id.clone());
+        let user_txs = self.tx_by_user.entry(user).or_insert_with(Vec::new);
+        if !user_txs.contains(&tx_id) {
+            user_txs.push(tx_id.clone());
+        }
         self.transactions.insert(tx_id, tx);
-        
-        Ok(())
     }
 
     pub fn remove_transaction(&mut self, tx_id: &str) -> Result<()> {
@@ -224,10 +808,10 @@ impl RawTxMempool {
             self.hash_to_tx.remove(&hash_str);
             
             // Remove from user transactions
-            if let Some(user_txs) = self.tx_by_user.get_mut(&tx.tx_data.user) {
+            if let Some(user_txs) = self.tx_by_user.get_mut(tx.tx_data.user.as_str()) {
                 user_txs.retain(|id| id != tx_id);
                 if user_txs.is_empty() {
-                    self.tx_by_user.remove(&tx.tx_data.user);
+                    self.tx_by_user.remove(tx.tx_data.user.as_str());
                 }
             }
         }
@@ -253,9 +837,13 @@ impl ValidationTasksMempool {
         }
     }
 
+    // Task ids are unique; a second task with the same id is refused
     pub fn add_task(&mut self, task: ValidationTask) -> Result<()> {
         let task_id = task.task_id.clone();
         let leader_id = task.leader_id.clone();
+        if self.tasks.contains_key(&task_id) {
+            return Err(PclError::Mempool(format!("Validation task {} is already in the mempool", task_id)));
+        }
         
         self.assigned_tasks.entry(leader_id).or_insert_with(Vec::new).push(task_id.clone());
         self.tasks.insert(task_id, task);
@@ -263,6 +851,10 @@ impl ValidationTasksMempool {
         Ok(())
     }
 
+    pub fn contains_task(&self, task_id: &str) -> bool {
+        self.tasks.contains_key(task_id)
+    }
+
     pub fn complete_task(&mut self, task_id: &str) -> Result<()> {
         if let Some(task) = self.tasks.get_mut(task_id) {
             task.complete();
@@ -270,6 +862,17 @@ impl ValidationTasksMempool {
         Ok(())
     }
 
+    // Moves an open task to another leader; returns the leader it had, or None if the task is unknown or done
+    pub fn reassign_task(&mut self, task_id: &str, leader_id: &str) -> Option<String> {
+        let task = self.tasks.get_mut(task_id).filter(|task| !task.complete)?;
+        let previous = std::mem::replace(&mut task.leader_id, leader_id.to_string());
+        if let Some(task_ids) = self.assigned_tasks.get_mut(&previous) {
+            task_ids.retain(|id| id != task_id);
+        }
+        self.assigned_tasks.entry(leader_id.to_string()).or_default().push(task_id.to_string());
+        Some(previous)
+    }
+
     pub fn remove_tasks_for_tx(&mut self, tx_id: &str) -> Result<()> {
         let task_ids: Vec<String> = self.tasks.keys().cloned().collect();
         for task_id in task_ids {
@@ -289,7 +892,14 @@ impl LockedUtxoMempool {
         }
     }
 
+    // Re-locking for the same transaction is a no-op; a UTXO locked by another transaction is refused
     pub fn lock_utxo(&mut self, utxo_id: String, amount: f64, tx_id: String) -> Result<()> {
+        if let Some(existing) = self.locked_utxos.get(&utxo_id) {
+            if existing.locked_by_tx == tx_id {
+                return Ok(());
+            }
+            return Err(PclError::Mempool(format!("UTXO {} is locked by transaction {}", utxo_id, existing.locked_by_tx)));
+        }
         let locked_utxo = LockedUtxo {
             utxo_id: utxo_id.clone(),
             amount,
@@ -328,6 +938,40 @@ impl LockedUtxoMempool {
     pub fn is_utxo_locked(&self, utxo_id: &str) -> bool {
         self.locked_utxos.contains_key(utxo_id)
     }
+
+    // UTXOs claimed by more than one transaction, or where the lock and the per-tx index disagree
+    pub fn lock_conflicts(&self) -> Vec<String> {
+        let mut claimed_by: HashMap<&String, Vec<&String>> = HashMap::new();
+        for (tx_id, utxo_ids) in &self.tx_locks {
+            for utxo_id in utxo_ids {
+                claimed_by.entry(utxo_id).or_default().push(tx_id);
+            }
+        }
+        let mut conflicts: Vec<String> = claimed_by.iter()
+            .filter(|(utxo_id, tx_ids)| {
+                tx_ids.len() > 1 || self.locked_utxos.get(**utxo_id).map(|lock| &lock.locked_by_tx) != Some(tx_ids[0])
+            })
+            .map(|(utxo_id, _)| utxo_id.to_string())
+            .chain(self.locked_utxos.keys().filter(|utxo_id| !claimed_by.contains_key(utxo_id)).cloned())
+            .collect();
+        conflicts.sort();
+        conflicts.dedup();
+        conflicts
+    }
+
+    // Releases locks held by transactions that are no longer live once they are older than the ttl.
+    // Returns the released utxo ids.
+    pub fn collect_garbage(&mut self, live_tx_ids: &HashSet<String>, ttl: chrono::Duration, now: DateTime<Utc>) -> Vec<String> {
+        let mut released: Vec<String> = self.locked_utxos.values()
+            .filter(|lock| !live_tx_ids.contains(&lock.locked_by_tx) && lock.locked_at + ttl <= now)
+            .map(|lock| lock.utxo_id.clone())
+            .collect();
+        released.sort();
+        for utxo_id in &released {
+            let _ = self.unlock_utxo(utxo_id);
+        }
+        released
+    }
 }
 
 impl ProcessingTxMempool {
@@ -365,30 +1009,40 @@ impl TxMempool {
             finalized_transactions: HashMap::new(),
             xmbl_integrated: HashMap::new(),
             utxo_pool: HashMap::new(),
+            finalization_claims: HashMap::new(),
         }
     }
 
-    pub fn finalize_transaction(&mut self, tx_id: String, validator_sig: String) -> Result<()> {
-        // This would normally get the transaction from processing mempool
-        // For now, create a placeholder
-        let tx_data = TransactionData::new(
-            vec![("placeholder".to_string(), 1.0)],
-            vec![("placeholder".to_string(), 1.0)],
-            "placeholder".to_string(),
-            0.1,
-            0.01,
-        );
-        
-        let finalized_tx = FinalizedTransaction {
-            tx_id: tx_id.clone(),
-            tx_data: tx_data.clone(),
-            xmbl_cubic_root: tx_data.calculate_digital_root() as u8,
-            validator_signature: validator_sig,
-            finalized_at: Utc::now(),
-        };
-        
-        self.finalized_transactions.insert(tx_id, finalized_tx);
-        Ok(())
+    pub fn record_finalization_claim(&mut self, claim: FinalizationClaim) -> ConflictResolution {
+        match self.finalization_claims.get(&claim.raw_tx_id) {
+            None => {
+                self.finalization_claims.insert(claim.raw_tx_id.clone(), claim);
+                ConflictResolution::Accepted
+            }
+            Some(existing) if *existing == claim => ConflictResolution::Duplicate,
+            Some(existing) if claim.beats(existing) => {
+                let loser = existing.clone();
+                // The losing leader's finalized entry is dropped so only the winner remains
+                self.finalized_transactions.remove(&claim.raw_tx_id);
+                self.xmbl_integrated.remove(&claim.raw_tx_id);
+                self.finalization_claims.insert(claim.raw_tx_id.clone(), claim);
+                ConflictResolution::Replaced(loser)
+            }
+            Some(existing) => ConflictResolution::Rejected(existing.clone()),
+        }
+    }
+
+    // Drops a finalized entry if it was produced by the given leader
+    pub fn invalidate_claim(&mut self, raw_tx_id: &str, leader_id: &str) -> bool {
+        match self.finalization_claims.get(raw_tx_id) {
+            Some(claim) if claim.leader_id == leader_id => {
+                self.finalization_claims.remove(raw_tx_id);
+                self.finalized_transactions.remove(raw_tx_id);
+                self.xmbl_integrated.remove(raw_tx_id);
+                true
+            }
+            _ => false,
+        }
     }
 
     pub fn integrate_xmbl(&mut self, tx_id: String, digital_root: u8, cubic_position: u64) -> Result<()> {
@@ -403,6 +1057,32 @@ impl TxMempool {
         Ok(())
     }
 
+    // Marks the inputs spent and creates one output per recipient plus the sender's change.
+    // Nothing changes if any input is already spent.
+    pub fn apply_spend(&mut self, tx_id: &str, tx_data: &TransactionData) -> Result<()> {
+        if let Some((utxo_id, _)) = tx_data.from.iter().find(|(utxo_id, _)| self.utxo_pool.get(utxo_id).is_none_or(|utxo| utxo.spent)) {
+            return Err(PclError::Transaction(format!("Transaction {} spends {}, which is missing or already spent", tx_id, utxo_id)));
+        }
+        for (utxo_id, _) in &tx_data.from {
+            if let Some(utxo) = self.utxo_pool.get_mut(utxo_id) {
+                utxo.spent = true;
+            }
+        }
+        for (utxo_id, amount, owner) in output_utxos(tx_id, tx_data) {
+            self.create_utxo(utxo_id, amount, owner)?;
+        }
+        Ok(())
+    }
+
+    // Unspent value per owner
+    pub fn balances(&self) -> HashMap<String, f64> {
+        let mut balances: HashMap<String, f64> = HashMap::new();
+        for utxo in self.utxo_pool.values().filter(|utxo| !utxo.spent) {
+            *balances.entry(utxo.owner.clone()).or_insert(0.0) += utxo.amount;
+        }
+        balances
+    }
+
     pub fn create_utxo(&mut self, utxo_id: String, amount: f64, owner: String) -> Result<()> {
         let utxo = UtxoEntry {
             utxo_id: utxo_id.clone(),
@@ -0,0 +1,176 @@
+// Mesh module - heartbeat probes that detect a degraded gossip mesh
+//
+// A node can stop receiving a topic's gossip without any peer disconnecting, which leaves a leader
+// working on a stale view. Every leader publishes a small signed heartbeat on each topic at a fixed
+// interval. Receivers record which leaders' heartbeats arrived recently per topic. If too few of the
+// other leaders get through on a topic, this node is isolated on it and should dial more peers.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use serde::{Deserialize, Serialize};
+use crate::config::NetworkConfig;
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::{decode_public_key, decode_signature};
+
+// Topics the gossip layer carries; each gets its own heartbeat since meshes are per topic
+pub const MESH_TOPICS: [&str; 4] = ["transactions", "processing", "consensus", "pulses"];
+
+// Heartbeats older than this many intervals count as not delivered
+const STALE_AFTER_INTERVALS: u64 = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshHeartbeat {
+    pub topic: String,
+    pub sender_id: String,
+    pub sequence: u64,
+    pub sent_at: u64,              // ms since epoch
+    pub sender_public_key: String, // hex encoded
+    pub signature: String,         // hex encoded
+}
+
+impl MeshHeartbeat {
+    pub fn sign(topic: &str, sender_id: &str, sequence: u64, keypair: &NodeKeypair, sent_at: u64) -> Self {
+        let mut heartbeat = Self {
+            topic: topic.to_string(),
+            sender_id: sender_id.to_string(),
+            sequence,
+            sent_at,
+            sender_public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: String::new(),
+        };
+        heartbeat.signature = hex::encode(keypair.sign_data(&heartbeat.signing_bytes()).to_bytes());
+        heartbeat
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!("pcl-mesh-heartbeat:{}:{}:{}:{}", self.topic, self.sender_id, self.sequence, self.sent_at).into_bytes()
+    }
+
+    // Checks the signature against the key the heartbeat carries; callers match that key to the sender
+    pub fn verify(&self) -> Result<()> {
+        let public_key = decode_public_key(&self.sender_public_key)?;
+        let signature = decode_signature(&self.signature)?;
+        if !verify_data_signature(&self.signing_bytes(), &signature, &public_key)? {
+            return Err(PclError::SignatureVerification(format!(
+                "Invalid mesh heartbeat from {} on {}", self.sender_id, self.topic
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicHealth {
+    pub topic: String,
+    pub expected_leaders: usize,
+    pub delivering_leaders: usize,
+    pub delivery_ratio: f64, // 1.0 when there are no other leaders to hear from
+    pub silent_leaders: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshHealth {
+    pub topics: Vec<TopicHealth>,
+    pub isolated: bool,       // some topic is below the minimum delivery ratio
+    pub network_health: f64,  // 0-100, the worst topic's delivery ratio
+    pub checked_at: u64,
+}
+
+impl MeshHealth {
+    // Leaders silent on any topic, each listed once
+    pub fn silent_leaders(&self) -> Vec<String> {
+        let mut silent: Vec<String> = self.topics.iter().flat_map(|topic| topic.silent_leaders.clone()).collect();
+        silent.sort();
+        silent.dedup();
+        silent
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP pcl_mesh_delivery_ratio Share of other leaders whose heartbeats arrived recently, per topic\n");
+        out.push_str("# TYPE pcl_mesh_delivery_ratio gauge\n");
+        for topic in &self.topics {
+            let _ = writeln!(out, "pcl_mesh_delivery_ratio{{topic=\"{}\"}} {}", topic.topic, topic.delivery_ratio);
+        }
+        out.push_str("# HELP pcl_mesh_isolated Whether this node looks isolated on some gossip topic\n");
+        out.push_str("# TYPE pcl_mesh_isolated gauge\n");
+        let _ = writeln!(out, "pcl_mesh_isolated {}", u8::from(self.isolated));
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MeshMonitor {
+    interval_ms: u64,
+    min_delivery_ratio: f64,
+    next_sequence: u64,
+    last_heard: HashMap<(String, String), (u64, u64)>, // (topic, leader) -> (sequence, received_at)
+}
+
+impl MeshMonitor {
+    pub fn new(config: &NetworkConfig) -> Self {
+        Self {
+            interval_ms: config.mesh_heartbeat_interval_secs * 1000,
+            min_delivery_ratio: config.mesh_min_delivery_ratio,
+            next_sequence: 0,
+            last_heard: HashMap::new(),
+        }
+    }
+
+    pub fn interval_ms(&self) -> u64 {
+        self.interval_ms
+    }
+
+    // Sequence for the next round of heartbeats this node publishes
+    pub fn next_sequence(&mut self) -> u64 {
+        self.next_sequence += 1;
+        self.next_sequence
+    }
+
+    // Returns true if the heartbeat is newer than the last one from the same leader on the topic.
+    // The signature is checked here; the caller checks the key belongs to the sender.
+    pub fn record(&mut self, heartbeat: &MeshHeartbeat, now: u64) -> Result<bool> {
+        heartbeat.verify()?;
+        if !MESH_TOPICS.contains(&heartbeat.topic.as_str()) {
+            return Err(PclError::Validation(format!("Unknown mesh topic {}", heartbeat.topic)));
+        }
+        let key = (heartbeat.topic.clone(), heartbeat.sender_id.clone());
+        if self.last_heard.get(&key).is_some_and(|(sequence, _)| *sequence >= heartbeat.sequence) {
+            return Ok(false);
+        }
+        self.last_heard.insert(key, (heartbeat.sequence, now));
+        Ok(true)
+    }
+
+    pub fn assess(&self, local_id: &str, leaders: &[String], now: u64) -> MeshHealth {
+        let stale_after = self.interval_ms * STALE_AFTER_INTERVALS;
+        let others: Vec<&String> = leaders.iter().filter(|leader| leader.as_str() != local_id).collect();
+
+        let topics: Vec<TopicHealth> = MESH_TOPICS.iter().map(|topic| {
+            let silent_leaders: Vec<String> = others.iter()
+                .filter(|leader| {
+                    self.last_heard.get(&(topic.to_string(), leader.to_string()))
+                        .is_none_or(|(_, received_at)| now.saturating_sub(*received_at) > stale_after)
+                })
+                .map(|leader| leader.to_string())
+                .collect();
+            let delivering_leaders = others.len() - silent_leaders.len();
+            TopicHealth {
+                topic: topic.to_string(),
+                expected_leaders: others.len(),
+                delivering_leaders,
+                delivery_ratio: if others.is_empty() { 1.0 } else { delivering_leaders as f64 / others.len() as f64 },
+                silent_leaders,
+            }
+        }).collect();
+
+        let worst = topics.iter().map(|topic| topic.delivery_ratio).fold(1.0, f64::min);
+        MeshHealth {
+            isolated: worst < self.min_delivery_ratio,
+            network_health: worst * 100.0,
+            topics,
+            checked_at: now,
+        }
+    }
+}
@@ -0,0 +1,232 @@
+// Metrics module - workflow step timings and Prometheus histograms
+
+use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+// Seconds; the workflow runs in-process in well under a second, gossip and validators can take much longer
+pub const LATENCY_BUCKETS_SECS: [f64; 14] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+// Weight units, up to MAX_TX_WEIGHT; a plain transfer is a few hundred
+pub const WEIGHT_BUCKETS: [f64; 9] = [
+    64.0, 128.0, 256.0, 512.0, 1024.0, 2048.0, 4096.0, 8192.0, 16384.0,
+];
+
+// The six steps of the README transaction workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowStep {
+    Submission,           // 1: Alice sends the transaction to leader Charlie
+    LeaderProcessing,     // 2: Charlie hashes, records and gossips it
+    TaskAssignment,       // 3: other leaders assign validation tasks to Alice
+    TaskCompletion,       // 4: Alice completes her tasks
+    ValidationProcessing, // 5: Charlie averages timestamps and signs
+    Finalization,         // 6: validators finalize the transaction
+}
+
+impl WorkflowStep {
+    pub const ALL: [WorkflowStep; 6] = [
+        WorkflowStep::Submission,
+        WorkflowStep::LeaderProcessing,
+        WorkflowStep::TaskAssignment,
+        WorkflowStep::TaskCompletion,
+        WorkflowStep::ValidationProcessing,
+        WorkflowStep::Finalization,
+    ];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            WorkflowStep::Submission => 1,
+            WorkflowStep::LeaderProcessing => 2,
+            WorkflowStep::TaskAssignment => 3,
+            WorkflowStep::TaskCompletion => 4,
+            WorkflowStep::ValidationProcessing => 5,
+            WorkflowStep::Finalization => 6,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkflowStep::Submission => "submission",
+            WorkflowStep::LeaderProcessing => "leader_processing",
+            WorkflowStep::TaskAssignment => "task_assignment",
+            WorkflowStep::TaskCompletion => "task_completion",
+            WorkflowStep::ValidationProcessing => "validation_processing",
+            WorkflowStep::Finalization => "finalization",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepTiming {
+    pub step: WorkflowStep,
+    pub started_at: u64, // ms since epoch
+    pub ended_at: Option<u64>,
+}
+
+impl StepTiming {
+    pub fn duration_ms(&self) -> Option<u64> {
+        self.ended_at.map(|ended| ended.saturating_sub(self.started_at))
+    }
+}
+
+// Start/end of each step a transaction has reached, in the order they ran
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowTimings {
+    pub steps: Vec<StepTiming>,
+    pub completed_at: Option<u64>,
+}
+
+impl WorkflowTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Starting a step ends whichever step was still open. A step re-entered after waiting
+    // (e.g. for more validator completions) keeps its first start time.
+    pub fn start(&mut self, step: WorkflowStep, now: u64) {
+        if self.steps.iter().any(|timing| timing.step == step) {
+            return;
+        }
+        self.close_open_step(now);
+        self.steps.push(StepTiming { step, started_at: now, ended_at: None });
+    }
+
+    pub fn finish(&mut self, now: u64) {
+        self.close_open_step(now);
+        self.completed_at = Some(now);
+    }
+
+    fn close_open_step(&mut self, now: u64) {
+        if let Some(open) = self.steps.last_mut().filter(|timing| timing.ended_at.is_none()) {
+            open.ended_at = Some(now.max(open.started_at));
+        }
+    }
+
+    pub fn get(&self, step: WorkflowStep) -> Option<&StepTiming> {
+        self.steps.iter().find(|timing| timing.step == step)
+    }
+
+    pub fn end_to_end_ms(&self) -> Option<u64> {
+        let started = self.steps.first()?.started_at;
+        self.completed_at.map(|completed| completed.saturating_sub(started))
+    }
+
+    // JSON breakdown for the transaction details endpoint
+    pub fn breakdown(&self) -> serde_json::Value {
+        let steps: Vec<serde_json::Value> = self.steps.iter().map(|timing| serde_json::json!({
+            "step": timing.step.number(),
+            "name": timing.step.as_str(),
+            "started_at": timing.started_at,
+            "ended_at": timing.ended_at,
+            "duration_ms": timing.duration_ms(),
+        })).collect();
+        serde_json::json!({
+            "steps": steps,
+            "completed_at": self.completed_at,
+            "end_to_end_ms": self.end_to_end_ms(),
+        })
+    }
+}
+
+// Cumulative Prometheus-style histogram over fixed bucket bounds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub bounds: Vec<f64>,
+    pub bucket_counts: Vec<u64>, // observations <= each bound, not cumulative
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &[f64]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            bucket_counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        if let Some(index) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.bucket_counts[index] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    // Appends the _bucket, _sum and _count series; labels are already formatted, e.g. step="submission"
+    pub fn render(&self, name: &str, labels: &str, out: &mut String) {
+        let prefix = if labels.is_empty() { String::new() } else { format!("{},", labels) };
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.bucket_counts) {
+            cumulative += count;
+            let _ = writeln!(out, "{}_bucket{{{}le=\"{}\"}} {}", name, prefix, bound, cumulative);
+        }
+        let _ = writeln!(out, "{}_bucket{{{}le=\"+Inf\"}} {}", name, prefix, self.count);
+        let labels = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowMetrics {
+    pub step_durations: Vec<(WorkflowStep, Histogram)>,
+    pub end_to_end: Histogram,
+    pub tx_weight: Histogram,
+}
+
+impl Default for WorkflowMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkflowMetrics {
+    pub fn new() -> Self {
+        Self {
+            step_durations: WorkflowStep::ALL.iter()
+                .map(|step| (*step, Histogram::new(&LATENCY_BUCKETS_SECS)))
+                .collect(),
+            end_to_end: Histogram::new(&LATENCY_BUCKETS_SECS),
+            tx_weight: Histogram::new(&WEIGHT_BUCKETS),
+        }
+    }
+
+    // Called once per finalized transaction with its completed timings
+    pub fn record(&mut self, timings: &WorkflowTimings) {
+        for timing in &timings.steps {
+            if let Some(duration) = timing.duration_ms() {
+                if let Some((_, histogram)) = self.step_durations.iter_mut().find(|(step, _)| *step == timing.step) {
+                    histogram.observe(duration as f64 / 1000.0);
+                }
+            }
+        }
+        if let Some(total) = timings.end_to_end_ms() {
+            self.end_to_end.observe(total as f64 / 1000.0);
+        }
+    }
+
+    pub fn record_weight(&mut self, weight: u64) {
+        self.tx_weight.observe(weight as f64);
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP pcl_workflow_step_duration_seconds Time spent in each transaction workflow step\n");
+        out.push_str("# TYPE pcl_workflow_step_duration_seconds histogram\n");
+        for (step, histogram) in &self.step_durations {
+            histogram.render("pcl_workflow_step_duration_seconds", &format!("step=\"{}\"", step.as_str()), &mut out);
+        }
+        out.push_str("# HELP pcl_workflow_end_to_end_seconds Submission to finalization latency\n");
+        out.push_str("# TYPE pcl_workflow_end_to_end_seconds histogram\n");
+        self.end_to_end.render("pcl_workflow_end_to_end_seconds", "", &mut out);
+        out.push_str("# HELP pcl_transaction_weight Weight of finalized transactions\n");
+        out.push_str("# TYPE pcl_transaction_weight histogram\n");
+        self.tx_weight.render("pcl_transaction_weight", "", &mut out);
+        out
+    }
+}
@@ -0,0 +1,145 @@
+// Multisig module - m-of-n accounts built from a set of ed25519 public keys
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use crate::address::Address;
+use crate::crypto::{encode_address, verify_data_signature, NodeKeypair, MULTISIG_ADDRESS_VERSION};
+use crate::error::{PclError, Result};
+
+// Upper bound on cosigners so a policy can't be used to make verification arbitrarily expensive
+pub const MAX_MULTISIG_KEYS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub threshold: u8,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_MULTISIG_KEYS>")]
+    pub public_keys: Vec<String>, // hex encoded, sorted so the address doesn't depend on key order
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub public_key: String, // hex encoded cosigner key
+    pub signature: String,  // hex encoded signature over the transaction signing bytes
+}
+
+impl MultisigPolicy {
+    pub fn new(threshold: u8, public_keys: &[VerifyingKey]) -> Result<Self> {
+        let mut keys: Vec<String> = public_keys.iter().map(|k| hex::encode(k.to_bytes())).collect();
+        keys.sort();
+        keys.dedup();
+
+        if keys.len() != public_keys.len() {
+            return Err(PclError::Validation("Multisig policy contains duplicate public keys".to_string()));
+        }
+
+        let policy = Self { threshold, public_keys: keys };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    // The policy itself is the account: hash of the threshold and the sorted key set
+    pub fn address(&self) -> Address {
+        let mut preimage = vec![self.threshold];
+        for key in &self.public_keys {
+            preimage.extend_from_slice(key.as_bytes());
+        }
+        Address::from_encoded(encode_address(MULTISIG_ADDRESS_VERSION, &preimage))
+    }
+
+    // Policies arriving over the wire skip `new`, so re-check the invariants before trusting them
+    pub fn validate(&self) -> Result<()> {
+        let mut keys = self.public_keys.clone();
+        keys.sort();
+        keys.dedup();
+
+        if keys != self.public_keys {
+            return Err(PclError::Validation("Multisig keys must be sorted and unique".to_string()));
+        }
+        if keys.is_empty() || keys.len() > MAX_MULTISIG_KEYS {
+            return Err(PclError::Validation(format!("Multisig policy must have between 1 and {} keys", MAX_MULTISIG_KEYS)));
+        }
+        if self.threshold == 0 || self.threshold as usize > keys.len() {
+            return Err(PclError::Validation(format!("Invalid multisig threshold {} for {} keys", self.threshold, keys.len())));
+        }
+        for key in &keys {
+            decode_public_key(key)?;
+        }
+        Ok(())
+    }
+
+    pub fn contains_key(&self, public_key_hex: &str) -> bool {
+        self.public_keys.iter().any(|k| k == public_key_hex)
+    }
+
+    // Counts distinct cosigners whose signature over `message` verifies; true once the threshold is met
+    pub fn verify(&self, message: &[u8], signatures: &[PartialSignature]) -> Result<bool> {
+        self.validate()?;
+        let mut valid_signers: Vec<&str> = Vec::new();
+
+        for partial in signatures {
+            if !self.contains_key(&partial.public_key) {
+                log::warn!("Ignoring signature from key outside multisig policy: {}", partial.public_key);
+                continue;
+            }
+            if valid_signers.contains(&partial.public_key.as_str()) {
+                continue;
+            }
+
+            let public_key = decode_public_key(&partial.public_key)?;
+            let signature = decode_signature(&partial.signature)?;
+            if verify_data_signature(message, &signature, &public_key)? {
+                valid_signers.push(&partial.public_key);
+            }
+        }
+
+        let satisfied = valid_signers.len() >= self.threshold as usize;
+        log::debug!("Multisig verification: {}/{} valid signatures", valid_signers.len(), self.threshold);
+        Ok(satisfied)
+    }
+}
+
+impl PartialSignature {
+    pub fn sign(keypair: &NodeKeypair, message: &[u8]) -> Self {
+        Self {
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: hex::encode(keypair.sign_data(message).to_bytes()),
+        }
+    }
+}
+
+// Merges partial signatures collected from cosigners: drops keys outside the policy and duplicates,
+// orders them by the policy's key order and fails if the threshold can't be reached
+pub fn combine_partial_signatures(policy: &MultisigPolicy, partials: Vec<PartialSignature>) -> Result<Vec<PartialSignature>> {
+    let mut combined: Vec<PartialSignature> = Vec::new();
+
+    for key in &policy.public_keys {
+        if let Some(partial) = partials.iter().find(|p| &p.public_key == key) {
+            combined.push(partial.clone());
+        }
+    }
+
+    if combined.len() < policy.threshold as usize {
+        return Err(PclError::SignatureVerification(format!(
+            "Only {} of {} required signatures present", combined.len(), policy.threshold
+        )));
+    }
+
+    Ok(combined)
+}
+
+pub fn decode_public_key(public_key_hex: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(public_key_hex)
+        .map_err(|e| PclError::SignatureVerification(format!("Invalid public key hex: {}", e)))?
+        .try_into()
+        .map_err(|_| PclError::SignatureVerification("Invalid public key length".to_string()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| PclError::SignatureVerification(format!("Invalid public key: {}", e)))
+}
+
+pub fn decode_signature(signature_hex: &str) -> Result<Signature> {
+    let bytes: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|e| PclError::SignatureVerification(format!("Invalid signature hex: {}", e)))?
+        .try_into()
+        .map_err(|_| PclError::SignatureVerification("Invalid signature length".to_string()))?;
+    Ok(Signature::from_bytes(&bytes))
+}
@@ -0,0 +1,155 @@
+// Negotiation module - leaders offer validation tasks, the origin leader picks among the offers
+//
+// Every non-origin leader used to send its task and the origin leader took whatever arrived first.
+// Offers for a transaction are now collected for a bounded window; each carries the spare task
+// capacity its leader advertises. When the window closes, or every expected leader has offered, the
+// origin leader selects a configured number of offers from distinct leaders, preferring spare
+// capacity, and acknowledges each one. Offers left unselected expire with the window.
+
+use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::config::ConsensusConfig;
+use crate::error::{PclError, Result};
+use crate::transaction::{derive_task_id, ValidationTaskType};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskOffer {
+    pub task_id: String, // derive_task_id over the fields below
+    pub raw_tx_id: String,
+    pub leader_id: String,  // the offering leader
+    pub assignee: String,   // the validator the task is for
+    pub task_type: ValidationTaskType,
+    pub capacity: u32,      // more tasks the offering leader can take on now
+    pub offered_at: u64,    // ms since epoch
+}
+
+impl TaskOffer {
+    pub fn new(raw_tx_id: &str, leader_id: &str, assignee: &str, task_type: ValidationTaskType, capacity: u32, offered_at: u64) -> Self {
+        Self {
+            task_id: derive_task_id(raw_tx_id, task_type.as_str(), leader_id, assignee),
+            raw_tx_id: raw_tx_id.to_string(),
+            leader_id: leader_id.to_string(),
+            assignee: assignee.to_string(),
+            task_type,
+            capacity,
+            offered_at,
+        }
+    }
+}
+
+// What the origin leader decided for one transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfferSelection {
+    pub raw_tx_id: String,
+    pub accepted: Vec<TaskOffer>,
+    pub rejected: Vec<TaskOffer>,
+}
+
+#[derive(Debug, Clone)]
+struct OfferWindow {
+    opened_at: u64,
+    expected: usize, // leaders asked to offer; the window settles early once all of them have
+    offers: Vec<TaskOffer>,
+}
+
+impl OfferWindow {
+    fn leaders(&self) -> usize {
+        self.offers.iter().map(|offer| offer.leader_id.as_str()).collect::<HashSet<_>>().len()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskNegotiation {
+    window_ms: u64,
+    select_count: usize,
+    windows: BTreeMap<String, OfferWindow>, // raw_tx_id -> offers so far
+}
+
+impl TaskNegotiation {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self { window_ms: config.task_offer_window_ms, select_count: config.task_offers_selected, windows: BTreeMap::new() }
+    }
+
+    // Starts collecting offers for a transaction; reopening an open window keeps its offers
+    pub fn open(&mut self, raw_tx_id: &str, expected: usize, now: u64) {
+        self.windows.entry(raw_tx_id.to_string())
+            .or_insert_with(|| OfferWindow { opened_at: now, expected, offers: Vec::new() });
+    }
+
+    pub fn is_open(&self, raw_tx_id: &str) -> bool {
+        self.windows.contains_key(raw_tx_id)
+    }
+
+    // Returns false for a repeat of an offer already held
+    pub fn submit(&mut self, offer: TaskOffer, now: u64) -> Result<bool> {
+        let window_ms = self.window_ms;
+        let window = self.windows.get_mut(&offer.raw_tx_id).ok_or_else(|| {
+            PclError::Consensus(format!("No task offers are being collected for {}", offer.raw_tx_id))
+        })?;
+        if now.saturating_sub(window.opened_at) >= window_ms {
+            return Err(PclError::Consensus(format!(
+                "Offer {} from {} arrived after the window for {} closed", offer.task_id, offer.leader_id, offer.raw_tx_id
+            )));
+        }
+        if offer.task_id != derive_task_id(&offer.raw_tx_id, offer.task_type.as_str(), &offer.leader_id, &offer.assignee) {
+            return Err(PclError::Validation(format!("Offer {} is not derived from its assignment", offer.task_id)));
+        }
+        if window.offers.iter().any(|held| held.task_id == offer.task_id) {
+            return Ok(false);
+        }
+        window.offers.push(offer);
+        Ok(true)
+    }
+
+    // Windows that can be settled: every expected leader has offered or the window has closed
+    pub fn ready(&self, now: u64) -> Vec<String> {
+        self.windows.iter()
+            .filter(|(_, window)| {
+                window.leaders() >= window.expected || now.saturating_sub(window.opened_at) >= self.window_ms
+            })
+            .map(|(raw_tx_id, _)| raw_tx_id.clone())
+            .collect()
+    }
+
+    // Closes the window and picks up to select_count offers, one per leader, most spare capacity
+    // first and earliest offered among equals. Everything else is rejected.
+    pub fn select(&mut self, raw_tx_id: &str) -> Option<OfferSelection> {
+        let mut offers = self.windows.remove(raw_tx_id)?.offers;
+        offers.sort_by(|a, b| {
+            b.capacity.cmp(&a.capacity)
+                .then_with(|| a.offered_at.cmp(&b.offered_at))
+                .then_with(|| a.leader_id.cmp(&b.leader_id))
+        });
+        let mut leaders = HashSet::new();
+        let (mut accepted, mut rejected) = (Vec::new(), Vec::new());
+        for offer in offers {
+            if offer.capacity > 0 && accepted.len() < self.select_count && leaders.insert(offer.leader_id.clone()) {
+                accepted.push(offer);
+            } else {
+                rejected.push(offer);
+            }
+        }
+        Some(OfferSelection { raw_tx_id: raw_tx_id.to_string(), accepted, rejected })
+    }
+
+    // Drops windows nobody settled within twice the window, e.g. because the transaction is gone;
+    // returns the offers that expired with them
+    pub fn expire(&mut self, now: u64) -> Vec<TaskOffer> {
+        let stale: Vec<String> = self.windows.iter()
+            .filter(|(_, window)| now.saturating_sub(window.opened_at) >= 2 * self.window_ms)
+            .map(|(raw_tx_id, _)| raw_tx_id.clone())
+            .collect();
+        stale.iter()
+            .filter_map(|raw_tx_id| self.windows.remove(raw_tx_id))
+            .flat_map(|window| window.offers)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+}
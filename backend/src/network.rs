@@ -8,7 +8,27 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::error::{PclError, Result};
 use crate::node::{Node, NodeRole};
-use crate::transaction::{RawTransaction, ValidationTask};
+use crate::transaction::{RawTransaction, ValidationTask, ProcessingTransaction};
+use crate::equivocation::{EquivocationEvidence, ForkEvidence};
+use crate::envelope::EncryptedEnvelope;
+use crate::blinding::{blind_transaction, AmountOpening};
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::multisig::{decode_public_key, decode_signature};
+use crate::probation::PulseReceipt;
+use crate::governance::GovernanceProposal;
+use crate::screening::ScreeningList;
+use crate::mesh::MeshHeartbeat;
+use crate::relay::TxAnnouncement;
+use crate::outbound::OutboundQueues;
+use crate::bandwidth::{topic_of, BandwidthMeter, TrafficDirection};
+use crate::peers::AddressBook;
+use crate::binding::{peer_id_for, PeerBinding};
+use crate::negotiation::TaskOffer;
+use crate::config::NetworkConfig;
+use crate::gossip::{gossip_message_id, relay_hops, GossipMesh, MeshChange};
+use crate::transport::Transport;
+use ed25519_dalek::VerifyingKey;
+use crate::limits::{decode_json, MAX_MESSAGE_SIZE};
 
 // Simple peer ID type for now
 pub type PeerId = String;
@@ -18,6 +38,7 @@ pub type Multiaddr = String;
 #[derive(Debug)]
 pub enum NetworkEvent {
     Message(String),
+    PeerMessage(PeerId, String), // a message whose sending peer is known, metered against its cap
     PeerConnected(PeerId),
     PeerDisconnected(PeerId),
     PingReceived(PeerId, std::time::Duration),
@@ -32,6 +53,37 @@ pub enum NetworkMessage {
     Pulse(PulseMessage),
     PulseResponse(PulseResponseMessage),
     UptimeData(UptimeMessage),
+    TransactionInvalidation(TransactionInvalidationMessage),
+    ProcessingTransactionGossip(ProcessingTransactionGossipMessage),
+    EquivocationEvidence(EquivocationEvidenceMessage),
+    VerifiedProcessingTxBroadcast(VerifiedProcessingTxBroadcastMessage),
+    AmountReveal(AmountRevealMessage),
+    PulseReceipt(PulseReceipt),
+    GovernanceProposal(GovernanceProposal),
+    MeshHeartbeat(MeshHeartbeat),
+    TransactionAnnouncement(TransactionAnnouncementMessage),
+    TransactionRequest(TransactionRequestMessage),
+    PeerBinding(PeerBinding),
+    TaskOffer(TaskOfferMessage),
+    TaskOfferAck(TaskOfferAckMessage),
+    ScreeningList(ScreeningList),
+}
+
+impl NetworkMessage {
+    // JSON wire encoding; refuses to produce anything a peer would reject
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let bytes = serde_json::to_vec(self)?;
+        if bytes.len() > MAX_MESSAGE_SIZE {
+            return Err(PclError::Network(format!(
+                "Outbound message of {} bytes exceeds the {} byte limit", bytes.len(), MAX_MESSAGE_SIZE
+            )));
+        }
+        Ok(bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        decode_json(bytes, MAX_MESSAGE_SIZE)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,16 +92,66 @@ pub struct TransactionGossipMessage {
     pub raw_transaction: RawTransaction,
     pub leader_id: String,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub amount_commitment: Option<String>, // set when raw_transaction's amounts are blinded
+    #[serde(default)]
+    pub target_node: Option<String>, // set when the body answers one peer's request
+}
+
+// Compact relay: ids and short hashes of new raw transactions, bodies are fetched on demand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionAnnouncementMessage {
+    pub announcements: Vec<TxAnnouncement>,
+    pub sender_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionRequestMessage {
+    pub raw_tx_ids: Vec<String>,
+    pub sender_id: String,
+    pub target_node: String, // the announcer being asked for the bodies
+    pub timestamp: DateTime<Utc>,
 }
 
+// A leader offering the origin leader a validation task for one of its transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskOfferMessage {
+    pub offer: TaskOffer,
+    pub target_node: String, // the origin leader collecting offers
+    pub timestamp: DateTime<Utc>,
+}
+
+// The origin leader's answer to an offer, sent back to the offering leader
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskOfferAckMessage {
+    pub raw_tx_id: String,
+    pub task_id: String,
+    pub leader_id: String, // the offering leader
+    pub accepted: bool,
+    pub sender_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+// The task itself travels encrypted to the assigned user; only the ids needed for routing are in the clear
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationTaskMessage {
     pub task_id: String,
-    pub task: ValidationTask,
+    pub envelope: EncryptedEnvelope,
     pub target_node: String,
     pub timestamp: DateTime<Utc>,
 }
 
+impl ValidationTaskMessage {
+    pub fn open(&self, keypair: &NodeKeypair) -> Result<ValidationTask> {
+        let task: ValidationTask = decode_json(&self.envelope.open(keypair)?, MAX_MESSAGE_SIZE)?;
+        if task.task_id != self.task_id {
+            return Err(PclError::Network(format!("Sealed task {} does not match message task {}", task.task_id, self.task_id)));
+        }
+        Ok(task)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaderElectionMessage {
     pub election_id: String,
@@ -57,6 +159,8 @@ pub struct LeaderElectionMessage {
     pub votes: u64,
     pub round: u8,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub voter_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +169,8 @@ pub struct PulseMessage {
     pub sender_id: String,
     pub family_id: Uuid,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub clock_drift: bool, // the sender's clock is off by more than its max_drift_ms
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,12 +189,133 @@ pub struct UptimeMessage {
     pub pulse_count: u64,
 }
 
+// Tells peers to drop a leader's processing/finalized entry that lost a fork tie-break, or a raw
+// transaction its sender replaced with a higher fee one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionInvalidationMessage {
+    pub raw_tx_id: String,
+    pub invalidated_leader_id: String,
+    pub winning_leader_id: String,
+    pub reason: String,
+    pub sender_id: String,
+    pub timestamp: DateTime<Utc>,
+    // Notices are acted on only if signed by a current leader or backed by fork evidence
+    #[serde(default)]
+    pub sender_public_key: String, // hex encoded
+    #[serde(default)]
+    pub signature: String,         // hex encoded
+    #[serde(default)]
+    pub evidence: Option<ForkEvidence>,
+    #[serde(default)]
+    pub replaced_by: Option<String>, // replace-by-fee: the transaction superseding raw_tx_id
+    #[serde(default)]
+    pub hops: u32, // times relayed so far; not signed, since every relay bumps it
+}
+
+impl TransactionInvalidationMessage {
+    // The replacement is only signed when present, so fork notices keep their original encoding
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "pcl-invalidation:{}:{}:{}:{}:{}:{}",
+            self.raw_tx_id, self.invalidated_leader_id, self.winning_leader_id, self.reason, self.sender_id,
+            self.timestamp.timestamp_millis()
+        );
+        if let Some(replacement) = &self.replaced_by {
+            bytes.push_str(&format!(":replaced-by:{}", replacement));
+        }
+        bytes.into_bytes()
+    }
+
+    pub fn sign(&mut self, keypair: &NodeKeypair) {
+        self.sender_public_key = hex::encode(keypair.public_key().to_bytes());
+        self.signature = hex::encode(keypair.sign_data(&self.signing_bytes()).to_bytes());
+    }
+
+    // Checks the signature against the key the notice carries; callers match that key to the sender
+    pub fn verify_signature(&self) -> Result<()> {
+        let public_key = decode_public_key(&self.sender_public_key)?;
+        let signature = decode_signature(&self.signature)?;
+        if !verify_data_signature(&self.signing_bytes(), &signature, &public_key)? {
+            return Err(PclError::SignatureVerification(format!(
+                "Invalid signature on invalidation notice for {} from {}", self.raw_tx_id, self.sender_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+// The amounts behind a blinded gossip, sent encrypted to one validating leader
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmountRevealMessage {
+    pub raw_tx_id: String,
+    pub envelope: EncryptedEnvelope,
+    pub target_node: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AmountRevealMessage {
+    pub fn open(&self, keypair: &NodeKeypair) -> Result<AmountOpening> {
+        let opening: AmountOpening = decode_json(&self.envelope.open(keypair)?, MAX_MESSAGE_SIZE)?;
+        if opening.raw_tx_id != self.raw_tx_id {
+            return Err(PclError::Network(format!("Sealed amounts for {} do not match message tx {}", opening.raw_tx_id, self.raw_tx_id)));
+        }
+        Ok(opening)
+    }
+}
+
+// A leader's signed processing entry, gossiped so other leaders can spot equivocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingTransactionGossipMessage {
+    pub tx_id: String,
+    pub processing_transaction: ProcessingTransaction,
+    pub sender_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquivocationEvidenceMessage {
+    pub evidence: EquivocationEvidence,
+    pub sender_id: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub hops: u32,
+}
+
+// A validator announcing that it verified and finalized a processing tx, for the leaders sampled to
+// finalize it too
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedProcessingTxBroadcastMessage {
+    pub tx_id: String,
+    pub validator_id: String,
+    pub validator_signature_on_tx_id: String, // hex encoded, over signing_bytes
+    pub xmbl_cubic_root: u8,
+    #[serde(default)]
+    pub target_leaders: Vec<String>, // empty: every leader that holds the processing tx
+    pub timestamp: DateTime<Utc>,
+}
+
+impl VerifiedProcessingTxBroadcastMessage {
+    // The tx id followed by its XMBL cubic root, the same signature the finalized tx keeps
+    pub fn signing_bytes(tx_id: &str, xmbl_cubic_root: u8) -> Vec<u8> {
+        format!("{}{}", tx_id, xmbl_cubic_root).into_bytes()
+    }
+}
+
 // Network manager for handling P2P communication
 pub struct NetworkManager {
     pub local_node: Node,
     pub peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
     pub message_history: Arc<RwLock<Vec<NetworkMessage>>>,
     pub connected: bool,
+    pub rejected_messages: u64, // inbound messages dropped for size or malformed content
+    pub listen_port: u16,       // 0 until listening
+    pub outbound: OutboundQueues, // published messages wait here until their class is under its cap
+    pub address_book: AddressBook, // every peer seen, in memory only until a stored book is attached
+    pub transport_keypair: NodeKeypair, // stands in for the libp2p identity; the local PeerId derives from it
+    pub mdns_enabled: bool, // off where multicast cannot reach peers; static peers and DNS seeds stand in
+    pub gossip: GossipMesh, // mesh membership and recent message ids, maintained by gossip_heartbeat
+    pub bandwidth: BandwidthMeter, // bytes per peer and topic, and the per-peer caps
+    pub transport: Option<Box<dyn Transport>>, // carries flushed messages to peers; None keeps them in the history only
 }
 
 #[derive(Debug, Clone)]
@@ -108,6 +335,15 @@ impl NetworkManager {
             peers: Arc::new(RwLock::new(HashMap::new())),
             message_history: Arc::new(RwLock::new(Vec::new())),
             connected: false,
+            rejected_messages: 0,
+            listen_port: 0,
+            outbound: OutboundQueues::new(&NetworkConfig::default()),
+            address_book: AddressBook::new(),
+            transport_keypair: NodeKeypair::new(),
+            mdns_enabled: true,
+            gossip: GossipMesh::new(&NetworkConfig::default()),
+            bandwidth: BandwidthMeter::new(&NetworkConfig::default()),
+            transport: None,
         };
 
         log::info!("Network manager created (simplified implementation)");
@@ -117,33 +353,83 @@ impl NetworkManager {
     pub async fn start_listening(&mut self, port: u16) -> Result<()> {
         log::info!("Network listening on port {} (placeholder)", port);
         self.connected = true;
+        self.listen_port = port;
         Ok(())
     }
 
     pub async fn connect_to_peer(&mut self, peer_addr: &str) -> Result<()> {
-        log::info!("Connecting to peer: {} (placeholder)", peer_addr);
-        
         // Simulate adding a peer
         let peer_id = format!("peer_{}", peer_addr.replace(":", "_"));
+        tracing::info!(peer_id = %peer_id, "Connecting to peer: {} (placeholder)", peer_addr);
+        // A peer the address book already knows keeps its node id and role
+        let (node_id, role) = self.address_book.get(&peer_id)
+            .map_or((peer_id.clone(), NodeRole::Extension), |record| (record.node_id.clone(), record.role));
         let peer_info = PeerInfo {
             peer_id: peer_id.clone(),
             multiaddr: peer_addr.to_string(),
-            node_id: peer_id.clone(),
-            role: NodeRole::Extension,
+            node_id,
+            role,
             last_seen: Utc::now(),
             uptime_percentage: 100.0,
         };
         
+        let seen_at = peer_info.last_seen.timestamp_millis().max(0) as u64;
+        self.address_book.observe(&peer_id, &peer_info.node_id, peer_addr, role, seen_at)?;
         self.peers.write().await.insert(peer_id, peer_info);
         Ok(())
     }
 
+    // One key for both layers: the PeerId is then derived from the node's own public key
+    pub fn use_node_key_for_transport(&mut self, node_keypair: &NodeKeypair) {
+        self.transport_keypair = node_keypair.clone();
+    }
+
+    pub fn local_peer_id(&self) -> String {
+        peer_id_for(&self.transport_keypair.public_key())
+    }
+
+    // Sent at handshake so peers can tie this PeerId to the node key behind its votes and signatures
+    pub async fn publish_peer_binding(&mut self, app_keypair: &NodeKeypair) -> Result<PeerBinding> {
+        let binding = PeerBinding::sign(&self.local_node.id.to_string(), app_keypair, &self.transport_keypair);
+        self.add_to_message_history(NetworkMessage::PeerBinding(binding.clone())).await;
+        log::debug!("Published binding of {} to {}", binding.peer_id, binding.node_id);
+        Ok(binding)
+    }
+
+    // Replaces the in-memory address book, e.g. with one loaded from storage
+    pub fn attach_address_book(&mut self, address_book: AddressBook) {
+        self.address_book = address_book;
+    }
+
+    // Dials the best known peers before discovery has found any, each at its most recent address.
+    // Returns the addresses that connected; failures count against the peer and may prune it.
+    pub async fn dial_known_peers(&mut self, limit: usize) -> Result<Vec<String>> {
+        let now = Utc::now().timestamp_millis().max(0) as u64;
+        let candidates: Vec<(String, String)> = self.address_book.dial_candidates(limit, now).into_iter()
+            .map(|record| (record.peer_id.clone(), record.multiaddrs[0].clone()))
+            .collect();
+        let mut dialed = Vec::new();
+        for (peer_id, address) in candidates {
+            let connected = self.connect_to_peer(&address).await;
+            if let Err(e) = &connected {
+                log::warn!("Could not dial known peer {} at {}: {}", peer_id, address, e);
+            }
+            self.address_book.record_dial(&peer_id, connected.is_ok(), now)?;
+            if connected.is_ok() {
+                dialed.push(address);
+            }
+        }
+        Ok(dialed)
+    }
+
     pub async fn gossip_transaction(&mut self, tx: &RawTransaction) -> Result<()> {
         let message = NetworkMessage::TransactionGossip(TransactionGossipMessage {
             tx_id: tx.raw_tx_id.clone(),
             raw_transaction: tx.clone(),
             leader_id: self.local_node.id.to_string(),
             timestamp: Utc::now(),
+            amount_commitment: None,
+            target_node: None,
         });
 
         self.add_to_message_history(message).await;
@@ -151,10 +437,122 @@ impl NetworkManager {
         Ok(())
     }
 
-    pub async fn send_validation_task(&mut self, task: &ValidationTask, target_node: &str) -> Result<()> {
+    pub async fn announce_transactions(&mut self, announcements: Vec<TxAnnouncement>) -> Result<()> {
+        let count = announcements.len();
+        let message = NetworkMessage::TransactionAnnouncement(TransactionAnnouncementMessage {
+            announcements,
+            sender_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Announced {} transactions", count);
+        Ok(())
+    }
+
+    pub async fn request_transactions(&mut self, target_node: &str, raw_tx_ids: Vec<String>) -> Result<()> {
+        let count = raw_tx_ids.len();
+        let message = NetworkMessage::TransactionRequest(TransactionRequestMessage {
+            raw_tx_ids,
+            sender_id: self.local_node.id.to_string(),
+            target_node: target_node.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Requested {} transaction bodies from {}", count, target_node);
+        Ok(())
+    }
+
+    pub async fn send_task_offer(&mut self, offer: &TaskOffer, target_node: &str) -> Result<()> {
+        let message = NetworkMessage::TaskOffer(TaskOfferMessage {
+            offer: offer.clone(),
+            target_node: target_node.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Offered task {} for {} to {}", offer.task_id, offer.raw_tx_id, target_node);
+        Ok(())
+    }
+
+    pub async fn acknowledge_task_offer(&mut self, offer: &TaskOffer, accepted: bool) -> Result<()> {
+        let message = NetworkMessage::TaskOfferAck(TaskOfferAckMessage {
+            raw_tx_id: offer.raw_tx_id.clone(),
+            task_id: offer.task_id.clone(),
+            leader_id: offer.leader_id.clone(),
+            accepted,
+            sender_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("{} task offer {} from {}", if accepted { "Accepted" } else { "Declined" }, offer.task_id, offer.leader_id);
+        Ok(())
+    }
+
+    // Answers a body request; only the requesting peer needs it
+    pub async fn send_transaction_body(&mut self, tx: &RawTransaction, target_node: &str) -> Result<()> {
+        let message = NetworkMessage::TransactionGossip(TransactionGossipMessage {
+            tx_id: tx.raw_tx_id.clone(),
+            raw_transaction: tx.clone(),
+            leader_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+            amount_commitment: None,
+            target_node: Some(target_node.to_string()),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Sent transaction body {} to {}", tx.raw_tx_id, target_node);
+        Ok(())
+    }
+
+    // Gossips the transaction with its amounts zeroed and a commitment to them in their place
+    pub async fn gossip_blinded_transaction(&mut self, tx: &RawTransaction, opening: &AmountOpening) -> Result<()> {
+        let message = NetworkMessage::TransactionGossip(TransactionGossipMessage {
+            tx_id: tx.raw_tx_id.clone(),
+            raw_transaction: blind_transaction(tx),
+            leader_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+            amount_commitment: Some(opening.commitment()?),
+            target_node: None,
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Gossiped blinded transaction: {}", tx.raw_tx_id);
+        Ok(())
+    }
+
+    pub async fn send_amount_reveal(
+        &mut self,
+        opening: &AmountOpening,
+        target_node: &str,
+        sender: &NodeKeypair,
+        recipient: &VerifyingKey,
+    ) -> Result<()> {
+        let message = NetworkMessage::AmountReveal(AmountRevealMessage {
+            raw_tx_id: opening.raw_tx_id.clone(),
+            envelope: EncryptedEnvelope::seal(sender, recipient, &serde_json::to_vec(opening)?)?,
+            target_node: target_node.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Sent amounts for {} to {}", opening.raw_tx_id, target_node);
+        Ok(())
+    }
+
+    // Sealed with the assigning leader's key for the user who has to complete the task
+    pub async fn send_validation_task(
+        &mut self,
+        task: &ValidationTask,
+        target_node: &str,
+        sender: &NodeKeypair,
+        recipient: &VerifyingKey,
+    ) -> Result<()> {
         let message = NetworkMessage::ValidationTask(ValidationTaskMessage {
             task_id: task.task_id.clone(),
-            task: task.clone(),
+            envelope: EncryptedEnvelope::seal(sender, recipient, &serde_json::to_vec(task)?)?,
             target_node: target_node.to_string(),
             timestamp: Utc::now(),
         });
@@ -169,7 +567,8 @@ impl NetworkManager {
             pulse_id: Uuid::new_v4().to_string(),
             sender_id: self.local_node.id.to_string(),
             family_id,
-            timestamp: Utc::now(),
+            timestamp: crate::clock::now_utc(),
+            clock_drift: !crate::clock::clock_healthy(),
         });
 
         self.add_to_message_history(message).await;
@@ -190,6 +589,60 @@ impl NetworkManager {
         Ok(())
     }
 
+    // Vouches that a peer's pulse arrived; counts towards ending the peer's probation
+    pub async fn send_pulse_receipt(&mut self, pulse: &PulseMessage, witness: &NodeKeypair) -> Result<()> {
+        let receipt = PulseReceipt::sign(
+            &pulse.sender_id,
+            &pulse.pulse_id,
+            &self.local_node.id.to_string(),
+            witness,
+            crate::clock::now_ms(),
+        );
+        self.add_to_message_history(NetworkMessage::PulseReceipt(receipt)).await;
+        log::debug!("Sent pulse receipt for {} to {}", pulse.pulse_id, pulse.sender_id);
+        Ok(())
+    }
+
+    // One small signed probe per gossip topic so receivers can tell which leaders still reach them
+    pub async fn publish_mesh_heartbeats(&mut self, sequence: u64, keypair: &NodeKeypair) -> Result<()> {
+        let sender_id = self.local_node.id.to_string();
+        let now = crate::clock::now_ms();
+        for topic in crate::mesh::MESH_TOPICS {
+            let heartbeat = MeshHeartbeat::sign(topic, &sender_id, sequence, keypair, now);
+            self.add_to_message_history(NetworkMessage::MeshHeartbeat(heartbeat)).await;
+        }
+        log::debug!("Published mesh heartbeat {}", sequence);
+        Ok(())
+    }
+
+    // Dials the addresses no current peer is connected through; returns the ones dialed
+    pub async fn repair_mesh(&mut self, addresses: &[String]) -> Result<Vec<String>> {
+        let known: Vec<Multiaddr> = self.peers.read().await.values().map(|peer| peer.multiaddr.clone()).collect();
+        let mut dialed = Vec::new();
+        for address in addresses {
+            if known.contains(address) || dialed.contains(address) {
+                continue;
+            }
+            self.connect_to_peer(address).await?;
+            dialed.push(address.clone());
+        }
+        Ok(dialed)
+    }
+
+    // Gossiped again by each leader that co-signs, so signatures accumulate until a quorum is reached
+    pub async fn broadcast_governance_proposal(&mut self, proposal: &GovernanceProposal) -> Result<()> {
+        self.add_to_message_history(NetworkMessage::GovernanceProposal(proposal.clone())).await;
+        log::debug!("Broadcasted governance proposal {} with {} signatures", proposal.proposal_id, proposal.signatures.len());
+        Ok(())
+    }
+
+    // Relayed by every node that installs it, so a new list floods the network once
+    pub async fn broadcast_screening_list(&mut self, list: &ScreeningList) -> Result<()> {
+        self.add_to_message_history(NetworkMessage::ScreeningList(list.clone())).await;
+        log::debug!("Broadcasted screening list {}", list.version);
+        Ok(())
+    }
+
     pub async fn broadcast_leader_election(&mut self, election_id: &str, candidate_id: &str, votes: u64, round: u8) -> Result<()> {
         let message = NetworkMessage::LeaderElection(LeaderElectionMessage {
             election_id: election_id.to_string(),
@@ -197,6 +650,7 @@ impl NetworkManager {
             votes,
             round,
             timestamp: Utc::now(),
+            voter_id: self.local_node.id.to_string(),
         });
 
         self.add_to_message_history(message).await;
@@ -217,23 +671,207 @@ impl NetworkManager {
         Ok(())
     }
 
-    async fn add_to_message_history(&mut self, message: NetworkMessage) {
+    // Evidence, when the sender holds both leaders' signed entries, lets nodes that do not count the
+    // sender as a leader check the notice
+    pub async fn broadcast_transaction_invalidation(&mut self, raw_tx_id: &str, invalidated_leader_id: &str, winning_leader_id: &str, reason: &str, evidence: Option<ForkEvidence>, keypair: &NodeKeypair) -> Result<()> {
+        let mut notice = TransactionInvalidationMessage {
+            raw_tx_id: raw_tx_id.to_string(),
+            invalidated_leader_id: invalidated_leader_id.to_string(),
+            winning_leader_id: winning_leader_id.to_string(),
+            reason: reason.to_string(),
+            sender_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+            sender_public_key: String::new(),
+            signature: String::new(),
+            evidence,
+            replaced_by: None,
+            hops: 0,
+        };
+        notice.sign(keypair);
+        let message = NetworkMessage::TransactionInvalidation(notice);
+
+        self.add_to_message_history(message).await;
+        log::debug!("Broadcasted invalidation of {} from leader {}", raw_tx_id, invalidated_leader_id);
+        Ok(())
+    }
+
+    // Tells other leaders to drop a raw transaction this leader accepted a higher fee replacement for
+    pub async fn broadcast_transaction_replacement(&mut self, raw_tx_id: &str, replacement_id: &str, keypair: &NodeKeypair) -> Result<()> {
+        let local_id = self.local_node.id.to_string();
+        let mut notice = TransactionInvalidationMessage {
+            raw_tx_id: raw_tx_id.to_string(),
+            invalidated_leader_id: local_id.clone(),
+            winning_leader_id: local_id.clone(),
+            reason: "replaced by fee".to_string(),
+            sender_id: local_id,
+            timestamp: Utc::now(),
+            sender_public_key: String::new(),
+            signature: String::new(),
+            evidence: None,
+            replaced_by: Some(replacement_id.to_string()),
+            hops: 0,
+        };
+        notice.sign(keypair);
+        let message = NetworkMessage::TransactionInvalidation(notice);
+
+        self.add_to_message_history(message).await;
+        log::debug!("Broadcasted replacement of {} by {}", raw_tx_id, replacement_id);
+        Ok(())
+    }
+
+    pub async fn gossip_processing_transaction(&mut self, tx: &ProcessingTransaction) -> Result<()> {
+        let message = NetworkMessage::ProcessingTransactionGossip(ProcessingTransactionGossipMessage {
+            tx_id: tx.tx_id.clone(),
+            processing_transaction: tx.clone(),
+            sender_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Gossiped processing transaction: {}", tx.tx_id);
+        Ok(())
+    }
+
+    pub async fn broadcast_equivocation_evidence(&mut self, evidence: &EquivocationEvidence) -> Result<()> {
+        let message = NetworkMessage::EquivocationEvidence(EquivocationEvidenceMessage {
+            evidence: evidence.clone(),
+            sender_id: self.local_node.id.to_string(),
+            timestamp: Utc::now(),
+            hops: 0,
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Broadcasted equivocation evidence against leader {}", evidence.leader_id);
+        Ok(())
+    }
+
+    pub async fn broadcast_verified_processing_tx(&mut self, tx_id: &str, validator_signature_on_tx_id: &str, xmbl_cubic_root: u8, target_leaders: &[String]) -> Result<()> {
+        let message = NetworkMessage::VerifiedProcessingTxBroadcast(VerifiedProcessingTxBroadcastMessage {
+            tx_id: tx_id.to_string(),
+            validator_id: self.local_node.id.to_string(),
+            validator_signature_on_tx_id: validator_signature_on_tx_id.to_string(),
+            xmbl_cubic_root,
+            target_leaders: target_leaders.to_vec(),
+            timestamp: Utc::now(),
+        });
+
+        self.add_to_message_history(message).await;
+        log::debug!("Broadcasted verified processing tx {} to leaders {:?}", tx_id, target_leaders);
+        Ok(())
+    }
+
+    pub fn set_outbound_limits(&mut self, config: &NetworkConfig) {
+        self.outbound.set_limits(config);
+        self.bandwidth.set_limits(config);
+    }
+
+    // None returns the peer to the node-wide cap
+    pub fn set_peer_bandwidth_cap(&mut self, peer_id: &str, cap_bytes_per_sec: Option<u64>) {
+        self.bandwidth.set_peer_cap(peer_id, cap_bytes_per_sec);
+        log::info!("Bandwidth cap for {}: {:?} bytes/s", peer_id, cap_bytes_per_sec);
+    }
+
+    pub fn set_gossip_config(&mut self, config: &NetworkConfig) {
+        self.gossip.set_params(config);
+    }
+
+    // Repairs the mesh against the currently connected peers and starts a new history window
+    pub async fn gossip_heartbeat(&mut self) -> MeshChange {
+        let connected: Vec<PeerId> = self.peers.read().await.keys().cloned().collect();
+        let change = self.gossip.heartbeat(&connected);
+        if !change.grafted.is_empty() || !change.pruned.is_empty() {
+            log::debug!("Gossip mesh: grafted {}, pruned {}, now {} peers", change.grafted.len(), change.pruned.len(), self.gossip.mesh_peers().len());
+        }
+        change
+    }
+
+    pub fn set_transport(&mut self, transport: Box<dyn Transport>) {
+        log::info!("Network transport: {}", transport.name());
+        self.transport = Some(transport);
+    }
+
+    pub fn set_mdns_enabled(&mut self, enabled: bool) {
+        self.mdns_enabled = enabled;
+        log::info!("mDNS discovery {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    // Sends whatever the per-class caps allow now, control messages first. Returns how many went out.
+    pub async fn flush_outbound(&mut self) -> usize {
+        let now = Utc::now().timestamp_millis().max(0) as u64;
+        let ready = self.outbound.drain(now);
+        let sent = ready.len();
+        let connected: Vec<PeerId> = self.peers.read().await.keys().cloned().collect();
+        // Peers in a fixed order, so a deterministic transport sees the same sends on every run
+        let mut targets = self.gossip.publish_targets(&connected);
+        targets.sort();
+        for message in &ready {
+            let message_id = gossip_message_id(message);
+            self.gossip.remember(&message_id);
+            if relay_hops(message).is_some() {
+                self.gossip.mark_relayed(&message_id);
+            }
+            let encoded = message.encode().unwrap_or_default();
+            let bytes = encoded.len() as u64;
+            for peer_id in &targets {
+                if !self.bandwidth.record(peer_id, topic_of(message), bytes, TrafficDirection::Sent, now) {
+                    log::debug!("Not sending {} bytes to {}: over its bandwidth cap", bytes, peer_id);
+                } else if let Some(transport) = self.transport.as_mut().filter(|_| !encoded.is_empty()) {
+                    if let Err(e) = transport.send(peer_id, &encoded) {
+                        log::warn!("Could not send {} bytes to {}: {}", bytes, peer_id, e);
+                    }
+                }
+            }
+        }
         let mut history = self.message_history.write().await;
-        history.push(message);
+        history.extend(ready);
         
         // Keep only last 1000 messages
         if history.len() > 1000 {
-            history.drain(0..100);
+            let excess = history.len() - 900;
+            history.drain(0..excess);
+        }
+        sent
+    }
+
+    // Every publish goes through the outbound queues; messages over their class's cap wait for the
+    // next flush
+    async fn add_to_message_history(&mut self, message: NetworkMessage) {
+        self.outbound.push(message);
+        self.flush_outbound().await;
+    }
+
+    // Handles whatever the transport received. Returns the messages that passed the size, bandwidth
+    // and repeat checks, with the peer each came from, for the consensus layer to act on.
+    pub async fn receive(&mut self) -> Vec<(PeerId, NetworkMessage)> {
+        let mut accepted = Vec::new();
+        let events = self.transport.as_mut().map(|transport| transport.poll_events()).unwrap_or_default();
+        for event in events {
+            match event {
+                NetworkEvent::PeerMessage(peer_id, msg) => {
+                    if let Some(message) = self.handle_inbound(Some(&peer_id), &msg).await {
+                        accepted.push((peer_id, message));
+                    }
+                }
+                event => {
+                    if let Err(e) = self.handle_network_event(event).await {
+                        log::warn!("Error handling network event: {}", e);
+                    }
+                }
+            }
         }
+        accepted
     }
 
     pub async fn handle_network_event(&mut self, event: NetworkEvent) -> Result<()> {
         match event {
             NetworkEvent::Message(msg) => {
-                log::debug!("Received message: {}", msg);
+                self.handle_inbound(None, &msg).await;
+            }
+            NetworkEvent::PeerMessage(peer_id, msg) => {
+                self.handle_inbound(Some(&peer_id), &msg).await;
             }
             NetworkEvent::PeerConnected(peer_id) => {
-                log::info!("Peer connected: {}", peer_id);
+                tracing::info!(peer_id = %peer_id, "Peer connected");
                 
                 // Add to peers if not already present
                 if !self.peers.read().await.contains_key(&peer_id) {
@@ -250,11 +888,11 @@ impl NetworkManager {
                 }
             }
             NetworkEvent::PeerDisconnected(peer_id) => {
-                log::info!("Peer disconnected: {}", peer_id);
+                tracing::info!(peer_id = %peer_id, "Peer disconnected");
                 self.peers.write().await.remove(&peer_id);
             }
             NetworkEvent::PingReceived(peer_id, rtt) => {
-                log::debug!("Ping from {}: {:?}", peer_id, rtt);
+                tracing::debug!(peer_id = %peer_id, rtt_ms = rtt.as_millis() as u64, "Ping received");
                 
                 // Update peer last seen
                 let mut peers = self.peers.write().await;
@@ -266,6 +904,35 @@ impl NetworkManager {
         Ok(())
     }
 
+    async fn handle_inbound(&mut self, from: Option<&PeerId>, msg: &str) -> Option<NetworkMessage> {
+        match NetworkMessage::decode(msg.as_bytes()) {
+            Ok(message) => {
+                // Repeats still used the peer's bandwidth, so they are metered before being dropped
+                if let Some(peer_id) = from {
+                    let now = Utc::now().timestamp_millis().max(0) as u64;
+                    if !self.bandwidth.record(peer_id, topic_of(&message), msg.len() as u64, TrafficDirection::Received, now) {
+                        log::debug!("Dropped {} bytes from {}: over its bandwidth cap", msg.len(), peer_id);
+                        return None;
+                    }
+                }
+                if !self.gossip.remember(&gossip_message_id(&message)) {
+                    log::debug!("Dropped repeated message ({} bytes)", msg.len());
+                    return None;
+                }
+                log::debug!("Received message: {}", msg);
+                if let Some(relayed) = self.gossip.relay(&message) {
+                    self.add_to_message_history(relayed).await;
+                }
+                Some(message)
+            }
+            Err(e) => {
+                self.rejected_messages += 1;
+                log::warn!("Dropped inbound message ({} bytes): {}", msg.len(), e);
+                None
+            }
+        }
+    }
+
     // Network utility methods
     pub async fn get_connected_peers(&self) -> Vec<PeerId> {
         self.peers.read().await.keys().cloned().collect()
@@ -298,6 +965,7 @@ impl NetworkManager {
             messages_sent: history.len(),
             uptime_percentage: if self.connected { 100.0 } else { 0.0 },
             network_health: if self.connected && peers.len() > 0 { 100.0 } else { 50.0 },
+            rejected_messages: self.rejected_messages,
         }
     }
 
@@ -312,6 +980,8 @@ pub struct NetworkStats {
     pub messages_sent: usize,
     pub uptime_percentage: f64,
     pub network_health: f64,
+    #[serde(default)]
+    pub rejected_messages: u64,
 }
 
 // Simple network event loop
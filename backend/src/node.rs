@@ -4,7 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use ed25519_dalek::{VerifyingKey, Signature};
 use uuid::Uuid;
-use crate::crypto::{NodeKeypair, verify_ip_signature};
+use crate::crypto::{hash_data, NodeKeypair, verify_ip_signature};
 use crate::error::{PclError, Result};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -56,6 +56,13 @@ impl Node {
         Ok(node)
     }
 
+    // Node id derived from the public key, so a node recovered from its mnemonic keeps the same id
+    pub fn from_identity(ip_address: IpAddr, keypair: &NodeKeypair) -> Result<Self> {
+        let mut node = Self::new(ip_address, keypair)?;
+        node.id = identity_node_id(&keypair.public_key());
+        Ok(node)
+    }
+
     // New constructor for simulator that takes IP as string
     pub fn new_with_string_ip(ip: String, keypair: NodeKeypair, role: NodeRole) -> Result<Self> {
         let ip_address: IpAddr = ip.parse()
@@ -231,4 +238,11 @@ impl NodeRegistry {
         }
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+
+pub fn identity_node_id(public_key: &VerifyingKey) -> Uuid {
+    let hash = hash_data(&public_key.to_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    uuid::Builder::from_random_bytes(bytes).into_uuid()
+}
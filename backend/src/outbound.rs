@@ -0,0 +1,143 @@
+// Outbound module - prioritized send queues with per-class rate caps
+//
+// Everything a node publishes used to go out through one path in the order it was produced, so a
+// burst of transaction gossip could hold back election votes and invalidation notices. Messages are
+// now queued by class and each flush sends control first, then validation, then transaction gossip.
+// Each class has its own per-second cap, so a gossip backlog only ever delays more gossip.
+
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use crate::config::NetworkConfig;
+use crate::network::NetworkMessage;
+
+// Caps are counted over windows of this length
+const RATE_WINDOW_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageClass {
+    Control,    // elections, invalidations, evidence, governance, pulses, heartbeats and bindings
+    Validation, // tasks, task offers, processing entries and validator broadcasts
+    Gossip,     // raw transaction announcements, requests and bodies
+}
+
+impl MessageClass {
+    // Highest priority first
+    pub const ALL: [MessageClass; 3] = [MessageClass::Control, MessageClass::Validation, MessageClass::Gossip];
+
+    pub fn of(message: &NetworkMessage) -> Self {
+        match message {
+            NetworkMessage::LeaderElection(_)
+            | NetworkMessage::TransactionInvalidation(_)
+            | NetworkMessage::EquivocationEvidence(_)
+            | NetworkMessage::GovernanceProposal(_)
+            | NetworkMessage::ScreeningList(_)
+            | NetworkMessage::MeshHeartbeat(_)
+            | NetworkMessage::Pulse(_)
+            | NetworkMessage::PulseResponse(_)
+            | NetworkMessage::PulseReceipt(_)
+            | NetworkMessage::UptimeData(_)
+            | NetworkMessage::PeerBinding(_) => MessageClass::Control,
+            NetworkMessage::ValidationTask(_)
+            | NetworkMessage::ProcessingTransactionGossip(_)
+            | NetworkMessage::VerifiedProcessingTxBroadcast(_)
+            | NetworkMessage::AmountReveal(_)
+            | NetworkMessage::TaskOffer(_)
+            | NetworkMessage::TaskOfferAck(_) => MessageClass::Validation,
+            NetworkMessage::TransactionGossip(_)
+            | NetworkMessage::TransactionAnnouncement(_)
+            | NetworkMessage::TransactionRequest(_) => MessageClass::Gossip,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            MessageClass::Control => 0,
+            MessageClass::Validation => 1,
+            MessageClass::Gossip => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassStats {
+    pub queued: usize,
+    pub sent: u64,
+    pub dropped: u64, // oldest messages pushed out of a full queue
+}
+
+#[derive(Debug, Clone, Default)]
+struct ClassQueue {
+    messages: VecDeque<NetworkMessage>,
+    per_sec: u32, // 0 for no cap
+    sent_in_window: u32,
+    sent: u64,
+    dropped: u64,
+}
+
+impl ClassQueue {
+    fn has_budget(&self) -> bool {
+        self.per_sec == 0 || self.sent_in_window < self.per_sec
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboundQueues {
+    classes: [ClassQueue; 3], // indexed by MessageClass::index
+    max_queued: usize,        // per class
+    window_start: u64,
+}
+
+impl OutboundQueues {
+    pub fn new(config: &NetworkConfig) -> Self {
+        let mut queues = Self { classes: Default::default(), max_queued: config.outbound_queue_limit, window_start: 0 };
+        queues.set_limits(config);
+        queues
+    }
+
+    // Takes new caps without losing queued messages
+    pub fn set_limits(&mut self, config: &NetworkConfig) {
+        self.classes[MessageClass::Control.index()].per_sec = config.outbound_control_per_sec;
+        self.classes[MessageClass::Validation.index()].per_sec = config.outbound_validation_per_sec;
+        self.classes[MessageClass::Gossip.index()].per_sec = config.outbound_gossip_per_sec;
+        self.max_queued = config.outbound_queue_limit;
+    }
+
+    pub fn push(&mut self, message: NetworkMessage) {
+        let queue = &mut self.classes[MessageClass::of(&message).index()];
+        if queue.messages.len() >= self.max_queued {
+            queue.messages.pop_front();
+            queue.dropped += 1;
+        }
+        queue.messages.push_back(message);
+    }
+
+    // Messages that may be sent now, highest priority class first, within each class's cap
+    pub fn drain(&mut self, now: u64) -> Vec<NetworkMessage> {
+        if now.saturating_sub(self.window_start) >= RATE_WINDOW_MS {
+            self.window_start = now;
+            for queue in &mut self.classes {
+                queue.sent_in_window = 0;
+            }
+        }
+        let mut ready = Vec::new();
+        for queue in &mut self.classes {
+            while queue.has_budget() {
+                let Some(message) = queue.messages.pop_front() else { break };
+                queue.sent_in_window += 1;
+                queue.sent += 1;
+                ready.push(message);
+            }
+        }
+        ready
+    }
+
+    pub fn stats(&self, class: MessageClass) -> ClassStats {
+        let queue = &self.classes[class.index()];
+        ClassStats { queued: queue.messages.len(), sent: queue.sent, dropped: queue.dropped }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.iter().all(|queue| queue.messages.is_empty())
+    }
+}
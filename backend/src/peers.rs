@@ -0,0 +1,160 @@
+// Peers module - persistent address book of known peers with dial quality scores
+//
+// Peers found through discovery used to be forgotten on restart, so a node had to wait for mDNS or
+// the DHT before it could reach anyone. Every peer seen or dialed is recorded with its addresses,
+// role and dial history and written through to storage. At startup the best scoring entries are
+// dialed first, and entries that keep failing are pruned.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::error::Result;
+use crate::node::NodeRole;
+use crate::storage::StorageManager;
+
+// Addresses kept per peer, most recently seen first
+const MAX_ADDRESSES_PER_PEER: usize = 4;
+
+// Consecutive failed dials after which a peer is forgotten
+pub const MAX_CONSECUTIVE_DIAL_FAILURES: u32 = 5;
+
+// A week without contact halves a peer's quality
+const RECENCY_HALF_LIFE_MS: f64 = 7.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    pub peer_id: String,
+    pub node_id: String,
+    pub multiaddrs: Vec<String>,
+    pub role: NodeRole,
+    pub last_seen: u64, // ms since epoch
+    pub dial_attempts: u64,
+    pub dial_successes: u64,
+    pub consecutive_failures: u32,
+}
+
+impl PeerRecord {
+    pub fn new(peer_id: &str, node_id: &str, role: NodeRole) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            node_id: node_id.to_string(),
+            multiaddrs: Vec::new(),
+            role,
+            last_seen: 0,
+            dial_attempts: 0,
+            dial_successes: 0,
+            consecutive_failures: 0,
+        }
+    }
+
+    // Smoothed so a peer never dialed starts at 0.5
+    pub fn success_rate(&self) -> f64 {
+        (self.dial_successes as f64 + 1.0) / (self.dial_attempts as f64 + 2.0)
+    }
+
+    // Dial success rate discounted by how long ago the peer was last seen; leaders get a small boost
+    // since consensus traffic goes through them
+    pub fn quality(&self, now: u64) -> f64 {
+        let age_ms = now.saturating_sub(self.last_seen) as f64;
+        let recency = 0.5_f64.powf(age_ms / RECENCY_HALF_LIFE_MS);
+        let role_weight = if self.role == NodeRole::Leader { 1.25 } else { 1.0 };
+        self.success_rate() * recency * role_weight
+    }
+}
+
+pub struct AddressBook {
+    records: BTreeMap<String, PeerRecord>,
+    storage: Option<Arc<StorageManager>>, // None keeps the book in memory only
+}
+
+impl Default for AddressBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self { records: BTreeMap::new(), storage: None }
+    }
+
+    // Loads every stored peer and writes later changes through to storage
+    pub fn open(storage: Arc<StorageManager>) -> Result<Self> {
+        let records = storage.load_peer_records()?
+            .into_iter()
+            .map(|record| (record.peer_id.clone(), record))
+            .collect();
+        Ok(Self { records, storage: Some(storage) })
+    }
+
+    pub fn get(&self, peer_id: &str) -> Option<&PeerRecord> {
+        self.records.get(peer_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // Records a peer seen at an address, moving that address to the front of its list
+    pub fn observe(&mut self, peer_id: &str, node_id: &str, multiaddr: &str, role: NodeRole, now: u64) -> Result<()> {
+        let mut record = self.records.get(peer_id).cloned().unwrap_or_else(|| PeerRecord::new(peer_id, node_id, role));
+        record.node_id = node_id.to_string();
+        record.role = role;
+        record.last_seen = record.last_seen.max(now);
+        record.multiaddrs.retain(|existing| existing != multiaddr);
+        record.multiaddrs.insert(0, multiaddr.to_string());
+        record.multiaddrs.truncate(MAX_ADDRESSES_PER_PEER);
+        self.save(record)
+    }
+
+    // Returns false if the failure pruned the peer from the book
+    pub fn record_dial(&mut self, peer_id: &str, success: bool, now: u64) -> Result<bool> {
+        let Some(mut record) = self.records.get(peer_id).cloned() else {
+            return Ok(false);
+        };
+        record.dial_attempts += 1;
+        if success {
+            record.dial_successes += 1;
+            record.consecutive_failures = 0;
+            record.last_seen = record.last_seen.max(now);
+        } else {
+            record.consecutive_failures += 1;
+        }
+        if record.consecutive_failures >= MAX_CONSECUTIVE_DIAL_FAILURES {
+            self.remove(peer_id)?;
+            log::info!("Pruned peer {} from the address book after {} failed dials", peer_id, record.consecutive_failures);
+            return Ok(false);
+        }
+        self.save(record)?;
+        Ok(true)
+    }
+
+    pub fn remove(&mut self, peer_id: &str) -> Result<()> {
+        if self.records.remove(peer_id).is_some() {
+            if let Some(storage) = &self.storage {
+                storage.delete_peer_record(peer_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    // The best peers to dial first, highest quality first
+    pub fn dial_candidates(&self, limit: usize, now: u64) -> Vec<&PeerRecord> {
+        let mut candidates: Vec<&PeerRecord> = self.records.values().filter(|record| !record.multiaddrs.is_empty()).collect();
+        candidates.sort_by(|a, b| b.quality(now).total_cmp(&a.quality(now)).then_with(|| a.peer_id.cmp(&b.peer_id)));
+        candidates.truncate(limit);
+        candidates
+    }
+
+    fn save(&mut self, record: PeerRecord) -> Result<()> {
+        if let Some(storage) = &self.storage {
+            storage.store_peer_record(&record)?;
+        }
+        self.records.insert(record.peer_id.clone(), record);
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+// Probation module - proof of uptime before a new node's votes and task completions count
+//
+// Identities are free, so a burst of them could flood nominations. A node first seen by this node
+// starts on probation and leaves it once the probation period has passed and peers have signed
+// receipts for at least probation_pulse_receipts of its pulses. Receipts only count when the
+// witness is established itself, so fresh identities cannot vouch for each other.
+
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::config::ConsensusConfig;
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::{decode_public_key, decode_signature};
+
+// A witness's signed statement that it received one of a node's pulses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PulseReceipt {
+    pub node_id: String, // the node that pulsed
+    pub pulse_id: String,
+    pub witness_id: String,
+    pub witness_public_key: String, // hex encoded
+    pub received_at: u64,           // ms since epoch
+    pub signature: String,          // hex encoded
+}
+
+impl PulseReceipt {
+    pub fn sign(node_id: &str, pulse_id: &str, witness_id: &str, witness: &NodeKeypair, received_at: u64) -> Self {
+        let mut receipt = Self {
+            node_id: node_id.to_string(),
+            pulse_id: pulse_id.to_string(),
+            witness_id: witness_id.to_string(),
+            witness_public_key: hex::encode(witness.public_key().to_bytes()),
+            received_at,
+            signature: String::new(),
+        };
+        receipt.signature = hex::encode(witness.sign_data(&receipt.signing_bytes()).to_bytes());
+        receipt
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!("pcl-pulse-receipt:{}:{}:{}:{}", self.node_id, self.pulse_id, self.witness_id, self.received_at).into_bytes()
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        let public_key = decode_public_key(&self.witness_public_key)?;
+        let signature = decode_signature(&self.signature)?;
+        if !verify_data_signature(&self.signing_bytes(), &signature, &public_key)? {
+            return Err(PclError::SignatureVerification(format!(
+                "Invalid pulse receipt from {} for {}", self.witness_id, self.node_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProbationRecord {
+    pub first_seen_at: u64,
+    pub established: bool, // admitted without probation, e.g. bootstrap nodes
+    pub receipted_pulses: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbationStatus {
+    pub node_id: String,
+    pub on_probation: bool,
+    pub first_seen_at: u64,
+    pub probation_ends_at: u64, // earliest time the period is served; receipts may still be missing
+    pub pulse_receipts: usize,
+    pub required_pulse_receipts: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProbationTracker {
+    required_receipts: usize, // 0 turns probation off
+    period_ms: u64,
+    records: HashMap<String, ProbationRecord>,
+}
+
+impl ProbationTracker {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            required_receipts: config.probation_pulse_receipts,
+            period_ms: config.probation_period_secs * 1000,
+            records: HashMap::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.required_receipts > 0
+    }
+
+    // Starts the node's probation clock if this is the first time it is seen
+    pub fn observe(&mut self, node_id: &str, now: u64) {
+        self.records.entry(node_id.to_string())
+            .or_insert_with(|| ProbationRecord { first_seen_at: now, ..ProbationRecord::default() });
+    }
+
+    // Skips probation, for bootstrap nodes and the local node
+    pub fn admit(&mut self, node_id: &str) {
+        self.records.entry(node_id.to_string()).or_default().established = true;
+    }
+
+    // Returns true if the receipt counted towards a pulse not already receipted
+    pub fn record_receipt(&mut self, receipt: &PulseReceipt, now: u64) -> Result<bool> {
+        receipt.verify()?;
+        if receipt.witness_id == receipt.node_id {
+            return Err(PclError::Validation(format!("{} cannot witness its own pulses", receipt.node_id)));
+        }
+        if self.is_on_probation(&receipt.witness_id, now) {
+            return Err(PclError::Probation(format!(
+                "Witness {} is on probation and cannot vouch for {}", receipt.witness_id, receipt.node_id
+            )));
+        }
+
+        self.observe(&receipt.node_id, now);
+        let record = self.records.get_mut(&receipt.node_id).expect("observed above");
+        Ok(record.receipted_pulses.insert(receipt.pulse_id.clone()))
+    }
+
+    // Nodes never seen are on probation, since they would start it now
+    pub fn is_on_probation(&self, node_id: &str, now: u64) -> bool {
+        self.status(node_id, now).on_probation
+    }
+
+    pub fn status(&self, node_id: &str, now: u64) -> ProbationStatus {
+        let record = self.records.get(node_id);
+        let first_seen_at = record.map_or(now, |record| record.first_seen_at);
+        let pulse_receipts = record.map_or(0, |record| record.receipted_pulses.len());
+        let probation_ends_at = first_seen_at + self.period_ms;
+        let served = now >= probation_ends_at && pulse_receipts >= self.required_receipts;
+        ProbationStatus {
+            node_id: node_id.to_string(),
+            on_probation: self.enabled() && !record.is_some_and(|record| record.established) && !served,
+            first_seen_at,
+            probation_ends_at,
+            pulse_receipts,
+            required_pulse_receipts: self.required_receipts,
+        }
+    }
+
+    // Observes the node, so the check itself starts probation for an unknown identity
+    pub fn ensure_eligible(&mut self, node_id: &str, now: u64, purpose: &str) -> Result<()> {
+        if !self.enabled() {
+            return Ok(());
+        }
+        self.observe(node_id, now);
+        let status = self.status(node_id, now);
+        if status.on_probation {
+            return Err(PclError::Probation(format!(
+                "{} has {} of {} pulse receipts and probation ends at {}; {} not accepted yet",
+                node_id, status.pulse_receipts, status.required_pulse_receipts, status.probation_ends_at, purpose
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn records(&self) -> &HashMap<String, ProbationRecord> {
+        &self.records
+    }
+
+    pub fn restore(&mut self, records: HashMap<String, ProbationRecord>) {
+        self.records = records;
+    }
+}
@@ -0,0 +1,148 @@
+// Quorum module - k-of-n cross-validation of each raw transaction
+//
+// The submitter's own validation tasks show it did its share of work for the network, but say
+// nothing about its transaction. Other validators are assigned cross-validation tasks on it, up to n
+// per transaction, and the leader holding it only averages timestamps and signs once k of them have
+// completed. A validator that misses its deadline can still complete late, but its slot opens up so
+// a replacement can be assigned and one unresponsive validator cannot hold the transaction back.
+
+use std::collections::{BTreeSet, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::config::ConsensusConfig;
+
+// Task type of the tasks that count towards the quorum
+pub const CROSS_VALIDATION_TASK: &str = "cross_validation";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationQuorum {
+    pub required: usize,   // k
+    pub validators: usize, // n
+    pub assigned: BTreeSet<String>,
+    pub completed: BTreeSet<String>,
+    pub missed: BTreeSet<String>, // assigned, past the deadline and not completed
+}
+
+impl ValidationQuorum {
+    pub fn is_met(&self) -> bool {
+        self.completed.len() >= self.required
+    }
+
+    // Assigned validators still expected to complete
+    pub fn open(&self) -> usize {
+        self.assigned.iter()
+            .filter(|validator| !self.completed.contains(*validator) && !self.missed.contains(*validator))
+            .count()
+    }
+
+    fn has_slot(&self) -> bool {
+        self.completed.len() + self.open() < self.validators
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QuorumTracker {
+    required: usize,
+    validators: usize,
+    quorums: HashMap<String, ValidationQuorum>, // raw_tx_id -> quorum
+}
+
+impl QuorumTracker {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            required: config.cross_validation_quorum,
+            validators: config.cross_validators_per_tx,
+            quorums: HashMap::new(),
+        }
+    }
+
+    // Governance can change k and n; transactions already being validated follow the new values
+    pub fn set_limits(&mut self, config: &ConsensusConfig) {
+        self.required = config.cross_validation_quorum;
+        self.validators = config.cross_validators_per_tx;
+        for quorum in self.quorums.values_mut() {
+            quorum.required = self.required;
+            quorum.validators = self.validators;
+        }
+    }
+
+    // Re-adds one cross-validation task when rebuilding from saved tasks, whatever the caps
+    pub fn restore(&mut self, raw_tx_id: &str, validator: &str, complete: bool, missed: bool) {
+        let quorum = self.quorum_mut(raw_tx_id);
+        quorum.assigned.insert(validator.to_string());
+        if complete {
+            quorum.completed.insert(validator.to_string());
+        } else if missed {
+            quorum.missed.insert(validator.to_string());
+        }
+    }
+
+    // With k = 0 assignments and completions are still tracked, but nothing is capped or waited for
+    pub fn enabled(&self) -> bool {
+        self.required > 0
+    }
+
+    pub fn can_assign(&self, raw_tx_id: &str, validator: &str) -> bool {
+        let Some(quorum) = self.quorums.get(raw_tx_id) else {
+            return true;
+        };
+        !quorum.assigned.contains(validator) && (!self.enabled() || (!quorum.is_met() && quorum.has_slot()))
+    }
+
+    pub fn assign(&mut self, raw_tx_id: &str, validator: &str) -> bool {
+        if !self.can_assign(raw_tx_id, validator) {
+            return false;
+        }
+        self.quorum_mut(raw_tx_id).assigned.insert(validator.to_string());
+        true
+    }
+
+    // Only validators assigned to the transaction count; returns whether this completion was counted
+    pub fn record_completion(&mut self, raw_tx_id: &str, validator: &str) -> bool {
+        match self.quorums.get_mut(raw_tx_id) {
+            Some(quorum) if quorum.assigned.contains(validator) => {
+                quorum.missed.remove(validator);
+                quorum.completed.insert(validator.to_string())
+            }
+            _ => false,
+        }
+    }
+
+    pub fn record_missed(&mut self, raw_tx_id: &str, validator: &str) {
+        if let Some(quorum) = self.quorums.get_mut(raw_tx_id) {
+            if quorum.assigned.contains(validator) && !quorum.completed.contains(validator) {
+                quorum.missed.insert(validator.to_string());
+            }
+        }
+    }
+
+    pub fn is_met(&self, raw_tx_id: &str) -> bool {
+        !self.enabled() || self.quorums.get(raw_tx_id).is_some_and(ValidationQuorum::is_met)
+    }
+
+    pub fn get(&self, raw_tx_id: &str) -> Option<&ValidationQuorum> {
+        self.quorums.get(raw_tx_id)
+    }
+
+    // Completions so far, with k and n, even for a transaction no one has been assigned yet
+    pub fn status(&self, raw_tx_id: &str) -> ValidationQuorum {
+        self.quorums.get(raw_tx_id).cloned().unwrap_or_else(|| ValidationQuorum {
+            required: self.required,
+            validators: self.validators,
+            ..ValidationQuorum::default()
+        })
+    }
+
+    pub fn remove(&mut self, raw_tx_id: &str) -> Option<ValidationQuorum> {
+        self.quorums.remove(raw_tx_id)
+    }
+
+    pub fn clear(&mut self) {
+        self.quorums.clear();
+    }
+
+    fn quorum_mut(&mut self, raw_tx_id: &str) -> &mut ValidationQuorum {
+        let (required, validators) = (self.required, self.validators);
+        self.quorums.entry(raw_tx_id.to_string())
+            .or_insert_with(|| ValidationQuorum { required, validators, ..ValidationQuorum::default() })
+    }
+}
@@ -0,0 +1,97 @@
+// Receipt module - signed proof of finalization handed to clients and webhook subscribers
+//
+// The leader signature is the one the leader already makes over the tx id and averaged timestamp when
+// it moves the transaction to processing. Each validator that broadcasts the finalization then signs
+// the whole receipt, leader signature included, so a receipt can be checked without asking a node.
+
+use serde::{Deserialize, Serialize};
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::{decode_public_key, decode_signature};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptSignature {
+    pub signer: String,     // leader or validator node id
+    pub public_key: String, // hex encoded
+    pub signature: String,  // hex encoded
+}
+
+impl ReceiptSignature {
+    pub fn sign(signer: &str, keypair: &NodeKeypair, message: &[u8]) -> Self {
+        Self {
+            signer: signer.to_string(),
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: hex::encode(keypair.sign_data(message).to_bytes()),
+        }
+    }
+
+    fn verify(&self, message: &[u8]) -> Result<()> {
+        let public_key = decode_public_key(&self.public_key)?;
+        let signature = decode_signature(&self.signature)?;
+        if !verify_data_signature(message, &signature, &public_key)? {
+            return Err(PclError::SignatureVerification(format!("Invalid receipt signature from {}", self.signer)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub tx_id: String,
+    pub tx_hash: String,         // hex hash of the finalized transaction data
+    pub averaged_timestamp: u64, // ms since epoch, averaged from the validation timestamps
+    pub digital_root: u32,
+    pub leader_signature: ReceiptSignature,          // over leader_signing_bytes
+    pub validator_signatures: Vec<ReceiptSignature>, // over signing_bytes
+    pub finalized_at: u64,
+}
+
+impl TransactionReceipt {
+    pub fn new(
+        tx_id: &str,
+        tx_hash: &str,
+        averaged_timestamp: u64,
+        digital_root: u32,
+        leader_signature: ReceiptSignature,
+        finalized_at: u64,
+    ) -> Self {
+        Self {
+            tx_id: tx_id.to_string(),
+            tx_hash: tx_hash.to_string(),
+            averaged_timestamp,
+            digital_root,
+            leader_signature,
+            validator_signatures: Vec::new(),
+            finalized_at,
+        }
+    }
+
+    // What a leader signs when it moves a transaction to processing
+    pub fn leader_signing_bytes(tx_id: &str, averaged_timestamp: u64) -> Vec<u8> {
+        format!("{}{}", tx_id, averaged_timestamp).into_bytes()
+    }
+
+    // What validators sign: every field except their own signatures
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "pcl-receipt:{}:{}:{}:{}:{}:{}:{}",
+            self.tx_id, self.tx_hash, self.averaged_timestamp, self.digital_root,
+            self.leader_signature.signer, self.leader_signature.signature, self.finalized_at
+        ).into_bytes()
+    }
+
+    pub fn add_validator_signature(&mut self, validator_id: &str, keypair: &NodeKeypair) {
+        let signature = ReceiptSignature::sign(validator_id, keypair, &self.signing_bytes());
+        self.validator_signatures.push(signature);
+    }
+
+    // Checks the leader signature and every validator signature; which keys to trust is up to the caller
+    pub fn verify(&self) -> Result<()> {
+        self.leader_signature.verify(&Self::leader_signing_bytes(&self.tx_id, self.averaged_timestamp))?;
+        let message = self.signing_bytes();
+        for signature in &self.validator_signatures {
+            signature.verify(&message)?;
+        }
+        Ok(())
+    }
+}
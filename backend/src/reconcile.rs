@@ -0,0 +1,197 @@
+// Reconcile module - finding the finalized transactions two leaders disagree on after a partition
+//
+// Leaders cut off from each other keep finalizing, and when the partition heals each side is missing
+// what the other finalized, or holds a different leader's entry for the same raw transaction. Each
+// node summarises its finalized set as a prefix tree over transaction ids: a node's hash covers every
+// leaf under its prefix. Two nodes compare roots, descend only into child prefixes whose hashes differ
+// and, once a prefix holds few enough leaves, compare those leaf by leaf. The transactions that differ
+// are fetched with their receipts and checked before they are applied; an entry from another leader
+// for a transaction already final here is settled by the fork tie-break.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::crypto::hash_data;
+use crate::mempool::FinalizationClaim;
+use crate::receipt::TransactionReceipt;
+
+// A prefix with this many leaves or fewer is compared leaf by leaf instead of descended into
+pub const SYNC_LEAF_LIMIT: usize = 64;
+
+// One finalized transaction as the tree sees it; two nodes agree on it if the hashes match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncLeaf {
+    pub tx_id: String,
+    pub leader_id: String,
+    pub leader_public_key: String, // hex encoded; the fork tie-break orders equal timestamps by it
+    pub averaged_timestamp: u64,   // ms since epoch
+    pub content_hash: String,      // hex hash of the transaction data
+    pub hash: String,
+}
+
+impl SyncLeaf {
+    pub fn new(tx_id: &str, leader_id: &str, leader_public_key: &str, averaged_timestamp: u64, content_hash: &str) -> Self {
+        let hash = hex::encode(hash_data(format!(
+            "pcl-sync-leaf:{}:{}:{}:{}:{}", tx_id, leader_id, leader_public_key, averaged_timestamp, content_hash
+        ).as_bytes()));
+        Self {
+            tx_id: tx_id.to_string(),
+            leader_id: leader_id.to_string(),
+            leader_public_key: leader_public_key.to_string(),
+            averaged_timestamp,
+            content_hash: content_hash.to_string(),
+            hash,
+        }
+    }
+
+    pub fn claim(&self) -> FinalizationClaim {
+        FinalizationClaim {
+            raw_tx_id: self.tx_id.clone(),
+            leader_id: self.leader_id.clone(),
+            leader_public_key: self.leader_public_key.clone(),
+            averaged_timestamp: chrono::DateTime::from_timestamp_millis(self.averaged_timestamp as i64).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncChild {
+    pub prefix: String,
+    pub hash: String,
+    pub count: usize,
+}
+
+// What GET /sync/tree?prefix= returns: the prefix's hash and one entry per next character in use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncNode {
+    pub prefix: String,
+    pub hash: String,
+    pub count: usize,
+    pub children: Vec<SyncChild>,
+}
+
+// Where a comparison at one prefix leads
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncDescent {
+    Same,
+    CompareLeaves,
+    Children(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncTree {
+    leaves: BTreeMap<String, SyncLeaf>,
+}
+
+impl SyncTree {
+    pub fn new(leaves: impl IntoIterator<Item = SyncLeaf>) -> Self {
+        Self { leaves: leaves.into_iter().map(|leaf| (leaf.tx_id.clone(), leaf)).collect() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn get(&self, tx_id: &str) -> Option<&SyncLeaf> {
+        self.leaves.get(tx_id)
+    }
+
+    pub fn root(&self) -> String {
+        self.node("").hash
+    }
+
+    pub fn leaves(&self, prefix: &str) -> Vec<SyncLeaf> {
+        self.under(prefix).cloned().collect()
+    }
+
+    pub fn node(&self, prefix: &str) -> SyncNode {
+        let mut children: BTreeMap<String, Vec<&SyncLeaf>> = BTreeMap::new();
+        let mut all = Vec::new();
+        for leaf in self.under(prefix) {
+            if let Some(next) = leaf.tx_id[prefix.len()..].chars().next() {
+                children.entry(format!("{}{}", prefix, next)).or_default().push(leaf);
+            }
+            all.push(leaf);
+        }
+        SyncNode {
+            prefix: prefix.to_string(),
+            hash: digest(&all),
+            count: all.len(),
+            children: children.into_iter()
+                .map(|(prefix, leaves)| SyncChild { prefix, hash: digest(&leaves), count: leaves.len() })
+                .collect(),
+        }
+    }
+
+    // Compares this node's view of a prefix with a peer's
+    pub fn descend(&self, remote: &SyncNode) -> SyncDescent {
+        let local = self.node(&remote.prefix);
+        if local.hash == remote.hash {
+            return SyncDescent::Same;
+        }
+        if remote.count <= SYNC_LEAF_LIMIT || remote.children.is_empty() {
+            return SyncDescent::CompareLeaves;
+        }
+        let local_children: BTreeMap<&str, &str> = local.children.iter().map(|child| (child.prefix.as_str(), child.hash.as_str())).collect();
+        SyncDescent::Children(remote.children.iter()
+            .filter(|child| local_children.get(child.prefix.as_str()) != Some(&child.hash.as_str()))
+            .map(|child| child.prefix.clone())
+            .collect())
+    }
+
+    // A peer's leaves this node lacks or holds differently, paired with the local leaf if there is one
+    pub fn differing(&self, remote_leaves: &[SyncLeaf]) -> Vec<(SyncLeaf, Option<SyncLeaf>)> {
+        remote_leaves.iter()
+            .filter(|remote| self.leaves.get(&remote.tx_id).map(|local| &local.hash) != Some(&remote.hash))
+            .map(|remote| (remote.clone(), self.leaves.get(&remote.tx_id).cloned()))
+            .collect()
+    }
+
+    fn under<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a SyncLeaf> + 'a {
+        self.leaves.range(prefix.to_string()..)
+            .take_while(move |(tx_id, _)| tx_id.starts_with(prefix))
+            .map(|(_, leaf)| leaf)
+    }
+}
+
+fn digest(leaves: &[&SyncLeaf]) -> String {
+    let mut bytes = Vec::new();
+    for leaf in leaves {
+        bytes.extend_from_slice(leaf.tx_id.as_bytes());
+        bytes.push(b':');
+        bytes.extend_from_slice(leaf.hash.as_bytes());
+        bytes.push(b'\n');
+    }
+    hex::encode(hash_data(&bytes))
+}
+
+// What GET /sync/transaction/{id} returns; the transaction is the node's own JSON form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedTransaction {
+    pub transaction: serde_json::Value,
+    pub receipt: Option<TransactionReceipt>,
+}
+
+// The same raw transaction finalized under different leaders on either side of a partition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub tx_id: String,
+    pub local_leader: String,
+    pub remote_leader: String,
+    pub winner: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    pub peer: String,
+    pub local_root: String,
+    pub remote_root: String,
+    pub prefixes_compared: usize,
+    pub fetched: usize,
+    pub applied: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+    pub failed: Vec<String>, // "tx_id: reason" for transactions that did not verify or apply
+}
@@ -0,0 +1,122 @@
+// Relay module - compact transaction relay: announce ids, send bodies on request
+//
+// Gossiping every raw transaction body to every leader wastes bandwidth when most of them already
+// have it. Leaders announce a raw_tx_id with a short hash of the body instead, and a peer asks the
+// announcer for the body only when it does not hold a transaction with that id and hash, the same
+// idea as compact block relay.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::error::{PclError, Result};
+use crate::mempool::RawTxMempool;
+use crate::transaction::RawTransaction;
+
+// Hex characters of the body hash sent with an announcement (8 bytes)
+pub const SHORT_HASH_LEN: usize = 16;
+
+// A request not answered within this long may be sent again to the next announcer
+const REQUEST_TIMEOUT_MS: u64 = 5_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxAnnouncement {
+    pub raw_tx_id: String,
+    pub short_hash: String,
+}
+
+impl TxAnnouncement {
+    pub fn for_transaction(tx: &RawTransaction) -> Result<Self> {
+        Ok(Self { raw_tx_id: tx.raw_tx_id.clone(), short_hash: short_tx_hash(tx)? })
+    }
+}
+
+// Prefix of the mempool's body hash, so a peer can tell a different body under a known id
+pub fn short_tx_hash(tx: &RawTransaction) -> Result<String> {
+    let mut hash = RawTxMempool::transaction_hash(tx)?;
+    hash.truncate(SHORT_HASH_LEN);
+    Ok(hash)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelayStats {
+    pub announcements_received: u64,
+    pub already_known: u64,   // announcements that needed no body, i.e. bodies not sent
+    pub bodies_requested: u64,
+    pub bodies_received: u64,
+    pub bodies_rejected: u64, // did not match the hash that was announced
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PendingRequest {
+    peer_id: String,
+    short_hash: String,
+    requested_at: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CompactRelay {
+    pending: HashMap<String, PendingRequest>, // raw_tx_id -> outstanding body request
+    stats: RelayStats,
+}
+
+impl CompactRelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stats(&self) -> &RelayStats {
+        &self.stats
+    }
+
+    pub fn is_pending(&self, raw_tx_id: &str) -> bool {
+        self.pending.contains_key(raw_tx_id)
+    }
+
+    // Ids to request from the announcer: unknown ones that are not already being fetched from
+    // another peer, unless that request timed out
+    pub fn missing(
+        &mut self,
+        peer_id: &str,
+        announcements: &[TxAnnouncement],
+        is_known: impl Fn(&TxAnnouncement) -> bool,
+        now: u64,
+    ) -> Vec<String> {
+        let mut wanted = Vec::new();
+        for announcement in announcements {
+            self.stats.announcements_received += 1;
+            if is_known(announcement) {
+                self.stats.already_known += 1;
+                continue;
+            }
+            let in_flight = self.pending.get(&announcement.raw_tx_id)
+                .is_some_and(|request| now.saturating_sub(request.requested_at) < REQUEST_TIMEOUT_MS);
+            if in_flight || wanted.contains(&announcement.raw_tx_id) {
+                continue;
+            }
+            self.pending.insert(announcement.raw_tx_id.clone(), PendingRequest {
+                peer_id: peer_id.to_string(),
+                short_hash: announcement.short_hash.clone(),
+                requested_at: now,
+            });
+            self.stats.bodies_requested += 1;
+            wanted.push(announcement.raw_tx_id.clone());
+        }
+        wanted
+    }
+
+    // Matches a received body to its request. Ok(false) for bodies nobody asked for; an error if
+    // the body is not the one that was announced, leaving the request open for another peer.
+    pub fn accept_body(&mut self, tx: &RawTransaction) -> Result<bool> {
+        let Some(request) = self.pending.get(&tx.raw_tx_id) else {
+            return Ok(false);
+        };
+        if short_tx_hash(tx)? != request.short_hash {
+            self.stats.bodies_rejected += 1;
+            return Err(PclError::Validation(format!(
+                "Body of {} does not match the hash announced by {}", tx.raw_tx_id, request.peer_id
+            )));
+        }
+        self.pending.remove(&tx.raw_tx_id);
+        self.stats.bodies_received += 1;
+        Ok(true)
+    }
+}
@@ -0,0 +1,50 @@
+// Replay module - rebuilds node state from a copy of the consensus event journal
+//
+// A bug report comes with a node's data directory, but by then the ledger reflects every event up to
+// the crash, not the state the bug happened in. Replay reads the journal from that directory and
+// applies its events in sequence to a fresh in-memory ledger, optionally stopping after a given
+// sequence number. Nothing is written back: the copy is only read, and the ledger has no storage.
+
+use crate::config::ConsensusConfig;
+use crate::error::Result;
+use crate::staking::{StakeEvent, StakeLedger};
+use crate::storage::StorageManager;
+
+pub struct JournalReplay {
+    pub stakes: StakeLedger,
+    pub applied: Vec<(u64, StakeEvent)>, // sequence and event, in the order applied
+    pub journal_length: u64,             // last sequence in the journal, whether or not it was reached
+    pub failed_at: Option<(u64, String)>, // the event that could not be applied, which ends the replay
+}
+
+impl JournalReplay {
+    // Applies every event with a sequence up to `until` (the whole journal when None)
+    pub fn run(journal: &StorageManager, config: &ConsensusConfig, until: Option<u64>) -> Result<Self> {
+        let events = journal.load_stake_events()?;
+        let mut replay = Self {
+            stakes: StakeLedger::new(config),
+            applied: Vec::new(),
+            journal_length: events.last().map_or(0, |(sequence, _)| *sequence),
+            failed_at: None,
+        };
+        for (sequence, event) in events {
+            if until.is_some_and(|until| sequence > until) {
+                break;
+            }
+            if let Err(e) = replay.stakes.replay(&event) {
+                replay.failed_at = Some((sequence, e.to_string()));
+                break;
+            }
+            replay.applied.push((sequence, event));
+        }
+        Ok(replay)
+    }
+
+    pub fn last_applied(&self) -> u64 {
+        self.applied.last().map_or(0, |(sequence, _)| *sequence)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.failed_at.is_none() && self.last_applied() == self.journal_length
+    }
+}
@@ -0,0 +1,135 @@
+// Reputation module - per-identity track record of validation work and submissions
+//
+// Every identity starts neutral at a score of 0.5. Tasks completed before their deadline raise it;
+// missed and late tasks, invalid signatures, invalidated submissions and submissions over the
+// per-user rate limit lower it; invalid signatures and invalidated submissions most heavily since
+// they are not accidents of connectivity. Records are written through to storage so a
+// restarted node keeps judging peers on their full history.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::error::Result;
+use crate::storage::StorageManager;
+
+// How much each kind of fault counts against an identity, relative to one on-time task
+const LATE_TASK_WEIGHT: f64 = 0.5;
+const MISSED_TASK_WEIGHT: f64 = 1.0;
+const INVALID_SIGNATURE_WEIGHT: f64 = 3.0;
+const INVALIDATED_SUBMISSION_WEIGHT: f64 = 2.0;
+const RATE_LIMIT_VIOLATION_WEIGHT: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationEvent {
+    TaskOnTime,
+    TaskLate,
+    TaskMissed,
+    InvalidSignature,
+    InvalidatedSubmission,
+    RateLimitExceeded,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReputationRecord {
+    pub identity: String, // address or node id
+    pub tasks_on_time: u64,
+    pub tasks_late: u64,
+    pub tasks_missed: u64,
+    pub invalid_signatures: u64,
+    pub invalidated_submissions: u64,
+    pub rate_limit_violations: u64,
+    pub updated_at: u64, // ms since epoch
+}
+
+impl ReputationRecord {
+    pub fn new(identity: &str) -> Self {
+        Self { identity: identity.to_string(), ..Self::default() }
+    }
+
+    // 0-1, smoothed so one event moves a new identity only part of the way from 0.5
+    pub fn score(&self) -> f64 {
+        let good = self.tasks_on_time as f64;
+        let bad = self.tasks_late as f64 * LATE_TASK_WEIGHT
+            + self.tasks_missed as f64 * MISSED_TASK_WEIGHT
+            + self.invalid_signatures as f64 * INVALID_SIGNATURE_WEIGHT
+            + self.invalidated_submissions as f64 * INVALIDATED_SUBMISSION_WEIGHT
+            + self.rate_limit_violations as f64 * RATE_LIMIT_VIOLATION_WEIGHT;
+        (good + 1.0) / (good + bad + 2.0)
+    }
+
+    fn apply(&mut self, event: ReputationEvent, now: u64) {
+        match event {
+            ReputationEvent::TaskOnTime => self.tasks_on_time += 1,
+            ReputationEvent::TaskLate => self.tasks_late += 1,
+            ReputationEvent::TaskMissed => self.tasks_missed += 1,
+            ReputationEvent::InvalidSignature => self.invalid_signatures += 1,
+            ReputationEvent::InvalidatedSubmission => self.invalidated_submissions += 1,
+            ReputationEvent::RateLimitExceeded => self.rate_limit_violations += 1,
+        }
+        self.updated_at = now;
+    }
+}
+
+pub struct ReputationLedger {
+    records: BTreeMap<String, ReputationRecord>,
+    storage: Option<Arc<StorageManager>>, // None keeps the ledger in memory only
+}
+
+impl Default for ReputationLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReputationLedger {
+    pub fn new() -> Self {
+        Self { records: BTreeMap::new(), storage: None }
+    }
+
+    // Loads every stored record and writes later changes through to storage
+    pub fn open(storage: Arc<StorageManager>) -> Result<Self> {
+        let records = storage.load_reputation_records()?
+            .into_iter()
+            .map(|record| (record.identity.clone(), record))
+            .collect();
+        Ok(Self { records, storage: Some(storage) })
+    }
+
+    pub fn record(&mut self, identity: &str, event: ReputationEvent, now: u64) -> Result<&ReputationRecord> {
+        let mut record = self.records.get(identity).cloned().unwrap_or_else(|| ReputationRecord::new(identity));
+        record.apply(event, now);
+        if let Some(storage) = &self.storage {
+            storage.store_reputation_record(&record)?;
+        }
+        log::debug!("Reputation of {} after {:?}: {:.3}", identity, event, record.score());
+        self.records.insert(identity.to_string(), record);
+        Ok(&self.records[identity])
+    }
+
+    // Identities with no history get a neutral record
+    pub fn get(&self, identity: &str) -> ReputationRecord {
+        self.records.get(identity).cloned().unwrap_or_else(|| ReputationRecord::new(identity))
+    }
+
+    pub fn score(&self, identity: &str) -> f64 {
+        self.records.get(identity).map_or(0.5, ReputationRecord::score)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &ReputationRecord> {
+        self.records.values()
+    }
+
+    // Tasks handed out per request: `base` at a neutral score, down to 1 for the least reliable and
+    // up to twice `base` for the most
+    pub fn task_allowance(&self, identity: &str, base: usize) -> usize {
+        let allowance = (2.0 * base as f64 * self.score(identity)).ceil() as usize;
+        allowance.clamp(1, 2 * base)
+    }
+
+    // Fraction taken off the minimum fee: nothing at or below a neutral score, rising to max_discount
+    pub fn fee_discount(&self, identity: &str, max_discount: f64) -> f64 {
+        let above_neutral = ((self.score(identity) - 0.5) / 0.5).clamp(0.0, 1.0);
+        max_discount * above_neutral
+    }
+}
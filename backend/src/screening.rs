@@ -0,0 +1,131 @@
+// Screening module - maintainer-signed address blocklist and allowlist
+//
+// Compliance deployments must refuse transactions touching sanctioned addresses, and every node has
+// to agree on which those are. The list is published by a maintainer whose public key is part of the
+// network's configuration, signed and versioned, and reaches nodes by gossip or from a URL they poll.
+// A node only installs a list with a valid maintainer signature and a higher version than the one it
+// holds. Senders and recipients are screened when a leader admits a transaction and again before it
+// is finalized, since the list may have changed in between; a match is refused and published as a
+// "screened" event. A non-empty allowlist closes the network to every address not on it.
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use crate::client::PclClient;
+use crate::config::ScreeningConfig;
+use crate::crypto::{hash_data, verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::multisig::{decode_public_key, decode_signature};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningList {
+    pub version: u64,   // a list only replaces one with a lower version
+    pub issued_at: u64, // ms since epoch
+    pub blocked: Vec<String>,
+    pub allowed: Vec<String>, // empty allows every address that is not blocked
+    pub public_key: String,   // hex encoded maintainer key
+    pub signature: String,    // hex encoded, over signing_bytes
+}
+
+impl ScreeningList {
+    pub fn sign(version: u64, issued_at: u64, blocked: Vec<String>, allowed: Vec<String>, maintainer: &NodeKeypair) -> Self {
+        let mut list = Self {
+            version,
+            issued_at,
+            blocked,
+            allowed,
+            public_key: hex::encode(maintainer.public_key().to_bytes()),
+            signature: String::new(),
+        };
+        list.signature = hex::encode(maintainer.sign_data(&list.signing_bytes()).to_bytes());
+        list
+    }
+
+    // Every field except the signature; addresses are newline separated so none can run into the next
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        hash_data(format!(
+            "pcl-screening:{}:{}:{}:{}:{}",
+            self.version, self.issued_at, self.public_key, self.blocked.join("\n"), self.allowed.join("\n")
+        ).as_bytes())
+    }
+
+    pub fn verify(&self, maintainer: &VerifyingKey) -> Result<()> {
+        if decode_public_key(&self.public_key)? != *maintainer {
+            return Err(PclError::SignatureVerification(format!(
+                "Screening list {} is not signed by the configured maintainer", self.version
+            )));
+        }
+        if !verify_data_signature(&self.signing_bytes(), &decode_signature(&self.signature)?, maintainer)? {
+            return Err(PclError::SignatureVerification(format!("Screening list {} has an invalid signature", self.version)));
+        }
+        Ok(())
+    }
+
+    // Why the address may not transact, if it may not
+    pub fn refusal(&self, address: &str) -> Option<String> {
+        if self.blocked.iter().any(|blocked| blocked == address) {
+            Some(format!("address {} is on screening list {}", address, self.version))
+        } else if !self.allowed.is_empty() && !self.allowed.iter().any(|allowed| allowed == address) {
+            Some(format!("address {} is not on the allowlist of screening list {}", address, self.version))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Screening {
+    maintainer: Option<VerifyingKey>, // None turns screening off
+    list: Option<ScreeningList>,
+    matches: u64,
+}
+
+impl Screening {
+    pub fn new(config: &ScreeningConfig) -> Result<Self> {
+        let maintainer = config.maintainer_key.as_deref().map(decode_public_key).transpose()?;
+        Ok(Self { maintainer, list: None, matches: 0 })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.maintainer.is_some()
+    }
+
+    pub fn list(&self) -> Option<&ScreeningList> {
+        self.list.as_ref()
+    }
+
+    // Transactions refused since the node started
+    pub fn matches(&self) -> u64 {
+        self.matches
+    }
+
+    // Ok(false) for a list that is not newer than the one installed
+    pub fn install(&mut self, list: ScreeningList) -> Result<bool> {
+        let maintainer = self.maintainer.as_ref()
+            .ok_or_else(|| PclError::Config("No screening maintainer key is configured".to_string()))?;
+        list.verify(maintainer)?;
+        if self.list.as_ref().is_some_and(|installed| installed.version >= list.version) {
+            return Ok(false);
+        }
+        log::info!("Screening list {} installed: {} blocked, {} allowed", list.version, list.blocked.len(), list.allowed.len());
+        self.list = Some(list);
+        Ok(true)
+    }
+
+    // Refuses the first address the installed list does not let through
+    pub fn screen<'a>(&mut self, addresses: impl IntoIterator<Item = &'a str>) -> Result<()> {
+        let Some(list) = &self.list else {
+            return Ok(());
+        };
+        if let Some(reason) = addresses.into_iter().find_map(|address| list.refusal(address)) {
+            self.matches += 1;
+            return Err(PclError::Screened(reason));
+        }
+        Ok(())
+    }
+}
+
+// Polled from ScreeningConfig::list_url; the caller installs it, which checks the signature
+pub async fn fetch_screening_list(url: &str) -> Result<ScreeningList> {
+    let (client, path) = PclClient::from_url(url)?;
+    Ok(serde_json::from_value(client.get(&path).await?)?)
+}
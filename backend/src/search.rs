@@ -0,0 +1,289 @@
+// Search module - secondary indexes over finalized transactions for explorer queries
+
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::client::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+use crate::error::{PclError, Result};
+
+// The fields a transaction can be searched by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    pub tx_id: String,
+    pub sender: String,
+    pub source: String, // UTXO or address the funds were spent from
+    pub recipient: String,
+    pub amount: f64,
+    pub timestamp: u64, // ms since epoch
+    pub status: String,
+}
+
+impl IndexedTransaction {
+    fn parties(&self) -> impl Iterator<Item = &String> {
+        [&self.sender, &self.source, &self.recipient].into_iter()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    #[default]
+    Newest,
+    Oldest,
+    AmountAsc,
+    AmountDesc,
+}
+
+impl FromStr for SearchSort {
+    type Err = PclError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "newest" => Ok(SearchSort::Newest),
+            "oldest" => Ok(SearchSort::Oldest),
+            "amount_asc" => Ok(SearchSort::AmountAsc),
+            "amount_desc" => Ok(SearchSort::AmountDesc),
+            other => Err(PclError::Validation(format!(
+                "Unknown sort '{}', expected newest, oldest, amount_asc or amount_desc", other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionQuery {
+    pub user: Option<String>, // sender, source or recipient
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    pub from_ts: Option<u64>,
+    pub to_ts: Option<u64>,
+    pub status: Option<String>,
+    pub sort: SearchSort,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for TransactionQuery {
+    fn default() -> Self {
+        Self {
+            user: None,
+            min_amount: None,
+            max_amount: None,
+            from_ts: None,
+            to_ts: None,
+            status: None,
+            sort: SearchSort::default(),
+            offset: 0,
+            limit: DEFAULT_PAGE_LIMIT,
+        }
+    }
+}
+
+fn parse_param<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| PclError::Validation(format!("Invalid value '{}' for {}", value, key)))
+}
+
+impl TransactionQuery {
+    pub fn from_query(query: &str) -> Result<Self> {
+        let mut parsed = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "user" => parsed.user = Some(value.to_string()),
+                "min_amount" => parsed.min_amount = Some(parse_param(key, value)?),
+                "max_amount" => parsed.max_amount = Some(parse_param(key, value)?),
+                "from_ts" => parsed.from_ts = Some(parse_param(key, value)?),
+                "to_ts" => parsed.to_ts = Some(parse_param(key, value)?),
+                "status" => parsed.status = Some(value.to_string()),
+                "sort" => parsed.sort = value.parse()?,
+                "offset" => parsed.offset = parse_param(key, value)?,
+                "limit" => parsed.limit = parse_param(key, value)?,
+                _ => {}
+            }
+        }
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    pub fn to_query(&self) -> String {
+        let sort = match self.sort {
+            SearchSort::Newest => "newest",
+            SearchSort::Oldest => "oldest",
+            SearchSort::AmountAsc => "amount_asc",
+            SearchSort::AmountDesc => "amount_desc",
+        };
+        let mut params = vec![format!("sort={}", sort), format!("offset={}", self.offset), format!("limit={}", self.limit)];
+        if let Some(user) = &self.user {
+            params.push(format!("user={}", user));
+        }
+        if let Some(status) = &self.status {
+            params.push(format!("status={}", status));
+        }
+        for (key, value) in [("min_amount", self.min_amount), ("max_amount", self.max_amount)] {
+            if let Some(value) = value {
+                params.push(format!("{}={}", key, value));
+            }
+        }
+        for (key, value) in [("from_ts", self.from_ts), ("to_ts", self.to_ts)] {
+            if let Some(value) = value {
+                params.push(format!("{}={}", key, value));
+            }
+        }
+        params.join("&")
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.limit == 0 || self.limit > MAX_PAGE_LIMIT {
+            return Err(PclError::Validation(format!("limit must be between 1 and {}", MAX_PAGE_LIMIT)));
+        }
+        for amount in [self.min_amount, self.max_amount].into_iter().flatten() {
+            if !amount.is_finite() || amount < 0.0 {
+                return Err(PclError::Validation(format!("Amount bounds must be non-negative, got {}", amount)));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(PclError::Validation("min_amount is greater than max_amount".to_string()));
+            }
+        }
+        if let (Some(from), Some(to)) = (self.from_ts, self.to_ts) {
+            if from > to {
+                return Err(PclError::Validation("from_ts is after to_ts".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn matches(&self, tx: &IndexedTransaction) -> bool {
+        self.user.as_ref().is_none_or(|user| tx.parties().any(|party| party == user))
+            && self.min_amount.is_none_or(|min| tx.amount >= min)
+            && self.max_amount.is_none_or(|max| tx.amount <= max)
+            && self.from_ts.is_none_or(|from| tx.timestamp >= from)
+            && self.to_ts.is_none_or(|to| tx.timestamp <= to)
+            && self.status.as_ref().is_none_or(|status| &tx.status == status)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub tx_ids: Vec<String>,
+}
+
+// Non-negative floats order the same way as their bit patterns
+fn amount_key(amount: f64) -> u64 {
+    amount.max(0.0).to_bits()
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionIndex {
+    records: HashMap<String, IndexedTransaction>,
+    by_time: BTreeSet<(u64, String)>,
+    by_amount: BTreeSet<(u64, String)>,
+    by_user: HashMap<String, BTreeSet<(u64, String)>>,
+    by_status: HashMap<String, BTreeSet<(u64, String)>>,
+}
+
+impl TransactionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    // Re-inserting a tx id replaces its previous entry, e.g. after a fork changed its timestamp
+    pub fn insert(&mut self, tx: IndexedTransaction) {
+        self.remove(&tx.tx_id);
+        let time_key = (tx.timestamp, tx.tx_id.clone());
+        self.by_time.insert(time_key.clone());
+        self.by_amount.insert((amount_key(tx.amount), tx.tx_id.clone()));
+        for party in tx.parties() {
+            self.by_user.entry(party.clone()).or_default().insert(time_key.clone());
+        }
+        self.by_status.entry(tx.status.clone()).or_default().insert(time_key);
+        self.records.insert(tx.tx_id.clone(), tx);
+    }
+
+    pub fn remove(&mut self, tx_id: &str) -> Option<IndexedTransaction> {
+        let tx = self.records.remove(tx_id)?;
+        let time_key = (tx.timestamp, tx.tx_id.clone());
+        self.by_time.remove(&time_key);
+        self.by_amount.remove(&(amount_key(tx.amount), tx.tx_id.clone()));
+        for user in tx.parties() {
+            if let Some(entries) = self.by_user.get_mut(user) {
+                entries.remove(&time_key);
+                if entries.is_empty() {
+                    self.by_user.remove(user);
+                }
+            }
+        }
+        if let Some(entries) = self.by_status.get_mut(&tx.status) {
+            entries.remove(&time_key);
+            if entries.is_empty() {
+                self.by_status.remove(&tx.status);
+            }
+        }
+        Some(tx)
+    }
+
+    pub fn get(&self, tx_id: &str) -> Option<&IndexedTransaction> {
+        self.records.get(tx_id)
+    }
+
+    // Starts from the narrowest index the query constrains, then filters and sorts the candidates
+    pub fn search(&self, query: &TransactionQuery) -> SearchResults {
+        let candidates: Vec<&String> = if let Some(user) = &query.user {
+            self.by_user.get(user).map(|entries| entries.iter().map(|(_, id)| id).collect()).unwrap_or_default()
+        } else if let Some(status) = &query.status {
+            self.by_status.get(status).map(|entries| entries.iter().map(|(_, id)| id).collect()).unwrap_or_default()
+        } else if query.min_amount.is_some() || query.max_amount.is_some() {
+            let lower = (amount_key(query.min_amount.unwrap_or(0.0)), String::new());
+            let upper = match query.max_amount {
+                Some(max) => Bound::Excluded((amount_key(max) + 1, String::new())),
+                None => Bound::Unbounded,
+            };
+            self.by_amount.range((Bound::Included(lower), upper)).map(|(_, id)| id).collect()
+        } else {
+            let lower = (query.from_ts.unwrap_or(0), String::new());
+            let upper = match query.to_ts {
+                Some(to) if to < u64::MAX => Bound::Excluded((to + 1, String::new())),
+                _ => Bound::Unbounded,
+            };
+            self.by_time.range((Bound::Included(lower), upper)).map(|(_, id)| id).collect()
+        };
+
+        let mut matches: Vec<&IndexedTransaction> = candidates.into_iter()
+            .filter_map(|id| self.records.get(id))
+            .filter(|tx| query.matches(tx))
+            .collect();
+
+        match query.sort {
+            SearchSort::Newest => matches.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.tx_id.cmp(&b.tx_id))),
+            SearchSort::Oldest => matches.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.tx_id.cmp(&b.tx_id))),
+            SearchSort::AmountAsc => matches.sort_by(|a, b| a.amount.total_cmp(&b.amount).then_with(|| a.tx_id.cmp(&b.tx_id))),
+            SearchSort::AmountDesc => matches.sort_by(|a, b| b.amount.total_cmp(&a.amount).then_with(|| a.tx_id.cmp(&b.tx_id))),
+        }
+
+        SearchResults {
+            total: matches.len(),
+            offset: query.offset,
+            limit: query.limit,
+            tx_ids: matches.into_iter()
+                .skip(query.offset)
+                .take(query.limit)
+                .map(|tx| tx.tx_id.clone())
+                .collect(),
+        }
+    }
+}
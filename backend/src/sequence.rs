@@ -0,0 +1,106 @@
+// Sequence module - a global finalization order agreed by leader co-signatures
+//
+// Local finalization sequence numbers only order what one node saw. The global sequence is signed
+// by the same leaders that co-sign a processing entry: each certificate binds a sequence number to
+// a transaction and to the transaction before it, so the order is gapless and any reordering breaks
+// a signature. Downstream consumers can apply transactions exactly once, in certificate order.
+
+use std::collections::HashMap;
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::error::{PclError, Result};
+use crate::governance::ProposalSignature;
+use crate::multisig::decode_signature;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceCertificate {
+    pub sequence: u64, // starts at 1
+    pub tx_id: String,
+    pub previous_tx_id: Option<String>, // None only for sequence 1
+    pub signatures: Vec<ProposalSignature>,
+}
+
+impl SequenceCertificate {
+    pub fn new(sequence: u64, tx_id: &str, previous_tx_id: Option<String>) -> Self {
+        Self { sequence, tx_id: tx_id.to_string(), previous_tx_id, signatures: Vec::new() }
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!(
+            "pcl-global-sequence:{}:{}:{}", self.sequence, self.tx_id, self.previous_tx_id.as_deref().unwrap_or("")
+        ).into_bytes()
+    }
+
+    // Adds a leader's signature; signing twice replaces the earlier one
+    pub fn cosign(&mut self, leader_id: &str, keypair: &NodeKeypair) {
+        let signature = keypair.sign_data(&self.signing_bytes());
+        self.signatures.retain(|existing| existing.leader_id != leader_id);
+        self.signatures.push(ProposalSignature {
+            leader_id: leader_id.to_string(),
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+    }
+
+    // Needs `quorum` distinct leaders whose signatures verify against their registered keys
+    pub fn verify(&self, leader_keys: &HashMap<String, VerifyingKey>, quorum: usize) -> Result<()> {
+        let message = self.signing_bytes();
+        let mut signers: Vec<&str> = Vec::new();
+        for signature in &self.signatures {
+            let Some(key) = leader_keys.get(&signature.leader_id) else { continue };
+            let Ok(decoded) = decode_signature(&signature.signature) else { continue };
+            if verify_data_signature(&message, &decoded, key)? && !signers.contains(&signature.leader_id.as_str()) {
+                signers.push(&signature.leader_id);
+            }
+        }
+        if signers.len() < quorum {
+            return Err(PclError::SignatureVerification(format!(
+                "Global sequence #{} for {} has {} of {} leader signatures", self.sequence, self.tx_id, signers.len(), quorum
+            )));
+        }
+        Ok(())
+    }
+}
+
+// The tail of the global order as this node knows it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GlobalSequencer {
+    last_sequence: u64,
+    last_tx_id: Option<String>,
+}
+
+impl GlobalSequencer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn last_sequence(&self) -> u64 {
+        self.last_sequence
+    }
+
+    // Unsigned certificate for the next transaction, ready for leaders to co-sign
+    pub fn propose(&self, tx_id: &str) -> SequenceCertificate {
+        SequenceCertificate::new(self.last_sequence + 1, tx_id, self.last_tx_id.clone())
+    }
+
+    // Checks the certificate's signatures, then extends the order with it
+    pub fn record(&mut self, certificate: &SequenceCertificate, leader_keys: &HashMap<String, VerifyingKey>, quorum: usize) -> Result<()> {
+        certificate.verify(leader_keys, quorum)?;
+        self.advance(certificate)
+    }
+
+    // Extends the order without checking signatures, for certificates verified upstream. Only the
+    // certificate directly after the current tail is accepted.
+    pub fn advance(&mut self, certificate: &SequenceCertificate) -> Result<()> {
+        if certificate.sequence != self.last_sequence + 1 || certificate.previous_tx_id != self.last_tx_id {
+            return Err(PclError::Consensus(format!(
+                "Global sequence #{} for {} does not follow #{} ({})",
+                certificate.sequence, certificate.tx_id, self.last_sequence, self.last_tx_id.as_deref().unwrap_or("none")
+            )));
+        }
+        self.last_sequence = certificate.sequence;
+        self.last_tx_id = Some(certificate.tx_id.clone());
+        Ok(())
+    }
+}
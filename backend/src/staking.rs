@@ -0,0 +1,286 @@
+// Staking module - bonded stake behind validators and leaders, with bonding and unbonding delays
+//
+// Stake moves Bonding -> Bonded once the bonding period has passed, Bonded -> Unbonding when its owner
+// asks for it back, and Unbonding -> Withdrawn after the unbonding delay. Only Bonded stake counts for
+// election weight and validation eligibility. Every transition is appended to the consensus event log
+// before it is applied, so replaying the log rebuilds the ledger after a restart.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::config::ConsensusConfig;
+use crate::crypto::hash_data;
+use crate::error::{PclError, Result};
+use crate::storage::StorageManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeStatus {
+    Bonding,
+    Bonded,
+    Unbonding,
+    Withdrawn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeEventKind {
+    Bonded,
+    Activated,
+    UnbondRequested,
+    Withdrawn,
+}
+
+// One stake transition as recorded in the consensus event log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StakeEvent {
+    pub stake_id: String,
+    pub kind: StakeEventKind,
+    pub owner: String,     // address whose balance is bonded
+    pub validator: String, // node id or address the stake counts for
+    pub amount: f64,
+    pub at: u64,           // ms since epoch
+    // Bonded: when the stake becomes active; UnbondRequested: when it can be withdrawn; otherwise `at`
+    pub effective_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StakePosition {
+    pub stake_id: String,
+    pub owner: String,
+    pub validator: String,
+    pub amount: f64,
+    pub status: StakeStatus,
+    pub bonded_at: u64,
+    pub active_at: u64,
+    pub unbond_requested_at: Option<u64>,
+    pub withdrawable_at: Option<u64>,
+}
+
+pub struct StakeLedger {
+    positions: BTreeMap<String, StakePosition>,
+    bonding_period_ms: u64,
+    unbonding_delay_ms: u64,
+    min_validator_stake: f64,
+    min_leader_stake: f64,
+    storage: Option<Arc<StorageManager>>, // event log; None keeps the ledger in memory only
+}
+
+impl StakeLedger {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            positions: BTreeMap::new(),
+            bonding_period_ms: config.stake_bonding_period_secs * 1000,
+            unbonding_delay_ms: config.stake_unbonding_delay_secs * 1000,
+            min_validator_stake: config.min_validator_stake,
+            min_leader_stake: config.min_leader_stake,
+            storage: None,
+        }
+    }
+
+    // Rebuilds the ledger from the stored event log and records new transitions there
+    pub fn open(storage: Arc<StorageManager>, config: &ConsensusConfig) -> Result<Self> {
+        let mut ledger = Self::new(config);
+        for (_, event) in storage.load_stake_events()? {
+            ledger.apply(&event)?;
+        }
+        ledger.storage = Some(storage);
+        Ok(ledger)
+    }
+
+    pub fn bond(&mut self, owner: &str, validator: &str, amount: f64, now: u64) -> Result<StakeEvent> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(PclError::Validation(format!("Stake amount must be positive, got {}", amount)));
+        }
+        if owner.is_empty() || validator.is_empty() {
+            return Err(PclError::Validation("Stake needs an owner and a validator".to_string()));
+        }
+
+        let preimage = format!("{}:{}:{}:{}:{}", owner, validator, amount, now, self.positions.len());
+        let event = StakeEvent {
+            stake_id: format!("stake_{}", hex::encode(&hash_data(preimage.as_bytes())[..8])),
+            kind: StakeEventKind::Bonded,
+            owner: owner.to_string(),
+            validator: validator.to_string(),
+            amount,
+            at: now,
+            effective_at: now + self.bonding_period_ms,
+        };
+        self.record(&event)?;
+        Ok(event)
+    }
+
+    // Only the owner can unbond, and only stake that has finished bonding
+    pub fn request_unbond(&mut self, stake_id: &str, owner: &str, now: u64) -> Result<StakeEvent> {
+        let position = self.positions.get(stake_id)
+            .ok_or_else(|| PclError::Validation(format!("Unknown stake {}", stake_id)))?;
+        if position.owner != owner {
+            return Err(PclError::Forbidden(format!("Stake {} is not owned by {}", stake_id, owner)));
+        }
+        match position.status {
+            StakeStatus::Bonded => {}
+            StakeStatus::Bonding => {
+                return Err(PclError::Validation(format!(
+                    "Stake {} is bonding until {} and cannot be unbonded before then", stake_id, position.active_at
+                )));
+            }
+            StakeStatus::Unbonding | StakeStatus::Withdrawn => {
+                return Err(PclError::Validation(format!("Stake {} is already unbonding", stake_id)));
+            }
+        }
+
+        let event = StakeEvent {
+            effective_at: now + self.unbonding_delay_ms,
+            ..Self::event_for(position, StakeEventKind::UnbondRequested, now)
+        };
+        self.record(&event)?;
+        Ok(event)
+    }
+
+    // Activates stake whose bonding period has passed and withdraws stake whose unbonding delay has
+    // passed. Withdrawn events tell the caller to return the amount to the owner.
+    pub fn advance(&mut self, now: u64) -> Result<Vec<StakeEvent>> {
+        let due: Vec<StakeEvent> = self.positions.values()
+            .filter_map(|position| match position.status {
+                StakeStatus::Bonding if now >= position.active_at => {
+                    Some(Self::event_for(position, StakeEventKind::Activated, now))
+                }
+                StakeStatus::Unbonding if position.withdrawable_at.is_some_and(|at| now >= at) => {
+                    Some(Self::event_for(position, StakeEventKind::Withdrawn, now))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for event in &due {
+            self.record(event)?;
+        }
+        Ok(due)
+    }
+
+    pub fn position(&self, stake_id: &str) -> Option<&StakePosition> {
+        self.positions.get(stake_id)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &StakePosition> {
+        self.positions.values()
+    }
+
+    // Stake an address owns or that counts for it
+    pub fn positions_for(&self, address: &str) -> Vec<&StakePosition> {
+        self.positions.values()
+            .filter(|position| position.owner == address || position.validator == address)
+            .collect()
+    }
+
+    // Active stake counting for a validator; bonding and unbonding stake carries no weight
+    pub fn bonded_stake(&self, validator: &str) -> f64 {
+        self.positions.values()
+            .filter(|position| position.status == StakeStatus::Bonded && position.validator == validator)
+            .map(|position| position.amount)
+            .sum()
+    }
+
+    pub fn total_bonded(&self) -> f64 {
+        self.positions.values()
+            .filter(|position| position.status == StakeStatus::Bonded)
+            .map(|position| position.amount)
+            .sum()
+    }
+
+    // 0-1: the validator's share of all bonded stake
+    pub fn stake_weight(&self, validator: &str) -> f64 {
+        let total = self.total_bonded();
+        if total > 0.0 {
+            self.bonded_stake(validator) / total
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_bonded(&self, validator: &str) -> bool {
+        self.bonded_stake(validator) > 0.0
+    }
+
+    // Validation tasks only go to addresses with at least min_validator_stake bonded
+    pub fn ensure_validator_eligible(&self, address: &str) -> Result<()> {
+        self.ensure_min_stake(address, self.min_validator_stake, "validation tasks")
+    }
+
+    // Leadership candidacy needs at least min_leader_stake bonded for the node
+    pub fn ensure_leader_eligible(&self, node_id: &str) -> Result<()> {
+        self.ensure_min_stake(node_id, self.min_leader_stake, "leader candidacy")
+    }
+
+    fn ensure_min_stake(&self, validator: &str, minimum: f64, purpose: &str) -> Result<()> {
+        let bonded = self.bonded_stake(validator);
+        if bonded < minimum {
+            let pending: f64 = self.positions.values()
+                .filter(|position| position.status == StakeStatus::Bonding && position.validator == validator)
+                .map(|position| position.amount)
+                .sum();
+            return Err(PclError::InsufficientStake(format!(
+                "{} has {} XMBL bonded ({} still bonding), {} needs at least {}",
+                validator, bonded, pending, purpose, minimum
+            )));
+        }
+        Ok(())
+    }
+
+    fn event_for(position: &StakePosition, kind: StakeEventKind, now: u64) -> StakeEvent {
+        StakeEvent {
+            stake_id: position.stake_id.clone(),
+            kind,
+            owner: position.owner.clone(),
+            validator: position.validator.clone(),
+            amount: position.amount,
+            at: now,
+            effective_at: now,
+        }
+    }
+
+    // Applies an event taken from a log without recording it again, e.g. when replaying a copy of the log
+    pub fn replay(&mut self, event: &StakeEvent) -> Result<()> {
+        self.apply(event)
+    }
+
+    // Logged first, so a transition is never applied without being recorded
+    fn record(&mut self, event: &StakeEvent) -> Result<()> {
+        if let Some(storage) = &self.storage {
+            storage.append_stake_event(event)?;
+        }
+        self.apply(event)?;
+        log::info!("Stake {} {:?}: {} for {} ({})", event.stake_id, event.kind, event.amount, event.validator, event.owner);
+        Ok(())
+    }
+
+    fn apply(&mut self, event: &StakeEvent) -> Result<()> {
+        if event.kind == StakeEventKind::Bonded {
+            self.positions.insert(event.stake_id.clone(), StakePosition {
+                stake_id: event.stake_id.clone(),
+                owner: event.owner.clone(),
+                validator: event.validator.clone(),
+                amount: event.amount,
+                status: StakeStatus::Bonding,
+                bonded_at: event.at,
+                active_at: event.effective_at,
+                unbond_requested_at: None,
+                withdrawable_at: None,
+            });
+            return Ok(());
+        }
+
+        let position = self.positions.get_mut(&event.stake_id)
+            .ok_or_else(|| PclError::Consensus(format!("Stake event for unknown stake {}", event.stake_id)))?;
+        position.status = match event.kind {
+            StakeEventKind::UnbondRequested => {
+                position.unbond_requested_at = Some(event.at);
+                position.withdrawable_at = Some(event.effective_at);
+                StakeStatus::Unbonding
+            }
+            StakeEventKind::Withdrawn => StakeStatus::Withdrawn,
+            StakeEventKind::Bonded | StakeEventKind::Activated => StakeStatus::Bonded,
+        };
+        Ok(())
+    }
+}
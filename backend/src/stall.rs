@@ -0,0 +1,118 @@
+// Stall module - noticing transactions whose workflow has stopped moving
+//
+// A transaction can sit in one workflow step indefinitely, e.g. waiting on validation tasks whose
+// leader went away, and nothing reports it. Each step has a timeout. Once a transaction's open step
+// has run past it, the transaction is stalled: the node raises an alert once, emits a stalled event
+// and tries to get it moving again. Remediation repeats at most once per timeout while it stays
+// stalled, and the transaction is dropped from the stalled set as soon as it moves on or finishes.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use serde::{Deserialize, Serialize};
+use crate::config::ConsensusConfig;
+use crate::metrics::{WorkflowStep, WorkflowTimings};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StalledTransaction {
+    pub tx_id: String,
+    pub step: WorkflowStep,
+    pub step_started_at: u64, // ms since epoch
+    pub stalled_for_ms: u64,  // time in the step so far
+    pub timeout_ms: u64,
+    pub detected_at: u64,
+    pub remediation_attempts: u32,
+    pub last_remediation_at: Option<u64>,
+    pub last_remediation: Vec<String>, // what the latest attempt did
+}
+
+#[derive(Debug, Clone)]
+pub struct StallMonitor {
+    timeouts_ms: [u64; 6], // indexed by step number - 1
+    stalled: HashMap<String, StalledTransaction>,
+    pub alerts: u64, // transactions found stalled, each counted once per stall
+}
+
+impl StallMonitor {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            timeouts_ms: config.workflow_step_timeouts_secs.map(|secs| secs * 1000),
+            stalled: HashMap::new(),
+            alerts: 0,
+        }
+    }
+
+    pub fn timeout_ms(&self, step: WorkflowStep) -> u64 {
+        self.timeouts_ms[step.number() as usize - 1]
+    }
+
+    // Updates the stalled set from the workflows still in flight and returns the stalled transactions
+    // due a remediation attempt: those newly stalled and those whose last attempt was a timeout ago
+    pub fn check<'a>(&mut self, workflows: impl IntoIterator<Item = (&'a str, &'a WorkflowTimings)>, now: u64) -> Vec<StalledTransaction> {
+        let mut still_stalled = HashMap::new();
+        let mut due = Vec::new();
+        for (tx_id, timings) in workflows {
+            let Some(open) = timings.steps.last().filter(|timing| timing.ended_at.is_none() && timings.completed_at.is_none()) else {
+                continue;
+            };
+            let timeout_ms = self.timeout_ms(open.step);
+            let stalled_for_ms = now.saturating_sub(open.started_at);
+            if stalled_for_ms <= timeout_ms {
+                continue;
+            }
+
+            let mut entry = match self.stalled.remove(tx_id).filter(|entry| entry.step == open.step) {
+                Some(entry) => entry,
+                None => {
+                    self.alerts += 1;
+                    StalledTransaction {
+                        tx_id: tx_id.to_string(),
+                        step: open.step,
+                        step_started_at: open.started_at,
+                        stalled_for_ms,
+                        timeout_ms,
+                        detected_at: now,
+                        remediation_attempts: 0,
+                        last_remediation_at: None,
+                        last_remediation: Vec::new(),
+                    }
+                }
+            };
+            entry.stalled_for_ms = stalled_for_ms;
+            if entry.last_remediation_at.is_none_or(|at| now.saturating_sub(at) >= timeout_ms) {
+                due.push(entry.clone());
+            }
+            still_stalled.insert(tx_id.to_string(), entry);
+        }
+        self.stalled = still_stalled;
+        due
+    }
+
+    pub fn record_remediation(&mut self, tx_id: &str, actions: Vec<String>, now: u64) {
+        if let Some(entry) = self.stalled.get_mut(tx_id) {
+            entry.remediation_attempts += 1;
+            entry.last_remediation_at = Some(now);
+            entry.last_remediation = actions;
+        }
+    }
+
+    // Longest stalled first
+    pub fn stalled(&self) -> Vec<StalledTransaction> {
+        let mut stalled: Vec<StalledTransaction> = self.stalled.values().cloned().collect();
+        stalled.sort_by(|a, b| b.stalled_for_ms.cmp(&a.stalled_for_ms).then_with(|| a.tx_id.cmp(&b.tx_id)));
+        stalled
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP pcl_workflow_stall_alerts_total Transactions found stuck in a workflow step past its timeout\n");
+        out.push_str("# TYPE pcl_workflow_stall_alerts_total counter\n");
+        let _ = writeln!(out, "pcl_workflow_stall_alerts_total {}", self.alerts);
+        out.push_str("# HELP pcl_workflow_stalled_transactions Transactions currently stalled, per step\n");
+        out.push_str("# TYPE pcl_workflow_stalled_transactions gauge\n");
+        for step in WorkflowStep::ALL {
+            let count = self.stalled.values().filter(|entry| entry.step == step).count();
+            let _ = writeln!(out, "pcl_workflow_stalled_transactions{{step=\"{}\"}} {}", step.as_str(), count);
+        }
+        out
+    }
+}
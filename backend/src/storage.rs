@@ -3,11 +3,27 @@
 use std::path::Path;
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use rocksdb::{DB, Options, ColumnFamily, ColumnFamilyDescriptor, IteratorMode};
+use rocksdb::{DB, Options, ColumnFamily, ColumnFamilyDescriptor, Direction, IteratorMode, WriteBatch};
 use crate::error::{PclError, Result};
 use crate::transaction::{RawTransaction, ProcessingTransaction, TransactionData};
 use crate::node::{Node, NodeRegistry};
+use crate::crypto::NodeKeypair;
 use crate::mempool::{MempoolManager, FinalizedTransaction};
+use crate::export::ExportRecord;
+use crate::webhook::{Subscription, WebhookDelivery, DeliveryStatus};
+use crate::auth::ApiKey;
+use crate::staking::StakeEvent;
+use crate::receipt::TransactionReceipt;
+use crate::clock::ClockStatus;
+use crate::reputation::ReputationRecord;
+use crate::peers::PeerRecord;
+use crate::tenancy::tenant_key;
+use crate::history::{UtxoCheckpoint, UtxoDelta};
+
+pub mod migrations;
+pub mod integrity;
+pub use migrations::{Versioned, MigrationReport, CURRENT_SCHEMA_VERSION, encode_record, decode_record};
+pub use integrity::{IntegrityIssue, IntegrityReport};
 
 pub struct StorageManager {
     db: DB,
@@ -22,6 +38,37 @@ pub const CF_MEMPOOL_STATE: &str = "mempool_state";
 pub const CF_UPTIME_DATA: &str = "uptime_data";
 pub const CF_LEADER_ELECTION: &str = "leader_election";
 pub const CF_NETWORK_STATE: &str = "network_state";
+pub const CF_EXPORT_OUTBOX: &str = "export_outbox";
+pub const CF_WEBHOOKS: &str = "webhooks";
+pub const CF_API_KEYS: &str = "api_keys";
+pub const CF_CONSENSUS_EVENTS: &str = "consensus_events";
+pub const CF_RECEIPTS: &str = "receipts";
+pub const CF_REPUTATION: &str = "reputation";
+pub const CF_FINALIZATION_SEQUENCE: &str = "finalization_sequence";
+pub const CF_UTXO_HISTORY: &str = "utxo_history";
+pub const ALL_COLUMN_FAMILIES: [&str; 16] = [
+    CF_NODES, CF_RAW_TRANSACTIONS, CF_PROCESSING_TRANSACTIONS, CF_FINALIZED_TRANSACTIONS, CF_MEMPOOL_STATE,
+    CF_UPTIME_DATA, CF_LEADER_ELECTION, CF_NETWORK_STATE, CF_EXPORT_OUTBOX, CF_WEBHOOKS, CF_API_KEYS,
+    CF_CONSENSUS_EVENTS, CF_RECEIPTS, CF_REPUTATION, CF_FINALIZATION_SEQUENCE, CF_UTXO_HISTORY,
+];
+
+const EXPORT_CURSOR_KEY: &str = "export_cursor";
+const NODE_IDENTITY_KEY: &str = "node_identity";
+const CONSENSUS_STATE_KEY: &str = "consensus_state";
+const CLOCK_STATUS_KEY: &str = "clock_status";
+// Raw transactions: one record per (leader, tx) under "tx/{leader}/{raw_tx_id}", plus an
+// "idx/{raw_tx_id}" -> leader entry so a tx can be found without knowing its leader
+const RAW_TX_RECORD_PREFIX: &str = "tx/";
+const RAW_TX_INDEX_PREFIX: &str = "idx/";
+// Pending webhook deliveries are also indexed under "due:{delivery_id}" -> next_attempt_at (big-endian
+// ms), so the dispatch tick reads only those instead of decoding every delivery ever queued
+const WEBHOOK_DUE_PREFIX: &str = "due:";
+// Address book entries live in the network state column family under "peer:{peer_id}"
+const PEER_RECORD_PREFIX: &str = "peer:";
+// UTXO history: checkpoints under b'c' and deltas under b'd', each followed by the big-endian sequence
+const UTXO_CHECKPOINT_TAG: u8 = b'c';
+const UTXO_DELTA_TAG: u8 = b'd';
+pub const UNASSIGNED_LEADER: &str = "unassigned";
 
 impl StorageManager {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -29,16 +76,9 @@ impl StorageManager {
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
         
-        let cf_descriptors = vec![
-            ColumnFamilyDescriptor::new(CF_NODES, Options::default()),
-            ColumnFamilyDescriptor::new(CF_RAW_TRANSACTIONS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_PROCESSING_TRANSACTIONS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_FINALIZED_TRANSACTIONS, Options::default()),
-            ColumnFamilyDescriptor::new(CF_MEMPOOL_STATE, Options::default()),
-            ColumnFamilyDescriptor::new(CF_UPTIME_DATA, Options::default()),
-            ColumnFamilyDescriptor::new(CF_LEADER_ELECTION, Options::default()),
-            ColumnFamilyDescriptor::new(CF_NETWORK_STATE, Options::default()),
-        ];
+        let cf_descriptors = ALL_COLUMN_FAMILIES.iter()
+            .map(|name| ColumnFamilyDescriptor::new(*name, Options::default()))
+            .collect::<Vec<_>>();
         
         let db = DB::open_cf_descriptors(&opts, path, cf_descriptors)
             .map_err(|e| PclError::Storage(format!("Failed to open database: {}", e)))?;
@@ -51,7 +91,7 @@ impl StorageManager {
     pub fn store_node(&self, node: &Node) -> Result<()> {
         let cf = self.get_cf(CF_NODES)?;
         let key = node.id.to_string();
-        let value = bincode::serialize(node)?;
+        let value = encode_record(node)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store node: {}", e)))?;
@@ -65,7 +105,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, node_id.as_bytes())? {
             Some(value) => {
-                let node: Node = bincode::deserialize(&value)?;
+                let node: Node = decode_record(&value)?;
                 Ok(Some(node))
             }
             None => Ok(None),
@@ -75,7 +115,7 @@ impl StorageManager {
     pub fn store_node_registry(&self, registry: &NodeRegistry) -> Result<()> {
         let cf = self.get_cf(CF_NODES)?;
         let key = "node_registry";
-        let value = bincode::serialize(registry)?;
+        let value = encode_record(registry)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store node registry: {}", e)))?;
@@ -90,7 +130,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, key.as_bytes())? {
             Some(value) => {
-                let registry: NodeRegistry = bincode::deserialize(&value)?;
+                let registry: NodeRegistry = decode_record(&value)?;
                 Ok(Some(registry))
             }
             None => Ok(None),
@@ -99,33 +139,105 @@ impl StorageManager {
 
     // Transaction storage operations
     pub fn store_raw_transaction(&self, tx: &RawTransaction) -> Result<()> {
+        let leader_id = tx.tx_data.leader.as_deref().unwrap_or(UNASSIGNED_LEADER);
+        self.store_leader_raw_transaction(leader_id, tx)
+    }
+
+    // Only this transaction's record is written, never the rest of the leader's pool
+    pub fn store_leader_raw_transaction(&self, leader_id: &str, tx: &RawTransaction) -> Result<()> {
         let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
-        let key = &tx.raw_tx_id;
-        let value = bincode::serialize(tx)?;
+        let mut batch = WriteBatch::default();
         
-        self.db.put_cf(&cf, key.as_bytes(), value)
+        // Re-assigned to another leader: drop the old record
+        if let Some(previous) = self.raw_transaction_leader(&tx.raw_tx_id)? {
+            if previous != leader_id {
+                batch.delete_cf(&cf, raw_tx_key(&previous, &tx.raw_tx_id));
+            }
+        }
+        batch.put_cf(&cf, raw_tx_key(leader_id, &tx.raw_tx_id), encode_record(tx)?);
+        batch.put_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx.raw_tx_id), leader_id.as_bytes());
+        
+        self.db.write(batch)
             .map_err(|e| PclError::Storage(format!("Failed to store raw transaction: {}", e)))?;
         
-        log::debug!("Raw transaction {} stored successfully", tx.raw_tx_id);
+        log::debug!("Raw transaction {} stored under leader {}", tx.raw_tx_id, leader_id);
         Ok(())
     }
 
     pub fn load_raw_transaction(&self, tx_id: &str) -> Result<Option<RawTransaction>> {
         let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
+        let Some(leader_id) = self.raw_transaction_leader(tx_id)? else {
+            return Ok(None);
+        };
         
-        match self.db.get_cf(&cf, tx_id.as_bytes())? {
+        match self.db.get_cf(&cf, raw_tx_key(&leader_id, tx_id))? {
             Some(value) => {
-                let tx: RawTransaction = bincode::deserialize(&value)?;
+                let tx: RawTransaction = decode_record(&value)?;
                 Ok(Some(tx))
             }
             None => Ok(None),
         }
     }
 
+    pub fn load_leader_raw_transactions(&self, leader_id: &str) -> Result<Vec<RawTransaction>> {
+        self.load_with_prefix(CF_RAW_TRANSACTIONS, &format!("{}{}/", RAW_TX_RECORD_PREFIX, leader_id))
+    }
+
+    pub fn raw_transaction_leader(&self, tx_id: &str) -> Result<Option<String>> {
+        let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
+        let key = format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id);
+        
+        Ok(self.db.get_cf(&cf, key.as_bytes())?
+            .map(|value| String::from_utf8_lossy(&value).into_owned()))
+    }
+
+    pub fn delete_raw_transaction(&self, tx_id: &str) -> Result<()> {
+        let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
+        let Some(leader_id) = self.raw_transaction_leader(tx_id)? else {
+            return Ok(());
+        };
+        
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(&cf, raw_tx_key(&leader_id, tx_id));
+        batch.delete_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id));
+        self.db.write(batch)
+            .map_err(|e| PclError::Storage(format!("Failed to delete raw transaction: {}", e)))?;
+        Ok(())
+    }
+
+    // Moves raw transactions written under the old bare raw_tx_id keys into the per-leader layout.
+    // Safe to run on every start; returns how many records were moved.
+    pub fn migrate_raw_transaction_layout(&self) -> Result<usize> {
+        let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
+        let mut batch = WriteBatch::default();
+        let mut migrated = 0;
+        
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            if key.starts_with(RAW_TX_RECORD_PREFIX.as_bytes()) || key.starts_with(RAW_TX_INDEX_PREFIX.as_bytes()) {
+                continue;
+            }
+            let tx: RawTransaction = decode_record(&value)?;
+            let leader_id = tx.tx_data.leader.as_deref().unwrap_or(UNASSIGNED_LEADER);
+            
+            batch.put_cf(&cf, raw_tx_key(leader_id, &tx.raw_tx_id), &value);
+            batch.put_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx.raw_tx_id), leader_id.as_bytes());
+            batch.delete_cf(&cf, &key);
+            migrated += 1;
+        }
+        
+        if migrated > 0 {
+            self.db.write(batch)
+                .map_err(|e| PclError::Storage(format!("Failed to migrate raw transactions: {}", e)))?;
+            log::info!("Migrated {} raw transactions to the per-leader layout", migrated);
+        }
+        Ok(migrated)
+    }
+
     pub fn store_processing_transaction(&self, tx: &ProcessingTransaction) -> Result<()> {
         let cf = self.get_cf(CF_PROCESSING_TRANSACTIONS)?;
         let key = &tx.tx_id;
-        let value = bincode::serialize(tx)?;
+        let value = encode_record(tx)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store processing transaction: {}", e)))?;
@@ -139,7 +251,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, tx_id.as_bytes())? {
             Some(value) => {
-                let tx: ProcessingTransaction = bincode::deserialize(&value)?;
+                let tx: ProcessingTransaction = decode_record(&value)?;
                 Ok(Some(tx))
             }
             None => Ok(None),
@@ -149,7 +261,7 @@ impl StorageManager {
     pub fn store_finalized_transaction(&self, tx: &FinalizedTransaction) -> Result<()> {
         let cf = self.get_cf(CF_FINALIZED_TRANSACTIONS)?;
         let key = &tx.tx_id;
-        let value = bincode::serialize(tx)?;
+        let value = encode_record(tx)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store finalized transaction: {}", e)))?;
@@ -163,7 +275,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, tx_id.as_bytes())? {
             Some(value) => {
-                let tx: FinalizedTransaction = bincode::deserialize(&value)?;
+                let tx: FinalizedTransaction = decode_record(&value)?;
                 Ok(Some(tx))
             }
             None => Ok(None),
@@ -174,7 +286,7 @@ impl StorageManager {
     pub fn store_mempool_state(&self, mempool: &MempoolManager) -> Result<()> {
         let cf = self.get_cf(CF_MEMPOOL_STATE)?;
         let key = "mempool_state";
-        let value = bincode::serialize(mempool)?;
+        let value = encode_record(mempool)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store mempool state: {}", e)))?;
@@ -189,7 +301,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, key.as_bytes())? {
             Some(value) => {
-                let mempool: MempoolManager = bincode::deserialize(&value)?;
+                let mempool: MempoolManager = decode_record(&value)?;
                 Ok(Some(mempool))
             }
             None => Ok(None),
@@ -200,7 +312,7 @@ impl StorageManager {
     pub fn store_uptime_data(&self, node_id: &str, uptime_data: &UptimeData) -> Result<()> {
         let cf = self.get_cf(CF_UPTIME_DATA)?;
         let key = format!("uptime_{}", node_id);
-        let value = bincode::serialize(uptime_data)?;
+        let value = encode_record(uptime_data)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store uptime data: {}", e)))?;
@@ -209,13 +321,26 @@ impl StorageManager {
         Ok(())
     }
 
+    // One WriteBatch for many nodes' records, as flushed by UptimeWriteBuffer
+    pub fn store_uptime_batch(&self, records: &[UptimeData]) -> Result<()> {
+        let cf = self.get_cf(CF_UPTIME_DATA)?;
+        let mut batch = WriteBatch::default();
+        for uptime_data in records {
+            batch.put_cf(cf, format!("uptime_{}", uptime_data.node_id), encode_record(uptime_data)?);
+        }
+        
+        self.db.write(batch)
+            .map_err(|e| PclError::Storage(format!("Failed to store uptime batch: {}", e)))?;
+        Ok(())
+    }
+
     pub fn load_uptime_data(&self, node_id: &str) -> Result<Option<UptimeData>> {
         let cf = self.get_cf(CF_UPTIME_DATA)?;
         let key = format!("uptime_{}", node_id);
         
         match self.db.get_cf(&cf, key.as_bytes())? {
             Some(value) => {
-                let uptime_data: UptimeData = bincode::deserialize(&value)?;
+                let uptime_data: UptimeData = decode_record(&value)?;
                 Ok(Some(uptime_data))
             }
             None => Ok(None),
@@ -225,7 +350,7 @@ impl StorageManager {
     pub fn store_leader_election_state(&self, state: &LeaderElectionState) -> Result<()> {
         let cf = self.get_cf(CF_LEADER_ELECTION)?;
         let key = "leader_election_state";
-        let value = bincode::serialize(state)?;
+        let value = encode_record(state)?;
         
         self.db.put_cf(&cf, key.as_bytes(), value)
             .map_err(|e| PclError::Storage(format!("Failed to store leader election state: {}", e)))?;
@@ -240,7 +365,7 @@ impl StorageManager {
         
         match self.db.get_cf(&cf, key.as_bytes())? {
             Some(value) => {
-                let state: LeaderElectionState = bincode::deserialize(&value)?;
+                let state: LeaderElectionState = decode_record(&value)?;
                 Ok(Some(state))
             }
             None => Ok(None),
@@ -249,12 +374,11 @@ impl StorageManager {
 
     // Utility methods
     pub fn delete_transaction(&self, tx_id: &str) -> Result<()> {
-        let cf_raw = self.get_cf(CF_RAW_TRANSACTIONS)?;
         let cf_processing = self.get_cf(CF_PROCESSING_TRANSACTIONS)?;
         let cf_finalized = self.get_cf(CF_FINALIZED_TRANSACTIONS)?;
         
         // Delete from all transaction column families
-        let _ = self.db.delete_cf(&cf_raw, tx_id.as_bytes());
+        let _ = self.delete_raw_transaction(tx_id);
         let _ = self.db.delete_cf(&cf_processing, tx_id.as_bytes());
         let _ = self.db.delete_cf(&cf_finalized, tx_id.as_bytes());
         
@@ -269,7 +393,7 @@ impl StorageManager {
         let iter = self.db.iterator_cf(&cf, IteratorMode::Start);
         for item in iter {
             let (_key, value) = item?;
-            let tx: FinalizedTransaction = bincode::deserialize(&value)?;
+            let tx: FinalizedTransaction = decode_record(&value)?;
             transactions.push(tx);
         }
         
@@ -289,6 +413,465 @@ impl StorageManager {
         Ok(count)
     }
 
+    // Export outbox: records keyed by big-endian sequence number so iteration is in append order
+    pub fn append_export_record(&self, record: &ExportRecord) -> Result<u64> {
+        let cf = self.get_cf(CF_EXPORT_OUTBOX)?;
+        let last_sequence = match self.db.iterator_cf(&cf, IteratorMode::End).next() {
+            Some(item) => {
+                let (key, _value) = item?;
+                sequence_from_key(&key)?
+            }
+            None => 0,
+        };
+        // Acknowledged records are deleted, so the cursor keeps sequences increasing across an empty outbox
+        let sequence = last_sequence.max(self.load_export_cursor()?) + 1;
+        
+        self.db.put_cf(&cf, sequence.to_be_bytes(), encode_record(record)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store export record: {}", e)))?;
+        
+        log::debug!("Export record {} queued as #{}", record.tx_id, sequence);
+        Ok(sequence)
+    }
+
+    pub fn load_export_records(&self, after: u64, limit: usize) -> Result<Vec<(u64, ExportRecord)>> {
+        let cf = self.get_cf(CF_EXPORT_OUTBOX)?;
+        let start = (after + 1).to_be_bytes();
+        let mut records = Vec::new();
+        
+        for item in self.db.iterator_cf(&cf, IteratorMode::From(&start, Direction::Forward)) {
+            if records.len() >= limit {
+                break;
+            }
+            let (key, value) = item?;
+            records.push((sequence_from_key(&key)?, decode_record(&value)?));
+        }
+        
+        Ok(records)
+    }
+
+    pub fn load_export_cursor(&self) -> Result<u64> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        
+        match self.db.get_cf(&cf, EXPORT_CURSOR_KEY.as_bytes())? {
+            Some(value) => Ok(sequence_from_key(&value)?),
+            None => Ok(0),
+        }
+    }
+
+    // Advances the cursor and drops everything up to and including `sequence`
+    pub fn acknowledge_export_records(&self, sequence: u64) -> Result<()> {
+        let outbox = self.get_cf(CF_EXPORT_OUTBOX)?;
+        let state = self.get_cf(CF_NETWORK_STATE)?;
+        
+        self.db.put_cf(&state, EXPORT_CURSOR_KEY.as_bytes(), sequence.to_be_bytes())
+            .map_err(|e| PclError::Storage(format!("Failed to store export cursor: {}", e)))?;
+        
+        let mut acknowledged = Vec::new();
+        for item in self.db.iterator_cf(&outbox, IteratorMode::Start) {
+            let (key, _value) = item?;
+            if sequence_from_key(&key)? > sequence {
+                break;
+            }
+            acknowledged.push(key);
+        }
+        for key in acknowledged {
+            self.db.delete_cf(&outbox, key)
+                .map_err(|e| PclError::Storage(format!("Failed to delete export record: {}", e)))?;
+        }
+        
+        log::debug!("Export cursor advanced to #{}", sequence);
+        Ok(())
+    }
+
+    // Consensus event log: append-only, keyed by big-endian sequence number like the export outbox
+    pub fn append_stake_event(&self, event: &StakeEvent) -> Result<u64> {
+        let cf = self.get_cf(CF_CONSENSUS_EVENTS)?;
+        let sequence = match self.db.iterator_cf(&cf, IteratorMode::End).next() {
+            Some(item) => {
+                let (key, _value) = item?;
+                sequence_from_key(&key)? + 1
+            }
+            None => 1,
+        };
+        
+        self.db.put_cf(&cf, sequence.to_be_bytes(), encode_record(event)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store stake event: {}", e)))?;
+        
+        log::debug!("Stake event {:?} for {} recorded as #{}", event.kind, event.stake_id, sequence);
+        Ok(sequence)
+    }
+
+    pub fn load_stake_events(&self) -> Result<Vec<(u64, StakeEvent)>> {
+        let cf = self.get_cf(CF_CONSENSUS_EVENTS)?;
+        let mut events = Vec::new();
+        
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            events.push((sequence_from_key(&key)?, decode_record(&value)?));
+        }
+        
+        Ok(events)
+    }
+
+    // Finalization order: tx ids keyed by big-endian sequence number, so explorers can page through
+    // every finalized transaction with a cursor. A tx finalized again after a crash gets a new entry.
+    pub fn append_finalization(&self, tx_id: &str) -> Result<u64> {
+        let cf = self.get_cf(CF_FINALIZATION_SEQUENCE)?;
+        let sequence = match self.db.iterator_cf(cf, IteratorMode::End).next() {
+            Some(item) => {
+                let (key, _value) = item?;
+                sequence_from_key(&key)? + 1
+            }
+            None => 1,
+        };
+        
+        self.db.put_cf(cf, sequence.to_be_bytes(), tx_id.as_bytes())
+            .map_err(|e| PclError::Storage(format!("Failed to store finalization sequence: {}", e)))?;
+        
+        log::debug!("Finalized transaction {} is #{}", tx_id, sequence);
+        Ok(sequence)
+    }
+
+    pub fn load_finalizations(&self, after: u64, limit: usize) -> Result<Vec<(u64, String)>> {
+        let cf = self.get_cf(CF_FINALIZATION_SEQUENCE)?;
+        let start = (after + 1).to_be_bytes();
+        let mut entries = Vec::new();
+        
+        for item in self.db.iterator_cf(cf, IteratorMode::From(&start, Direction::Forward)) {
+            if entries.len() >= limit {
+                break;
+            }
+            let (key, value) = item?;
+            let sequence = sequence_from_key(&key)?;
+            let tx_id = String::from_utf8(value.to_vec())
+                .map_err(|_| PclError::Storage(format!("Malformed tx id in finalization sequence #{}", sequence)))?;
+            entries.push((sequence, tx_id));
+        }
+        
+        Ok(entries)
+    }
+
+    pub fn store_utxo_checkpoint(&self, checkpoint: &UtxoCheckpoint) -> Result<()> {
+        let cf = self.get_cf(CF_UTXO_HISTORY)?;
+        self.db.put_cf(cf, history_key(UTXO_CHECKPOINT_TAG, checkpoint.sequence), encode_record(checkpoint)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store UTXO checkpoint #{}: {}", checkpoint.sequence, e)))?;
+        Ok(())
+    }
+
+    pub fn store_utxo_delta(&self, delta: &UtxoDelta) -> Result<()> {
+        let cf = self.get_cf(CF_UTXO_HISTORY)?;
+        self.db.put_cf(cf, history_key(UTXO_DELTA_TAG, delta.sequence), encode_record(delta)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store UTXO delta #{}: {}", delta.sequence, e)))?;
+        Ok(())
+    }
+
+    pub fn load_utxo_checkpoint_at_or_before(&self, sequence: u64) -> Result<Option<UtxoCheckpoint>> {
+        let cf = self.get_cf(CF_UTXO_HISTORY)?;
+        let start = history_key(UTXO_CHECKPOINT_TAG, sequence);
+        match self.db.iterator_cf(cf, IteratorMode::From(&start, Direction::Reverse)).next() {
+            Some(item) => {
+                let (key, value) = item?;
+                Ok((key.first() == Some(&UTXO_CHECKPOINT_TAG)).then(|| decode_record(&value)).transpose()?)
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Deltas after `after` up to and including `through`, in order
+    pub fn load_utxo_deltas(&self, after: u64, through: u64) -> Result<Vec<UtxoDelta>> {
+        let cf = self.get_cf(CF_UTXO_HISTORY)?;
+        let start = history_key(UTXO_DELTA_TAG, after + 1);
+        let end = history_key(UTXO_DELTA_TAG, through);
+        let mut deltas = Vec::new();
+        for item in self.db.iterator_cf(cf, IteratorMode::From(&start, Direction::Forward)) {
+            let (key, value) = item?;
+            if key.as_ref() > end.as_slice() {
+                break;
+            }
+            deltas.push(decode_record(&value)?);
+        }
+        Ok(deltas)
+    }
+
+    // Receipts are keyed by tx id; a fork that changes the winning leader overwrites the receipt
+    pub fn store_receipt(&self, receipt: &TransactionReceipt) -> Result<()> {
+        let cf = self.get_cf(CF_RECEIPTS)?;
+        
+        self.db.put_cf(&cf, receipt.tx_id.as_bytes(), encode_record(receipt)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store receipt: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_receipt(&self, tx_id: &str) -> Result<Option<TransactionReceipt>> {
+        let cf = self.get_cf(CF_RECEIPTS)?;
+        
+        match self.db.get_cf(&cf, tx_id.as_bytes())? {
+            Some(value) => Ok(Some(decode_record(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    // Reputation records are keyed by identity and overwritten on every change
+    pub fn store_reputation_record(&self, record: &ReputationRecord) -> Result<()> {
+        let cf = self.get_cf(CF_REPUTATION)?;
+        
+        self.db.put_cf(cf, record.identity.as_bytes(), encode_record(record)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store reputation: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_reputation_records(&self) -> Result<Vec<ReputationRecord>> {
+        let cf = self.get_cf(CF_REPUTATION)?;
+        let mut records = Vec::new();
+        
+        for item in self.db.iterator_cf(cf, IteratorMode::Start) {
+            let (_key, value) = item?;
+            records.push(decode_record(&value)?);
+        }
+        
+        Ok(records)
+    }
+
+    // Webhook subscriptions and deliveries share one column family, separated by key prefix
+    pub fn store_subscription(&self, subscription: &Subscription) -> Result<()> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let key = format!("subscription:{}", subscription.id);
+        
+        self.db.put_cf(&cf, key.as_bytes(), encode_record(subscription)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store subscription: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_subscription(&self, subscription_id: &str) -> Result<Option<Subscription>> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let key = format!("subscription:{}", subscription_id);
+        
+        match self.db.get_cf(&cf, key.as_bytes())? {
+            Some(value) => Ok(Some(decode_record(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_subscriptions(&self) -> Result<Vec<Subscription>> {
+        self.load_with_prefix(CF_WEBHOOKS, "subscription:")
+    }
+
+    pub fn delete_subscription(&self, subscription_id: &str) -> Result<()> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let key = format!("subscription:{}", subscription_id);
+        
+        self.db.delete_cf(&cf, key.as_bytes())
+            .map_err(|e| PclError::Storage(format!("Failed to delete subscription: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn store_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let key = format!("delivery:{}", delivery.id);
+        
+        let mut batch = WriteBatch::default();
+        batch.put_cf(cf, key.as_bytes(), encode_record(delivery)?);
+        index_webhook_delivery(&mut batch, cf, delivery);
+        self.db.write(batch)
+            .map_err(|e| PclError::Storage(format!("Failed to store webhook delivery: {}", e)))?;
+        Ok(())
+    }
+
+    // Ids of deliveries due by `now`, collected from one snapshot of the due index; the iterator is
+    // released before the caller attempts anything, and deliveries stored meanwhile wait for the next tick
+    pub fn due_webhook_delivery_ids(&self, now: u64) -> Result<Vec<String>> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let snapshot = self.db.snapshot();
+        let mut ids = Vec::new();
+        
+        for item in snapshot.iterator_cf(cf, IteratorMode::From(WEBHOOK_DUE_PREFIX.as_bytes(), Direction::Forward)) {
+            let (key, value) = item?;
+            let Some(delivery_id) = key.strip_prefix(WEBHOOK_DUE_PREFIX.as_bytes()) else {
+                break;
+            };
+            let next_attempt_at = <[u8; 8]>::try_from(value.as_ref()).map(u64::from_be_bytes)
+                .map_err(|_| PclError::Storage(format!("Malformed due entry for delivery {}", String::from_utf8_lossy(delivery_id))))?;
+            if next_attempt_at <= now {
+                ids.push(String::from_utf8_lossy(delivery_id).into_owned());
+            }
+        }
+        
+        Ok(ids)
+    }
+
+    // Indexes the pending deliveries of a database written before the due index existed. Safe to
+    // repeat; returns how many deliveries were indexed.
+    pub fn rebuild_webhook_due_index(&self) -> Result<usize> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let mut batch = WriteBatch::default();
+        let mut indexed = 0;
+        
+        for delivery in self.load_webhook_deliveries()? {
+            index_webhook_delivery(&mut batch, cf, &delivery);
+            if delivery.status == DeliveryStatus::Pending {
+                indexed += 1;
+            }
+        }
+        
+        self.db.write(batch)
+            .map_err(|e| PclError::Storage(format!("Failed to index webhook deliveries: {}", e)))?;
+        Ok(indexed)
+    }
+
+    pub fn load_webhook_delivery(&self, delivery_id: &str) -> Result<Option<WebhookDelivery>> {
+        let cf = self.get_cf(CF_WEBHOOKS)?;
+        let key = format!("delivery:{}", delivery_id);
+        
+        match self.db.get_cf(&cf, key.as_bytes())? {
+            Some(value) => Ok(Some(decode_record(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_webhook_deliveries(&self) -> Result<Vec<WebhookDelivery>> {
+        self.load_with_prefix(CF_WEBHOOKS, "delivery:")
+    }
+
+    // API keys are stored under the hash of their secret, so a request can be authenticated with one lookup
+    pub fn store_api_key(&self, key: &ApiKey) -> Result<()> {
+        let cf = self.get_cf(CF_API_KEYS)?;
+        
+        self.db.put_cf(&cf, key.key_hash.as_bytes(), encode_record(key)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store API key: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        let cf = self.get_cf(CF_API_KEYS)?;
+        
+        match self.db.get_cf(&cf, key_hash.as_bytes())? {
+            Some(value) => Ok(Some(decode_record(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_api_keys(&self) -> Result<Vec<ApiKey>> {
+        self.load_with_prefix(CF_API_KEYS, "")
+    }
+
+    fn load_with_prefix<T: Versioned>(&self, cf_name: &str, prefix: &str) -> Result<Vec<T>> {
+        let cf = self.get_cf(cf_name)?;
+        let mut values = Vec::new();
+        
+        for item in self.db.iterator_cf(&cf, IteratorMode::From(prefix.as_bytes(), Direction::Forward)) {
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            values.push(decode_record(&value)?);
+        }
+        
+        Ok(values)
+    }
+
+    // The node's own identity, so a restarted node comes back with the same id and keys
+    pub fn store_node_identity(&self, node: &Node, keypair: &NodeKeypair) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        let value = encode_record(&(node.clone(), keypair.signing_key.to_bytes()))?;
+        self.db.put_cf(&cf, NODE_IDENTITY_KEY.as_bytes(), value)
+            .map_err(|e| PclError::Storage(format!("Failed to store node identity: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_node_identity(&self) -> Result<Option<(Node, NodeKeypair)>> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        match self.db.get_cf(&cf, NODE_IDENTITY_KEY.as_bytes())? {
+            Some(value) => {
+                let (node, secret): (Node, [u8; 32]) = decode_record(&value)?;
+                let keypair = NodeKeypair::from_bytes(&secret)?;
+                if keypair.public_key() != node.public_key {
+                    return Err(PclError::Storage("Stored node identity key does not match its node record".to_string()));
+                }
+                Ok(Some((node, keypair)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Opaque snapshot of the node binary's in-memory consensus state, written periodically
+    pub fn store_consensus_state(&self, state: &[u8]) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        self.db.put_cf(&cf, CONSENSUS_STATE_KEY.as_bytes(), state)
+            .map_err(|e| PclError::Storage(format!("Failed to store consensus state: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_consensus_state(&self) -> Result<Option<Vec<u8>>> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        Ok(self.db.get_cf(&cf, CONSENSUS_STATE_KEY.as_bytes())?)
+    }
+
+    // A tenant's snapshot, kept beside the default ledger's under the tenant's key prefix
+    pub fn store_tenant_consensus_state(&self, tenant: &str, state: &[u8]) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        self.db.put_cf(cf, tenant_key(tenant, CONSENSUS_STATE_KEY).as_bytes(), state)
+            .map_err(|e| PclError::Storage(format!("Failed to store consensus state of tenant {}: {}", tenant, e)))?;
+        Ok(())
+    }
+
+    pub fn load_tenant_consensus_state(&self, tenant: &str) -> Result<Option<Vec<u8>>> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        Ok(self.db.get_cf(cf, tenant_key(tenant, CONSENSUS_STATE_KEY).as_bytes())?)
+    }
+
+    // Last measured clock offset, applied at startup until the first sync
+    pub fn store_clock_status(&self, status: &ClockStatus) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        self.db.put_cf(&cf, CLOCK_STATUS_KEY.as_bytes(), encode_record(status)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store clock status: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_clock_status(&self) -> Result<Option<ClockStatus>> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        match self.db.get_cf(&cf, CLOCK_STATUS_KEY.as_bytes())? {
+            Some(value) => Ok(Some(decode_record(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn store_peer_record(&self, record: &PeerRecord) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        let key = format!("{}{}", PEER_RECORD_PREFIX, record.peer_id);
+        self.db.put_cf(cf, key.as_bytes(), encode_record(record)?)
+            .map_err(|e| PclError::Storage(format!("Failed to store peer record: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn load_peer_records(&self) -> Result<Vec<PeerRecord>> {
+        self.load_with_prefix(CF_NETWORK_STATE, PEER_RECORD_PREFIX)
+    }
+
+    pub fn delete_peer_record(&self, peer_id: &str) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        let key = format!("{}{}", PEER_RECORD_PREFIX, peer_id);
+        self.db.delete_cf(cf, key.as_bytes())
+            .map_err(|e| PclError::Storage(format!("Failed to delete peer record: {}", e)))?;
+        Ok(())
+    }
+
+    // Round-trips a throwaway key so a read-only or full volume is caught before the node starts
+    pub fn write_probe(&self) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        let key = b"doctor_write_probe";
+        let value = uuid::Uuid::new_v4().to_string();
+        self.db.put_cf(&cf, key, value.as_bytes())
+            .map_err(|e| PclError::Storage(format!("Write failed: {}", e)))?;
+        let read_back = self.db.get_cf(&cf, key)
+            .map_err(|e| PclError::Storage(format!("Read back failed: {}", e)))?;
+        self.db.delete_cf(&cf, key)
+            .map_err(|e| PclError::Storage(format!("Delete failed: {}", e)))?;
+        if read_back.as_deref() != Some(value.as_bytes()) {
+            return Err(PclError::Storage("Read back a different value than was written".to_string()));
+        }
+        Ok(())
+    }
+
     pub fn compact_database(&self) -> Result<()> {
         self.db.compact_range::<&[u8], &[u8]>(None, None);
         log::info!("Database compaction completed");
@@ -318,7 +901,7 @@ impl StorageManager {
         
         // Count items in each column family
         stats.nodes_count = self.count_items_in_cf(&nodes_cf)?;
-        stats.raw_transactions_count = self.count_items_in_cf(&raw_tx_cf)?;
+        stats.raw_transactions_count = self.count_items_with_prefix(&raw_tx_cf, RAW_TX_INDEX_PREFIX)?;
         stats.processing_transactions_count = self.count_items_in_cf(&processing_tx_cf)?;
         stats.finalized_transactions_count = self.count_items_in_cf(&finalized_tx_cf)?;
         
@@ -330,6 +913,18 @@ impl StorageManager {
             .ok_or_else(|| PclError::Storage(format!("Column family {} not found", name)))
     }
 
+    fn count_items_with_prefix(&self, cf: &ColumnFamily, prefix: &str) -> Result<usize> {
+        let mut count = 0;
+        for item in self.db.iterator_cf(cf, IteratorMode::From(prefix.as_bytes(), Direction::Forward)) {
+            let (key, _) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
     fn count_items_in_cf(&self, cf: &ColumnFamily) -> Result<usize> {
         let mut count = 0;
         let iter = self.db.iterator_cf(cf, IteratorMode::Start);
@@ -341,6 +936,32 @@ impl StorageManager {
     }
 }
 
+// Only pending deliveries stay in the due index
+fn index_webhook_delivery(batch: &mut WriteBatch, cf: &ColumnFamily, delivery: &WebhookDelivery) {
+    let key = format!("{}{}", WEBHOOK_DUE_PREFIX, delivery.id);
+    if delivery.status == DeliveryStatus::Pending {
+        batch.put_cf(cf, key.as_bytes(), delivery.next_attempt_at.to_be_bytes());
+    } else {
+        batch.delete_cf(cf, key.as_bytes());
+    }
+}
+
+fn raw_tx_key(leader_id: &str, tx_id: &str) -> String {
+    format!("{}{}/{}", RAW_TX_RECORD_PREFIX, leader_id, tx_id)
+}
+
+fn history_key(tag: u8, sequence: u64) -> Vec<u8> {
+    let mut key = vec![tag];
+    key.extend_from_slice(&sequence.to_be_bytes());
+    key
+}
+
+fn sequence_from_key(key: &[u8]) -> Result<u64> {
+    let bytes: [u8; 8] = key.try_into()
+        .map_err(|_| PclError::Storage(format!("Malformed sequence key ({} bytes)", key.len())))?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
 // Data structures for storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UptimeData {
@@ -400,7 +1021,7 @@ pub fn cleanup_old_transactions(storage: &StorageManager, days_old: u64) -> Resu
     
     for item in iter {
         let (key, value) = item?;
-        let tx: FinalizedTransaction = bincode::deserialize(&value)?;
+        let tx: FinalizedTransaction = decode_record(&value)?;
         
         if tx.finalized_at < cutoff_time {
             keys_to_delete.push(key.to_vec());
@@ -0,0 +1,313 @@
+// Storage integrity - offline check of the mempool column families, run by `pcl-node verify-db`
+//
+// A crash mid-write or a bad disk can leave records that no longer decode, ids that do not match
+// their content, signatures that do not verify and locks held for transactions that are gone. Each
+// record is decoded and checked against what it claims; with repair on, in-flight records that fail
+// are deleted, indexes are rebuilt and orphaned locks are released. Finalized transactions are only
+// moved to their own key, never deleted for a failed check, so history is not rewritten silently.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use ed25519_dalek::VerifyingKey;
+use rocksdb::{IteratorMode, WriteBatch};
+use serde::{Deserialize, Serialize};
+use crate::error::{PclError, Result};
+use crate::mempool::{FinalizedTransaction, MempoolManager};
+use crate::node::{Node, NodeRegistry};
+use crate::transaction::{ProcessingTransaction, RawTransaction};
+use super::{
+    decode_record, encode_record, raw_tx_key, StorageManager, CF_FINALIZED_TRANSACTIONS, CF_MEMPOOL_STATE, CF_NODES,
+    CF_PROCESSING_TRANSACTIONS, CF_RAW_TRANSACTIONS, RAW_TX_INDEX_PREFIX, RAW_TX_RECORD_PREFIX,
+};
+
+const MEMPOOL_STATE_KEY: &str = "mempool_state";
+const NODE_REGISTRY_KEY: &str = "node_registry";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub column_family: String,
+    pub key: String,
+    pub problem: String,
+    pub repaired: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub checked: BTreeMap<String, usize>, // column family -> entries read
+    pub issues: Vec<IntegrityIssue>,
+    pub unverified_signatures: usize, // processing entries whose leader key is not stored on this node
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn repaired(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.repaired).count()
+    }
+
+    fn issue(&mut self, column_family: &str, key: &str, problem: String, repaired: bool) {
+        self.issues.push(IntegrityIssue { column_family: column_family.to_string(), key: key.to_string(), problem, repaired });
+    }
+}
+
+impl StorageManager {
+    // Checks every record in the mempool column families; with repair set, fixes what can be fixed
+    pub fn verify_integrity(&self, repair: bool) -> Result<IntegrityReport> {
+        let mut report = IntegrityReport::default();
+        if repair {
+            self.migrate_raw_transaction_layout()?;
+        }
+        let leader_keys = self.stored_node_keys()?;
+        let raw_ids = self.verify_raw_transactions(repair, &mut report)?;
+        let processing_ids = self.verify_processing_transactions(repair, &leader_keys, &mut report)?;
+        let finalized_ids = self.verify_finalized_transactions(repair, &mut report)?;
+        self.verify_mempool_state(repair, &raw_ids, &processing_ids, &finalized_ids, &mut report)?;
+        Ok(report)
+    }
+
+    fn scan(&self, cf_name: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let cf = self.get_cf(cf_name)?;
+        let mut entries = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            entries.push((String::from_utf8_lossy(&key).into_owned(), value.to_vec()));
+        }
+        Ok(entries)
+    }
+
+    // Node id -> public key, from individual node records and the stored registry
+    fn stored_node_keys(&self) -> Result<HashMap<String, VerifyingKey>> {
+        let mut keys = HashMap::new();
+        for (key, value) in self.scan(CF_NODES)? {
+            if key == NODE_REGISTRY_KEY {
+                if let Ok(registry) = decode_record::<NodeRegistry>(&value) {
+                    keys.extend(registry.nodes.values().map(|node| (node.id.to_string(), node.public_key)));
+                }
+            } else if let Ok(node) = decode_record::<Node>(&value) {
+                keys.insert(node.id.to_string(), node.public_key);
+            }
+        }
+        Ok(keys)
+    }
+
+    // Records live under tx/{leader}/{raw_tx_id} with an idx/{raw_tx_id} -> leader entry beside them
+    fn verify_raw_transactions(&self, repair: bool, report: &mut IntegrityReport) -> Result<HashSet<String>> {
+        let entries = self.scan(CF_RAW_TRANSACTIONS)?;
+        report.checked.insert(CF_RAW_TRANSACTIONS.to_string(), entries.len());
+        let index: HashMap<String, String> = entries.iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(RAW_TX_INDEX_PREFIX).map(|tx_id| (tx_id.to_string(), String::from_utf8_lossy(value).into_owned()))
+            })
+            .collect();
+
+        let cf = self.get_cf(CF_RAW_TRANSACTIONS)?;
+        let mut batch = WriteBatch::default();
+        let mut valid = HashSet::new();
+        for (key, value) in &entries {
+            if key.starts_with(RAW_TX_INDEX_PREFIX) {
+                continue;
+            }
+            let Some((leader_id, tx_id)) = key.strip_prefix(RAW_TX_RECORD_PREFIX).and_then(|rest| rest.split_once('/')) else {
+                report.issue(CF_RAW_TRANSACTIONS, key, "Written in the old layout, not yet migrated".to_string(), false);
+                continue;
+            };
+            let problem = match decode_record::<RawTransaction>(value) {
+                Err(e) => Some(format!("Does not deserialize: {}", e)),
+                Ok(tx) => raw_transaction_problem(&tx, tx_id),
+            };
+            if let Some(problem) = problem {
+                if repair {
+                    batch.delete_cf(&cf, key.as_bytes());
+                    if index.get(tx_id).is_some_and(|indexed| indexed == leader_id) {
+                        batch.delete_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id));
+                    }
+                }
+                report.issue(CF_RAW_TRANSACTIONS, key, problem, repair);
+                continue;
+            }
+            if index.get(tx_id).map(String::as_str) != Some(leader_id) {
+                if repair {
+                    batch.put_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id), leader_id.as_bytes());
+                }
+                report.issue(CF_RAW_TRANSACTIONS, key, "Missing or wrong leader index entry".to_string(), repair);
+            }
+            valid.insert(tx_id.to_string());
+        }
+        // An index entry for a record found under another leader was already rewritten above
+        for (tx_id, leader_id) in index.iter().filter(|(tx_id, _)| !valid.contains(*tx_id)) {
+            let record_exists = entries.iter().any(|(key, _)| key == &raw_tx_key(leader_id, tx_id));
+            if !record_exists {
+                if repair {
+                    batch.delete_cf(&cf, format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id));
+                }
+                report.issue(CF_RAW_TRANSACTIONS, &format!("{}{}", RAW_TX_INDEX_PREFIX, tx_id),
+                             format!("Points at leader {} which holds no such record", leader_id), repair);
+            }
+        }
+        self.write_repairs(batch, repair)?;
+        Ok(valid)
+    }
+
+    fn verify_processing_transactions(
+        &self,
+        repair: bool,
+        leader_keys: &HashMap<String, VerifyingKey>,
+        report: &mut IntegrityReport,
+    ) -> Result<HashSet<String>> {
+        let entries = self.scan(CF_PROCESSING_TRANSACTIONS)?;
+        report.checked.insert(CF_PROCESSING_TRANSACTIONS.to_string(), entries.len());
+        let cf = self.get_cf(CF_PROCESSING_TRANSACTIONS)?;
+        let mut batch = WriteBatch::default();
+        let mut valid = HashSet::new();
+        for (key, value) in &entries {
+            let problem = match decode_record::<ProcessingTransaction>(value) {
+                Err(e) => Some(format!("Does not deserialize: {}", e)),
+                Ok(tx) if tx.tx_id != *key => Some(format!("Stored under another transaction's key (holds {})", tx.tx_id)),
+                Ok(tx) => match leader_keys.get(&tx.leader) {
+                    Some(public_key) if !tx.verify_leader_signature(public_key) => {
+                        Some(format!("Leader signature from {} does not verify", tx.leader))
+                    }
+                    Some(_) => None,
+                    None => {
+                        report.unverified_signatures += 1;
+                        None
+                    }
+                },
+            };
+            match problem {
+                Some(problem) => {
+                    if repair {
+                        batch.delete_cf(&cf, key.as_bytes());
+                    }
+                    report.issue(CF_PROCESSING_TRANSACTIONS, key, problem, repair);
+                }
+                None => {
+                    valid.insert(key.clone());
+                }
+            }
+        }
+        self.write_repairs(batch, repair)?;
+        Ok(valid)
+    }
+
+    fn verify_finalized_transactions(&self, repair: bool, report: &mut IntegrityReport) -> Result<HashSet<String>> {
+        let entries = self.scan(CF_FINALIZED_TRANSACTIONS)?;
+        report.checked.insert(CF_FINALIZED_TRANSACTIONS.to_string(), entries.len());
+        let keys: HashSet<&String> = entries.iter().map(|(key, _)| key).collect();
+        let cf = self.get_cf(CF_FINALIZED_TRANSACTIONS)?;
+        let mut batch = WriteBatch::default();
+        let mut finalized = HashSet::new();
+        for (key, value) in &entries {
+            let tx = match decode_record::<FinalizedTransaction>(value) {
+                Ok(tx) => tx,
+                Err(e) => {
+                    report.issue(CF_FINALIZED_TRANSACTIONS, key, format!("Does not deserialize: {}", e), false);
+                    continue;
+                }
+            };
+            if tx.tx_id != *key {
+                // Moved to its own key unless a record is already there
+                let movable = repair && !keys.contains(&tx.tx_id);
+                if movable {
+                    batch.delete_cf(&cf, key.as_bytes());
+                    batch.put_cf(&cf, tx.tx_id.as_bytes(), encode_record(&tx)?);
+                }
+                report.issue(CF_FINALIZED_TRANSACTIONS, key, format!("Stored under another transaction's key (holds {})", tx.tx_id), movable);
+            }
+            if !tx.tx_data.validate_signature() {
+                report.issue(CF_FINALIZED_TRANSACTIONS, &tx.tx_id, "User signature does not verify".to_string(), false);
+            }
+            finalized.insert(tx.tx_id);
+        }
+        self.write_repairs(batch, repair)?;
+        Ok(finalized)
+    }
+
+    // Locks in the mempool snapshot must be held by a transaction still in flight
+    fn verify_mempool_state(
+        &self,
+        repair: bool,
+        raw_ids: &HashSet<String>,
+        processing_ids: &HashSet<String>,
+        finalized_ids: &HashSet<String>,
+        report: &mut IntegrityReport,
+    ) -> Result<()> {
+        let entries = self.scan(CF_MEMPOOL_STATE)?;
+        report.checked.insert(CF_MEMPOOL_STATE.to_string(), entries.len());
+        let Some((_, value)) = entries.iter().find(|(key, _)| key == MEMPOOL_STATE_KEY) else {
+            return Ok(());
+        };
+        let mut mempool = match decode_record::<MempoolManager>(value) {
+            Ok(mempool) => mempool,
+            Err(e) => {
+                // The node starts with an empty mempool without it
+                if repair {
+                    let cf = self.get_cf(CF_MEMPOOL_STATE)?;
+                    self.db.delete_cf(&cf, MEMPOOL_STATE_KEY.as_bytes())
+                        .map_err(|e| PclError::Storage(format!("Failed to delete mempool state: {}", e)))?;
+                }
+                report.issue(CF_MEMPOOL_STATE, MEMPOOL_STATE_KEY, format!("Does not deserialize: {}", e), repair);
+                return Ok(());
+            }
+        };
+
+        let mut locks: Vec<(String, String)> = mempool.locked_utxo.locked_utxos.values()
+            .map(|lock| (lock.utxo_id.clone(), lock.locked_by_tx.clone()))
+            .collect();
+        locks.sort();
+        let mut changed = false;
+        for (utxo_id, tx_id) in locks {
+            let problem = if finalized_ids.contains(&tx_id) || mempool.tx.finalized_transactions.contains_key(&tx_id) {
+                format!("UTXO {} is still locked by finalized transaction {}", utxo_id, tx_id)
+            } else if raw_ids.contains(&tx_id) || processing_ids.contains(&tx_id)
+                || mempool.raw_tx.transactions.contains_key(&tx_id) || mempool.processing_tx.transactions.contains_key(&tx_id) {
+                continue;
+            } else {
+                format!("UTXO {} is locked by unknown transaction {}", utxo_id, tx_id)
+            };
+            if repair {
+                mempool.unlock_utxo(&utxo_id)?;
+                changed = true;
+            }
+            report.issue(CF_MEMPOOL_STATE, MEMPOOL_STATE_KEY, problem, repair);
+        }
+        let locked: HashSet<String> = mempool.locked_utxo.locked_utxos.keys().cloned().collect();
+        for (tx_id, utxo_ids) in mempool.locked_utxo.tx_locks.iter_mut() {
+            let dangling = utxo_ids.iter().filter(|utxo_id| !locked.contains(*utxo_id)).count();
+            if dangling > 0 {
+                if repair {
+                    utxo_ids.retain(|utxo_id| locked.contains(utxo_id));
+                    changed = true;
+                }
+                report.issue(CF_MEMPOOL_STATE, MEMPOOL_STATE_KEY,
+                             format!("Transaction {} lists {} UTXOs that are not locked", tx_id, dangling), repair);
+            }
+        }
+        if changed {
+            mempool.locked_utxo.tx_locks.retain(|_, utxo_ids| !utxo_ids.is_empty());
+            self.store_mempool_state(&mempool)?;
+        }
+        Ok(())
+    }
+
+    fn write_repairs(&self, batch: WriteBatch, repair: bool) -> Result<()> {
+        if repair && !batch.is_empty() {
+            self.db.write(batch).map_err(|e| PclError::Storage(format!("Failed to write repairs: {}", e)))?;
+        }
+        Ok(())
+    }
+}
+
+// Why a decoded raw transaction cannot be trusted, if it cannot
+fn raw_transaction_problem(tx: &RawTransaction, key_tx_id: &str) -> Option<String> {
+    if tx.raw_tx_id != key_tx_id {
+        return Some(format!("Stored under another transaction's key (holds {})", tx.raw_tx_id));
+    }
+    match tx.tx_data.raw_tx_id() {
+        Ok(recomputed) if recomputed != tx.raw_tx_id => Some(format!("raw_tx_id does not match the content hash {}", recomputed)),
+        Err(e) => Some(format!("Content cannot be hashed: {}", e)),
+        Ok(_) if !tx.tx_data.validate_signature() => Some("User signature does not verify".to_string()),
+        Ok(_) => None,
+    }
+}
@@ -0,0 +1,482 @@
+// Storage migrations - record version tags and the schema upgrade pass run when a node opens its database
+//
+// Records are bincode, which is positional: a new or reordered field cannot be filled in by a serde
+// default. Changing a persisted struct means bumping its VERSION below, decoding the previous layout
+// in `upgrade`, and adding a schema step that calls `rewrite_outdated_records` so the whole database
+// moves forward at the next start. Reads upgrade old records on the fly until then.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use rocksdb::{IteratorMode, WriteBatch};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::auth::ApiKey;
+use crate::clock::ClockStatus;
+use crate::error::{PclError, Result};
+use crate::export::ExportRecord;
+use crate::address::Address;
+use crate::limits::{MAX_SIGNATURES, MAX_TX_IO, MAX_VALIDATION_ENTRIES};
+use crate::mempool::{
+    FinalizationClaim, FinalizedTransaction, LockedUtxoMempool, MempoolManager, ProcessingTxMempool, RawTxMempool,
+    TxMempool, UptimeMempool, UtxoEntry, ValidationTasksMempool, XmblIntegration,
+};
+use crate::multisig::{MultisigPolicy, PartialSignature};
+use crate::node::{Node, NodeRegistry};
+use crate::receipt::TransactionReceipt;
+use crate::reputation::ReputationRecord;
+use crate::peers::PeerRecord;
+use crate::history::{UtxoCheckpoint, UtxoDelta};
+use crate::staking::StakeEvent;
+use crate::transaction::{FeePayer, ProcessingTransaction, RawTransaction, TransactionData, ValidationTask};
+use crate::webhook::{Subscription, WebhookDelivery};
+use super::{
+    StorageManager, UptimeData, LeaderElectionState, ALL_COLUMN_FAMILIES, CF_NODES, CF_RAW_TRANSACTIONS,
+    CF_PROCESSING_TRANSACTIONS, CF_FINALIZED_TRANSACTIONS, CF_MEMPOOL_STATE, CF_UPTIME_DATA, CF_LEADER_ELECTION,
+    CF_NETWORK_STATE, CF_EXPORT_OUTBOX, CF_WEBHOOKS, CF_API_KEYS, CF_CONSENSUS_EVENTS, CF_RECEIPTS, CF_REPUTATION, CF_UTXO_HISTORY, NODE_IDENTITY_KEY, CLOCK_STATUS_KEY, RAW_TX_RECORD_PREFIX,
+    PEER_RECORD_PREFIX,
+};
+
+// Bumped whenever a step is added below
+pub const CURRENT_SCHEMA_VERSION: u32 = 6;
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+// Tagged records start with this marker and a big-endian u16 record version. Untagged records are
+// from before versioning and use version 1 layouts; every untagged type starts with a u64 length or
+// a 16 byte uuid, which would need an impossible length to collide with the marker.
+const RECORD_MAGIC: [u8; 4] = [0xfe, b'P', b'C', b'L'];
+const RECORD_HEADER_LEN: usize = RECORD_MAGIC.len() + 2;
+const LEGACY_RECORD_VERSION: u16 = 1;
+
+// A struct stored in RocksDB, with the version of its current layout
+pub trait Versioned: Serialize + DeserializeOwned {
+    const VERSION: u16;
+
+    // Decodes a record written with an older layout; nothing older exists until a VERSION is bumped
+    fn upgrade(version: u16, _payload: &[u8]) -> Result<Self> {
+        Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION)))
+    }
+}
+
+impl Versioned for Node { const VERSION: u16 = 1; }
+impl Versioned for NodeRegistry { const VERSION: u16 = 1; }
+impl Versioned for RawTransaction {
+    const VERSION: u16 = 2;
+
+    fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(bincode::deserialize::<RawTransactionV1>(payload)?.into()),
+            _ => Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION))),
+        }
+    }
+}
+impl Versioned for ProcessingTransaction {
+    const VERSION: u16 = 3;
+
+    fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(bincode::deserialize::<ProcessingTransactionV1>(payload)?.into()),
+            2 => Ok(bincode::deserialize::<ProcessingTransactionV2>(payload)?.into()),
+            _ => Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION))),
+        }
+    }
+}
+impl Versioned for FinalizedTransaction {
+    const VERSION: u16 = 2;
+
+    fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(bincode::deserialize::<FinalizedTransactionV1>(payload)?.into()),
+            _ => Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION))),
+        }
+    }
+}
+impl Versioned for MempoolManager {
+    const VERSION: u16 = 3;
+
+    fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(bincode::deserialize::<MempoolManagerV1>(payload)?.into()),
+            2 => Ok(bincode::deserialize::<MempoolManagerV2>(payload)?.into()),
+            _ => Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION))),
+        }
+    }
+}
+impl Versioned for UptimeData { const VERSION: u16 = 1; }
+impl Versioned for LeaderElectionState { const VERSION: u16 = 1; }
+impl Versioned for ExportRecord { const VERSION: u16 = 1; }
+impl Versioned for Subscription { const VERSION: u16 = 1; }
+impl Versioned for WebhookDelivery { const VERSION: u16 = 1; }
+impl Versioned for ApiKey { const VERSION: u16 = 1; }
+impl Versioned for StakeEvent { const VERSION: u16 = 1; }
+impl Versioned for TransactionReceipt { const VERSION: u16 = 1; }
+impl Versioned for ClockStatus { const VERSION: u16 = 1; }
+impl Versioned for ReputationRecord {
+    const VERSION: u16 = 2;
+
+    fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(bincode::deserialize::<ReputationRecordV1>(payload)?.into()),
+            _ => Err(PclError::Storage(format!("No upgrade from record version {} to {}", version, Self::VERSION))),
+        }
+    }
+}
+impl Versioned for PeerRecord { const VERSION: u16 = 1; }
+impl Versioned for UtxoCheckpoint { const VERSION: u16 = 1; }
+impl Versioned for UtxoDelta { const VERSION: u16 = 1; }
+// The node's identity record: its node entry and secret key
+impl Versioned for (Node, [u8; 32]) { const VERSION: u16 = 1; }
+
+// Layouts from before transactions carried an expiry. Processing entries also went without their
+// broadcasting epoch until version 2.
+#[derive(Deserialize)]
+struct TransactionDataV1 {
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_TX_IO>")]
+    to: Vec<(Address, f64)>,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_TX_IO>")]
+    from: Vec<(String, f64)>,
+    user: Address,
+    sig: Option<String>,
+    stake: f64,
+    fee: f64,
+    change: Option<f64>,
+    timestamp: DateTime<Utc>,
+    leader: Option<String>,
+    nonce: u64,
+    multisig: Option<MultisigPolicy>,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_SIGNATURES>")]
+    signatures: Vec<PartialSignature>,
+    fee_payer: Option<FeePayer>,
+}
+
+impl From<TransactionDataV1> for TransactionData {
+    fn from(v1: TransactionDataV1) -> Self {
+        Self {
+            to: v1.to,
+            from: v1.from,
+            user: v1.user,
+            sig: v1.sig,
+            stake: v1.stake,
+            fee: v1.fee,
+            change: v1.change,
+            timestamp: v1.timestamp,
+            leader: v1.leader,
+            nonce: v1.nonce,
+            multisig: v1.multisig,
+            signatures: v1.signatures,
+            fee_payer: v1.fee_payer,
+            expires_at: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawTransactionV1 {
+    raw_tx_id: String,
+    tx_data: TransactionDataV1,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_VALIDATION_ENTRIES>")]
+    validation_timestamps: Vec<DateTime<Utc>>,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_VALIDATION_ENTRIES>")]
+    validation_tasks: Vec<ValidationTask>,
+    tx_timestamp: DateTime<Utc>,
+}
+
+impl From<RawTransactionV1> for RawTransaction {
+    fn from(v1: RawTransactionV1) -> Self {
+        Self {
+            raw_tx_id: v1.raw_tx_id,
+            tx_data: v1.tx_data.into(),
+            validation_timestamps: v1.validation_timestamps,
+            validation_tasks: v1.validation_tasks,
+            tx_timestamp: v1.tx_timestamp,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProcessingTransactionV1 {
+    tx_id: String,
+    tx_data: TransactionDataV1,
+    sig: String,
+    leader: String,
+    timestamp: DateTime<Utc>,
+}
+
+impl From<ProcessingTransactionV1> for ProcessingTransaction {
+    fn from(v1: ProcessingTransactionV1) -> Self {
+        Self { tx_id: v1.tx_id, tx_data: v1.tx_data.into(), sig: v1.sig, leader: v1.leader, timestamp: v1.timestamp, epoch: 0 }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProcessingTransactionV2 {
+    tx_id: String,
+    tx_data: TransactionDataV1,
+    sig: String,
+    leader: String,
+    timestamp: DateTime<Utc>,
+    epoch: u64,
+}
+
+impl From<ProcessingTransactionV2> for ProcessingTransaction {
+    fn from(v2: ProcessingTransactionV2) -> Self {
+        Self { tx_id: v2.tx_id, tx_data: v2.tx_data.into(), sig: v2.sig, leader: v2.leader, timestamp: v2.timestamp, epoch: v2.epoch }
+    }
+}
+
+#[derive(Deserialize)]
+struct FinalizedTransactionV1 {
+    tx_id: String,
+    tx_data: TransactionDataV1,
+    xmbl_cubic_root: u8,
+    validator_signature: String,
+    finalized_at: DateTime<Utc>,
+}
+
+impl From<FinalizedTransactionV1> for FinalizedTransaction {
+    fn from(v1: FinalizedTransactionV1) -> Self {
+        Self {
+            tx_id: v1.tx_id,
+            tx_data: v1.tx_data.into(),
+            xmbl_cubic_root: v1.xmbl_cubic_root,
+            validator_signature: v1.validator_signature,
+            finalized_at: v1.finalized_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawTxMempoolV1 {
+    transactions: HashMap<String, RawTransactionV1>,
+    hash_to_tx: HashMap<String, String>,
+    tx_by_user: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ProcessingTxMempoolV1<P> {
+    transactions: HashMap<String, P>,
+    timestamp_averages: HashMap<String, DateTime<Utc>>,
+    signatures: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TxMempoolV1 {
+    finalized_transactions: HashMap<String, FinalizedTransactionV1>,
+    xmbl_integrated: HashMap<String, XmblIntegration>,
+    utxo_pool: HashMap<String, UtxoEntry>,
+    finalization_claims: HashMap<String, FinalizationClaim>,
+}
+
+// Mempool state versions 1 and 2 differ only in their processing entries
+#[derive(Deserialize)]
+struct LegacyMempoolManager<P> {
+    raw_tx: RawTxMempoolV1,
+    validation_tasks: ValidationTasksMempool,
+    locked_utxo: LockedUtxoMempool,
+    processing_tx: ProcessingTxMempoolV1<P>,
+    tx: TxMempoolV1,
+    uptime: UptimeMempool,
+}
+
+type MempoolManagerV1 = LegacyMempoolManager<ProcessingTransactionV1>;
+type MempoolManagerV2 = LegacyMempoolManager<ProcessingTransactionV2>;
+
+impl<P: Into<ProcessingTransaction>> From<LegacyMempoolManager<P>> for MempoolManager {
+    fn from(old: LegacyMempoolManager<P>) -> Self {
+        Self {
+            raw_tx: RawTxMempool {
+                transactions: old.raw_tx.transactions.into_iter().map(|(id, tx)| (id, tx.into())).collect(),
+                hash_to_tx: old.raw_tx.hash_to_tx,
+                tx_by_user: old.raw_tx.tx_by_user,
+            },
+            validation_tasks: old.validation_tasks,
+            locked_utxo: old.locked_utxo,
+            processing_tx: ProcessingTxMempool {
+                transactions: old.processing_tx.transactions.into_iter().map(|(id, entry)| (id, entry.into())).collect(),
+                timestamp_averages: old.processing_tx.timestamp_averages,
+                signatures: old.processing_tx.signatures,
+            },
+            tx: TxMempool {
+                finalized_transactions: old.tx.finalized_transactions.into_iter().map(|(id, tx)| (id, tx.into())).collect(),
+                xmbl_integrated: old.tx.xmbl_integrated,
+                utxo_pool: old.tx.utxo_pool,
+                finalization_claims: old.tx.finalization_claims,
+            },
+            uptime: old.uptime,
+        }
+    }
+}
+
+// Version 1 reputation records, from before submission rate limit violations were counted
+#[derive(Deserialize)]
+struct ReputationRecordV1 {
+    identity: String,
+    tasks_on_time: u64,
+    tasks_late: u64,
+    tasks_missed: u64,
+    invalid_signatures: u64,
+    invalidated_submissions: u64,
+    updated_at: u64,
+}
+
+impl From<ReputationRecordV1> for ReputationRecord {
+    fn from(v1: ReputationRecordV1) -> Self {
+        Self {
+            identity: v1.identity,
+            tasks_on_time: v1.tasks_on_time,
+            tasks_late: v1.tasks_late,
+            tasks_missed: v1.tasks_missed,
+            invalid_signatures: v1.invalid_signatures,
+            invalidated_submissions: v1.invalidated_submissions,
+            rate_limit_violations: 0,
+            updated_at: v1.updated_at,
+        }
+    }
+}
+
+pub fn encode_record<T: Versioned>(value: &T) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(RECORD_HEADER_LEN + 256);
+    bytes.extend_from_slice(&RECORD_MAGIC);
+    bytes.extend_from_slice(&T::VERSION.to_be_bytes());
+    bincode::serialize_into(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+// Record version and bincode payload; untagged bytes are a legacy version 1 record
+pub fn record_version(bytes: &[u8]) -> (u16, &[u8]) {
+    if bytes.len() >= RECORD_HEADER_LEN && bytes[..RECORD_MAGIC.len()] == RECORD_MAGIC {
+        let version = u16::from_be_bytes([bytes[RECORD_MAGIC.len()], bytes[RECORD_MAGIC.len() + 1]]);
+        (version, &bytes[RECORD_HEADER_LEN..])
+    } else {
+        (LEGACY_RECORD_VERSION, bytes)
+    }
+}
+
+pub fn decode_record<T: Versioned>(bytes: &[u8]) -> Result<T> {
+    let (version, payload) = record_version(bytes);
+    match version.cmp(&T::VERSION) {
+        std::cmp::Ordering::Equal => Ok(bincode::deserialize(payload)?),
+        std::cmp::Ordering::Less => T::upgrade(version, payload),
+        std::cmp::Ordering::Greater => Err(PclError::Storage(format!(
+            "Record version {} was written by a newer node (this node reads up to {})", version, T::VERSION
+        ))),
+    }
+}
+
+// Some(re-encoded bytes) if the record is untagged or older than the current layout
+fn rewrite<T: Versioned>(bytes: &[u8]) -> Result<Option<Vec<u8>>> {
+    let tagged = bytes.starts_with(&RECORD_MAGIC);
+    if tagged && record_version(bytes).0 == T::VERSION {
+        return Ok(None);
+    }
+    Ok(Some(encode_record(&decode_record::<T>(bytes)?)?))
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub steps: Vec<String>, // description and record count of each step that ran
+}
+
+type MigrationStep = fn(&StorageManager) -> Result<usize>;
+
+// Step n takes the database from schema version n - 1 to n
+const MIGRATIONS: [(&str, MigrationStep); CURRENT_SCHEMA_VERSION as usize] = [
+    ("per-leader raw transaction keys", StorageManager::migrate_raw_transaction_layout),
+    ("version-tagged records", rewrite_outdated_records),
+    ("processing entry epochs", rewrite_outdated_records),
+    ("reputation rate limit violations", rewrite_outdated_records),
+    ("webhook due index", StorageManager::rebuild_webhook_due_index),
+    ("transaction expiry", rewrite_outdated_records),
+];
+
+impl StorageManager {
+    // No key means a database from before schema versioning, or a fresh one; both start at 0
+    pub fn schema_version(&self) -> Result<u32> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        match self.db.get_cf(cf, SCHEMA_VERSION_KEY.as_bytes())? {
+            Some(value) => {
+                let bytes: [u8; 4] = value.as_slice().try_into()
+                    .map_err(|_| PclError::Storage(format!("Malformed schema version ({} bytes)", value.len())))?;
+                Ok(u32::from_be_bytes(bytes))
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn store_schema_version(&self, version: u32) -> Result<()> {
+        let cf = self.get_cf(CF_NETWORK_STATE)?;
+        self.db.put_cf(cf, SCHEMA_VERSION_KEY.as_bytes(), version.to_be_bytes())
+            .map_err(|e| PclError::Storage(format!("Failed to store schema version: {}", e)))
+    }
+
+    // Brings the database up to CURRENT_SCHEMA_VERSION. The version is stored after each step, so an
+    // interrupted run resumes at the step that failed; every step is safe to repeat.
+    pub fn run_migrations(&self) -> Result<MigrationReport> {
+        let from_version = self.schema_version()?;
+        if from_version > CURRENT_SCHEMA_VERSION {
+            return Err(PclError::Storage(format!(
+                "Database schema version {} is newer than this node supports ({})", from_version, CURRENT_SCHEMA_VERSION
+            )));
+        }
+
+        let mut report = MigrationReport { from_version, to_version: from_version, steps: Vec::new() };
+        for (index, (description, step)) in MIGRATIONS.iter().enumerate().skip(from_version as usize) {
+            let records = step(self)?;
+            let version = index as u32 + 1;
+            self.store_schema_version(version)?;
+            log::info!("Storage schema migrated to version {}: {} ({} records)", version, description, records);
+            report.to_version = version;
+            report.steps.push(format!("{} ({} records)", description, records));
+        }
+        Ok(report)
+    }
+}
+
+// Re-encodes every record that is untagged or behind its type's VERSION
+pub fn rewrite_outdated_records(storage: &StorageManager) -> Result<usize> {
+    let mut batch = WriteBatch::default();
+    let mut rewritten = 0;
+
+    for cf_name in ALL_COLUMN_FAMILIES {
+        let cf = storage.get_cf(cf_name)?;
+        for item in storage.db.iterator_cf(cf, IteratorMode::Start) {
+            let (key, value) = item?;
+            let updated = match (cf_name, key.as_ref()) {
+                (CF_NODES, b"node_registry") => rewrite::<NodeRegistry>(&value)?,
+                (CF_NODES, _) => rewrite::<Node>(&value)?,
+                (CF_RAW_TRANSACTIONS, key) if key.starts_with(RAW_TX_RECORD_PREFIX.as_bytes()) => rewrite::<RawTransaction>(&value)?,
+                (CF_PROCESSING_TRANSACTIONS, _) => rewrite::<ProcessingTransaction>(&value)?,
+                (CF_FINALIZED_TRANSACTIONS, _) => rewrite::<FinalizedTransaction>(&value)?,
+                (CF_MEMPOOL_STATE, _) => rewrite::<MempoolManager>(&value)?,
+                (CF_UPTIME_DATA, _) => rewrite::<UptimeData>(&value)?,
+                (CF_LEADER_ELECTION, _) => rewrite::<LeaderElectionState>(&value)?,
+                (CF_NETWORK_STATE, key) if key == NODE_IDENTITY_KEY.as_bytes() => rewrite::<(Node, [u8; 32])>(&value)?,
+                (CF_NETWORK_STATE, key) if key == CLOCK_STATUS_KEY.as_bytes() => rewrite::<ClockStatus>(&value)?,
+                (CF_NETWORK_STATE, key) if key.starts_with(PEER_RECORD_PREFIX.as_bytes()) => rewrite::<PeerRecord>(&value)?,
+                (CF_EXPORT_OUTBOX, _) => rewrite::<ExportRecord>(&value)?,
+                (CF_WEBHOOKS, key) if key.starts_with(b"subscription:") => rewrite::<Subscription>(&value)?,
+                (CF_WEBHOOKS, key) if key.starts_with(b"delivery:") => rewrite::<WebhookDelivery>(&value)?,
+                (CF_API_KEYS, _) => rewrite::<ApiKey>(&value)?,
+                (CF_CONSENSUS_EVENTS, _) => rewrite::<StakeEvent>(&value)?,
+                (CF_RECEIPTS, _) => rewrite::<TransactionReceipt>(&value)?,
+                (CF_REPUTATION, _) => rewrite::<ReputationRecord>(&value)?,
+                (CF_UTXO_HISTORY, [b'c', ..]) => rewrite::<UtxoCheckpoint>(&value)?,
+                (CF_UTXO_HISTORY, _) => rewrite::<UtxoDelta>(&value)?,
+                // Raw tx index entries, finalization sequence entries, the export cursor, the schema version and the consensus
+                // snapshot (JSON with its own version field) are not bincode records
+                _ => None,
+            };
+            if let Some(bytes) = updated {
+                batch.put_cf(cf, &key, bytes);
+                rewritten += 1;
+            }
+        }
+    }
+
+    if rewritten > 0 {
+        storage.db.write(batch)
+            .map_err(|e| PclError::Storage(format!("Failed to rewrite records: {}", e)))?;
+    }
+    Ok(rewritten)
+}
@@ -0,0 +1,91 @@
+// Submission rate module - per-user limits on transaction submissions at the consensus layer
+//
+// IP and API key limits do not stop one keypair from spamming through many connections or peers.
+// Each submitting user gets a token bucket holding up to user_submission_burst submissions, refilled
+// at user_submissions_per_sec. A submission that finds the bucket empty is refused with the time
+// until the next token and counted as a violation, which callers record against the user's
+// reputation. A sustained rate of 0 turns the limit off.
+
+use std::collections::HashMap;
+use crate::config::ConsensusConfig;
+use crate::error::{PclError, Result};
+
+// Past this many tracked users, buckets that have refilled completely are dropped
+const MAX_TRACKED_USERS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: u64, // ms since epoch
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmissionRateLimiter {
+    burst: f64,
+    per_sec: f64,
+    buckets: HashMap<String, Bucket>,
+    violations: HashMap<String, u64>,
+}
+
+impl SubmissionRateLimiter {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            burst: config.user_submission_burst as f64,
+            per_sec: config.user_submissions_per_sec,
+            buckets: HashMap::new(),
+            violations: HashMap::new(),
+        }
+    }
+
+    // Takes new limits, e.g. after a governance change; buckets keep their tokens up to the new burst
+    pub fn set_limits(&mut self, config: &ConsensusConfig) {
+        self.burst = config.user_submission_burst as f64;
+        self.per_sec = config.user_submissions_per_sec;
+        for bucket in self.buckets.values_mut() {
+            bucket.tokens = bucket.tokens.min(self.burst);
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.per_sec > 0.0
+    }
+
+    // Spends one of user's tokens, or refuses the submission if none are left
+    pub fn check(&mut self, user: &str, now: u64) -> Result<()> {
+        if !self.enabled() {
+            return Ok(());
+        }
+        if self.buckets.len() >= MAX_TRACKED_USERS {
+            self.prune(now);
+        }
+        let (burst, per_sec) = (self.burst, self.per_sec);
+        let bucket = self.buckets.entry(user.to_string())
+            .or_insert(Bucket { tokens: burst, refilled_at: now });
+        let elapsed_secs = now.saturating_sub(bucket.refilled_at) as f64 / 1000.0;
+        bucket.tokens = (bucket.tokens + elapsed_secs * per_sec).min(burst);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let retry_after_ms = ((1.0 - bucket.tokens) / per_sec * 1000.0).ceil() as u64;
+        *self.violations.entry(user.to_string()).or_insert(0) += 1;
+        Err(PclError::SubmissionRateLimited(format!(
+            "{} is limited to {} submissions per second with bursts of {}; retry in {} ms",
+            user, per_sec, burst, retry_after_ms
+        )))
+    }
+
+    // Refused submissions per user since the node started
+    pub fn violations(&self, user: &str) -> u64 {
+        self.violations.get(user).copied().unwrap_or(0)
+    }
+
+    fn prune(&mut self, now: u64) {
+        let (burst, per_sec) = (self.burst, self.per_sec);
+        self.buckets.retain(|_, bucket| {
+            bucket.tokens + now.saturating_sub(bucket.refilled_at) as f64 / 1000.0 * per_sec < burst
+        });
+    }
+}
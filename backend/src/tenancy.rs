@@ -0,0 +1,38 @@
+// Tenancy module - namespaced ledgers hosted by one node
+//
+// Every test environment used to need a node of its own. A node can instead host named tenants next
+// to its default ledger: each tenant has its own balances, mempools and faucet, is addressed by
+// prefixing an API path with /tenants/{id}, and has its state saved under its own storage key prefix.
+// The peer-to-peer layer and the leader set belong to the node and are shared by every tenant.
+
+use crate::error::{PclError, Result};
+
+pub const TENANT_PATH_PREFIX: &str = "/tenants/";
+const MAX_TENANT_ID_LEN: usize = 32;
+
+// Lowercase letters, digits, '-' and '_', so an id can go into paths and storage keys as it is
+pub fn validate_tenant_id(id: &str) -> Result<()> {
+    let valid = !id.is_empty()
+        && id.len() <= MAX_TENANT_ID_LEN
+        && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(PclError::Config(format!(
+            "tenant id '{}' must be 1-{} lowercase letters, digits, '-' or '_'", id, MAX_TENANT_ID_LEN
+        )));
+    }
+    Ok(())
+}
+
+// "/tenants/staging/balance/x" -> ("staging", "/balance/x"); None for a path outside any tenant
+pub fn split_tenant_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix(TENANT_PATH_PREFIX)?;
+    match rest.find('/') {
+        Some(slash) => Some((&rest[..slash], &rest[slash..])),
+        None => Some((rest, "/")),
+    }
+}
+
+// A tenant's copy of a storage key, e.g. "tenant/staging/consensus_state"
+pub fn tenant_key(tenant: &str, key: &str) -> String {
+    format!("tenant/{}/{}", tenant, key)
+}
@@ -0,0 +1,112 @@
+// TLS module - rustls termination for the node API, with ALPN to pick the protocol per connection
+
+#[cfg(feature = "tls")]
+use std::path::Path;
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+use crate::config::TlsConfig;
+#[cfg(feature = "tls")]
+use crate::error::{PclError, Result};
+
+pub const ALPN_HTTP1: &[u8] = b"http/1.1";
+pub const ALPN_HTTP2: &[u8] = b"h2";
+
+// Protocols that can be served on the API port. gRPC runs over HTTP/2, so it is selected by "h2".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationProtocol {
+    Http1,
+    Grpc,
+}
+
+impl ApplicationProtocol {
+    pub fn alpn_id(&self) -> &'static [u8] {
+        match self {
+            ApplicationProtocol::Http1 => ALPN_HTTP1,
+            ApplicationProtocol::Grpc => ALPN_HTTP2,
+        }
+    }
+
+    // Clients that skip ALPN get the JSON API
+    pub fn from_alpn(negotiated: Option<&[u8]>) -> Self {
+        match negotiated {
+            Some(protocol) if protocol == ALPN_HTTP2 => ApplicationProtocol::Grpc,
+            _ => ApplicationProtocol::Http1,
+        }
+    }
+}
+
+// Builds the acceptor for the API listener. Only the protocols actually served are advertised, so a
+// client never negotiates h2 on a node that has nothing listening for it.
+#[cfg(feature = "tls")]
+pub fn build_acceptor(config: &TlsConfig, protocols: &[ApplicationProtocol]) -> Result<tokio_rustls::TlsAcceptor> {
+    use tokio_rustls::rustls;
+
+    let cert_path = config.cert_path();
+    let key_path = config.key_path();
+    if config.self_signed && !(cert_path.exists() && key_path.exists()) {
+        generate_self_signed(&cert_path, &key_path)?;
+    }
+
+    let certs = load_certs(&cert_path)?;
+    let key = load_private_key(&key_path)?;
+
+    let mut server_config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| PclError::Config(format!("Invalid TLS certificate or key: {}", e)))?;
+    // Preference order is the server's: put gRPC first so HTTP/2 clients that can speak it get it
+    let mut ordered = protocols.to_vec();
+    ordered.sort_by_key(|protocol| *protocol != ApplicationProtocol::Grpc);
+    server_config.alpn_protocols = ordered.iter().map(|protocol| protocol.alpn_id().to_vec()).collect();
+
+    Ok(tokio_rustls::TlsAcceptor::from(Arc::new(server_config)))
+}
+
+// Devnet convenience: a certificate for localhost/127.0.0.1, written once and reused across restarts
+#[cfg(feature = "tls")]
+pub fn generate_self_signed(cert_path: &Path, key_path: &Path) -> Result<()> {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string(), "127.0.0.1".to_string()])
+        .map_err(|e| PclError::Config(format!("Failed to generate self-signed certificate: {}", e)))?;
+    let cert_pem = cert.serialize_pem()
+        .map_err(|e| PclError::Config(format!("Failed to encode self-signed certificate: {}", e)))?;
+
+    for path in [cert_path, key_path] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(cert_path, cert_pem)?;
+    std::fs::write(key_path, cert.serialize_private_key_pem())?;
+    log::warn!("Generated self-signed TLS certificate at {:?}; do not use it outside a devnet", cert_path);
+    Ok(())
+}
+
+#[cfg(feature = "tls")]
+fn load_certs(path: &Path) -> Result<Vec<tokio_rustls::rustls::Certificate>> {
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path)
+        .map_err(|e| PclError::Config(format!("Cannot open TLS certificate {:?}: {}", path, e)))?);
+    let certs = rustls_pemfile::certs(&mut reader)
+        .map_err(|e| PclError::Config(format!("Cannot parse TLS certificate {:?}: {}", path, e)))?;
+    if certs.is_empty() {
+        return Err(PclError::Config(format!("No certificates found in {:?}", path)));
+    }
+    Ok(certs.into_iter().map(tokio_rustls::rustls::Certificate).collect())
+}
+
+#[cfg(feature = "tls")]
+fn load_private_key(path: &Path) -> Result<tokio_rustls::rustls::PrivateKey> {
+    use rustls_pemfile::Item;
+
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path)
+        .map_err(|e| PclError::Config(format!("Cannot open TLS key {:?}: {}", path, e)))?);
+    let items = rustls_pemfile::read_all(&mut reader)
+        .map_err(|e| PclError::Config(format!("Cannot parse TLS key {:?}: {}", path, e)))?;
+    items.into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(tokio_rustls::rustls::PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| PclError::Config(format!("No private key found in {:?}", path)))
+}
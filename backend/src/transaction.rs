@@ -3,14 +3,24 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use crate::crypto::{verify_data_signature, NodeKeypair};
+use crate::address::Address;
+use crate::crypto::{address_matches_public_key, calculate_digital_root, hash_data, verify_data_signature, NodeKeypair};
+use crate::multisig::{decode_public_key, decode_signature, MultisigPolicy, PartialSignature};
+use crate::limits::{MAX_TX_IO, MAX_SIGNATURES, MAX_VALIDATION_ENTRIES};
 use ed25519_dalek::{VerifyingKey, Signature};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Weight units on top of the encoded size: every input or output is a UTXO lookup and write, every
+// signature a verification
+pub const WEIGHT_PER_IO: u64 = 64;
+pub const WEIGHT_PER_SIGNATURE: u64 = 128;
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct TransactionData {
-    pub to: Vec<(String, f64)>,  // (address, amount) pairs
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_TX_IO>")]
+    pub to: Vec<(Address, f64)>, // (address, amount) pairs
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_TX_IO>")]
     pub from: Vec<(String, f64)>, // (utxo_id, amount) pairs
-    pub user: String,            // sender address
+    pub user: Address,           // sender address
     pub sig: Option<String>,     // signature (signed message without sig property)
     pub stake: f64,             // validation stake
     pub fee: f64,               // transaction fee
@@ -18,13 +28,63 @@ pub struct TransactionData {
     pub timestamp: DateTime<Utc>,
     pub leader: Option<String>,  // leader node IP
     pub nonce: u64,             // transaction nonce
+    #[serde(default)]
+    pub multisig: Option<MultisigPolicy>, // set when `user` is a multisig address
+    #[serde(default, deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_SIGNATURES>")]
+    pub signatures: Vec<PartialSignature>, // cosigner signatures for multisig spends
+    #[serde(default)]
+    pub fee_payer: Option<FeePayer>, // sponsor covering the fee instead of the sender
+    // Leaders refuse to start validating after this and drop the transaction; signed when set
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// JSON leaves out an unset expiry so signing bytes, transaction ids and digital roots stay what they
+// were before it existed; bincode is positional and always carries it
+impl Serialize for TransactionData {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let omit_expiry = serializer.is_human_readable() && self.expires_at.is_none();
+        let mut state = serializer.serialize_struct("TransactionData", if omit_expiry { 13 } else { 14 })?;
+        state.serialize_field("to", &self.to)?;
+        state.serialize_field("from", &self.from)?;
+        state.serialize_field("user", &self.user)?;
+        state.serialize_field("sig", &self.sig)?;
+        state.serialize_field("stake", &self.stake)?;
+        state.serialize_field("fee", &self.fee)?;
+        state.serialize_field("change", &self.change)?;
+        state.serialize_field("timestamp", &self.timestamp)?;
+        state.serialize_field("leader", &self.leader)?;
+        state.serialize_field("nonce", &self.nonce)?;
+        state.serialize_field("multisig", &self.multisig)?;
+        state.serialize_field("signatures", &self.signatures)?;
+        state.serialize_field("fee_payer", &self.fee_payer)?;
+        if omit_expiry {
+            state.skip_field("expires_at")?;
+        } else {
+            state.serialize_field("expires_at", &self.expires_at)?;
+        }
+        state.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeePayer {
+    pub address: Address,
+    pub public_key: String,        // hex encoded sponsor key
+    pub signature: Option<String>, // sponsor signature over the tx hash
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawTransaction {
     pub raw_tx_id: String,
     pub tx_data: TransactionData,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_VALIDATION_ENTRIES>")]
     pub validation_timestamps: Vec<DateTime<Utc>>,
+    #[serde(deserialize_with = "crate::limits::bounded_vec::<_, _, MAX_VALIDATION_ENTRIES>")]
     pub validation_tasks: Vec<ValidationTask>,
     pub tx_timestamp: DateTime<Utc>,
 }
@@ -39,7 +99,7 @@ pub struct ValidationTask {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ValidationTaskType {
     SignatureValidation,
     SpendingPowerValidation,
@@ -48,6 +108,18 @@ pub enum ValidationTaskType {
     FinalValidation,
 }
 
+impl ValidationTaskType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationTaskType::SignatureValidation => "signature_validation",
+            ValidationTaskType::SpendingPowerValidation => "spending_power_validation",
+            ValidationTaskType::TimestampValidation => "timestamp_validation",
+            ValidationTaskType::MathValidation => "math_validation",
+            ValidationTaskType::FinalValidation => "final_validation",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessingTransaction {
     pub tx_id: String,
@@ -55,13 +127,16 @@ pub struct ProcessingTransaction {
     pub sig: String,            // leader signature
     pub leader: String,         // leader node ID
     pub timestamp: DateTime<Utc>, // averaged timestamp
+    // Broadcasting epoch the leader processed it in, signed with the entry; 0 when not recorded
+    #[serde(default)]
+    pub epoch: u64,
 }
 
 impl TransactionData {
     pub fn new(
-        to: Vec<(String, f64)>,
+        to: Vec<(Address, f64)>,
         from: Vec<(String, f64)>,
-        user: String,
+        user: Address,
         stake: f64,
         fee: f64,
     ) -> Self {
@@ -80,6 +155,10 @@ impl TransactionData {
             timestamp: Utc::now(),
             leader: None,
             nonce: 0,
+            multisig: None,
+            signatures: Vec::new(),
+            fee_payer: None,
+            expires_at: None,
         }
     }
     
@@ -95,10 +174,23 @@ impl TransactionData {
         self.nonce = nonce;
     }
     
+    pub fn set_expiry(&mut self, expires_at: DateTime<Utc>) {
+        self.expires_at = Some(expires_at);
+    }
+    
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+    
+    // Fee charged to the sender's inputs; zero when a sponsor pays it
+    pub fn sender_fee(&self) -> f64 {
+        if self.fee_payer.is_some() { 0.0 } else { self.fee }
+    }
+    
     pub fn validate_amounts(&self) -> bool {
         let total_from: f64 = self.from.iter().map(|(_, amount)| amount).sum();
         let total_to: f64 = self.to.iter().map(|(_, amount)| amount).sum();
-        let total_out = total_to + self.stake + self.fee;
+        let total_out = total_to + self.stake + self.sender_fee();
         
         if let Some(change) = self.change {
             total_from >= total_out + change
@@ -107,7 +199,81 @@ impl TransactionData {
         }
     }
     
+    // Cost of carrying and validating the transaction: its JSON size in bytes plus per input, output
+    // and signature charges. Fees scale with it and validation caps it at MAX_TX_WEIGHT.
+    pub fn weight(&self) -> u64 {
+        let size = serde_json::to_vec(self).map(|bytes| bytes.len()).unwrap_or(0);
+        let signatures = self.sig.iter().count()
+            + self.signatures.len()
+            + self.fee_payer.iter().filter(|payer| payer.signature.is_some()).count();
+        weight_of(size, self.from.len() + self.to.len(), signatures)
+    }
+    
+    pub fn set_multisig(&mut self, policy: MultisigPolicy) {
+        self.user = policy.address();
+        self.multisig = Some(policy);
+    }
+    
+    // Sponsoring moves the fee off the sender's inputs, so change is recomputed
+    pub fn set_fee_payer(&mut self, public_key: &VerifyingKey) {
+        self.fee_payer = Some(FeePayer {
+            address: Address::from_public_key(public_key),
+            public_key: hex::encode(public_key.to_bytes()),
+            signature: None,
+        });
+        
+        let change = self.get_total_input() - self.get_total_amount() - self.stake;
+        self.change = if change > 0.0 { Some(change) } else { None };
+    }
+    
+    // Bytes covered by user, cosigner and sponsor signatures: the transaction without any signatures attached
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut tx_for_signing = self.clone();
+        tx_for_signing.sig = None;
+        tx_for_signing.signatures.clear();
+        if let Some(fee_payer) = tx_for_signing.fee_payer.as_mut() {
+            fee_payer.signature = None;
+        }
+        
+        serde_json::to_vec(&tx_for_signing)
+            .map_err(|e| format!("Failed to serialize transaction: {}", e))
+    }
+    
+    pub fn tx_hash(&self) -> Result<Vec<u8>, String> {
+        Ok(hash_data(&self.signing_bytes()?))
+    }
+    
+    // Content-derived id; signatures are excluded so it is fixed before anyone signs
+    pub fn raw_tx_id(&self) -> Result<String, String> {
+        Ok(format!("tx_{}", hex::encode(self.tx_hash()?)))
+    }
+    
+    // XMBL cubic root over the transaction as submitted, signature included
+    pub fn digital_root(&self) -> Result<u8, String> {
+        let tx_bytes = serde_json::to_vec(self)
+            .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+        Ok(calculate_digital_root(&tx_bytes))
+    }
+    
+    pub fn sign_as_user(&mut self, keypair: &NodeKeypair) -> Result<(), String> {
+        if !address_matches_public_key(self.user.as_str(), &keypair.public_key()) {
+            return Err("Signing key does not match the sender address".to_string());
+        }
+        self.sig = Some(hex::encode(keypair.sign_data(&self.signing_bytes()?).to_bytes()));
+        Ok(())
+    }
+    
     pub fn validate_signature(&self) -> bool {
+        // A sponsored transaction needs the sponsor's signature as well as the sender's
+        if self.fee_payer.is_some() && !self.verify_fee_payer() {
+            return false;
+        }
+        
+        // Multisig spends are authorised by the cosigner set rather than a single user signature
+        if self.multisig.is_some() {
+            return self.verify_multisig();
+        }
+        
         // REAL IMPLEMENTATION: Verify user signature on transaction data
         match &self.sig {
             Some(sig_str) => {
@@ -138,11 +304,7 @@ impl TransactionData {
         log::info!("✍️  REAL TRANSACTION SIGNING: Signing transaction for user {}", self.user);
         
         // Create message to sign (serialize transaction data without signature)
-        let mut tx_for_signing = self.clone();
-        tx_for_signing.sig = None;
-        
-        let tx_bytes = serde_json::to_vec(&tx_for_signing)
-            .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+        let tx_bytes = self.signing_bytes()?;
         
         // Sign the transaction data
         let signature = keypair.sign_data(&tx_bytes);
@@ -178,10 +340,7 @@ impl TransactionData {
                 };
                 
                 // Create message to verify (serialize transaction data without signature)
-                let mut tx_for_verification = self.clone();
-                tx_for_verification.sig = None;
-                
-                let tx_bytes = match serde_json::to_vec(&tx_for_verification) {
+                let tx_bytes = match self.signing_bytes() {
                     Ok(bytes) => bytes,
                     Err(_) => {
                         log::warn!("❌ SERIALIZATION ERROR: Failed to serialize transaction for verification");
@@ -212,6 +371,125 @@ impl TransactionData {
         }
     }
     
+    pub fn sign_multisig_partial(&self, keypair: &NodeKeypair) -> Result<PartialSignature, String> {
+        let policy = self.multisig.as_ref()
+            .ok_or_else(|| "Transaction is not a multisig spend".to_string())?;
+        let partial = PartialSignature::sign(keypair, &self.signing_bytes()?);
+        
+        if !policy.contains_key(&partial.public_key) {
+            return Err("Signing key is not part of the multisig policy".to_string());
+        }
+        
+        log::info!("✍️  MULTISIG PARTIAL SIGNATURE: Cosigner {} signed for {}", partial.public_key, self.user);
+        Ok(partial)
+    }
+    
+    pub fn add_partial_signature(&mut self, partial: PartialSignature) {
+        if !self.signatures.iter().any(|s| s.public_key == partial.public_key) {
+            self.signatures.push(partial);
+        }
+    }
+    
+    pub fn verify_multisig(&self) -> bool {
+        let policy = match &self.multisig {
+            Some(policy) => policy,
+            None => {
+                log::warn!("❌ NO MULTISIG POLICY: Transaction for user {} is not a multisig spend", self.user);
+                return false;
+            }
+        };
+        
+        log::info!("🔐 MULTISIG VALIDATION: Checking {}-of-{} signatures for {}", policy.threshold, policy.public_keys.len(), self.user);
+        
+        if policy.address() != self.user {
+            log::warn!("❌ MULTISIG ADDRESS MISMATCH: Policy does not derive address {}", self.user);
+            return false;
+        }
+        
+        let tx_bytes = match self.signing_bytes() {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::warn!("❌ SERIALIZATION ERROR: {}", e);
+                return false;
+            }
+        };
+        
+        match policy.verify(&tx_bytes, &self.signatures) {
+            Ok(true) => {
+                log::info!("✅ MULTISIG VALID: Threshold met for {}", self.user);
+                true
+            }
+            Ok(false) => {
+                log::warn!("❌ MULTISIG INVALID: Not enough valid signatures for {}", self.user);
+                false
+            }
+            Err(e) => {
+                log::warn!("❌ MULTISIG VERIFICATION ERROR: {}", e);
+                false
+            }
+        }
+    }
+    
+    pub fn sign_as_fee_payer(&mut self, keypair: &NodeKeypair) -> Result<(), String> {
+        let tx_hash = self.tx_hash()?;
+        let fee_payer = self.fee_payer.as_mut()
+            .ok_or_else(|| "Transaction has no fee payer".to_string())?;
+        
+        if fee_payer.public_key != hex::encode(keypair.public_key().to_bytes()) {
+            return Err("Signing key does not match the fee payer".to_string());
+        }
+        
+        fee_payer.signature = Some(hex::encode(keypair.sign_data(&tx_hash).to_bytes()));
+        log::info!("✍️  FEE SPONSORED: {} signed to pay fee of {} for {}", fee_payer.address, self.fee, self.user);
+        Ok(())
+    }
+    
+    pub fn verify_fee_payer(&self) -> bool {
+        let fee_payer = match &self.fee_payer {
+            Some(fee_payer) => fee_payer,
+            None => return false,
+        };
+        
+        log::info!("🔐 FEE PAYER VALIDATION: Checking sponsor signature from {}", fee_payer.address);
+        
+        let signature = match fee_payer.signature.as_deref().map(decode_signature) {
+            Some(Ok(signature)) => signature,
+            _ => {
+                log::warn!("❌ NO FEE PAYER SIGNATURE: Sponsor {} has not signed", fee_payer.address);
+                return false;
+            }
+        };
+        let public_key = match decode_public_key(&fee_payer.public_key) {
+            Ok(key) => key,
+            Err(e) => {
+                log::warn!("❌ INVALID FEE PAYER KEY: {}", e);
+                return false;
+            }
+        };
+        if !address_matches_public_key(fee_payer.address.as_str(), &public_key) {
+            log::warn!("❌ FEE PAYER MISMATCH: {} is not the address of the sponsor key", fee_payer.address);
+            return false;
+        }
+        let tx_hash = match self.tx_hash() {
+            Ok(hash) => hash,
+            Err(e) => {
+                log::warn!("❌ SERIALIZATION ERROR: {}", e);
+                return false;
+            }
+        };
+        
+        match verify_data_signature(&tx_hash, &signature, &public_key) {
+            Ok(true) => {
+                log::info!("✅ FEE PAYER VALID: {} sponsors this transaction", fee_payer.address);
+                true
+            }
+            _ => {
+                log::warn!("❌ FEE PAYER INVALID: Sponsor signature verification failed for {}", fee_payer.address);
+                false
+            }
+        }
+    }
+    
     pub fn get_total_amount(&self) -> f64 {
         self.to.iter().map(|(_, amount)| amount).sum()
     }
@@ -236,6 +514,206 @@ impl TransactionData {
     }
 }
 
+// Message signed for an HTTP submission: the JSON body with the sender's, cosigner and sponsor
+// signatures removed, so the parties can sign in any order
+pub fn submission_signing_bytes(body: &serde_json::Value) -> crate::error::Result<Vec<u8>> {
+    let mut unsigned = body.clone();
+    if let Some(obj) = unsigned.as_object_mut() {
+        obj.remove("sig");
+        obj.remove("public_key");
+        obj.remove("signatures");
+    }
+    if let Some(fee_payer) = unsigned.get_mut("fee_payer").and_then(|f| f.as_object_mut()) {
+        fee_payer.remove("signature");
+    }
+    Ok(serde_json::to_vec(&unsigned)?)
+}
+
+pub fn submission_hash(body: &serde_json::Value) -> crate::error::Result<Vec<u8>> {
+    Ok(hash_data(&submission_signing_bytes(body)?))
+}
+
+// Fields a prepared submission keeps; anything else in the request is dropped
+const SUBMISSION_FIELDS: [&str; 10] = ["to", "from", "amount", "user", "stake", "fee", "multisig", "fee_payer", "replaces", "expires_at"];
+
+// Canonical unsigned form of an HTTP submission, as returned by POST /transaction/prepare: known
+// fields only, with submit_transaction's defaults for amount and stake filled in and every signature
+// removed. serde_json objects keep their keys sorted, so it serializes to the same bytes anywhere.
+pub fn prepare_submission(body: &serde_json::Value) -> crate::error::Result<serde_json::Value> {
+    let fields = body.as_object()
+        .ok_or_else(|| crate::error::PclError::Validation("Transaction must be a JSON object".to_string()))?;
+    let mut unsigned: serde_json::Map<String, serde_json::Value> = fields.iter()
+        .filter(|(key, _)| SUBMISSION_FIELDS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    unsigned.entry("amount").or_insert(serde_json::json!(1.0));
+    unsigned.entry("stake").or_insert(serde_json::json!(0.2));
+    if let Some(fee_payer) = unsigned.get_mut("fee_payer").and_then(|f| f.as_object_mut()) {
+        fee_payer.remove("signature");
+    }
+    Ok(serde_json::Value::Object(unsigned))
+}
+
+// Adds the sender's public key and signature over submission_hash; done offline by the wallet
+pub fn sign_submission(body: &serde_json::Value, keypair: &NodeKeypair) -> crate::error::Result<serde_json::Value> {
+    if !address_matches_public_key(body["user"].as_str().unwrap_or(""), &keypair.public_key()) {
+        return Err(crate::error::PclError::SignatureVerification(
+            "Secret key does not belong to the transaction's user".to_string()
+        ));
+    }
+    let signature = keypair.sign_data(&submission_hash(body)?);
+    Ok(attach_submission_signature(body, &keypair.public_key(), &signature))
+}
+
+// Adds a signature made elsewhere, e.g. on a hardware wallet; check it with verify_submission_signature
+pub fn attach_submission_signature(
+    body: &serde_json::Value,
+    public_key: &VerifyingKey,
+    signature: &Signature,
+) -> serde_json::Value {
+    let mut signed = body.clone();
+    signed["public_key"] = serde_json::Value::String(hex::encode(public_key.to_bytes()));
+    signed["sig"] = serde_json::Value::String(hex::encode(signature.to_bytes()));
+    signed
+}
+
+// The sender's signature on a single-signer submission: its key must be the user's and sign submission_hash
+pub fn verify_submission_signature(body: &serde_json::Value) -> crate::error::Result<()> {
+    let (Some(public_key), Some(signature)) = (body["public_key"].as_str(), body["sig"].as_str()) else {
+        return Err(crate::error::PclError::SignatureVerification(
+            "Transaction is not signed: public_key and sig are required".to_string()
+        ));
+    };
+    let public_key = decode_public_key(public_key)?;
+    if !address_matches_public_key(body["user"].as_str().unwrap_or(""), &public_key) {
+        return Err(crate::error::PclError::SignatureVerification(
+            "Signing key does not match the transaction's user".to_string()
+        ));
+    }
+    if !verify_data_signature(&submission_hash(body)?, &decode_signature(signature)?, &public_key)? {
+        return Err(crate::error::PclError::SignatureVerification("Transaction signature is invalid".to_string()));
+    }
+    Ok(())
+}
+
+// Signed by the submitting user to withdraw a raw transaction before its leader starts processing it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionCancellation {
+    pub raw_tx_id: String,
+    pub user: String,
+    pub public_key: String, // hex encoded
+    pub signature: String,  // hex encoded, over signing_bytes
+}
+
+impl TransactionCancellation {
+    pub fn signing_bytes(raw_tx_id: &str, user: &str) -> Vec<u8> {
+        format!("pcl-cancel:{}:{}", raw_tx_id, user).into_bytes()
+    }
+
+    pub fn sign(raw_tx_id: &str, keypair: &NodeKeypair) -> Self {
+        let user = keypair.address();
+        let signature = keypair.sign_data(&Self::signing_bytes(raw_tx_id, &user));
+        Self {
+            raw_tx_id: raw_tx_id.to_string(),
+            user,
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    // Only the transaction's own user may cancel it: the key must be theirs and sign this raw_tx_id
+    pub fn verify(&self, tx_user: &str) -> crate::error::Result<()> {
+        let public_key = decode_public_key(&self.public_key)?;
+        if self.user != tx_user || !address_matches_public_key(tx_user, &public_key) {
+            return Err(crate::error::PclError::SignatureVerification(format!(
+                "Only {} can cancel transaction {}", tx_user, self.raw_tx_id
+            )));
+        }
+        let bytes = Self::signing_bytes(&self.raw_tx_id, &self.user);
+        if !verify_data_signature(&bytes, &decode_signature(&self.signature)?, &public_key)? {
+            return Err(crate::error::PclError::SignatureVerification(format!(
+                "Cancellation signature for {} is invalid", self.raw_tx_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+// TransactionData::weight for an HTTP submission body, which names a single input and output
+pub fn submission_weight(body: &serde_json::Value) -> u64 {
+    let size = serde_json::to_vec(body).map(|bytes| bytes.len()).unwrap_or(0);
+    let signatures = body["sig"].is_string() as usize
+        + body["signatures"].as_array().map_or(0, |signatures| signatures.len())
+        + body["fee_payer"]["signature"].is_string() as usize;
+    weight_of(size, 2, signatures)
+}
+
+fn weight_of(size: usize, inputs_and_outputs: usize, signatures: usize) -> u64 {
+    size as u64 + inputs_and_outputs as u64 * WEIGHT_PER_IO + signatures as u64 * WEIGHT_PER_SIGNATURE
+}
+
+// Task ids are derived from the work they describe, so the same assignment offered twice gets the
+// same id and can be detected as a duplicate. The raw_tx_id prefix lets a transaction's tasks be
+// removed together.
+pub fn derive_task_id(raw_tx_id: &str, task_type: &str, leader_id: &str, assignee: &str) -> String {
+    let mut preimage = Vec::new();
+    for part in [raw_tx_id, task_type, leader_id, assignee] {
+        preimage.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        preimage.extend_from_slice(part.as_bytes());
+    }
+    format!("{}_task_{}", raw_tx_id, hex::encode(&hash_data(&preimage)[..16]))
+}
+
+// A wallet's signed answer to a validation task it was assigned. The signature covers the task,
+// the transaction it checked, the verdict and the completion time, so it cannot be replayed elsewhere.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserValidationTaskCompletion {
+    pub task_id: String,
+    pub raw_tx_id: String,
+    pub validator: Address, // the wallet the task was assigned to
+    pub public_key: String, // hex encoded wallet key
+    pub result: bool,       // whether the wallet's checks passed
+    pub completed_at: u64,  // ms since epoch
+    pub signature: String,  // hex encoded
+}
+
+impl UserValidationTaskCompletion {
+    pub fn sign(task_id: &str, raw_tx_id: &str, result: bool, completed_at: u64, keypair: &NodeKeypair) -> Self {
+        let mut completion = Self {
+            task_id: task_id.to_string(),
+            raw_tx_id: raw_tx_id.to_string(),
+            validator: Address::from_public_key(&keypair.public_key()),
+            public_key: hex::encode(keypair.public_key().to_bytes()),
+            result,
+            completed_at,
+            signature: String::new(),
+        };
+        completion.signature = hex::encode(keypair.sign_data(&completion.signing_bytes()).to_bytes());
+        completion
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        format!("pcl-task-completion:{}:{}:{}:{}", self.task_id, self.raw_tx_id, self.result, self.completed_at).into_bytes()
+    }
+
+    // The key must belong to the validator address and have signed exactly these fields
+    pub fn verify(&self) -> crate::error::Result<()> {
+        let public_key = decode_public_key(&self.public_key)?;
+        if !address_matches_public_key(self.validator.as_str(), &public_key) {
+            return Err(crate::error::PclError::SignatureVerification(
+                "Completion key does not match the validator address".to_string()
+            ));
+        }
+        let signature = decode_signature(&self.signature)?;
+        if !verify_data_signature(&self.signing_bytes(), &signature, &public_key)? {
+            return Err(crate::error::PclError::SignatureVerification(format!(
+                "Invalid completion signature for task {}", self.task_id
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl RawTransaction {
     pub fn new(raw_tx_id: String, tx_data: TransactionData) -> Self {
         Self {
@@ -297,6 +775,12 @@ impl ValidationTask {
             completed_at: None,
         }
     }
+
+    // A task for `assignee` to check `raw_tx_id`, with its id from derive_task_id
+    pub fn for_transaction(raw_tx_id: &str, leader_id: &str, task_type: ValidationTaskType, assignee: &str) -> Self {
+        let task_id = derive_task_id(raw_tx_id, task_type.as_str(), leader_id, assignee);
+        Self::new(task_id, leader_id.to_string(), task_type)
+    }
     
     pub fn complete(&mut self) {
         self.complete = true;
@@ -304,8 +788,9 @@ impl ValidationTask {
     }
     
     pub fn is_expired(&self, timeout_minutes: i64) -> bool {
+        // assigned_at can come from a peer; a far-future value must not overflow the addition
         let timeout = chrono::Duration::minutes(timeout_minutes);
-        Utc::now() > self.assigned_at + timeout
+        self.assigned_at.checked_add_signed(timeout).is_some_and(|deadline| Utc::now() > deadline)
     }
 }
 
@@ -317,6 +802,7 @@ impl ProcessingTransaction {
             sig: leader_sig,
             leader: leader_id,
             timestamp: Utc::now(),
+            epoch: 0,
         }
     }
     
@@ -332,6 +818,46 @@ impl ProcessingTransaction {
             sig: leader_sig,
             leader: leader_id,
             timestamp: avg_timestamp,
+            epoch: 0,
         })
     }
+    
+    // JSON that is signed and hashed. An entry without an epoch leaves the field out, so it signs and
+    // hashes exactly as entries from before epochs were recorded. epoch is the last field.
+    fn canonical_json(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = serde_json::to_vec(self)
+            .map_err(|e| format!("Failed to serialize processing transaction: {}", e))?;
+        const UNRECORDED_EPOCH: &[u8] = b",\"epoch\":0}";
+        if bytes.ends_with(UNRECORDED_EPOCH) {
+            bytes.truncate(bytes.len() - UNRECORDED_EPOCH.len());
+            bytes.push(b'}');
+        }
+        Ok(bytes)
+    }
+    
+    // The leader signs the whole entry except the signature field
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut unsigned = self.clone();
+        unsigned.sig = String::new();
+        unsigned.canonical_json()
+    }
+    
+    pub fn sign(&mut self, keypair: &NodeKeypair) -> Result<(), String> {
+        let signature = keypair.sign_data(&self.signing_bytes()?);
+        self.sig = hex::encode(signature.to_bytes());
+        Ok(())
+    }
+    
+    // Id of the finalized transaction: the hash of the whole signed entry, so it commits to the leader's signature
+    pub fn final_tx_id(&self) -> Result<String, String> {
+        let entry_bytes = self.canonical_json()?;
+        Ok(format!("tx_{}", hex::encode(hash_data(&entry_bytes))))
+    }
+    
+    pub fn verify_leader_signature(&self, public_key: &VerifyingKey) -> bool {
+        let (Ok(bytes), Ok(signature)) = (self.signing_bytes(), decode_signature(&self.sig)) else {
+            return false;
+        };
+        verify_data_signature(&bytes, &signature, public_key).unwrap_or(false)
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,194 @@
+// Transport module - how NetworkManager hands encoded messages to peers and receives theirs
+//
+// NetworkManager decides what to send and to whom; a Transport only moves bytes and reports what
+// arrived. No transport talks to the libp2p swarm yet: a NetworkManager without one keeps published
+// messages in its history as before, and Libp2pStub only counts sends for code that needs a
+// Transport value. MemoryTransport connects NetworkManagers in one process through a shared
+// MemoryNetwork that holds every sent message until the test delivers or drops it, in whatever order
+// it chooses, so runs of NetworkManager and ConsensusManager together are reproducible.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::error::{PclError, Result};
+use crate::network::{NetworkEvent, PeerId};
+
+pub trait Transport: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // Hands one encoded message to the transport for a peer; delivery is up to the transport
+    fn send(&mut self, to: &PeerId, bytes: &[u8]) -> Result<()>;
+
+    // Events that arrived since the last call, oldest first
+    fn poll_events(&mut self) -> Vec<NetworkEvent>;
+}
+
+// Not connected to the swarm: sends are counted and dropped, nothing is ever received
+#[derive(Debug, Default)]
+pub struct Libp2pStub {
+    pub sent: u64, // messages that would have gone to the swarm
+}
+
+impl Libp2pStub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Transport for Libp2pStub {
+    fn name(&self) -> &'static str {
+        "libp2p-stub"
+    }
+
+    fn send(&mut self, to: &PeerId, bytes: &[u8]) -> Result<()> {
+        self.sent += 1;
+        log::trace!("Dropped {} bytes for {}: the libp2p transport is a stub", bytes.len(), to);
+        Ok(())
+    }
+
+    fn poll_events(&mut self) -> Vec<NetworkEvent> {
+        Vec::new()
+    }
+}
+
+// A sent message the test has not delivered or dropped yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InFlight {
+    pub id: u64, // send order across the whole network
+    pub from: PeerId,
+    pub to: PeerId,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Default)]
+struct MemoryNetworkState {
+    next_id: u64,
+    in_flight: BTreeMap<u64, InFlight>,
+    inboxes: HashMap<PeerId, VecDeque<NetworkEvent>>,
+    blocked: HashSet<(PeerId, PeerId)>, // (from, to) links whose sends are dropped
+    dropped: u64,
+}
+
+// Shared by every MemoryTransport of one test network; clones refer to the same network
+#[derive(Clone, Default)]
+pub struct MemoryNetwork {
+    state: Arc<Mutex<MemoryNetworkState>>,
+}
+
+impl MemoryNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn endpoint(&self, peer_id: &str) -> MemoryTransport {
+        self.state().inboxes.entry(peer_id.to_string()).or_default();
+        MemoryTransport { peer_id: peer_id.to_string(), network: self.clone() }
+    }
+
+    // Both sides see PeerConnected the next time they poll
+    pub fn connect(&self, a: &str, b: &str) {
+        let mut state = self.state();
+        for (local, remote) in [(a, b), (b, a)] {
+            state.inboxes.entry(local.to_string()).or_default().push_back(NetworkEvent::PeerConnected(remote.to_string()));
+        }
+    }
+
+    pub fn disconnect(&self, a: &str, b: &str) {
+        let mut state = self.state();
+        for (local, remote) in [(a, b), (b, a)] {
+            state.inboxes.entry(local.to_string()).or_default().push_back(NetworkEvent::PeerDisconnected(remote.to_string()));
+        }
+    }
+
+    // Messages waiting for delivery, in send order
+    pub fn in_flight(&self) -> Vec<InFlight> {
+        self.state().in_flight.values().cloned().collect()
+    }
+
+    // Moves one message into its recipient's inbox; recipients see messages in delivery order
+    pub fn deliver(&self, id: u64) -> bool {
+        let mut state = self.state();
+        let Some(message) = state.in_flight.remove(&id) else {
+            return false;
+        };
+        let event = NetworkEvent::PeerMessage(message.from, String::from_utf8_lossy(&message.bytes).into_owned());
+        state.inboxes.entry(message.to).or_default().push_back(event);
+        true
+    }
+
+    pub fn deliver_next(&self) -> bool {
+        let next = self.state().in_flight.keys().next().copied();
+        next.is_some_and(|id| self.deliver(id))
+    }
+
+    // Delivers everything in flight in send order; returns how many were delivered
+    pub fn deliver_all(&self) -> usize {
+        let mut delivered = 0;
+        while self.deliver_next() {
+            delivered += 1;
+        }
+        delivered
+    }
+
+    pub fn drop_message(&self, id: u64) -> bool {
+        let mut state = self.state();
+        let removed = state.in_flight.remove(&id).is_some();
+        if removed {
+            state.dropped += 1;
+        }
+        removed
+    }
+
+    // Drops every later send from `from` to `to` until unblocked; one direction only
+    pub fn block(&self, from: &str, to: &str) {
+        self.state().blocked.insert((from.to_string(), to.to_string()));
+    }
+
+    pub fn unblock(&self, from: &str, to: &str) {
+        self.state().blocked.remove(&(from.to_string(), to.to_string()));
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.state().dropped
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemoryNetworkState> {
+        // A test that panicked mid-delivery leaves nothing half-done worth refusing over
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub struct MemoryTransport {
+    peer_id: PeerId,
+    network: MemoryNetwork,
+}
+
+impl MemoryTransport {
+    pub fn peer_id(&self) -> &str {
+        &self.peer_id
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn send(&mut self, to: &PeerId, bytes: &[u8]) -> Result<()> {
+        let mut state = self.network.state();
+        if !state.inboxes.contains_key(to) {
+            return Err(PclError::Network(format!("No peer {} on the in-memory network", to)));
+        }
+        if state.blocked.contains(&(self.peer_id.clone(), to.clone())) {
+            state.dropped += 1;
+            return Ok(());
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+        state.in_flight.insert(id, InFlight { id, from: self.peer_id.clone(), to: to.clone(), bytes: bytes.to_vec() });
+        Ok(())
+    }
+
+    fn poll_events(&mut self) -> Vec<NetworkEvent> {
+        self.network.state().inboxes.get_mut(&self.peer_id).map(|inbox| inbox.drain(..).collect()).unwrap_or_default()
+    }
+}
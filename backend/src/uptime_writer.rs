@@ -0,0 +1,70 @@
+// Uptime writer module - buffers uptime records and writes them to storage in batches
+//
+// Every pulse updates its node's uptime record, and with hundreds of peers one put per pulse was most
+// of the write load on RocksDB. Updates now land in an in-memory overlay that keeps only the latest
+// record per node, and a periodic flush (and one at shutdown) writes the overlay in a single
+// WriteBatch. Reads check the overlay first, so callers never see a record older than the last update.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::error::Result;
+use crate::storage::{StorageManager, UptimeData};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UptimeWriteStats {
+    pub pending: usize,
+    pub updates: u64,   // records passed to record()
+    pub coalesced: u64, // updates replaced by a newer one before they were flushed
+    pub batches: u64,
+    pub written: u64,   // records written across all batches
+}
+
+pub struct UptimeWriteBuffer {
+    storage: Arc<StorageManager>,
+    pending: HashMap<String, UptimeData>, // node id -> latest unflushed record
+    stats: UptimeWriteStats,
+}
+
+impl UptimeWriteBuffer {
+    pub fn new(storage: Arc<StorageManager>) -> Self {
+        Self { storage, pending: HashMap::new(), stats: UptimeWriteStats::default() }
+    }
+
+    pub fn record(&mut self, uptime_data: UptimeData) {
+        self.stats.updates += 1;
+        if self.pending.insert(uptime_data.node_id.clone(), uptime_data).is_some() {
+            self.stats.coalesced += 1;
+        }
+    }
+
+    // The buffered record if there is one, otherwise the stored one
+    pub fn get(&self, node_id: &str) -> Result<Option<UptimeData>> {
+        match self.pending.get(node_id) {
+            Some(uptime_data) => Ok(Some(uptime_data.clone())),
+            None => self.storage.load_uptime_data(node_id),
+        }
+    }
+
+    // Writes every buffered record in one batch; on failure they stay buffered for the next flush
+    pub fn flush(&mut self) -> Result<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let records: Vec<UptimeData> = self.pending.values().cloned().collect();
+        self.storage.store_uptime_batch(&records)?;
+        self.pending.clear();
+        self.stats.batches += 1;
+        self.stats.written += records.len() as u64;
+        log::debug!("Flushed {} uptime records", records.len());
+        Ok(records.len())
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn stats(&self) -> UptimeWriteStats {
+        UptimeWriteStats { pending: self.pending.len(), ..self.stats.clone() }
+    }
+}
@@ -0,0 +1,128 @@
+// UTXO module - moving value through the unspent output set
+//
+// Balances are never stored on their own: an owner's balance is the sum of its unspent outputs, so
+// there is no second copy that could drift from the set. Transfers pick inputs owned by the payer
+// (a preferred output first, then in id order), spend all of them and create the recipient output
+// "{tx_id}:0", the sender's change "{tx_id}:1" and, when a sponsor pays the fee, its change "{tx_id}:2".
+
+use std::collections::HashMap;
+use crate::error::{PclError, Result};
+use crate::mempool::UtxoEntry;
+
+// Tolerance for floating point dust when comparing UTXO sums
+pub const UTXO_EPSILON: f64 = 1e-9;
+
+pub fn create_utxo(utxos: &mut HashMap<String, UtxoEntry>, utxo_id: &str, owner: &str, amount: f64) {
+    utxos.insert(utxo_id.to_string(), UtxoEntry {
+        utxo_id: utxo_id.to_string(),
+        amount,
+        owner: owner.to_string(),
+        created_at: chrono::Utc::now(),
+        spent: false,
+    });
+}
+
+// Picks unspent UTXOs owned by `owner` until `required` is covered, starting with the preferred one
+pub fn select_utxos(utxos: &HashMap<String, UtxoEntry>, owner: &str, preferred: Option<&str>, required: f64) -> Result<(Vec<String>, f64)> {
+    let mut candidates: Vec<&UtxoEntry> = utxos.values()
+        .filter(|utxo| !utxo.spent && utxo.owner == owner)
+        .collect();
+    candidates.sort_by(|a, b| {
+        let a_preferred = Some(a.utxo_id.as_str()) == preferred;
+        let b_preferred = Some(b.utxo_id.as_str()) == preferred;
+        b_preferred.cmp(&a_preferred).then_with(|| a.utxo_id.cmp(&b.utxo_id))
+    });
+
+    let mut selected = Vec::new();
+    let mut total = 0.0;
+    for utxo in candidates {
+        if total >= required - UTXO_EPSILON {
+            break;
+        }
+        selected.push(utxo.utxo_id.clone());
+        total += utxo.amount;
+    }
+
+    if total < required - UTXO_EPSILON {
+        return Err(PclError::Transaction(format!("{} has {} XMBL spendable, needs {}", owner, total, required)));
+    }
+    Ok((selected, total))
+}
+
+pub fn spend(utxos: &mut HashMap<String, UtxoEntry>, utxo_ids: &[String]) {
+    for utxo_id in utxo_ids {
+        if let Some(utxo) = utxos.get_mut(utxo_id) {
+            utxo.spent = true;
+        }
+    }
+}
+
+// Spends enough of the payer's outputs to cover `amount`, paying the rest back as `change_id`.
+// Nothing is spent when the payer cannot cover it.
+pub fn charge(utxos: &mut HashMap<String, UtxoEntry>, payer: &str, preferred: Option<&str>, amount: f64, change_id: &str) -> Result<f64> {
+    let (inputs, total) = select_utxos(utxos, payer, preferred, amount)?;
+    spend(utxos, &inputs);
+    let change = total - amount;
+    if change > UTXO_EPSILON {
+        create_utxo(utxos, change_id, payer, change);
+    }
+    Ok(change.max(0.0))
+}
+
+// One transfer out of a sender's outputs. The fee is burned from the sender's inputs, or from the
+// sponsor's when one pays it.
+#[derive(Debug, Clone, Copy)]
+pub struct Transfer<'a> {
+    pub sender: &'a str,
+    pub preferred: Option<&'a str>, // sender output to spend first
+    pub recipient: &'a str,
+    pub amount: f64,
+    pub fee: f64,
+    pub sponsor: Option<&'a str>,
+}
+
+// Either both sender and sponsor are covered and the transfer is applied, or nothing changes. A
+// sender cannot sponsor itself: both selections would count the same outputs.
+pub fn apply_transfer(utxos: &mut HashMap<String, UtxoEntry>, tx_id: &str, transfer: &Transfer) -> Result<()> {
+    if transfer.sponsor == Some(transfer.sender) {
+        return Err(PclError::Transaction(format!("{} cannot sponsor the fee of its own transfer", transfer.sender)));
+    }
+    let sender_fee = if transfer.sponsor.is_some() { 0.0 } else { transfer.fee };
+    let (sender_inputs, sender_total) = select_utxos(utxos, transfer.sender, transfer.preferred, transfer.amount + sender_fee)?;
+    let sponsor_inputs = match transfer.sponsor {
+        Some(sponsor) => Some((sponsor, select_utxos(utxos, sponsor, None, transfer.fee)?)),
+        None => None,
+    };
+
+    spend(utxos, &sender_inputs);
+    create_utxo(utxos, &format!("{}:0", tx_id), transfer.recipient, transfer.amount);
+    let change = sender_total - transfer.amount - sender_fee;
+    if change > UTXO_EPSILON {
+        create_utxo(utxos, &format!("{}:1", tx_id), transfer.sender, change);
+    }
+
+    if let Some((sponsor, (inputs, total))) = sponsor_inputs {
+        spend(utxos, &inputs);
+        let sponsor_change = total - transfer.fee;
+        if sponsor_change > UTXO_EPSILON {
+            create_utxo(utxos, &format!("{}:2", tx_id), sponsor, sponsor_change);
+        }
+    }
+    Ok(())
+}
+
+// Unspent total per owner
+pub fn balances(utxos: &HashMap<String, UtxoEntry>) -> HashMap<String, f64> {
+    let mut balances: HashMap<String, f64> = HashMap::new();
+    for utxo in utxos.values().filter(|utxo| !utxo.spent) {
+        *balances.entry(utxo.owner.clone()).or_insert(0.0) += utxo.amount;
+    }
+    balances
+}
+
+pub fn balance_of(utxos: &HashMap<String, UtxoEntry>, owner: &str) -> f64 {
+    utxos.values()
+        .filter(|utxo| !utxo.spent && utxo.owner == owner)
+        .map(|utxo| utxo.amount)
+        .sum()
+}
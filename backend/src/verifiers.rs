@@ -0,0 +1,170 @@
+// Verifiers module - the check behind each validation task type, looked up by name
+//
+// What a validator does for a task used to be a match over ValidationTaskType in the workflow, so a
+// new task type meant new arms wherever tasks were handled. Each task type now has a TaskVerifier
+// registered under its name in the validation engine's registry. A new type only needs a verifier
+// registered here, and a task whose type has no verifier is refused instead of passing by default.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use crate::error::{PclError, Result};
+use crate::transaction::{ProcessingTransaction, RawTransaction, ValidationTaskType};
+
+// What a validator is given to check: the raw transaction, and for the later task types the
+// processing entry its leader produced and the digital root claimed for it
+#[derive(Debug, Clone)]
+pub struct TaskSubject<'a> {
+    pub raw_tx: &'a RawTransaction,
+    pub processing_tx: Option<&'a ProcessingTransaction>,
+    pub digital_root: Option<u8>,
+    pub now: DateTime<Utc>,
+}
+
+impl<'a> TaskSubject<'a> {
+    pub fn new(raw_tx: &'a RawTransaction) -> Self {
+        Self { raw_tx, processing_tx: None, digital_root: None, now: Utc::now() }
+    }
+
+    fn processing_tx(&self, task_type: &str) -> Result<&'a ProcessingTransaction> {
+        self.processing_tx.ok_or_else(|| PclError::Validation(format!(
+            "A {} task for {} needs the processing entry", task_type, self.raw_tx.raw_tx_id
+        )))
+    }
+}
+
+// Ok(false) means the transaction failed the check; an error means the task could not be checked
+pub trait TaskVerifier: Send + Sync {
+    fn task_type(&self) -> &str;
+    fn verify(&self, subject: &TaskSubject) -> Result<bool>;
+}
+
+pub struct SignatureVerifier;
+
+impl TaskVerifier for SignatureVerifier {
+    fn task_type(&self) -> &str {
+        ValidationTaskType::SignatureValidation.as_str()
+    }
+
+    fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+        Ok(subject.raw_tx.tx_data.validate_signature())
+    }
+}
+
+pub struct SpendingPowerVerifier;
+
+impl TaskVerifier for SpendingPowerVerifier {
+    fn task_type(&self) -> &str {
+        ValidationTaskType::SpendingPowerValidation.as_str()
+    }
+
+    fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+        Ok(subject.raw_tx.tx_data.validate_amounts())
+    }
+}
+
+// The transaction was made in the last hour
+pub struct TimestampVerifier;
+
+impl TaskVerifier for TimestampVerifier {
+    fn task_type(&self) -> &str {
+        ValidationTaskType::TimestampValidation.as_str()
+    }
+
+    fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+        let age = subject.now.signed_duration_since(subject.raw_tx.tx_data.timestamp);
+        Ok(age.num_hours() < 1 && age.num_seconds() > 0)
+    }
+}
+
+// The leader's processing entry carries the average of the validation timestamps it collected
+pub struct TimestampMathVerifier;
+
+impl TaskVerifier for TimestampMathVerifier {
+    fn task_type(&self) -> &str {
+        ValidationTaskType::MathValidation.as_str()
+    }
+
+    fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+        let processing_tx = subject.processing_tx(self.task_type())?;
+        let Some(average) = subject.raw_tx.get_average_timestamp() else {
+            return Ok(false);
+        };
+        Ok(processing_tx.tx_id == subject.raw_tx.raw_tx_id
+            && processing_tx.timestamp == average
+            && processing_tx.tx_data.timestamp == average)
+    }
+}
+
+// The digital root claimed for the finalized transaction is the one its processing entry gives
+pub struct DigitalRootVerifier;
+
+impl TaskVerifier for DigitalRootVerifier {
+    fn task_type(&self) -> &str {
+        ValidationTaskType::FinalValidation.as_str()
+    }
+
+    fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+        let processing_tx = subject.processing_tx(self.task_type())?;
+        let claimed = subject.digital_root.ok_or_else(|| PclError::Validation(format!(
+            "A {} task for {} needs the claimed digital root", self.task_type(), subject.raw_tx.raw_tx_id
+        )))?;
+        let digital_root = processing_tx.tx_data.digital_root().map_err(PclError::Serialization)?;
+        Ok(digital_root == claimed)
+    }
+}
+
+#[derive(Clone)]
+pub struct TaskVerifierRegistry {
+    verifiers: BTreeMap<String, Arc<dyn TaskVerifier>>,
+}
+
+impl fmt::Debug for TaskVerifierRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskVerifierRegistry").field("task_types", &self.task_types()).finish()
+    }
+}
+
+impl Default for TaskVerifierRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskVerifierRegistry {
+    // Starts with a verifier for every built-in task type
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(SignatureVerifier));
+        registry.register(Arc::new(SpendingPowerVerifier));
+        registry.register(Arc::new(TimestampVerifier));
+        registry.register(Arc::new(TimestampMathVerifier));
+        registry.register(Arc::new(DigitalRootVerifier));
+        registry
+    }
+
+    pub fn empty() -> Self {
+        Self { verifiers: BTreeMap::new() }
+    }
+
+    // Replaces any verifier already registered for the same task type
+    pub fn register(&mut self, verifier: Arc<dyn TaskVerifier>) {
+        self.verifiers.insert(verifier.task_type().to_string(), verifier);
+    }
+
+    pub fn supports(&self, task_type: &str) -> bool {
+        self.verifiers.contains_key(task_type)
+    }
+
+    pub fn task_types(&self) -> Vec<&str> {
+        self.verifiers.keys().map(String::as_str).collect()
+    }
+
+    pub fn verify(&self, task_type: &str, subject: &TaskSubject) -> Result<bool> {
+        let verifier = self.verifiers.get(task_type).ok_or_else(|| {
+            PclError::Validation(format!("No verifier is registered for task type {}", task_type))
+        })?;
+        verifier.verify(subject)
+    }
+}
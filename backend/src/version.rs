@@ -0,0 +1,57 @@
+// Version module - the software version and build a node runs, and how far apart two versions are
+//
+// Debugging a network with mixed versions needs to know who runs what. The crate version, git commit
+// and build time are embedded at build time (see build.rs), sent to peers at handshake and reported by
+// GET /status. Nodes more than one minor version apart may disagree on wire formats or rules, so a
+// handshake from such a peer is logged as a warning; it is not refused.
+
+use serde::{Deserialize, Serialize};
+
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_SHA: &str = env!("VERGEN_GIT_SHA");
+pub const BUILD_TIMESTAMP: &str = env!("VERGEN_BUILD_TIMESTAMP");
+
+// Peers further apart than this many minor versions (or on another major) get a warning
+pub const MAX_MINOR_VERSION_SKEW: u64 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,         // crate version, semver
+    pub git_sha: String,         // short commit hash
+    pub build_timestamp: String, // RFC 3339
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: CRATE_VERSION.to_string(),
+            git_sha: GIT_SHA.to_string(),
+            build_timestamp: BUILD_TIMESTAMP.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.version, self.git_sha)
+    }
+}
+
+// (major, minor, patch) of a semver string; pre-release and build suffixes are ignored
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v').split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
+}
+
+// True when the two versions differ in major or by more than MAX_MINOR_VERSION_SKEW minor versions.
+// Versions that do not parse are not compared.
+pub fn versions_too_far_apart(local: &str, remote: &str) -> bool {
+    match (parse_version(local), parse_version(remote)) {
+        (Some((local_major, local_minor, _)), Some((remote_major, remote_minor, _))) => {
+            local_major != remote_major || local_minor.abs_diff(remote_minor) > MAX_MINOR_VERSION_SKEW
+        }
+        _ => false,
+    }
+}
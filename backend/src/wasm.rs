@@ -0,0 +1,41 @@
+// Wasm module - transaction preparation and signing for the browser wallet
+//
+// Built for wasm32-unknown-unknown with --no-default-features --features wasm, where only the core
+// types are compiled. The exports are thin wrappers over prepare_submission, sign_submission and
+// verify_submission_signature, so the browser produces byte-for-byte the same bodies and signatures
+// as pcl-wallet. Bodies cross the boundary as JSON strings and secret keys as hex; errors become
+// JavaScript exceptions carrying the PclError message.
+
+use wasm_bindgen::prelude::*;
+use crate::crypto::NodeKeypair;
+use crate::error::PclError;
+use crate::transaction::{prepare_submission, sign_submission, verify_submission_signature};
+
+fn to_js(error: PclError) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+fn parse_body(body: &str) -> Result<serde_json::Value, JsValue> {
+    serde_json::from_str(body).map_err(|e| to_js(e.into()))
+}
+
+// The canonical unsigned body, as POST /transaction/prepare returns it
+#[wasm_bindgen]
+pub fn prepare(body: &str) -> Result<String, JsValue> {
+    Ok(prepare_submission(&parse_body(body)?).map_err(to_js)?.to_string())
+}
+
+// Adds public_key and sig; the key must belong to the body's user
+#[wasm_bindgen]
+pub fn sign(body: &str, secret_key_hex: &str) -> Result<String, JsValue> {
+    let secret = hex::decode(secret_key_hex)
+        .map_err(|e| to_js(PclError::NodeIdentity(format!("Secret key is not hex: {}", e))))?;
+    let keypair = NodeKeypair::from_bytes(&secret).map_err(to_js)?;
+    Ok(sign_submission(&parse_body(body)?, &keypair).map_err(to_js)?.to_string())
+}
+
+// False for a missing or wrong signature; an exception only if the body is not JSON
+#[wasm_bindgen]
+pub fn verify(body: &str) -> Result<bool, JsValue> {
+    Ok(verify_submission_signature(&parse_body(body)?).is_ok())
+}
@@ -0,0 +1,264 @@
+// Webhook module - notifies subscribers when an address they watch receives funds
+
+use std::sync::Arc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+use crate::address::Address;
+use crate::client::PclClient;
+use crate::error::{PclError, Result};
+use crate::receipt::TransactionReceipt;
+use crate::storage::StorageManager;
+
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+pub const MIN_SECRET_LEN: usize = 16;
+pub const SIGNATURE_HEADER: &str = "X-PCL-Signature";
+pub const DELIVERY_HEADER: &str = "X-PCL-Delivery";
+pub const EVENT_ADDRESS_RECEIVED: &str = "address.received";
+const BASE_RETRY_DELAY_MS: u64 = 1_000;
+const MAX_RETRY_DELAY_MS: u64 = 10 * 60 * 1000;
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: String,
+    pub address: Address,
+    pub url: String,
+    pub secret: String, // HMAC key for the payload signature, never returned by the API
+    pub created_at: u64,
+}
+
+impl Subscription {
+    pub fn new(address: Address, url: &str, secret: &str, created_at: u64) -> Result<Self> {
+        // Deliveries go out over the same plain HTTP client the CLI uses
+        PclClient::from_url(url)
+            .map_err(|e| PclError::Validation(format!("Invalid webhook url: {}", e)))?;
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(PclError::Validation(format!(
+                "Webhook secret must be at least {} characters", MIN_SECRET_LEN
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            address,
+            url: url.trim().to_string(),
+            secret: secret.to_string(),
+            created_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed, // gave up after MAX_DELIVERY_ATTEMPTS
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String, // subscription id + tx id, so a replayed finalization is not delivered twice
+    pub subscription_id: String,
+    pub tx_id: String,
+    pub payload: String, // exact bytes that are signed and sent
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+    pub last_attempt_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub response_status: Option<u16>,
+}
+
+impl WebhookDelivery {
+    pub fn is_due(&self, now: u64) -> bool {
+        self.status == DeliveryStatus::Pending && self.next_attempt_at <= now
+    }
+
+    pub fn record_success(&mut self, response_status: u16, now: u64) {
+        self.attempts += 1;
+        self.status = DeliveryStatus::Delivered;
+        self.last_attempt_at = Some(now);
+        self.last_error = None;
+        self.response_status = Some(response_status);
+    }
+
+    pub fn record_failure(&mut self, error: String, response_status: Option<u16>, now: u64) {
+        self.attempts += 1;
+        self.last_attempt_at = Some(now);
+        self.last_error = Some(error);
+        self.response_status = response_status;
+        if self.attempts >= MAX_DELIVERY_ATTEMPTS {
+            self.status = DeliveryStatus::Failed;
+        } else {
+            self.next_attempt_at = now + retry_delay_ms(self.attempts);
+        }
+    }
+}
+
+// Exponential backoff: 1s, 2s, 4s, ... capped at 10 minutes
+pub fn retry_delay_ms(attempts: u32) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(20);
+    (BASE_RETRY_DELAY_MS << exponent).min(MAX_RETRY_DELAY_MS)
+}
+
+// Hex HMAC-SHA256 of the body, sent in the X-PCL-Signature header
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// For receivers: constant-time check of a delivery's signature header
+pub fn verify_payload_signature(secret: &str, body: &str, signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    mac.verify_slice(&expected).is_ok()
+}
+
+pub struct WebhookDispatcher {
+    storage: Arc<StorageManager>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(storage: Arc<StorageManager>) -> Self {
+        Self { storage }
+    }
+
+    pub fn subscribe(&self, address: Address, url: &str, secret: &str, now: u64) -> Result<Subscription> {
+        let subscription = Subscription::new(address, url, secret, now)?;
+        self.storage.store_subscription(&subscription)?;
+        Ok(subscription)
+    }
+
+    pub fn unsubscribe(&self, subscription_id: &str) -> Result<bool> {
+        if self.storage.load_subscription(subscription_id)?.is_none() {
+            return Ok(false);
+        }
+        self.storage.delete_subscription(subscription_id)?;
+        Ok(true)
+    }
+
+    pub fn subscription(&self, subscription_id: &str) -> Result<Option<Subscription>> {
+        self.storage.load_subscription(subscription_id)
+    }
+
+    pub fn deliveries(&self, subscription_id: &str) -> Result<Vec<WebhookDelivery>> {
+        Ok(self.storage.load_webhook_deliveries()?
+            .into_iter()
+            .filter(|delivery| delivery.subscription_id == subscription_id)
+            .collect())
+    }
+
+    // Queues a delivery for every subscription on the receiving address, carrying the finalization
+    // receipt when there is one. Returns how many were queued.
+    pub fn notify_received(
+        &self,
+        address: &str,
+        tx_id: &str,
+        amount: f64,
+        sender: &str,
+        receipt: Option<&TransactionReceipt>,
+        now: u64,
+    ) -> Result<usize> {
+        let mut queued = 0;
+        for subscription in self.storage.load_subscriptions()? {
+            if subscription.address != address {
+                continue;
+            }
+            let id = format!("{}:{}", subscription.id, tx_id);
+            if self.storage.load_webhook_delivery(&id)?.is_some() {
+                continue;
+            }
+
+            let payload = serde_json::json!({
+                "event": EVENT_ADDRESS_RECEIVED,
+                "delivery_id": id,
+                "subscription_id": subscription.id,
+                "address": address,
+                "tx_id": tx_id,
+                "amount": amount,
+                "from": sender,
+                "receipt": receipt,
+                "timestamp": now,
+            });
+            self.storage.store_webhook_delivery(&WebhookDelivery {
+                id,
+                subscription_id: subscription.id.clone(),
+                tx_id: tx_id.to_string(),
+                payload: payload.to_string(),
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_attempt_at: None,
+                last_error: None,
+                response_status: None,
+            })?;
+            queued += 1;
+        }
+        Ok(queued)
+    }
+
+    // Reads the due index rather than every delivery record, then loads just the due ones
+    pub fn due_deliveries(&self, now: u64) -> Result<Vec<WebhookDelivery>> {
+        let mut due = Vec::new();
+        for delivery_id in self.storage.due_webhook_delivery_ids(now)? {
+            if let Some(delivery) = self.storage.load_webhook_delivery(&delivery_id)? {
+                if delivery.is_due(now) {
+                    due.push(delivery);
+                }
+            }
+        }
+        Ok(due)
+    }
+
+    // Attempts every due delivery once and persists the outcome. Returns how many succeeded.
+    pub async fn dispatch_due(&self, now: u64) -> Result<usize> {
+        let mut delivered = 0;
+        for mut delivery in self.due_deliveries(now)? {
+            match self.storage.load_subscription(&delivery.subscription_id)? {
+                Some(subscription) => self.attempt(&subscription, &mut delivery, now).await,
+                None => {
+                    delivery.status = DeliveryStatus::Failed;
+                    delivery.last_error = Some("Subscription was removed".to_string());
+                }
+            }
+            if delivery.status == DeliveryStatus::Delivered {
+                delivered += 1;
+            }
+            self.storage.store_webhook_delivery(&delivery)?;
+        }
+        Ok(delivered)
+    }
+
+    async fn attempt(&self, subscription: &Subscription, delivery: &mut WebhookDelivery, now: u64) {
+        let signature = sign_payload(&subscription.secret, &delivery.payload);
+        let headers = [
+            (SIGNATURE_HEADER, signature.as_str()),
+            (DELIVERY_HEADER, delivery.id.as_str()),
+        ];
+
+        let result = match PclClient::from_url(&subscription.url) {
+            Ok((client, path)) => tokio::time::timeout(
+                tokio::time::Duration::from_secs(DELIVERY_TIMEOUT_SECS),
+                client.post_raw(&path, &delivery.payload, &headers),
+            )
+            .await
+            .unwrap_or_else(|_| Err(PclError::Network("Webhook endpoint timed out".to_string()))),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(status) if (200..300).contains(&status) => delivery.record_success(status, now),
+            Ok(status) => delivery.record_failure(format!("Endpoint returned HTTP {}", status), Some(status), now),
+            Err(e) => delivery.record_failure(e.to_string(), None, now),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+
+    fn request(user: &str, recipient: &str, amount: f64, received_at: chrono::DateTime<Utc>) -> AdmissionRequest {
+        AdmissionRequest { user: user.to_string(), recipients: vec![recipient.to_string()], amount, fee: 0.1, received_at }
+    }
+
+    #[test]
+    fn test_rule_based_policy_decisions() {
+        // Test: Evaluate requests against a blocklist, an amount cap and overnight weekday hours
+        // Expected: Blocked senders and recipients, large amounts and requests outside the hours are
+        // rejected with a reason; everything else, and anything under allow-all, is admitted
+        println!("Expected: Each admission rule rejects with its own reason");
+
+        let config = AdmissionConfig {
+            blocked_addresses: vec!["blocked".to_string()],
+            max_amount: Some(100.0),
+            business_hours: Some(BusinessHours { start_hour: 22, end_hour: 6, weekdays_only: true }),
+        };
+        config.validate().unwrap();
+        let policy = RuleBasedPolicy::new(config);
+        let wednesday_night = Utc.with_ymd_and_hms(2026, 10, 14, 23, 30, 0).unwrap();
+        let wednesday_noon = Utc.with_ymd_and_hms(2026, 10, 14, 12, 0, 0).unwrap();
+        let saturday_night = Utc.with_ymd_and_hms(2026, 10, 17, 23, 30, 0).unwrap();
+
+        assert_eq!(policy.evaluate(&request("alice", "bob", 50.0, wednesday_night)), AdmissionDecision::Admit);
+        for rejected in [
+            request("blocked", "bob", 50.0, wednesday_night),
+            request("alice", "blocked", 50.0, wednesday_night),
+            request("alice", "bob", 150.0, wednesday_night),
+            request("alice", "bob", 50.0, wednesday_noon),
+            request("alice", "bob", 50.0, saturday_night),
+        ] {
+            assert!(matches!(policy.evaluate(&rejected), AdmissionDecision::Reject(_)));
+        }
+        let error = check_admission(&policy, &request("alice", "bob", 150.0, wednesday_night)).unwrap_err();
+        assert_eq!(error.code(), "ADMISSION_REJECTED");
+        assert!(error.to_string().contains("limit of 100"));
+        assert!(check_admission(&AllowAll, &request("blocked", "bob", 1e9, saturday_night)).is_ok());
+
+        let bad_hours = AdmissionConfig { business_hours: Some(BusinessHours { start_hour: 9, end_hour: 9, weekdays_only: false }), ..Default::default() };
+        assert!(bad_hours.validate().is_err());
+        assert!(AdmissionConfig { max_amount: Some(0.0), ..Default::default() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_leader_refuses_transactions_its_policy_rejects() {
+        // Test: Give an embedded node a policy blocking one recipient and submit to it and to another
+        // Expected: The blocked submission fails at step 1 with the policy's reason and never reaches
+        // the mempool; the other one is finalized
+        println!("Expected: A leader's admission policy is applied before the workflow starts");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let node = EmbeddedNode::open(local_node, &local_keypair, dir.path(), ConsensusConfig::default()).await.unwrap();
+        node.consensus().leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        let blocked = NodeKeypair::new().address();
+        node.consensus().set_admission_policy(Arc::new(RuleBasedPolicy::new(AdmissionConfig {
+            blocked_addresses: vec![blocked.clone()],
+            ..Default::default()
+        }))).await;
+
+        let tx_data = |recipient: &str| TransactionData::new(
+            vec![(recipient.parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 1.3)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        let refused = tx_data(&blocked);
+        let error = node.submit_transaction(refused.clone()).await.unwrap_err();
+        assert_eq!(error.code(), "ADMISSION_REJECTED");
+        assert!(error.to_string().contains(&blocked));
+        assert!(node.query_status(&refused.raw_tx_id().unwrap()).await.is_err());
+
+        let allowed = tx_data(&NodeKeypair::new().address());
+        node.consensus().mempool.tx.write().await.create_utxo("utxo_in".to_string(), 1.3, allowed.user.to_string()).unwrap();
+        let tx_id = node.submit_transaction(allowed).await.unwrap();
+        assert!(node.query_status(&tx_id).await.unwrap().finalized);
+    }
+}
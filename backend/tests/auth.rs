@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::auth::hash_api_key;
+    use std::sync::Arc;
+
+    fn manager(dir: &std::path::Path) -> ApiKeyManager {
+        ApiKeyManager::new(Arc::new(StorageManager::new(dir).unwrap()))
+    }
+
+    #[test]
+    fn test_api_key_scopes_enforced() {
+        // Test: Authorize a read-only key, an admin key, a missing key and a revoked key
+        // Expected: Scopes gate routes, admin implies all, unknown and revoked keys are unauthorized
+        println!("Expected: API key scopes are enforced per request");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keys = manager(dir.path());
+        let (reader, reader_secret) = keys.create_key("dashboard", vec![Scope::Read], 100, 0).unwrap();
+        let (_, admin_secret) = keys.create_key("ops", vec![Scope::Admin], 100, 0).unwrap();
+
+        assert!(keys.authorize(Some(&reader_secret), Scope::Read, 1).is_ok());
+        assert_eq!(keys.authorize(Some(&reader_secret), Scope::Submit, 1).unwrap_err().code(), "FORBIDDEN");
+        assert!(keys.authorize(Some(&admin_secret), Scope::Faucet, 1).is_ok());
+
+        assert_eq!(keys.authorize(None, Scope::Read, 1).unwrap_err().code(), "UNAUTHORIZED");
+        assert_eq!(keys.authorize(Some("pcl_not_a_key"), Scope::Read, 1).unwrap_err().code(), "UNAUTHORIZED");
+
+        assert!(keys.revoke(&reader.id).unwrap());
+        assert_eq!(keys.authorize(Some(&reader_secret), Scope::Read, 2).unwrap_err().code(), "UNAUTHORIZED");
+        assert!(!keys.revoke(&reader.id).unwrap());
+    }
+
+    #[test]
+    fn test_api_key_rate_limit_and_usage() {
+        // Test: Send three requests inside one minute with a 2/min key, then one in the next window
+        // Expected: The third is rate limited, the fourth passes, usage counts all four and one refusal
+        println!("Expected: Per-key rate limits reset every minute and are reflected in usage");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keys = manager(dir.path());
+        let (key, secret) = keys.create_key("wallet", vec![Scope::Submit], 2, 0).unwrap();
+
+        assert!(keys.authorize(Some(&secret), Scope::Submit, 1_000).is_ok());
+        assert!(keys.authorize(Some(&secret), Scope::Submit, 2_000).is_ok());
+        assert_eq!(keys.authorize(Some(&secret), Scope::Submit, 3_000).unwrap_err().code(), "RATE_LIMITED");
+        assert!(keys.authorize(Some(&secret), Scope::Submit, 61_000).is_ok());
+
+        let usage = keys.usage(&key.id);
+        assert_eq!(usage.requests, 4);
+        assert_eq!(usage.rejected, 1);
+        assert_eq!(usage.last_used_at, Some(61_000));
+    }
+
+    #[test]
+    fn test_api_keys_stored_hashed() {
+        // Test: Create a key and inspect what storage holds, then try invalid key definitions
+        // Expected: Only the hash of the secret is stored; empty scopes and zero limits are rejected
+        println!("Expected: API key secrets are never persisted");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keys = manager(dir.path());
+        let (_, secret) = keys.create_key("ci", vec![Scope::Read, Scope::Submit], 10, 0).unwrap();
+
+        let stored = keys.list_keys().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert!(secret.starts_with(auth::API_KEY_PREFIX));
+        assert_ne!(stored[0].key_hash, secret);
+        assert_eq!(stored[0].key_hash, hash_api_key(&secret));
+
+        assert!(keys.create_key("empty", vec![], 10, 0).is_err());
+        assert!(keys.create_key("zero", vec![Scope::Read], 0, 0).is_err());
+        assert!("superuser".parse::<Scope>().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn pulse(sender_id: &str) -> NetworkMessage {
+        NetworkMessage::Pulse(PulseMessage {
+            pulse_id: "pulse_1".to_string(),
+            sender_id: sender_id.to_string(),
+            family_id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            clock_drift: false,
+        })
+    }
+
+    #[test]
+    fn test_binding_needs_both_keys_and_a_matching_peer_id() {
+        // Test: Sign a binding with a node key and a transport key, then tamper with its node id and
+        // peer id, and rebind the node to a new transport key in the registry
+        // Expected: Only the untouched binding verifies, and the registry follows the newest binding
+        println!("Expected: Peer bindings tie a PeerId to a node key with signatures from both");
+
+        let (app_keypair, transport_keypair) = (NodeKeypair::new(), NodeKeypair::new());
+        let binding = PeerBinding::sign("node_a", &app_keypair, &transport_keypair);
+        assert_eq!(binding.peer_id, peer_id_for(&transport_keypair.public_key()));
+        assert!(binding.verify().is_ok());
+
+        let mut renamed = binding.clone();
+        renamed.node_id = "node_b".to_string();
+        assert!(matches!(renamed.verify(), Err(PclError::SignatureVerification(_))));
+        let mut stolen = binding.clone();
+        stolen.peer_id = peer_id_for(&NodeKeypair::new().public_key());
+        assert!(stolen.verify().is_err());
+
+        let mut registry = BindingRegistry::new();
+        assert!(registry.insert(renamed).is_err());
+        registry.insert(binding.clone()).unwrap();
+        assert!(registry.check(&binding.peer_id, "node_a").is_ok());
+        assert!(registry.check(&binding.peer_id, "node_b").is_err());
+
+        let rebound = PeerBinding::sign("node_a", &app_keypair, &NodeKeypair::new());
+        registry.insert(rebound.clone()).unwrap();
+        assert_eq!(registry.len(), 1);
+        assert!(registry.check(&binding.peer_id, "node_a").is_err());
+        assert_eq!(registry.node_for_peer(&rebound.peer_id), Some("node_a"));
+    }
+
+    #[tokio::test]
+    async fn test_identity_messages_need_a_verified_binding() {
+        // Test: Deliver a pulse before and after its sender's binding arrives, a pulse claiming another
+        // node from the bound peer, a binding relayed by a different peer and one with an unregistered key
+        // Expected: Only the pulse from the bound peer for its own node is accepted; the bad bindings
+        // are refused
+        println!("Expected: Votes, pulses and validator broadcasts are checked against peer bindings");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let app_keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.2".parse().unwrap(), &app_keypair).unwrap();
+        let node_id = node.id.to_string();
+        let mut remote = NetworkManager::new(node.clone()).await.unwrap();
+        consensus.node_registry.write().await.register_node(node).unwrap();
+
+        let binding = PeerBinding::sign(&node_id, &app_keypair, &remote.transport_keypair);
+        let peer_id = remote.local_peer_id();
+        assert!(matches!(
+            consensus.handle_peer_message(&peer_id, &pulse(&node_id)).await,
+            Err(PclError::SignatureVerification(_))
+        ));
+
+        assert!(consensus.handle_peer_message("peer_other", &NetworkMessage::PeerBinding(binding.clone())).await.is_err());
+        let impostor = PeerBinding::sign(&node_id, &NodeKeypair::new(), &remote.transport_keypair);
+        assert!(consensus.handle_peer_binding(&peer_id, &impostor).await.is_err());
+
+        let published = remote.publish_peer_binding(&app_keypair).await.unwrap();
+        assert_eq!(published.peer_id, peer_id);
+        consensus.handle_peer_message(&peer_id, &NetworkMessage::PeerBinding(published)).await.unwrap();
+        consensus.handle_peer_message(&peer_id, &pulse(&node_id)).await.unwrap();
+        assert!(consensus.handle_peer_message(&peer_id, &pulse(&uuid::Uuid::new_v4().to_string())).await.is_err());
+        assert_eq!(consensus.peer_bindings.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_node_key_as_transport_identity_needs_no_binding() {
+        // Test: Have a registered node use its identity key for transport and send a pulse with no
+        // binding, then send a pulse claiming that node from a peer with its own transport key
+        // Expected: The PeerId derives from the node key and is accepted on that alone; the other peer
+        // still needs a binding. The option is off unless configured.
+        println!("Expected: A PeerId derived from the registered node key speaks for that node");
+
+        assert!(!NetworkConfig::default().transport_key_from_identity);
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let node_keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.2".parse().unwrap(), &node_keypair).unwrap();
+        let node_id = node.id.to_string();
+        let mut remote = NetworkManager::new(node.clone()).await.unwrap();
+        let separate_peer_id = remote.local_peer_id();
+        remote.use_node_key_for_transport(&node_keypair);
+        assert_eq!(remote.local_peer_id(), peer_id_for(&node_keypair.public_key()));
+        consensus.node_registry.write().await.register_node(node).unwrap();
+
+        consensus.handle_peer_message(&remote.local_peer_id(), &pulse(&node_id)).await.unwrap();
+        assert!(consensus.handle_peer_message(&separate_peer_id, &pulse(&node_id)).await.is_err());
+        assert!(consensus.peer_bindings.read().await.is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn tx_data() -> TransactionData {
+        TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.5)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    #[test]
+    fn test_amount_commitment_opens_only_with_real_amounts() {
+        // Test: Blind a transaction, then check its opening against the commitment with honest and altered amounts
+        // Expected: The blinded copy has no amounts, the opening restores them, and any change to amounts or salt fails
+        println!("Expected: Amount commitments bind the salt and every amount");
+
+        let raw_tx = RawTransaction::new("tx_blind".to_string(), tx_data());
+        let opening = AmountOpening::new(&raw_tx.raw_tx_id, &raw_tx.tx_data);
+        let commitment = opening.commitment().unwrap();
+        assert_ne!(commitment, AmountOpening::new(&raw_tx.raw_tx_id, &raw_tx.tx_data).commitment().unwrap());
+
+        let blinded = blind_transaction(&raw_tx);
+        assert!(blinded.tx_data.to.iter().all(|(_, amount)| *amount == 0.0));
+        assert!(blinded.tx_data.from.iter().all(|(_, amount)| *amount == 0.0));
+        assert_eq!(blinded.tx_data.to[0].0, raw_tx.tx_data.to[0].0);
+        assert!(opening.verify(&commitment, &blinded.tx_data).is_err());
+
+        let mut revealed = blinded.tx_data.clone();
+        opening.reveal(&mut revealed);
+        assert!(opening.verify(&commitment, &revealed).is_ok());
+
+        let mut inflated = opening.clone();
+        inflated.to[0].1 = 15.0;
+        assert!(inflated.verify(&commitment, &raw_tx.tx_data).is_err());
+        let mut resalted = opening.clone();
+        resalted.salt = hex::encode([0u8; 32]);
+        assert!(resalted.verify(&commitment, &raw_tx.tx_data).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blinded_workflow_reveals_amounts_to_validating_leaders() {
+        // Test: Run the workflow with blinded_amounts on and one registered leader
+        // Expected: Gossip carries zeroed amounts and a commitment, the leader can open its reveal, and the tx finalizes
+        println!("Expected: Blinded gossip plus direct-message reveals still finalize the transaction");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let config = ConsensusConfig { blinded_amounts: true, ..Default::default() };
+        let consensus = ConsensusManager::with_config(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap(), config).unwrap();
+
+        let leader_keypair = NodeKeypair::new();
+        let leader = Node::new("10.0.0.2".parse().unwrap(), &leader_keypair).unwrap();
+        let leader_id = leader.id.to_string();
+        consensus.node_registry.write().await.register_node(leader).unwrap();
+        consensus.leader_election.write().await.current_leaders = vec![leader_id.clone()];
+
+        let raw_tx = RawTransaction::new("tx_private".to_string(), tx_data());
+        consensus.mempool.tx.write().await.create_utxo("utxo_in".to_string(), 2.0, raw_tx.tx_data.user.to_string()).unwrap();
+        consensus.process_transaction_workflow(raw_tx.clone()).await.unwrap();
+
+        let history = consensus.network_manager.lock().await.message_history.read().await.clone();
+        let gossip = history.iter().find_map(|message| match message {
+            NetworkMessage::TransactionGossip(gossip) => Some(gossip.clone()),
+            _ => None,
+        }).unwrap();
+        let commitment = gossip.amount_commitment.clone().unwrap();
+        assert_eq!(gossip.raw_transaction.tx_data.to[0].1, 0.0);
+
+        let reveal = history.iter().find_map(|message| match message {
+            NetworkMessage::AmountReveal(reveal) => Some(reveal.clone()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(reveal.target_node, leader_id);
+        assert!(reveal.open(&NodeKeypair::new()).is_err());
+        let opening = reveal.open(&leader_keypair).unwrap();
+        assert_eq!(opening.commitment().unwrap(), commitment);
+        assert_eq!(opening.to, raw_tx.tx_data.to);
+        assert_eq!(opening.from, raw_tx.tx_data.from);
+
+        assert_eq!(consensus.consensus_state.read().await.workflow_metrics.end_to_end.count, 1);
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_only_the_submitter_can_cancel() {
+        // Test: Sign a cancellation as the transaction's user, as someone else, and alter a signed one
+        // Expected: Only the untouched cancellation from the user verifies; the fee must be a valid amount
+        println!("Expected: Cancellations verify against the transaction's user only");
+
+        let alice = NodeKeypair::new();
+        let mallory = NodeKeypair::new();
+        let cancellation = TransactionCancellation::sign("tx_1", &alice);
+        assert!(cancellation.verify(&alice.address()).is_ok());
+
+        let forged = TransactionCancellation::sign("tx_1", &mallory);
+        assert!(matches!(forged.verify(&alice.address()), Err(PclError::SignatureVerification(_))));
+
+        let mut retargeted = cancellation.clone();
+        retargeted.raw_tx_id = "tx_2".to_string();
+        assert!(retargeted.verify(&alice.address()).is_err());
+
+        let mut impersonated = forged.clone();
+        impersonated.user = alice.address();
+        assert!(impersonated.verify(&alice.address()).is_err());
+
+        assert!(ConsensusConfig::default().cancellation_fee > 0.0);
+        assert!(ConsensusConfig { cancellation_fee: -0.5, ..Default::default() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_posts_cancellation() {
+        // Test: Cancel through the client against a local listener that answers like the node
+        // Expected: The signed cancellation is posted to /transaction/{id}/cancel and the refund decoded
+        println!("Expected: Client sends cancellations to the transaction's cancel endpoint");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 2048];
+            let n = stream.read(&mut buffer).await.unwrap();
+            let request = String::from_utf8_lossy(&buffer[..n]).to_string();
+            let body = serde_json::json!({
+                "status": "cancelled", "transaction_id": "tx_1", "stake_returned": 0.19, "cancellation_fee": 0.01,
+                "dropped_dependents": []
+            });
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{}\r\n", body);
+            stream.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+
+        let client = PclClient::new(&format!("http://{}", addr)).unwrap();
+        let cancellation = TransactionCancellation::sign("tx_1", &NodeKeypair::new());
+        let response = client.cancel_transaction(&cancellation).await.unwrap();
+        assert_eq!(response["status"], "cancelled");
+        assert_eq!(response["cancellation_fee"], 0.01);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /transaction/tx_1/cancel HTTP/1.1"));
+        let sent: TransactionCancellation = serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap().trim()).unwrap();
+        assert_eq!(sent, cancellation);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_response_success_and_error() {
+        // Test: Parse a 200 JSON response and a structured 400 error response
+        // Expected: The body is returned on success, the error code and message surface on failure
+        println!("Expected: Client turns node error responses into errors");
+
+        let ok = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"status\":\"healthy\"}\r\n";
+        assert_eq!(client::parse_response(ok).unwrap()["status"], "healthy");
+
+        let bad = "HTTP/1.1 400 Bad Request\r\n\r\n{\"error\":{\"code\":\"INVALID_ADDRESS\",\"message\":\"bad\"}}\r\n";
+        let err = client::parse_response(bad).unwrap_err().to_string();
+        assert!(err.contains("400") && err.contains("INVALID_ADDRESS"));
+
+        assert!(client::parse_response("garbage").is_err());
+    }
+
+    #[test]
+    fn test_mempool_stage_parsing() {
+        // Test: Parse every stage name and an unknown one
+        // Expected: Names round-trip, unknown stages are rejected
+        println!("Expected: Mempool stages parse from their CLI names");
+
+        for stage in MempoolStage::ALL {
+            assert_eq!(stage.as_str().parse::<MempoolStage>().unwrap(), stage);
+        }
+        assert!("pending".parse::<MempoolStage>().is_err());
+        assert!(PclClient::new("https://node.example:8080").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_fetches_mempool_stage() {
+        // Test: Serve a canned /mempool/raw response from a local listener
+        // Expected: The client sends the right request line and decodes the listing
+        println!("Expected: Client decodes a mempool listing from the node API");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+            let n = stream.read(&mut buffer).await.unwrap();
+            let request = String::from_utf8_lossy(&buffer[..n]).to_string();
+            let body = serde_json::json!({
+                "stage": "raw",
+                "count": 1,
+                "entries": [{
+                    "stage": "raw", "id": "tx_1", "tx_id": "tx_1", "leader": "leader_1", "user": null,
+                    "status": "pending_validation", "timestamp": 42, "details": {}
+                }]
+            });
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{}\r\n", body);
+            stream.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+
+        let client = PclClient::new(&format!("http://{}", addr)).unwrap();
+        let listing = client.mempool_stage(MempoolStage::Raw).await.unwrap();
+        assert_eq!(listing.count, 1);
+        assert_eq!(listing.entries[0].leader.as_deref(), Some("leader_1"));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /mempool/raw HTTP/1.1"));
+    }
+
+    fn entry(id: &str, leader: &str, timestamp: u64) -> MempoolEntry {
+        MempoolEntry {
+            stage: MempoolStage::Raw,
+            id: id.to_string(),
+            tx_id: id.to_string(),
+            leader: Some(leader.to_string()),
+            user: None,
+            status: "pending_validation".to_string(),
+            timestamp,
+            details: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_mempool_pagination_with_cursor() {
+        // Test: Page through a stage with cursors, including one tx held by two leaders, while a newer
+        // entry arrives between pages; also page by offset and reject bad parameters
+        // Expected: Cursor pages cover every original entry exactly once, newest first
+        println!("Expected: Cursor pagination is stable while the mempool changes");
+
+        let mut entries: Vec<MempoolEntry> = (0..7).map(|i| entry(&format!("tx_{}", i), "leader_1", 100 + i)).collect();
+        entries.push(entry("tx_3", "leader_2", 103));
+
+        let request = PageRequest::from_query("stage=raw&limit=3").unwrap();
+        let first = client::paginate(MempoolStage::Raw, entries.clone(), &request).unwrap();
+        assert_eq!(first.count, 8);
+        assert_eq!(first.entries[0].id, "tx_6");
+
+        entries.push(entry("tx_new", "leader_1", 999));
+        let mut seen: Vec<(String, Option<String>)> = first.entries.iter().map(|e| (e.id.clone(), e.leader.clone())).collect();
+        let mut cursor = first.next_cursor;
+        while let Some(next) = cursor {
+            let query = format!("limit=3&cursor={}", next);
+            let page = client::paginate(MempoolStage::Raw, entries.clone(), &PageRequest::from_query(&query).unwrap()).unwrap();
+            seen.extend(page.entries.iter().map(|e| (e.id.clone(), e.leader.clone())));
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen.len(), 8);
+        assert!(seen.contains(&("tx_3".to_string(), Some("leader_2".to_string()))));
+        assert!(!seen.iter().any(|(id, _)| id == "tx_new"));
+
+        let by_offset = client::paginate(MempoolStage::Raw, entries, &PageRequest::from_query("offset=8&limit=5").unwrap()).unwrap();
+        assert_eq!(by_offset.entries.len(), 1);
+        assert!(by_offset.next_cursor.is_none());
+
+        assert!(PageRequest::from_query("limit=0").is_err());
+        assert!(PageRequest::from_query("offset=-1").is_err());
+        assert!(client::paginate(MempoolStage::Raw, Vec::new(), &PageRequest { cursor: Some("zz".to_string()), ..PageRequest::default() }).is_err());
+    }
+}
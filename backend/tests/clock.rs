@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::clock;
+
+    fn config() -> ClockConfig {
+        ClockConfig { sync_interval_secs: 60, max_drift_ms: 500, ..ClockConfig::default() }
+    }
+
+    #[test]
+    fn test_offset_prefers_fresh_ntp_then_peer_median() {
+        // Test: Feed ClockSync an NTP measurement and peer pulse timestamps, then let the NTP one go stale
+        // Expected: Fresh NTP wins, stale NTP falls back to the peer median, and too few peers give no estimate
+        println!("Expected: Offset estimate uses NTP while fresh and the median of at least three peers otherwise");
+
+        let now = 10_000_000;
+        let mut sync = ClockSync::new(config());
+        assert_eq!(sync.estimate(now), None);
+
+        sync.record_ntp(-120, now);
+        sync.record_peer("peer_a", now + 300, now);
+        sync.record_peer("peer_b", now + 310, now);
+        assert_eq!(sync.estimate(now), Some((-120, OffsetSource::Ntp)));
+
+        // Two peers can't outvote anyone; a third makes the median usable
+        let later = now + 121_000;
+        sync.record_peer("peer_a", later + 300, later);
+        sync.record_peer("peer_b", later + 310, later);
+        assert_eq!(sync.estimate(later), None);
+        sync.record_peer("peer_c", later + 50_000, later);
+        assert_eq!(sync.estimate(later), Some((310, OffsetSource::PeerMedian)));
+    }
+
+    #[test]
+    fn test_update_applies_offset_and_flags_drift() {
+        // Test: Update the estimate with a small and then a large NTP offset, and restore a zero offset
+        // Expected: now_ms moves by the offset, and drift beyond max_drift_ms marks the clock unhealthy
+        println!("Expected: Measured offsets are applied to node timestamps and excess drift is unhealthy");
+
+        let mut sync = ClockSync::new(config());
+        let now = clock::system_now_ms();
+        sync.record_ntp(40, now);
+        let status = sync.update(now).unwrap();
+        assert!(status.healthy);
+        assert_eq!(clock::clock_offset_ms(), 40);
+        let corrected = clock::now_ms() as i64 - clock::system_now_ms() as i64;
+        assert!((35..=45).contains(&corrected), "corrected offset was {}", corrected);
+
+        sync.record_ntp(-2_000, now);
+        let status = sync.update(now).unwrap();
+        assert!(!status.healthy);
+        assert!(!clock::clock_healthy());
+        assert_eq!(sync.status(), Some(&status));
+
+        sync.restore(ClockStatus { offset_ms: 0, source: OffsetSource::Ntp, measured_at: now, healthy: true, max_drift_ms: 500 });
+        assert!(clock::clock_healthy());
+        assert_eq!(clock::clock_offset_ms(), 0);
+    }
+}
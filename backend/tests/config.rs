@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_default_consensus_config_is_valid() {
+        // Test: Validate the default consensus parameters
+        // Expected: Defaults pass and match the previous hardcoded behaviour
+        println!("Expected: Default consensus config is valid");
+
+        let config = NodeConfig::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.consensus.min_validation_completions, 1);
+        assert_eq!(config.consensus.required_leader_signatures, 1);
+        assert_eq!(config.consensus.broadcast_fanout, 3);
+    }
+
+    #[test]
+    fn test_consensus_config_sanity_bounds() {
+        // Test: Validate zero and out-of-range quorum parameters
+        // Expected: Each is rejected with a CONFIG_ERROR
+        println!("Expected: Out-of-range quorum parameters are rejected");
+
+        let invalid = [
+            ConsensusConfig { min_validation_completions: 0, ..Default::default() },
+            ConsensusConfig { broadcast_fanout: 0, ..Default::default() },
+            ConsensusConfig { broadcast_fanout: config::MAX_BROADCAST_FANOUT + 1, ..Default::default() },
+            ConsensusConfig { required_leader_signatures: 0, ..Default::default() },
+            ConsensusConfig { broadcast_fanout: 2, required_leader_signatures: 4, ..Default::default() },
+            ConsensusConfig { broadcast_fanout: 2, required_leader_signatures: 3, ..Default::default() },
+            ConsensusConfig { locked_utxo_ttl_secs: 0, ..Default::default() },
+            ConsensusConfig { stake_bonding_period_secs: 0, ..Default::default() },
+            ConsensusConfig { stake_unbonding_delay_secs: 0, ..Default::default() },
+            ConsensusConfig { min_validator_stake: -1.0, ..Default::default() },
+            ConsensusConfig { min_leader_stake: f64::NAN, ..Default::default() },
+        ];
+        for config in invalid {
+            let err = config.validate().unwrap_err();
+            assert_eq!(err.code(), "CONFIG_ERROR");
+        }
+
+        let invalid_fees = [
+            FeeConfig { base_fee: -0.1, ..Default::default() },
+            FeeConfig { target_mempool_depth: 0, ..Default::default() },
+            FeeConfig { window_secs: 0, ..Default::default() },
+            FeeConfig { max_multiplier: 0.5, ..Default::default() },
+        ];
+        for config in invalid_fees {
+            assert_eq!(config.validate().unwrap_err().code(), "CONFIG_ERROR");
+        }
+    }
+
+    #[test]
+    fn test_node_config_env_overrides() {
+        // Test: Apply environment overrides, then a non-numeric override
+        // Expected: Numeric values are applied, garbage is rejected
+        println!("Expected: PCL_* overrides update the consensus config");
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| match key {
+            "PCL_MIN_VALIDATION_COMPLETIONS" => Some("4".to_string()),
+            "PCL_BROADCAST_FANOUT" => Some("5".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.consensus.min_validation_completions, 4);
+        assert_eq!(config.consensus.broadcast_fanout, 5);
+        assert_eq!(config.consensus.required_leader_signatures, 1);
+
+        let result = config.apply_env_overrides(|key| {
+            (key == "PCL_REQUIRED_LEADER_SIGNATURES").then(|| "many".to_string())
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_bootnodes_env_override_and_validation() {
+        // Test: Set bootnodes from PCL_BOOTNODES, then configure one without a port
+        // Expected: The comma-separated list is trimmed and accepted, the portless entry is rejected
+        println!("Expected: Bootnodes must be host:port addresses");
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| {
+            (key == "PCL_BOOTNODES").then(|| "10.0.0.1:8080, seed.pcl.example:8080,".to_string())
+        }).unwrap();
+        assert_eq!(config.network.bootnodes, vec!["10.0.0.1:8080".to_string(), "seed.pcl.example:8080".to_string()]);
+        assert!(config.validate().is_ok());
+
+        config.network.bootnodes.push("seed.pcl.example".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_node_config_from_partial_file() {
+        // Test: Load a config file that only sets one consensus field
+        // Expected: Missing fields fall back to defaults
+        println!("Expected: Partial config files are filled in with defaults");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.json");
+        std::fs::write(&path, r#"{"consensus": {"min_validation_completions": 2}}"#).unwrap();
+
+        let config = NodeConfig::from_file(&path).unwrap();
+        assert_eq!(config.consensus.min_validation_completions, 2);
+        assert_eq!(config.consensus.broadcast_fanout, 3);
+
+        std::fs::write(&path, r#"{"consensus": {"broadcast_fanout": 0}}"#).unwrap();
+        assert!(NodeConfig::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_export_config_validation() {
+        // Test: Configure export with supported and unsupported DSNs, then a zero batch size
+        // Expected: sqlite and postgres DSNs pass, anything else is a CONFIG_ERROR
+        println!("Expected: Export config only accepts sqlite/postgres DSNs and sane batches");
+
+        let mut config = NodeConfig::default();
+        assert!(config.export.dsn.is_none());
+
+        config.apply_env_overrides(|key| (key == "PCL_EXPORT_DSN").then(|| "sqlite://export.db".to_string())).unwrap();
+        assert_eq!(config.export.dsn.as_deref(), Some("sqlite://export.db"));
+        assert!(config.validate().is_ok());
+
+        config.export.dsn = Some("postgres://pcl@localhost/analytics".to_string());
+        assert!(config.validate().is_ok());
+
+        config.export.dsn = Some("mysql://localhost/analytics".to_string());
+        assert_eq!(config.validate().unwrap_err().code(), "CONFIG_ERROR");
+
+        let zero_batch = ExportConfig { batch_size: 0, ..Default::default() };
+        assert!(zero_batch.validate().is_err());
+    }
+
+    #[test]
+    fn test_tls_config_requires_certificate_or_self_signed() {
+        // Test: Enable TLS without a certificate, with PCL_TLS_* overrides, and in self-signed mode
+        // Expected: Plain enablement is rejected; cert+key or self_signed are accepted
+        println!("Expected: TLS config needs a certificate pair unless self-signed");
+
+        let mut config = NodeConfig::default();
+        assert!(!config.tls.enabled);
+
+        config.tls.enabled = true;
+        assert_eq!(config.validate().unwrap_err().code(), "CONFIG_ERROR");
+
+        config.apply_env_overrides(|key| match key {
+            "PCL_TLS_CERT" => Some("/etc/pcl/cert.pem".to_string()),
+            "PCL_TLS_KEY" => Some("/etc/pcl/key.pem".to_string()),
+            _ => None,
+        }).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.tls.cert_path(), std::path::PathBuf::from("/etc/pcl/cert.pem"));
+
+        let mut devnet = NodeConfig::default();
+        devnet.apply_env_overrides(|key| (key == "PCL_TLS_SELF_SIGNED").then(|| "true".to_string())).unwrap();
+        assert!(devnet.tls.enabled && devnet.tls.self_signed);
+        assert!(devnet.validate().is_ok());
+        assert_eq!(devnet.tls.key_path(), std::path::PathBuf::from(config::DEFAULT_TLS_KEY_PATH));
+
+        // Clients that do not negotiate ALPN are served the JSON API
+        assert_eq!(tls::ApplicationProtocol::from_alpn(None), tls::ApplicationProtocol::Http1);
+        assert_eq!(tls::ApplicationProtocol::from_alpn(Some(b"h2")), tls::ApplicationProtocol::Grpc);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use serde_json::Value;
+
+    // Shared with the simulator and any other implementation that has to agree on ids and signatures
+    const VECTORS: &str = include_str!("../../vectors/transaction_vectors.json");
+
+    fn cases() -> Vec<Value> {
+        let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+        assert_eq!(vectors["version"], 1);
+        vectors["cases"].as_array().unwrap().clone()
+    }
+
+    fn keypair(case: &Value, field: &str) -> NodeKeypair {
+        NodeKeypair::from_bytes(&hex::decode(case[field].as_str().unwrap()).unwrap()).unwrap()
+    }
+
+    fn text(case: &Value, field: &str) -> String {
+        case[field].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_transaction_ids_and_signing_bytes_match_vectors() {
+        // Test: Rebuild signing bytes, raw_tx_id and user signature for each vector's tx_data and key
+        // Expected: Byte-for-byte equal to the published values, and the signature verifies
+        println!("Expected: Hashing and signing of TransactionData match the conformance vectors");
+
+        for case in cases() {
+            let name = text(&case, "name");
+            let mut tx_data: TransactionData = serde_json::from_value(case["tx_data"].clone()).unwrap();
+
+            let signing_bytes = tx_data.signing_bytes().unwrap();
+            assert_eq!(String::from_utf8(signing_bytes.clone()).unwrap(), text(&case, "signing_bytes"), "{}", name);
+            assert_eq!(tx_data.raw_tx_id().unwrap(), text(&case, "raw_tx_id"), "{}", name);
+
+            let user = keypair(&case, "user_secret_key");
+            tx_data.sign_as_user(&user).unwrap();
+            assert_eq!(tx_data.sig.clone().unwrap(), text(&case, "user_signature"), "{}", name);
+            let signature = multisig::decode_signature(&text(&case, "user_signature")).unwrap();
+            assert!(verify_data_signature(&signing_bytes, &signature, &user.public_key()).unwrap(), "{}", name);
+
+            // Signing must not move the id
+            assert_eq!(tx_data.raw_tx_id().unwrap(), text(&case, "raw_tx_id"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_processing_entry_final_id_and_digital_root_match_vectors() {
+        // Test: Sign each vector as user (and sponsor), build the leader's processing entry and finalize it
+        // Expected: Sponsor signature, digital root, leader signature and final tx_id equal the vectors
+        println!("Expected: Leader signatures, final tx ids and digital roots match the conformance vectors");
+
+        for case in cases() {
+            let name = text(&case, "name");
+            let mut tx_data: TransactionData = serde_json::from_value(case["tx_data"].clone()).unwrap();
+            tx_data.sign_as_user(&keypair(&case, "user_secret_key")).unwrap();
+            if case.get("fee_payer_secret_key").is_some() {
+                tx_data.sign_as_fee_payer(&keypair(&case, "fee_payer_secret_key")).unwrap();
+                assert_eq!(tx_data.fee_payer.as_ref().unwrap().signature.clone().unwrap(), text(&case, "fee_payer_signature"), "{}", name);
+                assert!(tx_data.verify_fee_payer(), "{}", name);
+            }
+            assert_eq!(tx_data.digital_root().unwrap() as u64, case["digital_root"].as_u64().unwrap(), "{}", name);
+
+            let leader = keypair(&case, "leader_secret_key");
+            let mut entry = ProcessingTransaction::new(text(&case, "raw_tx_id"), tx_data, String::new(), text(&case, "leader_id"));
+            entry.timestamp = text(&case, "processing_timestamp").parse().unwrap();
+            entry.sign(&leader).unwrap();
+            assert_eq!(entry.sig, text(&case, "leader_signature"), "{}", name);
+            assert!(entry.verify_leader_signature(&leader.public_key()), "{}", name);
+            assert_eq!(entry.final_tx_id().unwrap(), text(&case, "final_tx_id"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_vectors_reject_tampered_transaction() {
+        // Test: Change one output amount of a vector transaction
+        // Expected: raw_tx_id changes and the published user signature no longer verifies
+        println!("Expected: Any change to the transaction breaks its id and signature");
+
+        let case = &cases()[0];
+        let mut tx_data: TransactionData = serde_json::from_value(case["tx_data"].clone()).unwrap();
+        tx_data.to[0].1 += 0.01;
+        assert_ne!(tx_data.raw_tx_id().unwrap(), text(case, "raw_tx_id"));
+
+        let signature = multisig::decode_signature(&text(case, "user_signature")).unwrap();
+        let user = keypair(case, "user_secret_key");
+        assert!(!verify_data_signature(&tx_data.signing_bytes().unwrap(), &signature, &user.public_key()).unwrap());
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_same_digital_root_chains_into_next_facet() {
+        // Test: Place transactions that share a digital root, one whose root is 9, and enough others
+        // to fill the first layer
+        // Expected: The first keeps its home facet, later ones chain into the next free facet and
+        // wrap around the layer, and once nine are placed the next one opens layer 1 at its home facet
+        println!("Expected: Colliding digital roots resolve to the next free facet of the open layer");
+
+        let mut cube = CubicGeometry::new();
+        let first = cube.place("tx_a", 4, 1_000);
+        assert_eq!((first.layer, first.facet, first.chained), (0, 4, 0));
+        assert!(!first.collided());
+
+        let second = cube.place("tx_b", 4, 1_001);
+        assert_eq!((second.layer, second.facet, second.home_facet, second.chained), (0, 5, 4, 1));
+        assert!(second.collided());
+        assert_eq!(cube.occupant(0, 5), Some("tx_b"));
+
+        // A root of 9 lands on facet 0; with 8 and 0 taken, tx_e wraps around to facet 1
+        assert_eq!(cube.place("tx_c", 9, 1_002).facet, 0);
+        assert_eq!(cube.place("tx_d", 8, 1_003).facet, 8);
+        let wrapped = cube.place("tx_e", 8, 1_004);
+        assert_eq!((wrapped.facet, wrapped.chained), (1, 2));
+
+        for (n, root) in [2, 3, 6, 7].into_iter().enumerate() {
+            assert!(!cube.place(&format!("tx_f{}", n), root, 1_010 + n as u64).collided());
+        }
+        assert_eq!(cube.len(), 9);
+        assert_eq!(cube.collisions(), 2);
+
+        let next_layer = cube.place("tx_g", 4, 1_020);
+        assert_eq!((next_layer.layer, next_layer.facet, next_layer.position), (1, 4, FACETS_PER_LAYER + 4));
+        assert_eq!(cube.placements().last().unwrap().tx_id, "tx_g");
+        assert_eq!(cube.placement("tx_b"), Some(&second));
+        assert_eq!(cube.place("tx_b", 4, 1_001), second);
+    }
+
+    #[test]
+    fn test_placement_is_independent_of_arrival_order() {
+        // Test: Place the same transactions on two nodes in different orders, restore a third from
+        // the finalized set, and move one transaction's averaged timestamp ahead of its competitor
+        // Expected: All three agree on every slot; the earlier timestamp keeps the home facet, so the
+        // moved transaction takes it and the other one chains
+        println!("Expected: Every node resolves digital root collisions to the same placement");
+
+        let transactions = [("tx_a", 5, 3_000), ("tx_b", 5, 1_000), ("tx_c", 5, 2_000), ("tx_d", 1, 1_500), ("tx_e", 5, 2_000)];
+        let mut forward = CubicGeometry::new();
+        for (tx_id, root, timestamp) in transactions {
+            forward.place(tx_id, root, timestamp);
+        }
+        let mut backward = CubicGeometry::new();
+        for (tx_id, root, timestamp) in transactions.iter().rev() {
+            backward.place(tx_id, *root, *timestamp);
+        }
+        let mut restored = CubicGeometry::new();
+        restored.restore(transactions);
+
+        assert_eq!(forward.placements(), backward.placements());
+        assert_eq!(forward.placements(), restored.placements());
+        assert_eq!(forward.placement("tx_b").unwrap().facet, 5);
+        assert_eq!(forward.placement("tx_c").unwrap().facet, 6);
+        assert_eq!(forward.placement("tx_e").unwrap().facet, 7);
+        assert_eq!(forward.placement("tx_a").unwrap().facet, 8);
+
+        // A sync conflict can hand tx_a an earlier averaged timestamp than tx_b
+        let moved = forward.place("tx_a", 5, 500);
+        assert_eq!((moved.facet, moved.chained), (5, 0));
+        assert_eq!(forward.placement("tx_b").unwrap().facet, 6);
+        assert_eq!(forward.len(), 5);
+        assert_eq!(forward.collisions(), 3);
+        assert_eq!(home_facet(9), home_facet(0));
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn signed_tx() -> RawTransaction {
+        let user = NodeKeypair::new();
+        let mut tx_data = TransactionData::new(
+            vec![(Address::parse(&NodeKeypair::new().address()).unwrap(), 1.0)],
+            vec![("utxo_1".to_string(), 2.0)],
+            Address::parse(&user.address()).unwrap(),
+            0.2,
+            0.1,
+        );
+        tx_data.leader = Some("leader_1".to_string());
+        tx_data.sign_as_user(&user).unwrap();
+        RawTransaction::new(tx_data.raw_tx_id().unwrap(), tx_data)
+    }
+
+    fn mempool_with_lock(utxo_id: &str, tx_id: &str) -> MempoolManager {
+        let mut mempool = MempoolManager::new();
+        mempool.lock_utxo(utxo_id.to_string(), 2.0, tx_id.to_string()).unwrap();
+        mempool
+    }
+
+    #[test]
+    fn test_verify_db_finds_forged_ids_bad_signatures_and_orphaned_locks() {
+        // Test: Store a valid raw tx and a processing entry signed by a stored leader, then a raw tx whose
+        // id is not its content hash, a processing entry with a forged signature and a lock held by a
+        // transaction that is not stored
+        // Expected: Only the bad records are reported; a check without repair changes nothing
+        println!("Expected: verify-db reports every record that fails its checks");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageManager::new(dir.path()).unwrap();
+        let leader_keypair = NodeKeypair::new();
+        let leader = Node::new("10.0.0.1".parse().unwrap(), &leader_keypair).unwrap();
+        storage.store_node(&leader).unwrap();
+
+        let good = signed_tx();
+        storage.store_raw_transaction(&good).unwrap();
+        let mut processing = ProcessingTransaction::new(good.raw_tx_id.clone(), good.tx_data.clone(), String::new(), leader.id.to_string());
+        processing.sign(&leader_keypair).unwrap();
+        storage.store_processing_transaction(&processing).unwrap();
+        storage.store_mempool_state(&mempool_with_lock("utxo_1", &good.raw_tx_id)).unwrap();
+        let report = storage.verify_integrity(false).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.checked[CF_RAW_TRANSACTIONS], 2); // record and index entry
+
+        let mut forged = signed_tx();
+        forged.raw_tx_id = "tx_forged".to_string();
+        storage.store_raw_transaction(&forged).unwrap();
+        let mut tampered = processing.clone();
+        tampered.tx_id = signed_tx().raw_tx_id;
+        storage.store_processing_transaction(&tampered).unwrap();
+        storage.store_mempool_state(&mempool_with_lock("utxo_9", "tx_gone")).unwrap();
+
+        let report = storage.verify_integrity(false).unwrap();
+        let keys: Vec<&str> = report.issues.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(report.issues.len(), 3, "{:?}", report.issues);
+        assert!(keys.contains(&"tx/leader_1/tx_forged"));
+        assert!(keys.contains(&tampered.tx_id.as_str()));
+        assert!(report.issues.iter().any(|issue| issue.problem.contains("utxo_9")));
+        assert_eq!(report.repaired(), 0);
+        assert_eq!(storage.verify_integrity(false).unwrap().issues.len(), 3);
+    }
+
+    #[test]
+    fn test_verify_db_repair_leaves_a_consistent_database() {
+        // Test: Store a raw tx without its index entry, an index entry for a missing record, an
+        // undecodable processing entry and a lock held by a finalized transaction, then repair
+        // Expected: Every issue is repaired, the raw tx is still loadable and a second check is clean
+        println!("Expected: verify-db --repair fixes what it reports");
+
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().to_path_buf();
+        let good = signed_tx();
+        let finalized = FinalizedTransaction {
+            tx_id: "tx_final".to_string(),
+            tx_data: good.tx_data.clone(),
+            xmbl_cubic_root: 1,
+            validator_signature: "sig".to_string(),
+            finalized_at: chrono::Utc::now(),
+        };
+        {
+            let storage = StorageManager::new(&db_path).unwrap();
+            storage.store_raw_transaction(&good).unwrap();
+            storage.store_finalized_transaction(&finalized).unwrap();
+            storage.store_mempool_state(&mempool_with_lock("utxo_1", "tx_final")).unwrap();
+        }
+        {
+            let mut opts = rocksdb::Options::default();
+            opts.create_missing_column_families(true);
+            let cfs = ALL_COLUMN_FAMILIES.map(|name| rocksdb::ColumnFamilyDescriptor::new(name, rocksdb::Options::default()));
+            let db = rocksdb::DB::open_cf_descriptors(&opts, &db_path, cfs).unwrap();
+            let raw_cf = db.cf_handle(CF_RAW_TRANSACTIONS).unwrap();
+            db.delete_cf(raw_cf, format!("idx/{}", good.raw_tx_id)).unwrap();
+            db.put_cf(raw_cf, "idx/tx_missing", "leader_2").unwrap();
+            db.put_cf(db.cf_handle(CF_PROCESSING_TRANSACTIONS).unwrap(), "tx_corrupt", [0xde, 0xad]).unwrap();
+        }
+
+        let storage = StorageManager::new(&db_path).unwrap();
+        let report = storage.verify_integrity(true).unwrap();
+        assert_eq!(report.issues.len(), 4, "{:?}", report.issues);
+        assert_eq!(report.repaired(), 4);
+
+        assert!(storage.verify_integrity(false).unwrap().is_clean());
+        assert_eq!(storage.load_raw_transaction(&good.raw_tx_id).unwrap().unwrap().raw_tx_id, good.raw_tx_id);
+        assert!(storage.load_processing_transaction("tx_corrupt").unwrap().is_none());
+        assert!(storage.load_mempool_state().unwrap().unwrap().locked_utxo.locked_utxos.is_empty());
+        assert!(storage.load_finalized_transaction("tx_final").unwrap().is_some());
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn user() -> Address {
+        Address::parse(&NodeKeypair::new().address()).unwrap()
+    }
+
+    // Spends one UTXO, paying amount to the recipient and the rest less stake and fee back as change
+    fn transfer(tx_id: &str, input: (&str, f64), amount: f64, sender: &Address, recipient: &Address) -> RawTransaction {
+        let tx_data = TransactionData::new(
+            vec![(recipient.clone(), amount)],
+            vec![(input.0.to_string(), input.1)],
+            sender.clone(),
+            0.1,
+            0.05,
+        );
+        RawTransaction::new(tx_id.to_string(), tx_data)
+    }
+
+    #[test]
+    fn test_child_spending_pending_output_is_invalidated_with_parent() {
+        // Test: Bob spends the output Alice's raw transaction pays him, then spends his change from
+        // that again, and Alice's transaction is invalidated
+        // Expected: Both children are linked in the graph and removed with the parent, locks released
+        println!("Expected: Invalidating a parent takes every descendant out of the mempools");
+
+        let (alice, bob, carol) = (user(), user(), user());
+        let mut mempool = MempoolManager::new();
+        mempool.tx.create_utxo("genesis_alice".to_string(), 10.0, alice.to_string()).unwrap();
+
+        mempool.submit_transaction(transfer("tx_parent", ("genesis_alice", 10.0), 5.0, &alice, &bob)).unwrap();
+        mempool.submit_transaction(transfer("tx_child", ("tx_parent:0", 5.0), 2.0, &bob, &carol)).unwrap();
+        let child = mempool.raw_tx.get_transaction("tx_child").unwrap().tx_data.clone();
+        let (change_id, change, _) = output_utxos("tx_child", &child).pop().unwrap();
+        assert_eq!(change_id, "tx_child:change");
+        mempool.submit_transaction(transfer("tx_grandchild", (&change_id, change), 1.0, &bob, &alice)).unwrap();
+
+        let graph = mempool.dependency_graph();
+        let dependencies = graph.dependencies("tx_child");
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].tx_id, "tx_parent");
+        assert_eq!(dependencies[0].stage, MempoolStage::Raw);
+        assert_eq!(dependencies[0].utxo_ids, vec!["tx_parent:0".to_string()]);
+        assert_eq!(graph.dependents("tx_parent")[0].tx_id, "tx_child");
+        assert_eq!(graph.descendants("tx_parent"), vec!["tx_child".to_string(), "tx_grandchild".to_string()]);
+
+        let invalidated = mempool.invalidate_transaction("tx_parent").unwrap();
+        assert_eq!(invalidated, vec!["tx_parent".to_string(), "tx_child".to_string(), "tx_grandchild".to_string()]);
+        assert!(mempool.raw_tx.get_transaction("tx_grandchild").is_none());
+        assert!(!mempool.locked_utxo.is_utxo_locked("tx_child:change"));
+        assert!(!mempool.locked_utxo.is_utxo_locked("tx_parent:0"));
+        assert!(!mempool.locked_utxo.is_utxo_locked("genesis_alice"));
+    }
+
+    #[test]
+    fn test_pending_output_must_match_and_child_waits_for_parent() {
+        // Test: Spend a pending output with the wrong owner or amount, then finalize a child before its parent
+        // Expected: Mismatches are rejected; the child only finalizes once the parent's outputs exist
+        println!("Expected: Pending outputs are spendable only as created, and only after the parent finalizes");
+
+        let (alice, bob) = (user(), user());
+        let mut mempool = MempoolManager::new();
+        mempool.tx.create_utxo("genesis_alice".to_string(), 10.0, alice.to_string()).unwrap();
+        mempool.submit_transaction(transfer("tx_parent", ("genesis_alice", 10.0), 5.0, &alice, &bob)).unwrap();
+
+        assert!(mempool.submit_transaction(transfer("tx_thief", ("tx_parent:0", 5.0), 2.0, &alice, &alice)).is_err());
+        assert!(mempool.submit_transaction(transfer("tx_inflated", ("tx_parent:0", 6.0), 2.0, &bob, &alice)).is_err());
+        assert!(mempool.submit_transaction(transfer("tx_unknown", ("tx_missing:0", 5.0), 2.0, &bob, &alice)).is_err());
+        mempool.submit_transaction(transfer("tx_child", ("tx_parent:0", 5.0), 2.0, &bob, &alice)).unwrap();
+
+        mempool.start_processing("tx_child", "leader_1", "leader_sig").unwrap();
+        assert_eq!(mempool.dependency_graph().stage("tx_child"), Some(MempoolStage::Processing));
+        assert!(mempool.finalize_processing("tx_child", "validator_sig").is_err());
+
+        mempool.start_processing("tx_parent", "leader_1", "leader_sig").unwrap();
+        mempool.finalize_processing("tx_parent", "validator_sig").unwrap();
+        assert!(mempool.dependency_graph().dependencies("tx_child").is_empty());
+        mempool.finalize_processing("tx_child", "validator_sig").unwrap();
+        assert!(mempool.tx.utxo_pool.contains_key("tx_child:0"));
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_discovery_config_and_multiaddrs() {
+        // Test: Parse static peer multiaddrs, set mDNS, static peers and seeds from the environment,
+        // then configure a malformed multiaddr and a seed without a port
+        // Expected: mDNS is on by default, multiaddrs dial as host:port and bad entries fail validation
+        println!("Expected: mDNS can be turned off and peers named as multiaddrs and DNS seeds");
+
+        assert!(NetworkConfig::default().enable_mdns);
+        assert_eq!(parse_multiaddr("/ip4/10.0.0.5/tcp/9000").unwrap(), "10.0.0.5:9000");
+        assert_eq!(parse_multiaddr("/ip6/::1/tcp/9000/p2p/peer_a").unwrap(), "[::1]:9000");
+        assert_eq!(parse_multiaddr("/dns4/pcl-node-0.pcl/tcp/9000").unwrap(), "pcl-node-0.pcl:9000");
+        for bad in ["10.0.0.5:9000", "/ip4/10.0.0.5/udp/9000", "/ip4/not-an-ip/tcp/9000", "/ip4/10.0.0.5/tcp/0"] {
+            assert!(matches!(parse_multiaddr(bad), Err(PclError::Config(_))), "{} should be rejected", bad);
+        }
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| match key {
+            "PCL_ENABLE_MDNS" => Some("false".to_string()),
+            "PCL_STATIC_PEERS" => Some("/ip4/10.0.0.5/tcp/9000, /dns/pcl-node-1/tcp/9000".to_string()),
+            "PCL_DNS_SEEDS" => Some("pcl-nodes.default.svc:9000,".to_string()),
+            _ => None,
+        }).unwrap();
+        assert!(!config.network.enable_mdns);
+        assert_eq!(config.network.static_peers.len(), 2);
+        assert_eq!(config.network.dns_seeds, vec!["pcl-nodes.default.svc:9000".to_string()]);
+        assert!(config.validate().is_ok());
+
+        config.network.static_peers.push("10.0.0.6:9000".to_string());
+        assert!(config.validate().is_err());
+        config.network.static_peers.pop();
+        config.network.dns_seeds.push("pcl-nodes".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_static_peers_and_dns_seeds_are_dialed_once() {
+        // Test: Dial a static peer and a DNS seed that resolves to localhost, then dial again
+        // Expected: The first dial connects the static peer and every seed address; the second finds
+        // them all connected and dials nothing
+        println!("Expected: Startup dials static peers and resolved seeds, refreshes skip connected ones");
+
+        let node = Node::new("10.0.0.1".parse().unwrap(), &NodeKeypair::new()).unwrap();
+        let mut network = NetworkManager::new(node).await.unwrap();
+        let config = NetworkConfig {
+            enable_mdns: false,
+            static_peers: vec!["/ip4/10.0.0.5/tcp/9000".to_string()],
+            dns_seeds: vec!["localhost:9100".to_string()],
+            ..NetworkConfig::default()
+        };
+        let mut discovery = PeerDiscovery::new(&config).unwrap();
+        network.set_mdns_enabled(discovery.mdns_enabled());
+        assert!(!network.mdns_enabled);
+
+        let dialed = discovery.dial(&mut network).await.unwrap();
+        assert!(dialed.contains(&"10.0.0.5:9000".to_string()));
+        let seed_addresses = discovery.seed_addresses();
+        assert!(!seed_addresses.is_empty());
+        assert!(seed_addresses.iter().all(|address| dialed.contains(address) && address.ends_with(":9100")));
+        assert_eq!(network.peers.read().await.len(), dialed.len());
+
+        assert_eq!(discovery.resolve_seeds().await, 0);
+        assert!(discovery.dial(&mut network).await.unwrap().is_empty());
+    }
+}
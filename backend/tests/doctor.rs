@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn ntp_timestamp(unix_ms: u64) -> [u8; 8] {
+        let seconds = (unix_ms / 1000 + 2_208_988_800) as u32;
+        let fraction = (((unix_ms % 1000) << 32) / 1000) as u32;
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+        bytes[4..].copy_from_slice(&fraction.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_sntp_offset_calculation() {
+        // Test: Compute the clock offset from an SNTP reply whose server clock runs 2s ahead
+        // Expected: The offset is +2000ms regardless of the 100ms round trip, and unset timestamps are rejected
+        println!("Expected: SNTP offset is derived from the four exchange timestamps");
+
+        let sent_at: u64 = 1_700_000_000_000;
+        let received_at = sent_at + 100;
+        let mut response = [0u8; 48];
+        response[32..40].copy_from_slice(&ntp_timestamp(sent_at + 50 + 2_000));
+        response[40..48].copy_from_slice(&ntp_timestamp(sent_at + 50 + 2_000));
+        let offset = doctor::sntp_offset_ms(&response, sent_at as i64, received_at as i64).unwrap();
+        assert!((offset - 2_000).abs() <= 1, "offset {}", offset);
+
+        assert!(doctor::sntp_offset_ms(&[0u8; 48], sent_at as i64, received_at as i64).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_doctor_checks_report_pass_and_fail() {
+        // Test: Run the storage, port and bootnode checks against a fresh data dir, a port in use and
+        // a bootnode nothing listens on
+        // Expected: Storage passes, the busy port and dead bootnode fail, and the report is not passed
+        println!("Expected: Doctor checks report failures that would stop a node from starting");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = doctor::check_storage(&dir.path().join("pcl_data"));
+        assert_eq!(storage.status, CheckStatus::Pass, "{}", storage.detail);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let busy_port = listener.local_addr().unwrap().port();
+        assert_eq!(doctor::check_port(busy_port).await.status, CheckStatus::Fail);
+        drop(listener);
+        assert_eq!(doctor::check_port(busy_port).await.status, CheckStatus::Pass);
+
+        let skipped = doctor::check_bootnodes(&[]).await;
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].status, CheckStatus::Skip);
+
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let dead_bootnode = closed.local_addr().unwrap().to_string();
+        drop(closed);
+        let bootnodes = doctor::check_bootnodes(&[dead_bootnode]).await;
+        assert_eq!(bootnodes[0].status, CheckStatus::Fail);
+
+        let mut report = DoctorReport { checks: vec![storage] };
+        assert!(report.passed());
+        report.checks.extend(bootnodes);
+        assert!(!report.passed());
+        assert_eq!(report.count(CheckStatus::Fail), 1);
+    }
+}
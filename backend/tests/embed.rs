@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn tx_data(amount: f64) -> TransactionData {
+        TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), amount)],
+            vec![(format!("utxo_in_{}", amount), amount + 1.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    async fn embedded_node(dir: &std::path::Path) -> EmbeddedNode {
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let node = EmbeddedNode::open(local_node, &local_keypair, dir, ConsensusConfig::default()).await.unwrap();
+        node.consensus().leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        node
+    }
+
+    // Creates the UTXOs the transaction spends, then submits it
+    async fn submit_funded(node: &EmbeddedNode, data: TransactionData) -> Result<String> {
+        for (utxo_id, amount) in &data.from {
+            node.consensus().mempool.tx.write().await.create_utxo(utxo_id.clone(), *amount, data.user.to_string())?;
+        }
+        node.submit_transaction(data).await
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_follow_a_transaction() {
+        // Test: Open an embedded node, subscribe to events for one recipient, submit a transaction to
+        // it, then query its status, the leaders and the metrics
+        // Expected: The subscriber sees all six steps and the finalization in order, the status reports
+        // the transaction final, and leaders and metrics come straight from the node
+        println!("Expected: An embedded node is driven without building protocol messages");
+
+        let dir = tempfile::tempdir().unwrap();
+        let node = embedded_node(dir.path()).await;
+        let data = tx_data(1.5);
+        let recipient = data.to[0].0.to_string();
+        let mut events = node.subscribe_events(EventFilter { addresses: vec![recipient.clone()], ..Default::default() });
+
+        let tx_id = submit_funded(&node, data.clone()).await.unwrap();
+        assert_eq!(tx_id, data.raw_tx_id().unwrap());
+
+        let mut stages = Vec::new();
+        for _ in 0..7 {
+            let event = events.next().await.unwrap();
+            assert_eq!((event.tx_id.as_str(), event.recipient.as_str(), event.amount), (tx_id.as_str(), recipient.as_str(), 1.5));
+            stages.push(event.stage);
+        }
+        let expected: Vec<String> = WorkflowStep::ALL.iter().map(|step| step.as_str().to_string())
+            .chain([FINALIZED_STAGE.to_string()])
+            .collect();
+        assert_eq!(stages, expected);
+
+        let status = node.query_status(&tx_id).await.unwrap();
+        assert!(status.finalized);
+        assert_eq!(status.workflow_step, None);
+        assert_eq!(node.current_leaders().await, vec!["leader_a".to_string()]);
+        assert_eq!(node.metrics().await.unwrap().current_leaders, vec!["leader_a".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_filter_and_unknown_transactions() {
+        // Test: Subscribe with a minimum amount, submit a small and then a large transaction, and query
+        // a transaction that was never submitted
+        // Expected: Only the large transaction's events reach the subscriber, and the unknown id is an error
+        println!("Expected: Embedded subscriptions apply their filter and unknown statuses are errors");
+
+        let dir = tempfile::tempdir().unwrap();
+        let node = embedded_node(dir.path()).await;
+        let mut events = node.subscribe_events(EventFilter { min_amount: Some(10.0), stages: vec![FINALIZED_STAGE.to_string()], ..Default::default() });
+
+        let small = submit_funded(&node, tx_data(1.0)).await.unwrap();
+        let large = submit_funded(&node, tx_data(25.0)).await.unwrap();
+        let event = events.next().await.unwrap();
+        assert_eq!((event.tx_id.as_str(), event.stage.as_str()), (large.as_str(), FINALIZED_STAGE));
+        assert!(node.query_status(&small).await.unwrap().finalized);
+
+        assert!(node.query_status("never_submitted").await.is_err());
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn event(stage: &str, sender: &str, recipient: &str, amount: f64) -> StreamEvent {
+        StreamEvent::new(stage, "tx_1", sender, recipient, amount, 1_000)
+    }
+
+    #[test]
+    fn test_filter_matches_address_amount_and_stage() {
+        // Test: Parse a filter for two addresses, a minimum amount and two stages, then match events
+        // that miss one condition at a time
+        // Expected: Only events meeting every condition match, and an empty filter matches everything
+        println!("Expected: Event filters select by sender or recipient, minimum amount and stage");
+
+        let filter = EventFilter::from_query("address=alice,bob&min_amount=5&stage=finalized&stage=submission&limit=3").unwrap();
+        assert_eq!(filter.addresses, vec!["alice", "bob"]);
+        assert_eq!(filter.min_amount, Some(5.0));
+        assert_eq!(filter.stages, vec![FINALIZED_STAGE, "submission"]);
+
+        assert!(filter.matches(&event(FINALIZED_STAGE, "carol", "bob", 5.0)));
+        assert!(filter.matches(&event("submission", "alice", "carol", 7.5)));
+        assert!(!filter.matches(&event(FINALIZED_STAGE, "carol", "dave", 10.0)));
+        assert!(!filter.matches(&event(FINALIZED_STAGE, "alice", "bob", 4.9)));
+        assert!(!filter.matches(&event("task_assignment", "alice", "bob", 10.0)));
+
+        let everything = EventFilter::from_query("").unwrap();
+        assert_eq!(everything, EventFilter::default());
+        assert!(everything.matches(&event("task_completion", "carol", "dave", 0.0)));
+    }
+
+    #[test]
+    fn test_filter_rejects_bad_parameters_and_events_render_as_sse() {
+        // Test: Parse filters with an unknown stage and a non-numeric amount, then render an event
+        // Expected: Both filters are validation errors, and the event is a named SSE frame whose data
+        // line is the event as JSON
+        println!("Expected: Bad filters are refused and events are framed as server-sent events");
+
+        assert!(matches!(EventFilter::from_query("stage=mined"), Err(PclError::Validation(_))));
+        assert!(matches!(EventFilter::from_query("min_amount=lots"), Err(PclError::Validation(_))));
+        assert!(WorkflowStep::ALL.iter().all(|step| is_known_stage(step.as_str())));
+
+        let finalized = event(FINALIZED_STAGE, "alice", "bob", 2.5);
+        let frame = finalized.to_sse();
+        assert!(frame.starts_with("event: finalized\ndata: "));
+        assert!(frame.ends_with("\n\n"));
+        let data = frame.lines().nth(1).unwrap().strip_prefix("data: ").unwrap();
+        assert_eq!(serde_json::from_str::<StreamEvent>(data).unwrap(), finalized);
+    }
+}
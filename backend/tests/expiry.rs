@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn tx_data(utxo: &str) -> TransactionData {
+        TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![(utxo.to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    fn expiring(raw_tx_id: &str, utxo: &str, expires_in: chrono::Duration) -> RawTransaction {
+        let mut data = tx_data(utxo);
+        data.set_expiry(chrono::Utc::now() + expires_in);
+        RawTransaction::new(raw_tx_id.to_string(), data)
+    }
+
+    #[test]
+    fn test_expires_at_is_signed_and_optional() {
+        // Test: Set an expiry on a transaction, compare its signing bytes and id with and without one,
+        // and prepare an HTTP submission that carries expires_at
+        // Expected: Without an expiry nothing about the serialized transaction changes; with one the
+        // expiry is covered by the signature, and it counts as expired from expires_at onwards
+        println!("Expected: expires_at is optional, signed when set, and honored from that instant");
+
+        let mut data = tx_data("utxo_1");
+        let unset = data.signing_bytes().unwrap();
+        assert!(!String::from_utf8(unset.clone()).unwrap().contains("expires_at"));
+        assert!(!data.is_expired_at(chrono::Utc::now()));
+
+        let expires_at = chrono::Utc::now();
+        data.set_expiry(expires_at);
+        assert_ne!(data.signing_bytes().unwrap(), unset);
+        assert!(!data.is_expired_at(expires_at - chrono::Duration::milliseconds(1)));
+        assert!(data.is_expired_at(expires_at));
+
+        let signed_id = data.raw_tx_id().unwrap();
+        data.set_expiry(expires_at + chrono::Duration::seconds(60));
+        assert_ne!(data.raw_tx_id().unwrap(), signed_id);
+        let roundtrip: TransactionData = serde_json::from_slice(&serde_json::to_vec(&data).unwrap()).unwrap();
+        assert_eq!(roundtrip.expires_at, data.expires_at);
+
+        let prepared = prepare_submission(&serde_json::json!({"to": "a", "user": "b", "expires_at": 1_700_000_000_000u64, "note": "x"})).unwrap();
+        assert_eq!(prepared["expires_at"], 1_700_000_000_000u64);
+        assert!(prepared.get("note").is_none());
+
+        assert_eq!(PclError::Expired("tx".to_string()).code(), "EXPIRED");
+        assert!(is_known_stage(EXPIRED_STAGE));
+    }
+
+    #[tokio::test]
+    async fn test_leaders_drop_expired_transactions_and_unlock_inputs() {
+        // Test: Hold an expired, a live and an expired-but-processing transaction in the mempool, run
+        // the expiry sweep, then submit a transaction that has already expired
+        // Expected: Only the expired one not yet processing is dropped, its input unlocked and an expired
+        // event emitted; the late submission is refused with EXPIRED before reaching the mempool
+        println!("Expected: Leaders never start validating or settling a transaction past its expires_at");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let mut events = consensus.events.subscribe();
+
+        let stale = expiring("tx_stale", "utxo_stale", chrono::Duration::seconds(-1));
+        let live = expiring("tx_live", "utxo_live", chrono::Duration::hours(1));
+        let processing = expiring("tx_processing", "utxo_processing", chrono::Duration::seconds(-1));
+        for tx in [&stale, &live, &processing] {
+            let (utxo_id, amount) = tx.tx_data.from[0].clone();
+            consensus.mempool.tx.write().await.create_utxo(utxo_id, amount, tx.tx_data.user.to_string()).unwrap();
+            consensus.mempool.submit_transaction(tx.clone()).await.unwrap();
+        }
+        let mut timings = WorkflowTimings::new();
+        timings.start(WorkflowStep::Submission, 1_000);
+        timings.start(WorkflowStep::ValidationProcessing, 2_000);
+        consensus.consensus_state.write().await.active_transactions.insert("tx_processing".to_string(), TransactionWorkflowState {
+            tx_id: "tx_processing".to_string(),
+            current_step: 4,
+            workflow_data: TransactionWorkflowData {
+                alice_transaction: Some(processing.clone()),
+                charlie_processing: None,
+                validation_tasks: Vec::new(),
+                alice_completion: None,
+                charlie_final_processing: None,
+                validator_broadcast: None,
+                amount_commitment: None,
+                amount_opening: None,
+            },
+            start_time: chrono::Utc::now(),
+            last_update: chrono::Utc::now(),
+            timings,
+        });
+
+        assert_eq!(consensus.expire_transactions(chrono::Utc::now()).await.unwrap(), vec!["tx_stale".to_string()]);
+        let raw_tx = consensus.mempool.raw_tx.read().await;
+        assert!(raw_tx.get_transaction("tx_stale").is_none());
+        assert!(raw_tx.get_transaction("tx_live").is_some());
+        assert!(raw_tx.get_transaction("tx_processing").is_some());
+        drop(raw_tx);
+        let locked = consensus.mempool.locked_utxo.read().await;
+        assert!(!locked.is_utxo_locked("utxo_stale"));
+        assert!(locked.is_utxo_locked("utxo_live"));
+        drop(locked);
+
+        let event = events.try_recv().unwrap();
+        assert_eq!((event.stage.as_str(), event.tx_id.as_str()), (EXPIRED_STAGE, "tx_stale"));
+
+        let late = expiring("tx_late", "utxo_late", chrono::Duration::seconds(-1));
+        match consensus.process_transaction_workflow(late).await {
+            Err(e @ PclError::Expired(_)) => assert_eq!(e.code(), "EXPIRED"),
+            other => panic!("expected an expiry error, got {:?}", other),
+        }
+        assert!(consensus.mempool.raw_tx.read().await.get_transaction("tx_late").is_none());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    fn record(tx_id: &str) -> ExportRecord {
+        ExportRecord {
+            tx_id: tx_id.to_string(),
+            sender: "alice".to_string(),
+            from_utxo: "utxo_alice_1".to_string(),
+            recipient: "bob".to_string(),
+            amount: 1.5,
+            fee: 0.1,
+            leader_id: Some("leader_1".to_string()),
+            validators: vec!["validator_1".to_string(), "validator_2".to_string()],
+            validation_steps: vec!["Leader consensus".to_string()],
+            digital_root: 7,
+            finalized_at: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_export_outbox_batches_in_order() {
+        // Test: Queue five records and read them back with a batch size of two
+        // Expected: Batches come back in append order and the cursor only moves on acknowledge
+        println!("Expected: Export batches respect order and batch size");
+
+        let dir = tempfile::tempdir().unwrap();
+        let pipeline = ExportPipeline::new(Arc::new(StorageManager::new(dir.path()).unwrap()), 2);
+        for i in 0..5 {
+            assert_eq!(pipeline.enqueue(&record(&format!("tx_{}", i))).unwrap(), i + 1);
+        }
+
+        let first = pipeline.next_batch().unwrap();
+        assert_eq!(first.iter().map(|(seq, r)| (*seq, r.tx_id.as_str())).collect::<Vec<_>>(), vec![(1, "tx_0"), (2, "tx_1")]);
+
+        // Not acknowledged yet, so the same batch is delivered again
+        assert_eq!(pipeline.next_batch().unwrap(), first);
+
+        pipeline.acknowledge(2).unwrap();
+        assert_eq!(pipeline.cursor().unwrap(), 2);
+        let second = pipeline.next_batch().unwrap();
+        assert_eq!(second[0].1.tx_id, "tx_2");
+        assert_eq!(second[0].1, record("tx_2"));
+    }
+
+    #[test]
+    fn test_export_cursor_survives_drained_outbox() {
+        // Test: Acknowledge everything, then queue another record
+        // Expected: Sequence numbers keep increasing instead of restarting at 1
+        println!("Expected: Drained outbox keeps sequence numbers monotonic");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let pipeline = ExportPipeline::new(storage.clone(), 10);
+        pipeline.enqueue(&record("tx_a")).unwrap();
+        pipeline.enqueue(&record("tx_b")).unwrap();
+        pipeline.acknowledge(2).unwrap();
+        assert!(pipeline.next_batch().unwrap().is_empty());
+
+        assert_eq!(pipeline.enqueue(&record("tx_c")).unwrap(), 3);
+        assert_eq!(storage.load_export_records(0, 10).unwrap().len(), 1);
+        assert_eq!(pipeline.next_batch().unwrap()[0].1.tx_id, "tx_c");
+    }
+
+    #[test]
+    fn test_finalization_sequence_pages_from_cursor_across_restarts() {
+        // Test: Record three finalizations, reopen the database, record two more and page through
+        // them with a cursor and limit
+        // Expected: Sequence numbers continue after the restart and pages follow finalization order
+        println!("Expected: The finalization index is monotonic and pageable by cursor");
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let storage = StorageManager::new(dir.path()).unwrap();
+            for tx_id in ["tx_a", "tx_b", "tx_c"] {
+                storage.append_finalization(tx_id).unwrap();
+            }
+        }
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert_eq!(storage.append_finalization("tx_d").unwrap(), 4);
+        assert_eq!(storage.append_finalization("tx_b").unwrap(), 5);
+
+        let page = storage.load_finalizations(0, 2).unwrap();
+        assert_eq!(page, vec![(1, "tx_a".to_string()), (2, "tx_b".to_string())]);
+        let page = storage.load_finalizations(2, 10).unwrap();
+        assert_eq!(page.iter().map(|(seq, _)| *seq).collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert!(storage.load_finalizations(5, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    const SECOND_MS: u64 = 1000;
+
+    fn market() -> FeeMarket {
+        FeeMarket::new(FeeConfig {
+            base_fee: 0.01,
+            fee_per_weight_unit: 0.0001,
+            target_mempool_depth: 10,
+            target_finalization_latency_ms: 1000,
+            window_secs: 60,
+            max_multiplier: 20.0,
+            max_reputation_discount: 0.2,
+        })
+    }
+
+    #[test]
+    fn test_idle_network_charges_base_fee() {
+        // Test: Estimate fees with no samples, and with depth and latency under their targets
+        // Expected: The floor is the base fee and the suggestions are ordered low < medium < high
+        println!("Expected: An idle network asks for the base fee only");
+
+        let mut market = market();
+        assert_eq!(market.minimum_fee(0, 0), 0.01);
+        market.record_depth(5, SECOND_MS);
+        market.record_finalization(500, SECOND_MS);
+
+        let estimate = market.estimate(0, 2 * SECOND_MS);
+        assert_eq!((estimate.minimum_fee, estimate.pressure), (0.01, 1.0));
+        assert_eq!((estimate.mempool_depth, estimate.finalization_latency_ms), (5.0, 500.0));
+        assert!(estimate.low == estimate.minimum_fee && estimate.low < estimate.medium && estimate.medium < estimate.high);
+        assert!(market.check_fee(0.01, 0, 2 * SECOND_MS).is_ok());
+    }
+
+    #[test]
+    fn test_mempool_pressure_raises_the_floor_and_refuses_low_fees() {
+        // Test: Record a mempool twice the target depth with finalizations twice the target latency,
+        // then an extreme backlog
+        // Expected: The floor is 4x base, a fee below it is refused with FEE_TOO_LOW and the current
+        // floor in the message, and the multiplier is capped
+        println!("Expected: Submissions below the pressure-adjusted minimum fee are rejected");
+
+        let mut market = market();
+        market.record_depth(20, SECOND_MS);
+        market.record_finalization(2000, SECOND_MS);
+        assert!((market.minimum_fee(0, SECOND_MS) - 0.04).abs() < 1e-12);
+
+        let err = market.check_fee(0.02, 0, SECOND_MS).unwrap_err();
+        assert_eq!(err.code(), "FEE_TOO_LOW");
+        assert!(err.to_string().contains("minimum fee of 0.040000"), "{}", err);
+        assert!(market.check_fee(0.05, 0, SECOND_MS).is_ok());
+        assert!(market.check_fee(f64::NAN, 0, SECOND_MS).is_err());
+
+        market.record_depth(100_000, 2 * SECOND_MS);
+        assert_eq!(market.pressure(2 * SECOND_MS), 20.0);
+    }
+
+    #[test]
+    fn test_floor_recovers_once_samples_leave_the_window() {
+        // Test: Record a backlog, then only look at the market after the 60s window has passed
+        // Expected: The old samples no longer count and the floor is back at the base fee
+        println!("Expected: The minimum fee falls back once mempool pressure clears");
+
+        let mut market = market();
+        market.record_depth(50, SECOND_MS);
+        assert!(market.minimum_fee(0, SECOND_MS) > 0.01);
+        assert_eq!(market.minimum_fee(0, 62 * SECOND_MS), 0.01);
+
+        market.record_depth(0, 62 * SECOND_MS);
+        assert_eq!(market.estimate(0, 62 * SECOND_MS).mempool_depth, 0.0);
+    }
+
+    fn transfer(outputs: usize) -> TransactionData {
+        let to = (0..outputs).map(|_| (Address::parse(&NodeKeypair::new().address()).unwrap(), 1.0)).collect();
+        TransactionData::new(
+            to,
+            vec![("utxo_in".to_string(), outputs as f64 + 1.0)],
+            Address::parse(&NodeKeypair::new().address()).unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    #[test]
+    fn test_fee_scales_with_transaction_weight() {
+        // Test: Weigh a one-output and a fifty-output transaction, attach a signature to one, and price both under 2x pressure
+        // Expected: Weight grows with outputs and signatures, and the floor is (base + per-unit * weight) * pressure
+        println!("Expected: Heavier transactions need proportionally higher fees");
+
+        let small = transfer(1);
+        let large = transfer(50);
+        assert!(large.weight() > small.weight() + 49 * WEIGHT_PER_IO);
+        let mut signed = small.clone();
+        signed.set_signature("ab".repeat(64));
+        assert!(signed.weight() >= small.weight() + WEIGHT_PER_SIGNATURE);
+
+        let mut market = market();
+        market.record_depth(20, SECOND_MS);
+        let expected = (0.01 + 0.0001 * large.weight() as f64) * 2.0;
+        assert!((market.minimum_fee(large.weight(), SECOND_MS) - expected).abs() < 1e-12);
+        assert!(market.check_fee(market.minimum_fee(small.weight(), SECOND_MS), large.weight(), SECOND_MS).is_err());
+
+        let estimate = market.estimate(large.weight(), SECOND_MS);
+        assert_eq!(estimate.weight, large.weight());
+        assert!((estimate.fee_per_weight - 0.0002).abs() < 1e-12);
+        assert!(estimate.low == estimate.minimum_fee && estimate.medium < estimate.high);
+    }
+
+    #[test]
+    fn test_mempool_refuses_transactions_over_max_weight() {
+        // Test: Submit a transaction with 200 outputs and one with 10 to a mempool holding their input
+        // Expected: The heavy one is refused for exceeding MAX_TX_WEIGHT, the light one is accepted
+        println!("Expected: Validation caps transaction weight");
+
+        for (outputs, accepted) in [(200, false), (10, true)] {
+            let tx_data = transfer(outputs);
+            assert_eq!(tx_data.weight() <= MAX_TX_WEIGHT, accepted);
+            let mut mempool = MempoolManager::new();
+            mempool.tx.create_utxo("utxo_in".to_string(), outputs as f64 + 1.0, tx_data.user.to_string()).unwrap();
+
+            let result = mempool.submit_transaction(RawTransaction::new(format!("tx_{}", outputs), tx_data));
+            assert_eq!(result.is_ok(), accepted, "{:?}", result);
+            if !accepted {
+                assert!(result.unwrap_err().to_string().contains("exceeds the 16384 limit"));
+            }
+        }
+        assert!(check_tx_weight(MAX_TX_WEIGHT).is_ok());
+    }
+}
\ No newline at end of file
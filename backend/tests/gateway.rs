@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn signed_submission(sender: &NodeKeypair, amount: f64) -> serde_json::Value {
+        let unsigned = prepare_submission(&serde_json::json!({
+            "to": NodeKeypair::new().address(),
+            "from": "utxo_gateway",
+            "amount": amount,
+            "user": sender.address(),
+            "fee": 0.1,
+        })).unwrap();
+        sign_submission(&unsigned, sender).unwrap()
+    }
+
+    #[test]
+    fn test_submission_converts_to_transaction_data() {
+        // Test: Convert a signed HTTP submission, then a tampered one, an unsigned one and one without a UTXO
+        // Expected: The signed body becomes one output and one input covering amount, stake and fee with
+        // the sender's signature; the others are refused
+        println!("Expected: Only signed, complete submissions reach the consensus node");
+
+        let sender = NodeKeypair::new();
+        let body = signed_submission(&sender, 2.0);
+        let tx_data = transaction_from_submission(&body).unwrap();
+        assert_eq!(tx_data.user.as_str(), sender.address());
+        assert_eq!(tx_data.to.len(), 1);
+        assert_eq!(tx_data.to[0].0.as_str(), body["to"].as_str().unwrap());
+        assert_eq!(tx_data.to[0].1, 2.0);
+        assert_eq!(tx_data.from, vec![("utxo_gateway".to_string(), 2.0 + 0.2 + 0.1)]);
+        assert_eq!(tx_data.change, None);
+        assert_eq!(tx_data.sig.as_deref(), body["sig"].as_str());
+
+        let mut tampered = body.clone();
+        tampered["amount"] = serde_json::json!(20.0);
+        assert_eq!(transaction_from_submission(&tampered).unwrap_err().code(), "INVALID_SIGNATURE");
+        let mut unsigned = body.clone();
+        unsigned.as_object_mut().unwrap().remove("sig");
+        assert!(transaction_from_submission(&unsigned).is_err());
+
+        let mut no_utxo = prepare_submission(&serde_json::json!({
+            "to": NodeKeypair::new().address(), "user": sender.address(), "fee": 0.1,
+        })).unwrap();
+        no_utxo = sign_submission(&no_utxo, &sender).unwrap();
+        assert!(transaction_from_submission(&no_utxo).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gateway_forwards_and_reads_back() {
+        // Test: Submit a signed body through a gateway over an embedded node, then read it back with its
+        // receipt, the mempool counts and a balance, and look up a transaction that was never submitted
+        // Expected: The transaction is finalized by the node, its stored record and a receipt signed by
+        // the node's identity key are returned, and counts and balances come from the node; the unknown
+        // id is an error
+        println!("Expected: Gateway reads come from the consensus node and its database");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let node = EmbeddedNode::open(local_node, &local_keypair, dir.path(), ConsensusConfig::default()).await.unwrap();
+        node.consensus().leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        let gateway = Gateway::new(node);
+
+        let sender = NodeKeypair::new();
+        gateway.node().consensus().mempool.tx.write().await.create_utxo("utxo_gateway".to_string(), 1.8, sender.address()).unwrap();
+        let tx_id = gateway.submit(&signed_submission(&sender, 1.5)).await.unwrap();
+        let view = gateway.transaction(&tx_id).await.unwrap();
+        assert_eq!(view.status.tx_id, tx_id);
+        assert!(view.status.finalized);
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(json["finalized"], serde_json::json!(true));
+
+        let receipt = gateway.receipt(&tx_id).unwrap().unwrap();
+        assert!(receipt.verify().is_ok());
+        let identity = hex::encode(local_keypair.public_key().to_bytes());
+        assert_eq!(receipt.leader_signature.public_key, identity);
+        assert_eq!(receipt.validator_signatures.len(), 1);
+        assert_eq!(receipt.validator_signatures[0].public_key, identity);
+
+        let counts = gateway.mempool_counts().await;
+        assert_eq!(counts["tx_mempool"]["count"], serde_json::json!(1));
+        assert!(counts["raw_tx_mempool"]["count"].as_u64().is_some());
+
+        let owner = NodeKeypair::new().address();
+        gateway.node().consensus().mempool.tx.write().await.create_utxo("utxo_owned".to_string(), 4.0, owner.clone()).unwrap();
+        assert_eq!(gateway.balance(&owner).await.unwrap(), 4.0);
+        assert!(gateway.balance("not_an_address").await.is_err());
+
+        assert!(gateway.transaction("tx_missing").await.is_err());
+        assert!(gateway.receipt("tx_missing").unwrap().is_none());
+    }
+}
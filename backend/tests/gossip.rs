@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn peers(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("peer_{:02}", i)).collect()
+    }
+
+    #[test]
+    fn test_mesh_heartbeat_and_history() {
+        // Test: Run heartbeats with a small mesh configuration as peers join and leave, remember
+        // message ids across more heartbeats than the history holds, and compare publish targets
+        // Expected: The mesh is grafted to mesh_n below mesh_n_low, pruned to mesh_n above mesh_n_high,
+        // ids expire after history_length windows and flood publishing reaches every peer
+        println!("Expected: Mesh sizes and history follow the configured gossip parameters");
+
+        let mut config = NetworkConfig {
+            gossip_mesh_n: 3,
+            gossip_mesh_n_low: 2,
+            gossip_mesh_n_high: 4,
+            gossip_history_length: 3,
+            gossip_history_gossip: 2,
+            gossip_flood_publish: false,
+            ..NetworkConfig::default()
+        };
+        let mut mesh = GossipMesh::new(&config);
+
+        let connected = peers(8);
+        let change = mesh.heartbeat(&connected);
+        assert_eq!(change.grafted.len(), 3);
+        assert_eq!(mesh.mesh_peers().len(), 3);
+
+        // Two mesh peers leave: below mesh_n_low, so the mesh is refilled to mesh_n
+        let mesh_peers = mesh.mesh_peers();
+        let remaining: Vec<String> = connected.iter().filter(|peer| !mesh_peers[..2].contains(peer)).cloned().collect();
+        let change = mesh.heartbeat(&remaining);
+        assert_eq!(change.pruned.len(), 2);
+        assert_eq!(change.grafted.len(), 2);
+        assert_eq!(mesh.mesh_peers().len(), 3);
+        assert!(mesh.mesh_peers().iter().all(|peer| remaining.contains(peer)));
+
+        // Shrinking mesh_n_high prunes the mesh back to mesh_n on the next heartbeat
+        config.gossip_mesh_n = 1;
+        config.gossip_mesh_n_low = 1;
+        config.gossip_mesh_n_high = 2;
+        mesh.set_params(&config);
+        assert_eq!(mesh.heartbeat(&remaining).pruned.len(), 2);
+        assert_eq!(mesh.mesh_peers().len(), 1);
+        assert_eq!(mesh.publish_targets(&remaining), mesh.mesh_peers());
+        config.gossip_flood_publish = true;
+        mesh.set_params(&config);
+        assert_eq!(mesh.publish_targets(&remaining), remaining);
+
+        assert!(mesh.remember("msg_a"));
+        assert!(!mesh.remember("msg_a"));
+        mesh.heartbeat(&remaining);
+        assert!(mesh.remember("msg_b"));
+        assert_eq!(mesh.gossip_ids(), vec!["msg_b".to_string(), "msg_a".to_string()]);
+        mesh.heartbeat(&remaining);
+        assert_eq!(mesh.gossip_ids(), vec!["msg_b".to_string()]);
+        // msg_a is still in the third window, so it is still a repeat until that window expires
+        assert!(!mesh.remember("msg_a"));
+        mesh.heartbeat(&remaining);
+        assert!(mesh.remember("msg_a"));
+    }
+
+    #[tokio::test]
+    async fn test_gossip_config_validation_and_repeat_drop() {
+        // Test: Configure inconsistent mesh sizes and history, then feed the network manager the same
+        // message twice
+        // Expected: Bad parameters fail validation; the repeat is dropped without counting as rejected
+        println!("Expected: Gossip parameters are validated and repeated messages are dropped");
+
+        let mut config = NodeConfig::default();
+        assert!(config.validate().is_ok());
+        config.network.gossip_mesh_n_low = config.network.gossip_mesh_n + 1;
+        assert!(config.validate().is_err());
+        config.network = NetworkConfig::default();
+        config.network.gossip_mesh_n_high = config.network.gossip_mesh_n - 1;
+        assert!(config.validate().is_err());
+        config.network = NetworkConfig::default();
+        config.network.gossip_history_gossip = config.network.gossip_history_length + 1;
+        assert!(config.validate().is_err());
+        config.network = NetworkConfig::default();
+        config.network.gossip_heartbeat_interval_ms = 0;
+        assert!(config.validate().is_err());
+
+        let keypair = NodeKeypair::new();
+        let node = Node::new_with_string_ip("127.0.0.1".to_string(), keypair, NodeRole::Extension).unwrap();
+        let mut network = NetworkManager::new(node).await.unwrap();
+        network.set_gossip_config(&NetworkConfig::default());
+        let message = NetworkMessage::UptimeData(UptimeMessage {
+            node_id: "node1".to_string(),
+            uptime_percentage: 99.0,
+            last_seen: chrono::Utc::now(),
+            pulse_count: 10,
+        });
+        let encoded = String::from_utf8(message.encode().unwrap()).unwrap();
+        network.handle_network_event(NetworkEvent::Message(encoded.clone())).await.unwrap();
+        network.handle_network_event(NetworkEvent::Message(encoded)).await.unwrap();
+
+        assert_eq!(network.gossip.gossip_ids(), vec![gossip_message_id(&message)]);
+        assert_eq!(network.get_network_stats().await.rejected_messages, 0);
+    }
+}
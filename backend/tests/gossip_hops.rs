@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn notice(raw_tx_id: &str, hops: u32) -> NetworkMessage {
+        NetworkMessage::TransactionInvalidation(TransactionInvalidationMessage {
+            raw_tx_id: raw_tx_id.to_string(),
+            invalidated_leader_id: "leader_b".to_string(),
+            winning_leader_id: "leader_a".to_string(),
+            reason: "lost fork tie-break".to_string(),
+            sender_id: "leader_a".to_string(),
+            timestamp: chrono::DateTime::from_timestamp_millis(1_700_000_000_000).unwrap(),
+            sender_public_key: String::new(),
+            signature: String::new(),
+            evidence: None,
+            replaced_by: None,
+            hops,
+        })
+    }
+
+    #[test]
+    fn test_relay_bumps_hops_once_per_node() {
+        // Test: Relay notices through a mesh with max_hops 2, including a repeat after the history
+        // windows have expired, and check the hop limit config
+        // Expected: A relayed copy is one hop further with the original's id, each notice is relayed
+        // once, notices at max_hops and non-notice messages are not relayed
+        println!("Expected: Relayed notices carry a bounded hop count and go out once per node");
+
+        let config = NetworkConfig { gossip_max_hops: 2, gossip_history_length: 2, gossip_history_gossip: 1, ..NetworkConfig::default() };
+        let mut mesh = GossipMesh::new(&config);
+
+        let relayed = mesh.relay(&notice("tx1", 0)).unwrap();
+        assert_eq!(relay_hops(&relayed), Some(1));
+        assert_eq!(gossip_message_id(&relayed), gossip_message_id(&notice("tx1", 0)));
+        assert!(mesh.relay(&notice("tx1", 0)).is_none());
+        assert!(mesh.relay(&relayed).is_none());
+
+        // Long after the repeat filter forgets it, the notice is still not relayed again
+        for _ in 0..5 {
+            mesh.heartbeat(&[]);
+        }
+        assert!(mesh.remember(&gossip_message_id(&notice("tx1", 0))));
+        assert!(mesh.relay(&notice("tx1", 0)).is_none());
+
+        assert_eq!(relay_hops(&mesh.relay(&notice("tx2", 1)).unwrap()), Some(2));
+        assert!(mesh.relay(&notice("tx3", 2)).is_none());
+        assert_eq!(mesh.hop_limited, 1);
+
+        let uptime = NetworkMessage::UptimeData(UptimeMessage {
+            node_id: "n1".to_string(),
+            uptime_percentage: 99.0,
+            last_seen: chrono::Utc::now(),
+            pulse_count: 1,
+        });
+        assert_eq!(relay_hops(&uptime), None);
+        assert!(mesh.relay(&uptime).is_none());
+
+        let mut node_config = NodeConfig::default();
+        node_config.apply_env_overrides(|key| (key == "PCL_GOSSIP_MAX_HOPS").then(|| "4".to_string())).unwrap();
+        assert_eq!(node_config.network.gossip_max_hops, 4);
+        node_config.network.gossip_max_hops = 0;
+        assert!(node_config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notice_floods_network_exactly_once_per_node() {
+        // Test: Connect five network managers to each other, publish one invalidation notice and keep
+        // delivering every node's outgoing messages to all the others until nothing new is sent
+        // Expected: Delivery stops on its own and every node sent the notice exactly once
+        println!("Expected: An invalidation notice reaches every node and each node passes it on once");
+
+        let names: Vec<String> = (0..5).map(|i| format!("node_{}", i)).collect();
+        let mut nodes = Vec::new();
+        for name in &names {
+            let node = Node::new_with_string_ip("127.0.0.1".to_string(), NodeKeypair::new(), NodeRole::Extension).unwrap();
+            let mut network = NetworkManager::new(node).await.unwrap();
+            for peer in names.iter().filter(|peer| *peer != name) {
+                network.handle_network_event(NetworkEvent::PeerConnected(peer.clone())).await.unwrap();
+            }
+            nodes.push(network);
+        }
+
+        nodes[0].broadcast_transaction_invalidation("tx1", "leader_b", "leader_a", "lost fork tie-break", None, &NodeKeypair::new()).await.unwrap();
+
+        let mut delivered = vec![0; nodes.len()];
+        for _round in 0..20 {
+            let mut outgoing = Vec::new();
+            for (i, node) in nodes.iter().enumerate() {
+                let history = node.get_message_history().await;
+                for message in &history[delivered[i]..] {
+                    outgoing.push((i, String::from_utf8(message.encode().unwrap()).unwrap()));
+                }
+                delivered[i] = history.len();
+            }
+            if outgoing.is_empty() {
+                break;
+            }
+            for (from, encoded) in outgoing {
+                for (to, node) in nodes.iter_mut().enumerate() {
+                    if to == from {
+                        continue;
+                    }
+                    node.handle_network_event(NetworkEvent::PeerMessage(names[from].clone(), encoded.clone())).await.unwrap();
+                }
+            }
+        }
+
+        for node in &nodes {
+            let sent = node.get_message_history().await.iter()
+                .filter(|message| matches!(message, NetworkMessage::TransactionInvalidation(_)))
+                .count();
+            assert_eq!(sent, 1);
+        }
+        assert_eq!(delivered.iter().sum::<usize>(), nodes.len());
+    }
+}
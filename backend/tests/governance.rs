@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::collections::HashMap;
+
+    fn leaders(count: usize) -> (Vec<NodeKeypair>, HashMap<String, ed25519_dalek::VerifyingKey>) {
+        let keypairs: Vec<NodeKeypair> = (0..count).map(|_| NodeKeypair::new()).collect();
+        let keys = keypairs.iter().enumerate()
+            .map(|(i, keypair)| (format!("leader_{}", i), keypair.public_key()))
+            .collect();
+        (keypairs, keys)
+    }
+
+    #[test]
+    fn test_proposal_schedules_at_quorum_and_applies_when_due() {
+        // Test: Propose a fanout change with three leaders, co-sign it from an outsider and then a leader,
+        // and apply due proposals before and after effective_at
+        // Expected: Outsider signatures are ignored, two of three leaders schedule it, and the active
+        // parameters change only once effective_at has passed
+        println!("Expected: Leader-signed proposals apply at their effective time once a majority signs");
+
+        let (keypairs, keys) = leaders(3);
+        let mut governance = Governance::new(ParameterSet::default());
+        let mut proposal = GovernanceProposal::new("leader_0", &keypairs[0], ParameterChange::BroadcastFanout(5), 2_000, 1_000).unwrap();
+        assert_eq!(governance.submit(&proposal, &keys, 1_000).unwrap(), ProposalStatus::Pending);
+
+        proposal.cosign("outsider", &NodeKeypair::new()).unwrap();
+        assert_eq!(governance.submit(&proposal, &keys, 1_100).unwrap(), ProposalStatus::Pending);
+        proposal.cosign("leader_1", &keypairs[1]).unwrap();
+        assert_eq!(governance.submit(&proposal, &keys, 1_200).unwrap(), ProposalStatus::Scheduled);
+        assert_eq!(governance.proposal(&proposal.proposal_id).unwrap().proposal.signatures.len(), 2);
+
+        assert!(governance.apply_due(1_999).is_empty());
+        assert_eq!(governance.active().consensus.broadcast_fanout, ConsensusConfig::default().broadcast_fanout);
+        assert_eq!(governance.apply_due(2_000).len(), 1);
+        assert_eq!(governance.active().consensus.broadcast_fanout, 5);
+        assert_eq!(governance.proposal(&proposal.proposal_id).unwrap().status, ProposalStatus::Applied);
+    }
+
+    #[test]
+    fn test_invalid_proposals_are_refused() {
+        // Test: Submit proposals from a non-leader, with an effective time in the past, with invalid
+        // values (including more than one required leader signature), and with a change swapped after signing
+        // Expected: Each is refused and nothing is tracked
+        println!("Expected: Unsigned, stale, invalid and tampered proposals are refused");
+
+        let (keypairs, keys) = leaders(3);
+        let mut governance = Governance::new(ParameterSet::default());
+
+        let outsider = GovernanceProposal::new("outsider", &NodeKeypair::new(), ParameterChange::BaseFee(0.01), 2_000, 1_000).unwrap();
+        assert!(matches!(governance.submit(&outsider, &keys, 1_000), Err(PclError::SignatureVerification(_))));
+        let stale = GovernanceProposal::new("leader_0", &keypairs[0], ParameterChange::BaseFee(0.01), 900, 800).unwrap();
+        assert!(matches!(governance.submit(&stale, &keys, 1_000), Err(PclError::Validation(_))));
+        let invalid = GovernanceProposal::new("leader_0", &keypairs[0], ParameterChange::MinValidationCompletions(0), 2_000, 1_000).unwrap();
+        assert!(governance.submit(&invalid, &keys, 1_000).is_err());
+        let co_signed = GovernanceProposal::new("leader_0", &keypairs[0], ParameterChange::RequiredLeaderSignatures(2), 2_000, 1_000).unwrap();
+        assert_eq!(governance.submit(&co_signed, &keys, 1_000).unwrap_err().code(), "CONFIG_ERROR");
+        let mut tampered = GovernanceProposal::new("leader_0", &keypairs[0], ParameterChange::BaseFee(0.01), 2_000, 1_000).unwrap();
+        tampered.change = ParameterChange::BaseFee(100.0);
+        assert!(matches!(governance.submit(&tampered, &keys, 1_000), Err(PclError::SignatureVerification(_))));
+
+        assert_eq!(governance.proposals().count(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_readiness_lists_every_failed_check() {
+        // Test: Evaluate readiness with every check passing, then with a failed storage probe, no
+        // peers, consensus still initializing and a drifted clock
+        // Expected: Ready with no reasons first; not ready with one reason per failed check after
+        println!("Expected: Readiness is 503-worthy with a reason for each failed check");
+
+        let ready = Readiness::evaluate(Ok(()), MIN_READY_PEERS, ConsensusPhase::NormalOperation, true);
+        assert!(ready.ready && ready.reasons.is_empty() && ready.db_writable);
+
+        let not_ready = Readiness::evaluate(
+            Err(PclError::Storage("disk full".to_string())), 0, ConsensusPhase::Initialization, false,
+        );
+        assert!(!not_ready.ready && !not_ready.db_writable);
+        assert_eq!(not_ready.reasons.len(), 4);
+        assert!(not_ready.reasons[0].contains("disk full"));
+
+        let json = serde_json::to_value(&not_ready).unwrap();
+        assert_eq!(json["consensus_phase"], "initialization");
+        assert_eq!(json["reasons"].as_array().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_consensus_manager_is_ready_with_a_peer_in_normal_operation() {
+        // Test: Check readiness of a new consensus manager, then connect a peer and enter normal operation
+        // Expected: Storage is writable throughout; the peer and phase reasons go away once both are met
+        println!("Expected: A node reports ready only with a writable database, a peer and normal operation");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let before = consensus.readiness().await;
+        assert!(!before.ready && before.db_writable);
+        assert_eq!(before.peers, 0);
+        assert_eq!(before.consensus_phase, ConsensusPhase::Initialization);
+
+        consensus.network_manager.lock().await.connect_to_peer("10.0.0.2:9000").await.unwrap();
+        consensus.consensus_state.write().await.current_phase = ConsensusPhase::NormalOperation;
+        let after = consensus.readiness().await;
+        assert_eq!(after.peers, 1);
+        assert!(after.db_writable);
+        // The clock check reads process-wide state other tests may flip
+        assert_eq!(after.ready, after.clock_healthy);
+        assert!(after.reasons.len() <= 1);
+    }
+}
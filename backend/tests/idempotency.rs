@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_same_key_and_body_replays_the_original_response() {
+        // Test: Remember a submission under a key, check it again with the same body, from another
+        // scope, and after the TTL; then round-trip the store through JSON as the snapshot does
+        // Expected: The retry replays the stored response, another client using the same key starts
+        // fresh, the record is gone once it is over 24h old, and a restored store still replays
+        println!("Expected: A retry with the same idempotency key and body gets the original response");
+
+        let mut store = IdempotencyStore::new();
+        assert_eq!(store.check("user:alice", "retry-1", "hash_a", 1_000), IdempotencyCheck::New);
+        store.remember("user:alice", "retry-1", "tx_1", "hash_a", "{\"transaction_id\":\"tx_1\"}", 1_000);
+
+        match store.check("user:alice", "retry-1", "hash_a", 2_000) {
+            IdempotencyCheck::Replay(record) => {
+                assert_eq!(record.raw_tx_id, "tx_1");
+                assert_eq!(record.response, "{\"transaction_id\":\"tx_1\"}");
+            }
+            other => panic!("expected a replay, got {:?}", other),
+        }
+        assert_eq!(store.check("user:bob", "retry-1", "hash_a", 2_000), IdempotencyCheck::New);
+
+        let mut restored: IdempotencyStore = serde_json::from_str(&serde_json::to_string(&store).unwrap()).unwrap();
+        assert!(matches!(restored.check("user:alice", "retry-1", "hash_a", 3_000), IdempotencyCheck::Replay(_)));
+
+        assert_eq!(store.check("user:alice", "retry-1", "hash_a", 1_000 + IDEMPOTENCY_TTL_MS + 1), IdempotencyCheck::New);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_same_key_with_a_different_body_conflicts() {
+        // Test: Reuse a remembered key with a different request body, in the same scope and in another
+        // Expected: The same scope reports a conflict naming the original transaction; another scope
+        // is unaffected and can remember its own record under the key
+        println!("Expected: Reusing an idempotency key for a different request is rejected");
+
+        let mut store = IdempotencyStore::new();
+        store.remember("key:abc", "order-7", "tx_7", "hash_a", "{}", 0);
+
+        match store.check("key:abc", "order-7", "hash_b", 10) {
+            IdempotencyCheck::Conflict(record) => assert_eq!(record.raw_tx_id, "tx_7"),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+
+        assert_eq!(store.check("key:def", "order-7", "hash_b", 10), IdempotencyCheck::New);
+        store.remember("key:def", "order-7", "tx_8", "hash_b", "{}", 10);
+        assert_eq!(store.len(), 2);
+        assert!(matches!(store.check("key:abc", "order-7", "hash_a", 20), IdempotencyCheck::Replay(_)));
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    struct Cluster {
+        consensus: ConsensusManager,
+        ids: Vec<String>, // two current leaders, then a registered non-leader
+        keypairs: Vec<NodeKeypair>,
+        _dir: tempfile::TempDir,
+    }
+
+    async fn cluster() -> Cluster {
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let mut ids = Vec::new();
+        let mut keypairs = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3", "10.0.0.4"] {
+            let keypair = NodeKeypair::new();
+            let node = Node::new(ip.parse().unwrap(), &keypair).unwrap();
+            ids.push(node.id.to_string());
+            keypairs.push(keypair);
+            consensus.node_registry.write().await.register_node(node).unwrap();
+        }
+        consensus.leader_election.write().await.current_leaders = ids[..2].to_vec();
+        Cluster { consensus, ids, keypairs, _dir: dir }
+    }
+
+    fn signed_entry(raw_tx_id: &str, leader: &NodeKeypair, leader_id: &str, timestamp_ms: i64) -> ProcessingTransaction {
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo1".to_string(), 5.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        let mut entry = ProcessingTransaction::new(raw_tx_id.to_string(), tx_data, String::new(), leader_id.to_string());
+        entry.timestamp = chrono::DateTime::from_timestamp_millis(timestamp_ms).unwrap();
+        entry.sign(leader).unwrap();
+        entry
+    }
+
+    fn notice(raw_tx_id: &str, invalidated: &str, winning: &str, sender: &str) -> TransactionInvalidationMessage {
+        TransactionInvalidationMessage {
+            raw_tx_id: raw_tx_id.to_string(),
+            invalidated_leader_id: invalidated.to_string(),
+            winning_leader_id: winning.to_string(),
+            reason: "lost fork tie-break".to_string(),
+            sender_id: sender.to_string(),
+            timestamp: chrono::Utc::now(),
+            sender_public_key: String::new(),
+            signature: String::new(),
+            evidence: None,
+            replaced_by: None,
+            hops: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unverifiable_notices_are_refused_and_penalized() {
+        // Test: Hold a processing entry from one leader, then deliver an unsigned notice, a notice signed
+        // by a registered non-leader, and one naming a leader as sender but signed by someone else
+        // Expected: All are refused with the entry kept; only the non-leader that signed its own notice
+        // loses reputation, and every refusal is counted
+        println!("Expected: Invalidation notices without a leader signature or evidence change nothing");
+
+        let Cluster { consensus, ids, keypairs, .. } = cluster().await;
+        let entry = signed_entry("raw_tx_1", &keypairs[1], &ids[1], 1_000);
+        consensus.mempool.processing_tx.write().await.add_transaction(entry).unwrap();
+
+        let unsigned = notice("raw_tx_1", &ids[1], &ids[0], &ids[0]);
+        assert!(consensus.handle_transaction_invalidation_notice(&unsigned).await.is_err());
+
+        let mut outsider = notice("raw_tx_1", &ids[1], &ids[0], &ids[2]);
+        outsider.sign(&keypairs[2]);
+        assert!(outsider.verify_signature().is_ok());
+        assert!(consensus.handle_transaction_invalidation_notice(&outsider).await.is_err());
+
+        let mut impersonation = notice("raw_tx_1", &ids[1], &ids[0], &ids[0]);
+        impersonation.sign(&keypairs[2]);
+        assert!(matches!(
+            consensus.handle_transaction_invalidation_notice(&impersonation).await,
+            Err(PclError::SignatureVerification(_))
+        ));
+
+        assert!(consensus.mempool.processing_tx.read().await.transactions.contains_key("raw_tx_1"));
+        let reputation = consensus.reputation.read().await;
+        assert_eq!(reputation.get(&ids[2]).invalidated_submissions, 1);
+        assert_eq!(reputation.score(&ids[0]), 0.5);
+        drop(reputation);
+        assert_eq!(consensus.get_system_status().await.unwrap().rejected_invalidation_notices, 3);
+    }
+
+    #[tokio::test]
+    async fn test_leader_signed_or_evidence_backed_notices_clean_up() {
+        // Test: Deliver a notice signed by a current leader, and a notice from a non-leader carrying
+        // fork evidence, each for an entry held from the losing leader
+        // Expected: Both remove the losing entry; evidence whose entries are in the wrong order is refused
+        println!("Expected: Verified invalidation notices drop the losing processing entry");
+
+        let Cluster { consensus, ids, keypairs, .. } = cluster().await;
+        let mut processing_tx = consensus.mempool.processing_tx.write().await;
+        processing_tx.add_transaction(signed_entry("raw_tx_1", &keypairs[1], &ids[1], 1_000)).unwrap();
+        processing_tx.add_transaction(signed_entry("raw_tx_2", &keypairs[1], &ids[1], 2_000)).unwrap();
+        drop(processing_tx);
+
+        let mut signed = notice("raw_tx_1", &ids[1], &ids[0], &ids[0]);
+        signed.sign(&keypairs[0]);
+        assert!(consensus.handle_transaction_invalidation_notice(&signed).await.unwrap());
+
+        let winning = signed_entry("raw_tx_2", &keypairs[0], &ids[0], 1_500);
+        let losing = signed_entry("raw_tx_2", &keypairs[1], &ids[1], 2_000);
+        assert!(ForkEvidence::new(losing.clone(), &keypairs[1].public_key(), winning.clone(), &keypairs[0].public_key()).is_err());
+
+        let mut backed = notice("raw_tx_2", &ids[1], &ids[0], &ids[2]);
+        backed.evidence = Some(ForkEvidence::new(winning, &keypairs[0].public_key(), losing, &keypairs[1].public_key()).unwrap());
+        assert!(consensus.handle_transaction_invalidation_notice(&backed).await.unwrap());
+
+        assert!(consensus.mempool.processing_tx.read().await.transactions.is_empty());
+        assert_eq!(consensus.get_system_status().await.unwrap().rejected_invalidation_notices, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lost_fork_notice_is_signed_by_the_node_and_carries_evidence() {
+        // Test: A current leader's entry for a raw transaction arrives by gossip and its earlier claim
+        // is recorded, then the same transaction runs through this node's workflow
+        // Expected: Finalization is refused and the invalidation notice for this node's entry is signed
+        // by the node's identity key and carries verifiable evidence that the other leader won
+        println!("Expected: Fork invalidation notices are signed by the node and backed by fork evidence");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let local_id = local_node.id.to_string();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let winner_keypair = NodeKeypair::new();
+        let winner = Node::new("10.0.0.2".parse().unwrap(), &winner_keypair).unwrap();
+        let winner_id = winner.id.to_string();
+        consensus.node_registry.write().await.register_node(winner).unwrap();
+        consensus.leader_election.write().await.current_leaders = vec![winner_id.clone(), local_id.clone()];
+
+        let entry = signed_entry("raw_tx_fork", &winner_keypair, &winner_id, 1_000);
+        consensus.handle_processing_transaction_gossip(&ProcessingTransactionGossipMessage {
+            tx_id: "raw_tx_fork".to_string(),
+            processing_transaction: entry.clone(),
+            sender_id: winner_id.clone(),
+            timestamp: chrono::Utc::now(),
+        }).await.unwrap();
+        consensus.mempool.record_finalization_claim(FinalizationClaim {
+            raw_tx_id: "raw_tx_fork".to_string(),
+            leader_id: winner_id.clone(),
+            leader_public_key: hex::encode(winner_keypair.public_key().to_bytes()),
+            averaged_timestamp: entry.timestamp,
+        }).await;
+
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        assert!(consensus.process_transaction_workflow(RawTransaction::new("raw_tx_fork".to_string(), tx_data)).await.is_err());
+
+        let history = consensus.network_manager.lock().await.message_history.read().await.clone();
+        let notice = history.into_iter().find_map(|message| match message {
+            NetworkMessage::TransactionInvalidation(notice) => Some(notice),
+            _ => None,
+        }).unwrap();
+        assert_eq!(notice.invalidated_leader_id, local_id);
+        assert_eq!(notice.winning_leader_id, winner_id);
+        assert_eq!(notice.sender_public_key, hex::encode(local_keypair.public_key().to_bytes()));
+        assert!(notice.verify_signature().is_ok());
+
+        let evidence = notice.evidence.unwrap();
+        assert!(evidence.verify().is_ok());
+        assert_eq!(evidence.winning_entry.leader, winner_id);
+        assert_eq!(evidence.losing_entry.leader, local_id);
+        assert_eq!(evidence.losing_public_key, hex::encode(local_keypair.public_key().to_bytes()));
+        assert!(!consensus.mempool.processing_tx.read().await.transactions.contains_key("raw_tx_fork"));
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use pcl_backend::*;
 
     // Pulse System Tests (20 second intervals)
     #[test]
@@ -321,4 +322,47 @@ mod tests {
         println!("Expected: Smooth transition between old and new leader sets");
         // Implementation will test leader transition continuity
     }
+
+    // Leader Performance Tests
+    #[test]
+    fn test_leader_performance_tracks_workflow_and_pulses() {
+        // Test: Record originations, task assignments and completions, signatures, finalizations and pulses
+        // Expected: Counters and running averages match the recorded events and the score reflects them
+        println!("Expected: LeaderPerformance aggregates workflow events into dashboard statistics");
+
+        let mut performance = LeaderPerformance::new("leader_1");
+        assert_eq!(performance.last_signature_at, None);
+
+        performance.record_originated();
+        performance.record_tasks_assigned(4);
+        performance.record_task_completed(200);
+        performance.record_task_completed(400);
+        let signed_at = chrono::Utc::now();
+        performance.record_signature(signed_at, 1_000);
+        performance.record_signature(signed_at, 3_000);
+        performance.record_finalized();
+        performance.record_pulse(90.0, 100.0, signed_at);
+        performance.record_pulse(95.0, 300.0, signed_at);
+
+        assert_eq!(performance.transactions_originated, 1);
+        assert_eq!(performance.transactions_signed, 2);
+        assert_eq!(performance.transactions_processed, 1);
+        assert_eq!(performance.validation_tasks_assigned, 4);
+        assert_eq!(performance.validation_tasks_completed, 2);
+        assert!((performance.average_validation_latency_ms - 300.0).abs() < 1e-9);
+        assert!((performance.average_processing_time_ms - 2_000.0).abs() < 1e-9);
+        assert!((performance.average_response_time_ms - 200.0).abs() < 1e-9);
+        assert_eq!(performance.uptime_percentage, 95.0);
+        assert_eq!(performance.pulse_count, 2);
+        assert_eq!(performance.last_signature_at, Some(signed_at));
+
+        // Half the tasks done, 300ms latency, 95% uptime
+        let expected = 0.4 * 0.5 + 0.3 / 1.3 + 0.3 * 0.95;
+        assert!((performance.performance_score - expected).abs() < 1e-9);
+
+        // Finishing the outstanding tasks quickly raises the score
+        performance.record_task_completed(0);
+        performance.record_task_completed(0);
+        assert!(performance.performance_score > expected);
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::ledger::{public_key_request, sign_requests, check_status, INS_SIGN, P1_CONFIRM, P1_SIGN_INIT, P1_SIGN_ADD, P1_SIGN_LAST, MAX_APDU_DATA};
+
+    #[test]
+    fn test_ledger_apdu_encoding() {
+        // Test: Encode the wallet derivation path and split a long message into sign APDUs
+        // Expected: The path is a depth byte plus hardened big-endian indexes, and chunks fit one APDU each
+        println!("Expected: Ledger requests carry the BIP32 path and a chunked message");
+
+        let path = address_derivation_path(0, 5);
+        let encoded = encode_derivation_path(&path).unwrap();
+        assert_eq!(encoded.len(), 1 + 5 * 4);
+        assert_eq!(encoded[0], 5);
+        assert_eq!(&encoded[1..5], &(44 | 0x8000_0000u32).to_be_bytes());
+        assert_eq!(&encoded[17..21], &(5 | 0x8000_0000u32).to_be_bytes());
+        assert!(encode_derivation_path("44'/0'").is_err());
+        assert!(encode_derivation_path("m/x'").is_err());
+        assert!(encode_derivation_path("m").is_err());
+
+        let request = public_key_request(&path, true).unwrap();
+        assert_eq!(request.p1, P1_CONFIRM);
+        assert_eq!(request.data, encoded);
+
+        let message = vec![7u8; MAX_APDU_DATA * 2 + 10];
+        let requests = sign_requests(&path, &message).unwrap();
+        let flags: Vec<u8> = requests.iter().map(|r| r.p1).collect();
+        assert_eq!(flags, vec![P1_SIGN_INIT, P1_SIGN_ADD, P1_SIGN_ADD, P1_SIGN_LAST]);
+        assert!(requests.iter().all(|r| r.ins == INS_SIGN && r.data.len() <= MAX_APDU_DATA));
+        assert_eq!(requests[1..].iter().flat_map(|r| r.data.clone()).collect::<Vec<u8>>(), message);
+        assert!(sign_requests(&path, &[]).is_err());
+
+        assert!(check_status(0x9000).is_ok());
+        assert_eq!(check_status(0x6985).unwrap_err().code(), "HARDWARE_WALLET");
+    }
+
+    #[test]
+    fn test_external_signature_attaches_to_submission() {
+        // Test: Sign the submission hash outside sign_submission, as a device does, and attach it
+        // Expected: The attached signature verifies, and one from another key is refused
+        println!("Expected: Device signatures verify like wallet-made ones");
+
+        let device_key = NodeKeypair::new();
+        let unsigned = prepare_submission(&serde_json::json!({
+            "to": NodeKeypair::new().address(),
+            "from": "utxo_1",
+            "amount": 2.0,
+            "user": device_key.address(),
+            "fee": 0.1,
+        })).unwrap();
+
+        let signature = device_key.sign_data(&submission_hash(&unsigned).unwrap());
+        let signed = attach_submission_signature(&unsigned, &device_key.public_key(), &signature);
+        assert!(verify_submission_signature(&signed).is_ok());
+        assert_eq!(signed, sign_submission(&unsigned, &device_key).unwrap());
+
+        let other_key = NodeKeypair::new();
+        let wrong = attach_submission_signature(&unsigned, &other_key.public_key(), &other_key.sign_data(&submission_hash(&unsigned).unwrap()));
+        assert!(verify_submission_signature(&wrong).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn transaction_with_outputs(count: usize) -> TransactionData {
+        let recipient: Address = NodeKeypair::new().address().parse().unwrap();
+        TransactionData::new(
+            vec![(recipient, 0.1); count],
+            vec![("utxo1".to_string(), 1000.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    #[test]
+    fn test_oversized_collections_rejected_on_decode() {
+        // Test: Decode transactions whose output list is at and above the per-transaction cap
+        // Expected: The capped one round-trips through JSON and bincode, the larger one fails both
+        println!("Expected: Deserialization refuses Vecs longer than their declared cap");
+
+        let at_cap = transaction_with_outputs(limits::MAX_TX_IO);
+        let json = serde_json::to_vec(&at_cap).unwrap();
+        let decoded: TransactionData = decode_json(&json, MAX_MESSAGE_SIZE).unwrap();
+        assert_eq!(decoded.to.len(), limits::MAX_TX_IO);
+        let bin = bincode::serialize(&at_cap).unwrap();
+        assert!(decode_bincode::<TransactionData>(&bin, MAX_MESSAGE_SIZE).is_ok());
+
+        let over_cap = transaction_with_outputs(limits::MAX_TX_IO + 1);
+        let json = serde_json::to_vec(&over_cap).unwrap();
+        assert!(decode_json::<TransactionData>(&json, MAX_MESSAGE_SIZE).is_err());
+        let bin = bincode::serialize(&over_cap).unwrap();
+        assert!(decode_bincode::<TransactionData>(&bin, MAX_MESSAGE_SIZE).is_err());
+    }
+
+    #[test]
+    fn test_declared_length_does_not_drive_allocation() {
+        // Test: Bincode input whose length prefix claims billions of elements, and JSON over the byte cap
+        // Expected: Both fail fast without allocating for the claimed size
+        println!("Expected: Length checks happen before decoding");
+
+        let mut forged = Vec::new();
+        forged.extend_from_slice(&u64::MAX.to_le_bytes());
+        forged.extend_from_slice(&[0u8; 16]);
+        assert!(decode_bincode::<Vec<u64>>(&forged, 1024).is_err());
+
+        let oversized = vec![b' '; 2048];
+        assert!(decode_json::<serde_json::Value>(&oversized, 1024).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_network_drops_oversized_messages() {
+        // Test: Feed the network manager a valid message, an oversized one and garbage
+        // Expected: Only the valid message is accepted; the other two are counted as rejected
+        println!("Expected: Oversized and malformed gossip is dropped and counted");
+
+        let keypair = NodeKeypair::new();
+        let node = Node::new_with_string_ip("127.0.0.1".to_string(), keypair, NodeRole::Extension).unwrap();
+        let mut network = NetworkManager::new(node).await.unwrap();
+
+        let message = NetworkMessage::UptimeData(UptimeMessage {
+            node_id: "node1".to_string(),
+            uptime_percentage: 99.0,
+            last_seen: chrono::Utc::now(),
+            pulse_count: 10,
+        });
+        let encoded = String::from_utf8(message.encode().unwrap()).unwrap();
+        network.handle_network_event(NetworkEvent::Message(encoded)).await.unwrap();
+
+        let oversized = format!("\"{}\"", "a".repeat(MAX_MESSAGE_SIZE));
+        network.handle_network_event(NetworkEvent::Message(oversized)).await.unwrap();
+        network.handle_network_event(NetworkEvent::Message("not json".to_string())).await.unwrap();
+
+        assert_eq!(network.get_network_stats().await.rejected_messages, 2);
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::logging::{rotated_log_path, JsonEventFormat};
+    use std::io::Write;
+    use tracing_subscriber::fmt::format::JsonFields;
+
+    #[test]
+    fn test_rotating_file_writer_keeps_max_files() {
+        // Test: Write seven 40-byte lines through a writer that rotates at 100 bytes and keeps 2 old files
+        // Expected: Each file holds at most two lines, the newest line is in the active file and the
+        // first two lines have been rotated out entirely
+        println!("Expected: Log files rotate by size and old files are dropped");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("node.log");
+        let mut writer = RotatingFileWriter::new(&path, 100, 2).unwrap();
+        for i in 0..7 {
+            writer.write_all(format!("{:039}\n", i).as_bytes()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let read = |path: &std::path::Path| std::fs::read_to_string(path).unwrap();
+        assert_eq!(read(&path), format!("{:039}\n", 6));
+        assert_eq!(read(&rotated_log_path(&path, 1)), format!("{:039}\n{:039}\n", 4, 5));
+        assert_eq!(read(&rotated_log_path(&path, 2)), format!("{:039}\n{:039}\n", 2, 3));
+        assert!(!rotated_log_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn test_json_log_lines_carry_context_fields() {
+        // Test: Log through the JSON format inside a workflow span, once with tracing fields and once via the log crate
+        // Expected: Each line is a JSON object with level, message, node_id and the span's raw_tx_id;
+        // the tracing event also has peer_id
+        println!("Expected: JSON logs include node_id, peer_id and raw_tx_id");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        let subscriber = tracing_subscriber::fmt()
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonEventFormat)
+            .with_writer(RotatingFileWriter::new(&path, 1024 * 1024, 1).unwrap())
+            .finish();
+        set_log_node_id("node-a");
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("workflow", raw_tx_id = "tx-123");
+            let _entered = span.enter();
+            tracing::info!(peer_id = "peer-b", "Peer connected");
+            tracing::warn!("Second line");
+        });
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message"], "Peer connected");
+        assert_eq!(lines[0]["level"], "INFO");
+        assert_eq!(lines[0]["node_id"], "node-a");
+        assert_eq!(lines[0]["peer_id"], "peer-b");
+        assert_eq!(lines[0]["raw_tx_id"], "tx-123");
+        assert_eq!(lines[1]["level"], "WARN");
+        assert_eq!(lines[1]["raw_tx_id"], "tx-123");
+        assert!(lines[1].get("peer_id").is_none());
+    }
+
+    #[test]
+    fn test_logging_config_overrides_and_validation() {
+        // Test: Switch to JSON and a log file through PCL_* overrides, then give an unknown format and a zero size
+        // Expected: The overrides apply, the bad format and the zero rotation size are rejected
+        println!("Expected: Logging settings come from config and environment and are validated");
+
+        let mut config = NodeConfig::default();
+        assert_eq!(config.logging.format, LogFormat::Text);
+        config.apply_env_overrides(|key| match key {
+            "PCL_LOG_FORMAT" => Some("json".to_string()),
+            "PCL_LOG_FILE" => Some("/var/log/pcl/node.log".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.logging.file.as_deref(), Some("/var/log/pcl/node.log"));
+        assert!(config.validate().is_ok());
+
+        assert!(config.apply_env_overrides(|key| (key == "PCL_LOG_FORMAT").then(|| "xml".to_string())).is_err());
+        config.logging.max_file_size_mb = 0;
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(test)]
 mod tests {
+    use pcl_backend::*;
+    use chrono::Duration;
 
     // Raw Transaction Mempool Tests
     #[test]
@@ -208,4 +210,221 @@ mod tests {
         println!("Expected: Invalidation message gossiped to all leaders and nodes");
         // Implementation will gossip invalidation messages across network
     }
-} 
\ No newline at end of file
+
+    // Fork Resolution Tests
+    fn claim(leader_id: &str, public_key: &str, offset_ms: i64) -> FinalizationClaim {
+        FinalizationClaim {
+            raw_tx_id: "raw_tx_fork".to_string(),
+            leader_id: leader_id.to_string(),
+            leader_public_key: public_key.to_string(),
+            averaged_timestamp: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::milliseconds(offset_ms),
+        }
+    }
+
+    #[test]
+    fn test_fork_resolution_earliest_timestamp_wins() {
+        // Test: Two leaders finalize the same raw tx, the later one arrives first
+        // Expected: The earlier averaged timestamp replaces the later claim whatever the arrival order
+        println!("Expected: Earliest averaged timestamp wins the fork");
+
+        let mut pool = TxMempool::new();
+        let late = claim("leader_a", "00aa", 500);
+        let early = claim("leader_b", "ffbb", 0);
+
+        assert_eq!(pool.record_finalization_claim(late.clone()), ConflictResolution::Accepted);
+        assert_eq!(pool.record_finalization_claim(early.clone()), ConflictResolution::Replaced(late.clone()));
+        assert_eq!(pool.record_finalization_claim(late), ConflictResolution::Rejected(early.clone()));
+        assert_eq!(pool.finalization_claims["raw_tx_fork"], early);
+    }
+
+    #[test]
+    fn test_fork_resolution_ties_go_to_lowest_public_key() {
+        // Test: Two claims with identical averaged timestamps
+        // Expected: The lowest leader public key wins, duplicates are ignored
+        println!("Expected: Lowest leader public key breaks timestamp ties");
+
+        let mut pool = TxMempool::new();
+        let high = claim("leader_a", "9f00", 0);
+        let low = claim("leader_b", "1a00", 0);
+
+        assert_eq!(pool.record_finalization_claim(high.clone()), ConflictResolution::Accepted);
+        assert_eq!(pool.record_finalization_claim(low.clone()), ConflictResolution::Replaced(high));
+        assert_eq!(pool.record_finalization_claim(low), ConflictResolution::Duplicate);
+    }
+
+    #[test]
+    fn test_invalidate_claim_only_removes_named_leader() {
+        // Test: Invalidate a finalized entry naming the wrong leader, then the right one
+        // Expected: Only the matching leader's entry is removed
+        println!("Expected: Invalidation removes only the losing leader's entry");
+
+        let mut pool = TxMempool::new();
+        pool.record_finalization_claim(claim("leader_a", "00aa", 0));
+        pool.finalized_transactions.insert("raw_tx_fork".to_string(), finalized("raw_tx_fork"));
+
+        assert!(!pool.invalidate_claim("raw_tx_fork", "leader_b"));
+        assert!(pool.finalized_transactions.contains_key("raw_tx_fork"));
+        assert!(pool.invalidate_claim("raw_tx_fork", "leader_a"));
+        assert!(!pool.finalized_transactions.contains_key("raw_tx_fork"));
+    }
+
+    fn sample_tx_data() -> TransactionData {
+        TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        )
+    }
+
+    fn finalized(tx_id: &str) -> FinalizedTransaction {
+        let tx_data = sample_tx_data();
+        FinalizedTransaction {
+            tx_id: tx_id.to_string(),
+            xmbl_cubic_root: tx_data.calculate_digital_root() as u8,
+            tx_data,
+            validator_signature: "validator_sig".to_string(),
+            finalized_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_startup_recovery_repairs_snapshot() {
+        // Test: Persist a snapshot with an orphaned lock, an orphaned task, a finalized processing
+        // entry and a live transaction, reload it and run recovery
+        // Expected: Only the orphans are removed; state belonging to the live transaction survives
+        println!("Expected: Recovery scan releases orphans and keeps live mempool state");
+
+        let mut mempool = MempoolManager::new();
+        mempool.add_raw_transaction(RawTransaction::new("tx_live".to_string(), sample_tx_data())).unwrap();
+        mempool.lock_utxo("utxo_live".to_string(), 2.0, "tx_live".to_string()).unwrap();
+        mempool.add_validation_task(ValidationTask::new("tx_live_sig_validation".to_string(), "leader1".to_string(), ValidationTaskType::SignatureValidation)).unwrap();
+
+        mempool.lock_utxo("utxo_orphan".to_string(), 1.0, "tx_gone".to_string()).unwrap();
+        mempool.add_validation_task(ValidationTask::new("tx_gone_sig_validation".to_string(), "leader1".to_string(), ValidationTaskType::SignatureValidation)).unwrap();
+        // Finalized by an older node that left the processing entry behind
+        mempool.add_processing_transaction(ProcessingTransaction::new("tx_done".to_string(), sample_tx_data(), "sig".to_string(), "leader1".to_string())).unwrap();
+        mempool.tx.finalized_transactions.insert("tx_done".to_string(), finalized("tx_done"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageManager::new(dir.path()).unwrap();
+        storage.store_mempool_state(&mempool).unwrap();
+        let mut restored = storage.load_mempool_state().unwrap().unwrap();
+
+        let report = restored.recover();
+        assert_eq!(report.orphaned_locks_released, vec!["utxo_orphan".to_string()]);
+        assert_eq!(report.orphaned_tasks_removed, vec!["tx_gone_sig_validation".to_string()]);
+        assert_eq!(report.finalized_processing_removed, vec!["tx_done".to_string()]);
+        assert_eq!(report.dangling_index_entries, 1); // leader1's assignment list still named the orphaned task
+
+        assert!(restored.locked_utxo.is_utxo_locked("utxo_live"));
+        assert!(restored.validation_tasks.tasks.contains_key("tx_live_sig_validation"));
+        assert!(restored.processing_tx.transactions.is_empty());
+        assert!(restored.recover().is_clean());
+    }
+
+    #[test]
+    fn test_stale_lock_gc_releases_only_old_orphans() {
+        // Test: Run lock GC with one lock held by a live transaction and one whose transaction vanished
+        // Expected: The orphan survives until it is older than the ttl, then only it is released
+        println!("Expected: Lock GC releases orphaned locks past the ttl and keeps live ones");
+
+        let mut mempool = MempoolManager::new();
+        mempool.add_raw_transaction(RawTransaction::new("tx_live".to_string(), sample_tx_data())).unwrap();
+        mempool.lock_utxo("utxo_live".to_string(), 2.0, "tx_live".to_string()).unwrap();
+        mempool.lock_utxo("utxo_orphan".to_string(), 1.0, "tx_invalidated".to_string()).unwrap();
+
+        let ttl = Duration::minutes(10);
+        assert!(mempool.collect_stale_locks(ttl, chrono::Utc::now()).is_empty());
+
+        let later = chrono::Utc::now() + Duration::minutes(11);
+        assert_eq!(mempool.collect_stale_locks(ttl, later), vec!["utxo_orphan".to_string()]);
+        assert!(mempool.locked_utxo.is_utxo_locked("utxo_live"));
+        assert!(!mempool.locked_utxo.tx_locks.contains_key("tx_invalidated"));
+    }
+
+    #[test]
+    fn test_raw_transactions_stored_per_leader() {
+        // Test: Write raw transactions for two leaders, re-assign one, and migrate a record left under
+        // the old bare raw_tx_id key
+        // Expected: Each leader lists only its own transactions and the legacy record moves to its leader
+        println!("Expected: Raw transactions are keyed per (leader, raw_tx_id) and legacy keys migrate");
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut legacy = RawTransaction::new("tx_legacy".to_string(), sample_tx_data());
+        legacy.tx_data.leader = Some("leader_2".to_string());
+        {
+            // Pre-migration layout: the record sits directly under its raw_tx_id, untagged and with the
+            // version 1 field order (bincode writes structs as tuples; no expiry yet)
+            let mut opts = rocksdb::Options::default();
+            opts.create_if_missing(true);
+            opts.create_missing_column_families(true);
+            let cfs = [CF_RAW_TRANSACTIONS].map(|name| rocksdb::ColumnFamilyDescriptor::new(name, rocksdb::Options::default()));
+            let db = rocksdb::DB::open_cf_descriptors(&opts, dir.path(), cfs).unwrap();
+            let cf = db.cf_handle(CF_RAW_TRANSACTIONS).unwrap();
+            let d = &legacy.tx_data;
+            let tx_data_v1 = (&d.to, &d.from, &d.user, &d.sig, d.stake, d.fee, d.change, d.timestamp, &d.leader, d.nonce, &d.multisig, &d.signatures, &d.fee_payer);
+            let record = (&legacy.raw_tx_id, tx_data_v1, &legacy.validation_timestamps, &legacy.validation_tasks, legacy.tx_timestamp);
+            db.put_cf(cf, "tx_legacy", bincode::serialize(&record).unwrap()).unwrap();
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert_eq!(storage.migrate_raw_transaction_layout().unwrap(), 1);
+        assert_eq!(storage.migrate_raw_transaction_layout().unwrap(), 0);
+
+        storage.store_leader_raw_transaction("leader_1", &RawTransaction::new("tx_a".to_string(), sample_tx_data())).unwrap();
+        storage.store_leader_raw_transaction("leader_10", &RawTransaction::new("tx_b".to_string(), sample_tx_data())).unwrap();
+        let moved = RawTransaction::new("tx_c".to_string(), sample_tx_data());
+        storage.store_leader_raw_transaction("leader_1", &moved).unwrap();
+        storage.store_leader_raw_transaction("leader_2", &moved).unwrap();
+
+        let ids = |leader: &str| -> Vec<String> {
+            let mut ids: Vec<String> = storage.load_leader_raw_transactions(leader).unwrap().into_iter().map(|tx| tx.raw_tx_id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids("leader_1"), vec!["tx_a".to_string()]);
+        assert_eq!(ids("leader_2"), vec!["tx_c".to_string(), "tx_legacy".to_string()]);
+        assert_eq!(storage.raw_transaction_leader("tx_c").unwrap().as_deref(), Some("leader_2"));
+        assert!(storage.load_raw_transaction("tx_legacy").unwrap().is_some());
+
+        storage.delete_raw_transaction("tx_a").unwrap();
+        assert!(storage.load_raw_transaction("tx_a").unwrap().is_none());
+        assert_eq!(storage.get_storage_stats().unwrap().raw_transactions_count, 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_shared_mempool_concurrent_access() {
+        // Test: Concurrent writers on different pools of a SharedMempool, then invalidate one tx
+        // Expected: Every write lands, invalidation clears all pools, and the snapshot matches the stats
+        println!("Expected: Per-pool locks accept concurrent writers without losing updates");
+
+        let mempool = std::sync::Arc::new(SharedMempool::new());
+        let mut handles = Vec::new();
+        for worker in 0..4 {
+            let mempool = mempool.clone();
+            handles.push(tokio::spawn(async move {
+                for i in 0..25 {
+                    let tx_id = format!("tx_{}_{}", worker, i);
+                    mempool.add_raw_transaction(RawTransaction::new(tx_id.clone(), sample_tx_data())).await.unwrap();
+                    mempool.add_validation_task(ValidationTask::new(format!("{}_sig_validation", tx_id), "leader1".to_string(), ValidationTaskType::SignatureValidation)).await.unwrap();
+                    mempool.lock_utxo(format!("utxo_{}", tx_id), 1.0, tx_id).await.unwrap();
+                }
+            }));
+        }
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        mempool.invalidate_transaction("tx_0_0").await.unwrap();
+        let stats = mempool.get_mempool_stats().await;
+        assert_eq!(stats.raw_tx_count, 99);
+        assert_eq!(stats.validation_tasks_count, 99);
+        assert_eq!(stats.locked_utxo_count, 99);
+
+        let snapshot = mempool.snapshot().await;
+        assert_eq!(snapshot.get_mempool_stats().raw_tx_count, 99);
+        assert!(!snapshot.locked_utxo.is_utxo_locked("utxo_tx_0_0"));
+    }
+}
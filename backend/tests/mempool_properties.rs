@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use proptest::prelude::*;
+
+    const USERS: usize = 3;
+    const GENESIS_UTXOS_PER_USER: usize = 2;
+    const GENESIS_AMOUNT: f64 = 10.0;
+
+    // One step of mempool traffic; indexes are taken modulo whatever exists at that point
+    #[derive(Debug, Clone)]
+    enum Op {
+        // A user spends one of their unspent UTXOs, which may already be locked by a pending tx
+        Submit { sender: usize, recipient: usize, input: usize, fraction: f64 },
+        // An earlier transaction arrives again from a peer
+        Gossip { tx: usize },
+        // Validation tasks for an earlier transaction complete and its leader starts processing it
+        Complete { tx: usize },
+        Invalidate { tx: usize },
+        Finalize { tx: usize },
+    }
+
+    fn op_strategy() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            3 => (0..USERS, 0..USERS, 0..8usize, 0.1..0.9f64)
+                .prop_map(|(sender, recipient, input, fraction)| Op::Submit { sender, recipient, input, fraction }),
+            1 => (0..64usize).prop_map(|tx| Op::Gossip { tx }),
+            2 => (0..64usize).prop_map(|tx| Op::Complete { tx }),
+            1 => (0..64usize).prop_map(|tx| Op::Invalidate { tx }),
+            2 => (0..64usize).prop_map(|tx| Op::Finalize { tx }),
+        ]
+    }
+
+    struct Harness {
+        users: Vec<Address>,
+        submitted: Vec<RawTransaction>,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            Self {
+                users: (0..USERS).map(|_| Address::parse(&NodeKeypair::new().address()).unwrap()).collect(),
+                submitted: Vec::new(),
+            }
+        }
+
+        fn genesis(&self, pool: &mut TxMempool) {
+            for (i, user) in self.users.iter().enumerate() {
+                for j in 0..GENESIS_UTXOS_PER_USER {
+                    pool.create_utxo(format!("genesis_{}_{}", i, j), GENESIS_AMOUNT, user.to_string()).unwrap();
+                }
+            }
+        }
+
+        // Builds the transaction for a Submit against the current UTXO set, or None if the sender has nothing to spend
+        fn build(&mut self, pool: &TxMempool, sender: usize, recipient: usize, input: usize, fraction: f64) -> Option<RawTransaction> {
+            let owner = self.users[sender].to_string();
+            let mut spendable: Vec<&UtxoEntry> = pool.utxo_pool.values().filter(|utxo| !utxo.spent && utxo.owner == owner).collect();
+            if spendable.is_empty() {
+                return None;
+            }
+            spendable.sort_by(|a, b| a.utxo_id.cmp(&b.utxo_id));
+            let utxo = spendable[input % spendable.len()];
+            let amount = (utxo.amount - 0.15) * fraction;
+            let tx_data = TransactionData::new(
+                vec![(self.users[recipient].clone(), amount)],
+                vec![(utxo.utxo_id.clone(), utxo.amount)],
+                self.users[sender].clone(),
+                0.1,
+                0.05,
+            );
+            // Fixed width, since task ids are matched to their transaction by prefix
+            let tx = RawTransaction::new(format!("tx_{:04}", self.submitted.len()), tx_data);
+            self.submitted.push(tx.clone());
+            Some(tx)
+        }
+
+        fn pick(&self, tx: usize) -> Option<RawTransaction> {
+            if self.submitted.is_empty() {
+                None
+            } else {
+                Some(self.submitted[tx % self.submitted.len()].clone())
+            }
+        }
+    }
+
+    fn sig_task(tx_id: &str) -> ValidationTask {
+        ValidationTask::new(format!("{}_sig_validation", tx_id), "leader_1".to_string(), ValidationTaskType::SignatureValidation)
+    }
+
+    fn check_invariants(mempool: &MempoolManager) -> std::result::Result<(), TestCaseError> {
+        let conflicts = mempool.locked_utxo.lock_conflicts();
+        prop_assert!(conflicts.is_empty(), "UTXOs locked inconsistently: {:?}", conflicts);
+
+        for tx_id in mempool.tracked_transactions() {
+            let stages = mempool.stages_of(&tx_id);
+            prop_assert!(stages.len() <= 1, "{} is in {:?} at once", tx_id, stages);
+        }
+
+        for lock in mempool.locked_utxo.locked_utxos.values() {
+            let stages = mempool.stages_of(&lock.locked_by_tx);
+            prop_assert!(
+                stages == vec![MempoolStage::Raw] || stages == vec![MempoolStage::Processing],
+                "{} is locked by {}, which is not in flight ({:?})", lock.utxo_id, lock.locked_by_tx, stages
+            );
+        }
+
+        for (owner, balance) in mempool.tx.balances() {
+            prop_assert!(balance >= 0.0, "{} has a negative balance {}", owner, balance);
+        }
+        Ok(())
+    }
+
+    // Applies an op to the single-lock mempool; returns whether it was accepted
+    fn apply(harness: &mut Harness, mempool: &mut MempoolManager, op: &Op) -> Option<bool> {
+        match *op {
+            Op::Submit { sender, recipient, input, fraction } => {
+                let tx = harness.build(&mempool.tx, sender, recipient, input, fraction)?;
+                let tx_id = tx.raw_tx_id.clone();
+                let accepted = mempool.submit_transaction(tx).is_ok();
+                if accepted {
+                    mempool.add_validation_task(sig_task(&tx_id)).unwrap();
+                }
+                Some(accepted)
+            }
+            Op::Gossip { tx } => Some(mempool.submit_transaction(harness.pick(tx)?).is_ok()),
+            Op::Complete { tx } => {
+                let tx_id = harness.pick(tx)?.raw_tx_id;
+                mempool.validation_tasks.complete_task(&format!("{}_sig_validation", tx_id)).unwrap();
+                Some(mempool.start_processing(&tx_id, "leader_1", "leader_sig").is_ok())
+            }
+            Op::Invalidate { tx } => Some(mempool.invalidate_transaction(&harness.pick(tx)?.raw_tx_id).is_ok()),
+            Op::Finalize { tx } => Some(mempool.finalize_processing(&harness.pick(tx)?.raw_tx_id, "validator_sig").is_ok()),
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(128))]
+
+        #[test]
+        fn test_mempool_transitions_preserve_invariants(ops in prop::collection::vec(op_strategy(), 1..60)) {
+            // Test: Random sequences of submit/gossip/complete/invalidate/finalize against MempoolManager
+            // Expected: No UTXO locked twice, no tx in two stages, locks only held by in-flight txs,
+            // no negative balance, after every step
+            let mut harness = Harness::new();
+            let mut mempool = MempoolManager::new();
+            harness.genesis(&mut mempool.tx);
+
+            for op in &ops {
+                apply(&mut harness, &mut mempool, op);
+                check_invariants(&mempool)?;
+            }
+        }
+
+        #[test]
+        fn test_shared_mempool_matches_single_lock_mempool(ops in prop::collection::vec(op_strategy(), 1..40)) {
+            // Test: Replay the same random sequence against SharedMempool and MempoolManager
+            // Expected: Each op is accepted or rejected the same way, the shared snapshot upholds the
+            // same invariants and both end with identical stats
+            let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+            let mut harness = Harness::new();
+            let mut mempool = MempoolManager::new();
+            harness.genesis(&mut mempool.tx);
+            let shared = SharedMempool::from(mempool.clone());
+
+            for op in &ops {
+                // None: nothing to submit or pick yet, so neither mempool was touched
+                let Some(expected) = apply(&mut harness, &mut mempool, op) else {
+                    continue;
+                };
+                let actual = runtime.block_on(async {
+                    match *op {
+                        // The harness already built and recorded this submission above
+                        Op::Submit { .. } => {
+                            let tx = harness.submitted.last().unwrap().clone();
+                            let tx_id = tx.raw_tx_id.clone();
+                            let accepted = shared.submit_transaction(tx).await.is_ok();
+                            if accepted {
+                                shared.add_validation_task(sig_task(&tx_id)).await.unwrap();
+                            }
+                            accepted
+                        }
+                        Op::Gossip { tx } => shared.submit_transaction(harness.pick(tx).unwrap()).await.is_ok(),
+                        Op::Complete { tx } => {
+                            let tx_id = harness.pick(tx).unwrap().raw_tx_id;
+                            shared.validation_tasks.write().await.complete_task(&format!("{}_sig_validation", tx_id)).unwrap();
+                            shared.start_processing(&tx_id, "leader_1", "leader_sig").await.is_ok()
+                        }
+                        Op::Invalidate { tx } => shared.invalidate_transaction(&harness.pick(tx).unwrap().raw_tx_id).await.is_ok(),
+                        Op::Finalize { tx } => shared.finalize_processing(&harness.pick(tx).unwrap().raw_tx_id, "validator_sig").await.is_ok(),
+                    }
+                });
+                prop_assert_eq!(actual, expected, "{:?} diverged", op);
+                check_invariants(&runtime.block_on(shared.snapshot()))?;
+            }
+
+            let snapshot = runtime.block_on(shared.snapshot());
+            prop_assert_eq!(snapshot.tracked_transactions(), mempool.tracked_transactions());
+            prop_assert_eq!(snapshot.get_mempool_stats().locked_utxo_count, mempool.get_mempool_stats().locked_utxo_count);
+            prop_assert_eq!(snapshot.get_mempool_stats().finalized_tx_count, mempool.get_mempool_stats().finalized_tx_count);
+        }
+    }
+
+    #[test]
+    fn test_lock_refuses_utxo_held_by_another_transaction() {
+        // Test: Lock a UTXO for one transaction, then for a second one, then again for the first
+        // Expected: The second lock fails, relocking by the owner is a no-op, no conflicts are reported
+        println!("Expected: A UTXO can only be locked by one transaction at a time");
+
+        let mut locks = LockedUtxoMempool::new();
+        locks.lock_utxo("utxo_1".to_string(), 1.0, "tx_a".to_string()).unwrap();
+        assert!(locks.lock_utxo("utxo_1".to_string(), 1.0, "tx_b".to_string()).is_err());
+        locks.lock_utxo("utxo_1".to_string(), 1.0, "tx_a".to_string()).unwrap();
+
+        assert_eq!(locks.tx_locks["tx_a"], vec!["utxo_1".to_string()]);
+        assert!(!locks.tx_locks.contains_key("tx_b"));
+        assert!(locks.lock_conflicts().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    const INTERVAL_MS: u64 = 10_000;
+
+    fn heartbeats(sender_id: &str, keypair: &NodeKeypair, sequence: u64, sent_at: u64) -> Vec<MeshHeartbeat> {
+        MESH_TOPICS.iter().map(|topic| MeshHeartbeat::sign(topic, sender_id, sequence, keypair, sent_at)).collect()
+    }
+
+    #[test]
+    fn test_monitor_tracks_delivery_per_topic_and_goes_stale() {
+        // Test: Record heartbeats from two of four other leaders, replay and tamper with some, skip one
+        // topic, then assess as time passes
+        // Expected: Delivery ratios follow the fresh heartbeats per topic, replays and forgeries do not
+        // count, and silence beyond three intervals makes the node isolated
+        println!("Expected: Mesh health reflects which leaders' heartbeats arrived recently on each topic");
+
+        let leaders: Vec<String> = ["local", "a", "b", "c", "d"].iter().map(|id| id.to_string()).collect();
+        let keypairs: Vec<NodeKeypair> = (0..2).map(|_| NodeKeypair::new()).collect();
+        let mut monitor = MeshMonitor::new(&NetworkConfig::default());
+
+        for heartbeat in heartbeats("a", &keypairs[0], 1, 0) {
+            assert!(monitor.record(&heartbeat, 0).unwrap());
+        }
+        for heartbeat in heartbeats("b", &keypairs[1], 1, 0).into_iter().skip(1) {
+            assert!(monitor.record(&heartbeat, 0).unwrap());
+        }
+        assert!(!monitor.record(&heartbeats("a", &keypairs[0], 1, 0)[0], 1).unwrap());
+
+        let mut forged = MeshHeartbeat::sign(MESH_TOPICS[0], "b", 2, &keypairs[1], 0);
+        forged.sender_id = "c".to_string();
+        assert!(matches!(monitor.record(&forged, 0), Err(PclError::SignatureVerification(_))));
+        assert!(monitor.record(&MeshHeartbeat::sign("unknown", "a", 2, &keypairs[0], 0), 0).is_err());
+
+        let health = monitor.assess("local", &leaders, INTERVAL_MS);
+        assert_eq!(health.topics[0].delivering_leaders, 1);
+        assert_eq!(health.topics[0].silent_leaders, vec!["b", "c", "d"]);
+        assert_eq!(health.topics[1].delivery_ratio, 0.5);
+        assert!(health.isolated);
+        assert_eq!(health.network_health, 25.0);
+        assert_eq!(health.silent_leaders(), vec!["b", "c", "d"]);
+
+        for heartbeat in heartbeats("b", &keypairs[1], 2, 0) {
+            monitor.record(&heartbeat, INTERVAL_MS).unwrap();
+        }
+        assert!(!monitor.assess("local", &leaders, INTERVAL_MS).isolated);
+        let later = monitor.assess("local", &leaders, 4 * INTERVAL_MS);
+        assert!(later.isolated);
+        assert_eq!(later.topics[0].silent_leaders, vec!["a", "c", "d"]);
+
+        // A lone leader has nobody to hear from and is healthy
+        assert_eq!(monitor.assess("local", &leaders[..1], 0).network_health, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_isolated_node_raises_alert_and_dials_silent_leaders() {
+        // Test: Hear heartbeats from one of three other leaders, deliver heartbeats from a non-leader and
+        // with a key not matching the registry, then run the mesh check
+        // Expected: Only the registered leader's heartbeats count; the check lowers network_health,
+        // raises an alert, reports it in metrics and dials the two silent leaders
+        println!("Expected: Mesh isolation is reflected in consensus state and repaired by dialing peers");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let mut network = NetworkManager::new(local_node.clone()).await.unwrap();
+        network.start_listening(9000).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let mut ids = Vec::new();
+        let mut keypairs = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5"] {
+            let keypair = NodeKeypair::new();
+            let node = Node::new(ip.parse().unwrap(), &keypair).unwrap();
+            ids.push(node.id.to_string());
+            keypairs.push(keypair);
+            consensus.node_registry.write().await.register_node(node).unwrap();
+        }
+        consensus.leader_election.write().await.current_leaders = ids[..3].to_vec();
+
+        for heartbeat in heartbeats(&ids[0], &keypairs[0], 1, 0) {
+            assert!(consensus.handle_mesh_heartbeat(&heartbeat).await.unwrap());
+        }
+        let outsider = MeshHeartbeat::sign(MESH_TOPICS[0], &ids[3], 1, &keypairs[3], 0);
+        assert!(!consensus.handle_mesh_heartbeat(&outsider).await.unwrap());
+        let impostor = MeshHeartbeat::sign(MESH_TOPICS[0], &ids[1], 1, &keypairs[3], 0);
+        assert!(consensus.handle_mesh_heartbeat(&impostor).await.is_err());
+
+        let health = consensus.check_mesh_health().await.unwrap();
+        assert!(health.isolated);
+        assert_eq!(health.topics[0].delivering_leaders, 1);
+
+        let status = consensus.get_system_status().await.unwrap();
+        assert_eq!(status.mesh_alerts, 1);
+        assert!((status.network_health - 100.0 / 3.0).abs() < 1e-9);
+        assert!(consensus.workflow_metrics_prometheus().await.contains("pcl_mesh_isolated 1"));
+
+        let network = consensus.network_manager.lock().await;
+        let mut dialed: Vec<String> = network.peers.read().await.values().map(|peer| peer.multiaddr.clone()).collect();
+        dialed.sort();
+        assert_eq!(dialed, vec!["10.0.0.3:9000", "10.0.0.4:9000"]);
+    }
+
+    #[tokio::test]
+    async fn test_published_heartbeats_are_accepted_by_another_leader() {
+        // Test: Two managers are both current leaders and have registered each other, then the first
+        // publishes its mesh heartbeats and the second handles them
+        // Expected: Every heartbeat is signed with the first node's identity key and accepted, so the
+        // second node hears its fellow leader on every topic
+        println!("Expected: A leader's own mesh heartbeats pass another leader's checks");
+
+        let mut managers = Vec::new();
+        let mut dirs = Vec::new();
+        for ip in ["10.0.0.1", "10.0.0.2"] {
+            let dir = tempfile::tempdir().unwrap();
+            let keypair = NodeKeypair::new();
+            let node = Node::new(ip.parse().unwrap(), &keypair).unwrap();
+            let network = NetworkManager::new(node.clone()).await.unwrap();
+            managers.push((ConsensusManager::new(node.clone(), &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap(), node));
+            dirs.push(dir);
+        }
+        let leaders: Vec<String> = managers.iter().map(|(_, node)| node.id.to_string()).collect();
+        for (i, (consensus, _)) in managers.iter().enumerate() {
+            let (_, other) = &managers[1 - i];
+            consensus.node_registry.write().await.register_node(other.clone()).unwrap();
+            consensus.leader_election.write().await.current_leaders = leaders.clone();
+        }
+        let (sender, sender_node) = &managers[0];
+        let (receiver, _) = &managers[1];
+
+        sender.publish_mesh_heartbeats().await.unwrap();
+        let history = sender.network_manager.lock().await.message_history.read().await.clone();
+        let sent: Vec<MeshHeartbeat> = history.into_iter().filter_map(|message| match message {
+            NetworkMessage::MeshHeartbeat(heartbeat) => Some(heartbeat),
+            _ => None,
+        }).collect();
+        assert_eq!(sent.len(), MESH_TOPICS.len());
+
+        for heartbeat in &sent {
+            assert_eq!(heartbeat.sender_public_key, hex::encode(sender_node.public_key.to_bytes()));
+            assert!(receiver.handle_mesh_heartbeat(heartbeat).await.unwrap());
+        }
+        let health = receiver.check_mesh_health().await.unwrap();
+        assert!(!health.isolated);
+        assert!(health.topics.iter().all(|topic| topic.delivering_leaders == 1));
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_workflow_timings_close_steps_in_order() {
+        // Test: Start steps 1, 2 and 5 at known times, re-enter step 5 later, then finish
+        // Expected: Each step ends when the next starts, re-entering keeps the first start, and the
+        // end-to-end latency spans the first start to the finish
+        println!("Expected: Step timings record start and end per step");
+
+        let mut timings = WorkflowTimings::new();
+        timings.start(WorkflowStep::Submission, 1_000);
+        timings.start(WorkflowStep::LeaderProcessing, 1_010);
+        timings.start(WorkflowStep::ValidationProcessing, 1_050);
+        timings.start(WorkflowStep::ValidationProcessing, 1_400);
+        timings.finish(1_500);
+
+        assert_eq!(timings.get(WorkflowStep::Submission).unwrap().duration_ms(), Some(10));
+        assert_eq!(timings.get(WorkflowStep::LeaderProcessing).unwrap().duration_ms(), Some(40));
+        assert_eq!(timings.get(WorkflowStep::ValidationProcessing).unwrap().started_at, 1_050);
+        assert_eq!(timings.get(WorkflowStep::ValidationProcessing).unwrap().duration_ms(), Some(450));
+        assert!(timings.get(WorkflowStep::TaskAssignment).is_none());
+        assert_eq!(timings.end_to_end_ms(), Some(500));
+
+        let breakdown = timings.breakdown();
+        assert_eq!(breakdown["steps"][1]["name"], "leader_processing");
+        assert_eq!(breakdown["end_to_end_ms"], 500);
+    }
+
+    #[test]
+    fn test_histogram_renders_cumulative_buckets() {
+        // Test: Observe 3ms, 40ms and 2 minutes into the latency buckets and render them
+        // Expected: Buckets are cumulative, the out-of-range value only counts in +Inf, sum and count match
+        println!("Expected: Histograms render in Prometheus text format");
+
+        let mut metrics = WorkflowMetrics::new();
+        for end_to_end_ms in [3, 40, 120_000] {
+            let mut timings = WorkflowTimings::new();
+            timings.start(WorkflowStep::Submission, 0);
+            timings.finish(end_to_end_ms);
+            metrics.record(&timings);
+        }
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE pcl_workflow_end_to_end_seconds histogram"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"0.001\"} 0"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"0.005\"} 1"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"0.05\"} 2"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"60\"} 2"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_count 3"));
+        assert!(text.contains("pcl_workflow_step_duration_seconds_count{step=\"submission\"} 3"));
+        assert!(text.contains("pcl_workflow_step_duration_seconds_count{step=\"finalization\"} 0"));
+        assert!((metrics.end_to_end.sum - 120.043).abs() < 1e-9);
+    }
+}
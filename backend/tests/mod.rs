@@ -3,4 +3,73 @@ pub mod mempool;
 pub mod transaction_workflow;
 pub mod leader_election;
 pub mod network_communication;
-pub mod integration; 
\ No newline at end of file
+pub mod integration;
+pub mod multisig; 
+pub mod config;
+pub mod equivocation;
+pub mod client;
+pub mod export;
+pub mod webhook;
+pub mod auth;
+pub mod limits;
+pub mod search;
+pub mod doctor;
+pub mod logging;
+pub mod metrics;
+pub mod mempool_properties;
+pub mod conformance_vectors;
+pub mod storage_migrations;
+pub mod staking;
+pub mod fees;
+pub mod receipt;
+pub mod offline_signing;
+pub mod ledger;
+pub mod envelope;
+pub mod blinding;
+pub mod clock;
+pub mod probation;
+pub mod governance;
+pub mod epochs;
+pub mod reputation;
+pub mod invalidation;
+pub mod mesh;
+pub mod sequence;
+pub mod events;
+pub mod relay;
+pub mod outbound;
+pub mod peers;
+pub mod binding;
+pub mod task_ids;
+pub mod negotiation;
+pub mod verifiers;
+pub mod uptime_writer;
+pub mod discovery;
+pub mod health;
+pub mod db_integrity;
+pub mod replay;
+pub mod gossip;
+pub mod dependency;
+pub mod replace_by_fee;
+pub mod cancellation;
+pub mod submission_rate;
+pub mod embed;
+pub mod gateway;
+pub mod admission;
+pub mod screening;
+pub mod tenancy;
+pub mod history;
+pub mod bandwidth;
+pub mod gossip_hops;
+pub mod reconcile;
+pub mod webhook_due_index;
+pub mod stall;
+pub mod quorum;
+pub mod cubic_dlt;
+pub mod expiry;
+pub mod version;
+pub mod log_levels;
+pub mod transport;
+pub mod utxo;
+pub mod idempotency;
+pub mod standalone;
+pub mod node_api;
\ No newline at end of file
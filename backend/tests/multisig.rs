@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn multisig_transaction(policy: &MultisigPolicy) -> TransactionData {
+        let mut tx = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("multisig_utxo1".to_string(), 2.0)],
+            policy.address(),
+            0.2,
+            0.1,
+        );
+        tx.set_multisig(policy.clone());
+        tx
+    }
+
+    #[test]
+    fn test_multisig_address_independent_of_key_order() {
+        // Test: Derive a 2-of-3 multisig address from keys given in different orders
+        // Expected: Both policies produce the same address
+        println!("Expected: Multisig address is stable regardless of key order");
+
+        let keys: Vec<_> = (0..3).map(|_| NodeKeypair::new().public_key()).collect();
+        let reversed: Vec<_> = keys.iter().rev().cloned().collect();
+
+        let policy_a = MultisigPolicy::new(2, &keys).unwrap();
+        let policy_b = MultisigPolicy::new(2, &reversed).unwrap();
+
+        assert_eq!(policy_a.address(), policy_b.address());
+        assert_ne!(policy_a.address(), MultisigPolicy::new(3, &keys).unwrap().address());
+    }
+
+    #[test]
+    fn test_multisig_policy_rejects_invalid_threshold() {
+        // Test: Create policies with threshold 0, threshold > n and duplicate keys
+        // Expected: All are rejected
+        println!("Expected: Invalid multisig policies return errors");
+
+        let key = NodeKeypair::new().public_key();
+        let other = NodeKeypair::new().public_key();
+
+        assert!(MultisigPolicy::new(0, &[key, other]).is_err());
+        assert!(MultisigPolicy::new(3, &[key, other]).is_err());
+        assert!(MultisigPolicy::new(1, &[key, key]).is_err());
+    }
+
+    #[test]
+    fn test_multisig_transaction_meets_threshold() {
+        // Test: Two of three cosigners sign a multisig spend
+        // Expected: Signature validation passes once the threshold is reached
+        println!("Expected: 2-of-3 multisig transaction validates with two signatures");
+
+        let signers: Vec<_> = (0..3).map(|_| NodeKeypair::new()).collect();
+        let keys: Vec<_> = signers.iter().map(|k| k.public_key()).collect();
+        let policy = MultisigPolicy::new(2, &keys).unwrap();
+        let mut tx = multisig_transaction(&policy);
+
+        let first = tx.sign_multisig_partial(&signers[0]).unwrap();
+        tx.add_partial_signature(first);
+        assert!(!tx.validate_signature());
+
+        let second = tx.sign_multisig_partial(&signers[2]).unwrap();
+        tx.add_partial_signature(second);
+        assert!(tx.validate_signature());
+    }
+
+    #[test]
+    fn test_multisig_rejects_outsider_and_duplicate_signatures() {
+        // Test: Combine a duplicate cosigner signature with one from a key outside the policy
+        // Expected: Neither counts toward the threshold
+        println!("Expected: Outsider and duplicate signatures do not satisfy the threshold");
+
+        let signers: Vec<_> = (0..2).map(|_| NodeKeypair::new()).collect();
+        let keys: Vec<_> = signers.iter().map(|k| k.public_key()).collect();
+        let policy = MultisigPolicy::new(2, &keys).unwrap();
+        let tx = multisig_transaction(&policy);
+        let message = tx.signing_bytes().unwrap();
+
+        let partial = PartialSignature::sign(&signers[0], &message);
+        let outsider = PartialSignature::sign(&NodeKeypair::new(), &message);
+
+        assert!(!policy.verify(&message, &[partial.clone(), partial.clone(), outsider.clone()]).unwrap());
+        assert!(combine_partial_signatures(&policy, vec![partial, outsider]).is_err());
+        assert!(tx.sign_multisig_partial(&NodeKeypair::new()).is_err());
+    }
+
+    #[test]
+    fn test_multisig_signatures_bound_to_transaction() {
+        // Test: Alter the transaction after cosigners have signed
+        // Expected: Existing signatures no longer validate
+        println!("Expected: Modified multisig transaction fails validation");
+
+        let signers: Vec<_> = (0..2).map(|_| NodeKeypair::new()).collect();
+        let keys: Vec<_> = signers.iter().map(|k| k.public_key()).collect();
+        let policy = MultisigPolicy::new(1, &keys).unwrap();
+        let mut tx = multisig_transaction(&policy);
+
+        let partial = tx.sign_multisig_partial(&signers[1]).unwrap();
+        tx.add_partial_signature(partial);
+        assert!(tx.validate_signature());
+
+        tx.to[0].1 = 1.5;
+        assert!(!tx.validate_signature());
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn offer(leader_id: &str, task_type: ValidationTaskType, capacity: u32, offered_at: u64) -> TaskOffer {
+        TaskOffer::new("tx_1", leader_id, "alice", task_type, capacity, offered_at)
+    }
+
+    #[test]
+    fn test_offers_are_selected_from_distinct_leaders_by_capacity() {
+        // Test: Collect offers from three leaders, one of them offering twice and one with no spare
+        // capacity, then submit a repeat, a forged id and a late offer, and let an unsettled window age
+        // Expected: Two offers from different leaders are taken, highest capacity first; the rest are
+        // declined, repeats are ignored, late or forged offers refused and stale windows expire
+        println!("Expected: Task offers are collected for a window and selected with distinct leaders");
+
+        let config = ConsensusConfig { task_offer_window_ms: 1_000, task_offers_selected: 2, ..Default::default() };
+        let mut negotiation = TaskNegotiation::new(&config);
+        assert!(negotiation.submit(offer("leader_2", ValidationTaskType::SignatureValidation, 5, 0), 0).is_err());
+
+        negotiation.open("tx_1", 4, 0);
+        let offers = [
+            offer("leader_2", ValidationTaskType::SignatureValidation, 5, 10),
+            offer("leader_2", ValidationTaskType::SpendingPowerValidation, 5, 20),
+            offer("leader_8", ValidationTaskType::SignatureValidation, 9, 30),
+            offer("leader_9", ValidationTaskType::SignatureValidation, 0, 5),
+        ];
+        for offer in &offers {
+            assert!(negotiation.submit(offer.clone(), 100).unwrap());
+        }
+        assert!(!negotiation.submit(offers[0].clone(), 200).unwrap());
+        let mut forged = offers[2].clone();
+        forged.task_id = "tx_1_task_00".to_string();
+        assert!(matches!(negotiation.submit(forged, 200), Err(PclError::Validation(_))));
+        assert!(negotiation.ready(500).is_empty());
+        assert_eq!(negotiation.ready(1_000), vec!["tx_1"]);
+        assert!(negotiation.submit(offer("leader_7", ValidationTaskType::SignatureValidation, 5, 1_000), 1_000).is_err());
+
+        let selection = negotiation.select("tx_1").unwrap();
+        let accepted: Vec<&str> = selection.accepted.iter().map(|offer| offer.leader_id.as_str()).collect();
+        assert_eq!(accepted, vec!["leader_8", "leader_2"]);
+        assert_eq!(selection.accepted[1], offers[0]);
+        assert_eq!(selection.rejected.len(), 2);
+        assert!(negotiation.select("tx_1").is_none());
+
+        negotiation.open("tx_2", 3, 5_000);
+        negotiation.submit(TaskOffer::new("tx_2", "leader_2", "bob", ValidationTaskType::SignatureValidation, 1, 5_000), 5_000).unwrap();
+        assert!(negotiation.expire(6_500).is_empty());
+        assert_eq!(negotiation.expire(7_000).len(), 1);
+        assert!(negotiation.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_origin_leader_settles_offers_and_acknowledges_them() {
+        // Test: Offer a transaction's origin leader tasks from the leaders it was gossiped to, plus an
+        // offer addressed to another node and one for a transaction it does not hold, then settle
+        // Expected: Only offers for its own held transaction are collected; settling turns the selected
+        // offers into validation tasks and acknowledges every offer to its leader
+        println!("Expected: Accepted task offers become tasks and each offering leader gets an answer");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let local_id = node.id.to_string();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo1".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        consensus.mempool.raw_tx.write().await.add_transaction(RawTransaction::new("tx_1".to_string(), tx_data)).unwrap();
+
+        let message = |offer: TaskOffer, target_node: &str| TaskOfferMessage {
+            offer,
+            target_node: target_node.to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        assert!(!consensus.handle_task_offer(&message(offer("leader_2", ValidationTaskType::SignatureValidation, 3, now), "node_b")).await.unwrap());
+        let unknown = TaskOffer::new("tx_gone", "leader_2", "alice", ValidationTaskType::SignatureValidation, 3, now);
+        assert!(!consensus.handle_task_offer(&message(unknown, &local_id)).await.unwrap());
+        for (leader_id, capacity) in [("leader_2", 3), ("leader_3", 1), ("leader_4", 7)] {
+            let offer = offer(leader_id, ValidationTaskType::SignatureValidation, capacity, now);
+            assert!(consensus.handle_task_offer(&message(offer, &local_id)).await.unwrap());
+        }
+        assert_eq!(consensus.task_negotiation.read().await.ready(now), vec!["tx_1"]);
+
+        let selection = consensus.settle_task_offers("tx_1").await.unwrap().unwrap();
+        assert_eq!(selection.accepted.len(), ConsensusConfig::default().task_offers_selected);
+        let tasks = consensus.mempool.validation_tasks.read().await;
+        assert!(selection.accepted.iter().all(|offer| tasks.contains_task(&offer.task_id)));
+        assert!(selection.rejected.iter().all(|offer| !tasks.contains_task(&offer.task_id)));
+        drop(tasks);
+
+        let network = consensus.network_manager.lock().await;
+        let acks: Vec<(String, bool)> = network.message_history.read().await.iter()
+            .filter_map(|message| match message {
+                NetworkMessage::TaskOfferAck(ack) => Some((ack.leader_id.clone(), ack.accepted)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(acks, vec![("leader_4".to_string(), true), ("leader_2".to_string(), true), ("leader_3".to_string(), false)]);
+        assert!(consensus.settle_task_offers("tx_1").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+// Runs the pcl-node binary and talks to it over HTTP
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::process::{Child, Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    // Kills the node when the test ends, passing or not
+    struct NodeProcess(Child);
+
+    impl Drop for NodeProcess {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    fn free_port() -> u16 {
+        std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+
+    // Starts pcl-node with a fresh data directory and waits until /health answers
+    async fn start_node(args: &[&str], dir: &tempfile::TempDir) -> (NodeProcess, PclClient) {
+        let port = free_port().to_string();
+        let node = NodeProcess(Command::new(env!("CARGO_BIN_EXE_pcl-node"))
+            .args(["--port", &port, "--data-dir", dir.path().to_str().unwrap()])
+            .args(args)
+            .env_remove("PCL_CONFIG")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap());
+        let client = PclClient::new(&format!("127.0.0.1:{}", port)).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while let Err(e) = client.health().await {
+            assert!(Instant::now() < deadline, "Node never came up: {}", e);
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        (node, client)
+    }
+
+    #[tokio::test]
+    async fn test_post_transaction_refuses_bodies_not_signed_by_their_user() {
+        // Test: POST /transaction to a default node with no signature, with a signature from a key that
+        // is not the user's, with a tampered amount, and finally signed by the user
+        // Expected: The first three are refused with INVALID_SIGNATURE before anything enters a mempool;
+        // the signed one is accepted
+        println!("Expected: POST /transaction needs the sender's signature like /transaction/broadcast");
+
+        let dir = tempfile::tempdir().unwrap();
+        let (_node, client) = start_node(&[], &dir).await;
+        let (sender, mallory) = (NodeKeypair::new(), NodeKeypair::new());
+        let body = serde_json::json!({
+            "to": NodeKeypair::new().address(),
+            "from": "sender_utxo",
+            "amount": 1.0,
+            "user": sender.address(),
+            "fee": 0.5,
+        });
+        let signed = sign_submission(&body, &sender).unwrap();
+
+        let forged_signature = mallory.sign_data(&submission_hash(&body).unwrap());
+        let forged = attach_submission_signature(&body, &mallory.public_key(), &forged_signature);
+        let mut tampered = signed.clone();
+        tampered["amount"] = serde_json::json!(100.0);
+        for refused in [body, forged, tampered] {
+            let error = client.submit_transaction(&refused, None).await.unwrap_err().to_string();
+            assert!(error.contains("400 INVALID_SIGNATURE"), "{}", error);
+        }
+
+        let response = client.submit_transaction(&signed, None).await.unwrap();
+        assert!(response["transaction_id"].as_str().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_default_node_answers_every_route_clients_use() {
+        // Test: Start pcl-node without flags and call each route that --gateway does not bridge yet and that
+        // replicas, the simulator, the extension or the wallet tools depend on
+        // Expected: None answers 501 NOT_BRIDGED: a default node serves them from the workflow model, so the
+        // faucet funds an address and the finalized mempool a replica tails can be read
+        println!("Expected: A default node serves the whole API, not only the routes the gateway bridges");
+
+        let dir = tempfile::tempdir().unwrap();
+        let (_node, client) = start_node(&[], &dir).await;
+        let user = NodeKeypair::new();
+        let address = user.address();
+        let transfer = serde_json::json!({"to": NodeKeypair::new().address(), "from": "user_utxo", "amount": 1.0, "user": address, "fee": 0.5});
+        let signed = sign_submission(&transfer, &user).unwrap();
+        let cancellation = serde_json::to_value(TransactionCancellation::sign("missing_tx", &user)).unwrap();
+
+        let gets = [
+            format!("/tasks/{}", address),
+            "/peers".to_string(),
+            "/sync/tree?prefix=".to_string(),
+            "/sync/leaves?prefix=".to_string(),
+            "/sync/transaction/missing_tx".to_string(),
+            "/fee-estimate".to_string(),
+            format!("/utxos/{}", address),
+            "/export/transactions".to_string(),
+            format!("/search/transactions?user={}", address),
+            "/transaction/missing_tx/dependencies".to_string(),
+            format!("/stake/{}", address),
+            "/governance".to_string(),
+            format!("/reputation/{}", address),
+            format!("/probation/{}", address),
+            "/mempool/raw_tx".to_string(),
+            "/mempool/final".to_string(),
+            "/addresses".to_string(),
+        ];
+        let posts = [
+            ("/faucet", serde_json::json!({"address": address, "amount": 10.0})),
+            ("/subscriptions", serde_json::json!({"url": "http://127.0.0.1:1/hook"})),
+            ("/tasks/request", serde_json::json!({"user": address})),
+            ("/tasks/complete", serde_json::json!({})),
+            ("/transaction/prepare", transfer),
+            ("/transaction/broadcast", signed),
+            ("/transaction/missing_tx/cancel", cancellation),
+            ("/stake", serde_json::json!({"address": address, "amount": 1.0})),
+        ];
+        for path in &gets {
+            if let Err(e) = client.get(path).await {
+                assert!(!e.to_string().contains("NOT_BRIDGED"), "GET {}: {}", path, e);
+            }
+        }
+        for (path, body) in &posts {
+            if let Err(e) = client.post(path, body).await {
+                assert!(!e.to_string().contains("NOT_BRIDGED"), "POST {}: {}", path, e);
+            }
+        }
+
+        assert!(client.get("/mempool/final").await.is_ok());
+        let funded = client.get(&format!("/balance/{}", address)).await.unwrap();
+        assert!(funded["balance"].as_f64().unwrap() > 0.0);
+    }
+}
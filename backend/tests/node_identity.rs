@@ -147,4 +147,188 @@ mod tests {
         println!("Expected: Disqualified node cannot become leader for 24 hours");
         // Implementation will track node disqualification periods
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_address_derived_from_public_key() {
+        init_logger();
+        // Test: Derive an address from a public key and decode it again
+        // Expected: Checksum verifies and a single changed character is rejected
+        println!("Expected: Pubkey address round-trips, corrupted address fails checksum");
+
+        let keypair = NodeKeypair::new();
+        let address = keypair.address();
+        let (version, payload) = decode_address(&address).unwrap();
+
+        assert_eq!(version, ADDRESS_VERSION);
+        assert_eq!(payload, hash_data(&keypair.public_key().to_bytes())[..20].to_vec());
+        assert!(address_matches_public_key(&address, &keypair.public_key()));
+
+        let mut corrupted: Vec<char> = address.chars().collect();
+        let last = corrupted.len() - 1;
+        corrupted[last] = if corrupted[last] == '2' { '3' } else { '2' };
+        let corrupted: String = corrupted.into_iter().collect();
+        assert!(decode_address(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_hd_derivation_matches_slip10_vector() {
+        init_logger();
+        // Test: SLIP-0010 ed25519 test vector 1 (seed 000102...0f)
+        // Expected: Master and m/0' keys match the published vector
+        println!("Expected: HD derivation reproduces SLIP-0010 test vector 1");
+
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedKeypair::from_seed(&seed).unwrap();
+        assert_eq!(
+            hex::encode(master.keypair.signing_key.to_bytes()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(master.chain_code),
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"
+        );
+
+        let child = master.derive_path("m/0'").unwrap();
+        assert_eq!(
+            hex::encode(child.keypair.signing_key.to_bytes()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    #[test]
+    fn test_hd_seed_derives_many_addresses() {
+        init_logger();
+        // Test: Derive several addresses from one seed
+        // Expected: Derivation is deterministic and every index yields a distinct address
+        println!("Expected: Same seed and index give same address, different indexes differ");
+
+        let seed = [7u8; 32];
+        let (first, _) = derive_address(&seed, 0, 0).unwrap();
+        let (again, _) = derive_address(&seed, 0, 0).unwrap();
+        let (second, _) = derive_address(&seed, 0, 1).unwrap();
+        let (other_account, _) = derive_address(&seed, 1, 0).unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first, second);
+        assert_ne!(first, other_account);
+        assert!(ExtendedKeypair::from_seed(&[1u8; 8]).is_err());
+        assert!(ExtendedKeypair::from_seed(&seed).unwrap().derive_path("44'/0'").is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_seed_matches_bip39_vector() {
+        init_logger();
+        // Test: BIP39 test vector for 24 words of zero entropy with passphrase TREZOR, plus malformed phrases
+        // Expected: The seed matches the published vector; bad checksums and short phrases are rejected
+        println!("Expected: Mnemonic phrases turn into the standard BIP39 seed");
+
+        let phrase = format!("{} art", ["abandon"; 23].join(" "));
+        let seed = mnemonic_to_seed(&phrase, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8"
+        );
+
+        assert!(mnemonic_to_seed(&["abandon"; 24].join(" "), "").is_err());
+        let twelve_words = format!("{} about", ["abandon"; 11].join(" "));
+        assert_eq!(mnemonic_to_seed(&twelve_words, "").unwrap_err().code(), "NODE_IDENTITY");
+    }
+
+    #[test]
+    fn test_mnemonic_restores_identity_and_addresses() {
+        init_logger();
+        // Test: Generate a phrase, then recover the node identity and first address from it twice
+        // Expected: Recovery is deterministic down to the node id, the identity is not a wallet address, and a passphrase changes the keys
+        println!("Expected: A 24-word phrase is enough to recover identity and wallet keys");
+
+        let phrase = generate_mnemonic();
+        assert_eq!(phrase.split_whitespace().count(), MNEMONIC_WORDS);
+
+        let identity = identity_from_mnemonic(&phrase, "").unwrap();
+        assert_eq!(identity.public_key(), identity_from_mnemonic(&phrase, "").unwrap().public_key());
+        assert_ne!(identity.public_key(), identity_from_mnemonic(&phrase, "extra").unwrap().public_key());
+
+        let seed = mnemonic_to_seed(&phrase, "").unwrap();
+        let (address, _) = derive_address(&seed, 0, 0).unwrap();
+        assert_ne!(address, identity.address());
+        assert_eq!(address, derive_address(&mnemonic_to_seed(&phrase, "").unwrap(), 0, 0).unwrap().0);
+
+        let node = Node::from_identity("127.0.0.1".parse().unwrap(), &identity).unwrap();
+        let restored = Node::from_identity("10.0.0.2".parse().unwrap(), &identity_from_mnemonic(&phrase, "").unwrap()).unwrap();
+        assert_eq!(node.id, restored.id);
+        assert_eq!(node.id, identity_node_id(&identity.public_key()));
+    }
+
+    #[test]
+    fn test_address_parse_rejects_malformed_input() {
+        init_logger();
+        // Test: Parse well-formed and malformed addresses
+        // Expected: Valid address parses, wrong length/charset/checksum fail with INVALID_ADDRESS
+        println!("Expected: Address::parse accepts pubkey addresses and rejects typos");
+
+        let keypair = NodeKeypair::new();
+        let address = Address::parse(&keypair.address()).unwrap();
+        assert_eq!(address, Address::from_public_key(&keypair.public_key()));
+        assert!(!address.is_multisig());
+
+        let too_short = Address::parse("1abc").unwrap_err();
+        assert_eq!(too_short.code(), "INVALID_ADDRESS");
+
+        let mut bad_charset = keypair.address();
+        bad_charset.replace_range(1..2, "0");
+        assert_eq!(Address::parse(&bad_charset).unwrap_err().code(), "INVALID_ADDRESS");
+
+        let mut typo: Vec<char> = keypair.address().chars().collect();
+        typo[5] = if typo[5] == 'a' { 'b' } else { 'a' };
+        let typo: String = typo.into_iter().collect();
+        assert_eq!(Address::parse(&typo).unwrap_err().code(), "INVALID_ADDRESS");
+    }
+
+    #[test]
+    fn test_transaction_deserialization_validates_addresses() {
+        init_logger();
+        // Test: Deserialize transaction JSON with a valid and a corrupted recipient
+        // Expected: Corrupted recipient makes deserialization fail
+        println!("Expected: TransactionData rejects malformed addresses during deserialization");
+
+        let sender = NodeKeypair::new();
+        let recipient = NodeKeypair::new();
+        let tx = TransactionData::new(
+            vec![(Address::from_public_key(&recipient.public_key()), 1.0)],
+            vec![("alice_utxo1".to_string(), 2.0)],
+            Address::from_public_key(&sender.public_key()),
+            0.2,
+            0.1,
+        );
+
+        let json = serde_json::to_string(&tx).unwrap();
+        assert!(serde_json::from_str::<TransactionData>(&json).is_ok());
+
+        let corrupted = json.replace(&recipient.address(), "bob_address");
+        assert!(serde_json::from_str::<TransactionData>(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_node_identity_survives_reopen() {
+        init_logger();
+        // Test: Store a node identity, reopen the same data dir and load it back
+        // Expected: Same node id and key; empty storage has no identity
+        println!("Expected: A restarted node loads the identity it stored before");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new(IpAddr::from_str("127.0.0.1").unwrap(), &keypair).unwrap();
+        {
+            let storage = StorageManager::new(dir.path()).unwrap();
+            assert!(storage.load_node_identity().unwrap().is_none());
+            storage.store_node_identity(&node, &keypair).unwrap();
+            storage.store_consensus_state(b"{\"leaders\":[]}").unwrap();
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        let (restored, restored_keypair) = storage.load_node_identity().unwrap().unwrap();
+        assert_eq!(restored.id, node.id);
+        assert_eq!(restored_keypair.public_key(), keypair.public_key());
+        assert_eq!(storage.load_consensus_state().unwrap().unwrap(), b"{\"leaders\":[]}".to_vec());
+    }
+}
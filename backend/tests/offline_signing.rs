@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn submission(sender: &NodeKeypair) -> serde_json::Value {
+        serde_json::json!({
+            "user": sender.address(),
+            "to": NodeKeypair::new().address(),
+            "from": "utxo_1",
+            "amount": 2.5,
+            "fee": 0.1,
+            "note": "dropped by prepare",
+            "sig": "stale",
+        })
+    }
+
+    #[test]
+    fn test_prepared_transaction_is_canonical() {
+        // Test: Prepare the same submission twice with its fields in a different order, plus extra fields
+        // and a stale signature
+        // Expected: Both serialize to identical bytes, unknown fields and signatures are gone, defaults are filled in
+        println!("Expected: POST /transaction/prepare returns one canonical unsigned form");
+
+        let sender = NodeKeypair::new();
+        let body = submission(&sender);
+        let reordered: serde_json::Value = serde_json::from_str(&format!(
+            r#"{{"fee":0.1,"amount":2.5,"from":"utxo_1","to":"{}","user":"{}"}}"#,
+            body["to"].as_str().unwrap(), sender.address()
+        )).unwrap();
+
+        let unsigned = prepare_submission(&body).unwrap();
+        assert_eq!(serde_json::to_vec(&unsigned).unwrap(), serde_json::to_vec(&prepare_submission(&reordered).unwrap()).unwrap());
+        assert!(unsigned.get("note").is_none() && unsigned.get("sig").is_none());
+        assert_eq!(unsigned["stake"], 0.2);
+        assert!(prepare_submission(&serde_json::json!(["not", "an", "object"])).is_err());
+    }
+
+    #[test]
+    fn test_offline_signature_verifies_and_binds_the_transaction() {
+        // Test: Sign a prepared transaction with the sender's key, then alter it, strip the signature,
+        // and sign with someone else's key
+        // Expected: Only the untouched, sender-signed transaction verifies
+        println!("Expected: A transaction signed offline can be broadcast without the node seeing the key");
+
+        let sender = NodeKeypair::new();
+        let unsigned = prepare_submission(&submission(&sender)).unwrap();
+        assert!(verify_submission_signature(&unsigned).is_err());
+
+        let signed = sign_submission(&unsigned, &sender).unwrap();
+        assert!(verify_submission_signature(&signed).is_ok());
+        // The signature is over the unsigned form
+        assert_eq!(submission_hash(&signed).unwrap(), submission_hash(&unsigned).unwrap());
+
+        let mut tampered = signed.clone();
+        tampered["amount"] = serde_json::json!(25.0);
+        assert!(matches!(verify_submission_signature(&tampered), Err(PclError::SignatureVerification(_))));
+
+        let mut swapped = signed.clone();
+        swapped["public_key"] = serde_json::json!(hex::encode(NodeKeypair::new().public_key().to_bytes()));
+        assert!(verify_submission_signature(&swapped).is_err());
+
+        assert!(sign_submission(&unsigned, &NodeKeypair::new()).is_err());
+    }
+}
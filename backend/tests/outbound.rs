@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn vote(round: u8) -> NetworkMessage {
+        NetworkMessage::LeaderElection(LeaderElectionMessage {
+            election_id: format!("election_{}", round),
+            candidate_id: "candidate".to_string(),
+            votes: 1,
+            round,
+            timestamp: chrono::Utc::now(),
+            voter_id: "voter".to_string(),
+        })
+    }
+
+    fn announcement(index: usize) -> NetworkMessage {
+        NetworkMessage::TransactionAnnouncement(TransactionAnnouncementMessage {
+            announcements: vec![TxAnnouncement { raw_tx_id: format!("tx_{}", index), short_hash: "00".repeat(8) }],
+            sender_id: "leader".to_string(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_control_messages_jump_a_gossip_backlog() {
+        // Test: Queue more gossip than its cap, then an election vote, and drain across two windows
+        // with a small queue limit
+        // Expected: The vote goes out first, gossip stops at its cap until the next window, and the
+        // oldest gossip is dropped once the queue is full
+        println!("Expected: Outbound queues send control first and cap each class separately");
+
+        let config = NetworkConfig { outbound_gossip_per_sec: 2, outbound_queue_limit: 4, ..NetworkConfig::default() };
+        let mut queues = OutboundQueues::new(&config);
+        for index in 0..5 {
+            queues.push(announcement(index));
+        }
+        queues.push(vote(1));
+        assert_eq!(MessageClass::of(&vote(1)), MessageClass::Control);
+
+        let first = queues.drain(1_000);
+        assert_eq!(first.len(), 3);
+        assert!(matches!(&first[0], NetworkMessage::LeaderElection(_)));
+        assert!(matches!(&first[1], NetworkMessage::TransactionAnnouncement(m) if m.announcements[0].raw_tx_id == "tx_1"));
+        assert!(queues.drain(1_500).is_empty());
+        assert_eq!(queues.stats(MessageClass::Gossip), ClassStats { queued: 2, sent: 2, dropped: 1 });
+
+        assert_eq!(queues.drain(2_000).len(), 2);
+        assert!(queues.is_empty());
+        assert_eq!(queues.stats(MessageClass::Control).sent, 1);
+    }
+
+    #[tokio::test]
+    async fn test_network_manager_holds_gossip_over_its_cap() {
+        // Test: Cap gossip at one message per second, announce three transactions and broadcast a vote
+        // Expected: One announcement and the vote are sent at once, the rest wait in the queue and
+        // are reported in the system status
+        println!("Expected: Publishing goes through the prioritized outbound queues");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let mut network = NetworkManager::new(node.clone()).await.unwrap();
+        network.set_outbound_limits(&NetworkConfig { outbound_gossip_per_sec: 1, ..NetworkConfig::default() });
+
+        for index in 0..3 {
+            let announcement = TxAnnouncement { raw_tx_id: format!("tx_{}", index), short_hash: "00".repeat(8) };
+            network.announce_transactions(vec![announcement]).await.unwrap();
+        }
+        network.broadcast_leader_election("election_1", "candidate", 3, 1).await.unwrap();
+
+        let history = network.message_history.read().await.clone();
+        assert_eq!(history.len(), 2);
+        assert!(matches!(history[1], NetworkMessage::LeaderElection(_)));
+        assert_eq!(network.outbound.stats(MessageClass::Gossip).queued, 2);
+
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let status = consensus.get_system_status().await.unwrap();
+        assert_eq!(status.outbound_stats[&MessageClass::Gossip].queued, 2);
+        assert_eq!(status.outbound_stats[&MessageClass::Control].sent, 1);
+    }
+}
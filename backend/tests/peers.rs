@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn test_address_book_ranks_persists_and_prunes() {
+        // Test: Record three peers with different dial histories and ages, reopen the storage, then
+        // fail one peer's dials until it is pruned
+        // Expected: Candidates are ordered by dial success and recency, addresses are most recent
+        // first, the book survives a restart, and a repeatedly failing peer is removed for good
+        println!("Expected: The address book keeps good peers across restarts and drops failing ones");
+
+        let dir = tempfile::tempdir().unwrap();
+        let now = 30 * DAY_MS;
+        {
+            let mut book = AddressBook::open(Arc::new(StorageManager::new(dir.path()).unwrap())).unwrap();
+            book.observe("peer_a", "node_a", "10.0.0.1:9000", NodeRole::Leader, now).unwrap();
+            book.observe("peer_a", "node_a", "10.0.0.9:9000", NodeRole::Leader, now).unwrap();
+            book.observe("peer_b", "node_b", "10.0.0.2:9000", NodeRole::Validator, now).unwrap();
+            book.observe("peer_c", "node_c", "10.0.0.3:9000", NodeRole::Validator, now - 14 * DAY_MS).unwrap();
+            for _ in 0..3 {
+                book.record_dial("peer_a", true, now).unwrap();
+            }
+            book.record_dial("peer_b", false, now).unwrap();
+            assert!(!book.record_dial("unknown", true, now).unwrap());
+        }
+
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let mut book = AddressBook::open(storage.clone()).unwrap();
+        assert_eq!(book.len(), 3);
+        let peer_a = book.get("peer_a").unwrap();
+        assert_eq!(peer_a.multiaddrs, vec!["10.0.0.9:9000", "10.0.0.1:9000"]);
+        assert_eq!((peer_a.dial_attempts, peer_a.dial_successes, peer_a.success_rate()), (3, 3, 0.8));
+
+        let ranked: Vec<&str> = book.dial_candidates(10, now).iter().map(|record| record.peer_id.as_str()).collect();
+        assert_eq!(ranked, vec!["peer_a", "peer_b", "peer_c"]);
+        assert_eq!(book.dial_candidates(1, now).len(), 1);
+
+        for _ in 1..MAX_CONSECUTIVE_DIAL_FAILURES - 1 {
+            assert!(book.record_dial("peer_b", false, now).unwrap());
+        }
+        assert!(!book.record_dial("peer_b", false, now).unwrap());
+        assert!(book.get("peer_b").is_none());
+        assert_eq!(storage.load_peer_records().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stored_peers_are_dialed_at_startup() {
+        // Test: Connect to two peers through a network manager backed by storage, then build a fresh
+        // consensus manager over the same storage and dial from its address book
+        // Expected: The fresh node loads both peers, reconnects to them and counts the dials
+        println!("Expected: Peers discovered in an earlier run seed dialing after a restart");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        {
+            let mut network = NetworkManager::new(node.clone()).await.unwrap();
+            network.attach_address_book(AddressBook::open(Arc::new(StorageManager::new(dir.path()).unwrap())).unwrap());
+            network.connect_to_peer("10.0.0.2:9000").await.unwrap();
+            network.connect_to_peer("10.0.0.3:9000").await.unwrap();
+            assert_eq!(network.address_book.len(), 2);
+        }
+
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let mut network = consensus.network_manager.lock().await;
+        assert!(network.peers.read().await.is_empty());
+
+        let mut dialed = network.dial_known_peers(16).await.unwrap();
+        dialed.sort();
+        assert_eq!(dialed, vec!["10.0.0.2:9000", "10.0.0.3:9000"]);
+        assert_eq!(network.peers.read().await.len(), 2);
+        assert_eq!(network.address_book.get("peer_10.0.0.2_9000").unwrap().dial_successes, 1);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    const HOUR_MS: u64 = 3600 * 1000;
+
+    fn config() -> ConsensusConfig {
+        ConsensusConfig { probation_pulse_receipts: 2, probation_period_secs: 3600, ..Default::default() }
+    }
+
+    #[test]
+    fn test_probation_needs_period_and_receipts_from_established_witnesses() {
+        // Test: Track a new node, feed it receipts from itself, a probationary witness and an established
+        // one, and check its status before and after the probation period
+        // Expected: Only distinct pulses receipted by established witnesses count, and the node is
+        // eligible once both the period and the receipt count are met
+        println!("Expected: New nodes leave probation after the period with enough receipted pulses");
+
+        let witness = NodeKeypair::new();
+        let newcomer = NodeKeypair::new();
+        let mut tracker = ProbationTracker::new(&config());
+        tracker.admit("witness");
+        tracker.observe("newcomer", 0);
+        tracker.observe("other_newcomer", 0);
+
+        let own = PulseReceipt::sign("newcomer", "pulse_1", "newcomer", &newcomer, 10);
+        assert!(matches!(tracker.record_receipt(&own, 10), Err(PclError::Validation(_))));
+        let sybil = PulseReceipt::sign("newcomer", "pulse_1", "other_newcomer", &NodeKeypair::new(), 10);
+        assert!(matches!(tracker.record_receipt(&sybil, 10), Err(PclError::Probation(_))));
+        let mut forged = PulseReceipt::sign("newcomer", "pulse_1", "witness", &witness, 10);
+        forged.pulse_id = "pulse_9".to_string();
+        assert!(matches!(tracker.record_receipt(&forged, 10), Err(PclError::SignatureVerification(_))));
+
+        let first = PulseReceipt::sign("newcomer", "pulse_1", "witness", &witness, 10);
+        assert!(tracker.record_receipt(&first, 10).unwrap());
+        assert!(!tracker.record_receipt(&first, 20).unwrap());
+        let second = PulseReceipt::sign("newcomer", "pulse_2", "witness", &witness, 20);
+        assert!(tracker.record_receipt(&second, 20).unwrap());
+
+        let err = tracker.ensure_eligible("newcomer", HOUR_MS - 1, "votes").unwrap_err();
+        assert_eq!(err.code(), "ON_PROBATION");
+        assert!(tracker.ensure_eligible("newcomer", HOUR_MS, "votes").is_ok());
+        assert!(tracker.status("other_newcomer", HOUR_MS).on_probation);
+
+        // Unknown identities start probation when first checked, and nothing applies when it is off
+        assert!(tracker.ensure_eligible("stranger", 2 * HOUR_MS, "votes").is_err());
+        assert_eq!(tracker.status("stranger", 2 * HOUR_MS).probation_ends_at, 3 * HOUR_MS);
+        assert!(ProbationTracker::new(&ConsensusConfig::default()).ensure_eligible("stranger", 0, "votes").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_probation_gates_nominations_and_votes() {
+        // Test: Enable probation, register two fresh leader-eligible nodes, then nominate and deliver
+        // votes and pulse receipts from them and from the local node
+        // Expected: Fresh nodes are not nominated, their votes and receipts are refused, and votes from
+        // the established local node are tallied
+        println!("Expected: Probationary nodes cannot be nominated, vote or vouch");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let local_id = local_node.id.to_string();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::with_config(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap(), config()).unwrap();
+
+        let mut ids = Vec::new();
+        let mut keypairs = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3"] {
+            let keypair = NodeKeypair::new();
+            let mut node = Node::new(ip.parse().unwrap(), &keypair).unwrap();
+            node.role = NodeRole::Leader;
+            ids.push(node.id.to_string());
+            keypairs.push(keypair);
+            consensus.node_registry.write().await.register_node(node).unwrap();
+        }
+        assert!(consensus.nominate_candidates().await.unwrap().is_empty());
+        assert!(consensus.probation_status(&ids[0]).await.on_probation);
+        assert!(!consensus.probation_status(&local_id).await.on_probation);
+
+        let vote = |voter: &str| LeaderElectionMessage {
+            election_id: "election_1".to_string(),
+            candidate_id: ids[1].clone(),
+            votes: 7,
+            round: 1,
+            timestamp: chrono::Utc::now(),
+            voter_id: voter.to_string(),
+        };
+        assert!(!consensus.handle_leader_election_vote(&vote(&ids[0])).await.unwrap());
+        assert!(consensus.handle_leader_election_vote(&vote(&local_id)).await.unwrap());
+        assert_eq!(consensus.leader_election.read().await.voting_data[&ids[1]].votes, 7);
+
+        let vouch = PulseReceipt::sign(&ids[1], "pulse_1", &ids[0], &keypairs[0], 0);
+        assert!(matches!(consensus.handle_pulse_receipt(&vouch).await, Err(PclError::Probation(_))));
+        let impostor = PulseReceipt::sign(&ids[1], "pulse_1", &local_id, &NodeKeypair::new(), 0);
+        assert!(matches!(consensus.handle_pulse_receipt(&impostor).await, Err(PclError::SignatureVerification(_))));
+        let receipt = PulseReceipt::sign(&ids[1], "pulse_1", &local_id, &local_keypair, 0);
+        assert!(consensus.handle_pulse_receipt(&receipt).await.unwrap());
+        assert_eq!(consensus.probation_status(&ids[1]).await.pulse_receipts, 1);
+    }
+}
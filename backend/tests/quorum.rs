@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn tracker(k: usize, n: usize) -> QuorumTracker {
+        let config = ConsensusConfig { cross_validation_quorum: k, cross_validators_per_tx: n, ..ConsensusConfig::default() };
+        config.validate().unwrap();
+        QuorumTracker::new(&config)
+    }
+
+    #[test]
+    fn test_quorum_needs_k_completions_from_assigned_validators() {
+        // Test: Assign validators to a transaction under a 2-of-3 quorum, complete some of them,
+        // including a validator that was never assigned, and let one miss its deadline
+        // Expected: At most n validators are open at once, only assigned validators count towards k,
+        // a missed validator frees its slot but may still complete, and assignments stop once k is met
+        println!("Expected: A raw transaction waits for k of its n cross-validators");
+
+        let mut quorum = tracker(2, 3);
+        assert!(quorum.enabled());
+        assert!(!quorum.is_met("tx1"));
+        for validator in ["v1", "v2", "v3"] {
+            assert!(quorum.assign("tx1", validator));
+        }
+        assert!(!quorum.assign("tx1", "v4"));
+        assert!(!quorum.assign("tx1", "v1"));
+
+        assert!(!quorum.record_completion("tx1", "stranger"));
+        assert!(quorum.record_completion("tx1", "v1"));
+        assert!(!quorum.record_completion("tx1", "v1"));
+        assert!(!quorum.is_met("tx1"));
+
+        quorum.record_missed("tx1", "v2");
+        assert_eq!(quorum.get("tx1").unwrap().open(), 1);
+        assert!(quorum.can_assign("tx1", "v4"));
+        assert!(quorum.assign("tx1", "v4"));
+        assert!(!quorum.can_assign("tx1", "v5"));
+
+        // The late validator still counts, and once k is reached no one else is assigned
+        assert!(quorum.record_completion("tx1", "v2"));
+        assert!(quorum.is_met("tx1"));
+        let status = quorum.status("tx1");
+        assert_eq!((status.required, status.validators), (2, 3));
+        assert_eq!(status.completed.iter().map(String::as_str).collect::<Vec<_>>(), vec!["v1", "v2"]);
+        assert!(status.missed.is_empty());
+        assert!(!quorum.can_assign("tx1", "v5"));
+
+        assert!(quorum.remove("tx1").is_some());
+        assert!(!quorum.is_met("tx1"));
+        assert_eq!(quorum.status("tx1").assigned.len(), 0);
+    }
+
+    #[test]
+    fn test_disabled_quorum_and_config_limits() {
+        // Test: Track a transaction with k = 0, rebuild a tracker from saved tasks, raise k through
+        // new limits, and check config validation and env overrides
+        // Expected: With k = 0 nothing is capped or waited for; restored tasks count as before and new
+        // limits apply to transactions already tracked; k above n is refused
+        println!("Expected: The cross-validation quorum is off by default and configurable as k-of-n");
+
+        let mut quorum = tracker(0, 3);
+        assert!(!quorum.enabled());
+        assert!(quorum.is_met("tx1"));
+        for validator in ["v1", "v2", "v3", "v4"] {
+            assert!(quorum.assign("tx1", validator));
+        }
+        assert!(quorum.record_completion("tx1", "v4"));
+
+        let mut restored = tracker(0, 3);
+        restored.restore("tx2", "v1", true, false);
+        restored.restore("tx2", "v2", false, true);
+        restored.restore("tx2", "v3", false, false);
+        restored.set_limits(&ConsensusConfig { cross_validation_quorum: 2, cross_validators_per_tx: 3, ..ConsensusConfig::default() });
+        assert!(!restored.is_met("tx2"));
+        assert!(restored.can_assign("tx2", "v4"));
+        assert!(restored.record_completion("tx2", "v2"));
+        assert!(restored.is_met("tx2"));
+
+        assert_eq!(ConsensusConfig::default().cross_validation_quorum, 0);
+        assert!(ConsensusConfig { cross_validation_quorum: 4, cross_validators_per_tx: 3, ..ConsensusConfig::default() }.validate().is_err());
+        assert!(ConsensusConfig { cross_validation_quorum: 1, cross_validators_per_tx: config::MAX_CROSS_VALIDATORS + 1, ..ConsensusConfig::default() }.validate().is_err());
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| match key {
+            "PCL_CROSS_VALIDATION_QUORUM" => Some("2".to_string()),
+            "PCL_CROSS_VALIDATORS_PER_TX" => Some("5".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!((config.consensus.cross_validation_quorum, config.consensus.cross_validators_per_tx), (2, 5));
+        assert!(config.validate().is_ok());
+        assert_eq!(CROSS_VALIDATION_TASK, "cross_validation");
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    fn signed_receipt() -> TransactionReceipt {
+        let leader = NodeKeypair::new();
+        let leader_signature = ReceiptSignature::sign("leader_1", &leader, &TransactionReceipt::leader_signing_bytes("tx_1", 1_700_000_000_500));
+        let mut receipt = TransactionReceipt::new("tx_1", "ab12", 1_700_000_000_500, 7, leader_signature, 1_700_000_001_000);
+        receipt.add_validator_signature("validator_1", &NodeKeypair::new());
+        receipt.add_validator_signature("validator_2", &NodeKeypair::new());
+        receipt
+    }
+
+    #[test]
+    fn test_receipt_signatures_verify_and_bind_every_field() {
+        // Test: Verify a receipt signed by a leader and two validators, then alter its fields and signatures
+        // Expected: The original verifies; a changed digital root, timestamp or swapped key does not
+        println!("Expected: A receipt is self-verifying proof of finalization");
+
+        let receipt = signed_receipt();
+        assert!(receipt.verify().is_ok());
+        assert_eq!(receipt.validator_signatures.len(), 2);
+
+        let mut tampered = receipt.clone();
+        tampered.digital_root = 8;
+        assert!(matches!(tampered.verify(), Err(PclError::SignatureVerification(_))));
+
+        // The leader signed the old timestamp, so this fails before the validators are checked
+        let mut tampered = receipt.clone();
+        tampered.averaged_timestamp += 1;
+        assert!(tampered.verify().is_err());
+
+        let mut tampered = receipt.clone();
+        tampered.validator_signatures[1].public_key = hex::encode(NodeKeypair::new().public_key().to_bytes());
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn test_receipt_is_persisted_and_sent_with_webhooks() {
+        // Test: Store a receipt, reload it, and queue a webhook for the receiving address with it attached
+        // Expected: The stored receipt round-trips and the webhook payload carries it
+        println!("Expected: Receipts survive restarts and reach webhook subscribers");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let receipt = signed_receipt();
+        storage.store_receipt(&receipt).unwrap();
+        assert_eq!(storage.load_receipt("tx_1").unwrap(), Some(receipt.clone()));
+        assert_eq!(storage.load_receipt("tx_2").unwrap(), None);
+
+        let webhooks = WebhookDispatcher::new(storage);
+        let address: Address = NodeKeypair::new().address().parse().unwrap();
+        let subscription = webhooks.subscribe(address.clone(), "http://merchant.example/hook", "merchant-shared-secret", 0).unwrap();
+        webhooks.notify_received(address.as_str(), "tx_1", 5.0, "sender", Some(&receipt), 10).unwrap();
+
+        let payload: serde_json::Value = serde_json::from_str(&webhooks.deliveries(&subscription.id).unwrap()[0].payload).unwrap();
+        let delivered: TransactionReceipt = serde_json::from_value(payload["receipt"].clone()).unwrap();
+        assert_eq!(delivered, receipt);
+        assert!(delivered.verify().is_ok());
+    }
+}
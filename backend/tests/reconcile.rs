@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn leaf(n: u32, leader: &str, timestamp: u64) -> SyncLeaf {
+        let tx_id = hex::encode(&hash_data(format!("tx{}", n).as_bytes())[..16]);
+        SyncLeaf::new(&tx_id, leader, &format!("{}_key", leader), timestamp, &format!("data{}", n))
+    }
+
+    // The walk a node makes against a peer, with the peer's tree standing in for its API
+    fn walk(local: &SyncTree, remote: &SyncTree) -> (Vec<(SyncLeaf, Option<SyncLeaf>)>, usize) {
+        let mut differing = Vec::new();
+        let mut compared = 0;
+        let mut pending = vec![String::new()];
+        while let Some(prefix) = pending.pop() {
+            compared += 1;
+            match local.descend(&remote.node(&prefix)) {
+                SyncDescent::Same => {}
+                SyncDescent::CompareLeaves => differing.extend(local.differing(&remote.leaves(&prefix))),
+                SyncDescent::Children(children) => pending.extend(children),
+            }
+        }
+        (differing, compared)
+    }
+
+    #[test]
+    fn test_prefix_walk_finds_only_the_differences() {
+        // Test: Build two finalized sets of 2000 shared transactions where the peer has three the
+        // local node lacks and one finalized under another leader, then walk the peer's tree
+        // Expected: Identical sets agree at the root; otherwise exactly the four differing leaves are
+        // found, the conflicting one paired with the local leaf, without visiting every prefix
+        println!("Expected: Prefix comparison narrows reconciliation to the transactions that differ");
+
+        let shared: Vec<SyncLeaf> = (0..2000).map(|n| leaf(n, "leader_a", 1_000 + n as u64)).collect();
+        let local = SyncTree::new(shared.clone());
+        assert_eq!(local.descend(&SyncTree::new(shared.clone()).node("")), SyncDescent::Same);
+        assert_eq!(local.root(), SyncTree::new(shared.iter().rev().cloned()).root());
+
+        let mut remote_leaves = shared.clone();
+        remote_leaves.extend((2000..2003).map(|n| leaf(n, "leader_b", 5_000 + n as u64)));
+        remote_leaves[7] = leaf(7, "leader_b", 1_007);
+        let remote = SyncTree::new(remote_leaves);
+        assert_ne!(local.root(), remote.root());
+
+        let (differing, compared) = walk(&local, &remote);
+        assert_eq!(differing.len(), 4);
+        assert!(compared < 100);
+        let conflicts: Vec<_> = differing.iter().filter(|(_, local_leaf)| local_leaf.is_some()).collect();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0.tx_id, shared[7].tx_id);
+        assert_eq!(conflicts[0].1.as_ref().unwrap().leader_id, "leader_a");
+
+        // Leaves only the local node holds are the peer's to fetch, not this node's
+        let (differing, _) = walk(&remote, &local);
+        assert_eq!(differing.len(), 1);
+    }
+
+    #[test]
+    fn test_conflicts_follow_the_fork_tie_break() {
+        // Test: Compare leaves of one transaction finalized under two leaders, and check the reconcile
+        // config and the conflict event stage
+        // Expected: Any change to a leaf changes its hash, the earlier averaged timestamp wins and equal
+        // timestamps go to the lower leader key; peers must be host:port
+        println!("Expected: Sync conflicts are settled by the same rule as forks");
+
+        let a = SyncLeaf::new("tx1", "leader_a", "aa", 1_000, "data");
+        let b = SyncLeaf::new("tx1", "leader_b", "bb", 1_000, "data");
+        let late = SyncLeaf::new("tx1", "leader_a", "aa", 1_001, "data");
+        assert_ne!(a.hash, b.hash);
+        assert_ne!(a.hash, late.hash);
+        assert_ne!(a.hash, SyncLeaf::new("tx1", "leader_a", "aa", 1_000, "other").hash);
+        assert!(a.claim().beats(&b.claim()));
+        assert!(!b.claim().beats(&a.claim()));
+        assert!(b.claim().beats(&late.claim()));
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| (key == "PCL_RECONCILE_PEERS").then(|| "10.0.0.5:8080, leader-b:8080".to_string())).unwrap();
+        assert_eq!(config.reconcile.peers.len(), 2);
+        assert!(config.validate().is_ok());
+        config.reconcile.peers.push("leader-c".to_string());
+        assert!(config.validate().is_err());
+        assert!(ReconcileConfig { interval_secs: 0, ..ReconcileConfig::default() }.validate().is_err());
+
+        assert!(is_known_stage(SYNC_CONFLICT_STAGE));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn raw_tx(raw_tx_id: &str, amount: f64) -> RawTransaction {
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), amount)],
+            vec![("utxo1".to_string(), amount + 1.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        RawTransaction::new(raw_tx_id.to_string(), tx_data)
+    }
+
+    async fn consensus(ip: &str, dir: &tempfile::TempDir) -> ConsensusManager {
+        let keypair = NodeKeypair::new();
+        let node = Node::new(ip.parse().unwrap(), &keypair).unwrap();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_relay_requests_each_unknown_body_once() {
+        // Test: Announce a known and two unknown transactions, announce one again from another peer
+        // before and after the request times out, then deliver a body that does not match its hash
+        // Expected: Known ids and in-flight requests are skipped, a timed out request is re-sent, and
+        // a mismatched body is refused while a matching one completes the request
+        println!("Expected: Compact relay fetches a body only when it is unknown and not already requested");
+
+        let (known, first, second) = (raw_tx("tx_known", 1.0), raw_tx("tx_1", 2.0), raw_tx("tx_2", 3.0));
+        let announcements: Vec<TxAnnouncement> = [&known, &first, &second].iter()
+            .map(|tx| TxAnnouncement::for_transaction(tx).unwrap())
+            .collect();
+        assert_eq!(announcements[0].short_hash.len(), SHORT_HASH_LEN);
+
+        let mut relay = CompactRelay::new();
+        let is_known = |announcement: &TxAnnouncement| announcement.raw_tx_id == "tx_known";
+        assert_eq!(relay.missing("peer_a", &announcements, is_known, 0), vec!["tx_1", "tx_2"]);
+        assert!(relay.missing("peer_b", &announcements[1..2], is_known, 1_000).is_empty());
+        assert_eq!(relay.missing("peer_b", &announcements[1..2], is_known, 6_000), vec!["tx_1"]);
+
+        let mut forged = raw_tx("tx_2", 30.0);
+        forged.raw_tx_id = second.raw_tx_id.clone();
+        assert!(matches!(relay.accept_body(&forged), Err(PclError::Validation(_))));
+        assert!(relay.is_pending("tx_2"));
+        assert!(relay.accept_body(&second).unwrap());
+        assert!(!relay.accept_body(&second).unwrap());
+
+        let stats = relay.stats();
+        assert_eq!((stats.announcements_received, stats.already_known), (5, 1));
+        assert_eq!((stats.bodies_requested, stats.bodies_received, stats.bodies_rejected), (3, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_announced_transaction_is_fetched_from_the_announcer() {
+        // Test: One node holds a raw transaction and announces it; a second node that lacks it handles
+        // the announcement, the first answers the request, and the second takes the body
+        // Expected: Only the unknown id is requested, the body is addressed to the requester, and it
+        // ends up in the requester's raw mempool; a repeat announcement costs no body
+        println!("Expected: Announced transactions are requested and delivered on demand");
+
+        let dir = tempfile::tempdir().unwrap();
+        let announcer = consensus("10.0.0.1", &dir).await;
+        let other_dir = tempfile::tempdir().unwrap();
+        let receiver = consensus("10.0.0.2", &other_dir).await;
+        let (announcer_id, receiver_id) = (announcer.local_node.id.to_string(), receiver.local_node.id.to_string());
+
+        let tx = raw_tx("tx_relay", 4.0);
+        announcer.mempool.raw_tx.write().await.add_transaction(tx.clone()).unwrap();
+        let announcement = TransactionAnnouncementMessage {
+            announcements: vec![TxAnnouncement::for_transaction(&tx).unwrap()],
+            sender_id: announcer_id.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert!(announcer.handle_transaction_announcement(&announcement).await.unwrap().is_empty());
+        assert_eq!(receiver.handle_transaction_announcement(&announcement).await.unwrap(), vec!["tx_relay"]);
+
+        let request = match receiver.network_manager.lock().await.message_history.read().await.last() {
+            Some(NetworkMessage::TransactionRequest(request)) => request.clone(),
+            other => panic!("expected a transaction request, got {:?}", other),
+        };
+        assert_eq!(request.target_node, announcer_id);
+        assert_eq!(announcer.handle_transaction_request(&request).await.unwrap(), 1);
+
+        let body = match announcer.network_manager.lock().await.message_history.read().await.last() {
+            Some(NetworkMessage::TransactionGossip(body)) => body.clone(),
+            other => panic!("expected a transaction body, got {:?}", other),
+        };
+        assert_eq!(body.target_node.as_deref(), Some(receiver_id.as_str()));
+        assert!(receiver.handle_transaction_body(&body).await.unwrap());
+        assert!(receiver.mempool.raw_tx.read().await.get_transaction("tx_relay").is_some());
+
+        assert!(receiver.handle_transaction_announcement(&announcement).await.unwrap().is_empty());
+        let stats = receiver.get_system_status().await.unwrap().relay_stats;
+        assert_eq!((stats.already_known, stats.bodies_requested, stats.bodies_received), (1, 1, 1));
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn user() -> (NodeKeypair, Address) {
+        let keypair = NodeKeypair::new();
+        let address = Address::parse(&keypair.address()).unwrap();
+        (keypair, address)
+    }
+
+    fn unsigned_transfer(tx_id: &str, input: (&str, f64), fee: f64, sender: &Address, recipient: &Address) -> RawTransaction {
+        let tx_data = TransactionData::new(
+            vec![(recipient.clone(), 5.0)],
+            vec![(input.0.to_string(), input.1)],
+            sender.clone(),
+            0.1,
+            fee,
+        );
+        RawTransaction::new(tx_id.to_string(), tx_data)
+    }
+
+    // Signed by the sender's key
+    fn transfer(tx_id: &str, input: (&str, f64), fee: f64, sender: &NodeKeypair, recipient: &Address) -> RawTransaction {
+        let sender_address = Address::parse(&sender.address()).unwrap();
+        let mut tx = unsigned_transfer(tx_id, input, fee, &sender_address, recipient);
+        tx.tx_data.sign_transaction(sender).unwrap();
+        tx
+    }
+
+    #[test]
+    fn test_higher_fee_replacement_supersedes_original_and_its_children() {
+        // Test: Alice's transaction stalls with a child spending its output; she resubmits with an equal
+        // fee, with different inputs, and then with a higher fee over the same input
+        // Expected: Only the higher fee replacement is accepted; it takes the original's locks, the original
+        // and its child are invalidated, and the replacement itself cannot be replaced once processing
+        println!("Expected: Replace-by-fee needs the same sender and inputs and a strictly higher fee");
+
+        let ((alice_key, alice), (bob_key, bob)) = (user(), user());
+        let mut mempool = MempoolManager::new();
+        mempool.tx.create_utxo("genesis_alice".to_string(), 10.0, alice.to_string()).unwrap();
+        mempool.tx.create_utxo("genesis_other".to_string(), 10.0, alice.to_string()).unwrap();
+        mempool.submit_transaction(transfer("tx_stuck", ("genesis_alice", 10.0), 0.05, &alice_key, &bob)).unwrap();
+        let child = RawTransaction::new("tx_child".to_string(), TransactionData::new(
+            vec![(alice.clone(), 4.0)], vec![("tx_stuck:0".to_string(), 5.0)], bob.clone(), 0.1, 0.05,
+        ));
+        mempool.submit_transaction(child).unwrap();
+
+        let alice_public = alice_key.public_key();
+        assert!(mempool.replace_transaction("tx_stuck", transfer("tx_same_fee", ("genesis_alice", 10.0), 0.05, &alice_key, &bob), &alice_public).is_err());
+        assert!(mempool.replace_transaction("tx_stuck", transfer("tx_other_input", ("genesis_other", 10.0), 0.5, &alice_key, &bob), &alice_public).is_err());
+        assert!(mempool.replace_transaction("tx_stuck", transfer("tx_wrong_sender", ("genesis_alice", 10.0), 0.5, &bob_key, &bob), &bob_key.public_key()).is_err());
+        assert_eq!(mempool.locked_utxo.locked_utxos["genesis_alice"].locked_by_tx, "tx_stuck");
+
+        let invalidated = mempool.replace_transaction("tx_stuck", transfer("tx_bumped", ("genesis_alice", 10.0), 0.5, &alice_key, &bob), &alice_public).unwrap();
+        assert_eq!(invalidated, vec!["tx_stuck".to_string(), "tx_child".to_string()]);
+        assert!(mempool.raw_tx.get_transaction("tx_stuck").is_none());
+        assert!(mempool.raw_tx.get_transaction("tx_child").is_none());
+        assert_eq!(mempool.locked_utxo.locked_utxos["genesis_alice"].locked_by_tx, "tx_bumped");
+        assert!(!mempool.locked_utxo.is_utxo_locked("tx_stuck:0"));
+
+        mempool.start_processing("tx_bumped", "leader_1", "leader_sig").unwrap();
+        let late = mempool.replace_transaction("tx_bumped", transfer("tx_late", ("genesis_alice", 10.0), 0.9, &alice_key, &bob), &alice_public);
+        assert!(matches!(late, Err(PclError::Mempool(message)) if message.contains("already processing")));
+    }
+
+    #[tokio::test]
+    async fn test_replacement_notices_drop_the_original_on_other_leaders() {
+        // Test: A peer holds Alice's raw transaction when a current leader announces its replacement, once
+        // with a tampered replacement id and once as signed
+        // Expected: The tampered notice is refused; the signed one drops the original, and a repeat changes nothing
+        println!("Expected: Leaders drop a raw transaction when told it was replaced by fee");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let leader_keypair = NodeKeypair::new();
+        let leader = Node::new("10.0.0.2".parse().unwrap(), &leader_keypair).unwrap();
+        let leader_id = leader.id.to_string();
+        consensus.node_registry.write().await.register_node(leader).unwrap();
+        consensus.leader_election.write().await.current_leaders = vec![leader_id.clone()];
+
+        let ((alice_key, alice), (_, bob)) = (user(), user());
+        consensus.mempool.tx.write().await.create_utxo("genesis_alice".to_string(), 10.0, alice.to_string()).unwrap();
+        consensus.mempool.submit_transaction(transfer("tx_stuck", ("genesis_alice", 10.0), 0.05, &alice_key, &bob)).await.unwrap();
+
+        let mut notice = TransactionInvalidationMessage {
+            raw_tx_id: "tx_stuck".to_string(),
+            invalidated_leader_id: leader_id.clone(),
+            winning_leader_id: leader_id.clone(),
+            reason: "replaced by fee".to_string(),
+            sender_id: leader_id.clone(),
+            timestamp: chrono::Utc::now(),
+            sender_public_key: String::new(),
+            signature: String::new(),
+            evidence: None,
+            replaced_by: Some("tx_bumped".to_string()),
+            hops: 0,
+        };
+        notice.sign(&leader_keypair);
+
+        let mut tampered = notice.clone();
+        tampered.replaced_by = Some("tx_other".to_string());
+        assert!(consensus.handle_transaction_invalidation_notice(&tampered).await.is_err());
+        assert!(consensus.mempool.raw_tx.read().await.get_transaction("tx_stuck").is_some());
+
+        assert!(consensus.handle_transaction_invalidation_notice(&notice).await.unwrap());
+        assert!(consensus.mempool.raw_tx.read().await.get_transaction("tx_stuck").is_none());
+        assert!(!consensus.mempool.locked_utxo.read().await.is_utxo_locked("genesis_alice"));
+        assert!(!consensus.handle_transaction_invalidation_notice(&notice).await.unwrap());
+
+        consensus.mempool.submit_transaction(transfer("tx_again", ("genesis_alice", 10.0), 0.05, &alice_key, &bob)).await.unwrap();
+        let bumped = transfer("tx_again_bumped", ("genesis_alice", 10.0), 0.2, &alice_key, &bob);
+        let invalidated = consensus.replace_transaction("tx_again", bumped, &alice_key.public_key(), &leader_keypair).await.unwrap();
+        assert_eq!(invalidated, vec!["tx_again".to_string()]);
+        assert!(consensus.mempool.raw_tx.read().await.get_transaction("tx_again_bumped").is_some());
+    }
+
+    #[test]
+    fn test_replacement_needs_the_senders_signature() {
+        // Test: Someone other than Alice tries to replace her stuck transaction with a higher fee one in her
+        // name: unsigned, signed by their own key and presented with Alice's key, and presented with theirs
+        // Expected: Every attempt is refused as a signature failure and the original keeps its lock; Alice's
+        // own signed replacement is accepted
+        println!("Expected: Only the sender can replace a transaction by fee");
+
+        let ((alice_key, alice), (_, bob), (mallory_key, _)) = (user(), user(), user());
+        let mut mempool = MempoolManager::new();
+        mempool.tx.create_utxo("genesis_alice".to_string(), 10.0, alice.to_string()).unwrap();
+        mempool.submit_transaction(transfer("tx_stuck", ("genesis_alice", 10.0), 0.05, &alice_key, &bob)).unwrap();
+
+        let unsigned = unsigned_transfer("tx_unsigned", ("genesis_alice", 10.0), 0.5, &alice, &bob);
+        let mut forged = unsigned_transfer("tx_forged", ("genesis_alice", 10.0), 0.5, &alice, &bob);
+        forged.tx_data.sign_transaction(&mallory_key).unwrap();
+        for (replacement, key) in [
+            (unsigned, alice_key.public_key()),
+            (forged.clone(), alice_key.public_key()),
+            (forged, mallory_key.public_key()),
+        ] {
+            let result = mempool.replace_transaction("tx_stuck", replacement, &key);
+            assert!(matches!(result, Err(PclError::SignatureVerification(_))));
+        }
+        assert_eq!(mempool.locked_utxo.locked_utxos["genesis_alice"].locked_by_tx, "tx_stuck");
+
+        let signed = transfer("tx_bumped", ("genesis_alice", 10.0), 0.5, &alice_key, &bob);
+        assert!(mempool.replace_transaction("tx_stuck", signed, &alice_key.public_key()).is_ok());
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    const HOUR_MS: u64 = 3600 * 1000;
+
+    fn config() -> ConsensusConfig {
+        ConsensusConfig { stake_bonding_period_secs: 3600, stake_unbonding_delay_secs: 2 * 3600, ..Default::default() }
+    }
+
+    #[test]
+    fn test_replay_stops_at_the_requested_sequence() {
+        // Test: Bond two stakes, activate them and unbond one on a stored ledger, then replay the
+        // journal until the activations and in full
+        // Expected: The partial replay shows both stakes bonded; the full replay matches the live
+        // ledger; the journal is left as it was
+        println!("Expected: Replaying the event journal reproduces the state at any sequence number");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let mut ledger = StakeLedger::open(storage.clone(), &config()).unwrap();
+        let first = ledger.bond("alice", "validator_1", 20.0, 0).unwrap();
+        ledger.bond("bob", "validator_2", 30.0, 1).unwrap();
+        ledger.advance(HOUR_MS + 1).unwrap();
+        ledger.request_unbond(&first.stake_id, "alice", 2 * HOUR_MS).unwrap();
+
+        let partial = JournalReplay::run(&storage, &config(), Some(4)).unwrap();
+        assert_eq!((partial.last_applied(), partial.journal_length), (4, 5));
+        assert!(!partial.is_complete());
+        assert_eq!(partial.stakes.position(&first.stake_id).unwrap().status, StakeStatus::Bonded);
+        assert_eq!(partial.stakes.total_bonded(), 50.0);
+
+        let full = JournalReplay::run(&storage, &config(), None).unwrap();
+        assert!(full.is_complete());
+        assert_eq!(full.stakes.positions().collect::<Vec<_>>(), ledger.positions().collect::<Vec<_>>());
+        assert_eq!(storage.load_stake_events().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_replay_reports_the_event_it_cannot_apply() {
+        // Test: Journal a bond, then an activation for a stake that was never bonded, then another bond
+        // Expected: The replay applies the first event, stops at the second and names it
+        println!("Expected: A journal event that cannot be applied ends the replay with its sequence");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageManager::new(dir.path()).unwrap();
+        let bonded = StakeEvent {
+            stake_id: "stake_1".to_string(),
+            kind: StakeEventKind::Bonded,
+            owner: "alice".to_string(),
+            validator: "validator_1".to_string(),
+            amount: 10.0,
+            at: 0,
+            effective_at: HOUR_MS,
+        };
+        storage.append_stake_event(&bonded).unwrap();
+        storage.append_stake_event(&StakeEvent { stake_id: "stake_unknown".to_string(), kind: StakeEventKind::Activated, ..bonded.clone() }).unwrap();
+        storage.append_stake_event(&StakeEvent { stake_id: "stake_2".to_string(), ..bonded.clone() }).unwrap();
+
+        let replay = JournalReplay::run(&storage, &config(), None).unwrap();
+        assert_eq!(replay.applied.len(), 1);
+        let (sequence, error) = replay.failed_at.clone().unwrap();
+        assert_eq!(sequence, 2);
+        assert!(error.contains("stake_unknown"));
+        assert!(!replay.is_complete());
+        assert!(replay.stakes.position("stake_2").is_none());
+    }
+}
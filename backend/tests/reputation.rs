@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_score_weights_allowance_and_fee_discount() {
+        // Test: Record on-time tasks for one validator and faults for another, then compare scores,
+        // task allowances and fee discounts with an identity that has no history
+        // Expected: Unknown identities are neutral, reliable ones get more tasks and a fee discount,
+        // and invalid signatures pull a score down harder than missed tasks
+        println!("Expected: Reputation scores weight task assignment and fee discounts");
+
+        let mut ledger = ReputationLedger::new();
+        assert_eq!(ledger.score("stranger"), 0.5);
+        assert_eq!(ledger.task_allowance("stranger", 2), 2);
+        assert_eq!(ledger.fee_discount("stranger", 0.2), 0.0);
+
+        for at in 0..8 {
+            ledger.record("reliable", ReputationEvent::TaskOnTime, at).unwrap();
+        }
+        ledger.record("missing", ReputationEvent::TaskMissed, 1).unwrap();
+        ledger.record("forger", ReputationEvent::InvalidSignature, 1).unwrap();
+
+        assert!(ledger.score("reliable") > 0.85);
+        assert!(ledger.score("forger") < ledger.score("missing"));
+        assert!(ledger.score("missing") < 0.5);
+        assert_eq!(ledger.task_allowance("reliable", 2), 4);
+        assert_eq!(ledger.task_allowance("forger", 2), 1);
+        assert!(ledger.fee_discount("reliable", 0.2) > 0.14);
+        assert_eq!(ledger.fee_discount("forger", 0.2), 0.0);
+        assert_eq!(ledger.get("reliable").tasks_on_time, 8);
+
+        // The discount lowers the floor the fee market enforces
+        let market = FeeMarket::new(FeeConfig::default());
+        let minimum = market.minimum_fee(REFERENCE_TX_WEIGHT, 0);
+        assert!(market.check_fee(minimum * 0.9, REFERENCE_TX_WEIGHT, 0).is_err());
+        assert!(market.check_discounted_fee(minimum * 0.9, REFERENCE_TX_WEIGHT, 0, ledger.fee_discount("reliable", 0.2)).is_ok());
+    }
+
+    #[test]
+    fn test_reputation_survives_restart() {
+        // Test: Record events through a storage-backed ledger, drop it, and open a new one on the same database
+        // Expected: Every counter and the update time come back unchanged
+        println!("Expected: Reputation records are persisted and reloaded");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let mut ledger = ReputationLedger::open(storage.clone()).unwrap();
+        ledger.record("validator_1", ReputationEvent::TaskOnTime, 10).unwrap();
+        ledger.record("validator_1", ReputationEvent::TaskLate, 20).unwrap();
+        ledger.record("leader_1", ReputationEvent::InvalidatedSubmission, 30).unwrap();
+        let before = ledger.get("validator_1");
+        drop(ledger);
+
+        let reopened = ReputationLedger::open(storage).unwrap();
+        assert_eq!(reopened.records().count(), 2);
+        assert_eq!(reopened.get("validator_1"), before);
+        assert_eq!(before.updated_at, 20);
+        assert_eq!(reopened.get("leader_1").invalidated_submissions, 1);
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn config(maintainer: &NodeKeypair) -> ScreeningConfig {
+        ScreeningConfig {
+            maintainer_key: Some(hex::encode(maintainer.public_key().to_bytes())),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_screening_list_signature_version_and_refusals() {
+        // Test: Install lists signed by the maintainer and by another key, an older version, then screen
+        // addresses against a blocklist and an allowlist
+        // Expected: Only maintainer-signed lists with a higher version are installed; blocked addresses,
+        // and under an allowlist every address not on it, are refused with ADDRESS_SCREENED
+        println!("Expected: Screening installs only newer maintainer-signed lists and refuses matches");
+
+        let maintainer = NodeKeypair::new();
+        let mut screening = Screening::new(&config(&maintainer)).unwrap();
+        assert!(screening.enabled());
+        assert!(screening.screen(["anyone"]).is_ok()); // nothing installed yet
+
+        let forged = ScreeningList::sign(5, 1_000, vec!["alice".to_string()], vec![], &NodeKeypair::new());
+        assert!(screening.install(forged).is_err());
+        let mut tampered = ScreeningList::sign(5, 1_000, vec!["alice".to_string()], vec![], &maintainer);
+        tampered.blocked.clear();
+        assert!(screening.install(tampered).is_err());
+
+        assert!(screening.install(ScreeningList::sign(2, 1_000, vec!["mallory".to_string()], vec![], &maintainer)).unwrap());
+        assert!(!screening.install(ScreeningList::sign(1, 2_000, vec![], vec![], &maintainer)).unwrap());
+        assert_eq!(screening.list().unwrap().version, 2);
+
+        let error = screening.screen(["alice", "mallory"]).unwrap_err();
+        assert_eq!(error.code(), "ADDRESS_SCREENED");
+        assert!(error.to_string().contains("mallory"));
+        assert!(screening.screen(["alice", "bob"]).is_ok());
+
+        assert!(screening.install(ScreeningList::sign(3, 3_000, vec![], vec!["alice".to_string(), "bob".to_string()], &maintainer)).unwrap());
+        assert!(screening.screen(["alice", "bob"]).is_ok());
+        assert!(screening.screen(["alice", "carol"]).is_err());
+        assert_eq!(screening.matches(), 2);
+
+        assert!(Screening::default().install(ScreeningList::sign(1, 0, vec![], vec![], &maintainer)).is_err());
+        assert!(ScreeningConfig { list_url: Some("http://lists.example".to_string()), ..Default::default() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_node_refuses_screened_recipient() {
+        // Test: Install a list blocking one recipient on an embedded node, then submit to it and to another
+        // Expected: The blocked submission fails with ADDRESS_SCREENED and a "screened" event; the other
+        // one is finalized
+        println!("Expected: A screened address is refused and published as a screened event");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let node = EmbeddedNode::open(local_node, &local_keypair, dir.path(), ConsensusConfig::default()).await.unwrap();
+        node.consensus().leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        let maintainer = NodeKeypair::new();
+        let blocked = NodeKeypair::new().address();
+        *node.consensus().screening.write().await = Screening::new(&config(&maintainer)).unwrap();
+        let list = ScreeningList::sign(1, 1_000, vec![blocked.clone()], vec![], &maintainer);
+        assert!(node.consensus().screening.write().await.install(list).unwrap());
+        let mut events = node.consensus().events.subscribe();
+
+        let tx_data = |recipient: &str| TransactionData::new(
+            vec![(recipient.parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 1.3)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        let error = node.submit_transaction(tx_data(&blocked)).await.unwrap_err();
+        assert_eq!(error.code(), "ADDRESS_SCREENED");
+        let mut event = events.recv().await.unwrap();
+        while event.stage != SCREENED_STAGE {
+            event = events.recv().await.unwrap(); // the submission step is announced first
+        }
+        assert_eq!(event.recipient, blocked);
+
+        let allowed = tx_data(&NodeKeypair::new().address());
+        node.consensus().mempool.tx.write().await.create_utxo("utxo_in".to_string(), 1.3, allowed.user.to_string()).unwrap();
+        let tx_id = node.submit_transaction(allowed).await.unwrap();
+        assert!(node.query_status(&tx_id).await.unwrap().finalized);
+        assert_eq!(node.consensus().screening.read().await.matches(), 1);
+    }
+}
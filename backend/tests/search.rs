@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn indexed(tx_id: &str, sender: &str, recipient: &str, amount: f64, timestamp: u64, status: &str) -> IndexedTransaction {
+        IndexedTransaction {
+            tx_id: tx_id.to_string(),
+            sender: sender.to_string(),
+            source: format!("utxo_{}", tx_id),
+            recipient: recipient.to_string(),
+            amount,
+            timestamp,
+            status: status.to_string(),
+        }
+    }
+
+    fn sample_index() -> TransactionIndex {
+        let mut index = TransactionIndex::new();
+        index.insert(indexed("tx1", "alice", "bob", 10.0, 1_000, "finalized"));
+        index.insert(indexed("tx2", "bob", "carol", 25.0, 2_000, "finalized"));
+        index.insert(indexed("tx3", "alice", "carol", 5.0, 3_000, "invalidated"));
+        index.insert(indexed("tx4", "carol", "alice", 50.0, 4_000, "finalized"));
+        index
+    }
+
+    #[test]
+    fn test_search_combines_filters_sorting_and_paging() {
+        // Test: Query the index by user, amount range, time range and status with different sorts
+        // Expected: Only matching transactions come back, in the requested order, one page at a time
+        println!("Expected: Search results honour every filter and the sort order");
+
+        let index = sample_index();
+
+        let query = TransactionQuery::from_query("user=alice").unwrap();
+        let results = index.search(&query);
+        assert_eq!(results.total, 3);
+        assert_eq!(results.tx_ids, vec!["tx4", "tx3", "tx1"]);
+
+        let query = TransactionQuery::from_query("min_amount=10&max_amount=25&sort=amount_desc").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx2", "tx1"]);
+
+        let query = TransactionQuery::from_query("from_ts=2000&to_ts=3000&sort=oldest").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx2", "tx3"]);
+
+        let query = TransactionQuery::from_query("status=finalized&user=carol&max_amount=30").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx2"]);
+
+        let query = TransactionQuery::from_query("sort=amount_asc&offset=1&limit=2").unwrap();
+        let results = index.search(&query);
+        assert_eq!(results.total, 4);
+        assert_eq!(results.tx_ids, vec!["tx1", "tx2"]);
+
+        // Source UTXOs are searchable like addresses
+        let query = TransactionQuery::from_query("user=utxo_tx3").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx3"]);
+
+        // Round-trips through the client's query string
+        let query = TransactionQuery::from_query("user=bob&min_amount=1.5&to_ts=9000&sort=oldest&limit=5").unwrap();
+        assert_eq!(TransactionQuery::from_query(&query.to_query()).unwrap(), query);
+    }
+
+    #[test]
+    fn test_reinsert_moves_transaction_between_index_entries() {
+        // Test: Re-index a transaction with a new timestamp and status, then remove one
+        // Expected: Old index entries disappear so the transaction is only found under its new values
+        println!("Expected: The index follows updates and removals");
+
+        let mut index = sample_index();
+        index.insert(indexed("tx1", "alice", "bob", 10.0, 5_000, "invalidated"));
+        assert_eq!(index.len(), 4);
+
+        let query = TransactionQuery::from_query("to_ts=1500").unwrap();
+        assert_eq!(index.search(&query).total, 0);
+        let query = TransactionQuery::from_query("status=invalidated").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx1", "tx3"]);
+
+        assert!(index.remove("tx1").is_some());
+        assert!(index.remove("tx1").is_none());
+        let query = TransactionQuery::from_query("user=bob").unwrap();
+        assert_eq!(index.search(&query).tx_ids, vec!["tx2"]);
+    }
+
+    #[test]
+    fn test_invalid_search_parameters_rejected() {
+        // Test: Parse queries with inverted ranges, bad numbers, unknown sorts and out of range limits
+        // Expected: Each one is a validation error
+        println!("Expected: Malformed search queries are rejected before touching the index");
+
+        for query in [
+            "min_amount=10&max_amount=5",
+            "from_ts=20&to_ts=10",
+            "min_amount=-1",
+            "max_amount=abc",
+            "sort=random",
+            "limit=0",
+            "limit=100000",
+        ] {
+            assert!(matches!(TransactionQuery::from_query(query), Err(PclError::Validation(_))), "{}", query);
+        }
+    }
+}
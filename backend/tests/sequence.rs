@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::collections::HashMap;
+
+    fn leaders(count: usize) -> (Vec<(String, NodeKeypair)>, HashMap<String, ed25519_dalek::VerifyingKey>) {
+        let leaders: Vec<(String, NodeKeypair)> = (0..count).map(|i| (format!("leader_{}", i), NodeKeypair::new())).collect();
+        let keys = leaders.iter().map(|(id, keypair)| (id.clone(), keypair.public_key())).collect();
+        (leaders, keys)
+    }
+
+    #[test]
+    fn test_certificate_needs_quorum_of_registered_leaders() {
+        // Test: Co-sign a sequence certificate with one leader, an outsider and a second leader, then
+        // tamper with the transaction it names
+        // Expected: It verifies only once two registered leaders signed, and tampering breaks it
+        println!("Expected: Global sequence certificates need a quorum of leader signatures");
+
+        let (leaders, keys) = leaders(3);
+        let mut certificate = GlobalSequencer::new().propose("tx_a");
+        assert_eq!((certificate.sequence, certificate.previous_tx_id.clone()), (1, None));
+
+        certificate.cosign(&leaders[0].0, &leaders[0].1);
+        certificate.cosign(&leaders[0].0, &leaders[0].1);
+        certificate.cosign("outsider", &NodeKeypair::new());
+        assert!(matches!(certificate.verify(&keys, 2), Err(PclError::SignatureVerification(_))));
+
+        // A leader id with someone else's signature does not count either
+        let mut impersonated = certificate.clone();
+        impersonated.cosign(&leaders[1].0, &NodeKeypair::new());
+        assert!(impersonated.verify(&keys, 2).is_err());
+
+        certificate.cosign(&leaders[1].0, &leaders[1].1);
+        assert!(certificate.verify(&keys, 2).is_ok());
+
+        let mut tampered = certificate.clone();
+        tampered.tx_id = "tx_b".to_string();
+        assert!(tampered.verify(&keys, 1).is_err());
+    }
+
+    #[test]
+    fn test_sequencer_accepts_only_the_next_certificate() {
+        // Test: Record certificates in order, then try a replay, a gap and a certificate whose
+        // previous transaction does not match the tail
+        // Expected: The order grows by one each time and everything else is refused
+        println!("Expected: The global order is gapless and each certificate chains to the previous one");
+
+        let (leaders, keys) = leaders(2);
+        let certify = |sequencer: &GlobalSequencer, tx_id: &str| {
+            let mut certificate = sequencer.propose(tx_id);
+            for (id, keypair) in &leaders {
+                certificate.cosign(id, keypair);
+            }
+            certificate
+        };
+
+        let mut sequencer = GlobalSequencer::new();
+        let first = certify(&sequencer, "tx_a");
+        sequencer.record(&first, &keys, 2).unwrap();
+        let second = certify(&sequencer, "tx_b");
+        assert_eq!(second.previous_tx_id.as_deref(), Some("tx_a"));
+        sequencer.record(&second, &keys, 2).unwrap();
+        assert_eq!(sequencer.last_sequence(), 2);
+
+        assert!(matches!(sequencer.record(&second, &keys, 2), Err(PclError::Consensus(_))));
+        let mut gap = certify(&sequencer, "tx_d");
+        gap.sequence = 4;
+        assert!(sequencer.advance(&gap).is_err());
+        let mut forked = certify(&sequencer, "tx_c");
+        forked.previous_tx_id = Some("tx_a".to_string());
+        assert!(sequencer.advance(&forked).is_err());
+
+        // A replica extends its copy of the order with certificates verified upstream
+        let mut replica = GlobalSequencer::new();
+        replica.advance(&first).unwrap();
+        replica.advance(&second).unwrap();
+        assert_eq!(replica, sequencer);
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    const HOUR_MS: u64 = 3600 * 1000;
+
+    fn config() -> ConsensusConfig {
+        ConsensusConfig { stake_bonding_period_secs: 3600, stake_unbonding_delay_secs: 2 * 3600, ..Default::default() }
+    }
+
+    #[test]
+    fn test_stake_bonding_and_unbonding_lifecycle() {
+        // Test: Bond stake, try to unbond it early and as someone else, let the bonding period pass,
+        // unbond it and let the unbonding delay pass
+        // Expected: Stake only carries weight once bonded, cannot leave before then, stops counting
+        // as soon as unbonding starts and is withdrawn only after the delay
+        println!("Expected: Stake moves bonding -> bonded -> unbonding -> withdrawn on schedule");
+
+        let mut ledger = StakeLedger::new(&config());
+        let bonded = ledger.bond("alice", "validator_1", 50.0, 0).unwrap();
+        assert_eq!((bonded.kind, bonded.effective_at), (StakeEventKind::Bonded, HOUR_MS));
+        assert_eq!(ledger.position(&bonded.stake_id).unwrap().status, StakeStatus::Bonding);
+        assert_eq!(ledger.bonded_stake("validator_1"), 0.0);
+        assert!(ledger.advance(HOUR_MS - 1).unwrap().is_empty());
+        assert!(matches!(ledger.request_unbond(&bonded.stake_id, "alice", 10), Err(PclError::Validation(_))));
+
+        let activated = ledger.advance(HOUR_MS).unwrap();
+        assert_eq!(activated.len(), 1);
+        assert_eq!(activated[0].kind, StakeEventKind::Activated);
+        assert_eq!(ledger.bonded_stake("validator_1"), 50.0);
+        assert!(ledger.is_bonded("validator_1"));
+
+        ledger.bond("bob", "validator_2", 150.0, 0).unwrap();
+        ledger.advance(HOUR_MS).unwrap();
+        assert_eq!(ledger.stake_weight("validator_1"), 0.25);
+        assert_eq!(ledger.positions_for("alice").len(), 1);
+
+        assert!(matches!(ledger.request_unbond(&bonded.stake_id, "bob", 2 * HOUR_MS), Err(PclError::Forbidden(_))));
+        let unbond = ledger.request_unbond(&bonded.stake_id, "alice", 2 * HOUR_MS).unwrap();
+        assert_eq!(unbond.effective_at, 4 * HOUR_MS);
+        assert_eq!(ledger.bonded_stake("validator_1"), 0.0);
+        assert!(ledger.request_unbond(&bonded.stake_id, "alice", 2 * HOUR_MS).is_err());
+
+        assert!(ledger.advance(4 * HOUR_MS - 1).unwrap().is_empty());
+        let withdrawn = ledger.advance(4 * HOUR_MS).unwrap();
+        assert_eq!(withdrawn.len(), 1);
+        assert_eq!((withdrawn[0].kind, withdrawn[0].owner.as_str(), withdrawn[0].amount), (StakeEventKind::Withdrawn, "alice", 50.0));
+        assert_eq!(ledger.position(&bonded.stake_id).unwrap().status, StakeStatus::Withdrawn);
+
+        assert!(ledger.bond("alice", "validator_1", 0.0, 0).is_err());
+        assert!(ledger.bond("alice", "validator_1", f64::NAN, 0).is_err());
+    }
+
+    #[test]
+    fn test_stake_transitions_are_recorded_and_replayed() {
+        // Test: Bond, activate and unbond stake on a ledger backed by storage, then reopen it
+        // Expected: Every transition is in the consensus event log in order, and the reopened ledger
+        // has the same positions
+        println!("Expected: The stake ledger survives a restart through the consensus event log");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let mut ledger = StakeLedger::open(storage.clone(), &config()).unwrap();
+        let first = ledger.bond("alice", "validator_1", 20.0, 0).unwrap();
+        let second = ledger.bond("alice", "validator_2", 30.0, 1).unwrap();
+        assert_ne!(first.stake_id, second.stake_id);
+        ledger.advance(HOUR_MS + 1).unwrap();
+        ledger.request_unbond(&first.stake_id, "alice", 2 * HOUR_MS).unwrap();
+
+        let events = storage.load_stake_events().unwrap();
+        let kinds: Vec<StakeEventKind> = events.iter().map(|(_, event)| event.kind).collect();
+        assert_eq!(kinds, vec![
+            StakeEventKind::Bonded, StakeEventKind::Bonded,
+            StakeEventKind::Activated, StakeEventKind::Activated, StakeEventKind::UnbondRequested,
+        ]);
+        assert_eq!(events.iter().map(|(sequence, _)| *sequence).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        let reopened = StakeLedger::open(storage, &config()).unwrap();
+        assert_eq!(reopened.positions().collect::<Vec<_>>(), ledger.positions().collect::<Vec<_>>());
+        assert_eq!(reopened.position(&first.stake_id).unwrap().status, StakeStatus::Unbonding);
+        assert_eq!(reopened.bonded_stake("validator_2"), 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_bonded_stake_raises_election_weight() {
+        // Test: Nominate two leader-eligible nodes with equal history, one of them backed by bonded stake
+        // Expected: Both are nominated, the staked one carries the full stake weight and more votes
+        println!("Expected: Bonded stake increases a candidate's election weight");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let mut ids = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3"] {
+            let mut node = Node::new(ip.parse().unwrap(), &NodeKeypair::new()).unwrap();
+            node.role = NodeRole::Leader;
+            ids.push(node.id.to_string());
+            consensus.node_registry.write().await.register_node(node).unwrap();
+        }
+        // Bonded long ago, so nomination activates it
+        consensus.stake_ledger.write().await.bond("alice", &ids[0], 100.0, 0).unwrap();
+
+        let candidates = consensus.nominate_candidates().await.unwrap();
+        assert_eq!(candidates.len(), 2);
+        let staked = candidates.iter().find(|candidate| candidate.candidate_id == ids[0]).unwrap();
+        let unstaked = candidates.iter().find(|candidate| candidate.candidate_id == ids[1]).unwrap();
+        assert_eq!((staked.stake_weight, unstaked.stake_weight), (1.0, 0.0));
+        assert!(candidate_votes(staked) > candidate_votes(unstaked));
+        assert_eq!(consensus.storage_manager.load_stake_events().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_minimum_stake_gates_validation_tasks() {
+        // Test: Check validator eligibility with a minimum of 10 before bonding, while bonding and once bonded
+        // Expected: Refused with INSUFFICIENT_STAKE naming the shortfall until 10 XMBL is actually bonded
+        println!("Expected: Validation tasks need the configured minimum bonded stake");
+
+        let mut ledger = StakeLedger::new(&ConsensusConfig { min_validator_stake: 10.0, ..config() });
+        let err = ledger.ensure_validator_eligible("alice").unwrap_err();
+        assert_eq!(err.code(), "INSUFFICIENT_STAKE");
+        assert!(err.to_string().contains("validation tasks needs at least 10"), "{}", err);
+
+        ledger.bond("alice", "alice", 10.0, 0).unwrap();
+        let err = ledger.ensure_validator_eligible("alice").unwrap_err();
+        assert!(err.to_string().contains("10 still bonding"), "{}", err);
+
+        ledger.advance(HOUR_MS).unwrap();
+        assert!(ledger.ensure_validator_eligible("alice").is_ok());
+        // Leadership has its own, unset, minimum
+        assert!(ledger.ensure_leader_eligible("bob").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_nomination_skips_nodes_below_minimum_stake() {
+        // Test: Nominate two leader-eligible nodes with min_leader_stake 50 when only one has 100 bonded
+        // Expected: Only the staked node is nominated
+        println!("Expected: Leader candidacy needs the configured minimum bonded stake");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let config = ConsensusConfig { min_leader_stake: 50.0, ..config() };
+        let consensus = ConsensusManager::with_config(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap(), config).unwrap();
+
+        let mut ids = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3"] {
+            let mut node = Node::new(ip.parse().unwrap(), &NodeKeypair::new()).unwrap();
+            node.role = NodeRole::Leader;
+            ids.push(node.id.to_string());
+            consensus.node_registry.write().await.register_node(node).unwrap();
+        }
+        consensus.stake_ledger.write().await.bond("alice", &ids[1], 100.0, 0).unwrap();
+
+        let candidates = consensus.nominate_candidates().await.unwrap();
+        assert_eq!(candidates.iter().map(|candidate| &candidate.candidate_id).collect::<Vec<_>>(), vec![&ids[1]]);
+    }
+}
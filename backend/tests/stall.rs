@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn timings(steps: &[(WorkflowStep, u64)]) -> WorkflowTimings {
+        let mut timings = WorkflowTimings::new();
+        for (step, at) in steps {
+            timings.start(*step, *at);
+        }
+        timings
+    }
+
+    #[test]
+    fn test_monitor_flags_steps_past_their_timeout() {
+        // Test: Check workflows in different steps against per-step timeouts, then check again before
+        // and after another timeout has passed, and once the stalled workflow moves to the next step
+        // Expected: Only the workflow past its open step's timeout is stalled and alerted once; it is due
+        // remediation again only a timeout after the last attempt, and leaves the set once it moves on
+        println!("Expected: A transaction stuck in one workflow step is noticed and alerted on once");
+
+        let config = ConsensusConfig { workflow_step_timeouts_secs: [10, 10, 10, 60, 10, 10], ..ConsensusConfig::default() };
+        config.validate().unwrap();
+        let mut monitor = StallMonitor::new(&config);
+        assert_eq!(monitor.timeout_ms(WorkflowStep::TaskCompletion), 60_000);
+
+        let stuck = timings(&[(WorkflowStep::Submission, 0), (WorkflowStep::LeaderProcessing, 1_000), (WorkflowStep::TaskAssignment, 2_000)]);
+        let waiting = timings(&[(WorkflowStep::Submission, 0), (WorkflowStep::TaskCompletion, 5_000)]);
+        let mut done = stuck.clone();
+        done.finish(3_000);
+
+        let due = monitor.check([("tx_stuck", &stuck), ("tx_waiting", &waiting), ("tx_done", &done)], 20_000);
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].tx_id.as_str(), due[0].step, due[0].stalled_for_ms), ("tx_stuck", WorkflowStep::TaskAssignment, 18_000));
+        assert_eq!(monitor.alerts, 1);
+        monitor.record_remediation("tx_stuck", vec!["re-gossiped transaction".to_string()], 20_000);
+
+        assert!(monitor.check([("tx_stuck", &stuck)], 25_000).is_empty());
+        let due = monitor.check([("tx_stuck", &stuck)], 30_000);
+        assert_eq!(due[0].remediation_attempts, 1);
+        assert_eq!(monitor.alerts, 1);
+        assert_eq!(monitor.stalled()[0].last_remediation, vec!["re-gossiped transaction".to_string()]);
+        assert!(monitor.render_prometheus().contains("pcl_workflow_stalled_transactions{step=\"task_assignment\"} 1"));
+
+        let mut moved = stuck.clone();
+        moved.start(WorkflowStep::TaskCompletion, 31_000);
+        assert!(monitor.check([("tx_stuck", &moved)], 32_000).is_empty());
+        assert!(monitor.stalled().is_empty());
+        assert!(monitor.render_prometheus().contains("pcl_workflow_stall_alerts_total 1"));
+
+        assert!(ConsensusConfig { workflow_step_timeouts_secs: [10, 0, 10, 10, 10, 10], ..ConsensusConfig::default() }.validate().is_err());
+        assert!(is_known_stage(STALLED_STAGE));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_workflow_is_reported_and_remediated() {
+        // Test: Track a workflow stuck in task completion with one open and one completed validation
+        // task, then run the stall check well past the step's timeout
+        // Expected: A stalled event is emitted, the open task moves to another leader, the transaction
+        // is gossiped again, and it is listed as stalled with what was done
+        println!("Expected: Stalled transactions get an event, task reassignment and another gossip round");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        consensus.leader_election.write().await.current_leaders = vec!["leader_a".to_string(), "leader_b".to_string()];
+        let mut events = consensus.events.subscribe();
+
+        let tx = RawTransaction::new("tx_stuck".to_string(), TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_1".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        ));
+        let open_task = ValidationTask::for_transaction("tx_stuck", "leader_a", ValidationTaskType::SignatureValidation, "alice");
+        let mut done_task = ValidationTask::for_transaction("tx_stuck", "leader_a", ValidationTaskType::TimestampValidation, "alice");
+        done_task.complete();
+        {
+            let mut tasks_pool = consensus.mempool.validation_tasks.write().await;
+            tasks_pool.add_task(open_task.clone()).unwrap();
+            tasks_pool.add_task(done_task.clone()).unwrap();
+        }
+        let workflow = TransactionWorkflowState {
+            tx_id: "tx_stuck".to_string(),
+            current_step: 3,
+            workflow_data: TransactionWorkflowData {
+                alice_transaction: Some(tx),
+                charlie_processing: None,
+                validation_tasks: vec![open_task.clone(), done_task.clone()],
+                alice_completion: None,
+                charlie_final_processing: None,
+                validator_broadcast: None,
+                amount_commitment: None,
+                amount_opening: None,
+            },
+            start_time: chrono::Utc::now(),
+            last_update: chrono::Utc::now(),
+            timings: timings(&[(WorkflowStep::Submission, 1_000), (WorkflowStep::TaskCompletion, 2_000)]),
+        };
+        consensus.consensus_state.write().await.active_transactions.insert("tx_stuck".to_string(), workflow);
+
+        let timeout_ms = consensus.stall_monitor.read().await.timeout_ms(WorkflowStep::TaskCompletion);
+        assert!(consensus.check_stalled_workflows(2_000 + timeout_ms).await.unwrap().is_empty());
+        let due = consensus.check_stalled_workflows(3_000 + timeout_ms).await.unwrap();
+        assert_eq!(due.len(), 1);
+
+        let event = events.try_recv().unwrap();
+        assert_eq!((event.stage.as_str(), event.tx_id.as_str()), (STALLED_STAGE, "tx_stuck"));
+
+        let tasks_pool = consensus.mempool.validation_tasks.read().await;
+        assert_eq!(tasks_pool.tasks[&open_task.task_id].leader_id, "leader_b");
+        assert_eq!(tasks_pool.tasks[&done_task.task_id].leader_id, "leader_a");
+        assert_eq!(tasks_pool.assigned_tasks["leader_b"], vec![open_task.task_id.clone()]);
+        drop(tasks_pool);
+
+        let history = consensus.network_manager.lock().await.get_message_history().await;
+        assert!(history.iter().any(|message| matches!(message, NetworkMessage::TransactionGossip(gossip) if gossip.tx_id == "tx_stuck")));
+
+        let stalled = consensus.stalled_transactions().await;
+        assert_eq!(stalled.len(), 1);
+        assert_eq!(stalled[0].remediation_attempts, 1);
+        assert_eq!(stalled[0].last_remediation.len(), 2);
+        assert!(consensus.workflow_metrics_prometheus().await.contains("pcl_workflow_stall_alerts_total 1"));
+    }
+}
@@ -0,0 +1,87 @@
+// Runs the pcl-node binary, so it is only built with the feature that gives it --standalone
+#[cfg(all(test, feature = "standalone"))]
+mod tests {
+    use pcl_backend::*;
+    use std::process::{Child, Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    // Kills the node when the test ends, passing or not
+    struct NodeProcess(Child);
+
+    impl Drop for NodeProcess {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    fn free_port() -> u16 {
+        std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+
+    // Retries until the call succeeds or the deadline passes
+    async fn eventually<T, F, Fut>(timeout: Duration, mut call: F) -> T
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match call().await {
+                Ok(value) => return value,
+                Err(e) if Instant::now() >= deadline => panic!("Gave up waiting: {}", e),
+                Err(_) => tokio::time::sleep(Duration::from_millis(200)).await,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_standalone_node_runs_all_six_steps() {
+        // Test: Start pcl-node --standalone, then submit a transfer signed by one of its dev wallets to
+        // another over HTTP
+        // Expected: With no peers the node submits, processes, assigns and completes the validation
+        // tasks, averages and signs, and finalizes: a receipt with leader and validator signatures
+        // verifies, the recipient holds the amount and the finalized record names its cross validators
+        println!("Expected: A standalone node takes a transaction through all six steps on its own");
+
+        let dir = tempfile::tempdir().unwrap();
+        let port = free_port();
+        let _node = NodeProcess(Command::new(env!("CARGO_BIN_EXE_pcl-node"))
+            .args(["--standalone", "--port", &port.to_string(), "--data-dir", dir.path().to_str().unwrap()])
+            .env_remove("PCL_CONFIG")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap());
+        let client = PclClient::new(&format!("127.0.0.1:{}", port)).unwrap();
+        eventually(Duration::from_secs(60), || client.health()).await;
+
+        // The dev wallets are derived from fixed seeds and funded from "{seed}_genesis"
+        let (sender, sender_key) = derive_address(&hash_data(b"standalone_wallet_0"), 0, 0).unwrap();
+        let (recipient, _) = derive_address(&hash_data(b"standalone_wallet_1"), 0, 0).unwrap();
+        let recipient_before = client.balance(&recipient).await.unwrap()["balance"].as_f64().unwrap();
+
+        let unsigned = prepare_submission(&serde_json::json!({
+            "to": recipient,
+            "from": "standalone_wallet_0_genesis",
+            "amount": 2.5,
+            "user": sender,
+            "fee": 0.5,
+        })).unwrap();
+        let response = client.submit_transaction(&sign_submission(&unsigned, &sender_key).unwrap(), None).await.unwrap();
+        let tx_id = response["transaction_id"].as_str().unwrap().to_string();
+
+        let receipt = eventually(Duration::from_secs(30), || client.receipt(&tx_id)).await;
+        assert_eq!(receipt.tx_id, tx_id);
+        assert!(receipt.verify().is_ok());
+        assert!(!receipt.leader_signature.signature.is_empty());
+
+        let recipient_after = client.balance(&recipient).await.unwrap()["balance"].as_f64().unwrap();
+        assert!((recipient_after - recipient_before - 2.5).abs() < 1e-9);
+
+        let details = client.transaction(&tx_id).await.unwrap();
+        let proof = &details["cross_validation_proof"];
+        assert!(!proof["cross_validators"].as_array().unwrap().is_empty());
+        assert!(proof["validation_steps_completed"].as_u64().unwrap() > 0);
+    }
+}
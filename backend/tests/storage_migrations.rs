@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::storage::migrations::{record_version, rewrite_outdated_records};
+    use serde::{Deserialize, Serialize};
+
+    fn sample_tx(raw_tx_id: &str) -> RawTransaction {
+        let tx_data = TransactionData::new(
+            vec![(Address::parse(&NodeKeypair::new().address()).unwrap(), 1.0)],
+            vec![("utxo_1".to_string(), 2.0)],
+            Address::parse(&NodeKeypair::new().address()).unwrap(),
+            0.2,
+            0.1,
+        );
+        RawTransaction::new(raw_tx_id.to_string(), tx_data)
+    }
+
+    // TransactionData as stored before it carried an expiry
+    #[derive(Serialize)]
+    struct TransactionDataV1<'a> {
+        to: &'a Vec<(Address, f64)>,
+        from: &'a Vec<(String, f64)>,
+        user: &'a Address,
+        sig: &'a Option<String>,
+        stake: f64,
+        fee: f64,
+        change: Option<f64>,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        leader: &'a Option<String>,
+        nonce: u64,
+        multisig: &'a Option<MultisigPolicy>,
+        signatures: &'a Vec<PartialSignature>,
+        fee_payer: &'a Option<FeePayer>,
+    }
+
+    impl<'a> From<&'a TransactionData> for TransactionDataV1<'a> {
+        fn from(data: &'a TransactionData) -> Self {
+            Self {
+                to: &data.to,
+                from: &data.from,
+                user: &data.user,
+                sig: &data.sig,
+                stake: data.stake,
+                fee: data.fee,
+                change: data.change,
+                timestamp: data.timestamp,
+                leader: &data.leader,
+                nonce: data.nonce,
+                multisig: &data.multisig,
+                signatures: &data.signatures,
+                fee_payer: &data.fee_payer,
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct RawTransactionV1<'a> {
+        raw_tx_id: &'a str,
+        tx_data: TransactionDataV1<'a>,
+        validation_timestamps: &'a Vec<chrono::DateTime<chrono::Utc>>,
+        validation_tasks: &'a Vec<ValidationTask>,
+        tx_timestamp: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl<'a> From<&'a RawTransaction> for RawTransactionV1<'a> {
+        fn from(tx: &'a RawTransaction) -> Self {
+            Self {
+                raw_tx_id: &tx.raw_tx_id,
+                tx_data: (&tx.tx_data).into(),
+                validation_timestamps: &tx.validation_timestamps,
+                validation_tasks: &tx.validation_tasks,
+                tx_timestamp: tx.tx_timestamp,
+            }
+        }
+    }
+
+    fn open_raw(path: &std::path::Path) -> rocksdb::DB {
+        let mut opts = rocksdb::Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let cfs = ALL_COLUMN_FAMILIES.map(|name| rocksdb::ColumnFamilyDescriptor::new(name, rocksdb::Options::default()));
+        rocksdb::DB::open_cf_descriptors(&opts, path, cfs).unwrap()
+    }
+
+    #[test]
+    fn test_legacy_database_migrates_to_current_schema() {
+        // Test: Write untagged bincode records as a pre-versioning node would, including a raw
+        // transaction under its old bare key, then open the database and run the migrations twice
+        // Expected: Schema goes from 0 to the current version, every record is tagged and still loads,
+        // and the second run does nothing
+        println!("Expected: Pre-versioning databases are upgraded in place at startup");
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut legacy_tx = sample_tx("tx_legacy");
+        legacy_tx.tx_data.leader = Some("leader_1".to_string());
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let subscription = Subscription::new(Address::parse(&keypair.address()).unwrap(), "http://localhost:9000/hook", "webhook-secret-0123456789", 1_700_000_000).unwrap();
+        {
+            let db = open_raw(dir.path());
+            db.put_cf(db.cf_handle(CF_RAW_TRANSACTIONS).unwrap(), "tx_legacy", bincode::serialize(&RawTransactionV1::from(&legacy_tx)).unwrap()).unwrap();
+            db.put_cf(db.cf_handle(CF_NODES).unwrap(), node.id.to_string(), bincode::serialize(&node).unwrap()).unwrap();
+            db.put_cf(db.cf_handle(CF_NETWORK_STATE).unwrap(), "node_identity", bincode::serialize(&(&node, keypair.signing_key.to_bytes())).unwrap()).unwrap();
+            db.put_cf(db.cf_handle(CF_WEBHOOKS).unwrap(), format!("subscription:{}", subscription.id), bincode::serialize(&subscription).unwrap()).unwrap();
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), 0);
+        let report = storage.run_migrations().unwrap();
+        assert_eq!((report.from_version, report.to_version), (0, CURRENT_SCHEMA_VERSION));
+        assert_eq!(report.steps.len(), CURRENT_SCHEMA_VERSION as usize);
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+
+        assert_eq!(storage.load_raw_transaction("tx_legacy").unwrap().unwrap().raw_tx_id, "tx_legacy");
+        assert_eq!(storage.load_leader_raw_transactions("leader_1").unwrap().len(), 1);
+        assert_eq!(storage.load_node(&node.id.to_string()).unwrap().unwrap().id, node.id);
+        assert_eq!(storage.load_node_identity().unwrap().unwrap().0.id, node.id);
+        assert_eq!(storage.load_subscriptions().unwrap(), vec![subscription]);
+
+        let again = storage.run_migrations().unwrap();
+        assert!(again.steps.is_empty());
+        assert_eq!(rewrite_outdated_records(&storage).unwrap(), 0);
+        drop(storage);
+
+        // Everything left in a record column family now carries a version tag
+        let db = open_raw(dir.path());
+        let raw_cf = db.cf_handle(CF_RAW_TRANSACTIONS).unwrap();
+        let record = db.get_cf(raw_cf, "tx/leader_1/tx_legacy").unwrap().unwrap();
+        assert_eq!(record_version(&record).0, RawTransaction::VERSION);
+        assert_ne!(record_version(&record).1.len(), record.len());
+    }
+
+    #[test]
+    fn test_newer_records_and_schema_are_refused() {
+        // Test: Decode a record tagged with a version above the type's, and open a database whose
+        // stored schema version is above CURRENT_SCHEMA_VERSION
+        // Expected: Both fail with a storage error instead of misreading the data
+        println!("Expected: A node never reads data written by a newer schema");
+
+        let mut record = encode_record(&sample_tx("tx_new")).unwrap();
+        record[4..6].copy_from_slice(&99u16.to_be_bytes());
+        assert!(decode_record::<RawTransaction>(&record).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let db = open_raw(dir.path());
+            db.put_cf(db.cf_handle(CF_NETWORK_STATE).unwrap(), "schema_version", (CURRENT_SCHEMA_VERSION + 1).to_be_bytes()).unwrap();
+        }
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert!(matches!(storage.run_migrations(), Err(PclError::Storage(_))));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct EntryV1 {
+        tx_id: String,
+        amount: f64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct EntryV2 {
+        tx_id: String,
+        amount: f64,
+        leader_id: Option<String>,
+    }
+
+    impl Versioned for EntryV1 {
+        const VERSION: u16 = 1;
+    }
+
+    impl Versioned for EntryV2 {
+        const VERSION: u16 = 2;
+
+        fn upgrade(version: u16, payload: &[u8]) -> Result<Self> {
+            match version {
+                1 => {
+                    let old: EntryV1 = bincode::deserialize(payload)?;
+                    Ok(EntryV2 { tx_id: old.tx_id, amount: old.amount, leader_id: None })
+                }
+                _ => Err(PclError::Storage(format!("Unknown entry version {}", version))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_versioned_struct_upgrades_older_layout() {
+        // Test: Store an entry with the version 1 layout, then read it as the version 2 struct that
+        // added a field; also read a version 2 record directly
+        // Expected: The old record upgrades with the new field defaulted, new records round-trip
+        println!("Expected: Adding a field to a persisted struct keeps old records readable");
+
+        let old = encode_record(&EntryV1 { tx_id: "tx_1".to_string(), amount: 2.5 }).unwrap();
+        assert_eq!(
+            decode_record::<EntryV2>(&old).unwrap(),
+            EntryV2 { tx_id: "tx_1".to_string(), amount: 2.5, leader_id: None }
+        );
+
+        let current = EntryV2 { tx_id: "tx_2".to_string(), amount: 1.0, leader_id: Some("leader_1".to_string()) };
+        let bytes = encode_record(&current).unwrap();
+        assert_eq!(record_version(&bytes).0, 2);
+        assert_eq!(decode_record::<EntryV2>(&bytes).unwrap(), current);
+    }
+
+    // ProcessingTransaction as stored before it recorded an epoch
+    #[derive(Serialize)]
+    struct ProcessingEntryV1<'a> {
+        tx_id: &'a str,
+        tx_data: TransactionDataV1<'a>,
+        sig: &'a str,
+        leader: &'a str,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    }
+
+    #[test]
+    fn test_processing_entries_from_before_epochs_upgrade() {
+        // Test: Store a processing entry with the version 1 layout under its tx id, load it, and
+        // compare its final id with the same entry built in memory
+        // Expected: It loads with no recorded epoch and hashes exactly as before epochs existed
+        println!("Expected: Processing entries written before epochs stay readable and keep their ids");
+
+        let dir = tempfile::tempdir().unwrap();
+        let entry = ProcessingTransaction::new("tx_old".to_string(), sample_tx("tx_old").tx_data, "sig".to_string(), "leader_1".to_string());
+        let legacy = ProcessingEntryV1 { tx_id: &entry.tx_id, tx_data: (&entry.tx_data).into(), sig: &entry.sig, leader: &entry.leader, timestamp: entry.timestamp };
+        {
+            let db = open_raw(dir.path());
+            db.put_cf(db.cf_handle(CF_PROCESSING_TRANSACTIONS).unwrap(), "tx_old", bincode::serialize(&legacy).unwrap()).unwrap();
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        let loaded = storage.load_processing_transaction("tx_old").unwrap().unwrap();
+        assert_eq!(loaded.epoch, 0);
+        assert_eq!(loaded.final_tx_id().unwrap(), entry.final_tx_id().unwrap());
+        assert!(!String::from_utf8(loaded.signing_bytes().unwrap()).unwrap().contains("epoch"));
+
+        let mut recorded = loaded.clone();
+        recorded.epoch = 7;
+        assert_ne!(recorded.final_tx_id().unwrap(), loaded.final_tx_id().unwrap());
+        storage.store_processing_transaction(&recorded).unwrap();
+        assert_eq!(storage.load_processing_transaction("tx_old").unwrap().unwrap().epoch, 7);
+    }
+}
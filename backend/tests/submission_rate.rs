@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use serde::Serialize;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_burst_then_sustained_rate() {
+        // Test: Submit as one user until the burst is spent, wait part of a refill interval, then
+        // long enough for one token, while a second user submits alongside
+        // Expected: The burst is accepted, the next submission is refused with SUBMISSION_RATE_LIMITED
+        // and a retry time, one token comes back after 1 / rate seconds, and other users are unaffected
+        println!("Expected: Users get a burst of submissions, then the sustained rate");
+
+        let config = ConsensusConfig { user_submission_burst: 3, user_submissions_per_sec: 2.0, ..Default::default() };
+        config.validate().unwrap();
+        let mut limiter = SubmissionRateLimiter::new(&config);
+
+        for _ in 0..3 {
+            limiter.check("alice", 1_000).unwrap();
+        }
+        let refused = limiter.check("alice", 1_000).unwrap_err();
+        assert_eq!(refused.code(), "SUBMISSION_RATE_LIMITED");
+        assert!(refused.to_string().contains("retry in 500 ms"));
+        limiter.check("bob", 1_000).unwrap();
+
+        assert!(limiter.check("alice", 1_250).is_err());
+        limiter.check("alice", 1_500).unwrap();
+        assert!(limiter.check("alice", 1_500).is_err());
+        assert_eq!(limiter.violations("alice"), 3);
+        assert_eq!(limiter.violations("bob"), 0);
+
+        // A rate of 0 turns the limit off; a burst of 0 with a rate is rejected
+        let mut unlimited = SubmissionRateLimiter::new(&ConsensusConfig { user_submissions_per_sec: 0.0, ..Default::default() });
+        for _ in 0..100 {
+            unlimited.check("alice", 1_000).unwrap();
+        }
+        assert!(ConsensusConfig { user_submission_burst: 0, ..Default::default() }.validate().is_err());
+        assert!(ConsensusConfig { user_submissions_per_sec: -1.0, ..Default::default() }.validate().is_err());
+    }
+
+    // ReputationRecord as stored before rate limit violations were counted
+    #[derive(Serialize)]
+    struct ReputationRecordV1 {
+        identity: String,
+        tasks_on_time: u64,
+        tasks_late: u64,
+        tasks_missed: u64,
+        invalid_signatures: u64,
+        invalidated_submissions: u64,
+        updated_at: u64,
+    }
+
+    #[test]
+    fn test_violations_lower_reputation_and_persist() {
+        // Test: Store a reputation record with the version 1 layout, open the ledger over it, record
+        // rate limit violations, and reopen the ledger
+        // Expected: The old record loads with no violations, each violation lowers the score, and the
+        // count survives the restart
+        println!("Expected: Rate limit violations count against reputation and are persisted");
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut opts = rocksdb::Options::default();
+            opts.create_if_missing(true);
+            opts.create_missing_column_families(true);
+            let cfs = ALL_COLUMN_FAMILIES.map(|name| rocksdb::ColumnFamilyDescriptor::new(name, rocksdb::Options::default()));
+            let db = rocksdb::DB::open_cf_descriptors(&opts, dir.path(), cfs).unwrap();
+            let legacy = ReputationRecordV1 {
+                identity: "spammer".to_string(),
+                tasks_on_time: 4,
+                tasks_late: 0,
+                tasks_missed: 0,
+                invalid_signatures: 0,
+                invalidated_submissions: 0,
+                updated_at: 10,
+            };
+            db.put_cf(db.cf_handle(CF_REPUTATION).unwrap(), "spammer", bincode::serialize(&legacy).unwrap()).unwrap();
+        }
+
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        let mut ledger = ReputationLedger::open(storage.clone()).unwrap();
+        let before = ledger.get("spammer");
+        assert_eq!(before.tasks_on_time, 4);
+        assert_eq!(before.rate_limit_violations, 0);
+
+        ledger.record("spammer", ReputationEvent::RateLimitExceeded, 20).unwrap();
+        let once = ledger.score("spammer");
+        assert!(once < before.score());
+        ledger.record("spammer", ReputationEvent::RateLimitExceeded, 30).unwrap();
+        assert!(ledger.score("spammer") < once);
+        drop(ledger);
+
+        let reopened = ReputationLedger::open(storage).unwrap();
+        assert_eq!(reopened.get("spammer").rate_limit_violations, 2);
+        assert_eq!(reopened.get("spammer").tasks_on_time, 4);
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    fn offer(task: &ValidationTask, target_node: &str, leader: &NodeKeypair, assignee: &NodeKeypair) -> ValidationTaskMessage {
+        ValidationTaskMessage {
+            task_id: task.task_id.clone(),
+            envelope: EncryptedEnvelope::seal(leader, &assignee.public_key(), &serde_json::to_vec(task).unwrap()).unwrap(),
+            target_node: target_node.to_string(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_task_ids_are_derived_from_the_assignment() {
+        // Test: Derive task ids for the same assignment twice and for assignments that differ in one
+        // part each, then add the same task to the validation tasks mempool twice
+        // Expected: The same assignment gives the same id under the transaction's prefix, any change
+        // gives a new id, and the mempool refuses the second copy
+        println!("Expected: Task ids are a hash of transaction, task type, leader and assignee");
+
+        let id = derive_task_id("tx_1", "cross_validation", "leader_1", "alice");
+        assert_eq!(id, derive_task_id("tx_1", "cross_validation", "leader_1", "alice"));
+        assert!(id.starts_with("tx_1_task_"));
+        for other in [
+            derive_task_id("tx_2", "cross_validation", "leader_1", "alice"),
+            derive_task_id("tx_1", "signature_validation", "leader_1", "alice"),
+            derive_task_id("tx_1", "cross_validation", "leader_2", "alice"),
+            derive_task_id("tx_1", "cross_validation", "leader_1", "bob"),
+            derive_task_id("tx_1", "cross_validation", "leader_1a", "lice"),
+        ] {
+            assert_ne!(id, other);
+        }
+
+        let mut mempool = ValidationTasksMempool::new();
+        let task = ValidationTask::for_transaction("tx_1", "leader_1", ValidationTaskType::SignatureValidation, "alice");
+        mempool.add_task(task.clone()).unwrap();
+        assert!(matches!(mempool.add_task(task.clone()), Err(PclError::Mempool(_))));
+        assert!(mempool.contains_task(&task.task_id));
+        mempool.remove_tasks_for_tx("tx_1").unwrap();
+        assert!(!mempool.contains_task(&task.task_id));
+    }
+
+    #[tokio::test]
+    async fn test_offered_tasks_are_deduplicated() {
+        // Test: Offer this node a task twice, offer a task addressed to another node, and offer a task
+        // whose id was not derived from its assignment
+        // Expected: The first offer is taken and the repeat ignored, the other node's task is ignored,
+        // and the task with a made up id is refused
+        println!("Expected: Repeated offers of the same validation task are detected and dropped");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let node_id = node.id.to_string();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        let leader = NodeKeypair::new();
+
+        let task = ValidationTask::for_transaction("tx_1", "leader_1", ValidationTaskType::SpendingPowerValidation, &node_id);
+        let message = offer(&task, &node_id, &leader, &keypair);
+        assert!(consensus.handle_offered_validation_task(&message, &keypair).await.unwrap());
+        assert!(!consensus.handle_offered_validation_task(&message, &keypair).await.unwrap());
+
+        let elsewhere = ValidationTask::for_transaction("tx_2", "leader_1", ValidationTaskType::SpendingPowerValidation, "node_b");
+        assert!(!consensus.handle_offered_validation_task(&offer(&elsewhere, "node_b", &leader, &keypair), &keypair).await.unwrap());
+
+        let made_up = ValidationTask::new("tx_3_task_0000".to_string(), "leader_1".to_string(), ValidationTaskType::SignatureValidation);
+        assert!(matches!(
+            consensus.handle_offered_validation_task(&offer(&made_up, &node_id, &leader, &keypair), &keypair).await,
+            Err(PclError::Validation(_))
+        ));
+        let tasks = consensus.mempool.validation_tasks.read().await;
+        assert!(tasks.contains_task(&task.task_id) && !tasks.contains_task(&made_up.task_id));
+    }
+
+    #[tokio::test]
+    async fn test_workflow_assigns_tasks_to_the_submitting_node() {
+        // Test: Run a transaction through the workflow on a node with two current leaders, then build a
+        // manager with a keypair that is not the node's
+        // Expected: Every validation task sent out is addressed to the node the transaction was submitted
+        // through, its id is derived from that node's id, and its envelope is sealed by the identity key
+        // to the assignee's key; the mismatched keypair is refused
+        println!("Expected: Validation tasks are assigned to the real node id and sealed with real keys");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let node_id = node.id.to_string();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+        consensus.leader_election.write().await.current_leaders = vec!["leader_a".to_string(), "leader_b".to_string()];
+
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        consensus.mempool.tx.write().await.create_utxo("utxo_in".to_string(), 2.0, tx_data.user.to_string()).unwrap();
+        consensus.process_transaction_workflow(RawTransaction::new("tx_assigned".to_string(), tx_data)).await.unwrap();
+
+        let history = consensus.network_manager.lock().await.message_history.read().await.clone();
+        let sent: Vec<ValidationTaskMessage> = history.into_iter().filter_map(|message| match message {
+            NetworkMessage::ValidationTask(task) => Some(task),
+            _ => None,
+        }).collect();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|message| message.target_node == node_id));
+        let assigned = [("leader_a", ValidationTaskType::SignatureValidation), ("leader_b", ValidationTaskType::SpendingPowerValidation)];
+        for (message, (leader, task_type)) in sent.iter().zip(assigned) {
+            assert_eq!(message.task_id, derive_task_id("tx_assigned", task_type.as_str(), leader, &node_id));
+        }
+
+        // Sealed by the node's identity key to the assignee's registered key, so only that key opens them
+        let identity = hex::encode(keypair.public_key().to_bytes());
+        for message in &sent {
+            assert_eq!(message.envelope.sender_public_key, identity);
+            assert_eq!(message.open(&keypair).unwrap().task_id, message.task_id);
+            assert!(message.open(&NodeKeypair::new()).is_err());
+        }
+
+        let other = Node::new("10.0.0.2".parse().unwrap(), &NodeKeypair::new()).unwrap();
+        let network = NetworkManager::new(other.clone()).await.unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let mismatched = ConsensusManager::new(other, &keypair, network, StorageManager::new(other_dir.path()).unwrap());
+        assert!(matches!(mismatched, Err(PclError::NodeIdentity(_))));
+    }
+}
\ No newline at end of file
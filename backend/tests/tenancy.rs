@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_tenant_ids_paths_and_config() {
+        // Test: Validate tenant ids, split tenant paths and load tenants from PCL_TENANTS
+        // Expected: Only short lowercase ids are accepted, a tenant path splits into the id and the
+        // path its ledger answers, and duplicate or invalid tenants fail config validation
+        println!("Expected: Tenant ids are checked and tenant paths are routed by their prefix");
+
+        for valid in ["dev", "qa-2", "load_test"] {
+            assert!(validate_tenant_id(valid).is_ok());
+        }
+        for invalid in ["", "Dev", "qa/2", "a b", &"x".repeat(33)] {
+            assert!(validate_tenant_id(invalid).is_err());
+        }
+
+        assert_eq!(split_tenant_path("/tenants/dev/balance/alice"), Some(("dev", "/balance/alice")));
+        assert_eq!(split_tenant_path("/tenants/dev"), Some(("dev", "/")));
+        assert_eq!(split_tenant_path("/balance/alice"), None);
+        assert_eq!(tenant_key("dev", "consensus_state"), "tenant/dev/consensus_state");
+
+        let mut config = NodeConfig::default();
+        config.apply_env_overrides(|key| (key == "PCL_TENANTS").then(|| "dev, qa".to_string())).unwrap();
+        assert_eq!(config.tenancy.tenants, vec!["dev".to_string(), "qa".to_string()]);
+        assert!(config.validate().is_ok());
+        assert!(TenancyConfig { tenants: vec!["dev".to_string(), "dev".to_string()] }.validate().is_err());
+        assert!(TenancyConfig { tenants: vec!["Dev".to_string()] }.validate().is_err());
+        assert_eq!(PclError::UnknownTenant("nope".to_string()).code(), "UNKNOWN_TENANT");
+    }
+
+    #[test]
+    fn test_tenant_state_is_stored_apart() {
+        // Test: Store consensus state for the default ledger and two tenants, then reopen the database
+        // Expected: Each ledger reads back only its own state, and a tenant never saved has none
+        println!("Expected: Tenant snapshots live under their own key prefix");
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let storage = StorageManager::new(dir.path()).unwrap();
+            storage.store_consensus_state(b"default ledger").unwrap();
+            storage.store_tenant_consensus_state("dev", b"dev ledger").unwrap();
+            storage.store_tenant_consensus_state("qa", b"qa ledger").unwrap();
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert_eq!(storage.load_consensus_state().unwrap().unwrap(), b"default ledger");
+        assert_eq!(storage.load_tenant_consensus_state("dev").unwrap().unwrap(), b"dev ledger");
+        assert_eq!(storage.load_tenant_consensus_state("qa").unwrap().unwrap(), b"qa ledger");
+        assert!(storage.load_tenant_consensus_state("staging").unwrap().is_none());
+        assert!(storage.verify_integrity(false).unwrap().issues.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use pcl_backend::*;
 
     // Step 1: Alice sends transaction to leader Charlie
     #[test]
@@ -305,4 +306,321 @@ mod tests {
         println!("Expected: Transaction processing maintained under high load");
         // Implementation will test system under high load conditions
     }
-} 
\ No newline at end of file
+
+    // Fee sponsorship: a third party pays the fee
+    fn sponsored_transaction(alice: &NodeKeypair, sponsor: &NodeKeypair) -> TransactionData {
+        let mut tx = TransactionData::new(
+            vec![(Address::from_public_key(&NodeKeypair::new().public_key()), 1.0)],
+            vec![("alice_utxo1".to_string(), 1.2)],
+            Address::from_public_key(&alice.public_key()),
+            0.2,
+            0.1,
+        );
+        tx.set_fee_payer(&sponsor.public_key());
+        tx
+    }
+
+    #[test]
+    fn test_sponsored_transaction_fee_not_charged_to_sender() {
+        // Test: Alice's inputs cover amount and stake only, the dApp pays the fee
+        // Expected: Amounts validate without the fee coming from Alice's UTXOs
+        println!("Expected: 1.2 coins from Alice cover 1 to Bob + 0.2 stake, fee paid by sponsor");
+
+        let sponsor = NodeKeypair::new();
+        let tx = sponsored_transaction(&NodeKeypair::new(), &sponsor);
+
+        assert_eq!(tx.sender_fee(), 0.0);
+        assert!(tx.validate_amounts());
+    }
+
+    #[test]
+    fn test_sponsored_transaction_requires_both_signatures() {
+        // Test: Validate a sponsored transaction before and after the sponsor signs
+        // Expected: Only valid once both Alice and the sponsor have signed
+        println!("Expected: Sponsored transaction needs sender and fee payer signatures");
+
+        let alice = NodeKeypair::new();
+        let sponsor = NodeKeypair::new();
+        let mut tx = sponsored_transaction(&alice, &sponsor);
+
+        tx.sign_transaction(&alice).unwrap();
+        assert!(!tx.validate_signature());
+
+        tx.sign_as_fee_payer(&sponsor).unwrap();
+        assert!(tx.verify_fee_payer());
+        assert!(tx.validate_signature());
+        assert!(tx.verify_signature_with_public_key(&alice.public_key()));
+    }
+
+    #[test]
+    fn test_fee_payer_signature_rejected_from_other_key() {
+        // Test: Someone other than the named sponsor signs, or the tx changes after signing
+        // Expected: Fee payer verification fails
+        println!("Expected: Fee payer signature only valid for the named sponsor and unchanged tx");
+
+        let sponsor = NodeKeypair::new();
+        let mut tx = sponsored_transaction(&NodeKeypair::new(), &sponsor);
+
+        assert!(tx.sign_as_fee_payer(&NodeKeypair::new()).is_err());
+
+        tx.sign_as_fee_payer(&sponsor).unwrap();
+        tx.fee = 0.5;
+        assert!(!tx.verify_fee_payer());
+    }
+
+    #[test]
+    fn test_broadcast_targets_are_deterministic() {
+        // Test: Sample broadcast targets twice, once with the leader list in a different order
+        // Expected: Every node computes the same target set for the same processing tx
+        println!("Expected: Broadcast target sample depends only on proctx_id and leader set");
+
+        let leaders: Vec<String> = (1..=8).map(|i| format!("leader_{}", i)).collect();
+        let mut shuffled = leaders.clone();
+        shuffled.reverse();
+        shuffled.swap(0, 3);
+
+        let targets = sample_broadcast_targets("proctx_abc", &leaders, 3);
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets, sample_broadcast_targets("proctx_abc", &shuffled, 3));
+        assert!(targets.iter().all(|t| leaders.contains(t)));
+        assert_eq!(sample_broadcast_targets("proctx_abc", &leaders, 20).len(), leaders.len());
+    }
+
+    #[test]
+    fn test_broadcast_targets_vary_with_transaction() {
+        // Test: Sample targets for many different processing tx ids
+        // Expected: Load is spread across leaders rather than always hitting the same ones
+        println!("Expected: Different processing transactions pick different leaders");
+
+        let leaders: Vec<String> = (1..=8).map(|i| format!("leader_{}", i)).collect();
+        let mut picked = std::collections::HashSet::new();
+        for i in 0..50 {
+            picked.extend(sample_broadcast_targets(&format!("proctx_{}", i), &leaders, 2));
+        }
+        assert_eq!(picked.len(), leaders.len());
+    }
+
+    async fn consensus_with_validator(dir: &std::path::Path, validator: &NodeKeypair) -> (ConsensusManager, String) {
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let storage = StorageManager::new(dir).unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, storage).unwrap();
+
+        let mut validator_node = Node::new("10.0.0.2".parse().unwrap(), validator).unwrap();
+        validator_node.role = NodeRole::Validator;
+        let validator_id = validator_node.id.to_string();
+        consensus.node_registry.write().await.register_node(validator_node).unwrap();
+        (consensus, validator_id)
+    }
+
+    fn validator_broadcast(tx_id: &str, validator_id: &str, signer: &NodeKeypair) -> VerifiedProcessingTxBroadcastMessage {
+        VerifiedProcessingTxBroadcastMessage {
+            tx_id: tx_id.to_string(),
+            validator_id: validator_id.to_string(),
+            validator_signature_on_tx_id: hex::encode(signer.sign_data(&VerifiedProcessingTxBroadcastMessage::signing_bytes(tx_id, 7)).to_bytes()),
+            xmbl_cubic_root: 7,
+            target_leaders: Vec::new(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validator_broadcast_signature_verified() {
+        // Test: Receive broadcasts signed by the registered validator, by another key and by an unknown node
+        // Expected: Only the registered validator's broadcast is accepted, rejections are counted
+        println!("Expected: Validator broadcasts are verified against the registered public key");
+
+        let dir = tempfile::tempdir().unwrap();
+        let validator = NodeKeypair::new();
+        let (consensus, validator_id) = consensus_with_validator(dir.path(), &validator).await;
+
+        let valid = validator_broadcast("tx_1", &validator_id, &validator);
+        assert!(consensus.handle_verified_processing_tx_broadcast(&valid).await.is_ok());
+
+        let forged = validator_broadcast("tx_1", &validator_id, &NodeKeypair::new());
+        assert!(consensus.handle_verified_processing_tx_broadcast(&forged).await.is_err());
+
+        let unknown = validator_broadcast("tx_1", &uuid::Uuid::new_v4().to_string(), &validator);
+        assert!(consensus.handle_verified_processing_tx_broadcast(&unknown).await.is_err());
+
+        assert_eq!(consensus.consensus_state.read().await.rejected_validator_broadcasts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_gossip_is_rejected_without_panicking() {
+        // Test: Decode truncated and mistyped payloads, then dispatch well-formed messages with short
+        // signatures and unregistered or non-UUID senders
+        // Expected: Every case surfaces an error or is ignored; a valid broadcast is still routed
+        println!("Expected: Malformed gossip produces errors, never panics");
+
+        let dir = tempfile::tempdir().unwrap();
+        let validator = NodeKeypair::new();
+        let (consensus, validator_id) = consensus_with_validator(dir.path(), &validator).await;
+
+        let valid = NetworkMessage::VerifiedProcessingTxBroadcast(validator_broadcast("tx_1", &validator_id, &validator));
+        let bytes = valid.encode().unwrap();
+        for payload in [&bytes[..bytes.len() / 2], b"{}", b"{\"Pulse\":[]}", b"\xff\xfe", b"null"] {
+            assert!(NetworkMessage::decode(payload).is_err());
+        }
+
+        let mut short_sig = validator_broadcast("tx_1", &validator_id, &validator);
+        short_sig.validator_signature_on_tx_id = "ab".to_string();
+        assert!(consensus.handle_network_message(&NetworkMessage::VerifiedProcessingTxBroadcast(short_sig)).await.is_err());
+
+        let not_a_uuid = validator_broadcast("tx_1", "é", &validator);
+        assert!(consensus.handle_network_message(&NetworkMessage::VerifiedProcessingTxBroadcast(not_a_uuid)).await.is_err());
+
+        let decoded = NetworkMessage::decode(&bytes).unwrap();
+        assert!(consensus.handle_network_message(&decoded).await.is_ok());
+
+        let mut far_future = ValidationTask::new("task_1".to_string(), "leader_1".to_string(), ValidationTaskType::SignatureValidation);
+        far_future.assigned_at = chrono::DateTime::<chrono::Utc>::MAX_UTC;
+        assert!(!far_future.is_expired(5));
+
+        assert_eq!(short_hex("ab"), "ab");
+        assert_eq!(short_hex("éééééééééé"), "éééééééé");
+        assert_eq!(short_hex("0123456789abcdef0123"), "0123456789abcdef");
+    }
+
+    #[tokio::test]
+    async fn test_workflow_feeds_leader_performance_into_election() {
+        // Test: Run a transaction through the six-step workflow with three current leaders
+        // Expected: Originating, signing, finalizing and task activity are recorded per leader, and the
+        // election score comes from those records instead of a fixed per-role value
+        println!("Expected: Workflow events populate leader performance used by leader election");
+
+        let dir = tempfile::tempdir().unwrap();
+        let (consensus, _) = consensus_with_validator(dir.path(), &NodeKeypair::new()).await;
+        let local_id = consensus.local_node.id.to_string();
+        let leaders = vec!["leader_a".to_string(), "leader_b".to_string(), "leader_c".to_string()];
+        consensus.leader_election.write().await.current_leaders = leaders.clone();
+        assert_eq!(consensus.election_performance_score("leader_a").await, 0.5);
+
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        consensus.mempool.tx.write().await.create_utxo("utxo_in".to_string(), 2.0, tx_data.user.to_string()).unwrap();
+        consensus.process_transaction_workflow(RawTransaction::new("tx_perf".to_string(), tx_data)).await.unwrap();
+
+        let state = consensus.consensus_state.read().await;
+        let local = &state.leader_performance[&local_id];
+        assert_eq!(local.transactions_originated, 1);
+        assert_eq!(local.transactions_signed, 1);
+        assert_eq!(local.transactions_processed, 1);
+        assert!(local.last_signature_at.is_some());
+        for leader in &leaders {
+            let performance = &state.leader_performance[leader];
+            assert_eq!(performance.validation_tasks_assigned, 1);
+            assert_eq!(performance.validation_tasks_completed, 1);
+        }
+        drop(state);
+
+        consensus.record_pulse_data(consensus::PulseData {
+            node_id: "leader_a".to_string(),
+            family_id: uuid::Uuid::new_v4(),
+            pulse_count: 1,
+            average_response_time_ms: 40.0,
+            uptime_percentage: 100.0,
+            last_pulse: chrono::Utc::now(),
+        }).await;
+        let with_pulse = consensus.election_performance_score("leader_a").await;
+        assert!(with_pulse > consensus.election_performance_score("leader_b").await);
+        assert_eq!(consensus.leader_dashboard().await.len(), leaders.len());
+    }
+
+    #[tokio::test]
+    async fn test_workflow_records_step_latency_histograms() {
+        // Test: Run one transaction through the six-step workflow
+        // Expected: Each step and the end-to-end latency are observed once in the Prometheus histograms
+        println!("Expected: Workflow step durations are exported as histograms");
+
+        let dir = tempfile::tempdir().unwrap();
+        let (consensus, _) = consensus_with_validator(dir.path(), &NodeKeypair::new()).await;
+        consensus.leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo_in".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        consensus.mempool.tx.write().await.create_utxo("utxo_in".to_string(), 2.0, tx_data.user.to_string()).unwrap();
+        consensus.process_transaction_workflow(RawTransaction::new("tx_timed".to_string(), tx_data)).await.unwrap();
+
+        let metrics = consensus.consensus_state.read().await.workflow_metrics.clone();
+        for (step, histogram) in &metrics.step_durations {
+            assert_eq!(histogram.count, 1, "step {:?}", step);
+        }
+        assert_eq!(metrics.end_to_end.count, 1);
+
+        let text = consensus.workflow_metrics_prometheus().await;
+        assert!(text.contains("pcl_workflow_step_duration_seconds_count{step=\"finalization\"} 1"));
+        assert!(text.contains("pcl_workflow_end_to_end_seconds_bucket{le=\"+Inf\"} 1"));
+    }
+
+    #[test]
+    fn test_user_task_completion_signature() {
+        // Test: A wallet signs a task completion, then the verdict, the task and the claimed validator are tampered with
+        // Expected: The original verifies, every tampered copy fails signature verification
+        println!("Expected: Task completions are bound to the wallet key and the signed fields");
+
+        let wallet = NodeKeypair::new();
+        let completion = UserValidationTaskCompletion::sign("task-1", "raw-tx-1", true, 1_700_000_000_000, &wallet);
+        assert_eq!(completion.validator.as_str(), wallet.address());
+        assert!(completion.verify().is_ok());
+
+        let mut flipped = completion.clone();
+        flipped.result = false;
+        assert!(matches!(flipped.verify(), Err(PclError::SignatureVerification(_))));
+
+        let mut other_task = completion.clone();
+        other_task.task_id = "task-2".to_string();
+        assert!(matches!(other_task.verify(), Err(PclError::SignatureVerification(_))));
+
+        let mut impersonated = completion.clone();
+        impersonated.validator = Address::from_public_key(&NodeKeypair::new().public_key());
+        assert!(matches!(impersonated.verify(), Err(PclError::SignatureVerification(_))));
+
+        let json = serde_json::to_value(&completion).unwrap();
+        let parsed: UserValidationTaskCompletion = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, completion);
+    }
+
+    #[tokio::test]
+    async fn test_finalization_moves_balances_from_sender_to_recipient() {
+        // Test: Fund a sender with one 10 XMBL output and run a 4 XMBL transfer with 0.2 stake and 0.1 fee
+        // through the workflow
+        // Expected: The input is spent, the recipient holds 4 and the sender its 5.7 change, and the
+        // processing entry is gone from the mempool
+        println!("Expected: Manager finalization spends the real inputs and creates the real outputs");
+
+        let dir = tempfile::tempdir().unwrap();
+        let (consensus, _) = consensus_with_validator(dir.path(), &NodeKeypair::new()).await;
+        consensus.leader_election.write().await.current_leaders = vec!["leader_a".to_string()];
+        let (sender, recipient) = (NodeKeypair::new().address(), NodeKeypair::new().address());
+        consensus.mempool.tx.write().await.create_utxo("utxo_sender".to_string(), 10.0, sender.clone()).unwrap();
+
+        let tx_data = TransactionData::new(
+            vec![(recipient.parse().unwrap(), 4.0)],
+            vec![("utxo_sender".to_string(), 10.0)],
+            sender.parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        consensus.process_transaction_workflow(RawTransaction::new("tx_paid".to_string(), tx_data)).await.unwrap();
+
+        let tx_pool = consensus.mempool.tx.read().await;
+        assert!(tx_pool.utxo_pool["utxo_sender"].spent);
+        let balances = tx_pool.balances();
+        assert!((balances[&sender] - 5.7).abs() < 1e-9);
+        assert_eq!(balances[&recipient], 4.0);
+        assert!(tx_pool.finalized_transactions.contains_key("tx_paid"));
+        drop(tx_pool);
+        assert!(consensus.mempool.processing_tx.read().await.transactions.is_empty());
+    }
+}
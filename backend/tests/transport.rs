@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    async fn network_on(memory: &MemoryNetwork, ip: &str, keypair: &NodeKeypair) -> NetworkManager {
+        let node = Node::new(ip.parse().unwrap(), keypair).unwrap();
+        let mut network = NetworkManager::new(node).await.unwrap();
+        let endpoint = memory.endpoint(&network.local_peer_id());
+        network.set_transport(Box::new(endpoint));
+        network
+    }
+
+    fn uptime_pulses(received: &[(PeerId, NetworkMessage)]) -> Vec<u64> {
+        received.iter()
+            .filter_map(|(_, message)| match message {
+                NetworkMessage::UptimeData(uptime) => Some(uptime.pulse_count),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_memory_transport_controls_delivery_order_and_drops() {
+        // Test: Connect node A to B and C over the in-memory transport, have A broadcast twice, deliver
+        // the second message to B before the first and drop the first on its way to C, then block A -> C
+        // and broadcast again
+        // Expected: Sends are queued in a fixed order until delivered; each peer receives in delivery
+        // order, dropped and blocked messages never arrive, and sends to unknown peers fail
+        println!("Expected: Tests decide exactly which messages arrive and in what order");
+
+        let memory = MemoryNetwork::new();
+        let mut a = network_on(&memory, "10.0.0.1", &NodeKeypair::new()).await;
+        let mut b = network_on(&memory, "10.0.0.2", &NodeKeypair::new()).await;
+        let mut c = network_on(&memory, "10.0.0.3", &NodeKeypair::new()).await;
+        let (a_id, b_id, c_id) = (a.local_peer_id(), b.local_peer_id(), c.local_peer_id());
+        memory.connect(&a_id, &b_id);
+        memory.connect(&a_id, &c_id);
+        for network in [&mut a, &mut b, &mut c] {
+            assert!(network.receive().await.is_empty());
+        }
+        assert_eq!(a.get_peer_count().await, 2);
+        assert_eq!(b.get_connected_peers().await, vec![a_id.clone()]);
+
+        a.broadcast_uptime_data(99.0, 1).await.unwrap();
+        a.broadcast_uptime_data(99.0, 2).await.unwrap();
+        let in_flight = memory.in_flight();
+        let mut recipients = vec![b_id.clone(), c_id.clone()];
+        recipients.sort();
+        let expected: Vec<(u64, &str)> = (0..4).map(|id| (id, recipients[id as usize % 2].as_str())).collect();
+        assert_eq!(in_flight.iter().map(|message| (message.id, message.to.as_str())).collect::<Vec<_>>(), expected);
+
+        let to = |peer: &str, nth: usize| in_flight.iter().filter(|message| message.to == peer).nth(nth).unwrap().id;
+        assert!(memory.deliver(to(&b_id, 1)));
+        assert!(memory.deliver(to(&b_id, 0)));
+        assert!(memory.drop_message(to(&c_id, 0)));
+        assert_eq!(memory.deliver_all(), 1);
+        let received = b.receive().await;
+        assert_eq!(uptime_pulses(&received), vec![2, 1]);
+        assert!(received.iter().all(|(peer, _)| *peer == a_id));
+        assert_eq!(uptime_pulses(&c.receive().await), vec![2]);
+
+        memory.block(&a_id, &c_id);
+        a.broadcast_uptime_data(99.0, 3).await.unwrap();
+        assert_eq!(memory.deliver_all(), 1);
+        assert_eq!(uptime_pulses(&b.receive().await), vec![3]);
+        assert!(c.receive().await.is_empty());
+        assert_eq!(memory.dropped(), 2);
+
+        let mut stray = memory.endpoint("peer_stray");
+        assert!(stray.send(&"peer_unknown".to_string(), b"{}").is_err());
+        assert_eq!(stray.peer_id(), "peer_stray");
+        let mut stub = Libp2pStub::new();
+        assert!(stub.send(&"peer_unknown".to_string(), b"{}").is_ok());
+        assert!(stub.poll_events().is_empty());
+        assert_eq!((stub.name(), stub.sent), ("libp2p-stub", 1));
+    }
+
+    #[tokio::test]
+    async fn test_consensus_nodes_over_memory_transport_are_reproducible() {
+        // Test: Run two consensus managers over the in-memory transport; A sends a pulse and then its
+        // peer binding, and B receives them first in the order sent and, on a fresh network, reversed
+        // Expected: In send order the pulse is refused because A is not bound yet; reversed, both are
+        // accepted. A later pulse that is dropped never reaches B.
+        println!("Expected: NetworkManager and ConsensusManager interactions replay the same way every run");
+
+        for binding_first in [false, true] {
+            let memory = MemoryNetwork::new();
+            let (key_a, key_b) = (NodeKeypair::new(), NodeKeypair::new());
+            let network_a = network_on(&memory, "10.0.0.1", &key_a).await;
+            let network_b = network_on(&memory, "10.0.0.2", &key_b).await;
+            let (peer_a, peer_b) = (network_a.local_peer_id(), network_b.local_peer_id());
+            let node_a = network_a.local_node.clone();
+            let node_b = network_b.local_node.clone();
+
+            let dir_a = tempfile::tempdir().unwrap();
+            let dir_b = tempfile::tempdir().unwrap();
+            let a = ConsensusManager::new(node_a.clone(), &key_a, network_a, StorageManager::new(dir_a.path()).unwrap()).unwrap();
+            let b = ConsensusManager::new(node_b, &key_b, network_b, StorageManager::new(dir_b.path()).unwrap()).unwrap();
+            b.node_registry.write().await.register_node(node_a.clone()).unwrap();
+
+            memory.connect(&peer_a, &peer_b);
+            assert_eq!((a.process_inbound().await, b.process_inbound().await), (0, 0));
+
+            {
+                let mut network = a.network_manager.lock().await;
+                network.send_pulse(uuid::Uuid::new_v4()).await.unwrap();
+                network.publish_peer_binding(&key_a).await.unwrap();
+            }
+            let in_flight = memory.in_flight();
+            assert_eq!(in_flight.len(), 2);
+            assert!(in_flight.iter().all(|message| message.from == peer_a && message.to == peer_b));
+            let mut order: Vec<u64> = in_flight.iter().map(|message| message.id).collect();
+            if binding_first {
+                order.reverse();
+            }
+            for id in order {
+                assert!(memory.deliver(id));
+            }
+
+            let handled = b.process_inbound().await;
+            assert_eq!(handled, if binding_first { 2 } else { 1 });
+            assert_eq!(b.peer_bindings.read().await.node_for_peer(&peer_a), Some(node_a.id.to_string().as_str()));
+
+            a.network_manager.lock().await.send_pulse(uuid::Uuid::new_v4()).await.unwrap();
+            let pulse = memory.in_flight()[0].id;
+            assert!(memory.drop_message(pulse));
+            assert_eq!(b.process_inbound().await, 0);
+            assert_eq!(memory.dropped(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_network_without_a_transport_keeps_published_messages_in_its_history() {
+        // Test: Publish uptime pulses from a network manager that was never given a transport
+        // Expected: Both land in the message history as before transports existed, and nothing is received
+        println!("Expected: Without a transport, published messages go to the history only");
+
+        let keypair = NodeKeypair::new();
+        let mut network = NetworkManager::new(Node::new("10.0.0.9".parse().unwrap(), &keypair).unwrap()).await.unwrap();
+        assert!(network.transport.is_none());
+
+        network.broadcast_uptime_data(99.0, 1).await.unwrap();
+        network.broadcast_uptime_data(99.0, 2).await.unwrap();
+        assert_eq!(network.get_message_history().await.len(), 2);
+        assert!(network.receive().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validator_broadcast_finalizes_on_a_sampled_leader() {
+        // Test: Over the in-memory transport, A runs a transfer through the workflow with B as its only
+        // current leader; B holds the same processing entry and has A registered as a validator
+        // Expected: A's validator broadcast names B, its signature verifies on B against A's key, and B
+        // finalizes its entry with the same signature and moves the value to the recipient
+        println!("Expected: The step 6 broadcast reaches the sampled leaders and finalizes there");
+
+        let memory = MemoryNetwork::new();
+        let (key_a, key_b) = (NodeKeypair::new(), NodeKeypair::new());
+        let network_a = network_on(&memory, "10.0.0.1", &key_a).await;
+        let network_b = network_on(&memory, "10.0.0.2", &key_b).await;
+        let (peer_a, peer_b) = (network_a.local_peer_id(), network_b.local_peer_id());
+        let (node_a, node_b) = (network_a.local_node.clone(), network_b.local_node.clone());
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+        let a = ConsensusManager::new(node_a.clone(), &key_a, network_a, StorageManager::new(dir_a.path()).unwrap()).unwrap();
+        let b = ConsensusManager::new(node_b.clone(), &key_b, network_b, StorageManager::new(dir_b.path()).unwrap()).unwrap();
+        let mut validator = node_a.clone();
+        validator.role = NodeRole::Validator;
+        b.node_registry.write().await.register_node(validator).unwrap();
+        a.leader_election.write().await.current_leaders = vec![node_b.id.to_string()];
+
+        memory.connect(&peer_a, &peer_b);
+        assert_eq!((a.process_inbound().await, b.process_inbound().await), (0, 0));
+        a.network_manager.lock().await.publish_peer_binding(&key_a).await.unwrap();
+
+        let (sender, recipient) = (NodeKeypair::new().address(), NodeKeypair::new().address());
+        let tx_data = TransactionData::new(
+            vec![(recipient.parse().unwrap(), 4.0)],
+            vec![("utxo_sender".to_string(), 10.0)],
+            sender.parse().unwrap(),
+            0.2,
+            0.1,
+        );
+        for consensus in [&a, &b] {
+            consensus.mempool.tx.write().await.create_utxo("utxo_sender".to_string(), 10.0, sender.clone()).unwrap();
+        }
+        let entry = ProcessingTransaction::new("tx_shared".to_string(), tx_data.clone(), String::new(), node_a.id.to_string());
+        b.mempool.add_processing_transaction(entry).await.unwrap();
+        a.process_transaction_workflow(RawTransaction::new("tx_shared".to_string(), tx_data)).await.unwrap();
+
+        let broadcast = a.network_manager.lock().await.get_message_history().await.into_iter()
+            .find_map(|message| match message {
+                NetworkMessage::VerifiedProcessingTxBroadcast(broadcast) => Some(broadcast),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(broadcast.target_leaders, vec![node_b.id.to_string()]);
+
+        for message in memory.in_flight() {
+            assert!(memory.deliver(message.id));
+        }
+        b.process_inbound().await;
+
+        assert_eq!(b.consensus_state.read().await.rejected_validator_broadcasts, 0);
+        assert!(b.mempool.processing_tx.read().await.transactions.is_empty());
+        let tx_pool = b.mempool.tx.read().await;
+        assert_eq!(tx_pool.finalized_transactions["tx_shared"].validator_signature, broadcast.validator_signature_on_tx_id);
+        assert_eq!(tx_pool.balances()[&recipient], 4.0);
+    }
+}
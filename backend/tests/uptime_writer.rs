@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    fn uptime(node_id: &str, pulse_count: u64) -> UptimeData {
+        UptimeData {
+            node_id: node_id.to_string(),
+            total_uptime_seconds: pulse_count * 20,
+            last_seen: chrono::Utc::now(),
+            pulse_count,
+            average_response_time_ms: 40.0,
+            uptime_percentage: 99.0,
+        }
+    }
+
+    #[test]
+    fn test_uptime_updates_are_coalesced_and_flushed_in_one_batch() {
+        // Test: Record several pulses for two nodes, read them before and after a flush, and reopen
+        // the storage
+        // Expected: Reads see the newest buffered record before anything is written, only the latest
+        // record per node is flushed, in a single batch, and it survives a restart
+        println!("Expected: Uptime records are buffered in memory and written in batches");
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+            let mut buffer = UptimeWriteBuffer::new(storage.clone());
+            for pulse_count in 1..=3 {
+                buffer.record(uptime("node_a", pulse_count));
+            }
+            buffer.record(uptime("node_b", 1));
+            assert_eq!(buffer.get("node_a").unwrap().unwrap().pulse_count, 3);
+            assert!(storage.load_uptime_data("node_a").unwrap().is_none());
+
+            assert_eq!(buffer.flush().unwrap(), 2);
+            assert_eq!(buffer.flush().unwrap(), 0);
+            let stats = buffer.stats();
+            assert_eq!((stats.pending, stats.updates, stats.coalesced, stats.batches, stats.written), (0, 4, 2, 1, 2));
+
+            buffer.record(uptime("node_a", 4));
+            assert_eq!(buffer.get("node_a").unwrap().unwrap().pulse_count, 4);
+            assert_eq!(storage.load_uptime_data("node_a").unwrap().unwrap().pulse_count, 3);
+        }
+
+        let buffer = UptimeWriteBuffer::new(Arc::new(StorageManager::new(dir.path()).unwrap()));
+        assert_eq!(buffer.get("node_a").unwrap().unwrap().pulse_count, 3);
+        assert_eq!(buffer.get("node_b").unwrap().unwrap().pulse_count, 1);
+        assert!(buffer.get("node_c").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pulses_accumulate_uptime_until_shutdown_flushes_it() {
+        // Test: Record three pulses for a node 20 seconds apart and a fourth after a long silence, then
+        // shut the consensus manager down
+        // Expected: Uptime grows by each gap up to the pulse timeout, nothing is stored until the
+        // shutdown flush, and the status reports the buffered writes
+        println!("Expected: Pulse uptime is buffered by the consensus manager and flushed at shutdown");
+
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = NodeKeypair::new();
+        let node = Node::new("10.0.0.1".parse().unwrap(), &keypair).unwrap();
+        let network = NetworkManager::new(node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(node, &keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let start = chrono::Utc::now();
+        for (pulse_count, offset_secs) in [(1, 0), (2, 20), (3, 40), (4, 3_600)] {
+            consensus.record_pulse_data(pcl_backend::consensus::PulseData {
+                node_id: "node_a".to_string(),
+                family_id: uuid::Uuid::new_v4(),
+                pulse_count,
+                average_response_time_ms: 30.0,
+                uptime_percentage: 98.0,
+                last_pulse: start + chrono::Duration::seconds(offset_secs),
+            }).await;
+        }
+        assert!(consensus.storage_manager.load_uptime_data("node_a").unwrap().is_none());
+        let writes = consensus.get_system_status().await.unwrap().uptime_writes;
+        assert_eq!((writes.pending, writes.updates, writes.coalesced), (1, 4, 3));
+
+        consensus.shutdown().await.unwrap();
+        let stored = consensus.storage_manager.load_uptime_data("node_a").unwrap().unwrap();
+        assert_eq!((stored.pulse_count, stored.total_uptime_seconds), (4, 100));
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::collections::HashMap;
+
+    fn funded(outputs: &[(&str, &str, f64)]) -> HashMap<String, UtxoEntry> {
+        let mut utxos = HashMap::new();
+        for (utxo_id, owner, amount) in outputs {
+            utxo::create_utxo(&mut utxos, utxo_id, owner, *amount);
+        }
+        utxos
+    }
+
+    #[test]
+    fn test_select_utxos_prefers_the_named_output_then_id_order() {
+        // Test: Select inputs for an owner holding three outputs (one spent) and an output of someone
+        // else, with and without a preferred output, and for more than the owner holds
+        // Expected: The preferred output comes first, the rest follow in id order only until the amount
+        // is covered; spent and foreign outputs are never picked; too large an amount is refused
+        println!("Expected: Input selection covers the amount from the owner's unspent outputs only");
+
+        let mut utxos = funded(&[("a", "alice", 5.0), ("b", "alice", 3.0), ("c", "alice", 4.0), ("d", "bob", 50.0), ("e", "alice", 20.0)]);
+        utxo::spend(&mut utxos, &["e".to_string()]);
+
+        let (inputs, total) = utxo::select_utxos(&utxos, "alice", None, 7.0).unwrap();
+        assert_eq!(inputs, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(total, 8.0);
+
+        let (inputs, total) = utxo::select_utxos(&utxos, "alice", Some("c"), 4.0).unwrap();
+        assert_eq!(inputs, vec!["c".to_string()]);
+        assert_eq!(total, 4.0);
+
+        let (inputs, total) = utxo::select_utxos(&utxos, "alice", Some("c"), 12.0).unwrap();
+        assert_eq!(inputs, vec!["c".to_string(), "a".to_string(), "b".to_string()]);
+        assert_eq!(total, 12.0);
+
+        assert!(utxo::select_utxos(&utxos, "alice", None, 12.5).is_err());
+        assert!(utxo::select_utxos(&utxos, "carol", None, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_transfer_pays_change_back_to_the_sender() {
+        // Test: Transfer 6 with a fee of 1 from a sender holding 5 and 4
+        // Expected: Both inputs are spent, the recipient gets "tx:0" for 6, the sender gets "tx:1" for
+        // the remaining 2, and balances add up to what was there less the fee
+        println!("Expected: A transfer spends its inputs and returns the change as tx:1");
+
+        let mut utxos = funded(&[("a", "alice", 5.0), ("b", "alice", 4.0)]);
+        let transfer = Transfer { sender: "alice", preferred: None, recipient: "bob", amount: 6.0, fee: 1.0, sponsor: None };
+        utxo::apply_transfer(&mut utxos, "tx", &transfer).unwrap();
+
+        assert!(utxos["a"].spent && utxos["b"].spent);
+        assert_eq!((utxos["tx:0"].owner.as_str(), utxos["tx:0"].amount), ("bob", 6.0));
+        assert_eq!((utxos["tx:1"].owner.as_str(), utxos["tx:1"].amount), ("alice", 2.0));
+        assert!(!utxos.contains_key("tx:2"));
+        assert_eq!(utxo::balance_of(&utxos, "alice"), 2.0);
+        assert_eq!(utxo::balances(&utxos).values().sum::<f64>(), 8.0);
+
+        // An exact spend leaves no change output behind
+        let transfer = Transfer { sender: "alice", preferred: Some("tx:1"), recipient: "bob", amount: 1.5, fee: 0.5, sponsor: None };
+        utxo::apply_transfer(&mut utxos, "tx2", &transfer).unwrap();
+        assert!(!utxos.contains_key("tx2:1"));
+        assert_eq!(utxo::balance_of(&utxos, "alice"), 0.0);
+        assert_eq!(utxo::balance_of(&utxos, "bob"), 7.5);
+    }
+
+    #[test]
+    fn test_sponsored_transfer_and_shortfall() {
+        // Test: Transfer with the fee paid by a sponsor, then a transfer whose sponsor cannot cover the fee
+        // Expected: The sponsor's change is "tx:2" and the sender pays no fee; the uncovered transfer
+        // fails without spending or creating anything
+        println!("Expected: A sponsor pays the fee from its own outputs, and a shortfall changes nothing");
+
+        let mut utxos = funded(&[("a", "alice", 10.0), ("s", "sponsor", 3.0)]);
+        let transfer = Transfer { sender: "alice", preferred: None, recipient: "bob", amount: 4.0, fee: 1.0, sponsor: Some("sponsor") };
+        utxo::apply_transfer(&mut utxos, "tx", &transfer).unwrap();
+        assert_eq!(utxos["tx:1"].amount, 6.0);
+        assert_eq!((utxos["tx:2"].owner.as_str(), utxos["tx:2"].amount), ("sponsor", 2.0));
+
+        let before = utxos.len();
+        let transfer = Transfer { sender: "alice", preferred: None, recipient: "bob", amount: 1.0, fee: 5.0, sponsor: Some("sponsor") };
+        assert!(utxo::apply_transfer(&mut utxos, "tx3", &transfer).is_err());
+        assert_eq!(utxos.len(), before);
+        assert_eq!(utxo::balance_of(&utxos, "alice"), 6.0);
+        assert_eq!(utxo::balance_of(&utxos, "sponsor"), 2.0);
+    }
+
+    #[test]
+    fn test_sender_cannot_sponsor_its_own_transfer() {
+        // Test: Transfer 8 of alice's 10 with a fee of 3, naming alice as the sponsor
+        // Expected: Refused, since the sender and sponsor selections would both count the same 10;
+        // nothing is spent or created
+        println!("Expected: A self-sponsored transfer is refused instead of spending more than the sender holds");
+
+        let mut utxos = funded(&[("a", "alice", 10.0)]);
+        let transfer = Transfer { sender: "alice", preferred: None, recipient: "bob", amount: 8.0, fee: 3.0, sponsor: Some("alice") };
+        assert!(utxo::apply_transfer(&mut utxos, "tx", &transfer).is_err());
+        assert_eq!(utxos.len(), 1);
+        assert_eq!(utxo::balance_of(&utxos, "alice"), 10.0);
+        assert_eq!(utxo::balance_of(&utxos, "bob"), 0.0);
+    }
+}
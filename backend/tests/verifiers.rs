@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    fn raw_tx() -> RawTransaction {
+        let tx_data = TransactionData::new(
+            vec![(NodeKeypair::new().address().parse().unwrap(), 1.0)],
+            vec![("utxo1".to_string(), 2.0)],
+            NodeKeypair::new().address().parse().unwrap(),
+            0.5,
+            0.25,
+        );
+        RawTransaction::new("tx_1".to_string(), tx_data)
+    }
+
+    struct MemoVerifier;
+
+    impl TaskVerifier for MemoVerifier {
+        fn task_type(&self) -> &str {
+            "memo_validation"
+        }
+
+        fn verify(&self, subject: &TaskSubject) -> Result<bool> {
+            Ok(subject.raw_tx.tx_data.nonce == 7)
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatches_by_task_type_and_refuses_unknown_types() {
+        // Test: Check a transaction against the built-in verifiers, ask for a task type nothing is
+        // registered for, then register a verifier for it and check again
+        // Expected: Each built-in type has a verifier, an unknown type is a validation error rather
+        // than a pass, and a newly registered type is checked without any other change
+        println!("Expected: Task types are verified through the registry and unknown types are refused");
+
+        let registry = TaskVerifierRegistry::new();
+        for task_type in [
+            ValidationTaskType::SignatureValidation,
+            ValidationTaskType::SpendingPowerValidation,
+            ValidationTaskType::TimestampValidation,
+            ValidationTaskType::MathValidation,
+            ValidationTaskType::FinalValidation,
+        ] {
+            assert!(registry.supports(task_type.as_str()));
+        }
+
+        let mut tx = raw_tx();
+        let mut subject = TaskSubject::new(&tx);
+        assert!(!registry.verify("signature_validation", &subject).unwrap());
+        assert!(registry.verify("spending_power_validation", &subject).unwrap());
+        subject.now = tx.tx_data.timestamp + chrono::Duration::seconds(30);
+        assert!(registry.verify("timestamp_validation", &subject).unwrap());
+        subject.now = tx.tx_data.timestamp + chrono::Duration::hours(2);
+        assert!(!registry.verify("timestamp_validation", &subject).unwrap());
+        assert!(matches!(registry.verify("memo_validation", &subject), Err(PclError::Validation(_))));
+
+        let mut extended = registry.clone();
+        extended.register(Arc::new(MemoVerifier));
+        tx.tx_data.set_nonce(7);
+        assert!(extended.verify("memo_validation", &TaskSubject::new(&tx)).unwrap());
+        assert!(!registry.supports("memo_validation"));
+        assert_eq!(extended.task_types().len(), 6);
+        assert!(TaskVerifierRegistry::empty().verify("signature_validation", &TaskSubject::new(&tx)).is_err());
+    }
+
+    #[test]
+    fn test_math_and_digital_root_verifiers_check_the_processing_entry() {
+        // Test: Check a processing entry built from averaged validation timestamps, one with a shifted
+        // timestamp, and digital roots claimed for it, with and without the entry
+        // Expected: The averaged entry and its true digital root pass, the shifted entry and a wrong
+        // root fail, and a missing entry or claim is an error
+        println!("Expected: Timestamp math and digital root tasks are checked against the leader's entry");
+
+        let mut tx = raw_tx();
+        let base = chrono::Utc::now();
+        tx.add_validation_timestamp(base);
+        tx.add_validation_timestamp(base + chrono::Duration::seconds(10));
+        let processing = ProcessingTransaction::from_raw_transaction(&tx, String::new(), "leader_1".to_string()).unwrap();
+        let registry = TaskVerifierRegistry::new();
+
+        let mut subject = TaskSubject::new(&tx);
+        assert!(matches!(registry.verify("math_validation", &subject), Err(PclError::Validation(_))));
+        subject.processing_tx = Some(&processing);
+        assert!(registry.verify("math_validation", &subject).unwrap());
+        let mut shifted = processing.clone();
+        shifted.timestamp += chrono::Duration::seconds(1);
+        assert!(!registry.verify("math_validation", &TaskSubject { processing_tx: Some(&shifted), ..subject.clone() }).unwrap());
+
+        assert!(registry.verify("final_validation", &subject).is_err());
+        let root = processing.tx_data.digital_root().unwrap();
+        subject.digital_root = Some(root);
+        assert!(registry.verify("final_validation", &subject).unwrap());
+        subject.digital_root = Some((root + 1) % 10);
+        assert!(!registry.verify("final_validation", &subject).unwrap());
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+
+    #[test]
+    fn test_version_skew_beyond_one_minor_version() {
+        // Test: Compare this node's version with peers on the same, neighbouring and distant minor
+        // versions, another major version, and versions that do not parse
+        // Expected: Only peers more than one minor version apart or on another major are flagged, and
+        // unparseable versions are never flagged
+        println!("Expected: Nodes more than one minor version apart are told apart from close ones");
+
+        assert_eq!(parse_version("1.4.2"), Some((1, 4, 2)));
+        assert_eq!(parse_version("v0.3.0-rc.1+abc"), Some((0, 3, 0)));
+        assert_eq!(parse_version("1.4"), None);
+        assert_eq!(parse_version("1.4.2.7"), None);
+
+        assert!(!versions_too_far_apart("0.3.0", "0.3.9"));
+        assert!(!versions_too_far_apart("0.3.0", "0.4.1"));
+        assert!(!versions_too_far_apart("0.3.0", "0.2.0"));
+        assert!(versions_too_far_apart("0.3.0", "0.5.0"));
+        assert!(versions_too_far_apart("0.3.0", "0.1.7"));
+        assert!(versions_too_far_apart("1.3.0", "2.3.0"));
+        assert!(!versions_too_far_apart("0.3.0", "unknown"));
+
+        let build = BuildInfo::current();
+        assert_eq!(build.version, CRATE_VERSION);
+        assert!(parse_version(&build.version).is_some());
+        assert!(!build.git_sha.is_empty() && !build.build_timestamp.is_empty());
+        assert_eq!(build.to_string(), format!("{} ({})", CRATE_VERSION, GIT_SHA));
+        assert_eq!(MAX_MINOR_VERSION_SKEW, 1);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_carries_build_info_across_versions() {
+        // Test: Publish a binding, strip its build info as an older node would send it, give another
+        // one a distant version, and hand each to a consensus node
+        // Expected: Published bindings carry this build; build info is outside the signed statement,
+        // so all of them verify and are kept, and the registry reports what each peer runs
+        println!("Expected: Peers learn each other's software version at handshake without breaking older nodes");
+
+        let dir = tempfile::tempdir().unwrap();
+        let local_keypair = NodeKeypair::new();
+        let local_node = Node::new("10.0.0.1".parse().unwrap(), &local_keypair).unwrap();
+        let network = NetworkManager::new(local_node.clone()).await.unwrap();
+        let consensus = ConsensusManager::new(local_node, &local_keypair, network, StorageManager::new(dir.path()).unwrap()).unwrap();
+
+        let mut bindings = Vec::new();
+        for ip in ["10.0.0.2", "10.0.0.3", "10.0.0.4"] {
+            let app_keypair = NodeKeypair::new();
+            let node = Node::new(ip.parse().unwrap(), &app_keypair).unwrap();
+            let mut remote = NetworkManager::new(node.clone()).await.unwrap();
+            consensus.node_registry.write().await.register_node(node).unwrap();
+            let binding = remote.publish_peer_binding(&app_keypair).await.unwrap();
+            assert_eq!(binding.build, Some(BuildInfo::current()));
+            bindings.push(binding);
+        }
+
+        // An older node sends no build field at all
+        let mut json = serde_json::to_value(&bindings[1]).unwrap();
+        json.as_object_mut().unwrap().remove("build");
+        bindings[1] = serde_json::from_value(json).unwrap();
+        assert_eq!(bindings[1].build, None);
+        bindings[2].build.as_mut().unwrap().version = "99.0.0".to_string();
+
+        for binding in &bindings {
+            assert!(binding.verify().is_ok());
+            consensus.handle_peer_message(&binding.peer_id, &NetworkMessage::PeerBinding(binding.clone())).await.unwrap();
+        }
+
+        let registry = consensus.peer_bindings.read().await;
+        assert_eq!(registry.len(), 3);
+        let skewed: Vec<&str> = registry.iter()
+            .filter(|binding| binding.build.as_ref().is_some_and(|build| versions_too_far_apart(CRATE_VERSION, &build.version)))
+            .map(|binding| binding.node_id.as_str())
+            .collect();
+        assert_eq!(skewed, vec![bindings[2].node_id.as_str()]);
+        assert_eq!(registry.for_node(&bindings[0].node_id).unwrap().build.as_ref().unwrap().git_sha, GIT_SHA);
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use pcl_backend::webhook::{sign_payload, verify_payload_signature, retry_delay_ms, MAX_DELIVERY_ATTEMPTS};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const SECRET: &str = "merchant-shared-secret";
+
+    fn dispatcher(dir: &std::path::Path) -> WebhookDispatcher {
+        WebhookDispatcher::new(Arc::new(StorageManager::new(dir).unwrap()))
+    }
+
+    fn merchant_address() -> Address {
+        NodeKeypair::new().address().parse().unwrap()
+    }
+
+    #[test]
+    fn test_webhook_signature_roundtrip() {
+        // Test: Sign a payload, then verify it, a tampered body and a wrong secret
+        // Expected: Only the original body with the right secret verifies
+        println!("Expected: Webhook signatures bind the secret to the exact body");
+
+        let body = r#"{"event":"address.received","amount":5.0}"#;
+        let signature = sign_payload(SECRET, body);
+
+        assert!(verify_payload_signature(SECRET, body, &signature));
+        assert!(!verify_payload_signature(SECRET, &body.replace("5.0", "50.0"), &signature));
+        assert!(!verify_payload_signature("another-secret-value", body, &signature));
+        assert!(!verify_payload_signature(SECRET, body, "not-hex"));
+    }
+
+    #[test]
+    fn test_webhook_retry_backoff() {
+        // Test: Fail a delivery repeatedly
+        // Expected: The retry delay doubles each time and the delivery is abandoned after the attempt limit
+        println!("Expected: Failed deliveries back off exponentially, then give up");
+
+        assert_eq!(retry_delay_ms(1), 1_000);
+        assert_eq!(retry_delay_ms(2), 2_000);
+        assert_eq!(retry_delay_ms(4), 8_000);
+        assert_eq!(retry_delay_ms(30), 10 * 60 * 1000);
+
+        let dir = tempfile::tempdir().unwrap();
+        let webhooks = dispatcher(dir.path());
+        let address = merchant_address();
+        webhooks.subscribe(address.clone(), "http://127.0.0.1:9/hook", SECRET, 0).unwrap();
+        webhooks.notify_received(address.as_str(), "tx_1", 5.0, "sender", None, 0).unwrap();
+
+        let mut delivery = webhooks.due_deliveries(0).unwrap().remove(0);
+        delivery.record_failure("connection refused".to_string(), None, 100);
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, 1_100);
+        assert!(!delivery.is_due(1_099));
+
+        for attempt in 2..=MAX_DELIVERY_ATTEMPTS {
+            delivery.record_failure(format!("attempt {}", attempt), Some(500), 100);
+        }
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert!(!delivery.is_due(u64::MAX));
+    }
+
+    #[test]
+    fn test_webhook_notifications_match_subscribed_address() {
+        // Test: Subscribe one address, then finalize transfers to it and to another address
+        // Expected: Only the subscribed address gets a delivery, and the same tx is only queued once
+        println!("Expected: Deliveries are queued per subscribed recipient, once per transaction");
+
+        let dir = tempfile::tempdir().unwrap();
+        let webhooks = dispatcher(dir.path());
+        let address = merchant_address();
+
+        assert!(webhooks.subscribe(address.clone(), "https://merchant.example/hook", SECRET, 0).is_err());
+        assert!(webhooks.subscribe(address.clone(), "http://merchant.example/hook", "short", 0).is_err());
+        let subscription = webhooks.subscribe(address.clone(), "http://merchant.example/hook", SECRET, 0).unwrap();
+
+        assert_eq!(webhooks.notify_received(address.as_str(), "tx_1", 5.0, "sender", None, 10).unwrap(), 1);
+        assert_eq!(webhooks.notify_received(address.as_str(), "tx_1", 5.0, "sender", None, 20).unwrap(), 0);
+        assert_eq!(webhooks.notify_received(merchant_address().as_str(), "tx_2", 1.0, "sender", None, 10).unwrap(), 0);
+
+        let deliveries = webhooks.deliveries(&subscription.id).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        let payload: serde_json::Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["tx_id"], "tx_1");
+        assert_eq!(payload["address"], address.as_str());
+
+        assert!(webhooks.unsubscribe(&subscription.id).unwrap());
+        assert!(!webhooks.unsubscribe(&subscription.id).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery_is_signed() {
+        // Test: Dispatch a due delivery to a local endpoint
+        // Expected: The endpoint receives a body whose signature header verifies and the delivery is marked delivered
+        println!("Expected: Webhook POST carries a valid HMAC signature");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 4096];
+            let n = stream.read(&mut buffer).await.unwrap();
+            let request = String::from_utf8_lossy(&buffer[..n]).to_string();
+            stream.write_all(b"HTTP/1.1 204 No Content\r\n\r\n").await.unwrap();
+            request
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let webhooks = dispatcher(dir.path());
+        let address = merchant_address();
+        let subscription = webhooks.subscribe(address.clone(), &format!("http://{}/payments", addr), SECRET, 0).unwrap();
+        webhooks.notify_received(address.as_str(), "tx_paid", 12.5, "sender", None, 0).unwrap();
+
+        assert_eq!(webhooks.dispatch_due(1).await.unwrap(), 1);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /payments HTTP/1.1"));
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        let signature = head.lines()
+            .find_map(|line| line.strip_prefix("X-PCL-Signature: "))
+            .unwrap();
+        assert!(verify_payload_signature(SECRET, body, signature));
+
+        let delivery = &webhooks.deliveries(&subscription.id).unwrap()[0];
+        assert_eq!(delivery.status, DeliveryStatus::Delivered);
+        assert_eq!(delivery.response_status, Some(204));
+        assert!(webhooks.due_deliveries(u64::MAX).unwrap().is_empty());
+    }
+}
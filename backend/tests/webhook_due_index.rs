@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use pcl_backend::*;
+    use std::sync::Arc;
+
+    fn delivery(n: u32, status: DeliveryStatus, next_attempt_at: u64) -> WebhookDelivery {
+        WebhookDelivery {
+            id: format!("sub_1:tx_{}", n),
+            subscription_id: "sub_1".to_string(),
+            tx_id: format!("tx_{}", n),
+            payload: "{}".to_string(),
+            status,
+            attempts: 0,
+            next_attempt_at,
+            last_attempt_at: None,
+            last_error: None,
+            response_status: None,
+        }
+    }
+
+    #[test]
+    fn test_due_index_follows_delivery_status() {
+        // Test: Store a mix of delivered, failed and pending deliveries, then move one pending
+        // delivery's retry time forward and mark another delivered
+        // Expected: Only pending deliveries whose retry time has passed are reported due, and the
+        // index reflects every later store of a delivery
+        println!("Expected: The dispatch tick sees only due deliveries without reading the rest");
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(StorageManager::new(dir.path()).unwrap());
+        for n in 0..200 {
+            storage.store_webhook_delivery(&delivery(n, DeliveryStatus::Delivered, 0)).unwrap();
+        }
+        storage.store_webhook_delivery(&delivery(200, DeliveryStatus::Failed, 0)).unwrap();
+        storage.store_webhook_delivery(&delivery(201, DeliveryStatus::Pending, 1_000)).unwrap();
+        storage.store_webhook_delivery(&delivery(202, DeliveryStatus::Pending, 5_000)).unwrap();
+
+        let mut due = storage.due_webhook_delivery_ids(5_000).unwrap();
+        due.sort();
+        assert_eq!(due, vec!["sub_1:tx_201".to_string(), "sub_1:tx_202".to_string()]);
+        assert_eq!(storage.due_webhook_delivery_ids(4_999).unwrap(), vec!["sub_1:tx_201".to_string()]);
+
+        storage.store_webhook_delivery(&delivery(201, DeliveryStatus::Pending, 9_000)).unwrap();
+        storage.store_webhook_delivery(&delivery(202, DeliveryStatus::Delivered, 5_000)).unwrap();
+        assert!(storage.due_webhook_delivery_ids(8_999).unwrap().is_empty());
+
+        let webhooks = WebhookDispatcher::new(storage.clone());
+        let due = webhooks.due_deliveries(9_000).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, "sub_1:tx_201");
+        assert_eq!(storage.load_webhook_deliveries().unwrap().len(), 203);
+    }
+
+    #[test]
+    fn test_migration_indexes_existing_pending_deliveries() {
+        // Test: Write delivery records directly, as a node from before the due index would have,
+        // then open the database and run the migrations
+        // Expected: Nothing is due before the migration; afterwards only the pending delivery is
+        println!("Expected: Deliveries queued before the due index are still dispatched after upgrading");
+
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut opts = rocksdb::Options::default();
+            opts.create_if_missing(true);
+            opts.create_missing_column_families(true);
+            let cfs = ALL_COLUMN_FAMILIES.map(|name| rocksdb::ColumnFamilyDescriptor::new(name, rocksdb::Options::default()));
+            let db = rocksdb::DB::open_cf_descriptors(&opts, dir.path(), cfs).unwrap();
+            let cf = db.cf_handle(CF_WEBHOOKS).unwrap();
+            for record in [delivery(1, DeliveryStatus::Pending, 100), delivery(2, DeliveryStatus::Delivered, 100)] {
+                db.put_cf(cf, format!("delivery:{}", record.id), encode_record(&record).unwrap()).unwrap();
+            }
+        }
+
+        let storage = StorageManager::new(dir.path()).unwrap();
+        assert!(storage.due_webhook_delivery_ids(100).unwrap().is_empty());
+        let report = storage.run_migrations().unwrap();
+        assert_eq!(report.to_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(storage.due_webhook_delivery_ids(100).unwrap(), vec!["sub_1:tx_1".to_string()]);
+        assert_eq!(storage.rebuild_webhook_due_index().unwrap(), 1);
+    }
+}
@@ -0,0 +1,14 @@
+// Regenerates include/pcl_ffi.h from the exported functions on every build
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap_or_default();
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{}/include/pcl_ffi.h", crate_dir));
+        }
+        // A broken header should not stop the library building; the committed one stays in place
+        Err(e) => println!("cargo:warning=Could not generate pcl_ffi.h: {}", e),
+    }
+}
@@ -0,0 +1,107 @@
+#ifndef PCL_FFI_H
+#define PCL_FFI_H
+
+/* Generated by cbindgen from src/lib.rs; rebuild pcl-ffi instead of editing. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define PCL_OK 0
+
+#define PCL_ERR_NULL_POINTER -1
+
+#define PCL_ERR_INVALID_UTF8 -2
+
+#define PCL_ERR_INVALID_INPUT -3
+
+#define PCL_ERR_VERIFICATION_FAILED -4
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * A fresh random secret key, hex encoded
+ *
+ * # Safety
+ * `out_secret_key` must be a valid pointer to write a string pointer to.
+ */
+int32_t pcl_generate_secret_key(char **out_secret_key);
+
+/**
+ * The address a secret key signs for
+ *
+ * # Safety
+ * `secret_key_hex` must be a NUL terminated string and `out_address` a valid pointer.
+ */
+int32_t pcl_address_from_secret_key(const char *secret_key_hex, char **out_address);
+
+/**
+ * The address at index of account for a BIP39 phrase, with its secret key; same path as pcl-wallet
+ *
+ * # Safety
+ * `mnemonic` and `passphrase` must be NUL terminated strings (the passphrase may be empty) and both
+ * out pointers must be valid.
+ */
+int32_t pcl_derive_address(const char *mnemonic,
+                           const char *passphrase,
+                           uint32_t account,
+                           uint32_t index,
+                           char **out_address,
+                           char **out_secret_key);
+
+/**
+ * The canonical unsigned body, as POST /transaction/prepare returns it
+ *
+ * # Safety
+ * `body_json` must be a NUL terminated string and `out_body_json` a valid pointer.
+ */
+int32_t pcl_prepare_transaction(const char *body_json, char **out_body_json);
+
+/**
+ * Adds public_key and sig to a body; the key must belong to the body's user
+ *
+ * # Safety
+ * `body_json` and `secret_key_hex` must be NUL terminated strings and `out_body_json` a valid pointer.
+ */
+int32_t pcl_sign_transaction(const char *body_json,
+                             const char *secret_key_hex,
+                             char **out_body_json);
+
+/**
+ * PCL_OK if the body carries a valid signature from its user
+ *
+ * # Safety
+ * `body_json` must be a NUL terminated string.
+ */
+int32_t pcl_verify_transaction(const char *body_json);
+
+/**
+ * PCL_OK if the leader and every validator signature on a finalization receipt check out; which
+ * signers to trust is up to the caller
+ *
+ * # Safety
+ * `receipt_json` must be a NUL terminated string.
+ */
+int32_t pcl_verify_receipt(const char *receipt_json);
+
+/**
+ * The last failure on this thread, or null; owned by the library and valid until the next call
+ */
+const char *pcl_last_error(void);
+
+/**
+ * Releases a string returned through an out pointer; null is ignored
+ *
+ * # Safety
+ * `value` must come from this library and must not be used or freed again afterwards.
+ */
+void pcl_string_free(char *value);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* PCL_FFI_H */
@@ -0,0 +1,219 @@
+// PCL FFI - C ABI bindings for the mobile wallets
+//
+// Swift links the staticlib and Kotlin loads the cdylib; both go through include/pcl_ffi.h, which
+// build.rs regenerates with cbindgen. Only the core types of pcl-backend are compiled in, so the
+// signatures and addresses match pcl-wallet and the browser wallet exactly. Strings cross the boundary
+// as NUL terminated UTF-8: transaction bodies and receipts as JSON, secret keys as hex. Functions
+// return PCL_OK or a negative status, and pcl_last_error describes the most recent failure on the
+// calling thread. Every string this library returns must be released with pcl_string_free.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use pcl_backend::{
+    derive_address, mnemonic_to_seed, prepare_submission, sign_submission, verify_submission_signature,
+    NodeKeypair, PclError, TransactionReceipt,
+};
+
+pub const PCL_OK: i32 = 0;
+pub const PCL_ERR_NULL_POINTER: i32 = -1;
+pub const PCL_ERR_INVALID_UTF8: i32 = -2;
+pub const PCL_ERR_INVALID_INPUT: i32 = -3; // malformed JSON, hex or key
+pub const PCL_ERR_VERIFICATION_FAILED: i32 = -4;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn fail(status: i32, message: impl ToString) -> i32 {
+    set_last_error(message.to_string());
+    status
+}
+
+// Borrows a C string argument; the status is already recorded when this fails
+unsafe fn read_str<'a>(name: &str, value: *const c_char) -> Result<&'a str, i32> {
+    if value.is_null() {
+        return Err(fail(PCL_ERR_NULL_POINTER, format!("{} is null", name)));
+    }
+    CStr::from_ptr(value).to_str()
+        .map_err(|_| fail(PCL_ERR_INVALID_UTF8, format!("{} is not valid UTF-8", name)))
+}
+
+// Hands a string to the caller through an out pointer
+unsafe fn write_str(out: *mut *mut c_char, value: String) -> i32 {
+    if out.is_null() {
+        return fail(PCL_ERR_NULL_POINTER, "Output pointer is null");
+    }
+    match CString::new(value) {
+        Ok(value) => {
+            *out = value.into_raw();
+            PCL_OK
+        }
+        Err(_) => fail(PCL_ERR_INVALID_INPUT, "Output contains a NUL byte"),
+    }
+}
+
+fn parse_json(name: &str, value: &str) -> Result<serde_json::Value, i32> {
+    serde_json::from_str(value).map_err(|e| fail(PCL_ERR_INVALID_INPUT, format!("{} is not JSON: {}", name, e)))
+}
+
+fn keypair_from_hex(secret_key_hex: &str) -> Result<NodeKeypair, i32> {
+    let secret = hex::decode(secret_key_hex)
+        .map_err(|e| fail(PCL_ERR_INVALID_INPUT, format!("Secret key is not hex: {}", e)))?;
+    NodeKeypair::from_bytes(&secret).map_err(|e| fail(PCL_ERR_INVALID_INPUT, e))
+}
+
+fn invalid(error: PclError) -> i32 {
+    fail(PCL_ERR_INVALID_INPUT, error)
+}
+
+/// A fresh random secret key, hex encoded
+///
+/// # Safety
+/// `out_secret_key` must be a valid pointer to write a string pointer to.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_generate_secret_key(out_secret_key: *mut *mut c_char) -> i32 {
+    write_str(out_secret_key, hex::encode(NodeKeypair::new().signing_key.to_bytes()))
+}
+
+/// The address a secret key signs for
+///
+/// # Safety
+/// `secret_key_hex` must be a NUL terminated string and `out_address` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_address_from_secret_key(secret_key_hex: *const c_char, out_address: *mut *mut c_char) -> i32 {
+    let keypair = match read_str("secret_key_hex", secret_key_hex).and_then(keypair_from_hex) {
+        Ok(keypair) => keypair,
+        Err(status) => return status,
+    };
+    write_str(out_address, keypair.address())
+}
+
+/// The address at index of account for a BIP39 phrase, with its secret key; same path as pcl-wallet
+///
+/// # Safety
+/// `mnemonic` and `passphrase` must be NUL terminated strings (the passphrase may be empty) and both
+/// out pointers must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_derive_address(
+    mnemonic: *const c_char,
+    passphrase: *const c_char,
+    account: u32,
+    index: u32,
+    out_address: *mut *mut c_char,
+    out_secret_key: *mut *mut c_char,
+) -> i32 {
+    let (mnemonic, passphrase) = match (read_str("mnemonic", mnemonic), read_str("passphrase", passphrase)) {
+        (Ok(mnemonic), Ok(passphrase)) => (mnemonic, passphrase),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    let derived = mnemonic_to_seed(mnemonic, passphrase)
+        .and_then(|seed| derive_address(&seed, account, index));
+    let (address, keypair) = match derived {
+        Ok(derived) => derived,
+        Err(e) => return invalid(e),
+    };
+    let status = write_str(out_address, address);
+    if status != PCL_OK {
+        return status;
+    }
+    write_str(out_secret_key, hex::encode(keypair.signing_key.to_bytes()))
+}
+
+/// The canonical unsigned body, as POST /transaction/prepare returns it
+///
+/// # Safety
+/// `body_json` must be a NUL terminated string and `out_body_json` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_prepare_transaction(body_json: *const c_char, out_body_json: *mut *mut c_char) -> i32 {
+    let body = match read_str("body_json", body_json).and_then(|body| parse_json("body_json", body)) {
+        Ok(body) => body,
+        Err(status) => return status,
+    };
+    match prepare_submission(&body) {
+        Ok(prepared) => write_str(out_body_json, prepared.to_string()),
+        Err(e) => invalid(e),
+    }
+}
+
+/// Adds public_key and sig to a body; the key must belong to the body's user
+///
+/// # Safety
+/// `body_json` and `secret_key_hex` must be NUL terminated strings and `out_body_json` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_sign_transaction(
+    body_json: *const c_char,
+    secret_key_hex: *const c_char,
+    out_body_json: *mut *mut c_char,
+) -> i32 {
+    let body = match read_str("body_json", body_json).and_then(|body| parse_json("body_json", body)) {
+        Ok(body) => body,
+        Err(status) => return status,
+    };
+    let keypair = match read_str("secret_key_hex", secret_key_hex).and_then(keypair_from_hex) {
+        Ok(keypair) => keypair,
+        Err(status) => return status,
+    };
+    match sign_submission(&body, &keypair) {
+        Ok(signed) => write_str(out_body_json, signed.to_string()),
+        Err(e) => invalid(e),
+    }
+}
+
+/// PCL_OK if the body carries a valid signature from its user
+///
+/// # Safety
+/// `body_json` must be a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_verify_transaction(body_json: *const c_char) -> i32 {
+    let body = match read_str("body_json", body_json).and_then(|body| parse_json("body_json", body)) {
+        Ok(body) => body,
+        Err(status) => return status,
+    };
+    match verify_submission_signature(&body) {
+        Ok(()) => PCL_OK,
+        Err(e) => fail(PCL_ERR_VERIFICATION_FAILED, e),
+    }
+}
+
+/// PCL_OK if the leader and every validator signature on a finalization receipt check out; which
+/// signers to trust is up to the caller
+///
+/// # Safety
+/// `receipt_json` must be a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_verify_receipt(receipt_json: *const c_char) -> i32 {
+    let receipt: TransactionReceipt = match read_str("receipt_json", receipt_json) {
+        Ok(receipt) => match serde_json::from_str(receipt) {
+            Ok(receipt) => receipt,
+            Err(e) => return fail(PCL_ERR_INVALID_INPUT, format!("receipt_json is not a receipt: {}", e)),
+        },
+        Err(status) => return status,
+    };
+    match receipt.verify() {
+        Ok(()) => PCL_OK,
+        Err(e) => fail(PCL_ERR_VERIFICATION_FAILED, e),
+    }
+}
+
+/// The last failure on this thread, or null; owned by the library and valid until the next call
+#[no_mangle]
+pub extern "C" fn pcl_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Releases a string returned through an out pointer; null is ignored
+///
+/// # Safety
+/// `value` must come from this library and must not be used or freed again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn pcl_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
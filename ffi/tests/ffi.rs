@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr;
+    use pcl_backend::*;
+    use pcl_ffi::*;
+
+    // Copies and frees a string the library handed out
+    fn take(value: *mut c_char) -> String {
+        let copy = unsafe { CStr::from_ptr(value) }.to_str().unwrap().to_string();
+        unsafe { pcl_string_free(value) };
+        copy
+    }
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(pcl_last_error()) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_sign_and_verify_through_c_abi() {
+        // Test: Generate a key, prepare and sign a body through the C functions, then verify it
+        // Expected: The signature matches sign_submission and a tampered body fails verification
+        println!("Expected: C ABI signing matches the wallet");
+
+        let mut secret = ptr::null_mut();
+        assert_eq!(unsafe { pcl_generate_secret_key(&mut secret) }, PCL_OK);
+        let secret = take(secret);
+        let secret_c = CString::new(secret.clone()).unwrap();
+        let mut address = ptr::null_mut();
+        assert_eq!(unsafe { pcl_address_from_secret_key(secret_c.as_ptr(), &mut address) }, PCL_OK);
+        let address = take(address);
+        let keypair = NodeKeypair::from_bytes(&hex::decode(&secret).unwrap()).unwrap();
+        assert_eq!(address, keypair.address());
+
+        let body = serde_json::json!({
+            "to": NodeKeypair::new().address(),
+            "from": "utxo_1",
+            "amount": 2.0,
+            "user": address,
+            "fee": 0.1,
+        });
+        let body_c = CString::new(body.to_string()).unwrap();
+        let mut prepared = ptr::null_mut();
+        assert_eq!(unsafe { pcl_prepare_transaction(body_c.as_ptr(), &mut prepared) }, PCL_OK);
+        let prepared = CString::new(take(prepared)).unwrap();
+        let mut signed = ptr::null_mut();
+        assert_eq!(unsafe { pcl_sign_transaction(prepared.as_ptr(), secret_c.as_ptr(), &mut signed) }, PCL_OK);
+        let signed = take(signed);
+        let expected = sign_submission(&prepare_submission(&body).unwrap(), &keypair).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&signed).unwrap(), expected);
+
+        let signed_c = CString::new(signed.clone()).unwrap();
+        assert_eq!(unsafe { pcl_verify_transaction(signed_c.as_ptr()) }, PCL_OK);
+        let tampered = CString::new(signed.replace("\"amount\":2.0", "\"amount\":20.0")).unwrap();
+        assert_eq!(unsafe { pcl_verify_transaction(tampered.as_ptr()) }, PCL_ERR_VERIFICATION_FAILED);
+        assert!(!last_error().is_empty());
+    }
+
+    #[test]
+    fn test_derivation_receipts_and_bad_input() {
+        // Test: Derive an address from a phrase, verify a receipt, and pass malformed arguments
+        // Expected: Derivation matches derive_address, a forged receipt fails, bad input gets a status
+        println!("Expected: Derivation and receipt checks match the library");
+
+        let phrase = generate_mnemonic();
+        let phrase_c = CString::new(phrase.clone()).unwrap();
+        let passphrase = CString::new("").unwrap();
+        let (mut address, mut secret) = (ptr::null_mut(), ptr::null_mut());
+        assert_eq!(unsafe { pcl_derive_address(phrase_c.as_ptr(), passphrase.as_ptr(), 0, 3, &mut address, &mut secret) }, PCL_OK);
+        let (expected_address, expected_keypair) = derive_address(&mnemonic_to_seed(&phrase, "").unwrap(), 0, 3).unwrap();
+        assert_eq!(take(address), expected_address);
+        assert_eq!(take(secret), hex::encode(expected_keypair.signing_key.to_bytes()));
+
+        let (leader, validator) = (NodeKeypair::new(), NodeKeypair::new());
+        let leader_signature = ReceiptSignature::sign("leader", &leader, &TransactionReceipt::leader_signing_bytes("tx_1", 1000));
+        let mut receipt = TransactionReceipt::new("tx_1", "abcd", 1000, 7, leader_signature, 2000);
+        receipt.add_validator_signature("validator", &validator);
+        let receipt_c = CString::new(serde_json::to_string(&receipt).unwrap()).unwrap();
+        assert_eq!(unsafe { pcl_verify_receipt(receipt_c.as_ptr()) }, PCL_OK);
+        receipt.digital_root = 8;
+        let forged = CString::new(serde_json::to_string(&receipt).unwrap()).unwrap();
+        assert_eq!(unsafe { pcl_verify_receipt(forged.as_ptr()) }, PCL_ERR_VERIFICATION_FAILED);
+
+        let not_json = CString::new("{not json").unwrap();
+        assert_eq!(unsafe { pcl_verify_transaction(not_json.as_ptr()) }, PCL_ERR_INVALID_INPUT);
+        assert!(last_error().contains("not JSON"));
+        assert_eq!(unsafe { pcl_verify_receipt(ptr::null()) }, PCL_ERR_NULL_POINTER);
+        let bad_key = CString::new("zz").unwrap();
+        let mut out = ptr::null_mut();
+        assert_eq!(unsafe { pcl_address_from_secret_key(bad_key.as_ptr(), &mut out) }, PCL_ERR_INVALID_INPUT);
+        assert!(out.is_null());
+        unsafe { pcl_string_free(ptr::null_mut()) };
+    }
+}
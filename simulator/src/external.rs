@@ -0,0 +1,239 @@
+use pcl_backend::{PclClient, sign_submission};
+use pcl_backend::webhook::{verify_payload_signature, EVENT_ADDRESS_RECEIVED, SIGNATURE_HEADER};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::time::sleep;
+use crate::wallet_agent::{SimUser, UserManager};
+
+// Largest webhook body the listener will read
+const MAX_EVENT_SIZE: usize = 64 * 1024;
+
+// The node exposes its API over HTTP only, so a multiaddr names the API host and port:
+// /ip4/127.0.0.1/tcp/8080, /ip6/::1/tcp/8080 or /dns4/node.example/tcp/8080, optionally ending in /http
+pub fn api_url_from_multiaddr(multiaddr: &str) -> Result<String, String> {
+    let parts: Vec<&str> = multiaddr.trim().trim_start_matches('/').split('/').collect();
+    let (host, port) = match parts.as_slice() {
+        ["ip4" | "dns" | "dns4" | "dns6", host, "tcp", port] | ["ip4" | "dns" | "dns4" | "dns6", host, "tcp", port, "http"] => {
+            (host.to_string(), *port)
+        }
+        ["ip6", host, "tcp", port] | ["ip6", host, "tcp", port, "http"] => (format!("[{}]", host), *port),
+        _ => return Err(format!("Unsupported multiaddr {}, expected /ip4|ip6|dns4/<host>/tcp/<port>", multiaddr)),
+    };
+    let port: u16 = port.parse().map_err(|_| format!("Invalid port in multiaddr {}", multiaddr))?;
+    Ok(format!("http://{}:{}", host, port))
+}
+
+// Finalizations reported by the node's address.received webhooks, keyed by tx id
+#[derive(Clone)]
+pub struct FinalizationEvents {
+    secret: String,
+    received: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl FinalizationEvents {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.to_string(),
+            received: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // Accepts webhook deliveries until the process exits; returns the bound address
+    pub async fn listen(&self, addr: &str) -> std::io::Result<SocketAddr> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let events = self.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let events = events.clone();
+                tokio::spawn(async move {
+                    let mut buffer = vec![0u8; MAX_EVENT_SIZE];
+                    let len = stream.read(&mut buffer).await.unwrap_or(0);
+                    let status = events.handle_delivery(&String::from_utf8_lossy(&buffer[..len]));
+                    let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        Ok(local_addr)
+    }
+
+    fn handle_delivery(&self, request: &str) -> &'static str {
+        let (head, body) = request.split_once("\r\n\r\n").unwrap_or((request, ""));
+        let signature = head.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(SIGNATURE_HEADER))
+            .map(|(_, value)| value.trim())
+            .unwrap_or("");
+        if !verify_payload_signature(&self.secret, body, signature) {
+            warn!("❌ Dropped webhook delivery with a bad signature");
+            return "401 Unauthorized";
+        }
+
+        let event: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        if event["event"] == EVENT_ADDRESS_RECEIVED {
+            if let Some(tx_id) = event["tx_id"].as_str() {
+                if let Ok(mut received) = self.received.lock() {
+                    received.entry(tx_id.to_string()).or_insert_with(Instant::now);
+                }
+            }
+        }
+        "200 OK"
+    }
+
+    pub fn finalized_at(&self, tx_id: &str) -> Option<Instant> {
+        self.received.lock().ok()?.get(tx_id).copied()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExternalRunReport {
+    pub submitted: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub finalized: u64,
+    pub latencies_ms: Vec<u64>, // submission to finalization event, sorted
+}
+
+impl ExternalRunReport {
+    pub fn success_rate(&self) -> f64 {
+        if self.submitted == 0 {
+            0.0
+        } else {
+            self.finalized as f64 / self.submitted as f64 * 100.0
+        }
+    }
+
+    pub fn percentile_ms(&self, percentile: f64) -> Option<u64> {
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+        let index = ((self.latencies_ms.len() - 1) as f64 * percentile / 100.0).round() as usize;
+        self.latencies_ms.get(index).copied()
+    }
+}
+
+// Load against a running node: funded users send each other signed transfers over the HTTP API,
+// and finalization is timed from the node's webhook events rather than assumed
+pub struct ExternalRunner {
+    client: PclClient,
+    users: UserManager,
+    events: FinalizationEvents,
+    events_url: String,
+}
+
+impl ExternalRunner {
+    pub fn new(client: PclClient, users: UserManager, events: FinalizationEvents, events_url: &str) -> Self {
+        Self {
+            client,
+            users,
+            events,
+            events_url: events_url.to_string(),
+        }
+    }
+
+    pub fn users(&self) -> &UserManager {
+        &self.users
+    }
+
+    pub fn client(&self) -> &PclClient {
+        &self.client
+    }
+
+    // Users carried over from an earlier run are only topped up to funding
+    pub async fn prepare(&mut self, funding: f64) -> Result<(), String> {
+        let health = self.client.health().await.map_err(|e| format!("Node is not reachable: {}", e))?;
+        info!("🌐 Connected to node: {}", health["status"]);
+
+        for user in self.users.users() {
+            let subscription = serde_json::json!({
+                "address": user.address,
+                "url": self.events_url,
+                "secret": self.events.secret,
+            });
+            self.client.post("/subscriptions", &subscription).await
+                .map_err(|e| format!("Could not subscribe to events for {}: {}", user.address, e))?;
+        }
+        self.top_up(funding).await;
+        Ok(())
+    }
+
+    // Faucets each user back up to funding; long runs call this between batches as fees drain them
+    pub async fn top_up(&mut self, funding: f64) {
+        for (index, missing) in self.users.shortfalls(funding) {
+            let user = &self.users.users()[index];
+            let faucet = serde_json::json!({ "address": user.address, "amount": missing });
+            match self.client.post("/faucet", &faucet).await {
+                Ok(response) => {
+                    let utxo_id = format!("{}:0", response["transaction_id"].as_str().unwrap_or("faucet"));
+                    self.users.credit(index, utxo_id, missing);
+                }
+                Err(e) => warn!("❌ Faucet request for {} failed: {}", user.name, e),
+            }
+        }
+    }
+
+    pub async fn run(&mut self, tps: u32, duration: Duration, finalization_timeout: Duration) -> ExternalRunReport {
+        let mut report = ExternalRunReport::default();
+        let mut pending: HashMap<String, Instant> = HashMap::new();
+        let count = self.users.users().len();
+        let interval = Duration::from_secs_f64(1.0 / tps.max(1) as f64);
+        let started = Instant::now();
+
+        while started.elapsed() < duration && count > 0 {
+            let index = report.submitted as usize;
+            let (sender, recipient) = (index % count, (index + 1) % count);
+            let body = signed_transfer(&self.users.users()[sender], &self.users.users()[recipient], 0.5);
+            report.submitted += 1;
+
+            let submitted_at = Instant::now();
+            match self.client.submit_transaction(&body, None).await {
+                Ok(response) => match response["transaction_id"].as_str() {
+                    Some(tx_id) => {
+                        report.accepted += 1;
+                        pending.insert(tx_id.to_string(), submitted_at);
+                        self.users.record_transfer(sender, recipient, 0.5, 0.3, tx_id);
+                    }
+                    None => report.rejected += 1,
+                },
+                Err(e) => {
+                    report.rejected += 1;
+                    warn!("❌ Transfer from {} rejected: {}", self.users.users()[sender].name, e);
+                }
+            }
+            sleep(interval.saturating_sub(submitted_at.elapsed())).await;
+        }
+
+        // Webhooks are dispatched on a timer, so give stragglers a chance to arrive
+        let deadline = Instant::now() + finalization_timeout;
+        while Instant::now() < deadline && pending.keys().any(|tx_id| self.events.finalized_at(tx_id).is_none()) {
+            sleep(Duration::from_millis(200)).await;
+        }
+
+        for (tx_id, submitted_at) in &pending {
+            if let Some(finalized_at) = self.events.finalized_at(tx_id) {
+                report.finalized += 1;
+                report.latencies_ms.push(finalized_at.saturating_duration_since(*submitted_at).as_millis() as u64);
+            }
+        }
+        report.latencies_ms.sort_unstable();
+        report
+    }
+}
+
+// Signed by the sender, as the node refuses unsigned submissions
+fn signed_transfer(sender: &SimUser, recipient: &SimUser, amount: f64) -> serde_json::Value {
+    let body = serde_json::json!({
+        "to": recipient.address,
+        "amount": amount,
+        "user": sender.address,
+        "stake": 0.2,
+        "fee": 0.1,
+    });
+    sign_submission(&body, &sender.keypair).unwrap_or(body)
+}
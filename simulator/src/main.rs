@@ -11,15 +11,25 @@ mod node_spawner;
 mod transaction_generator;
 mod metrics;
 mod network;
+mod wallet_agent;
+mod external;
+mod scenario;
+mod process_nodes;
+mod report;
+mod resources;
+mod soak;
 
 use simulation::Simulation;
 
+// Users a scenario run generates transfers between when given a --users-file
+const SCENARIO_USERS: usize = 10;
+
 #[derive(Parser)]
 #[command(name = "pcl-simulator")]
 #[command(about = "Peer Consensus Layer Transaction Load Simulator")]
 struct Cli {
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
@@ -45,6 +55,42 @@ enum Commands {
         /// Enable verbose logging
         #[arg(short, long)]
         verbose: bool,
+        
+        /// Submit to a running node's HTTP API instead of simulating in memory
+        #[arg(long, conflicts_with = "target_multiaddr")]
+        target_url: Option<String>,
+        
+        /// Running node's API as a multiaddr, e.g. /ip4/127.0.0.1/tcp/8080
+        #[arg(long)]
+        target_multiaddr: Option<String>,
+        
+        /// API key for nodes with auth enabled
+        #[arg(long)]
+        api_key: Option<String>,
+        
+        /// Number of funded users sending transfers to a running node
+        #[arg(long, default_value_t = 10)]
+        users: usize,
+        
+        /// JSON file users are loaded from and saved back to, so they persist across runs
+        #[arg(long)]
+        users_file: Option<std::path::PathBuf>,
+        
+        /// Local address for the node's finalization webhooks
+        #[arg(long, default_value = "127.0.0.1:0")]
+        events_listen: String,
+        
+        /// Webhook URL the node should call, when it cannot reach the listen address directly
+        #[arg(long)]
+        events_url: Option<String>,
+        
+        /// Seconds to wait for finalization events after the last submission
+        #[arg(long, default_value_t = 10)]
+        finalization_timeout: u64,
+        
+        /// Write the run's key metrics as JSON, for `compare`
+        #[arg(long)]
+        report: Option<std::path::PathBuf>,
     },
     /// Stress test the system with high load
     StressTest {
@@ -70,6 +116,126 @@ enum Commands {
         #[arg(short, long, default_value_t = 5)]
         iterations: u32,
     },
+    /// Run a scripted scenario file (TOML or YAML) and fail if its expectations are not met
+    Scenario {
+        /// Path to the scenario file
+        #[arg(short, long)]
+        file: std::path::PathBuf,
+        
+        /// Generate transfers between users loaded from and saved back to this JSON file
+        #[arg(long)]
+        users_file: Option<std::path::PathBuf>,
+        
+        /// Write the run's key metrics as JSON, for `compare`
+        #[arg(long)]
+        report: Option<std::path::PathBuf>,
+    },
+    /// Compare a run report against a baseline and fail on regressions beyond the thresholds
+    Compare {
+        /// Report of the reference run
+        #[arg(long)]
+        baseline: std::path::PathBuf,
+        
+        /// Report of the run being checked
+        #[arg(long)]
+        current: std::path::PathBuf,
+        
+        /// Largest allowed drop in TPS, in percent
+        #[arg(long, default_value_t = 10.0)]
+        max_tps_drop: f64,
+        
+        /// Largest allowed increase in p99 latency, in percent
+        #[arg(long, default_value_t = 20.0)]
+        max_p99_increase: f64,
+        
+        /// Largest allowed increase in failure rate, in percentage points
+        #[arg(long, default_value_t = 1.0)]
+        max_failure_rate_increase: f64,
+        
+        /// Largest allowed increase in spawned node memory, in percent
+        #[arg(long, default_value_t = 20.0)]
+        max_memory_increase: f64,
+    },
+    /// Run moderate load against a running node for hours and fail if its memory, mempools or database keep growing
+    Soak {
+        /// Running node's HTTP API
+        #[arg(long, default_value = pcl_backend::client::DEFAULT_NODE_URL)]
+        target_url: String,
+        
+        /// API key for nodes with auth enabled
+        #[arg(long)]
+        api_key: Option<String>,
+        
+        /// Process id of the node, for sampling its resident memory
+        #[arg(long)]
+        target_pid: Option<u32>,
+        
+        /// The node's data directory, for sampling the database size
+        #[arg(long)]
+        data_dir: Option<std::path::PathBuf>,
+        
+        /// Transactions per second to submit
+        #[arg(short, long, default_value_t = 10)]
+        tps: u32,
+        
+        /// Duration of the run in seconds
+        #[arg(short, long, default_value_t = 4 * 3600)]
+        duration: u64,
+        
+        /// Seconds between samples
+        #[arg(long, default_value_t = 60)]
+        sample_interval: u64,
+        
+        /// Seconds at the start whose samples are left out of the plateau check
+        #[arg(long, default_value_t = 600)]
+        warmup: u64,
+        
+        /// Largest allowed growth of a metric's late peak over its early peak, in percent
+        #[arg(long, default_value_t = 10.0)]
+        max_growth: f64,
+        
+        /// Number of funded users sending transfers
+        #[arg(long, default_value_t = 10)]
+        users: usize,
+        
+        /// JSON file users are loaded from and saved back to, so they persist across runs
+        #[arg(long)]
+        users_file: Option<std::path::PathBuf>,
+        
+        /// Local address for the node's finalization webhooks
+        #[arg(long, default_value = "127.0.0.1:0")]
+        events_listen: String,
+        
+        /// Webhook URL the node should call, when it cannot reach the listen address directly
+        #[arg(long)]
+        events_url: Option<String>,
+        
+        /// Write the samples and per-metric trends as JSON
+        #[arg(long)]
+        report: Option<std::path::PathBuf>,
+    },
+    /// Run simulated user wallets that complete their validation tasks on a running node
+    WalletAgents {
+        /// Node started with --external-validators
+        #[arg(long, default_value = pcl_backend::client::DEFAULT_NODE_URL)]
+        target_url: String,
+        
+        /// Number of simulated users
+        #[arg(short, long, default_value_t = 5)]
+        users: usize,
+        
+        /// JSON file users are loaded from and saved back to, so they persist across runs
+        #[arg(long)]
+        users_file: Option<std::path::PathBuf>,
+        
+        /// How often each wallet polls for tasks
+        #[arg(long, default_value_t = 1000)]
+        poll_interval_ms: u64,
+        
+        /// Duration of the run in seconds
+        #[arg(short, long, default_value_t = 30)]
+        duration: u64,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -227,9 +393,9 @@ impl RealSimulator {
             
             // REAL IMPLEMENTATION: Create and sign transaction
             let tx_data = TransactionData::new(
-                vec![("recipient_address".to_string(), 10.0)],
+                vec![(Address::from_public_key(&generate_keypair().public_key()), 10.0)],
                 vec![("sender_utxo".to_string(), 15.0)],
-                "sender_address".to_string(),
+                Address::from_public_key(&leader_keypair.public_key()),
                 1.0,
                 0.1,
             );
@@ -443,6 +609,47 @@ impl RealSimulator {
 async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
     
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Commands::Scenario { file, users_file, report }) => {
+            return run_scenario_file(&file, users_file.as_deref(), report.as_deref()).await;
+        }
+        Some(Commands::Compare { baseline, current, max_tps_drop, max_p99_increase, max_failure_rate_increase, max_memory_increase }) => {
+            let thresholds = report::Thresholds {
+                max_tps_drop_pct: max_tps_drop,
+                max_p99_increase_pct: max_p99_increase,
+                max_failure_rate_increase,
+                max_memory_increase_pct: max_memory_increase,
+            };
+            return run_compare(&baseline, &current, &thresholds);
+        }
+        Some(Commands::WalletAgents { target_url, users, users_file, poll_interval_ms, duration }) => {
+            return run_wallet_agents(&target_url, users, users_file.as_deref(), poll_interval_ms, duration).await;
+        }
+        Some(Commands::Soak {
+            target_url, api_key, target_pid, data_dir, tps, duration, sample_interval, warmup, max_growth,
+            users, users_file, events_listen, events_url, report,
+        }) => {
+            let options = SoakOptions {
+                api_key, target_pid, data_dir, tps, duration, sample_interval, warmup, max_growth,
+                users, users_file, events_listen, events_url, report,
+            };
+            return run_soak(&target_url, options).await;
+        }
+        Some(Commands::LoadTest {
+            tps, duration, target_url, target_multiaddr, api_key, users, users_file, events_listen, events_url, finalization_timeout, report, ..
+        }) if target_url.is_some() || target_multiaddr.is_some() => {
+            let target_url = match (target_url, target_multiaddr) {
+                (Some(url), _) => url,
+                (None, Some(multiaddr)) => external::api_url_from_multiaddr(&multiaddr)?,
+                (None, None) => unreachable!(),
+            };
+            let options = ExternalLoadOptions { tps, duration, api_key, users, users_file, events_listen, events_url, finalization_timeout, report };
+            return run_external_load(&target_url, options).await;
+        }
+        _ => {}
+    }
+    
     log::info!("🚀 STARTING REAL CRYPTOGRAPHIC SIMULATOR");
     log::info!("=========================================");
     
@@ -461,4 +668,245 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     log::info!("All operations performed with real cryptographic signatures and verifications");
     
     Ok(())
-} 
\ No newline at end of file
+} 
+
+async fn run_wallet_agents(target_url: &str, users: usize, users_file: Option<&std::path::Path>, poll_interval_ms: u64, duration: u64) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    log::info!("👛 STARTING {} WALLET AGENTS AGAINST {}", users, target_url);
+    
+    let client = PclClient::new(target_url)?;
+    let mut agent = wallet_agent::WalletAgent::new(
+        client,
+        wallet_agent::UserManager::load_or_create(users_file, users)?,
+        Duration::from_millis(poll_interval_ms),
+    );
+    
+    agent.fund_users(100.0).await;
+    agent.submit_transfers(1.0).await;
+    agent.run(Duration::from_secs(duration)).await;
+    if let Some(path) = users_file {
+        agent.users().save(path)?;
+    }
+    
+    let stats = agent.stats();
+    log::info!("📊 WALLET AGENT RESULTS:");
+    log::info!("   Transactions submitted: {}", stats.transactions_submitted);
+    log::info!("   Tasks seen: {}", stats.tasks_seen);
+    log::info!("   Tasks completed: {}", stats.tasks_completed);
+    log::info!("   Tasks disputed: {}", stats.tasks_disputed);
+    log::info!("   Completions rejected: {}", stats.completions_rejected);
+    log::info!("   Transactions finalized by a completion: {}", stats.transactions_finalized);
+    
+    Ok(())
+}
+
+struct ExternalLoadOptions {
+    tps: u32,
+    duration: u64,
+    api_key: Option<String>,
+    users: usize,
+    users_file: Option<std::path::PathBuf>,
+    events_listen: String,
+    events_url: Option<String>,
+    finalization_timeout: u64,
+    report: Option<std::path::PathBuf>,
+}
+
+async fn run_external_load(target_url: &str, options: ExternalLoadOptions) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    log::info!("🌐 STARTING LOAD TEST AGAINST {}", target_url);
+    
+    let mut client = PclClient::new(target_url)?;
+    if let Some(api_key) = &options.api_key {
+        client = client.with_api_key(api_key);
+    }
+    
+    let events = external::FinalizationEvents::new(&uuid::Uuid::new_v4().simple().to_string());
+    let listen_addr = events.listen(&options.events_listen).await?;
+    let events_url = options.events_url.unwrap_or_else(|| format!("http://{}/events", listen_addr));
+    log::info!("🔔 Listening for finalization events on {} ({})", listen_addr, events_url);
+    
+    let users = wallet_agent::UserManager::load_or_create(options.users_file.as_deref(), options.users)?;
+    let mut runner = external::ExternalRunner::new(client, users, events, &events_url);
+    runner.prepare(100.0).await?;
+    let report = runner.run(
+        options.tps,
+        Duration::from_secs(options.duration),
+        Duration::from_secs(options.finalization_timeout),
+    ).await;
+    if let Some(path) = &options.users_file {
+        runner.users().save(path)?;
+    }
+    
+    let latency = |percentile: f64| report.percentile_ms(percentile)
+        .map_or("n/a".to_string(), |ms| format!("{}ms", ms));
+    log::info!("📊 END-TO-END RESULTS:");
+    log::info!("   Submitted: {}", report.submitted);
+    log::info!("   Accepted: {}", report.accepted);
+    log::info!("   Rejected: {}", report.rejected);
+    log::info!("   Finalized: {} ({:.1}% success)", report.finalized, report.success_rate());
+    log::info!("   Latency p50: {}, p95: {}, p99: {}, max: {}", latency(50.0), latency(95.0), latency(99.0), latency(100.0));
+    if let Some(path) = &options.report {
+        report::RunReport::from_external(target_url, &report, options.duration).save(path)?;
+        log::info!("📝 Report written to {}", path.display());
+    }
+    
+    Ok(())
+}
+
+struct SoakOptions {
+    api_key: Option<String>,
+    target_pid: Option<u32>,
+    data_dir: Option<std::path::PathBuf>,
+    tps: u32,
+    duration: u64,
+    sample_interval: u64,
+    warmup: u64,
+    max_growth: f64,
+    users: usize,
+    users_file: Option<std::path::PathBuf>,
+    events_listen: String,
+    events_url: Option<String>,
+    report: Option<std::path::PathBuf>,
+}
+
+async fn run_soak(target_url: &str, options: SoakOptions) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    log::info!("🧪 STARTING {}s SOAK AGAINST {} AT {} TPS", options.duration, target_url, options.tps);
+    if options.target_pid.is_none() {
+        log::warn!("⚠️  No --target-pid, so the node's memory is not sampled");
+    }
+    if options.data_dir.is_none() {
+        log::warn!("⚠️  No --data-dir, so the node's database size is not sampled");
+    }
+    
+    let mut client = PclClient::new(target_url)?;
+    if let Some(api_key) = &options.api_key {
+        client = client.with_api_key(api_key);
+    }
+    
+    let events = external::FinalizationEvents::new(&uuid::Uuid::new_v4().simple().to_string());
+    let listen_addr = events.listen(&options.events_listen).await?;
+    let events_url = options.events_url.unwrap_or_else(|| format!("http://{}/events", listen_addr));
+    
+    let users = wallet_agent::UserManager::load_or_create(options.users_file.as_deref(), options.users)?;
+    let mut runner = external::ExternalRunner::new(client, users, events, &events_url);
+    runner.prepare(100.0).await?;
+    
+    // Load runs in batches of one sample interval; finalization is not waited for, only resource use
+    let mut report = soak::SoakReport { target: target_url.to_string(), duration_secs: options.duration, ..Default::default() };
+    let interval = Duration::from_secs(options.sample_interval.max(1));
+    let started = Instant::now();
+    loop {
+        let mempools = runner.client().mempools().await.unwrap_or_else(|e| {
+            log::warn!("❌ Could not read mempools: {}", e);
+            serde_json::Value::Null
+        });
+        let sample = soak::SoakSample::take(started.elapsed().as_secs_f64(), options.target_pid, options.data_dir.as_deref(), &mempools);
+        let rss = sample.rss_kb.map_or("n/a".to_string(), |kb| format!("{} KB", kb));
+        let db = sample.db_kb.map_or("n/a".to_string(), |kb| format!("{} KB", kb));
+        log::info!("   {:>6.0}s: RSS {}, DB {}, mempools {:?}", sample.at_secs, rss, db, sample.mempools);
+        report.samples.push(sample);
+        
+        let remaining = Duration::from_secs(options.duration).saturating_sub(started.elapsed());
+        if remaining.is_zero() {
+            break;
+        }
+        runner.top_up(100.0).await;
+        let batch = runner.run(options.tps, interval.min(remaining), Duration::ZERO).await;
+        report.submitted += batch.submitted;
+        report.accepted += batch.accepted;
+        report.rejected += batch.rejected;
+    }
+    if let Some(path) = &options.users_file {
+        runner.users().save(path)?;
+    }
+    
+    report.trends = soak::trends(&report.samples, options.warmup as f64, options.max_growth)?;
+    log::info!("📊 SOAK RESULTS:");
+    log::info!("   Submitted: {} ({} accepted, {} rejected)", report.submitted, report.accepted, report.rejected);
+    for trend in &report.trends {
+        let marker = if trend.unbounded { "❌" } else { "✅" };
+        let growth = trend.growth_pct.map_or("n/a".to_string(), |growth| format!("{:+.1}%", growth));
+        log::info!("   {} {}: early peak {:.0}, late peak {:.0} ({}), {:+.1}/hour",
+                   marker, trend.metric, trend.early_peak, trend.late_peak, growth, trend.per_hour);
+    }
+    if let Some(path) = &options.report {
+        report.save(path)?;
+        log::info!("📝 Soak report written to {}", path.display());
+    }
+    
+    let unbounded = report.unbounded();
+    if unbounded.is_empty() {
+        log::info!("✅ SOAK PASSED: every sampled metric plateaued");
+        Ok(())
+    } else {
+        let metrics: Vec<&str> = unbounded.iter().map(|trend| trend.metric.as_str()).collect();
+        Err(format!("{} metric(s) grew without plateauing: {}", metrics.len(), metrics.join(", ")).into())
+    }
+}
+
+async fn run_scenario_file(path: &std::path::Path, users_file: Option<&std::path::Path>, report: Option<&std::path::Path>) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let scenario = scenario::Scenario::from_file(path).map_err(|e| e.to_string())?;
+    log::info!("🎬 SCENARIO {}: {}", scenario.name, scenario.description.as_deref().unwrap_or(""));
+    
+    let mut simulation = Simulation::new(scenario.nodes, scenario.leaders, false).await.map_err(|e| e.to_string())?;
+    if let Some(users_file) = users_file {
+        let users = wallet_agent::UserManager::load_or_create(Some(users_file), SCENARIO_USERS)?;
+        simulation.transaction_generator.set_users(users);
+    }
+    let outcome = simulation.run_scenario(&scenario).await.map_err(|e| e.to_string())?;
+    if let (Some(users_file), Some(users)) = (users_file, simulation.transaction_generator.users()) {
+        users.read().await.save(users_file)?;
+    }
+    
+    log::info!("📊 SCENARIO RESULTS:");
+    log::info!("   Transactions: {} ({:.2}% success)", outcome.transactions, outcome.success_rate());
+    log::info!("   Failed validations: {}", outcome.failed_validations);
+    log::info!("   Leader elections: {}", outcome.leader_elections);
+    log::info!("   Active nodes: {}, leaders: {}", outcome.active_nodes, outcome.leaders);
+    if outcome.node_restarts > 0 {
+        log::info!("   Process node restarts: {} ({} recovery failures)", outcome.node_restarts, outcome.recovery_failures.len());
+    }
+    for profile in &outcome.resource_profiles {
+        let cpu = profile.average_cpu_percent.map_or("n/a".to_string(), |cpu| format!("{:.1}%", cpu));
+        let rss = profile.peak_rss_kb.map_or("n/a".to_string(), |kb| format!("{} KB", kb));
+        let fds = profile.peak_open_fds.map_or("n/a".to_string(), |fds| fds.to_string());
+        log::info!("   {}: avg CPU {}, peak RSS {}, peak fds {}, disk {} KB", profile.node, cpu, rss, fds, profile.final_disk_bytes / 1024);
+    }
+    if let Some(report) = report {
+        report::RunReport::from_scenario(&scenario, &outcome).save(report)?;
+        log::info!("📝 Report written to {}", report.display());
+    }
+    
+    let failures = scenario.check(&outcome);
+    if failures.is_empty() {
+        log::info!("✅ SCENARIO {} PASSED", scenario.name);
+        Ok(())
+    } else {
+        for failure in &failures {
+            log::error!("❌ {}", failure);
+        }
+        Err(format!("Scenario {} failed {} expectation(s)", scenario.name, failures.len()).into())
+    }
+}
+
+fn run_compare(baseline: &std::path::Path, current: &std::path::Path, thresholds: &report::Thresholds) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let baseline = report::RunReport::load(baseline)?;
+    let current = report::RunReport::load(current)?;
+    log::info!("📊 COMPARING {} AGAINST BASELINE {}", current.name, baseline.name);
+    
+    let value = |value: Option<f64>| value.map_or("n/a".to_string(), |value| format!("{:.2}", value));
+    let comparisons = report::compare(&baseline, &current, thresholds);
+    for comparison in &comparisons {
+        let marker = if comparison.regression { "❌" } else { "✅" };
+        let unit = if comparison.metric == "failure_rate" { " pts" } else { "%" };
+        let change = comparison.change.map_or("n/a".to_string(), |change| format!("{:+.2}{}", change, unit));
+        log::info!("   {} {}: {} -> {} ({})", marker, comparison.metric, value(comparison.baseline), value(comparison.current), change);
+    }
+    
+    let regressions = comparisons.iter().filter(|comparison| comparison.regression).count();
+    if regressions == 0 {
+        log::info!("✅ NO REGRESSIONS");
+        Ok(())
+    } else {
+        Err(format!("{} metric(s) regressed beyond their thresholds", regressions).into())
+    }
+}
@@ -0,0 +1,281 @@
+use pcl_backend::{MempoolStage, PclClient};
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+use tokio::time::sleep;
+use crate::resources::{ResourceMonitor, ResourceSample};
+use crate::scenario::ProcessNodeSpec;
+
+type ProcessResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+// Overrides where the pcl-node binary is looked up
+pub const NODE_BINARY_ENV: &str = "PCL_NODE_BIN";
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(60);
+// Nodes save their state every second; anything older than this must survive a kill
+const STATE_SAVE_GRACE: Duration = Duration::from_secs(2);
+const CATCH_UP_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// pcl-node from PCL_NODE_BIN, or next to the simulator binary as in a shared target dir
+pub fn node_binary() -> PathBuf {
+    if let Ok(path) = std::env::var(NODE_BINARY_ENV) {
+        return PathBuf::from(path);
+    }
+    std::env::current_exe().ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join("pcl-node")))
+        .unwrap_or_else(|| PathBuf::from("pcl-node"))
+}
+
+// What a node looked like before it was killed, as seen through its API
+#[derive(Debug, Clone)]
+struct NodeState {
+    node_id: String,
+    public_key: String,
+    leaders: Vec<String>,
+    stages: HashMap<String, MempoolStage>, // tx id -> furthest stage it had reached
+}
+
+struct ProcessNode {
+    spec: ProcessNodeSpec,
+    data_dir: PathBuf,
+    child: Option<Child>,
+    before_kill: Option<NodeState>,
+}
+
+// A small network of real pcl-node processes, each with its own port and RocksDB directory
+pub struct ProcessCluster {
+    binary: PathBuf,
+    base_dir: PathBuf,
+    nodes: Vec<ProcessNode>,
+}
+
+impl ProcessCluster {
+    // Starts every node in declaration order, so upstreams are serving before their followers
+    pub async fn start(specs: &[ProcessNodeSpec], binary: PathBuf) -> ProcessResult<Self> {
+        let base_dir = std::env::temp_dir().join(format!("pcl-sim-{}", uuid::Uuid::new_v4()));
+        let nodes = specs.iter()
+            .map(|spec| ProcessNode {
+                spec: spec.clone(),
+                data_dir: base_dir.join(&spec.id),
+                child: None,
+                before_kill: None,
+            })
+            .collect();
+        let mut cluster = Self { binary, base_dir, nodes };
+        info!("🖥️  Starting {} pcl-node processes from {}", specs.len(), cluster.binary.display());
+        for spec in specs {
+            cluster.spawn(&spec.id).await?;
+        }
+        Ok(cluster)
+    }
+
+    fn index_of(&self, id: &str) -> ProcessResult<usize> {
+        self.nodes.iter().position(|node| node.spec.id == id)
+            .ok_or_else(|| format!("Unknown process node {}", id).into())
+    }
+
+    fn client(&self, id: &str) -> ProcessResult<PclClient> {
+        let node = &self.nodes[self.index_of(id)?];
+        Ok(PclClient::new(&format!("127.0.0.1:{}", node.spec.port))?)
+    }
+
+    fn url(&self, id: &str) -> ProcessResult<String> {
+        Ok(format!("http://127.0.0.1:{}", self.nodes[self.index_of(id)?].spec.port))
+    }
+
+    async fn spawn(&mut self, id: &str) -> ProcessResult<()> {
+        let index = self.index_of(id)?;
+        let upstream_url = match self.nodes[index].spec.upstream() {
+            Some(upstream) => Some(self.url(upstream)?),
+            None => None,
+        };
+        let node = &self.nodes[index];
+        if node.child.is_some() {
+            return Err(format!("Process node {} is already running", id).into());
+        }
+        std::fs::create_dir_all(&node.data_dir)?;
+        let log = std::fs::OpenOptions::new().create(true).append(true).open(node.data_dir.join("node.log"))?;
+
+        let mut command = Command::new(&self.binary);
+        command.arg("--port").arg(node.spec.port.to_string())
+            .arg("--data-dir").arg(node.data_dir.join("db"));
+        if let Some(url) = &upstream_url {
+            let flag = if node.spec.replica_of.is_some() { "--replica-of" } else { "--catch-up-from" };
+            command.arg(flag).arg(url);
+        }
+        // Run from the node's own directory so the node does not find and launch a simulator of its own
+        let child = command.current_dir(&node.data_dir)
+            .stdout(Stdio::from(log.try_clone()?))
+            .stderr(Stdio::from(log))
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Could not start {}: {}", self.binary.display(), e))?;
+        self.nodes[index].child = Some(child);
+
+        self.wait_healthy(id).await?;
+        info!("🟢 Process node {} up on port {}", id, self.nodes[index].spec.port);
+        Ok(())
+    }
+
+    async fn wait_healthy(&self, id: &str) -> ProcessResult<()> {
+        let client = self.client(id)?;
+        let started = Instant::now();
+        while started.elapsed() < HEALTH_TIMEOUT {
+            if client.health().await.is_ok() {
+                return Ok(());
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+        Err(format!("Process node {} did not become healthy within {:?}", id, HEALTH_TIMEOUT).into())
+    }
+
+    async fn capture(&self, id: &str) -> ProcessResult<NodeState> {
+        let client = self.client(id)?;
+        let info = client.node_info().await?;
+        let mut stages = HashMap::new();
+        // Later stages overwrite earlier ones, leaving the furthest stage per transaction
+        for stage in [MempoolStage::Raw, MempoolStage::Processing, MempoolStage::Final] {
+            for entry in client.mempool_stage_entries(stage).await? {
+                stages.insert(entry.tx_id, stage);
+            }
+        }
+        Ok(NodeState {
+            node_id: info["node_id"].as_str().unwrap_or_default().to_string(),
+            public_key: info["public_key"].as_str().unwrap_or_default().to_string(),
+            leaders: serde_json::from_value(info["leaders"].clone()).unwrap_or_default(),
+            stages,
+        })
+    }
+
+    // Records what the node holds, lets a save go through, then SIGKILLs it with no chance to shut down
+    pub async fn kill(&mut self, id: &str) -> ProcessResult<()> {
+        let index = self.index_of(id)?;
+        if self.nodes[index].child.is_none() {
+            return Err(format!("Process node {} is not running", id).into());
+        }
+        let state = self.capture(id).await?;
+        sleep(STATE_SAVE_GRACE).await;
+
+        if let Some(mut child) = self.nodes[index].child.take() {
+            child.kill().await?;
+        }
+        info!("💀 Killed process node {} ({} transactions in its mempools)", id, state.stages.len());
+        self.nodes[index].before_kill = Some(state);
+        Ok(())
+    }
+
+    // Starts a killed node on its old data dir; returns what it failed to recover
+    pub async fn restart(&mut self, id: &str) -> ProcessResult<Vec<String>> {
+        let index = self.index_of(id)?;
+        let before = self.nodes[index].before_kill.take()
+            .ok_or_else(|| format!("Process node {} was not killed", id))?;
+        self.spawn(id).await?;
+
+        let after = self.capture(id).await?;
+        let mut failures = recovery_failures(id, &before, &after);
+        if let Some(upstream) = self.nodes[index].spec.upstream().map(str::to_string) {
+            if let Some(failure) = self.wait_caught_up(id, &upstream).await? {
+                failures.push(failure);
+            }
+        }
+
+        if failures.is_empty() {
+            info!("♻️  Process node {} recovered identity, leaders and {} transactions", id, before.stages.len());
+        } else {
+            for failure in &failures {
+                warn!("❌ {}", failure);
+            }
+        }
+        Ok(failures)
+    }
+
+    // Everything the upstream finalized, including while this node was down, must reach it
+    async fn wait_caught_up(&self, id: &str, upstream: &str) -> ProcessResult<Option<String>> {
+        let node = self.client(id)?;
+        let upstream_client = self.client(upstream)?;
+        let started = Instant::now();
+        loop {
+            let expected: HashSet<String> = upstream_client.mempool_stage_entries(MempoolStage::Final).await?
+                .into_iter().map(|entry| entry.tx_id).collect();
+            let have: HashSet<String> = node.mempool_stage_entries(MempoolStage::Final).await?
+                .into_iter().map(|entry| entry.tx_id).collect();
+            let missing = expected.difference(&have).count();
+            if missing == 0 {
+                info!("📥 Process node {} caught up on {} finalized transactions from {}", id, expected.len(), upstream);
+                return Ok(None);
+            }
+            if started.elapsed() >= CATCH_UP_TIMEOUT {
+                return Ok(Some(format!(
+                    "{}: still missing {} of {} finalized transactions from {} after {:?}",
+                    id, missing, expected.len(), upstream, CATCH_UP_TIMEOUT
+                )));
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    pub async fn faucet(&self, id: &str, count: u32) -> ProcessResult<()> {
+        let client = self.client(id)?;
+        for _ in 0..count {
+            let address = pcl_backend::NodeKeypair::new().address();
+            let body = serde_json::json!({ "address": address, "amount": 10.0 });
+            if let Err(e) = client.post("/faucet", &body).await {
+                warn!("❌ Faucet request through {} failed: {}", id, e);
+            }
+        }
+        Ok(())
+    }
+
+    // One sample per node, running or not, recorded in the monitor
+    pub fn sample_resources(&self, monitor: &mut ResourceMonitor, at_secs: f64) {
+        let round: Vec<ResourceSample> = self.nodes.iter()
+            .map(|node| monitor.sample(&node.spec.id, node.child.as_ref().and_then(|child| child.id()), &node.data_dir, at_secs))
+            .collect();
+        monitor.record_total_rss(&round);
+    }
+
+    // Kills every node and removes their data; logs are kept if a node failed to recover
+    pub async fn shutdown(&mut self, keep_data: bool) {
+        for node in &mut self.nodes {
+            if let Some(mut child) = node.child.take() {
+                let _ = child.kill().await;
+            }
+        }
+        if keep_data {
+            info!("📁 Process node data and logs kept in {}", self.base_dir.display());
+        } else if let Err(e) = std::fs::remove_dir_all(&self.base_dir) {
+            warn!("Could not remove {}: {}", self.base_dir.display(), e);
+        }
+    }
+}
+
+fn stage_rank(stage: MempoolStage) -> u8 {
+    match stage {
+        MempoolStage::Raw => 0,
+        MempoolStage::Tasks => 1,
+        MempoolStage::Processing => 2,
+        MempoolStage::Final => 3,
+    }
+}
+
+// Same identity and leader set, and no transaction lost or moved back a stage
+fn recovery_failures(id: &str, before: &NodeState, after: &NodeState) -> Vec<String> {
+    let mut failures = Vec::new();
+    if after.node_id != before.node_id || after.public_key != before.public_key {
+        failures.push(format!("{}: came back as {} instead of {}", id, after.node_id, before.node_id));
+    }
+    if after.leaders != before.leaders {
+        failures.push(format!("{}: leaders changed from {:?} to {:?}", id, before.leaders, after.leaders));
+    }
+    let lost: Vec<&String> = before.stages.iter()
+        .filter(|(tx_id, stage)| after.stages.get(*tx_id).is_none_or(|now| stage_rank(*now) < stage_rank(**stage)))
+        .map(|(tx_id, _)| tx_id)
+        .collect();
+    if !lost.is_empty() {
+        failures.push(format!("{}: lost or rolled back {} of {} transactions", id, lost.len(), before.stages.len()));
+    }
+    failures
+}
@@ -0,0 +1,220 @@
+// Run reports written by load tests and scenarios, and the comparison that gates on them
+//
+// A run can save its key metrics as JSON with --report. `compare` diffs a current report against a
+// stored baseline and flags a regression when throughput drops, p99 latency or spawned node memory
+// grows by more than a relative threshold, or the failure rate rises by more than a number of
+// percentage points. Metrics missing from either report are shown but never fail the comparison.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::external::ExternalRunReport;
+use crate::resources::ResourceProfile;
+use crate::scenario::{Scenario, ScenarioOutcome};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub name: String,
+    pub transactions: u64,
+    pub tps: f64,
+    pub p99_latency_ms: Option<f64>,
+    pub failure_rate: f64,               // percent
+    pub peak_node_memory_kb: Option<u64>, // summed RSS of spawned pcl-node processes
+    #[serde(default)]
+    pub resource_profiles: Vec<ResourceProfile>, // per spawned node, sampled over the run
+}
+
+impl RunReport {
+    pub fn from_scenario(scenario: &Scenario, outcome: &ScenarioOutcome) -> Self {
+        Self {
+            name: scenario.name.clone(),
+            transactions: outcome.transactions,
+            tps: outcome.transactions as f64 / scenario.duration_secs as f64,
+            p99_latency_ms: outcome.p99_latency_ms,
+            failure_rate: 100.0 - outcome.success_rate(),
+            peak_node_memory_kb: outcome.peak_node_memory_kb,
+            resource_profiles: outcome.resource_profiles.clone(),
+        }
+    }
+
+    // Transactions and throughput both count finalized ones, the rest shows in the failure rate; the
+    // node under test was not spawned, so there is no memory
+    pub fn from_external(name: &str, report: &ExternalRunReport, duration_secs: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            transactions: report.finalized,
+            tps: report.finalized as f64 / duration_secs.max(1) as f64,
+            p99_latency_ms: report.percentile_ms(99.0).map(|ms| ms as f64),
+            failure_rate: 100.0 - report.success_rate(),
+            peak_node_memory_kb: None,
+            resource_profiles: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read report {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid report {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, contents).map_err(|e| format!("Could not write report {}: {}", path.display(), e))
+    }
+}
+
+// Largest change each metric may take before it counts as a regression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thresholds {
+    pub max_tps_drop_pct: f64,
+    pub max_p99_increase_pct: f64,
+    pub max_failure_rate_increase: f64, // percentage points
+    pub max_memory_increase_pct: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricComparison {
+    pub metric: &'static str,
+    pub baseline: Option<f64>,
+    pub current: Option<f64>,
+    pub change: Option<f64>, // percent, or percentage points for the failure rate
+    pub regression: bool,
+}
+
+impl MetricComparison {
+    // Relative change; a worse value is a positive change when higher is worse
+    fn relative(metric: &'static str, baseline: Option<f64>, current: Option<f64>, higher_is_worse: bool, limit: f64) -> Self {
+        let change = match (baseline, current) {
+            (Some(baseline), Some(current)) if baseline > 0.0 => Some((current - baseline) / baseline * 100.0),
+            _ => None,
+        };
+        let worse = change.map(|change| if higher_is_worse { change } else { -change });
+        Self { metric, baseline, current, change, regression: worse.is_some_and(|worse| worse > limit) }
+    }
+}
+
+pub fn compare(baseline: &RunReport, current: &RunReport, thresholds: &Thresholds) -> Vec<MetricComparison> {
+    let failure_change = current.failure_rate - baseline.failure_rate;
+    vec![
+        MetricComparison::relative("tps", Some(baseline.tps), Some(current.tps), false, thresholds.max_tps_drop_pct),
+        MetricComparison::relative(
+            "p99_latency_ms",
+            baseline.p99_latency_ms,
+            current.p99_latency_ms,
+            true,
+            thresholds.max_p99_increase_pct,
+        ),
+        MetricComparison {
+            metric: "failure_rate",
+            baseline: Some(baseline.failure_rate),
+            current: Some(current.failure_rate),
+            change: Some(failure_change),
+            regression: failure_change > thresholds.max_failure_rate_increase,
+        },
+        MetricComparison::relative(
+            "peak_node_memory_kb",
+            baseline.peak_node_memory_kb.map(|kb| kb as f64),
+            current.peak_node_memory_kb.map(|kb| kb as f64),
+            true,
+            thresholds.max_memory_increase_pct,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> Thresholds {
+        Thresholds {
+            max_tps_drop_pct: 10.0,
+            max_p99_increase_pct: 20.0,
+            max_failure_rate_increase: 2.0,
+            max_memory_increase_pct: 25.0,
+        }
+    }
+
+    fn report(tps: f64, p99_latency_ms: Option<f64>, failure_rate: f64, peak_node_memory_kb: Option<u64>) -> RunReport {
+        RunReport { name: "run".to_string(), transactions: 1000, tps, p99_latency_ms, failure_rate, peak_node_memory_kb, ..Default::default() }
+    }
+
+    fn metric<'a>(comparisons: &'a [MetricComparison], name: &str) -> &'a MetricComparison {
+        comparisons.iter().find(|comparison| comparison.metric == name).unwrap()
+    }
+
+    #[test]
+    fn test_tps_drop_beyond_threshold_is_a_regression() {
+        // Test: Compare 100 TPS against 80 and against 95 with a 10% drop allowed
+        // Expected: -20% is a regression, -5% is not, and a gain never is
+        println!("Expected: Throughput regresses only when it drops by more than the threshold");
+
+        let baseline = report(100.0, None, 0.0, None);
+        let dropped = compare(&baseline, &report(80.0, None, 0.0, None), &thresholds());
+        assert_eq!(metric(&dropped, "tps").change, Some(-20.0));
+        assert!(metric(&dropped, "tps").regression);
+
+        assert!(!metric(&compare(&baseline, &report(95.0, None, 0.0, None), &thresholds()), "tps").regression);
+        assert!(!metric(&compare(&baseline, &report(150.0, None, 0.0, None), &thresholds()), "tps").regression);
+    }
+
+    #[test]
+    fn test_p99_increase_beyond_threshold_is_a_regression() {
+        // Test: Compare a 100 ms p99 against 130 ms and against 110 ms with a 20% increase allowed
+        // Expected: +30% is a regression, +10% is not, and a faster p99 never is
+        println!("Expected: p99 latency regresses only when it grows by more than the threshold");
+
+        let baseline = report(100.0, Some(100.0), 0.0, None);
+        let slower = compare(&baseline, &report(100.0, Some(130.0), 0.0, None), &thresholds());
+        let p99 = metric(&slower, "p99_latency_ms");
+        assert!((p99.change.unwrap() - 30.0).abs() < 1e-9);
+        assert!(p99.regression);
+
+        assert!(!metric(&compare(&baseline, &report(100.0, Some(110.0), 0.0, None), &thresholds()), "p99_latency_ms").regression);
+        assert!(!metric(&compare(&baseline, &report(100.0, Some(50.0), 0.0, None), &thresholds()), "p99_latency_ms").regression);
+    }
+
+    #[test]
+    fn test_failure_rate_is_compared_in_percentage_points() {
+        // Test: Compare a 1% failure rate against 4% and against 2.5% with 2 points allowed
+        // Expected: The change is in points, not relative: +3 is a regression, +1.5 is not
+        println!("Expected: The failure rate regresses when it rises by more than the allowed points");
+
+        let baseline = report(100.0, None, 1.0, None);
+        let worse = compare(&baseline, &report(100.0, None, 4.0, None), &thresholds());
+        assert_eq!(metric(&worse, "failure_rate").change, Some(3.0));
+        assert!(metric(&worse, "failure_rate").regression);
+
+        let slightly_worse = compare(&baseline, &report(100.0, None, 2.5, None), &thresholds());
+        assert_eq!(metric(&slightly_worse, "failure_rate").change, Some(1.5));
+        assert!(!metric(&slightly_worse, "failure_rate").regression);
+    }
+
+    #[test]
+    fn test_missing_metrics_never_fail_the_comparison() {
+        // Test: Compare reports where p99 is missing from the baseline, memory from the current run,
+        // and the baseline TPS is zero
+        // Expected: Those metrics keep the values that exist but have no change and no regression
+        println!("Expected: Metrics missing from either side are shown but never regress");
+
+        let comparisons = compare(&report(0.0, None, 0.0, Some(1000)), &report(50.0, Some(500.0), 0.0, None), &thresholds());
+        for name in ["tps", "p99_latency_ms", "peak_node_memory_kb"] {
+            assert_eq!(metric(&comparisons, name).change, None);
+            assert!(!metric(&comparisons, name).regression);
+        }
+        assert_eq!(metric(&comparisons, "p99_latency_ms").current, Some(500.0));
+        assert_eq!(metric(&comparisons, "peak_node_memory_kb").baseline, Some(1000.0));
+    }
+
+    #[test]
+    fn test_external_report_counts_finalized_transactions_for_both_totals() {
+        // Test: Build a report from 100 submissions of which 80 finalized over 10 seconds
+        // Expected: 80 transactions at 8 TPS, with the 20 that never finalized in the failure rate
+        println!("Expected: Transactions and TPS share the finalized basis");
+
+        let external = ExternalRunReport { submitted: 100, accepted: 90, rejected: 10, finalized: 80, latencies_ms: vec![5, 10, 20] };
+        let run = RunReport::from_external("external", &external, 10);
+        assert_eq!(run.transactions, 80);
+        assert_eq!(run.tps, run.transactions as f64 / 10.0);
+        assert_eq!(run.failure_rate, 20.0);
+        assert_eq!(run.p99_latency_ms, Some(20.0));
+    }
+}
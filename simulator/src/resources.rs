@@ -0,0 +1,118 @@
+// Resource monitoring for spawned pcl-node processes
+//
+// Spawned nodes were started and killed with nothing recorded about what they used. The monitor
+// samples every running node from /proc: CPU since the previous sample, resident memory and open
+// file descriptors, plus the size of the node's data directory. Samples are kept per node over the
+// whole run and summarised into a profile that goes into the run report. Where /proc is missing
+// (anything but Linux) only disk usage is recorded.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+// Kernel clock ticks per second, the unit of utime and stime in /proc/<pid>/stat
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub at_secs: f64,                // since the run started
+    pub cpu_percent: Option<f64>,    // of one core, since the previous sample of the same process
+    pub rss_kb: Option<u64>,
+    pub open_fds: Option<u64>,
+    pub disk_bytes: u64,             // data directory, logs included
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceProfile {
+    pub node: String,
+    pub peak_cpu_percent: Option<f64>,
+    pub average_cpu_percent: Option<f64>,
+    pub peak_rss_kb: Option<u64>,
+    pub peak_open_fds: Option<u64>,
+    pub final_disk_bytes: u64,
+    pub samples: Vec<ResourceSample>,
+}
+
+#[derive(Default)]
+pub struct ResourceMonitor {
+    samples: BTreeMap<String, Vec<ResourceSample>>,
+    cpu_ticks: HashMap<(String, u32), (u64, f64)>, // (node, pid) -> ticks and time at the last sample
+    peak_total_rss_kb: Option<u64>,
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Samples one node; pid is None while the node is down, which records disk usage only
+    pub fn sample(&mut self, node: &str, pid: Option<u32>, data_dir: &Path, at_secs: f64) -> ResourceSample {
+        let mut sample = ResourceSample { at_secs, disk_bytes: dir_size(data_dir), ..Default::default() };
+        if let Some(pid) = pid {
+            sample.rss_kb = read_rss_kb(pid);
+            sample.open_fds = std::fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|fds| fds.count() as u64);
+            if let Some(ticks) = read_cpu_ticks(pid) {
+                let previous = self.cpu_ticks.insert((node.to_string(), pid), (ticks, at_secs));
+                sample.cpu_percent = previous
+                    .filter(|(_, then)| at_secs > *then)
+                    .map(|(before, then)| ticks.saturating_sub(before) as f64 / CLOCK_TICKS_PER_SEC / (at_secs - then) * 100.0);
+            }
+        }
+        self.samples.entry(node.to_string()).or_default().push(sample.clone());
+        sample
+    }
+
+    // Records the summed RSS of one round of samples
+    pub fn record_total_rss(&mut self, round: &[ResourceSample]) {
+        let total = round.iter().filter_map(|sample| sample.rss_kb).reduce(|a, b| a + b);
+        self.peak_total_rss_kb = self.peak_total_rss_kb.max(total);
+    }
+
+    pub fn peak_total_rss_kb(&self) -> Option<u64> {
+        self.peak_total_rss_kb
+    }
+
+    pub fn profiles(&self) -> Vec<ResourceProfile> {
+        self.samples.iter()
+            .map(|(node, samples)| {
+                let cpu: Vec<f64> = samples.iter().filter_map(|sample| sample.cpu_percent).collect();
+                ResourceProfile {
+                    node: node.clone(),
+                    peak_cpu_percent: cpu.iter().copied().reduce(f64::max),
+                    average_cpu_percent: (!cpu.is_empty()).then(|| cpu.iter().sum::<f64>() / cpu.len() as f64),
+                    peak_rss_kb: samples.iter().filter_map(|sample| sample.rss_kb).max(),
+                    peak_open_fds: samples.iter().filter_map(|sample| sample.open_fds).max(),
+                    final_disk_bytes: samples.last().map_or(0, |sample| sample.disk_bytes),
+                    samples: samples.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn read_rss_kb(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+// utime + stime; the command name in field 2 may contain spaces, so fields are counted after its ')'
+fn read_cpu_ticks(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+    entries.flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
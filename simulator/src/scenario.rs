@@ -0,0 +1,256 @@
+use config::{Config, File, FileFormat};
+use serde::{Deserialize, Serialize};
+use crate::resources::ResourceProfile;
+use std::path::Path;
+
+type ScenarioResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+// A scripted simulation: a network size, a timeline of actions and the outcome it must reach.
+// Written as TOML or YAML, e.g.
+//
+//   name = "leader-crash"
+//   nodes = 20
+//   leaders = 3
+//   duration_secs = 90
+//
+//   [[timeline]]
+//   at_secs = 0
+//   action = "set_tps"
+//   tps = 20
+//
+//   [[timeline]]
+//   at_secs = 45
+//   action = "crash_leader"
+//
+//   [expect]
+//   min_success_rate = 90.0
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub nodes: u32,
+    pub leaders: u32,
+    pub duration_secs: u64,
+    // Real pcl-node child processes, started before the timeline and targeted by kill/restart
+    #[serde(default)]
+    pub process_nodes: Vec<ProcessNodeSpec>,
+    #[serde(default)]
+    pub timeline: Vec<TimedAction>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessNodeSpec {
+    pub id: String,
+    pub port: u16,
+    // Another process node this one tails as a read-only replica
+    #[serde(default)]
+    pub replica_of: Option<String>,
+    // Another process node to fetch missed finalized transactions from on restart
+    #[serde(default)]
+    pub catch_up_from: Option<String>,
+}
+
+impl ProcessNodeSpec {
+    // The node it follows, whose finalized transactions it must have caught up on after a restart
+    pub fn upstream(&self) -> Option<&str> {
+        self.replica_of.as_deref().or(self.catch_up_from.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedAction {
+    pub at_secs: f64, // offset from the start of the run
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    SetTps { tps: u32 },
+    // Linear change in load starting at the action's offset
+    Ramp { from_tps: u32, to_tps: u32, over_secs: f64 },
+    // Cut this many nodes off from the rest until heal
+    Partition { nodes: u32 },
+    Heal,
+    CrashLeader {
+        #[serde(default = "default_count")]
+        count: u32,
+    },
+    CrashNodes { count: u32 },
+    RecoverNodes { count: u32 },
+    SpawnNodes { count: u32 },
+    // Validators that sign off on invalid transactions
+    ByzantineValidatorJoins {
+        #[serde(default = "default_count")]
+        count: u32,
+    },
+    LeaderElection,
+    // Process nodes: SIGKILL, then start again on the same data dir and verify what it recovered
+    KillNode { node: String },
+    RestartNode { node: String },
+    // Faucet drips to fresh addresses through a process node, so there is state to lose
+    Faucet {
+        node: String,
+        #[serde(default = "default_count")]
+        count: u32,
+    },
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+// Checked once the timeline has run; unset fields are not checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Expectations {
+    pub min_success_rate: Option<f64>, // percent
+    pub max_success_rate: Option<f64>,
+    pub min_transactions: Option<u64>,
+    pub min_active_nodes: Option<u32>,
+    pub min_leaders: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioOutcome {
+    pub transactions: u64,
+    pub successful: u64,
+    pub failed_validations: u64,
+    pub leader_elections: u64,
+    pub active_nodes: u32,
+    pub leaders: u32,
+    #[serde(default)]
+    pub node_restarts: u32,
+    // What restarted process nodes failed to recover; any entry fails the scenario
+    #[serde(default)]
+    pub recovery_failures: Vec<String>,
+    #[serde(default)]
+    pub p99_latency_ms: Option<f64>,
+    // Highest summed RSS of the scenario's process nodes while it ran
+    #[serde(default)]
+    pub peak_node_memory_kb: Option<u64>,
+    #[serde(default)]
+    pub resource_profiles: Vec<ResourceProfile>,
+}
+
+impl ScenarioOutcome {
+    pub fn success_rate(&self) -> f64 {
+        if self.transactions == 0 {
+            0.0
+        } else {
+            self.successful as f64 / self.transactions as f64 * 100.0
+        }
+    }
+}
+
+impl Scenario {
+    // The format follows the extension: .toml, .yaml or .yml
+    pub fn from_file<P: AsRef<Path>>(path: P) -> ScenarioResult<Self> {
+        let path = path.as_ref();
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => FileFormat::Toml,
+            Some("yaml") | Some("yml") => FileFormat::Yaml,
+            _ => return Err(format!("Scenario {:?} must be a .toml, .yaml or .yml file", path).into()),
+        };
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse(&contents, format)
+    }
+
+    pub fn parse(contents: &str, format: FileFormat) -> ScenarioResult<Self> {
+        let scenario: Scenario = Config::builder()
+            .add_source(File::from_str(contents, format))
+            .build()?
+            .try_deserialize()?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn validate(&self) -> ScenarioResult<()> {
+        if self.leaders == 0 || self.leaders > self.nodes {
+            return Err(format!("{}: leaders must be between 1 and nodes ({}), got {}", self.name, self.nodes, self.leaders).into());
+        }
+        if self.duration_secs == 0 {
+            return Err(format!("{}: duration_secs must be positive", self.name).into());
+        }
+        for (i, spec) in self.process_nodes.iter().enumerate() {
+            if self.process_nodes[..i].iter().any(|other| other.id == spec.id || other.port == spec.port) {
+                return Err(format!("{}: process node {} reuses an earlier id or port", self.name, spec.id).into());
+            }
+            if spec.replica_of.is_some() && spec.catch_up_from.is_some() {
+                return Err(format!("{}: process node {} cannot be both a replica and catch up", self.name, spec.id).into());
+            }
+            // Upstreams are started first, so they must be declared first
+            if let Some(upstream) = spec.upstream().filter(|upstream| !self.process_nodes[..i].iter().any(|other| other.id == *upstream)) {
+                return Err(format!("{}: process node {} follows {}, which is not declared before it", self.name, spec.id, upstream).into());
+            }
+        }
+        for timed in &self.timeline {
+            if let Action::KillNode { node } | Action::RestartNode { node } | Action::Faucet { node, .. } = &timed.action {
+                if !self.process_nodes.iter().any(|spec| spec.id == *node) {
+                    return Err(format!("{}: action at {}s targets unknown process node {}", self.name, timed.at_secs, node).into());
+                }
+            }
+            if !(0.0..=self.duration_secs as f64).contains(&timed.at_secs) {
+                return Err(format!(
+                    "{}: action at {}s is outside the {}s run", self.name, timed.at_secs, self.duration_secs
+                ).into());
+            }
+            if let Action::Ramp { over_secs, .. } = timed.action {
+                if over_secs <= 0.0 {
+                    return Err(format!("{}: ramp at {}s needs a positive over_secs", self.name, timed.at_secs).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Actions in the order they fire; ties keep file order
+    pub fn sorted_timeline(&self) -> Vec<TimedAction> {
+        let mut timeline = self.timeline.clone();
+        timeline.sort_by(|a, b| a.at_secs.total_cmp(&b.at_secs));
+        timeline
+    }
+
+    // One message per unmet expectation; empty means the scenario passed
+    pub fn check(&self, outcome: &ScenarioOutcome) -> Vec<String> {
+        let expect = &self.expect;
+        let mut failures = Vec::new();
+        let success_rate = outcome.success_rate();
+        if let Some(min) = expect.min_success_rate.filter(|min| success_rate < *min) {
+            failures.push(format!("success rate {:.2}% is below {:.2}%", success_rate, min));
+        }
+        if let Some(max) = expect.max_success_rate.filter(|max| success_rate > *max) {
+            failures.push(format!("success rate {:.2}% is above {:.2}%", success_rate, max));
+        }
+        if let Some(min) = expect.min_transactions.filter(|min| outcome.transactions < *min) {
+            failures.push(format!("{} transactions, expected at least {}", outcome.transactions, min));
+        }
+        if let Some(min) = expect.min_active_nodes.filter(|min| outcome.active_nodes < *min) {
+            failures.push(format!("{} active nodes, expected at least {}", outcome.active_nodes, min));
+        }
+        if let Some(min) = expect.min_leaders.filter(|min| outcome.leaders < *min) {
+            failures.push(format!("{} leaders, expected at least {}", outcome.leaders, min));
+        }
+        failures.extend(outcome.recovery_failures.iter().cloned());
+        failures
+    }
+}
+
+// Target load at `elapsed` seconds given the actions that have fired so far
+pub fn tps_at(fired: &[TimedAction], elapsed: f64) -> u32 {
+    let mut tps = 0;
+    for timed in fired {
+        match timed.action {
+            Action::SetTps { tps: value } => tps = value,
+            Action::Ramp { from_tps, to_tps, over_secs } => {
+                let progress = ((elapsed - timed.at_secs) / over_secs).clamp(0.0, 1.0);
+                tps = (from_tps as f64 + (to_tps as f64 - from_tps as f64) * progress).round() as u32;
+            }
+            _ => {}
+        }
+    }
+    tps
+}
@@ -3,10 +3,13 @@ use crate::transaction_generator::TransactionGenerator;
 use crate::metrics::SimulationMetrics;
 use crate::network::NetworkSimulator;
 use crate::BenchmarkScenario;
+use crate::scenario::{tps_at, Action, Scenario, ScenarioOutcome, TimedAction};
+use crate::process_nodes::{node_binary, ProcessCluster};
+use crate::resources::ResourceMonitor;
 
-use pcl_backend::{Node, NodeKeypair, NodeRole, NodeRegistry};
+use pcl_backend::{Node, NodeKeypair, NodeRole, NodeRegistry, StorageManager, UptimeData, UptimeWriteBuffer};
 use log::{info, warn, error, debug};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, mpsc};
@@ -15,6 +18,22 @@ use uuid::Uuid;
 use rand::Rng;
 use indicatif::{ProgressBar, ProgressStyle};
 
+// How often a scenario run checks its timeline and tops up the transaction rate
+const SCENARIO_TICK: Duration = Duration::from_millis(100);
+// How often process nodes' CPU, memory, descriptors and disk are sampled
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+// Faults a scenario has injected that are still in effect
+#[derive(Default)]
+struct ScenarioFaults {
+    partitioned: HashSet<Uuid>,
+    byzantine: HashSet<Uuid>,
+    crashed: Vec<Uuid>,
+    processes: Option<ProcessCluster>, // the scenario's real pcl-node processes, if it declares any
+    node_restarts: u32,
+    recovery_failures: Vec<String>,
+}
+
 pub struct Simulation {
     pub node_spawner: NodeSpawner,
     pub transaction_generator: TransactionGenerator,
@@ -344,9 +363,241 @@ impl Simulation {
         }
         
         self.print_benchmark_results("Mempool Performance", &lookup_times);
+        self.benchmark_uptime_writes(iterations)?;
+        Ok(())
+    }
+    
+    // One round is a pulse from every peer: written one put at a time, then buffered and flushed as a batch
+    fn benchmark_uptime_writes(&self, rounds: u32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        const UPTIME_BENCH_PEERS: usize = 500;
+        let dir = std::env::temp_dir().join(format!("pcl_uptime_bench_{}", Uuid::new_v4()));
+        let storage = Arc::new(StorageManager::new(&dir)?);
+        let uptime = |peer: usize, round: u32| UptimeData {
+            node_id: format!("peer_{}", peer),
+            total_uptime_seconds: round as u64 * 20,
+            last_seen: chrono::Utc::now(),
+            pulse_count: round as u64,
+            average_response_time_ms: 50.0,
+            uptime_percentage: 100.0,
+        };
+        
+        let mut individual_times = Vec::new();
+        for round in 0..rounds {
+            let start = Instant::now();
+            for peer in 0..UPTIME_BENCH_PEERS {
+                storage.store_uptime_data(&format!("peer_{}", peer), &uptime(peer, round))?;
+            }
+            individual_times.push(start.elapsed());
+        }
+        
+        let mut buffer = UptimeWriteBuffer::new(storage.clone());
+        let mut batched_times = Vec::new();
+        for round in 0..rounds {
+            let start = Instant::now();
+            for peer in 0..UPTIME_BENCH_PEERS {
+                buffer.record(uptime(peer, round));
+            }
+            buffer.flush()?;
+            batched_times.push(start.elapsed());
+        }
+        
+        self.print_benchmark_results(&format!("Uptime Writes ({} peers, one put each)", UPTIME_BENCH_PEERS), &individual_times);
+        self.print_benchmark_results(&format!("Uptime Writes ({} peers, batched)", UPTIME_BENCH_PEERS), &batched_times);
+        drop(buffer);
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&dir);
+        Ok(())
+    }
+    
+    pub async fn run_scenario(&mut self, scenario: &Scenario) -> Result<ScenarioOutcome, Box<dyn std::error::Error + Send + Sync>> {
+        info!("Running scenario {} for {}s", scenario.name, scenario.duration_secs);
+        
+        let timeline = scenario.sorted_timeline();
+        let duration = scenario.duration_secs as f64;
+        let mut fired: Vec<TimedAction> = Vec::new();
+        let mut faults = ScenarioFaults::default();
+        if !scenario.process_nodes.is_empty() {
+            faults.processes = Some(ProcessCluster::start(&scenario.process_nodes, node_binary()).await?);
+        }
+        let mut owed_transactions = 0.0;
+        let mut last_tick = 0.0;
+        let mut ticker = interval(SCENARIO_TICK);
+        let start_time = Instant::now();
+        let mut resources = ResourceMonitor::new();
+        let mut last_sample: Option<Instant> = None;
+        self.metrics.write().await.start_simulation();
+        
+        loop {
+            ticker.tick().await;
+            let elapsed = start_time.elapsed().as_secs_f64().min(duration);
+            
+            while fired.len() < timeline.len() && timeline[fired.len()].at_secs <= elapsed {
+                let timed = timeline[fired.len()].clone();
+                info!("t={:.1}s: {:?}", timed.at_secs, timed.action);
+                self.apply_scenario_action(&timed.action, &mut faults).await?;
+                fired.push(timed);
+            }
+            
+            if let Some(processes) = faults.processes.as_ref().filter(|_| last_sample.is_none_or(|at| at.elapsed() >= RESOURCE_SAMPLE_INTERVAL)) {
+                processes.sample_resources(&mut resources, elapsed);
+                last_sample = Some(Instant::now());
+            }
+            
+            owed_transactions += tps_at(&fired, elapsed) as f64 * (elapsed - last_tick);
+            last_tick = elapsed;
+            while owed_transactions >= 1.0 {
+                owed_transactions -= 1.0;
+                self.scenario_transaction(&faults).await;
+            }
+            
+            if elapsed >= duration {
+                break;
+            }
+        }
+        self.metrics.write().await.end_simulation();
+        if let Some(processes) = faults.processes.as_mut() {
+            processes.shutdown(!faults.recovery_failures.is_empty()).await;
+        }
+        
+        let metrics = self.metrics.read().await;
+        Ok(ScenarioOutcome {
+            transactions: metrics.total_transactions,
+            successful: metrics.successful_transactions,
+            failed_validations: metrics.failed_validations,
+            leader_elections: metrics.leader_election_count,
+            active_nodes: self.active_nodes.read().await.len() as u32,
+            leaders: self.node_spawner.get_leader_count().await,
+            node_restarts: faults.node_restarts,
+            recovery_failures: faults.recovery_failures,
+            p99_latency_ms: metrics.get_percentile_latency(99.0).map(|latency| latency.as_secs_f64() * 1000.0),
+            peak_node_memory_kb: resources.peak_total_rss_kb(),
+            resource_profiles: resources.profiles(),
+        })
+    }
+    
+    async fn apply_scenario_action(&mut self, action: &Action, faults: &mut ScenarioFaults) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match action {
+            // Load changes are read back from the fired timeline by tps_at
+            Action::SetTps { .. } | Action::Ramp { .. } => {}
+            Action::Partition { nodes } => {
+                let candidates: Vec<Uuid> = self.active_nodes.read().await.keys()
+                    .filter(|id| !faults.partitioned.contains(id))
+                    .copied()
+                    .collect();
+                faults.partitioned.extend(candidates.into_iter().take(*nodes as usize));
+                self.network.simulate_network_partition(*nodes).await?;
+            }
+            Action::Heal => {
+                faults.partitioned.clear();
+                self.network.simulate_network_recovery().await?;
+            }
+            Action::CrashLeader { count } => {
+                let leaders: Vec<Uuid> = self.node_spawner.get_all_leaders().await.iter().map(|node| node.id).collect();
+                for node_id in leaders.into_iter().take(*count as usize) {
+                    self.node_spawner.simulate_node_failure(node_id).await?;
+                    faults.crashed.push(node_id);
+                }
+                self.replace_lost_leaders(faults).await?;
+            }
+            Action::CrashNodes { count } => {
+                let nodes: Vec<Uuid> = self.active_nodes.read().await.values()
+                    .filter(|node| node.role != NodeRole::Leader)
+                    .map(|node| node.id)
+                    .collect();
+                for node_id in nodes.into_iter().take(*count as usize) {
+                    self.node_spawner.simulate_node_failure(node_id).await?;
+                    faults.crashed.push(node_id);
+                }
+            }
+            Action::RecoverNodes { count } => {
+                let recovering = faults.crashed.len().min(*count as usize);
+                for node_id in faults.crashed.drain(..recovering) {
+                    self.node_spawner.simulate_node_recovery(node_id).await?;
+                }
+            }
+            Action::SpawnNodes { count } => {
+                for _ in 0..*count {
+                    self.node_spawner.spawn_extension_node().await?;
+                }
+            }
+            Action::ByzantineValidatorJoins { count } => {
+                for _ in 0..*count {
+                    let node = self.node_spawner.spawn_validator_node().await?;
+                    warn!("Byzantine validator {} joined", node.id);
+                    faults.byzantine.insert(node.id);
+                }
+            }
+            Action::LeaderElection => {
+                let start = Instant::now();
+                self.network.trigger_leader_election().await?;
+                self.metrics.write().await.record_leader_election(start.elapsed());
+            }
+            Action::KillNode { node } => {
+                faults.processes.as_mut().ok_or("Scenario has no process nodes")?.kill(node).await?;
+            }
+            Action::RestartNode { node } => {
+                let failures = faults.processes.as_mut().ok_or("Scenario has no process nodes")?.restart(node).await?;
+                faults.node_restarts += 1;
+                faults.recovery_failures.extend(failures);
+            }
+            Action::Faucet { node, count } => {
+                faults.processes.as_ref().ok_or("Scenario has no process nodes")?.faucet(node, *count).await?;
+            }
+        }
+        Ok(())
+    }
+    
+    // Crashed leaders are replaced by election from healthy, honest nodes
+    async fn replace_lost_leaders(&mut self, faults: &ScenarioFaults) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let missing = self.leader_count.saturating_sub(self.node_spawner.get_leader_count().await);
+        if missing == 0 {
+            return Ok(());
+        }
+        
+        let start = Instant::now();
+        self.network.trigger_leader_election().await?;
+        let candidates: Vec<Uuid> = self.active_nodes.read().await.values()
+            .filter(|node| node.role != NodeRole::Leader)
+            .filter(|node| !faults.partitioned.contains(&node.id) && !faults.byzantine.contains(&node.id))
+            .map(|node| node.id)
+            .collect();
+        for node_id in candidates.into_iter().take(missing as usize) {
+            self.node_spawner.promote_to_leader(node_id).await?;
+        }
+        self.metrics.write().await.record_leader_election(start.elapsed());
         Ok(())
     }
     
+    // The generator does not report which nodes a transaction touched, so faults apply in
+    // proportion to the share of the network they affect
+    async fn scenario_transaction(&self, faults: &ScenarioFaults) {
+        let started = Instant::now();
+        let result = self.transaction_generator.generate_random_transaction().await;
+        let latency = started.elapsed();
+        let (partitioned, byzantine) = {
+            let active_nodes = self.active_nodes.read().await;
+            let total = active_nodes.len().max(1) as f64;
+            let partitioned = faults.partitioned.iter().filter(|id| active_nodes.contains_key(id)).count() as f64 / total;
+            let byzantine = faults.byzantine.iter().filter(|id| active_nodes.contains_key(id)).count() as f64 / total;
+            (partitioned, byzantine)
+        };
+        
+        let roll: f64 = rand::thread_rng().gen();
+        let mut metrics = self.metrics.write().await;
+        let result = match result {
+            Ok(_) if roll < partitioned => Err("Transaction did not reach its leader across the partition".into()),
+            Ok(_) if roll < partitioned + byzantine => {
+                metrics.record_failed_validation();
+                Err("Transaction was validated by a byzantine validator".into())
+            }
+            other => other,
+        };
+        if result.is_ok() {
+            metrics.record_transaction_latency(latency);
+        }
+        metrics.record_transaction(result);
+    }
+    
     fn print_benchmark_results(&self, benchmark_name: &str, times: &[Duration]) {
         if times.is_empty() {
             return;
@@ -0,0 +1,227 @@
+// Soak runs - hours of moderate load against a running node, watching whether it holds steady
+//
+// Short load tests cannot show a node that keeps everything it ever saw: finalized transactions,
+// processed tasks and their records are never pruned, so memory, mempools and the database grow for
+// as long as the node runs. A soak run submits transfers at a fixed rate and samples the target's RSS
+// (given its pid), mempool counts (from GET /mempools) and data directory size at an interval. After
+// a warmup, the samples are split into an early and a late half; a metric has plateaued when its late
+// peak is within a relative tolerance of its early peak. Any metric that keeps growing fails the run.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::resources::{dir_size, read_rss_kb};
+
+// Mempools reported by GET /mempools, each with a count
+pub const MEMPOOLS: [&str; 5] = [
+    "raw_tx_mempool",
+    "validation_tasks_mempool",
+    "locked_utxo_mempool",
+    "processing_tx_mempool",
+    "tx_mempool",
+];
+
+// Growth below these is noise whatever the percentage: a mempool going from 2 to 40 entries, or a
+// few megabytes of allocator slack, is not a leak
+const MIN_COUNT_GROWTH: f64 = 100.0;
+const MIN_KB_GROWTH: f64 = 8.0 * 1024.0;
+
+// Fewest samples after warmup that can tell a plateau from growth
+const MIN_SAMPLES: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoakSample {
+    pub at_secs: f64,                   // since the run started
+    pub rss_kb: Option<u64>,            // None without a pid, or once the process is gone
+    pub db_kb: Option<u64>,             // data directory, when given
+    pub mempools: BTreeMap<String, u64>, // count per mempool; empty if the node did not answer
+}
+
+impl SoakSample {
+    pub fn take(at_secs: f64, pid: Option<u32>, data_dir: Option<&Path>, mempools: &serde_json::Value) -> Self {
+        Self {
+            at_secs,
+            rss_kb: pid.and_then(read_rss_kb),
+            db_kb: data_dir.map(|dir| dir_size(dir) / 1024),
+            mempools: MEMPOOLS.iter()
+                .filter_map(|name| mempools[name]["count"].as_u64().map(|count| (name.to_string(), count)))
+                .collect(),
+        }
+    }
+
+    // Every sampled metric by name, so the check treats memory, disk and mempools alike
+    fn metrics(&self) -> Vec<(String, f64)> {
+        let mut metrics = Vec::new();
+        if let Some(rss_kb) = self.rss_kb {
+            metrics.push(("rss_kb".to_string(), rss_kb as f64));
+        }
+        if let Some(db_kb) = self.db_kb {
+            metrics.push(("db_kb".to_string(), db_kb as f64));
+        }
+        metrics.extend(self.mempools.iter().map(|(name, count)| (name.clone(), *count as f64)));
+        metrics
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricTrend {
+    pub metric: String,
+    pub early_peak: f64,
+    pub late_peak: f64,
+    pub growth_pct: Option<f64>, // late peak over early peak; None when the early peak is zero
+    pub per_hour: f64,           // least-squares slope over the samples after warmup
+    pub unbounded: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoakReport {
+    pub target: String,
+    pub duration_secs: u64,
+    pub submitted: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub trends: Vec<MetricTrend>,
+    pub samples: Vec<SoakSample>,
+}
+
+impl SoakReport {
+    pub fn unbounded(&self) -> Vec<&MetricTrend> {
+        self.trends.iter().filter(|trend| trend.unbounded).collect()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, contents).map_err(|e| format!("Could not write soak report {}: {}", path.display(), e))
+    }
+}
+
+// Trends of every metric sampled after warmup; fails when there are too few samples to judge
+pub fn trends(samples: &[SoakSample], warmup_secs: f64, max_growth_pct: f64) -> Result<Vec<MetricTrend>, String> {
+    let settled: Vec<&SoakSample> = samples.iter().filter(|sample| sample.at_secs >= warmup_secs).collect();
+    if settled.len() < MIN_SAMPLES {
+        return Err(format!(
+            "Only {} sample(s) after the {:.0}s warmup, at least {} are needed; run longer or sample more often",
+            settled.len(), warmup_secs, MIN_SAMPLES
+        ));
+    }
+
+    let mut series: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
+    for sample in &settled {
+        for (metric, value) in sample.metrics() {
+            series.entry(metric).or_default().push((sample.at_secs, value));
+        }
+    }
+
+    Ok(series.into_iter()
+        .filter(|(_, points)| points.len() >= MIN_SAMPLES)
+        .map(|(metric, points)| {
+            let (early, late) = points.split_at(points.len() / 2);
+            let peak = |points: &[(f64, f64)]| points.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+            let (early_peak, late_peak) = (peak(early), peak(late));
+            let growth_pct = (early_peak > 0.0).then(|| (late_peak - early_peak) / early_peak * 100.0);
+            let floor = if metric.ends_with("_kb") { MIN_KB_GROWTH } else { MIN_COUNT_GROWTH };
+            let unbounded = late_peak - early_peak > floor && growth_pct.is_none_or(|growth| growth > max_growth_pct);
+            MetricTrend { per_hour: slope(&points) * 3600.0, metric, early_peak, late_peak, growth_pct, unbounded }
+        })
+        .collect())
+}
+
+// Change per second of a least-squares line through the points
+fn slope(points: &[(f64, f64)]) -> f64 {
+    let n = points.len() as f64;
+    let mean_t = points.iter().map(|(t, _)| t).sum::<f64>() / n;
+    let mean_v = points.iter().map(|(_, v)| v).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|(t, v)| (t - mean_t) * (v - mean_v)).sum();
+    let variance: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+    if variance > 0.0 { covariance / variance } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One sample a minute with RSS and the tx mempool count
+    fn series(rss_kb: impl Fn(u64) -> u64, count: impl Fn(u64) -> u64, samples: u64) -> Vec<SoakSample> {
+        (0..samples)
+            .map(|i| SoakSample {
+                at_secs: (i * 60) as f64,
+                rss_kb: Some(rss_kb(i)),
+                db_kb: None,
+                mempools: BTreeMap::from([("tx_mempool".to_string(), count(i))]),
+            })
+            .collect()
+    }
+
+    fn trend<'a>(trends: &'a [MetricTrend], metric: &str) -> &'a MetricTrend {
+        trends.iter().find(|trend| trend.metric == metric).unwrap()
+    }
+
+    #[test]
+    fn test_flat_series_has_plateaued() {
+        // Test: Eight samples with constant memory and mempool count
+        // Expected: Zero growth and zero slope, nothing unbounded
+        println!("Expected: A flat series is bounded");
+
+        let trends = trends(&series(|_| 100_000, |_| 50, 8), 0.0, 10.0).unwrap();
+        assert_eq!(trends.len(), 2);
+        for trend in &trends {
+            assert_eq!(trend.growth_pct, Some(0.0));
+            assert_eq!(trend.per_hour, 0.0);
+            assert!(!trend.unbounded);
+        }
+    }
+
+    #[test]
+    fn test_linear_growth_is_unbounded() {
+        // Test: Memory grows 10 MB and the mempool 500 entries every minute
+        // Expected: Both are unbounded, with slopes of 600 MB and 30000 entries per hour
+        println!("Expected: A linearly growing series is unbounded with its per hour slope");
+
+        let trends = trends(&series(|i| 100_000 + 10_000 * i, |i| 500 * i, 8), 0.0, 10.0).unwrap();
+        let rss = trend(&trends, "rss_kb");
+        assert_eq!((rss.early_peak, rss.late_peak), (130_000.0, 170_000.0));
+        assert!((rss.per_hour - 600_000.0).abs() < 1e-6);
+        assert!(rss.unbounded);
+
+        let mempool = trend(&trends, "tx_mempool");
+        assert!((mempool.per_hour - 30_000.0).abs() < 1e-6);
+        assert!(mempool.unbounded);
+        assert_eq!(SoakReport { trends, ..Default::default() }.unbounded().len(), 2);
+    }
+
+    #[test]
+    fn test_growth_below_the_noise_floors_is_bounded() {
+        // Test: Memory grows 1 MB and the mempool 10 entries a minute from small starting values, which
+        // more than doubles both but stays under the 8 MB and 100 entry floors
+        // Expected: Growth is reported well over the tolerance, yet neither metric is unbounded
+        println!("Expected: Growth under the absolute floors is noise, whatever the percentage");
+
+        let trends = trends(&series(|i| 1_000 + 1_000 * i, |i| 2 + 10 * i, 8), 0.0, 10.0).unwrap();
+        for trend in &trends {
+            assert!(trend.growth_pct.unwrap() >= 100.0);
+            assert!(trend.per_hour > 0.0);
+            assert!(!trend.unbounded);
+        }
+    }
+
+    #[test]
+    fn test_too_few_samples_after_warmup_is_an_error() {
+        // Test: Five samples a minute apart with a two minute warmup, leaving three
+        // Expected: An error naming the three samples, as MIN_SAMPLES is four
+        println!("Expected: Trends refuse to judge fewer than MIN_SAMPLES settled samples");
+
+        let error = trends(&series(|_| 100_000, |_| 50, 5), 120.0, 10.0).unwrap_err();
+        assert!(error.contains("Only 3 sample(s) after the 120s warmup"), "{}", error);
+        assert!(trends(&series(|_| 100_000, |_| 50, 6), 120.0, 10.0).is_ok());
+    }
+
+    #[test]
+    fn test_slope_of_points_at_one_time_is_zero() {
+        // Test: Fit a line through points sharing a timestamp, and through a line of slope 2
+        // Expected: 0 where time has no spread, 2 otherwise
+        println!("Expected: slope has no division by zero");
+
+        assert_eq!(slope(&[(5.0, 1.0), (5.0, 9.0)]), 0.0);
+        assert!((slope(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]) - 2.0).abs() < 1e-12);
+    }
+}
@@ -1,4 +1,4 @@
-use pcl_backend::{Node, NodeRole, TransactionData, sign_data, hash_data};
+use pcl_backend::{Address, Node, NodeRole, TransactionData, sign_data};
 use log::{info, debug, warn};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -6,10 +6,15 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 use rand::Rng;
 use chrono::{DateTime, Utc};
+use crate::wallet_agent::UserManager;
+
+// What a simulated user is granted when it has nothing left to spend
+const USER_GENESIS_FUNDING: f64 = 100.0;
 
 pub struct TransactionGenerator {
     active_nodes: Arc<RwLock<HashMap<Uuid, Node>>>,
     transaction_counter: Arc<RwLock<u64>>,
+    users: Option<Arc<RwLock<UserManager>>>, // when set, users send to each other from their own UTXOs
 }
 
 impl TransactionGenerator {
@@ -17,10 +22,22 @@ impl TransactionGenerator {
         Self {
             active_nodes,
             transaction_counter: Arc::new(RwLock::new(0)),
+            users: None,
         }
     }
     
+    pub fn set_users(&mut self, users: UserManager) {
+        self.users = Some(Arc::new(RwLock::new(users)));
+    }
+    
+    pub fn users(&self) -> Option<Arc<RwLock<UserManager>>> {
+        self.users.clone()
+    }
+    
     pub async fn generate_random_transaction(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(users) = &self.users {
+            return self.generate_user_transaction(users).await;
+        }
         let active_nodes = self.active_nodes.read().await;
         let nodes: Vec<Node> = active_nodes.values().cloned().collect();
         
@@ -74,9 +91,9 @@ impl TransactionGenerator {
         let change = utxo_value - total_required;
         
         let tx_data = TransactionData {
-            to: vec![(receiver.ip.clone(), amount)],
+            to: vec![(Address::from_public_key(&receiver.public_key), amount)],
             from: vec![(format!("{}:utxo1", sender.ip), utxo_value)],
-            user: sender.ip.clone(),
+            user: Address::from_public_key(&sender.public_key),
             sig: None, // Will be set when signed
             stake,
             fee,
@@ -84,16 +101,79 @@ impl TransactionGenerator {
             timestamp: Utc::now(),
             leader: Some(leader.ip.clone()),
             nonce: rng.gen::<u64>(),
+            multisig: None,
+            signatures: Vec::new(),
+            fee_payer: None,
+            expires_at: None,
         };
         
         Ok(tx_data)
     }
     
+    // A signed transfer between two simulated users that spends the sender's tracked UTXOs, so
+    // nonces and UTXO chains carry on from one run to the next when the users are persisted
+    async fn generate_user_transaction(&self, users: &RwLock<UserManager>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let leader = self.active_nodes.read().await
+            .values()
+            .find(|node| node.role == NodeRole::Leader)
+            .map(|node| node.ip.clone())
+            .ok_or("No leader nodes available")?;
+        
+        let mut users = users.write().await;
+        let count = users.users().len();
+        if count < 2 {
+            return Err("Need at least 2 users for transaction generation".into());
+        }
+        let (sender, recipient, amount) = {
+            let mut rng = rand::thread_rng();
+            let sender = rng.gen_range(0..count);
+            let recipient = (sender + rng.gen_range(1..count)) % count;
+            (sender, recipient, rng.gen_range(0.1..10.0))
+        };
+        let fee = amount * 0.1;
+        let stake = amount * 0.2;
+        let total_required = amount + fee + stake;
+        
+        let inputs = match users.select_inputs(sender, total_required) {
+            Some(inputs) => inputs,
+            None => {
+                let user = &users.users()[sender];
+                let utxo_id = format!("genesis:{}:{}", user.address, user.nonce);
+                users.credit(sender, utxo_id, USER_GENESIS_FUNDING);
+                users.select_inputs(sender, total_required).ok_or("Genesis funding does not cover the transfer")?
+            }
+        };
+        let change = inputs.iter().map(|(_, value)| value).sum::<f64>() - total_required;
+        
+        let user = &users.users()[sender];
+        let mut tx_data = TransactionData {
+            to: vec![(Address::parse(&users.users()[recipient].address)?, amount)],
+            from: inputs.clone(),
+            user: Address::parse(&user.address)?,
+            sig: None,
+            stake,
+            fee,
+            change: Some(change),
+            timestamp: Utc::now(),
+            leader: Some(leader),
+            nonce: user.nonce,
+            multisig: None,
+            signatures: Vec::new(),
+            fee_payer: None,
+            expires_at: None,
+        };
+        tx_data.sign_as_user(&user.keypair)?;
+        let tx_id = self.create_transaction_id(&tx_data).await?;
+        users.apply_transfer(sender, recipient, &inputs, amount, change, &tx_id);
+        
+        *self.transaction_counter.write().await += 1;
+        debug!("Generated user transaction {}: {} -> {}", tx_id, users.users()[sender].name, users.users()[recipient].name);
+        Ok(tx_id)
+    }
+    
     async fn create_transaction_id(&self, tx_data: &TransactionData) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // Hash the transaction data to create ID
-        let serialized = serde_json::to_string(tx_data)?;
-        let hash = hash_data(serialized.as_bytes());
-        Ok(hex::encode(hash))
+        // Same content-derived id the backend computes, pinned by the conformance vectors
+        Ok(tx_data.raw_tx_id()?)
     }
     
     pub async fn generate_burst_transactions(&self, count: u32) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
@@ -221,9 +301,9 @@ impl TransactionGenerator {
         
         // Create transaction data exactly as in README
         let tx_data = TransactionData {
-            to: vec![(bob.ip.clone(), 1.0)],
+            to: vec![(Address::from_public_key(&bob.public_key), 1.0)],
             from: vec![(format!("{}:utxo1", alice.ip), 2.0)],
-            user: alice.ip.clone(),
+            user: Address::from_public_key(&alice.public_key),
             sig: None, // Will be signed later
             stake: 0.2,
             fee: 0.1,
@@ -231,6 +311,10 @@ impl TransactionGenerator {
             timestamp: Utc::now(),
             leader: Some(leader.ip.clone()),
             nonce: rand::thread_rng().gen::<u64>(),
+            multisig: None,
+            signatures: Vec::new(),
+            fee_payer: None,
+            expires_at: None,
         };
         
         let tx_id = self.create_transaction_id(&tx_data).await?;
@@ -261,9 +345,9 @@ impl TransactionGenerator {
         let receiver = &nodes[0]; // Same sender and receiver (invalid)
         
         let tx_data = TransactionData {
-            to: vec![(receiver.ip.clone(), 1000.0)], // Unrealistic amount
+            to: vec![(Address::from_public_key(&receiver.public_key), 1000.0)], // Unrealistic amount
             from: vec![(format!("{}:utxo1", sender.ip), 0.1)], // Insufficient funds
-            user: sender.ip.clone(),
+            user: Address::from_public_key(&sender.public_key),
             sig: None,
             stake: 0.0, // No stake
             fee: 0.0,   // No fee
@@ -271,6 +355,10 @@ impl TransactionGenerator {
             timestamp: Utc::now(),
             leader: None, // No leader
             nonce: 0,
+            multisig: None,
+            signatures: Vec::new(),
+            fee_payer: None,
+            expires_at: None,
         };
         
         let tx_id = self.create_transaction_id(&tx_data).await?;
@@ -0,0 +1,309 @@
+use pcl_backend::{sign_submission, Address, NodeKeypair, PclClient, UserValidationTaskCompletion};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+
+// Tolerated clock difference between a wallet and the leader that stamped the transaction
+const MAX_FUTURE_SKEW_MS: u64 = 5_000;
+
+pub struct SimUser {
+    pub name: String,
+    pub keypair: NodeKeypair,
+    pub address: String,
+    pub nonce: u64,                // transactions this user has sent, across runs
+    pub utxos: Vec<(String, f64)>, // unspent outputs the user can spend: utxo id -> value
+}
+
+impl SimUser {
+    pub fn new(name: String) -> Self {
+        let keypair = NodeKeypair::new();
+        let address = keypair.address();
+        Self { name, keypair, address, nonce: 0, utxos: Vec::new() }
+    }
+
+    pub fn balance(&self) -> f64 {
+        self.utxos.iter().map(|(_, value)| value).sum()
+    }
+}
+
+// A user as written to the --users-file
+#[derive(Serialize, Deserialize)]
+struct StoredUser {
+    name: String,
+    secret_key: String, // hex
+    nonce: u64,
+    utxos: Vec<(String, f64)>,
+}
+
+// Simulated users, each with its own signing key
+pub struct UserManager {
+    users: Vec<SimUser>,
+}
+
+impl UserManager {
+    pub fn new(count: usize) -> Self {
+        let users = (0..count).map(|i| SimUser::new(format!("user_{}", i))).collect();
+        Self { users }
+    }
+
+    // Users from a previous run's file, topped up with new ones to reach count. Without a file, or
+    // before its first run, every user is new.
+    pub fn load_or_create(path: Option<&Path>, count: usize) -> Result<Self, String> {
+        let Some(path) = path.filter(|path| path.exists()) else {
+            return Ok(Self::new(count));
+        };
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read users file {}: {}", path.display(), e))?;
+        let stored: Vec<StoredUser> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid users file {}: {}", path.display(), e))?;
+        let mut users = Vec::new();
+        for user in stored {
+            let secret_key = hex::decode(&user.secret_key)
+                .map_err(|e| format!("Invalid secret key for {}: {}", user.name, e))?;
+            let keypair = NodeKeypair::from_bytes(&secret_key).map_err(|e| format!("Invalid secret key for {}: {}", user.name, e))?;
+            let address = keypair.address();
+            users.push(SimUser { name: user.name, keypair, address, nonce: user.nonce, utxos: user.utxos });
+        }
+        info!("👥 Loaded {} users from {}", users.len(), path.display());
+        while users.len() < count {
+            users.push(SimUser::new(format!("user_{}", users.len())));
+        }
+        Ok(Self { users })
+    }
+
+    // Writes keys, nonces and UTXOs so the next run continues with the same users
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let stored: Vec<StoredUser> = self.users.iter()
+            .map(|user| StoredUser {
+                name: user.name.clone(),
+                secret_key: hex::encode(user.keypair.signing_key.to_bytes()),
+                nonce: user.nonce,
+                utxos: user.utxos.clone(),
+            })
+            .collect();
+        let contents = serde_json::to_string_pretty(&stored).map_err(|e| e.to_string())?;
+        // Written beside the target and renamed, so an interrupted run leaves the previous file intact
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, contents)
+            .and_then(|_| std::fs::rename(&temp, path))
+            .map_err(|e| format!("Could not write users file {}: {}", path.display(), e))?;
+        info!("💾 Saved {} users to {}", self.users.len(), path.display());
+        Ok(())
+    }
+
+    pub fn users(&self) -> &[SimUser] {
+        &self.users
+    }
+
+    // How far each user is below amount, so users carried over from an earlier run that still hold
+    // funds are not funded again
+    pub fn shortfalls(&self, amount: f64) -> Vec<(usize, f64)> {
+        self.users.iter().enumerate()
+            .map(|(index, user)| (index, amount - user.balance()))
+            .filter(|(_, missing)| *missing > 0.0)
+            .collect()
+    }
+
+    pub fn credit(&mut self, index: usize, utxo_id: String, value: f64) {
+        self.users[index].utxos.push((utxo_id, value));
+    }
+
+    // Oldest outputs of the sender that together cover total, or None if the balance is too low
+    pub fn select_inputs(&self, index: usize, total: f64) -> Option<Vec<(String, f64)>> {
+        let mut inputs = Vec::new();
+        let mut covered = 0.0;
+        for utxo in &self.users[index].utxos {
+            if covered >= total {
+                break;
+            }
+            covered += utxo.1;
+            inputs.push(utxo.clone());
+        }
+        (covered >= total).then_some(inputs)
+    }
+
+    // Spends the inputs and creates the outputs of tx_id: output 0 pays the recipient and output 1
+    // returns the change to the sender
+    pub fn apply_transfer(&mut self, sender: usize, recipient: usize, inputs: &[(String, f64)], amount: f64, change: f64, tx_id: &str) {
+        let spender = &mut self.users[sender];
+        spender.utxos.retain(|utxo| !inputs.iter().any(|input| input.0 == utxo.0));
+        spender.nonce += 1;
+        if change > 0.0 {
+            spender.utxos.push((format!("{}:1", tx_id), change));
+        }
+        self.users[recipient].utxos.push((format!("{}:0", tx_id), amount));
+    }
+
+    // Records a transfer a node accepted: amount to the recipient, fee and stake spent, the rest
+    // back to the sender as change
+    pub fn record_transfer(&mut self, sender: usize, recipient: usize, amount: f64, fee_and_stake: f64, tx_id: &str) {
+        let total = amount + fee_and_stake;
+        match self.select_inputs(sender, total) {
+            Some(inputs) => {
+                let change = inputs.iter().map(|(_, value)| value).sum::<f64>() - total;
+                self.apply_transfer(sender, recipient, &inputs, amount, change, tx_id);
+            }
+            None => warn!("⚠️ {} sent {} with too few tracked UTXOs; balances may be stale", self.users[sender].name, tx_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WalletAgentStats {
+    pub transactions_submitted: u64,
+    pub tasks_seen: u64,
+    pub tasks_completed: u64,
+    pub tasks_disputed: u64,
+    pub completions_rejected: u64,
+    pub transactions_finalized: u64,
+}
+
+// Drives the simulated users' wallets against a node started with --external-validators:
+// asks leaders for tasks, checks the transactions, and submits signed completions
+pub struct WalletAgent {
+    client: PclClient,
+    users: UserManager,
+    poll_interval: Duration,
+    stats: WalletAgentStats,
+}
+
+impl WalletAgent {
+    pub fn new(client: PclClient, users: UserManager, poll_interval: Duration) -> Self {
+        Self {
+            client,
+            users,
+            poll_interval,
+            stats: WalletAgentStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> &WalletAgentStats {
+        &self.stats
+    }
+
+    pub fn users(&self) -> &UserManager {
+        &self.users
+    }
+
+    pub async fn fund_users(&mut self, amount: f64) {
+        for (index, missing) in self.users.shortfalls(amount) {
+            let user = &self.users.users()[index];
+            let body = serde_json::json!({ "address": user.address, "amount": missing });
+            match self.client.post("/faucet", &body).await {
+                Ok(response) => {
+                    info!("🚰 Funded {} ({}) with {} XMBL", user.name, user.address, missing);
+                    let utxo_id = format!("{}:0", response["transaction_id"].as_str().unwrap_or("faucet"));
+                    self.users.credit(index, utxo_id, missing);
+                }
+                Err(e) => warn!("❌ Faucet request for {} failed: {}", user.name, e),
+            }
+        }
+    }
+
+    // Every user pays the next one; each transfer leaves tasks for its sender to complete
+    pub async fn submit_transfers(&mut self, amount: f64) {
+        let count = self.users.users().len();
+        for i in 0..count {
+            let (user, recipient) = (&self.users.users()[i], &self.users.users()[(i + 1) % count]);
+            let body = serde_json::json!({
+                "to": recipient.address,
+                "amount": amount,
+                "user": user.address,
+                "stake": 0.2,
+                "fee": 0.1,
+            });
+            let body = match sign_submission(&body, &user.keypair) {
+                Ok(signed) => signed,
+                Err(e) => {
+                    warn!("❌ Could not sign the transfer from {}: {}", user.name, e);
+                    continue;
+                }
+            };
+            match self.client.submit_transaction(&body, None).await {
+                Ok(response) => {
+                    self.stats.transactions_submitted += 1;
+                    info!("📤 {} sent {} XMBL to {}: {}", user.name, amount, recipient.name, response["transaction_id"]);
+                    if let Some(tx_id) = response["transaction_id"].as_str() {
+                        self.users.record_transfer(i, (i + 1) % count, amount, 0.3, tx_id);
+                    }
+                }
+                Err(e) => warn!("❌ Transfer from {} failed: {}", user.name, e),
+            }
+        }
+    }
+
+    pub async fn poll_once(&mut self) {
+        let stats = &mut self.stats;
+        for user in self.users.users() {
+            // Cross-validation work on other users' transactions, on top of the user's own tasks
+            if let Err(e) = self.client.request_validation_tasks(&user.address).await {
+                warn!("❌ Task request for {} failed: {}", user.name, e);
+            }
+
+            let tasks = match self.client.validation_tasks(&user.address).await {
+                Ok(response) => response["tasks"].as_array().cloned().unwrap_or_default(),
+                Err(e) => {
+                    warn!("❌ Task poll for {} failed: {}", user.name, e);
+                    continue;
+                }
+            };
+
+            for task in tasks {
+                stats.tasks_seen += 1;
+                let (Some(task_id), Some(raw_tx_id)) = (task["task_id"].as_str(), task["raw_tx_id"].as_str()) else {
+                    continue;
+                };
+                let now = unix_millis();
+                let result = check_task(&task, now);
+                let completion = UserValidationTaskCompletion::sign(task_id, raw_tx_id, result, now, &user.keypair);
+
+                match self.client.complete_validation_task(&completion).await {
+                    Ok(response) => {
+                        if result {
+                            stats.tasks_completed += 1;
+                        } else {
+                            stats.tasks_disputed += 1;
+                        }
+                        if response["finalized"].as_bool() == Some(true) {
+                            stats.transactions_finalized += 1;
+                        }
+                        info!("✅ {} completed task {} for {}: {}", user.name, task_id, raw_tx_id, response["status"]);
+                    }
+                    Err(e) => {
+                        stats.completions_rejected += 1;
+                        warn!("❌ Completion of task {} by {} rejected: {}", task_id, user.name, e);
+                    }
+                }
+            }
+        }
+    }
+
+    pub async fn run(&mut self, duration: Duration) {
+        let started = Instant::now();
+        while started.elapsed() < duration {
+            self.poll_once().await;
+            sleep(self.poll_interval).await;
+        }
+    }
+}
+
+// The checks a wallet can make on its own: a positive amount, well formed addresses and a
+// timestamp that is not in the future. Tasks whose transaction the node no longer holds pass.
+pub fn check_task(task: &serde_json::Value, now_ms: u64) -> bool {
+    let tx = &task["tx"];
+    if tx.is_null() {
+        return true;
+    }
+    let data = &tx["tx_data"];
+    let amount_ok = data["amount"].as_f64().is_some_and(|amount| amount > 0.0);
+    let addresses_ok = ["to", "user"].iter()
+        .all(|field| data[*field].as_str().is_some_and(|address| Address::parse(address).is_ok()));
+    let timestamp_ok = tx["tx_timestamp"].as_u64().is_some_and(|ts| ts <= now_ms + MAX_FUTURE_SKEW_MS);
+    amount_ok && addresses_ok && timestamp_ok
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}